@@ -12,7 +12,7 @@ const ONEIROBOT_CONTRACTS = {
     explorer: "https://elated-tan-skat.explorer.mainnet.skalenodes.com"
   },
   solana: {
-    programId: "Oneir8BotPr0gram1DSynt1cat3M4st3r5",
+    programId: "35XexXW4EgvZfN71nQ8mS53r3AerKc9Djs6F37TrDMq6",
     network: "Solana Mainnet",
     explorer: "https://solscan.io"
   }
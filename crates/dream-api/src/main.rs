@@ -0,0 +1,30 @@
+//! REST API over the database the `indexer` binary writes. Serves paginated
+//! dream listings, per-dreamer stats, a leaderboard and NFT lookups, plus the
+//! OpenAPI document at `/openapi.json`.
+
+mod error;
+mod routes;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use dream_mind_indexer::sink::Sink;
+
+/// Read configuration from the environment:
+/// - `INDEXER_DATABASE_URL`: `sqlite://...` or `postgres://...` (same as the indexer)
+/// - `DREAM_API_ADDR`: listen address, default `0.0.0.0:8080`
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::init();
+
+    let database_url = std::env::var("INDEXER_DATABASE_URL")
+        .unwrap_or_else(|_| "sqlite://indexer.db".to_string());
+    let addr = std::env::var("DREAM_API_ADDR").unwrap_or_else(|_| "0.0.0.0:8080".to_string());
+
+    let sink = Arc::new(Sink::connect(&database_url).await?);
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+
+    log::info!("dream-api listening on {}", addr);
+    axum::serve(listener, routes::router(sink)).await?;
+    Ok(())
+}
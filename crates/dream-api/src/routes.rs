@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use dream_mind_indexer::{
+    query::{DreamRow, DreamerStats, MintRow, StakeRow},
+    sink::Sink,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::error::{ApiError, ErrorBody};
+
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 200;
+
+type AppState = Arc<Sink>;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Dream-Mind-Lucid API"),
+    paths(list_dreams, get_dreamer, leaderboard, get_nft),
+    components(schemas(
+        DreamPage, DreamerProfile, Leaderboard, DreamRow, DreamerStats, MintRow, StakeRow, ErrorBody
+    ))
+)]
+pub struct ApiDoc;
+
+pub fn router(sink: Arc<Sink>) -> Router {
+    Router::new()
+        .route("/dreams", get(list_dreams))
+        .route("/dreamers/:pubkey", get(get_dreamer))
+        .route("/leaderboard", get(leaderboard))
+        .route("/nfts/:mint", get(get_nft))
+        .route("/openapi.json", get(|| async { Json(ApiDoc::openapi()) }))
+        .with_state(sink)
+}
+
+/// Cursor pagination: pass the previous page's `next_before` to continue.
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageParams {
+    /// Only return dreams with an ID lower than this
+    before: Option<u64>,
+    /// Page size, at most 200 (default 50)
+    limit: Option<u32>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LimitParams {
+    /// Number of entries, at most 200 (default 50)
+    limit: Option<u32>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DreamPage {
+    dreams: Vec<DreamRow>,
+    /// Cursor for the next page; absent on the last page
+    next_before: Option<u64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DreamerProfile {
+    stats: DreamerStats,
+    stake: Option<StakeRow>,
+    nfts: Vec<MintRow>,
+    recent_dreams: Vec<DreamRow>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Leaderboard {
+    dreamers: Vec<DreamerStats>,
+}
+
+fn page_limit(limit: Option<u32>) -> Result<u32, ApiError> {
+    match limit.unwrap_or(DEFAULT_LIMIT) {
+        0 => Err(ApiError::BadRequest("limit must be positive".to_string())),
+        limit => Ok(limit.min(MAX_LIMIT)),
+    }
+}
+
+/// Validate a base58 pubkey path segment without pulling in the Solana SDK.
+fn pubkey_param(value: &str) -> Result<&str, ApiError> {
+    let valid = (32..=44).contains(&value.len())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l'));
+    if valid {
+        Ok(value)
+    } else {
+        Err(ApiError::BadRequest(format!("invalid pubkey: {value}")))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/dreams",
+    params(PageParams),
+    responses(
+        (status = 200, description = "Dreams, newest first", body = DreamPage),
+        (status = 400, body = ErrorBody),
+    )
+)]
+async fn list_dreams(
+    State(sink): State<AppState>,
+    Query(params): Query<PageParams>,
+) -> Result<Json<DreamPage>, ApiError> {
+    let limit = page_limit(params.limit)?;
+    let dreams = sink.dreams_before(params.before, limit).await?;
+    let next_before = match dreams.last() {
+        Some(last) if dreams.len() == limit as usize => Some(last.id),
+        _ => None,
+    };
+    Ok(Json(DreamPage { dreams, next_before }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/dreamers/{pubkey}",
+    params(("pubkey" = String, Path, description = "Dreamer wallet address")),
+    responses(
+        (status = 200, description = "Dreamer stats, stake, NFTs and recent dreams", body = DreamerProfile),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn get_dreamer(
+    State(sink): State<AppState>,
+    Path(pubkey): Path<String>,
+) -> Result<Json<DreamerProfile>, ApiError> {
+    let dreamer = pubkey_param(&pubkey)?;
+    let stats = sink
+        .dreamer_stats(dreamer)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("no dreams recorded by {dreamer}")))?;
+
+    Ok(Json(DreamerProfile {
+        stats,
+        stake: sink.stake(dreamer).await?,
+        nfts: sink.mints_by_owner(dreamer).await?,
+        recent_dreams: sink.dreams_by_dreamer(dreamer, None, DEFAULT_LIMIT).await?,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/leaderboard",
+    params(LimitParams),
+    responses(
+        (status = 200, description = "Dreamers ranked by dreams recorded", body = Leaderboard),
+        (status = 400, body = ErrorBody),
+    )
+)]
+async fn leaderboard(
+    State(sink): State<AppState>,
+    Query(params): Query<LimitParams>,
+) -> Result<Json<Leaderboard>, ApiError> {
+    let limit = page_limit(params.limit)?;
+    Ok(Json(Leaderboard {
+        dreamers: sink.leaderboard(limit).await?,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/nfts/{mint}",
+    params(("mint" = String, Path, description = "OneirobotNFT mint address")),
+    responses(
+        (status = 200, description = "Indexed mint", body = MintRow),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+    )
+)]
+async fn get_nft(
+    State(sink): State<AppState>,
+    Path(mint): Path<String>,
+) -> Result<Json<MintRow>, ApiError> {
+    let mint = pubkey_param(&mint)?;
+    sink.mint(mint)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("mint not indexed: {mint}")))
+}
@@ -0,0 +1,278 @@
+//! `dream`: send Dream-Mind-Lucid treasury and admin instructions.
+//!
+//! With `--simulate`, the transaction is first run through
+//! `simulateTransaction` and the program error or the expected SOL and token
+//! balance changes are printed; nothing is sent until the operator confirms.
+//!
+//! With `--nonce` and `--authority`, an admin command is built against a
+//! durable nonce and printed instead of sent. The authority signs it with
+//! `dream sign` on an air-gapped machine, and `dream submit` sends the result.
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{anyhow, bail, Result};
+use clap::{ArgAction, Parser, Subcommand};
+use dream_mind_client::{
+    dispatch::{self, DispatchConfig, DurableNonce},
+    instructions, offline,
+    simulate::{simulate_transaction, SimulationReport},
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+#[derive(Parser)]
+#[command(name = "dream", version, about)]
+struct Cli {
+    #[arg(long, env = "SOLANA_RPC_URL", default_value = "https://api.mainnet-beta.solana.com")]
+    url: String,
+    /// Fee payer; also the authority unless --authority is given
+    #[arg(long, short, env = "DREAM_KEYPAIR", default_value = "~/.config/solana/id.json")]
+    keypair: String,
+    /// Simulate first, print the expected balance changes and ask before sending
+    #[arg(long)]
+    simulate: bool,
+    /// Don't ask for confirmation after a successful simulation or before signing
+    #[arg(long, short)]
+    yes: bool,
+    /// Build against this durable nonce account instead of a recent blockhash
+    #[arg(long)]
+    nonce: Option<Pubkey>,
+    /// Authority that signs offline (and advances the nonce). The transaction
+    /// is printed for `dream sign` instead of being sent.
+    #[arg(long, requires = "nonce")]
+    authority: Option<Pubkey>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(flatten)]
+    Admin(AdminCommand),
+    /// Add this keypair's signature to a transaction printed by `--authority`.
+    /// Needs no network access.
+    Sign { transaction: String },
+    /// Send a transaction once every signer has signed it
+    Submit { transaction: String },
+}
+
+#[derive(Subcommand)]
+enum AdminCommand {
+    /// Set the SOL fees charged by record_dream and mint_oneirobot (lamports)
+    SetProtocolFees {
+        record_dream_fee: u64,
+        mint_oneirobot_fee: u64,
+    },
+    /// Reconcile the treasury, then send the fee vault's balance above rent
+    /// to RECIPIENT. Fails while the treasury is unhealthy.
+    SweepFees { recipient: Pubkey },
+    /// Turn MEV protection on or off
+    SetMevProtection {
+        #[arg(action = ArgAction::Set)]
+        enabled: bool,
+    },
+    /// Set the crank bounty (lamports) and per-kind cooldown (seconds)
+    SetCrankBounty {
+        bounty_lamports: u64,
+        cooldown_secs: i64,
+    },
+    /// Open a new leaderboard season
+    StartSeason,
+}
+
+impl AdminCommand {
+    fn instructions(&self, authority: &Pubkey) -> Vec<Instruction> {
+        vec![match *self {
+            AdminCommand::SetProtocolFees {
+                record_dream_fee,
+                mint_oneirobot_fee,
+            } => instructions::set_protocol_fees(authority, record_dream_fee, mint_oneirobot_fee),
+            AdminCommand::SweepFees { recipient } => {
+                return vec![
+                    instructions::reconcile_treasury(authority),
+                    instructions::sweep_fees(authority, &recipient),
+                ];
+            }
+            AdminCommand::SetMevProtection { enabled } => {
+                instructions::set_mev_protection(authority, enabled)
+            }
+            AdminCommand::SetCrankBounty {
+                bounty_lamports,
+                cooldown_secs,
+            } => instructions::set_crank_bounty(authority, bounty_lamports, cooldown_secs),
+            AdminCommand::StartSeason => instructions::start_season(authority),
+        }]
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair_path = expand_home(&cli.keypair);
+    let keypair = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow!("could not read keypair {keypair_path}: {err}"))?;
+
+    match &cli.command {
+        Command::Sign { transaction } => sign(&cli, &keypair, transaction),
+        Command::Submit { transaction } => {
+            let client = RpcClient::new_with_commitment(&cli.url, CommitmentConfig::confirmed());
+            submit(&cli, &client, transaction)
+        }
+        Command::Admin(command) => {
+            let client = RpcClient::new_with_commitment(&cli.url, CommitmentConfig::confirmed());
+            run_admin(&cli, &client, &keypair, command)
+        }
+    }
+}
+
+fn run_admin(cli: &Cli, client: &RpcClient, payer: &Keypair, command: &AdminCommand) -> Result<()> {
+    let authority = cli.authority.unwrap_or_else(|| payer.pubkey());
+    let instructions = command.instructions(&authority);
+
+    let Some(nonce_account) = cli.nonce else {
+        let transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        if cli.simulate && !preview(cli, client, &transaction, Some("Send this transaction?"))? {
+            return Ok(());
+        }
+        let config = DispatchConfig::default();
+        let signature = dispatch::send_with_retry(client, &instructions, payer, &[], &config)?;
+        println!("{signature}");
+        return Ok(());
+    };
+
+    let nonce = DurableNonce {
+        nonce_account,
+        authority,
+    };
+    let blockhash = dispatch::fetch_nonce_blockhash(client, &nonce_account)?;
+    let mut transaction =
+        offline::build_nonce_transaction(&instructions, &payer.pubkey(), &nonce, blockhash);
+    let prompt = cli.authority.is_none().then_some("Send this transaction?");
+    if cli.simulate && !preview(cli, client, &transaction, prompt)? {
+        return Ok(());
+    }
+    transaction.try_partial_sign(&[payer], blockhash)?;
+
+    if cli.authority.is_some() {
+        println!("{}", offline::encode_transaction(&transaction)?);
+        eprintln!("Sign with `dream sign` on the authority's machine, then `dream submit` it.");
+        return Ok(());
+    }
+    println!("{}", dispatch::send_presigned(client, &transaction)?);
+    Ok(())
+}
+
+fn sign(cli: &Cli, keypair: &Keypair, encoded: &str) -> Result<()> {
+    let mut transaction = offline::decode_transaction(encoded)?;
+    if !offline::missing_signers(&transaction).contains(&keypair.pubkey()) {
+        bail!("{} is not a pending signer of this transaction", keypair.pubkey());
+    }
+    print_transaction(&transaction);
+    if !cli.yes && !confirm("Sign this transaction?")? {
+        println!("Not signed.");
+        return Ok(());
+    }
+    let blockhash = transaction.message.recent_blockhash;
+    transaction.try_partial_sign(&[keypair], blockhash)?;
+    println!("{}", offline::encode_transaction(&transaction)?);
+    Ok(())
+}
+
+fn submit(cli: &Cli, client: &RpcClient, encoded: &str) -> Result<()> {
+    let transaction = offline::decode_transaction(encoded)?;
+    let missing = offline::missing_signers(&transaction);
+    if !missing.is_empty() {
+        bail!("transaction is still missing signatures from {missing:?}");
+    }
+    if cli.simulate && !preview(cli, client, &transaction, Some("Send this transaction?"))? {
+        return Ok(());
+    }
+    println!("{}", dispatch::send_presigned(client, &transaction)?);
+    Ok(())
+}
+
+/// Simulate and print the report. Returns whether to go on, asking `prompt`
+/// first unless `--yes` was given.
+fn preview(
+    cli: &Cli,
+    client: &RpcClient,
+    transaction: &Transaction,
+    prompt: Option<&str>,
+) -> Result<bool> {
+    let report = simulate_transaction(client, transaction)?;
+    print_report(&report);
+    if !report.succeeded() {
+        bail!("simulation failed; nothing was sent");
+    }
+    match prompt {
+        Some(prompt) if !cli.yes && !confirm(prompt)? => {
+            println!("Not sent.");
+            Ok(false)
+        }
+        _ => Ok(true),
+    }
+}
+
+/// What the offline signer is about to approve.
+fn print_transaction(transaction: &Transaction) {
+    let message = &transaction.message;
+    println!("Fee payer: {}", message.account_keys[0]);
+    println!("Nonce:     {}", message.recent_blockhash);
+    for (index, instruction) in message.instructions.iter().enumerate() {
+        let program = message.account_keys[usize::from(instruction.program_id_index)];
+        println!("Instruction {index}: program {program}");
+        for &account in &instruction.accounts {
+            let account = usize::from(account);
+            let access = if message.is_writable(account) { "writable" } else { "readonly" };
+            let signer = if message.is_signer(account) { ", signer" } else { "" };
+            println!("  {} ({access}{signer})", message.account_keys[account]);
+        }
+    }
+}
+
+fn print_report(report: &SimulationReport) {
+    match &report.error {
+        Some(error) => println!("Simulation failed: {error}"),
+        None => println!("Simulation succeeded"),
+    }
+    if let Some(units) = report.units_consumed {
+        println!("  compute units: {units}");
+    }
+    for delta in &report.sol_deltas {
+        let sol = delta.delta as f64 / LAMPORTS_PER_SOL as f64;
+        println!("  SOL    {:<44} {sol:+.9}", delta.account);
+    }
+    for delta in &report.token_deltas {
+        println!(
+            "  token  {:<44} {:+} (mint {}, owner {})",
+            delta.account, delta.delta, delta.mint, delta.owner
+        );
+    }
+    if report.error.is_some() {
+        for line in &report.logs {
+            println!("  | {line}");
+        }
+    }
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{prompt} [y/N] ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{home}/{rest}"),
+        _ => path.to_string(),
+    }
+}
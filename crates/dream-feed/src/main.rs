@@ -0,0 +1,42 @@
+//! gRPC streaming feed of indexed dreams and OneirobotNFT mints for partners
+//! that want push-based integration. Reads the database the `indexer` binary
+//! writes, so both must point at the same `INDEXER_DATABASE_URL`.
+
+mod service;
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use dream_mind_indexer::sink::Sink;
+use tonic::transport::Server;
+
+use crate::service::{proto::dream_feed_server::DreamFeedServer, Feed};
+
+/// Read configuration from the environment:
+/// - `INDEXER_DATABASE_URL`: `sqlite://...` or `postgres://...` (same as the indexer)
+/// - `DREAM_FEED_ADDR`: listen address, default `0.0.0.0:50051`
+/// - `DREAM_FEED_POLL_MS`: how often followers poll for new rows, default 1000
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::init();
+
+    let database_url = std::env::var("INDEXER_DATABASE_URL")
+        .unwrap_or_else(|_| "sqlite://indexer.db".to_string());
+    let addr = std::env::var("DREAM_FEED_ADDR")
+        .unwrap_or_else(|_| "0.0.0.0:50051".to_string())
+        .parse()
+        .context("invalid DREAM_FEED_ADDR")?;
+    let poll_interval = match std::env::var("DREAM_FEED_POLL_MS") {
+        Ok(ms) => Duration::from_millis(ms.parse().context("invalid DREAM_FEED_POLL_MS")?),
+        Err(_) => Duration::from_secs(1),
+    };
+
+    let sink = Arc::new(Sink::connect(&database_url).await?);
+
+    log::info!("dream-feed listening on {}", addr);
+    Server::builder()
+        .add_service(DreamFeedServer::new(Feed::new(sink, poll_interval)))
+        .serve(addr)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use dream_mind_indexer::{
+    query::{DreamRow, MintRow},
+    sink::Sink,
+};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+pub mod proto {
+    tonic::include_proto!("dream_feed.v1");
+}
+
+use proto::{dream_feed_server::DreamFeed, Dream, Mint, StreamRequest};
+
+/// Rows fetched per poll; a backlog larger than this drains over several polls.
+const PAGE_SIZE: u32 = 500;
+
+/// Messages buffered per subscriber before the follower waits on the client.
+const CHANNEL_CAPACITY: usize = 256;
+
+type FeedStream<T> = ReceiverStream<Result<T, Status>>;
+type Page<T> = Pin<Box<dyn Future<Output = anyhow::Result<Vec<(u64, T)>>> + Send>>;
+
+pub struct Feed {
+    sink: Arc<Sink>,
+    poll_interval: Duration,
+}
+
+impl Feed {
+    pub fn new(sink: Arc<Sink>, poll_interval: Duration) -> Self {
+        Self { sink, poll_interval }
+    }
+
+    /// Spawn a follower that pages through rows after `cursor` and keeps polling
+    /// for new ones until the client disconnects.
+    fn follow<T, F>(&self, mut cursor: Option<u64>, fetch: F) -> FeedStream<T>
+    where
+        T: Send + 'static,
+        F: Fn(Arc<Sink>, Option<u64>) -> Page<T> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let sink = self.sink.clone();
+        let poll_interval = self.poll_interval;
+
+        tokio::spawn(async move {
+            loop {
+                let page = match fetch(sink.clone(), cursor).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        log::error!("Feed query failed: {}", e);
+                        let _ = tx.send(Err(Status::internal("indexer query failed"))).await;
+                        return;
+                    }
+                };
+
+                if page.is_empty() {
+                    if tx.is_closed() {
+                        return;
+                    }
+                    tokio::time::sleep(poll_interval).await;
+                    continue;
+                }
+
+                for (position, message) in page {
+                    cursor = Some(position);
+                    if tx.send(Ok(message)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+}
+
+#[tonic::async_trait]
+impl DreamFeed for Feed {
+    type NewDreamsStream = FeedStream<Dream>;
+    type MintsStream = FeedStream<Mint>;
+
+    async fn new_dreams(
+        &self,
+        request: Request<StreamRequest>,
+    ) -> Result<Response<Self::NewDreamsStream>, Status> {
+        let stream = self.follow(request.into_inner().after, |sink, after| {
+            Box::pin(async move {
+                let rows = sink.dreams_after(after, PAGE_SIZE).await?;
+                Ok(rows.into_iter().map(|row| (row.id, row.into())).collect())
+            })
+        });
+        Ok(Response::new(stream))
+    }
+
+    async fn mints(
+        &self,
+        request: Request<StreamRequest>,
+    ) -> Result<Response<Self::MintsStream>, Status> {
+        let stream = self.follow(request.into_inner().after, |sink, after| {
+            Box::pin(async move {
+                let rows = sink.mints_after(after, PAGE_SIZE).await?;
+                Ok(rows.into_iter().map(|row| (row.token_id, row.into())).collect())
+            })
+        });
+        Ok(Response::new(stream))
+    }
+}
+
+impl From<DreamRow> for Dream {
+    fn from(row: DreamRow) -> Self {
+        Self {
+            id: row.id,
+            dreamer: row.dreamer,
+            content_hash: row.content_hash,
+            token_reward: row.token_reward,
+            mev_protected: row.mev_protected,
+            timestamp: row.recorded_at,
+            signature: row.signature,
+            slot: row.slot,
+        }
+    }
+}
+
+impl From<MintRow> for Mint {
+    fn from(row: MintRow) -> Self {
+        Self {
+            mint: row.mint,
+            owner: row.owner,
+            token_id: row.token_id,
+            quantum_core: row.quantum_core,
+            dream_level: row.dream_level.into(),
+            lucid_power: row.lucid_power.into(),
+            mind_strength: row.mind_strength.into(),
+            metadata_uri: row.metadata_uri,
+            timestamp: row.minted_at,
+            signature: row.signature,
+            slot: row.slot,
+        }
+    }
+}
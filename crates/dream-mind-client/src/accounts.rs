@@ -0,0 +1,389 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    AccountDeserialize,
+};
+use dream_mind_lucid::{
+    ArchivedDream, BuildInfo, Charity, CharitySplit, Companion, CompanionStamp, CompanionXp,
+    CompressedStakeTree, CrankPool, DreamAnnotation, DreamArchiveTree, DreamCapsule,
+    DreamCertificate, DreamClassification, DreamCoauthors, DreamDuel, DreamFlag, DreamInterface,
+    DreamInterpretation, DreamLicense, DreamLicenseTerms, DreamLicenseType, DreamRecord,
+    DreamRecordExtras, DreamValidation, DreamerProfile, EpochCommitment, EpochStats, FaucetClaim,
+    FeeVault, Grant, GuardianCouncil, IdempotencyNonce, InsuranceFund, InternedString, Leaderboard,
+    LookupTableConfig, LucidStake, MarketPosition, OptimisticScore, OracleQuorum, PinConfirmation,
+    PinningOracle, PredictionMarket, ProgramInfo, RelayNonce, ResearchLicense, ResearchPool,
+    RewardClaim, RewardConversionRates, RewardPool, RewardStream, SeasonScore, SessionKey,
+    SleepSession, SpliceConfig, SplicedDream, SponsorPool, Sponsorship, StatsRing,
+    TranslationBounty, Treasury, TreasuryHealth, UpgradeGovernance, ValidationDispute,
+    ValidationQueue, ValidatorGate, ValidatorRegistration, ValidatorRewardPool, ValidatorRewards,
+    ValidatorStats,
+};
+use solana_client::rpc_client::RpcClient;
+
+use crate::{pda::*, ClientError, Result};
+
+/// Fetch any program account and decode it, checking the Anchor discriminator.
+pub fn fetch_account<T: AccountDeserialize>(client: &RpcClient, address: &Pubkey) -> Result<T> {
+    let data = client.get_account_data(address)?;
+    Ok(T::try_deserialize(&mut data.as_slice())?)
+}
+
+pub fn fetch_treasury(client: &RpcClient) -> Result<Treasury> {
+    fetch_account(client, &find_treasury_address().0)
+}
+
+/// Records created before sleep sessions existed are shorter than the current
+/// layout; they are zero-padded, so `session` reads as the default key.
+pub fn fetch_dream_record(client: &RpcClient, dreamer: &Pubkey, dream_id: u64) -> Result<DreamRecord> {
+    let mut data = client.get_account_data(&find_dream_record_address(dreamer, dream_id).0)?;
+    let len = 8 + std::mem::size_of::<DreamRecord>();
+    if data.len() < len {
+        data.resize(len, 0);
+    }
+    Ok(DreamRecord::try_deserialize(&mut data.as_slice())?)
+}
+
+pub fn fetch_dream_extras(
+    client: &RpcClient,
+    dreamer: &Pubkey,
+    dream_id: u64,
+) -> Result<DreamRecordExtras> {
+    let data = client.get_account_data(&find_dream_record_address(dreamer, dream_id).0)?;
+    Ok(DreamRecord::extras(&data)?)
+}
+
+pub fn fetch_interned_string(client: &RpcClient, id: u64) -> Result<InternedString> {
+    fetch_account(client, &find_interned_string_address(id).0)
+}
+
+/// Resolve interned `ids`, such as a dream's tags, in one round trip. An id
+/// nobody interned resolves to `None`.
+pub fn resolve_interned_strings(client: &RpcClient, ids: &[u64]) -> Result<Vec<Option<String>>> {
+    let addresses: Vec<Pubkey> =
+        ids.iter().map(|&id| find_interned_string_address(id).0).collect();
+    let mut strings = Vec::with_capacity(ids.len());
+    for account in client.get_multiple_accounts(&addresses)? {
+        let string = match account {
+            Some(account) => Some(InternedString::try_deserialize(&mut account.data.as_slice())?),
+            None => None,
+        };
+        strings.push(string.map(|string| string.value));
+    }
+    Ok(strings)
+}
+
+pub fn fetch_epoch_stats(client: &RpcClient, day: u32) -> Result<EpochStats> {
+    fetch_account(client, &find_epoch_stats_address(day).0)
+}
+
+pub fn fetch_epoch_commitment(client: &RpcClient, day: u32) -> Result<EpochCommitment> {
+    fetch_account(client, &find_epoch_commitment_address(day).0)
+}
+
+pub fn fetch_research_pool(client: &RpcClient) -> Result<ResearchPool> {
+    fetch_account(client, &find_research_pool_address().0)
+}
+
+pub fn fetch_research_license(client: &RpcClient, licensee: &Pubkey) -> Result<ResearchLicense> {
+    fetch_account(client, &find_research_license_address(licensee).0)
+}
+
+pub fn fetch_license_terms(client: &RpcClient, dream_record: &Pubkey) -> Result<DreamLicenseTerms> {
+    fetch_account(client, &find_license_terms_address(dream_record).0)
+}
+
+pub fn fetch_dream_license(
+    client: &RpcClient,
+    dream_record: &Pubkey,
+    licensee: &Pubkey,
+    license_type: DreamLicenseType,
+) -> Result<DreamLicense> {
+    fetch_account(client, &find_dream_license_address(dream_record, licensee, license_type).0)
+}
+
+pub fn fetch_dream_coauthors(client: &RpcClient, dream_record: &Pubkey) -> Result<DreamCoauthors> {
+    fetch_account(client, &find_dream_coauthors_address(dream_record).0)
+}
+
+pub fn fetch_annotation(
+    client: &RpcClient,
+    dream_record: &Pubkey,
+    annotator: &Pubkey,
+    language: [u8; 2],
+) -> Result<DreamAnnotation> {
+    fetch_account(client, &find_annotation_address(dream_record, annotator, language).0)
+}
+
+pub fn fetch_translation_bounty(
+    client: &RpcClient,
+    dream_record: &Pubkey,
+    language: [u8; 2],
+) -> Result<TranslationBounty> {
+    fetch_account(client, &find_translation_bounty_address(dream_record, language).0)
+}
+
+pub fn fetch_dream_interpretation(
+    client: &RpcClient,
+    dream_record: &Pubkey,
+) -> Result<DreamInterpretation> {
+    fetch_account(client, &find_dream_interpretation_address(dream_record).0)
+}
+
+pub fn fetch_market(client: &RpcClient, creator: &Pubkey, market_id: u64) -> Result<PredictionMarket> {
+    fetch_account(client, &find_market_address(creator, market_id).0)
+}
+
+pub fn fetch_market_position(client: &RpcClient, market: &Pubkey, owner: &Pubkey) -> Result<MarketPosition> {
+    fetch_account(client, &find_market_position_address(market, owner).0)
+}
+
+pub fn fetch_charity(client: &RpcClient, wallet: &Pubkey) -> Result<Charity> {
+    fetch_account(client, &find_charity_address(wallet).0)
+}
+
+pub fn fetch_charity_split(client: &RpcClient, dreamer: &Pubkey) -> Result<CharitySplit> {
+    fetch_account(client, &find_charity_split_address(dreamer).0)
+}
+
+pub fn fetch_capsule(client: &RpcClient, dreamer: &Pubkey, capsule_id: u64) -> Result<DreamCapsule> {
+    fetch_account(client, &find_capsule_address(dreamer, capsule_id).0)
+}
+
+pub fn fetch_reward_stream(client: &RpcClient, source: &Pubkey, beneficiary: &Pubkey) -> Result<RewardStream> {
+    fetch_account(client, &find_reward_stream_address(source, beneficiary).0)
+}
+
+pub fn fetch_duel(client: &RpcClient, challenger: &Pubkey, duel_id: u64) -> Result<DreamDuel> {
+    fetch_account(client, &find_duel_address(challenger, duel_id).0)
+}
+
+pub fn fetch_grant(client: &RpcClient, recipient: &Pubkey, grant_id: u64) -> Result<Grant> {
+    fetch_account(client, &find_grant_address(recipient, grant_id).0)
+}
+
+pub fn fetch_leaderboard(client: &RpcClient) -> Result<Leaderboard> {
+    fetch_account(client, &find_leaderboard_address().0)
+}
+
+pub fn fetch_stats_ring(client: &RpcClient) -> Result<StatsRing> {
+    fetch_account(client, &find_stats_ring_address().0)
+}
+
+pub fn fetch_validation_queue(client: &RpcClient) -> Result<ValidationQueue> {
+    fetch_account(client, &find_validation_queue_address().0)
+}
+
+pub fn fetch_pinning_oracle(client: &RpcClient, oracle: &Pubkey) -> Result<PinningOracle> {
+    fetch_account(client, &find_pinning_oracle_address(oracle).0)
+}
+
+pub fn fetch_oracle_quorum(client: &RpcClient) -> Result<OracleQuorum> {
+    fetch_account(client, &find_oracle_quorum_address().0)
+}
+
+pub fn fetch_pin_confirmation(client: &RpcClient, dream_record: &Pubkey) -> Result<PinConfirmation> {
+    fetch_account(client, &find_pin_confirmation_address(dream_record).0)
+}
+
+pub fn fetch_dream_classification(client: &RpcClient, dream_record: &Pubkey) -> Result<DreamClassification> {
+    fetch_account(client, &find_dream_classification_address(dream_record).0)
+}
+
+pub fn fetch_dream_validation(client: &RpcClient, dream_record: &Pubkey) -> Result<DreamValidation> {
+    fetch_account(client, &find_dream_validation_address(dream_record).0)
+}
+
+pub fn fetch_validator_gate(client: &RpcClient) -> Result<ValidatorGate> {
+    fetch_account(client, &find_validator_gate_address().0)
+}
+
+pub fn fetch_validator_registration(
+    client: &RpcClient,
+    validator: &Pubkey,
+) -> Result<ValidatorRegistration> {
+    fetch_account(client, &find_validator_registration_address(validator).0)
+}
+
+pub fn fetch_validator_reward_pool(client: &RpcClient) -> Result<ValidatorRewardPool> {
+    fetch_account(client, &find_validator_reward_pool_address().0)
+}
+
+pub fn fetch_validator_rewards(client: &RpcClient, validator: &Pubkey) -> Result<ValidatorRewards> {
+    fetch_account(client, &find_validator_rewards_address(validator).0)
+}
+
+pub fn fetch_validator_stats(client: &RpcClient, validator: &Pubkey) -> Result<ValidatorStats> {
+    fetch_account(client, &find_validator_stats_address(validator).0)
+}
+
+pub fn fetch_validation_dispute(client: &RpcClient, dream_record: &Pubkey) -> Result<ValidationDispute> {
+    fetch_account(client, &find_validation_dispute_address(dream_record).0)
+}
+
+pub fn fetch_optimistic_score(
+    client: &RpcClient,
+    dream_record: &Pubkey,
+) -> Result<OptimisticScore> {
+    fetch_account(client, &find_optimistic_score_address(dream_record).0)
+}
+
+pub fn fetch_splice_config(client: &RpcClient) -> Result<SpliceConfig> {
+    fetch_account(client, &find_splice_config_address().0)
+}
+
+pub fn fetch_spliced_dream(client: &RpcClient, dream_record: &Pubkey) -> Result<SplicedDream> {
+    fetch_account(client, &find_spliced_dream_address(dream_record).0)
+}
+
+/// Fails for a dream that was never certified.
+pub fn fetch_dream_certificate(
+    client: &RpcClient,
+    dream_record: &Pubkey,
+) -> Result<DreamCertificate> {
+    fetch_account(client, &find_dream_certificate_address(dream_record).0)
+}
+
+/// Fails for a dreamer who never equipped a companion.
+pub fn fetch_companion(client: &RpcClient, dreamer: &Pubkey) -> Result<Companion> {
+    fetch_account(client, &find_companion_address(dreamer).0)
+}
+
+pub fn fetch_companion_xp(client: &RpcClient, mint: &Pubkey) -> Result<CompanionXp> {
+    fetch_account(client, &find_companion_xp_address(mint).0)
+}
+
+/// Fails for a dream recorded without a companion.
+pub fn fetch_companion_stamp(client: &RpcClient, dream_record: &Pubkey) -> Result<CompanionStamp> {
+    fetch_account(client, &find_companion_stamp_address(dream_record).0)
+}
+
+pub fn fetch_dream_flag(client: &RpcClient, dream_record: &Pubkey) -> Result<DreamFlag> {
+    fetch_account(client, &find_dream_flag_address(dream_record).0)
+}
+
+pub fn fetch_session_key(
+    client: &RpcClient,
+    authority: &Pubkey,
+    signer: &Pubkey,
+) -> Result<SessionKey> {
+    fetch_account(client, &find_session_key_address(authority, signer).0)
+}
+
+/// A dreamer who has never relayed a dream has no account; their next nonce is 0.
+pub fn fetch_relay_nonce(client: &RpcClient, dreamer: &Pubkey) -> Result<RelayNonce> {
+    fetch_account(client, &find_relay_nonce_address(dreamer).0)
+}
+
+/// Whether a `record_dream` under `key` already landed: the nonce names the
+/// record it created.
+pub fn fetch_idempotency_nonce(
+    client: &RpcClient,
+    dreamer: &Pubkey,
+    key: u64,
+) -> Result<IdempotencyNonce> {
+    fetch_account(client, &find_idempotency_nonce_address(dreamer, key).0)
+}
+
+pub fn fetch_compressed_stake_tree(client: &RpcClient) -> Result<CompressedStakeTree> {
+    fetch_account(client, &find_compressed_stake_tree_address().0)
+}
+
+pub fn fetch_dream_archive_tree(client: &RpcClient) -> Result<DreamArchiveTree> {
+    fetch_account(client, &find_dream_archive_tree_address().0)
+}
+
+pub fn fetch_archived_dream(client: &RpcClient, dream_record: &Pubkey) -> Result<ArchivedDream> {
+    fetch_account(client, &find_archived_dream_address(dream_record).0)
+}
+
+pub fn fetch_crank_pool(client: &RpcClient) -> Result<CrankPool> {
+    fetch_account(client, &find_crank_pool_address().0)
+}
+
+pub fn fetch_sponsor_pool(client: &RpcClient) -> Result<SponsorPool> {
+    fetch_account(client, &find_sponsor_pool_address().0)
+}
+
+pub fn fetch_sponsorship(client: &RpcClient, dreamer: &Pubkey) -> Result<Sponsorship> {
+    fetch_account(client, &find_sponsorship_address(dreamer).0)
+}
+
+pub fn fetch_reward_pool(client: &RpcClient) -> Result<RewardPool> {
+    fetch_account(client, &find_reward_pool_address().0)
+}
+
+pub fn fetch_faucet_claim(client: &RpcClient, wallet: &Pubkey) -> Result<FaucetClaim> {
+    fetch_account(client, &find_faucet_claim_address(wallet).0)
+}
+
+pub fn fetch_conversion_rates(client: &RpcClient) -> Result<RewardConversionRates> {
+    fetch_account(client, &find_conversion_rates_address().0)
+}
+
+pub fn fetch_reward_claim(client: &RpcClient, dream_record: &Pubkey) -> Result<RewardClaim> {
+    fetch_account(client, &find_reward_claim_address(dream_record).0)
+}
+
+pub fn fetch_insurance_fund(client: &RpcClient) -> Result<InsuranceFund> {
+    fetch_account(client, &find_insurance_fund_address().0)
+}
+
+pub fn fetch_fee_vault(client: &RpcClient) -> Result<FeeVault> {
+    fetch_account(client, &find_fee_vault_address().0)
+}
+
+pub fn fetch_treasury_health(client: &RpcClient) -> Result<TreasuryHealth> {
+    fetch_account(client, &find_treasury_health_address().0)
+}
+
+pub fn fetch_season_score(client: &RpcClient, dreamer: &Pubkey) -> Result<SeasonScore> {
+    fetch_account(client, &find_season_score_address(dreamer).0)
+}
+
+pub fn fetch_sleep_session(client: &RpcClient, dreamer: &Pubkey, day: u32) -> Result<SleepSession> {
+    fetch_account(client, &find_sleep_session_address(dreamer, day).0)
+}
+
+pub fn fetch_dreamer_profile(client: &RpcClient, dreamer: &Pubkey) -> Result<DreamerProfile> {
+    fetch_account(client, &find_dreamer_profile_address(dreamer).0)
+}
+
+pub fn fetch_dream_interface(
+    client: &RpcClient,
+    dreamer: &Pubkey,
+    interface_index: u64,
+) -> Result<DreamInterface> {
+    fetch_account(client, &find_dream_interface_address(dreamer, interface_index).0)
+}
+
+pub fn fetch_stake(client: &RpcClient, user: &Pubkey, mint: &Pubkey) -> Result<LucidStake> {
+    fetch_account(client, &find_stake_address(user, mint).0)
+}
+
+pub fn fetch_program_info(client: &RpcClient) -> Result<ProgramInfo> {
+    fetch_account(client, &find_program_info_address().0)
+}
+
+pub fn fetch_build_info(client: &RpcClient) -> Result<BuildInfo> {
+    fetch_account(client, &find_build_info_address().0)
+}
+
+pub fn fetch_upgrade_governance(client: &RpcClient) -> Result<UpgradeGovernance> {
+    fetch_account(client, &find_upgrade_governance_address().0)
+}
+
+pub fn fetch_guardian_council(client: &RpcClient) -> Result<GuardianCouncil> {
+    fetch_account(client, &find_guardian_council_address().0)
+}
+
+/// Fetch the program's address lookup table, ready for
+/// [`crate::transaction::compile_v0`].
+pub fn fetch_lookup_table(client: &RpcClient) -> Result<AddressLookupTableAccount> {
+    let config: LookupTableConfig = fetch_account(client, &find_lookup_table_config_address().0)?;
+    let data = client.get_account_data(&config.lookup_table)?;
+    let table = AddressLookupTable::deserialize(&data).map_err(ClientError::LookupTable)?;
+    Ok(AddressLookupTableAccount {
+        key: config.lookup_table,
+        addresses: table.addresses.into_owned(),
+    })
+}
@@ -0,0 +1,202 @@
+//! Send transactions that survive congestion: a compute-unit price estimated
+//! from recent prioritization fees, a fresh blockhash on every attempt (or a
+//! durable nonce), and exponential backoff between attempts.
+//!
+//! Only failures that say nothing about the transaction itself are retried
+//! (expired blockhash, RPC or network errors, confirmation timeouts). A program
+//! error is returned straight away.
+
+use std::{thread, time::Duration};
+
+use anchor_lang::solana_program::{hash::Hash, instruction::Instruction, pubkey::Pubkey};
+use solana_client::{nonce_utils, rpc_client::RpcClient};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    signature::Signature,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+use crate::{ClientError, Result};
+
+/// A nonce account the transaction is built against instead of a recent
+/// blockhash. Transactions built this way stay valid until the nonce advances.
+#[derive(Clone, Copy, Debug)]
+pub struct DurableNonce {
+    pub nonce_account: Pubkey,
+    /// Must sign the transaction, since it advances the nonce.
+    pub authority: Pubkey,
+}
+
+#[derive(Clone, Debug)]
+pub struct DispatchConfig {
+    /// Compute unit limit requested for the transaction; `None` keeps the
+    /// runtime default (200k per instruction).
+    pub compute_unit_limit: Option<u32>,
+    /// Percentile of recent prioritization fees (over the transaction's
+    /// writable accounts) to pay, 0-100.
+    pub fee_percentile: u8,
+    /// Floor and cap on the estimated price, in micro-lamports per compute unit.
+    pub min_compute_unit_price: u64,
+    pub max_compute_unit_price: u64,
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub nonce: Option<DurableNonce>,
+}
+
+impl Default for DispatchConfig {
+    fn default() -> Self {
+        Self {
+            compute_unit_limit: None,
+            fee_percentile: 75,
+            min_compute_unit_price: 0,
+            max_compute_unit_price: 1_000_000,
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            nonce: None,
+        }
+    }
+}
+
+/// Compute-unit price paid by the `percentile`th of recent slots that touched
+/// any of `writable_accounts` (or the whole cluster if empty).
+pub fn estimate_compute_unit_price(
+    client: &RpcClient,
+    writable_accounts: &[Pubkey],
+    percentile: u8,
+) -> Result<u64> {
+    let mut fees: Vec<u64> = client
+        .get_recent_prioritization_fees(writable_accounts)?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    if fees.is_empty() {
+        return Ok(0);
+    }
+    fees.sort_unstable();
+    let index = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    Ok(fees[index])
+}
+
+/// Prepend compute budget instructions to `instructions`.
+pub fn with_compute_budget(
+    instructions: &[Instruction],
+    compute_unit_price: u64,
+    compute_unit_limit: Option<u32>,
+) -> Vec<Instruction> {
+    let mut out = Vec::with_capacity(instructions.len() + 2);
+    if let Some(limit) = compute_unit_limit {
+        out.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+    if compute_unit_price > 0 {
+        out.push(ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price));
+    }
+    out.extend_from_slice(instructions);
+    out
+}
+
+/// Current value stored in a durable nonce account, to use as the
+/// transaction's recent blockhash.
+pub fn fetch_nonce_blockhash(client: &RpcClient, nonce_account: &Pubkey) -> Result<Hash> {
+    let account = nonce_utils::get_account(client, nonce_account)?;
+    Ok(nonce_utils::data_from_account(&account)?.blockhash())
+}
+
+/// `instructions` with the nonce advance first, as the runtime requires for
+/// durable-nonce transactions.
+pub fn with_advance_nonce(instructions: &[Instruction], nonce: &DurableNonce) -> Vec<Instruction> {
+    let mut out = Vec::with_capacity(instructions.len() + 1);
+    out.push(system_instruction::advance_nonce_account(&nonce.nonce_account, &nonce.authority));
+    out.extend_from_slice(instructions);
+    out
+}
+
+fn is_retryable(err: &ClientError) -> bool {
+    match err {
+        ClientError::Rpc(err) => matches!(
+            err.get_transaction_error(),
+            None | Some(TransactionError::BlockhashNotFound)
+        ),
+        _ => false,
+    }
+}
+
+/// Sign and send `instructions` with `payer` as fee payer, waiting for
+/// confirmation.
+///
+/// With a recent blockhash, each attempt re-estimates the compute-unit price
+/// and re-signs against a fresh blockhash; an attempt only times out once its
+/// blockhash has expired, so two attempts can't both land. A durable-nonce
+/// transaction is signed once and rebroadcast unchanged instead: the nonce can
+/// only be consumed once, which makes the retries idempotent.
+pub fn send_with_retry(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &dyn Signer,
+    signers: &[&dyn Signer],
+    config: &DispatchConfig,
+) -> Result<Signature> {
+    let mut writable_accounts: Vec<Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| ix.accounts.iter())
+        .filter(|meta| meta.is_writable)
+        .map(|meta| meta.pubkey)
+        .collect();
+    writable_accounts.sort_unstable();
+    writable_accounts.dedup();
+    let build = || -> Result<Transaction> {
+        let price = estimate_compute_unit_price(client, &writable_accounts, config.fee_percentile)?
+            .clamp(config.min_compute_unit_price, config.max_compute_unit_price);
+        let instructions = with_compute_budget(instructions, price, config.compute_unit_limit);
+        let (instructions, blockhash) = match &config.nonce {
+            Some(nonce) => (
+                with_advance_nonce(&instructions, nonce),
+                fetch_nonce_blockhash(client, &nonce.nonce_account)?,
+            ),
+            None => (instructions, client.get_latest_blockhash()?),
+        };
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        transaction.try_sign(&all_signers, blockhash)?;
+        Ok(transaction)
+    };
+
+    let presigned = match config.nonce {
+        Some(_) => Some(build()?),
+        None => None,
+    };
+    let mut backoff = config.initial_backoff;
+    let mut attempt = 1;
+    loop {
+        let result = match &presigned {
+            Some(transaction) => send_presigned(client, transaction),
+            None => build().and_then(|transaction| {
+                Ok(client.send_and_confirm_transaction(&transaction)?)
+            }),
+        };
+        match result {
+            Ok(signature) => return Ok(signature),
+            Err(err) if attempt < config.max_attempts && is_retryable(&err) => {
+                thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Broadcast an already signed transaction and wait for it, unless an earlier
+/// broadcast of it has already landed.
+pub fn send_presigned(client: &RpcClient, transaction: &Transaction) -> Result<Signature> {
+    let signature = transaction.signatures[0];
+    if let Some(status) = client.get_signature_status(&signature)? {
+        status.map_err(solana_client::client_error::ClientError::from)?;
+        return Ok(signature);
+    }
+    client.send_transaction(transaction)?;
+    client.poll_for_signature(&signature)?;
+    Ok(signature)
+}
@@ -0,0 +1,2847 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        address_lookup_table,
+        bpf_loader_upgradeable,
+        ed25519_program,
+        instruction::{AccountMeta, Instruction},
+        system_program,
+        sysvar,
+    },
+    Discriminator, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{associated_token, token, token_2022};
+use dream_mind_common::constants::GUARDIAN_COUNCIL_SIZE;
+use dream_mind_lucid::{
+    accounts,
+    cid::delegated_dream_message,
+    instruction,
+    intern,
+    messaging::GovernanceAction,
+    oneirobot::{self, OneirobotAttributes},
+    BatchedDream, DreamFlagReason, DreamLicenseType, DreamMetadata, DreamRecordExtras, EpochStats,
+    FraudProof, RewardToken, RoyaltyShare, ShortfallTarget, ID,
+};
+
+use crate::{pda::*, wormhole};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Instructions where a user pays rent. Each takes the user's signer first and
+/// a separate `payer` second; the builders here fill both with the user.
+const USER_PAID: [[u8; 8]; 28] = [
+    <instruction::AssignInterpreter as Discriminator>::DISCRIMINATOR,
+    <instruction::AttachAnnotation as Discriminator>::DISCRIMINATOR,
+    <instruction::AttachInterpretation as Discriminator>::DISCRIMINATOR,
+    <instruction::BridgeDreamOut as Discriminator>::DISCRIMINATOR,
+    <instruction::BuyOutcome as Discriminator>::DISCRIMINATOR,
+    <instruction::ClaimMarketWinnings as Discriminator>::DISCRIMINATOR,
+    <instruction::ClaimResearchRevenue as Discriminator>::DISCRIMINATOR,
+    <instruction::ClaimRewardAs as Discriminator>::DISCRIMINATOR,
+    <instruction::ClaimStreamed as Discriminator>::DISCRIMINATOR,
+    <instruction::ConfirmCoauthorship as Discriminator>::DISCRIMINATOR,
+    <instruction::CreateDuel as Discriminator>::DISCRIMINATOR,
+    <instruction::CreateMarket as Discriminator>::DISCRIMINATOR,
+    <instruction::EquipOneirobotToProfile as Discriminator>::DISCRIMINATOR,
+    <instruction::InterfaceDream as Discriminator>::DISCRIMINATOR,
+    <instruction::LicenseDream as Discriminator>::DISCRIMINATOR,
+    <instruction::MigrateLegacyStorage as Discriminator>::DISCRIMINATOR,
+    <instruction::MigrateStake as Discriminator>::DISCRIMINATOR,
+    <instruction::OpenSession as Discriminator>::DISCRIMINATOR,
+    <instruction::PostTranslationBounty as Discriminator>::DISCRIMINATOR,
+    <instruction::PurchaseResearchLicense as Discriminator>::DISCRIMINATOR,
+    <instruction::RecordDream as Discriminator>::DISCRIMINATOR,
+    <instruction::RecordDreamsBatch as Discriminator>::DISCRIMINATOR,
+    <instruction::RecordTimelockedDream as Discriminator>::DISCRIMINATOR,
+    <instruction::SetCharitySplit as Discriminator>::DISCRIMINATOR,
+    <instruction::SetDreamExtras as Discriminator>::DISCRIMINATOR,
+    <instruction::SetLicenseTerms as Discriminator>::DISCRIMINATOR,
+    <instruction::SetResearchConsent as Discriminator>::DISCRIMINATOR,
+    <instruction::StakeForLucidAccess as Discriminator>::DISCRIMINATOR,
+];
+
+/// Position of the rent payer in `ix`, if it is a user-paid instruction.
+pub fn rent_payer_index(ix: &Instruction) -> Option<usize> {
+    let paid = ix.program_id == ID
+        && USER_PAID.iter().any(|discriminator| ix.data.starts_with(discriminator));
+    paid.then_some(1)
+}
+
+/// Move the rent (and `record_dream`'s fee) of a user-paid instruction to
+/// `payer`, e.g. a relayer that also pays the transaction fee. The user still
+/// signs. Other instructions are returned unchanged.
+pub fn with_rent_payer(mut ix: Instruction, payer: &Pubkey) -> Instruction {
+    if let Some(index) = rent_payer_index(&ix) {
+        ix.accounts[index] = AccountMeta::new(*payer, true);
+    }
+    ix
+}
+
+pub fn initialize_tokens(
+    authority: &Pubkey,
+    dream_mint: &Pubkey,
+    smind_mint: &Pubkey,
+    lucid_mint: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::InitializeTokens {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            fee_vault: find_fee_vault_address().0,
+            dream_mint: *dream_mint,
+            smind_mint: *smind_mint,
+            lucid_mint: *lucid_mint,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::InitializeTokens {},
+    )
+}
+
+pub fn bootstrap(authority: &Pubkey) -> Instruction {
+    let treasury = find_treasury_address().0;
+    let dream_mint = find_dream_mint_address().0;
+    let smind_mint = find_smind_mint_address().0;
+    let lucid_mint = find_lucid_mint_address().0;
+    let treasury_ata = |mint: &Pubkey| {
+        associated_token::get_associated_token_address_with_program_id(&treasury, mint, &token_2022::ID)
+    };
+    build(
+        accounts::Bootstrap {
+            authority: *authority,
+            treasury,
+            fee_vault: find_fee_vault_address().0,
+            treasury_health: find_treasury_health_address().0,
+            dream_mint,
+            smind_mint,
+            lucid_mint,
+            treasury_dream_account: treasury_ata(&dream_mint),
+            treasury_smind_account: treasury_ata(&smind_mint),
+            treasury_lucid_account: treasury_ata(&lucid_mint),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::Bootstrap {},
+    )
+}
+
+/// Mint `amount` DREAM into the reward pool, at most
+/// `MAX_REWARD_POOL_TOP_UP_PER_PERIOD` per period.
+pub fn top_up_pool(authority: &Pubkey, dream_mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::TopUpPool {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            reward_pool: find_reward_pool_address().0,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::TopUpPool { amount },
+    )
+}
+
+/// Fails unless the program was built with the `devnet` feature; each
+/// wallet may use it once per `FAUCET_COOLDOWN_SECS`.
+pub fn faucet_mint(
+    wallet: &Pubkey,
+    dream_mint: &Pubkey,
+    smind_mint: &Pubkey,
+    lucid_mint: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::FaucetMint {
+            wallet: *wallet,
+            treasury: find_treasury_address().0,
+            faucet_claim: find_faucet_claim_address(wallet).0,
+            dream_mint: *dream_mint,
+            smind_mint: *smind_mint,
+            lucid_mint: *lucid_mint,
+            wallet_dream_account: associated_token::get_associated_token_address_with_program_id(
+                wallet,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            wallet_smind_account: smind_ata(wallet, smind_mint),
+            wallet_lucid_account: lucid_ata(wallet, lucid_mint),
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::FaucetMint {},
+    )
+}
+
+pub fn set_conversion_rates(
+    authority: &Pubkey,
+    smind_rate_bps: u32,
+    lucid_rate_bps: u32,
+) -> Instruction {
+    build(
+        accounts::SetConversionRates {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            conversion_rates: find_conversion_rates_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetConversionRates {
+            smind_rate_bps,
+            lucid_rate_bps,
+        },
+    )
+}
+
+/// `reward_mint` is the treasury's SMIND or LUCID mint, matching `token`.
+pub fn claim_reward_as(
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    reward_mint: &Pubkey,
+    dream_record: &Pubkey,
+    token: RewardToken,
+    dream_amount: u64,
+) -> Instruction {
+    build(
+        accounts::ClaimRewardAs {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            treasury: find_treasury_address().0,
+            dream_record: *dream_record,
+            conversion_rates: find_conversion_rates_address().0,
+            reward_claim: find_reward_claim_address(dream_record).0,
+            dream_mint: *dream_mint,
+            dreamer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            reward_mint: *reward_mint,
+            dreamer_reward_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                reward_mint,
+                &token_2022::ID,
+            ),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimRewardAs {
+            token,
+            dream_amount,
+        },
+    )
+}
+
+/// Optional accounts for `record_dream`.
+#[derive(Clone, Debug, Default)]
+pub struct RecordDreamOptions {
+    /// Wallet named in the dreamer's `CharitySplit`. Without it the split
+    /// is not applied and the whole reward goes to the dreamer.
+    pub charity: Option<Pubkey>,
+    /// Adds the dream to the validation queue.
+    pub enqueue: bool,
+    /// The treasury's LUCID mint, when the dreamer has a LUCID stake; with
+    /// `enqueue`, the stake raises the dream's queue priority.
+    pub lucid_mint: Option<Pubkey>,
+    /// Co-dreamers who each claim an equal share of the reward with
+    /// `confirm_coauthorship`.
+    pub co_dreamers: Vec<Pubkey>,
+    /// Client-generated key; resending under the same key fails instead of
+    /// recording the dream twice.
+    pub idempotency_key: Option<u64>,
+    /// The dreamer's equipped companion, stamped on the record in a
+    /// `CompanionStamp`. Without it the companion earns no XP.
+    pub companion: Option<OneirobotHolding>,
+    /// Analyst to interpret the dream, who is held back `INTERPRETER_SHARE_BPS`
+    /// of the reward until validation.
+    pub interpreter: Option<Pubkey>,
+}
+
+/// `dream_id` must equal the treasury's current `total_dreams_recorded`.
+/// `unix_timestamp` selects the day's `EpochStats` and `SleepSession`; the
+/// transaction fails if the cluster is already on another day when it lands.
+pub fn record_dream(
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_id: u64,
+    dream_content_hash: [u8; 32],
+    metadata: DreamMetadata,
+    options: RecordDreamOptions,
+    unix_timestamp: i64,
+) -> Instruction {
+    let day = EpochStats::day_of(unix_timestamp);
+    let dream_record = find_dream_record_address(dreamer, dream_id).0;
+    build(
+        accounts::RecordDream {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            treasury: find_treasury_address().0,
+            fee_vault: find_fee_vault_address().0,
+            dream_record,
+            dream_mint: *dream_mint,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            dreamer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            sleep_session: find_sleep_session_address(dreamer, day).0,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            epoch_stats: find_epoch_stats_address(day).0,
+            season_score: find_season_score_address(dreamer).0,
+            charity_split: options.charity.map(|_| find_charity_split_address(dreamer).0),
+            charity: options.charity.map(|wallet| find_charity_address(&wallet).0),
+            charity_dream_account: options.charity.map(|wallet| {
+                associated_token::get_associated_token_address_with_program_id(
+                    &wallet,
+                    dream_mint,
+                    &token_2022::ID,
+                )
+            }),
+            validation_queue: options.enqueue.then(|| find_validation_queue_address().0),
+            lucid_stake: options
+                .lucid_mint
+                .filter(|_| options.enqueue)
+                .map(|mint| find_stake_address(dreamer, &mint).0),
+            dream_coauthors: (!options.co_dreamers.is_empty())
+                .then(|| find_dream_coauthors_address(&dream_record).0),
+            treasury_health: (!options.co_dreamers.is_empty() || options.interpreter.is_some())
+                .then(|| find_treasury_health_address().0),
+            interpretation: options
+                .interpreter
+                .map(|_| find_dream_interpretation_address(&dream_record).0),
+            companion: options.companion.as_ref().map(|_| find_companion_address(dreamer).0),
+            companion_token_account: options
+                .companion
+                .as_ref()
+                .map(|holding| holding.token_account),
+            companion_xp: options
+                .companion
+                .as_ref()
+                .map(|holding| find_companion_xp_address(&holding.mint).0),
+            companion_stamp: options
+                .companion
+                .as_ref()
+                .map(|_| find_companion_stamp_address(&dream_record).0),
+            idempotency_nonce: options
+                .idempotency_key
+                .map(|key| find_idempotency_nonce_address(dreamer, key).0),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::RecordDream {
+            dream_content_hash,
+            metadata,
+            co_dreamers: options.co_dreamers,
+            idempotency_key: options.idempotency_key,
+            interpreter: options.interpreter,
+        },
+    )
+}
+
+/// Refunds `payer`, who paid for the nonce, once its TTL has passed.
+pub fn close_idempotency_nonce(payer: &Pubkey, dreamer: &Pubkey, key: u64) -> Instruction {
+    build(
+        accounts::CloseIdempotencyNonce {
+            payer: *payer,
+            idempotency_nonce: find_idempotency_nonce_address(dreamer, key).0,
+        },
+        instruction::CloseIdempotencyNonce {},
+    )
+}
+
+/// `first_dream_id` must equal the treasury's current `total_dreams_recorded`;
+/// the dreams get consecutive ids from there. `unix_timestamp` works as in
+/// `record_dream`.
+pub fn record_dreams_batch(
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    first_dream_id: u64,
+    dreams: Vec<BatchedDream>,
+    unix_timestamp: i64,
+) -> Instruction {
+    let day = EpochStats::day_of(unix_timestamp);
+    let records: Vec<AccountMeta> = (first_dream_id..)
+        .take(dreams.len())
+        .map(|id| AccountMeta::new(find_dream_record_address(dreamer, id).0, false))
+        .collect();
+    let mut ix = build(
+        accounts::RecordDreamsBatch {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            treasury: find_treasury_address().0,
+            fee_vault: find_fee_vault_address().0,
+            dream_mint: *dream_mint,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            dreamer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            sleep_session: find_sleep_session_address(dreamer, day).0,
+            epoch_stats: find_epoch_stats_address(day).0,
+            season_score: find_season_score_address(dreamer).0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::RecordDreamsBatch { dreams },
+    );
+    ix.accounts.extend(records);
+    ix
+}
+
+/// A dream as the dreamer signs it for a relayer.
+#[derive(Clone, Debug)]
+pub struct SignedDream {
+    pub dreamer: Pubkey,
+    pub dream_content_hash: [u8; 32],
+    pub metadata: DreamMetadata,
+    /// The dreamer's current `RelayNonce`, or 0 before their first relayed dream.
+    pub nonce: u64,
+}
+
+impl SignedDream {
+    /// The bytes the dreamer signs with ed25519.
+    pub fn message(&self) -> Vec<u8> {
+        delegated_dream_message(&self.dream_content_hash, &self.metadata, self.nonce)
+    }
+}
+
+/// Must directly follow [`ed25519_verify`] of the dreamer's signature over
+/// [`SignedDream::message`]. `dream_id` and `unix_timestamp` work as in
+/// `record_dream`.
+pub fn record_dream_delegated(
+    relayer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_id: u64,
+    dream: &SignedDream,
+    unix_timestamp: i64,
+) -> Instruction {
+    relayed_dream(relayer, dream_mint, dream_id, dream, false, unix_timestamp)
+}
+
+/// Record a dream signed by one of the dreamer's session keys instead of
+/// the dreamer's wallet; the session key pays the fee and rent.
+pub fn record_dream_with_session(
+    session_signer: &Pubkey,
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_id: u64,
+    dream_content_hash: [u8; 32],
+    metadata: DreamMetadata,
+    unix_timestamp: i64,
+) -> Instruction {
+    let dream = SignedDream {
+        dreamer: *dreamer,
+        dream_content_hash,
+        metadata,
+        nonce: 0,
+    };
+    relayed_dream(session_signer, dream_mint, dream_id, &dream, true, unix_timestamp)
+}
+
+fn relayed_dream(
+    relayer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_id: u64,
+    dream: &SignedDream,
+    with_session: bool,
+    unix_timestamp: i64,
+) -> Instruction {
+    let day = EpochStats::day_of(unix_timestamp);
+    let dreamer = &dream.dreamer;
+    build(
+        accounts::RecordDreamDelegated {
+            relayer: *relayer,
+            dreamer: *dreamer,
+            treasury: find_treasury_address().0,
+            fee_vault: find_fee_vault_address().0,
+            dream_record: find_dream_record_address(dreamer, dream_id).0,
+            relay_nonce: find_relay_nonce_address(dreamer).0,
+            dream_mint: *dream_mint,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            dreamer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            sleep_session: find_sleep_session_address(dreamer, day).0,
+            epoch_stats: find_epoch_stats_address(day).0,
+            season_score: find_season_score_address(dreamer).0,
+            session_key: with_session.then(|| find_session_key_address(dreamer, relayer).0),
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::RecordDreamDelegated {
+            dream_content_hash: dream.dream_content_hash,
+            metadata: dream.metadata,
+            nonce: dream.nonce,
+        },
+    )
+}
+
+/// `scope` is a set of `SESSION_SCOPE_*` bits; `lamports` moves to the session
+/// key so it can pay for the transactions it signs.
+pub fn create_session_key(
+    authority: &Pubkey,
+    session_signer: &Pubkey,
+    scope: u8,
+    expires_at: i64,
+    lamports: u64,
+) -> Instruction {
+    build(
+        accounts::CreateSessionKey {
+            authority: *authority,
+            session_signer: *session_signer,
+            session_key: find_session_key_address(authority, session_signer).0,
+            system_program: system_program::ID,
+        },
+        instruction::CreateSessionKey {
+            scope,
+            expires_at,
+            lamports,
+        },
+    )
+}
+
+pub fn revoke_session_key(authority: &Pubkey, session_signer: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeSessionKey {
+            authority: *authority,
+            session_key: find_session_key_address(authority, session_signer).0,
+        },
+        instruction::RevokeSessionKey {},
+    )
+}
+
+/// Idempotent, so it can lead any transaction that sets `value`'s id as a tag.
+pub fn intern_string(payer: &Pubkey, value: String) -> Instruction {
+    build(
+        accounts::InternString {
+            payer: *payer,
+            interned_string: find_interned_string_address(intern::string_id(&value)).0,
+            system_program: system_program::ID,
+        },
+        instruction::InternString { value },
+    )
+}
+
+/// Reallocs the record to fit `extras`; the dreamer pays for any growth.
+pub fn set_dream_extras(
+    dreamer: &Pubkey,
+    dream_record: &Pubkey,
+    extras: DreamRecordExtras,
+) -> Instruction {
+    build(
+        accounts::SetDreamExtras {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            dream_record: *dream_record,
+            system_program: system_program::ID,
+        },
+        instruction::SetDreamExtras { extras },
+    )
+}
+
+/// Co-sign a shared dream; may share a transaction with its `record_dream`.
+pub fn confirm_coauthorship(
+    co_dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_record: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ConfirmCoauthorship {
+            co_dreamer: *co_dreamer,
+            payer: *co_dreamer,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            dream_coauthors: find_dream_coauthors_address(dream_record).0,
+            treasury_health: find_treasury_health_address().0,
+            co_dreamer_dream_account:
+                associated_token::get_associated_token_address_with_program_id(
+                    co_dreamer,
+                    dream_mint,
+                    &token_2022::ID,
+                ),
+            season_score: find_season_score_address(co_dreamer).0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ConfirmCoauthorship {},
+    )
+}
+
+/// Permissionless once the claim window has closed; forfeits unconfirmed
+/// shares and refunds the rent to `dreamer`. A `cranker` (who must sign)
+/// collects the crank bounty, if one is due.
+pub fn expire_coauthor_shares(
+    dream_record: &Pubkey,
+    dreamer: &Pubkey,
+    cranker: Option<&Pubkey>,
+) -> Instruction {
+    build(
+        accounts::ExpireCoauthorShares {
+            treasury: find_treasury_address().0,
+            dream_coauthors: find_dream_coauthors_address(dream_record).0,
+            dreamer: *dreamer,
+            treasury_health: find_treasury_health_address().0,
+            crank_pool: cranker.map(|_| find_crank_pool_address().0),
+            cranker: cranker.copied(),
+        },
+        instruction::ExpireCoauthorShares {},
+    )
+}
+
+/// Build `merkle_root` with `dream_mind_lucid::merkle` over the day's records.
+pub fn commit_epoch_root(
+    authority: &Pubkey,
+    day: u32,
+    merkle_root: [u8; 32],
+    leaf_count: u64,
+) -> Instruction {
+    build(
+        accounts::CommitEpochRoot {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            epoch_stats: find_epoch_stats_address(day).0,
+            epoch_commitment: find_epoch_commitment_address(day).0,
+            system_program: system_program::ID,
+        },
+        instruction::CommitEpochRoot {
+            day,
+            merkle_root,
+            leaf_count,
+        },
+    )
+}
+
+pub fn set_research_consent(dreamer: &Pubkey, consent: bool) -> Instruction {
+    build(
+        accounts::SetResearchConsent {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            research_pool: find_research_pool_address().0,
+            research_participant: find_research_participant_address(dreamer).0,
+            system_program: system_program::ID,
+        },
+        instruction::SetResearchConsent { consent },
+    )
+}
+
+/// Pays `RESEARCH_LICENSE_PRICE` LUCID from the licensee's ATA.
+pub fn purchase_research_license(licensee: &Pubkey, lucid_mint: &Pubkey) -> Instruction {
+    let treasury = find_treasury_address().0;
+    build(
+        accounts::PurchaseResearchLicense {
+            licensee: *licensee,
+            payer: *licensee,
+            treasury,
+            lucid_mint: *lucid_mint,
+            licensee_lucid_account: associated_token::get_associated_token_address_with_program_id(
+                licensee,
+                lucid_mint,
+                &token_2022::ID,
+            ),
+            treasury_lucid_account: associated_token::get_associated_token_address_with_program_id(
+                &treasury,
+                lucid_mint,
+                &token_2022::ID,
+            ),
+            research_vault: find_research_vault_address().0,
+            research_pool: find_research_pool_address().0,
+            research_license: find_research_license_address(licensee).0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::PurchaseResearchLicense {},
+    )
+}
+
+/// Prices are LUCID per year; 0 leaves that license type unavailable.
+pub fn set_license_terms(
+    dreamer: &Pubkey,
+    dream_record: &Pubkey,
+    commercial_price: u64,
+    derivative_price: u64,
+    co_authors: Vec<RoyaltyShare>,
+) -> Instruction {
+    build(
+        accounts::SetLicenseTerms {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            dream_record: *dream_record,
+            license_terms: find_license_terms_address(dream_record).0,
+            system_program: system_program::ID,
+        },
+        instruction::SetLicenseTerms {
+            prices: [commercial_price, derivative_price],
+            co_authors,
+        },
+    )
+}
+
+/// `co_authors` are the royalty recipients of the dream's terms, in order;
+/// each needs a LUCID ATA.
+pub fn license_dream(
+    licensee: &Pubkey,
+    lucid_mint: &Pubkey,
+    dream_record: &Pubkey,
+    dreamer: &Pubkey,
+    co_authors: &[Pubkey],
+    license_type: DreamLicenseType,
+) -> Instruction {
+    let treasury = find_treasury_address().0;
+    let mut ix = build(
+        accounts::LicenseDream {
+            licensee: *licensee,
+            payer: *licensee,
+            treasury,
+            lucid_mint: *lucid_mint,
+            license_terms: find_license_terms_address(dream_record).0,
+            dreamer: *dreamer,
+            licensee_lucid_account: lucid_ata(licensee, lucid_mint),
+            dreamer_lucid_account: lucid_ata(dreamer, lucid_mint),
+            treasury_lucid_account: lucid_ata(&treasury, lucid_mint),
+            dream_license: find_dream_license_address(dream_record, licensee, license_type).0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::LicenseDream { license_type },
+    );
+    ix.accounts.extend(
+        co_authors
+            .iter()
+            .map(|co_author| AccountMeta::new(lucid_ata(co_author, lucid_mint), false)),
+    );
+    ix
+}
+
+pub fn post_translation_bounty(
+    sponsor: &Pubkey,
+    lucid_mint: &Pubkey,
+    dream_record: &Pubkey,
+    language: [u8; 2],
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::PostTranslationBounty {
+            sponsor: *sponsor,
+            payer: *sponsor,
+            treasury: find_treasury_address().0,
+            lucid_mint: *lucid_mint,
+            dream_record: *dream_record,
+            translation_bounty: find_translation_bounty_address(dream_record, language).0,
+            bounty_vault: find_translation_bounty_vault_address(dream_record, language).0,
+            sponsor_lucid_account: lucid_ata(sponsor, lucid_mint),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::PostTranslationBounty { language, amount },
+    )
+}
+
+pub fn attach_annotation(
+    annotator: &Pubkey,
+    dream_record: &Pubkey,
+    language: [u8; 2],
+    cid: String,
+) -> Instruction {
+    build(
+        accounts::AttachAnnotation {
+            annotator: *annotator,
+            payer: *annotator,
+            dream_record: *dream_record,
+            annotation: find_annotation_address(dream_record, annotator, language).0,
+            system_program: system_program::ID,
+        },
+        instruction::AttachAnnotation { language, cid },
+    )
+}
+
+/// With `pay_bounty`, the language's `TranslationBounty` is paid to the
+/// annotator's LUCID account, which must already exist.
+pub fn approve_annotation(
+    authority: &Pubkey,
+    lucid_mint: &Pubkey,
+    dream_record: &Pubkey,
+    annotator: &Pubkey,
+    language: [u8; 2],
+    pay_bounty: bool,
+) -> Instruction {
+    build(
+        accounts::ApproveAnnotation {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            lucid_mint: *lucid_mint,
+            annotation: find_annotation_address(dream_record, annotator, language).0,
+            translation_bounty: pay_bounty
+                .then(|| find_translation_bounty_address(dream_record, language).0),
+            bounty_vault: pay_bounty
+                .then(|| find_translation_bounty_vault_address(dream_record, language).0),
+            annotator_lucid_account: pay_bounty.then(|| lucid_ata(annotator, lucid_mint)),
+            token_program: token_2022::ID,
+        },
+        instruction::ApproveAnnotation {},
+    )
+}
+
+/// Name `interpreter` to analyse `dreamer`'s dream before it is validated.
+pub fn assign_interpreter(
+    dreamer: &Pubkey,
+    dream_record: &Pubkey,
+    interpreter: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::AssignInterpreter {
+            dreamer: *dreamer,
+            dream_record: *dream_record,
+            dream_validation: find_dream_validation_address(dream_record).0,
+            interpretation: find_dream_interpretation_address(dream_record).0,
+        },
+        instruction::AssignInterpreter { interpreter: *interpreter },
+    )
+}
+
+pub fn attach_interpretation(
+    interpreter: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_record: &Pubkey,
+    analysis_cid: String,
+) -> Instruction {
+    build(
+        accounts::AttachInterpretation {
+            interpreter: *interpreter,
+            payer: *interpreter,
+            dream_record: *dream_record,
+            dream_validation: find_dream_validation_address(dream_record).0,
+            interpretation: find_dream_interpretation_address(dream_record).0,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            interpreter_dream_account: associated_token::get_associated_token_address_with_program_id(
+                interpreter,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::AttachInterpretation { analysis_cid },
+    )
+}
+
+pub fn claim_research_revenue(dreamer: &Pubkey, lucid_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimResearchRevenue {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            treasury: find_treasury_address().0,
+            lucid_mint: *lucid_mint,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            research_pool: find_research_pool_address().0,
+            research_participant: find_research_participant_address(dreamer).0,
+            research_vault: find_research_vault_address().0,
+            dreamer_lucid_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                lucid_mint,
+                &token_2022::ID,
+            ),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimResearchRevenue {},
+    )
+}
+
+pub fn create_market(
+    creator: &Pubkey,
+    dream_mint: &Pubkey,
+    market_id: u64,
+    question_hash: [u8; 32],
+    outcome_count: u8,
+    closes_at: i64,
+) -> Instruction {
+    let market = find_market_address(creator, market_id).0;
+    build(
+        accounts::CreateMarket {
+            creator: *creator,
+            creator_profile: find_dreamer_profile_address(creator).0,
+            payer: *creator,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            market,
+            market_vault: find_market_vault_address(&market).0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::CreateMarket {
+            market_id,
+            question_hash,
+            outcome_count,
+            closes_at,
+        },
+    )
+}
+
+pub fn buy_outcome(
+    buyer: &Pubkey,
+    dream_mint: &Pubkey,
+    market: &Pubkey,
+    outcome: u8,
+    amount: u64,
+) -> Instruction {
+    let treasury = find_treasury_address().0;
+    build(
+        accounts::BuyOutcome {
+            buyer: *buyer,
+            payer: *buyer,
+            treasury,
+            dream_mint: *dream_mint,
+            market: *market,
+            market_vault: find_market_vault_address(market).0,
+            position: find_market_position_address(market, buyer).0,
+            buyer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                buyer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            treasury_dream_account: associated_token::get_associated_token_address_with_program_id(
+                &treasury,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            insurance_fund: find_insurance_fund_address().0,
+            insurance_vault: find_insurance_vault_address().0,
+            validator_reward_pool: find_validator_reward_pool_address().0,
+            validator_reward_vault: find_validator_reward_vault_address().0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::BuyOutcome { outcome, amount },
+    )
+}
+
+pub fn resolve_market(authority: &Pubkey, market: &Pubkey, winning_outcome: u8) -> Instruction {
+    build(
+        accounts::ResolveMarket {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            market: *market,
+        },
+        instruction::ResolveMarket { winning_outcome },
+    )
+}
+
+/// Set `streamed` when `PredictionMarket::payout` for the position is above
+/// `REWARD_STREAM_THRESHOLD`; the winnings then vest in a `RewardStream`.
+pub fn claim_market_winnings(
+    owner: &Pubkey,
+    dream_mint: &Pubkey,
+    market: &Pubkey,
+    streamed: bool,
+) -> Instruction {
+    build(
+        accounts::ClaimMarketWinnings {
+            owner: *owner,
+            payer: *owner,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            market: *market,
+            market_vault: find_market_vault_address(market).0,
+            position: find_market_position_address(market, owner).0,
+            owner_dream_account: associated_token::get_associated_token_address_with_program_id(
+                owner,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            reward_stream: streamed.then(|| find_reward_stream_address(market, owner).0),
+            reward_stream_vault: streamed.then(|| find_reward_stream_vault_address().0),
+            treasury_health: streamed.then(|| find_treasury_health_address().0),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimMarketWinnings {},
+    )
+}
+
+/// `source` is the account the streamed payout came from, e.g. its market.
+pub fn claim_streamed(beneficiary: &Pubkey, dream_mint: &Pubkey, source: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimStreamed {
+            beneficiary: *beneficiary,
+            payer: *beneficiary,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            reward_stream: find_reward_stream_address(source, beneficiary).0,
+            reward_stream_vault: find_reward_stream_vault_address().0,
+            treasury_health: find_treasury_health_address().0,
+            beneficiary_dream_account: associated_token::get_associated_token_address_with_program_id(
+                beneficiary,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimStreamed {},
+    )
+}
+
+pub fn set_charity_approval(
+    authority: &Pubkey,
+    dream_mint: &Pubkey,
+    charity_wallet: &Pubkey,
+    approved: bool,
+) -> Instruction {
+    build(
+        accounts::SetCharityApproval {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            charity_wallet: *charity_wallet,
+            charity: find_charity_address(charity_wallet).0,
+            charity_dream_account: associated_token::get_associated_token_address_with_program_id(
+                charity_wallet,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::SetCharityApproval { approved },
+    )
+}
+
+pub fn set_charity_split(dreamer: &Pubkey, charity_wallet: &Pubkey, share_bps: u16) -> Instruction {
+    build(
+        accounts::SetCharitySplit {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            charity: find_charity_address(charity_wallet).0,
+            charity_split: find_charity_split_address(dreamer).0,
+            system_program: system_program::ID,
+        },
+        instruction::SetCharitySplit { share_bps },
+    )
+}
+
+/// `content_hash` must be `DreamCapsule::commitment` of the CID revealed later.
+pub fn record_timelocked_dream(
+    dreamer: &Pubkey,
+    capsule_id: u64,
+    content_hash: [u8; 32],
+    unlock_at: i64,
+) -> Instruction {
+    build(
+        accounts::RecordTimelockedDream {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            capsule: find_capsule_address(dreamer, capsule_id).0,
+            system_program: system_program::ID,
+        },
+        instruction::RecordTimelockedDream {
+            capsule_id,
+            content_hash,
+            unlock_at,
+        },
+    )
+}
+
+pub fn reveal_dream(dreamer: &Pubkey, capsule_id: u64, cid: String) -> Instruction {
+    build(
+        accounts::RevealDream {
+            dreamer: *dreamer,
+            capsule: find_capsule_address(dreamer, capsule_id).0,
+        },
+        instruction::RevealDream { cid },
+    )
+}
+
+fn lucid_ata(owner: &Pubkey, lucid_mint: &Pubkey) -> Pubkey {
+    associated_token::get_associated_token_address_with_program_id(owner, lucid_mint, &token_2022::ID)
+}
+
+pub fn create_duel(
+    challenger: &Pubkey,
+    lucid_mint: &Pubkey,
+    duel_id: u64,
+    opponent: &Pubkey,
+    stake: u64,
+) -> Instruction {
+    let duel = find_duel_address(challenger, duel_id).0;
+    build(
+        accounts::CreateDuel {
+            challenger: *challenger,
+            payer: *challenger,
+            treasury: find_treasury_address().0,
+            lucid_mint: *lucid_mint,
+            duel,
+            duel_vault: find_duel_vault_address(&duel).0,
+            challenger_lucid_account: lucid_ata(challenger, lucid_mint),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::CreateDuel {
+            duel_id,
+            opponent: *opponent,
+            stake,
+        },
+    )
+}
+
+pub fn accept_duel(opponent: &Pubkey, lucid_mint: &Pubkey, duel: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptDuel {
+            opponent: *opponent,
+            treasury: find_treasury_address().0,
+            lucid_mint: *lucid_mint,
+            duel: *duel,
+            duel_vault: find_duel_vault_address(duel).0,
+            opponent_lucid_account: lucid_ata(opponent, lucid_mint),
+            token_program: token_2022::ID,
+        },
+        instruction::AcceptDuel {},
+    )
+}
+
+pub fn cancel_duel(challenger: &Pubkey, lucid_mint: &Pubkey, duel: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelDuel {
+            challenger: *challenger,
+            treasury: find_treasury_address().0,
+            lucid_mint: *lucid_mint,
+            duel: *duel,
+            duel_vault: find_duel_vault_address(duel).0,
+            challenger_lucid_account: lucid_ata(challenger, lucid_mint),
+            token_program: token_2022::ID,
+        },
+        instruction::CancelDuel {},
+    )
+}
+
+pub fn submit_duel_dream(dreamer: &Pubkey, duel: &Pubkey, dream_record: &Pubkey) -> Instruction {
+    build(
+        accounts::SubmitDuelDream {
+            dreamer: *dreamer,
+            duel: *duel,
+            dream_record: *dream_record,
+        },
+        instruction::SubmitDuelDream {},
+    )
+}
+
+pub fn settle_duel(
+    authority: &Pubkey,
+    lucid_mint: &Pubkey,
+    duel: &Pubkey,
+    challenger: &Pubkey,
+    opponent: &Pubkey,
+    challenger_score: u8,
+    opponent_score: u8,
+) -> Instruction {
+    let treasury = find_treasury_address().0;
+    build(
+        accounts::SettleDuel {
+            authority: *authority,
+            treasury,
+            lucid_mint: *lucid_mint,
+            duel: *duel,
+            duel_vault: find_duel_vault_address(duel).0,
+            challenger_lucid_account: lucid_ata(challenger, lucid_mint),
+            opponent_lucid_account: lucid_ata(opponent, lucid_mint),
+            treasury_lucid_account: lucid_ata(&treasury, lucid_mint),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::SettleDuel {
+            challenger_score,
+            opponent_score,
+        },
+    )
+}
+
+/// Escrow the sum of `milestone_amounts` of the treasury's LUCID for
+/// `recipient`, released one milestone at a time.
+pub fn create_grant(
+    authority: &Pubkey,
+    lucid_mint: &Pubkey,
+    recipient: &Pubkey,
+    grant_id: u64,
+    milestone_amounts: Vec<u64>,
+) -> Instruction {
+    let treasury = find_treasury_address().0;
+    let grant = find_grant_address(recipient, grant_id).0;
+    build(
+        accounts::CreateGrant {
+            authority: *authority,
+            treasury,
+            lucid_mint: *lucid_mint,
+            recipient: *recipient,
+            grant,
+            grant_vault: find_grant_vault_address(&grant).0,
+            treasury_lucid_account: lucid_ata(&treasury, lucid_mint),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::CreateGrant {
+            grant_id,
+            milestone_amounts,
+        },
+    )
+}
+
+pub fn approve_milestone(
+    authority: &Pubkey,
+    lucid_mint: &Pubkey,
+    recipient: &Pubkey,
+    grant_id: u64,
+) -> Instruction {
+    let grant = find_grant_address(recipient, grant_id).0;
+    build(
+        accounts::ApproveMilestone {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            lucid_mint: *lucid_mint,
+            recipient: *recipient,
+            grant,
+            grant_vault: find_grant_vault_address(&grant).0,
+            recipient_lucid_account: lucid_ata(recipient, lucid_mint),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ApproveMilestone {},
+    )
+}
+
+pub fn claw_back(
+    authority: &Pubkey,
+    lucid_mint: &Pubkey,
+    recipient: &Pubkey,
+    grant_id: u64,
+) -> Instruction {
+    let treasury = find_treasury_address().0;
+    let grant = find_grant_address(recipient, grant_id).0;
+    build(
+        accounts::ClawBack {
+            authority: *authority,
+            treasury,
+            lucid_mint: *lucid_mint,
+            grant,
+            grant_vault: find_grant_vault_address(&grant).0,
+            treasury_lucid_account: lucid_ata(&treasury, lucid_mint),
+            token_program: token_2022::ID,
+        },
+        instruction::ClawBack {},
+    )
+}
+
+pub fn initialize_leaderboard(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeLeaderboard {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            leaderboard: find_leaderboard_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeLeaderboard {},
+    )
+}
+
+pub fn start_season(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::StartSeason {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            leaderboard: find_leaderboard_address().0,
+        },
+        instruction::StartSeason {},
+    )
+}
+
+/// Permissionless; pushes `dreamer`'s season score onto the leaderboard.
+/// A `cranker` (who must sign) collects the crank bounty, if one is due.
+pub fn update_leaderboard(dreamer: &Pubkey, cranker: Option<&Pubkey>) -> Instruction {
+    build(
+        accounts::UpdateLeaderboard {
+            treasury: find_treasury_address().0,
+            season_score: find_season_score_address(dreamer).0,
+            leaderboard: find_leaderboard_address().0,
+            crank_pool: cranker.map(|_| find_crank_pool_address().0),
+            cranker: cranker.copied(),
+        },
+        instruction::UpdateLeaderboard {},
+    )
+}
+
+pub fn initialize_stats_ring(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeStatsRing {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            stats_ring: find_stats_ring_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeStatsRing {},
+    )
+}
+
+/// Permissionless; once per day.
+pub fn roll_stats() -> Instruction {
+    build(
+        accounts::RollStats {
+            treasury: find_treasury_address().0,
+            compressed_stake_vault: find_compressed_stake_vault_address().0,
+            stats_ring: find_stats_ring_address().0,
+        },
+        instruction::RollStats {},
+    )
+}
+
+pub fn set_crank_bounty(
+    authority: &Pubkey,
+    bounty_lamports: u64,
+    cooldown_secs: i64,
+) -> Instruction {
+    build(
+        accounts::SetCrankBounty {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            crank_pool: find_crank_pool_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetCrankBounty {
+            bounty_lamports,
+            cooldown_secs,
+        },
+    )
+}
+
+pub fn fund_crank_pool(funder: &Pubkey, lamports: u64) -> Instruction {
+    build(
+        accounts::FundCrankPool {
+            funder: *funder,
+            treasury: find_treasury_address().0,
+            crank_pool: find_crank_pool_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::FundCrankPool { lamports },
+    )
+}
+
+pub fn set_sponsor_pool(authority: &Pubkey, enabled: bool) -> Instruction {
+    build(
+        accounts::SetSponsorPool {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            sponsor_pool: find_sponsor_pool_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetSponsorPool { enabled },
+    )
+}
+
+pub fn fund_sponsor_pool(funder: &Pubkey, lamports: u64) -> Instruction {
+    build(
+        accounts::FundSponsorPool {
+            funder: *funder,
+            treasury: find_treasury_address().0,
+            sponsor_pool: find_sponsor_pool_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::FundSponsorPool { lamports },
+    )
+}
+
+/// Put this right before the dreamer's first `record_dream`, in the same
+/// transaction, with `payer` as the fee payer. `unix_timestamp` selects the
+/// day's `EpochStats` and `SleepSession` like it does for `record_dream`.
+pub fn claim_sponsorship(
+    payer: &Pubkey,
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    unix_timestamp: i64,
+) -> Instruction {
+    let day = EpochStats::day_of(unix_timestamp);
+    build(
+        accounts::ClaimSponsorship {
+            payer: *payer,
+            dreamer: *dreamer,
+            sponsor_pool: find_sponsor_pool_address().0,
+            sponsorship: find_sponsorship_address(dreamer).0,
+            treasury: find_treasury_address().0,
+            dreamer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            epoch_stats: find_epoch_stats_address(day).0,
+            sleep_session: find_sleep_session_address(dreamer, day).0,
+            fee_vault: find_fee_vault_address().0,
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimSponsorship {},
+    )
+}
+
+/// Fees are in lamports, at most `MAX_PROTOCOL_FEE_LAMPORTS` each.
+pub fn set_protocol_fees(
+    authority: &Pubkey,
+    record_dream_fee: u64,
+    mint_oneirobot_fee: u64,
+) -> Instruction {
+    build(
+        accounts::SetProtocolFees {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            fee_vault: find_fee_vault_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetProtocolFees {
+            record_dream_fee,
+            mint_oneirobot_fee,
+        },
+    )
+}
+
+pub fn sweep_fees(authority: &Pubkey, recipient: &Pubkey) -> Instruction {
+    build(
+        accounts::SweepFees {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            fee_vault: find_fee_vault_address().0,
+            insurance_fund: find_insurance_fund_address().0,
+            insurance_vault: find_insurance_vault_address().0,
+            treasury_health: find_treasury_health_address().0,
+            recipient: *recipient,
+        },
+        instruction::SweepFees {},
+    )
+}
+
+/// Permissionless; `payer` covers the health account's rent on first use.
+pub fn reconcile_treasury(payer: &Pubkey) -> Instruction {
+    build(
+        accounts::ReconcileTreasury {
+            payer: *payer,
+            treasury_health: find_treasury_health_address().0,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            reward_stream_vault: find_reward_stream_vault_address().0,
+            insurance_fund: find_insurance_fund_address().0,
+            insurance_vault: find_insurance_vault_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::ReconcileTreasury {},
+    )
+}
+
+pub fn set_insurance_fee_share(
+    authority: &Pubkey,
+    dream_mint: &Pubkey,
+    fee_share_bps: u16,
+) -> Instruction {
+    build(
+        accounts::SetInsuranceFeeShare {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            insurance_fund: find_insurance_fund_address().0,
+            insurance_vault: find_insurance_vault_address().0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::SetInsuranceFeeShare { fee_share_bps },
+    )
+}
+
+/// `amount` is lamports for the crank pool, DREAM base units for the reward
+/// pool.
+pub fn cover_shortfall(
+    authority: &Pubkey,
+    dream_mint: &Pubkey,
+    target: ShortfallTarget,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::CoverShortfall {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            insurance_fund: find_insurance_fund_address().0,
+            insurance_vault: find_insurance_vault_address().0,
+            crank_pool: (target == ShortfallTarget::CrankPool)
+                .then(|| find_crank_pool_address().0),
+            reward_pool_vault: (target == ShortfallTarget::RewardPool)
+                .then(|| find_reward_pool_vault_address().0),
+            token_program: token_2022::ID,
+        },
+        instruction::CoverShortfall { target, amount },
+    )
+}
+
+pub fn initialize_validation_queue(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeValidationQueue {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            validation_queue: find_validation_queue_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeValidationQueue {},
+    )
+}
+
+/// Authority only; returns the popped dream record's address.
+pub fn pop_validation(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::PopValidation {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            validation_queue: find_validation_queue_address().0,
+        },
+        instruction::PopValidation {},
+    )
+}
+
+/// `score` is 1 to `MAX_VALIDATION_SCORE`.
+pub fn set_pinning_oracle(authority: &Pubkey, oracle: &Pubkey, approved: bool) -> Instruction {
+    build(
+        accounts::SetPinningOracle {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            oracle: *oracle,
+            pinning_oracle: find_pinning_oracle_address(oracle).0,
+            system_program: system_program::ID,
+        },
+        instruction::SetPinningOracle { approved },
+    )
+}
+
+/// Authority only; `threshold` approved oracles must then sign each pin
+/// confirmation and classification.
+pub fn set_oracle_quorum(authority: &Pubkey, threshold: u8) -> Instruction {
+    build(
+        accounts::SetOracleQuorum {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            oracle_quorum: find_oracle_quorum_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetOracleQuorum { threshold },
+    )
+}
+
+/// Must directly follow [`ed25519_verify`] of the oracle's signature over
+/// `dream_mind_state::cid::pin_attestation_message`, or
+/// [`ed25519_verify_quorum`] under an oracle quorum.
+pub fn confirm_pinned(payer: &Pubkey, oracle: &Pubkey, dream_record: &Pubkey) -> Instruction {
+    build(
+        accounts::ConfirmPinned {
+            payer: *payer,
+            pinning_oracle: find_pinning_oracle_address(oracle).0,
+            dream_record: *dream_record,
+            pin_confirmation: find_pin_confirmation_address(dream_record).0,
+            oracle_quorum: find_oracle_quorum_address().0,
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ConfirmPinned {},
+    )
+}
+
+/// Must directly follow [`ed25519_verify`] of the oracle's signature over
+/// `dream_mind_state::cid::classification_attestation_message`, or
+/// [`ed25519_verify_quorum`] under an oracle quorum.
+pub fn classify_dream(
+    payer: &Pubkey,
+    oracle: &Pubkey,
+    dream_record: &Pubkey,
+    language: [u8; 2],
+    nsfw: bool,
+    topics: u64,
+) -> Instruction {
+    build(
+        accounts::ClassifyDream {
+            payer: *payer,
+            pinning_oracle: find_pinning_oracle_address(oracle).0,
+            dream_record: *dream_record,
+            dream_classification: find_dream_classification_address(dream_record).0,
+            oracle_quorum: find_oracle_quorum_address().0,
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClassifyDream {
+            language,
+            nsfw,
+            topics,
+        },
+    )
+}
+
+/// Ed25519 precompile instruction verifying one signature, with the public
+/// key, signature and message all carried in its own data.
+pub fn ed25519_verify(public_key: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    ed25519_verify_quorum(&[(*public_key, *signature)], message)
+}
+
+/// Ed25519 precompile instruction verifying every oracle's signature over
+/// one shared `message`, as an oracle quorum requires.
+pub fn ed25519_verify_quorum(signatures: &[(Pubkey, [u8; 64])], message: &[u8]) -> Instruction {
+    // num_signatures, padding, then 7 u16 offsets per signature
+    const THIS_INSTRUCTION: u16 = u16::MAX;
+    let header_len = 2 + signatures.len() * 7 * 2;
+    let message_offset = (header_len + signatures.len() * (32 + 64)) as u16;
+
+    let mut data = vec![signatures.len() as u8, 0];
+    for index in 0..signatures.len() {
+        let public_key_offset = (header_len + index * (32 + 64)) as u16;
+        let offsets = [
+            public_key_offset + 32,
+            THIS_INSTRUCTION,
+            public_key_offset,
+            THIS_INSTRUCTION,
+            message_offset,
+            message.len() as u16,
+            THIS_INSTRUCTION,
+        ];
+        data.extend(offsets.iter().flat_map(|offset| offset.to_le_bytes()));
+    }
+    for (public_key, signature) in signatures {
+        data.extend_from_slice(public_key.as_ref());
+        data.extend_from_slice(signature);
+    }
+    data.extend_from_slice(message);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Pass the `co_signers` of an oracle quorum to `confirm_pinned` or
+/// `classify_dream`; the lead oracle is already in `ix`.
+pub fn with_oracle_co_signers(mut ix: Instruction, co_signers: &[Pubkey]) -> Instruction {
+    ix.accounts.extend(
+        co_signers
+            .iter()
+            .map(|oracle| AccountMeta::new(find_pinning_oracle_address(oracle).0, false)),
+    );
+    ix
+}
+
+/// `validator` attributes the verdict to a registered validator, counts it
+/// in their stats and credits them the validator reward; `None` records it
+/// as the authority's own.
+pub fn validate_dream(
+    authority: &Pubkey,
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_record: &Pubkey,
+    validator: Option<&Pubkey>,
+    score: u8,
+) -> Instruction {
+    validation(authority, dreamer, dream_mint, dream_record, validator, score, None)
+}
+
+/// Like [`validate_dream`], for a dream whose `interpreter` attached an
+/// analysis; their DREAM account receives their share of the rewards.
+pub fn validate_interpreted_dream(
+    authority: &Pubkey,
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_record: &Pubkey,
+    validator: Option<&Pubkey>,
+    score: u8,
+    interpreter: &Pubkey,
+) -> Instruction {
+    validation(authority, dreamer, dream_mint, dream_record, validator, score, Some(interpreter))
+}
+
+fn validation(
+    authority: &Pubkey,
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_record: &Pubkey,
+    validator: Option<&Pubkey>,
+    score: u8,
+    interpreter: Option<&Pubkey>,
+) -> Instruction {
+    build(
+        accounts::ValidateDream {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_record: *dream_record,
+            dreamer: *dreamer,
+            dream_mint: *dream_mint,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            dreamer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            pin_confirmation: find_pin_confirmation_address(dream_record).0,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            dream_validation: find_dream_validation_address(dream_record).0,
+            validator_registration: validator
+                .map(|validator| find_validator_registration_address(validator).0),
+            validator_reward_pool: validator.map(|_| find_validator_reward_pool_address().0),
+            validator_reward_vault: validator.map(|_| find_validator_reward_vault_address().0),
+            validator_rewards: validator
+                .map(|validator| find_validator_rewards_address(validator).0),
+            validator_stats: validator.map(|validator| find_validator_stats_address(validator).0),
+            interpretation: find_dream_interpretation_address(dream_record).0,
+            interpreter_dream_account: interpreter.map(|interpreter| {
+                associated_token::get_associated_token_address_with_program_id(
+                    interpreter,
+                    dream_mint,
+                    &token_2022::ID,
+                )
+            }),
+            treasury_health: find_treasury_health_address().0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ValidateDream { score },
+    )
+}
+
+/// `validator` is the one the disputed verdict is attributed to.
+pub fn resolve_validation_dispute(
+    authority: &Pubkey,
+    dream_record: &Pubkey,
+    validator: &Pubkey,
+    consensus_score: u8,
+) -> Instruction {
+    build(
+        accounts::ResolveValidationDispute {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_validation: find_dream_validation_address(dream_record).0,
+            validation_dispute: find_validation_dispute_address(dream_record).0,
+            validator_stats: find_validator_stats_address(validator).0,
+            system_program: system_program::ID,
+        },
+        instruction::ResolveValidationDispute { consensus_score },
+    )
+}
+
+/// `oracle` must be an approved pinning oracle; it signs and pays the bond.
+pub fn post_optimistic_score(oracle: &Pubkey, dream_record: &Pubkey, score: u8) -> Instruction {
+    build(
+        accounts::PostOptimisticScore {
+            oracle: *oracle,
+            treasury: find_treasury_address().0,
+            pinning_oracle: find_pinning_oracle_address(oracle).0,
+            dream_record: *dream_record,
+            pin_confirmation: find_pin_confirmation_address(dream_record).0,
+            dream_validation: find_dream_validation_address(dream_record).0,
+            optimistic_score: find_optimistic_score_address(dream_record).0,
+            system_program: system_program::ID,
+        },
+        instruction::PostOptimisticScore { score },
+    )
+}
+
+/// Void the score of `dream_record` because `original_record` recorded the
+/// same content earlier.
+pub fn challenge_duplicate_content(
+    challenger: &Pubkey,
+    dream_record: &Pubkey,
+    original_record: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ChallengeOptimisticScore {
+            challenger: *challenger,
+            treasury: find_treasury_address().0,
+            optimistic_score: find_optimistic_score_address(dream_record).0,
+            dream_record: *dream_record,
+            original_record: Some(*original_record),
+            witness_oracle: None,
+            instructions_sysvar: None,
+        },
+        instruction::ChallengeOptimisticScore {
+            proof: FraudProof::DuplicateContent,
+        },
+    )
+}
+
+/// Void the score of `dream_record` on `witness_oracle`'s word that its CID
+/// is dead. Must directly follow [`ed25519_verify`] of the witness's
+/// signature over `dream_mind_state::cid::dead_cid_attestation_message`.
+pub fn challenge_dead_cid(
+    challenger: &Pubkey,
+    dream_record: &Pubkey,
+    witness_oracle: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ChallengeOptimisticScore {
+            challenger: *challenger,
+            treasury: find_treasury_address().0,
+            optimistic_score: find_optimistic_score_address(dream_record).0,
+            dream_record: *dream_record,
+            original_record: None,
+            witness_oracle: Some(find_pinning_oracle_address(witness_oracle).0),
+            instructions_sysvar: Some(sysvar::instructions::ID),
+        },
+        instruction::ChallengeOptimisticScore {
+            proof: FraudProof::DeadCid,
+        },
+    )
+}
+
+/// `oracle` is the one that posted the score; `payer` pays the validation's rent.
+/// Pass the dream's `interpreter` once they have attached an analysis; their
+/// DREAM account receives the share held back for them.
+pub fn finalize_optimistic_score(
+    payer: &Pubkey,
+    oracle: &Pubkey,
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_record: &Pubkey,
+    interpreter: Option<&Pubkey>,
+) -> Instruction {
+    build(
+        accounts::FinalizeOptimisticScore {
+            payer: *payer,
+            treasury: find_treasury_address().0,
+            oracle: *oracle,
+            optimistic_score: find_optimistic_score_address(dream_record).0,
+            dream_record: *dream_record,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            dream_validation: find_dream_validation_address(dream_record).0,
+            interpretation: find_dream_interpretation_address(dream_record).0,
+            dreamer: *dreamer,
+            dream_mint: *dream_mint,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            dreamer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            interpreter_dream_account: interpreter.map(|interpreter| {
+                associated_token::get_associated_token_address_with_program_id(
+                    interpreter,
+                    dream_mint,
+                    &token_2022::ID,
+                )
+            }),
+            treasury_health: find_treasury_health_address().0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::FinalizeOptimisticScore {},
+    )
+}
+
+pub fn expire_interpretation(
+    dreamer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_record: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ExpireInterpretation {
+            treasury: find_treasury_address().0,
+            dream_record: *dream_record,
+            interpretation: find_dream_interpretation_address(dream_record).0,
+            dreamer: *dreamer,
+            dream_validation: find_dream_validation_address(dream_record).0,
+            dream_mint: *dream_mint,
+            reward_pool_vault: find_reward_pool_vault_address().0,
+            dreamer_dream_account: associated_token::get_associated_token_address_with_program_id(
+                dreamer,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            treasury_health: find_treasury_health_address().0,
+            token_program: token_2022::ID,
+        },
+        instruction::ExpireInterpretation {},
+    )
+}
+
+/// An Oneirobot and the token account holding it, for the validator gate or
+/// as a companion.
+#[derive(Clone, Debug)]
+pub struct OneirobotHolding {
+    /// The OneirobotNFT program named in the `ValidatorGate`, or in the
+    /// `SpliceConfig` for a companion.
+    pub nft_program: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+}
+
+pub fn set_validator_gate(
+    authority: &Pubkey,
+    enabled: bool,
+    min_dream_level: u8,
+    nft_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::SetValidatorGate {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            validator_gate: find_validator_gate_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetValidatorGate {
+            enabled,
+            min_dream_level,
+            nft_program: *nft_program,
+        },
+    )
+}
+
+/// `oneirobot` is required while the validator gate is on.
+pub fn register_validator(validator: &Pubkey, oneirobot: Option<&OneirobotHolding>) -> Instruction {
+    build(
+        accounts::RegisterValidator {
+            validator: *validator,
+            validator_gate: find_validator_gate_address().0,
+            validator_registration: find_validator_registration_address(validator).0,
+            nft_token_account: oneirobot.map(|holding| holding.token_account),
+            nft_attributes: oneirobot
+                .map(|holding| OneirobotAttributes::address(&holding.nft_program, &holding.mint)),
+            system_program: system_program::ID,
+        },
+        instruction::RegisterValidator {},
+    )
+}
+
+/// `oneirobot` names the validator's registered Oneirobot and the token
+/// account that holds it now; omit it for validators registered without one.
+pub fn challenge_validator(
+    challenger: &Pubkey,
+    validator: &Pubkey,
+    oneirobot: Option<&OneirobotHolding>,
+) -> Instruction {
+    build(
+        accounts::ChallengeValidator {
+            challenger: *challenger,
+            validator_gate: find_validator_gate_address().0,
+            validator_registration: find_validator_registration_address(validator).0,
+            nft_token_account: oneirobot.map(|holding| holding.token_account),
+            nft_attributes: oneirobot
+                .map(|holding| OneirobotAttributes::address(&holding.nft_program, &holding.mint)),
+        },
+        instruction::ChallengeValidator {},
+    )
+}
+
+pub fn set_validator_rewards(
+    authority: &Pubkey,
+    dream_mint: &Pubkey,
+    fee_share_bps: u16,
+    reward_per_validation: u64,
+) -> Instruction {
+    build(
+        accounts::SetValidatorRewards {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            validator_reward_pool: find_validator_reward_pool_address().0,
+            validator_reward_vault: find_validator_reward_vault_address().0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::SetValidatorRewards {
+            fee_share_bps,
+            reward_per_validation,
+        },
+    )
+}
+
+pub fn set_validator_claim_cap(authority: &Pubkey, epoch_claim_cap: u64) -> Instruction {
+    build(
+        accounts::SetValidatorClaimCap {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            validator_reward_pool: find_validator_reward_pool_address().0,
+        },
+        instruction::SetValidatorClaimCap { epoch_claim_cap },
+    )
+}
+
+pub fn claim_validator_rewards(validator: &Pubkey, dream_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimValidatorRewards {
+            validator: *validator,
+            payer: *validator,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            validator_rewards: find_validator_rewards_address(validator).0,
+            validator_reward_pool: find_validator_reward_pool_address().0,
+            validator_reward_vault: find_validator_reward_vault_address().0,
+            validator_dream_account: associated_token::get_associated_token_address_with_program_id(
+                validator,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimValidatorRewards {},
+    )
+}
+
+pub fn set_splice_config(authority: &Pubkey, nft_program: &Pubkey) -> Instruction {
+    build(
+        accounts::SetSpliceConfig {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            splice_config: find_splice_config_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetSpliceConfig {
+            nft_program: *nft_program,
+        },
+    )
+}
+
+/// Splice the dreamer's dreams `dream_ids`, which must be validated and
+/// number `SPLICE_DREAM_COUNT`, into a new Oneirobot `oneirobot_mint`.
+/// `nft_program` is the one named in the `SpliceConfig`.
+pub fn splice_from_dreams(
+    dreamer: &Pubkey,
+    nft_program: &Pubkey,
+    oneirobot_mint: &Pubkey,
+    dream_ids: &[u64],
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+) -> Instruction {
+    let dreams: Vec<AccountMeta> = dream_ids
+        .iter()
+        .flat_map(|&id| {
+            let record = find_dream_record_address(dreamer, id).0;
+            [
+                AccountMeta::new_readonly(record, false),
+                AccountMeta::new_readonly(find_dream_validation_address(&record).0, false),
+                AccountMeta::new(find_spliced_dream_address(&record).0, false),
+            ]
+        })
+        .collect();
+    let mut ix = build(
+        accounts::SpliceFromDreams {
+            dreamer: *dreamer,
+            treasury: find_treasury_address().0,
+            splice_config: find_splice_config_address().0,
+            splicer: find_splicer_address().0,
+            nft_program: *nft_program,
+            oneirobot_state: oneirobot::state_address(nft_program),
+            nft_attributes: OneirobotAttributes::address(nft_program, oneirobot_mint),
+            oneirobot_mint: *oneirobot_mint,
+            token_account: associated_token::get_associated_token_address(dreamer, oneirobot_mint),
+            metadata: oneirobot::metadata_address(oneirobot_mint),
+            master_edition: oneirobot::master_edition_address(oneirobot_mint),
+            fee_vault: find_fee_vault_address().0,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            metadata_program: oneirobot::TOKEN_METADATA_PROGRAM_ID,
+        },
+        instruction::SpliceFromDreams {
+            metadata_uri,
+            name,
+            symbol,
+        },
+    );
+    ix.accounts.extend(dreams);
+    ix
+}
+
+/// Mint the certificate Oneirobot `oneirobot_mint` for the dreamer's dream
+/// `dream_id`, which must be scored at least `CERTIFICATE_MIN_SCORE`.
+/// `nft_program` is the one named in the `SpliceConfig`.
+pub fn claim_dream_certificate(
+    dreamer: &Pubkey,
+    nft_program: &Pubkey,
+    oneirobot_mint: &Pubkey,
+    dream_id: u64,
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+) -> Instruction {
+    let dream_record = find_dream_record_address(dreamer, dream_id).0;
+    build(
+        accounts::ClaimDreamCertificate {
+            dreamer: *dreamer,
+            treasury: find_treasury_address().0,
+            dream_record,
+            dream_validation: find_dream_validation_address(&dream_record).0,
+            certificate: find_dream_certificate_address(&dream_record).0,
+            splice_config: find_splice_config_address().0,
+            splicer: find_splicer_address().0,
+            nft_program: *nft_program,
+            oneirobot_state: oneirobot::state_address(nft_program),
+            nft_attributes: OneirobotAttributes::address(nft_program, oneirobot_mint),
+            oneirobot_mint: *oneirobot_mint,
+            token_account: associated_token::get_associated_token_address(dreamer, oneirobot_mint),
+            metadata: oneirobot::metadata_address(oneirobot_mint),
+            master_edition: oneirobot::master_edition_address(oneirobot_mint),
+            fee_vault: find_fee_vault_address().0,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            metadata_program: oneirobot::TOKEN_METADATA_PROGRAM_ID,
+        },
+        instruction::ClaimDreamCertificate {
+            metadata_uri,
+            name,
+            symbol,
+        },
+    )
+}
+
+/// `oneirobot.nft_program` is the one named in the `SpliceConfig`.
+pub fn equip_oneirobot_to_profile(dreamer: &Pubkey, oneirobot: &OneirobotHolding) -> Instruction {
+    build(
+        accounts::EquipOneirobotToProfile {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            splice_config: find_splice_config_address().0,
+            nft_token_account: oneirobot.token_account,
+            nft_attributes: OneirobotAttributes::address(&oneirobot.nft_program, &oneirobot.mint),
+            companion: find_companion_address(dreamer).0,
+            companion_xp: find_companion_xp_address(&oneirobot.mint).0,
+            system_program: system_program::ID,
+        },
+        instruction::EquipOneirobotToProfile {},
+    )
+}
+
+pub fn flag_dream(
+    authority: &Pubkey,
+    dreamer: &Pubkey,
+    dream_record: &Pubkey,
+    reason: DreamFlagReason,
+) -> Instruction {
+    build(
+        accounts::FlagDream {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_record: *dream_record,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            dream_flag: find_dream_flag_address(dream_record).0,
+            system_program: system_program::ID,
+        },
+        instruction::FlagDream { reason },
+    )
+}
+
+/// `unix_timestamp` selects the day's session, as in `record_dream`.
+pub fn open_session(
+    dreamer: &Pubkey,
+    device_attestation: [u8; 32],
+    unix_timestamp: i64,
+) -> Instruction {
+    let day = EpochStats::day_of(unix_timestamp);
+    build(
+        accounts::OpenSession {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            sleep_session: find_sleep_session_address(dreamer, day).0,
+            system_program: system_program::ID,
+        },
+        instruction::OpenSession { device_attestation },
+    )
+}
+
+/// `day` is `EpochStats::day_of` the session's day. Closing a past day's
+/// session returns its rent to the dreamer.
+pub fn close_session(dreamer: &Pubkey, day: u32) -> Instruction {
+    build(
+        accounts::CloseSession {
+            dreamer: *dreamer,
+            sleep_session: find_sleep_session_address(dreamer, day).0,
+        },
+        instruction::CloseSession { day },
+    )
+}
+
+/// `interface_index` must equal the dreamer profile's current `interface_count`
+/// (0 for a dreamer without a profile).
+pub fn interface_dream(dreamer: &Pubkey, interface_index: u64, ipfs_hash: String) -> Instruction {
+    build(
+        accounts::InterfaceDream {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            dream_interface: find_dream_interface_address(dreamer, interface_index).0,
+            system_program: system_program::ID,
+        },
+        instruction::InterfaceDream { ipfs_hash },
+    )
+}
+
+pub fn update_interface(dreamer: &Pubkey, interface_index: u64, ipfs_hash: String) -> Instruction {
+    build(
+        accounts::UpdateInterface {
+            dreamer: *dreamer,
+            dream_interface: find_dream_interface_address(dreamer, interface_index).0,
+        },
+        instruction::UpdateInterface {
+            interface_index,
+            ipfs_hash,
+        },
+    )
+}
+
+pub fn close_interface(dreamer: &Pubkey, interface_index: u64) -> Instruction {
+    build(
+        accounts::CloseInterface {
+            dreamer: *dreamer,
+            dream_interface: find_dream_interface_address(dreamer, interface_index).0,
+        },
+        instruction::CloseInterface { interface_index },
+    )
+}
+
+pub fn stake_for_lucid_access(
+    user: &Pubkey,
+    stake_mint: &Pubkey,
+    user_token_account: &Pubkey,
+    stake_vault: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::StakeLucid {
+            user: *user,
+            payer: *user,
+            treasury: find_treasury_address().0,
+            stake_mint: *stake_mint,
+            stake_account: find_stake_address(user, stake_mint).0,
+            user_token_account: *user_token_account,
+            stake_vault: *stake_vault,
+            token_program: token_2022::ID,
+            system_program: system_program::ID,
+        },
+        instruction::StakeForLucidAccess { amount },
+    )
+}
+
+/// Move a stake from its pre-mint address to the LUCID stake address.
+pub fn migrate_stake(user: &Pubkey, lucid_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateStake {
+            user: *user,
+            payer: *user,
+            treasury: find_treasury_address().0,
+            legacy_stake: find_legacy_stake_address(user).0,
+            stake_account: find_stake_address(user, lucid_mint).0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateStake {},
+    )
+}
+
+pub fn create_tier_credential_mint(authority: &Pubkey, access_level: u8) -> Instruction {
+    build(
+        accounts::CreateTierCredentialMint {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            credential_mint: find_tier_credential_mint_address(access_level).0,
+            token_program: token_2022::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateTierCredentialMint { access_level },
+    )
+}
+
+/// Bring `user`'s VIP (3) and Quantum (4) credentials in line with their
+/// LUCID stake; `payer` covers the credential accounts' rent the first time.
+pub fn sync_tier_credentials(payer: &Pubkey, user: &Pubkey, lucid_mint: &Pubkey) -> Instruction {
+    let credential = |access_level| {
+        let mint = find_tier_credential_mint_address(access_level).0;
+        let account = associated_token::get_associated_token_address_with_program_id(
+            user,
+            &mint,
+            &token_2022::ID,
+        );
+        (mint, account)
+    };
+    let (vip_credential_mint, vip_credential_account) = credential(3);
+    let (quantum_credential_mint, quantum_credential_account) = credential(4);
+    build(
+        accounts::SyncTierCredentials {
+            payer: *payer,
+            user: *user,
+            treasury: find_treasury_address().0,
+            lucid_stake: find_stake_address(user, lucid_mint).0,
+            vip_credential_mint,
+            vip_credential_account,
+            quantum_credential_mint,
+            quantum_credential_account,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::SyncTierCredentials {},
+    )
+}
+
+fn smind_ata(owner: &Pubkey, smind_mint: &Pubkey) -> Pubkey {
+    associated_token::get_associated_token_address_with_program_id(owner, smind_mint, &token_2022::ID)
+}
+
+pub fn initialize_compressed_stake_tree(authority: &Pubkey, smind_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeCompressedStakeTree {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            smind_mint: *smind_mint,
+            compressed_stake_tree: find_compressed_stake_tree_address().0,
+            compressed_stake_vault: find_compressed_stake_vault_address().0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::InitializeCompressedStakeTree {},
+    )
+}
+
+/// Stakes from the staker's SMIND ATA. The leaf index is in `CompressedStakedEvent`.
+pub fn compressed_stake(staker: &Pubkey, smind_mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::CompressedStake {
+            staker: *staker,
+            treasury: find_treasury_address().0,
+            smind_mint: *smind_mint,
+            compressed_stake_tree: find_compressed_stake_tree_address().0,
+            compressed_stake_vault: find_compressed_stake_vault_address().0,
+            staker_smind_account: smind_ata(staker, smind_mint),
+            token_program: token_2022::ID,
+        },
+        instruction::CompressedStake { amount },
+    )
+}
+
+/// Build `proof` with `dream_mind_lucid::stake_tree::proof` over every leaf
+/// appended so far, with unstaked leaves set to `EMPTY_LEAF`.
+pub fn compressed_unstake(
+    staker: &Pubkey,
+    smind_mint: &Pubkey,
+    leaf_index: u64,
+    amount: u64,
+    staked_at: i64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::CompressedUnstake {
+            staker: *staker,
+            treasury: find_treasury_address().0,
+            smind_mint: *smind_mint,
+            compressed_stake_tree: find_compressed_stake_tree_address().0,
+            compressed_stake_vault: find_compressed_stake_vault_address().0,
+            staker_smind_account: smind_ata(staker, smind_mint),
+            token_program: token_2022::ID,
+        },
+        instruction::CompressedUnstake {
+            leaf_index,
+            amount,
+            staked_at,
+            proof,
+        },
+    )
+}
+
+pub fn initialize_dream_archive_tree(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeDreamArchiveTree {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            dream_archive_tree: find_dream_archive_tree_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeDreamArchiveTree {},
+    )
+}
+
+/// The leaf index and the record's data are in `DreamArchivedEvent`.
+pub fn archive_dream(dreamer: &Pubkey, dream_id: u64) -> Instruction {
+    let dream_record = find_dream_record_address(dreamer, dream_id).0;
+    build(
+        accounts::ArchiveDream {
+            dreamer: *dreamer,
+            dream_record,
+            dream_validation: find_dream_validation_address(&dream_record).0,
+            dream_archive_tree: find_dream_archive_tree_address().0,
+            archived_dream: find_archived_dream_address(&dream_record).0,
+            system_program: system_program::ID,
+        },
+        instruction::ArchiveDream {},
+    )
+}
+
+pub fn set_mev_protection(authority: &Pubkey, enabled: bool) -> Instruction {
+    build(
+        accounts::SetMevProtection {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+        },
+        instruction::SetMevProtection { enabled },
+    )
+}
+
+pub fn store_treasury_bump() -> Instruction {
+    build(
+        accounts::StoreTreasuryBump {
+            treasury: find_treasury_address().0,
+        },
+        instruction::StoreTreasuryBump {},
+    )
+}
+
+pub fn migrate_legacy_storage(dreamer: &Pubkey, legacy_storage: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateLegacyStorage {
+            dreamer: *dreamer,
+            payer: *dreamer,
+            legacy_storage: *legacy_storage,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateLegacyStorage {},
+    )
+}
+
+/// Permissionless; `payer` covers the profile's extra rent.
+pub fn migrate_dreamer_profile(payer: &Pubkey, dreamer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateDreamerProfile {
+            payer: *payer,
+            dreamer: *dreamer,
+            dreamer_profile: find_dreamer_profile_address(dreamer).0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateDreamerProfile {},
+    )
+}
+
+/// Permissionless; `payer` covers the record's extra rent.
+pub fn migrate_dream_record(payer: &Pubkey, dream_record: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateDreamRecord {
+            payer: *payer,
+            dream_record: *dream_record,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateDreamRecord {},
+    )
+}
+
+pub fn register_foreign_emitter(authority: &Pubkey, chain: u16, address: [u8; 32]) -> Instruction {
+    build(
+        accounts::RegisterForeignEmitter {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            foreign_emitter: find_foreign_emitter_address(chain).0,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterForeignEmitter { chain, address },
+    )
+}
+
+/// `message` is a fresh keypair that must also sign the transaction.
+pub fn attest_dream_mint(
+    payer: &Pubkey,
+    dream_mint: &Pubkey,
+    message: &Pubkey,
+    nonce: u32,
+) -> Instruction {
+    build(
+        accounts::AttestDreamMint {
+            payer: *payer,
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+            wormhole: wormhole::post_message_accounts(message),
+        },
+        instruction::AttestDreamMint { nonce },
+    )
+}
+
+/// `message` is a fresh keypair that must also sign the transaction.
+pub fn bridge_dream_out(
+    sender: &Pubkey,
+    dream_mint: &Pubkey,
+    message: &Pubkey,
+    amount: u64,
+    recipient_chain: u16,
+    recipient: [u8; 32],
+    nonce: u32,
+) -> Instruction {
+    build(
+        accounts::BridgeDreamOut {
+            sender: *sender,
+            payer: *sender,
+            treasury: find_treasury_address().0,
+            foreign_emitter: find_foreign_emitter_address(recipient_chain).0,
+            dream_mint: *dream_mint,
+            sender_dream_account: associated_token::get_associated_token_address_with_program_id(
+                sender,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            dream_custody: find_dream_custody_address().0,
+            wormhole: wormhole::post_message_accounts(message),
+            token_program: token_2022::ID,
+            system_program: system_program::ID,
+        },
+        instruction::BridgeDreamOut {
+            amount,
+            recipient_chain,
+            recipient,
+            nonce,
+        },
+    )
+}
+
+/// `posted_vaa` is the core bridge account for the guardian-signed transfer;
+/// see [`wormhole::parse_signed_vaa`].
+pub fn complete_bridge_in(
+    payer: &Pubkey,
+    recipient: &Pubkey,
+    dream_mint: &Pubkey,
+    posted_vaa: &Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Instruction {
+    build(
+        accounts::CompleteBridgeIn {
+            payer: *payer,
+            treasury: find_treasury_address().0,
+            posted_vaa: *posted_vaa,
+            foreign_emitter: find_foreign_emitter_address(emitter_chain).0,
+            receipt: find_bridge_receipt_address(emitter_chain, sequence).0,
+            recipient: *recipient,
+            recipient_dream_account: associated_token::get_associated_token_address_with_program_id(
+                recipient,
+                dream_mint,
+                &token_2022::ID,
+            ),
+            dream_custody: find_dream_custody_address().0,
+            dream_mint: *dream_mint,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CompleteBridgeIn {
+            emitter_chain,
+            sequence,
+        },
+    )
+}
+
+/// `dream_id` must equal the treasury's current `total_dreams_recorded`.
+pub fn record_mirrored_dream(
+    payer: &Pubkey,
+    dreamer: &Pubkey,
+    dream_id: u64,
+    posted_vaa: &Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Instruction {
+    build(
+        accounts::RecordMirroredDream {
+            payer: *payer,
+            dreamer: *dreamer,
+            treasury: find_treasury_address().0,
+            dream_record: find_dream_record_address(dreamer, dream_id).0,
+            posted_vaa: *posted_vaa,
+            foreign_emitter: find_foreign_emitter_address(emitter_chain).0,
+            receipt: find_bridge_receipt_address(emitter_chain, sequence).0,
+            system_program: system_program::ID,
+        },
+        instruction::RecordMirroredDream {
+            emitter_chain,
+            sequence,
+        },
+    )
+}
+
+pub fn configure_message_adapter(authority: &Pubkey, delivery_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::ConfigureMessageAdapter {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            message_adapter: find_message_adapter_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::ConfigureMessageAdapter {
+            delivery_authority: *delivery_authority,
+        },
+    )
+}
+
+pub fn set_trusted_remote(authority: &Pubkey, origin: u32, sender: [u8; 32]) -> Instruction {
+    build(
+        accounts::SetTrustedRemote {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            trusted_remote: find_trusted_remote_address(origin).0,
+            system_program: system_program::ID,
+        },
+        instruction::SetTrustedRemote { origin, sender },
+    )
+}
+
+/// `message` is `borsh(GovernanceAction)`. The `UpgradeGovernance` account is
+/// attached when the message approves making the program immutable.
+pub fn receive_message(
+    delivery_authority: &Pubkey,
+    origin: u32,
+    sender: [u8; 32],
+    nonce: u64,
+    message: Vec<u8>,
+) -> Instruction {
+    build(
+        accounts::ReceiveMessage {
+            delivery_authority: *delivery_authority,
+            message_adapter: find_message_adapter_address().0,
+            trusted_remote: find_trusted_remote_address(origin).0,
+            treasury: find_treasury_address().0,
+            upgrade_governance: matches!(
+                GovernanceAction::decode(&message),
+                Some(GovernanceAction::ApproveFinalizeImmutable)
+            )
+            .then(|| find_upgrade_governance_address().0),
+        },
+        instruction::ReceiveMessage {
+            origin,
+            sender,
+            nonce,
+            message,
+        },
+    )
+}
+
+/// `lookup_table` is [`find_lookup_table_address`] for `recent_slot` on the
+/// first call, and the table in `LookupTableConfig` afterwards.
+pub fn sync_lookup_table(authority: &Pubkey, lookup_table: &Pubkey, recent_slot: u64) -> Instruction {
+    build(
+        accounts::SyncLookupTable {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            lookup_table_config: find_lookup_table_config_address().0,
+            lookup_table: *lookup_table,
+            address_lookup_table_program: address_lookup_table::program::ID,
+            system_program: system_program::ID,
+        },
+        instruction::SyncLookupTable { recent_slot },
+    )
+}
+
+pub fn upgrade_program_info(
+    authority: &Pubkey,
+    version: String,
+    git_commit: String,
+    audit_uri: String,
+) -> Instruction {
+    build(
+        accounts::UpgradeProgramInfo {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            program_info: find_program_info_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::UpgradeProgramInfo {
+            version,
+            git_commit,
+            audit_uri,
+        },
+    )
+}
+
+/// Signed by the current upgrade authority (usually the deployer key).
+/// `program_hash` is the output of `solana-verify get-executable-hash` on the
+/// verifiable build that was deployed.
+pub fn set_build_info(
+    authority: &Pubkey,
+    program_hash: [u8; 32],
+    git_commit: String,
+) -> Instruction {
+    build(
+        accounts::SetBuildInfo {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            program: ID,
+            program_data: find_program_data_address().0,
+            build_info: find_build_info_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetBuildInfo {
+            program_hash,
+            git_commit,
+        },
+    )
+}
+
+pub fn hand_over_upgrade_authority(current_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::HandOverUpgradeAuthority {
+            current_authority: *current_authority,
+            program: ID,
+            program_data: find_program_data_address().0,
+            upgrade_authority: find_upgrade_authority_address().0,
+            upgrade_governance: find_upgrade_governance_address().0,
+            bpf_loader_upgradeable_program: bpf_loader_upgradeable::ID,
+            system_program: system_program::ID,
+        },
+        instruction::HandOverUpgradeAuthority {},
+    )
+}
+
+pub fn finalize_immutable() -> Instruction {
+    build(
+        accounts::FinalizeImmutable {
+            program: ID,
+            program_data: find_program_data_address().0,
+            upgrade_authority: find_upgrade_authority_address().0,
+            upgrade_governance: find_upgrade_governance_address().0,
+            bpf_loader_upgradeable_program: bpf_loader_upgradeable::ID,
+        },
+        instruction::FinalizeImmutable {},
+    )
+}
+
+pub fn set_guardians(
+    authority: &Pubkey,
+    guardians: [Pubkey; GUARDIAN_COUNCIL_SIZE],
+) -> Instruction {
+    build(
+        accounts::SetGuardians {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+            guardian_council: find_guardian_council_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::SetGuardians { guardians },
+    )
+}
+
+/// `guardians` sign the transaction; at least GUARDIAN_THRESHOLD of them
+/// must be on the council.
+pub fn guardian_pause(guardians: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::GuardianPause {
+            treasury: find_treasury_address().0,
+            guardian_council: find_guardian_council_address().0,
+        },
+        instruction::GuardianPause {},
+    );
+    ix.accounts
+        .extend(guardians.iter().map(|guardian| AccountMeta::new_readonly(*guardian, true)));
+    ix
+}
+
+pub fn unpause(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::Unpause {
+            authority: *authority,
+            treasury: find_treasury_address().0,
+        },
+        instruction::Unpause {},
+    )
+}
+
+/// Same signing rules as [`guardian_pause`].
+pub fn guardian_cancel_finalize(guardians: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::GuardianCancelFinalize {
+            guardian_council: find_guardian_council_address().0,
+            upgrade_governance: find_upgrade_governance_address().0,
+        },
+        instruction::GuardianCancelFinalize {},
+    );
+    ix.accounts
+        .extend(guardians.iter().map(|guardian| AccountMeta::new_readonly(*guardian, true)));
+    ix
+}
+
+/// Read-only; run through [`crate::views`] to decode the returned stats.
+pub fn get_treasury_stats(dream_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::GetTreasuryStats {
+            treasury: find_treasury_address().0,
+            dream_mint: *dream_mint,
+        },
+        instruction::GetTreasuryStats {},
+    )
+}
+
+/// Read-only. Profile and stake accounts that don't exist yet are reported as zero.
+pub fn get_dreamer_stats(
+    dreamer: &Pubkey,
+    lucid_mint: &Pubkey,
+    has_profile: bool,
+    has_stake: bool,
+) -> Instruction {
+    build(
+        accounts::GetDreamerStats {
+            dreamer_profile: has_profile.then(|| find_dreamer_profile_address(dreamer).0),
+            treasury: find_treasury_address().0,
+            stake_account: has_stake.then(|| find_stake_address(dreamer, lucid_mint).0),
+        },
+        instruction::GetDreamerStats { dreamer: *dreamer },
+    )
+}
+
+/// Read-only.
+pub fn get_stake_info(user: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::GetStakeInfo {
+            user: *user,
+            mint: *mint,
+            stake_account: find_stake_address(user, mint).0,
+        },
+        instruction::GetStakeInfo {},
+    )
+}
+
+/// Read-only. Pass whether each module's root account exists; missing ones
+/// leave their capability bit unset.
+pub fn get_capabilities(
+    has_upgrade_governance: bool,
+    has_guardian_council: bool,
+    has_compressed_stake_tree: bool,
+    has_dream_archive_tree: bool,
+) -> Instruction {
+    build(
+        accounts::GetCapabilities {
+            upgrade_governance: has_upgrade_governance.then(|| find_upgrade_governance_address().0),
+            guardian_council: has_guardian_council.then(|| find_guardian_council_address().0),
+            compressed_stake_tree: has_compressed_stake_tree
+                .then(|| find_compressed_stake_tree_address().0),
+            dream_archive_tree: has_dream_archive_tree.then(|| find_dream_archive_tree_address().0),
+        },
+        instruction::GetCapabilities {},
+    )
+}
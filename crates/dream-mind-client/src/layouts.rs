@@ -0,0 +1,1062 @@
+//! Plain mirrors of every program account, decoded straight from account
+//! data, for services that read accounts without going through Anchor's
+//! account types (geyser streams, indexers, bots).
+//!
+//! Borsh accounts decode into owned structs. The zero-copy accounts
+//! (`Leaderboard`, `StatsRing`, `ValidationQueue`, `CompressedStakeTree`,
+//! `DreamArchiveTree`) are `#[repr(C)]` `Pod` structs borrowed in place, so
+//! reading one doesn't copy its entries.
+//! Field order matches `dream_mind_state`, which documents each field;
+//! discriminators come from the program types, so they can't drift.
+
+use anchor_lang::{
+    error,
+    error::ErrorCode,
+    prelude::{borsh, Pubkey},
+    AnchorDeserialize, Discriminator,
+};
+use bytemuck::{Pod, Zeroable};
+use dream_mind_common::constants::{
+    COMPRESSED_STAKE_TREE_DEPTH, CRANK_KIND_COUNT, DREAM_LICENSE_TYPE_COUNT, GUARDIAN_COUNCIL_SIZE,
+    LEADERBOARD_SIZE, MAX_CO_DREAMERS, MAX_GRANT_MILESTONES, MAX_MARKET_OUTCOMES,
+    MAX_ROYALTY_CO_AUTHORS, STATS_RING_SIZE, VALIDATION_QUEUE_SIZE,
+};
+use dream_mind_lucid::{
+    self as program, DreamFlagReason, DreamLicenseType, DreamMetadata, DuelStatus, GrantStatus,
+    RewardToken, RoyaltyShare,
+};
+
+use crate::Result;
+
+fn check_discriminator(data: &[u8], discriminator: &[u8; 8]) -> Result<()> {
+    match data.get(..8) {
+        None => Err(error!(ErrorCode::AccountDiscriminatorNotFound).into()),
+        Some(prefix) if prefix != discriminator => {
+            Err(error!(ErrorCode::AccountDiscriminatorMismatch).into())
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// A borsh-encoded account.
+pub trait AccountLayout: AnchorDeserialize {
+    const DISCRIMINATOR: [u8; 8];
+
+    /// Decode discriminator-prefixed account data, as returned by RPC or geyser.
+    fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        check_discriminator(data, &Self::DISCRIMINATOR)?;
+        Self::deserialize(&mut &data[8..])
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize).into())
+    }
+}
+
+/// A zero-copy account.
+pub trait ZeroCopyLayout: Pod {
+    const DISCRIMINATOR: [u8; 8];
+
+    /// Borrow the account in place from discriminator-prefixed account data.
+    /// Fails if the data isn't aligned for `Self`; copy it out with
+    /// `bytemuck::pod_read_unaligned` in that case.
+    fn try_from_account_data(data: &[u8]) -> Result<&Self> {
+        check_discriminator(data, &Self::DISCRIMINATOR)?;
+        data.get(8..8 + std::mem::size_of::<Self>())
+            .and_then(|bytes| bytemuck::try_from_bytes(bytes).ok())
+            .ok_or_else(|| error!(ErrorCode::AccountDidNotDeserialize).into())
+    }
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Treasury {
+    pub authority: Pubkey,
+    pub dream_mint: Pubkey,
+    pub smind_mint: Pubkey,
+    pub lucid_mint: Pubkey,
+    pub total_dreams_recorded: u64,
+    pub total_rewards_distributed: u64,
+    pub mev_protection_enabled: bool,
+    pub total_bridged_out: u64,
+    pub total_bridged_in: u64,
+    pub bridge_messages_sent: u64,
+    pub total_dreams_mirrored: u64,
+    pub season: u32,
+    pub total_rewards_expired: u64,
+    pub bump: u8,
+    pub paused: bool,
+}
+
+impl AccountLayout for Treasury {
+    const DISCRIMINATOR: [u8; 8] = program::Treasury::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct RewardConversionRates {
+    pub smind_rate_bps: u32,
+    pub lucid_rate_bps: u32,
+    pub updated_at: i64,
+    pub total_dream_burned: u64,
+}
+
+impl AccountLayout for RewardConversionRates {
+    const DISCRIMINATOR: [u8; 8] = program::RewardConversionRates::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct RewardClaim {
+    pub dream_record: Pubkey,
+    pub token: RewardToken,
+    pub dream_amount: u64,
+    pub amount_out: u64,
+    pub claimed_at: i64,
+}
+
+impl AccountLayout for RewardClaim {
+    const DISCRIMINATOR: [u8; 8] = program::RewardClaim::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamRecord {
+    pub id: u64,
+    pub dreamer: Pubkey,
+    pub content_hash: [u8; 32],
+    pub timestamp: i64,
+    pub token_reward: u64,
+    pub mev_protected: bool,
+    pub origin_chain: u16,
+    pub metadata: DreamMetadata,
+    pub session: Pubkey,
+}
+
+impl AccountLayout for DreamRecord {
+    const DISCRIMINATOR: [u8; 8] = program::DreamRecord::DISCRIMINATOR;
+
+    /// Records created before sleep sessions existed end before `session`;
+    /// they are zero-padded so it reads as the default key. Extras past the
+    /// fixed fields are read with `dream_mind_lucid::DreamRecord::extras`.
+    fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        let mut data = data.to_vec();
+        if data.len() < program::DreamRecord::EXTRAS_OFFSET {
+            data.resize(program::DreamRecord::EXTRAS_OFFSET, 0);
+        }
+        check_discriminator(&data, &Self::DISCRIMINATOR)?;
+        Self::deserialize(&mut &data[8..])
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize).into())
+    }
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct InternedString {
+    pub id: u64,
+    pub value: String,
+}
+
+impl AccountLayout for InternedString {
+    const DISCRIMINATOR: [u8; 8] = program::InternedString::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SleepSession {
+    pub dreamer: Pubkey,
+    pub session_id: u64,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub device_attestation: [u8; 32],
+    pub dream_count: u32,
+    pub rewards_earned: u64,
+}
+
+impl AccountLayout for SleepSession {
+    const DISCRIMINATOR: [u8; 8] = program::SleepSession::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamerProfile {
+    pub dreamer: Pubkey,
+    pub interface_count: u64,
+    pub legacy_migrated: bool,
+    pub legacy_dream_count: u64,
+    pub legacy_tokens_earned: u64,
+    pub last_active_day: u32,
+    pub research_consent: bool,
+    pub reputation: i16,
+    pub account_created_at: i64,
+    pub validated_dreams: u32,
+}
+
+impl AccountLayout for DreamerProfile {
+    const DISCRIMINATOR: [u8; 8] = program::DreamerProfile::DISCRIMINATOR;
+
+    /// Profiles not yet grown by `migrate_dreamer_profile` end before
+    /// `account_created_at`; they are zero-padded so both new fields read 0.
+    fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        let space = 8 + std::mem::size_of::<program::DreamerProfile>();
+        let mut data = data.to_vec();
+        if data.len() < space {
+            data.resize(space, 0);
+        }
+        check_discriminator(&data, &Self::DISCRIMINATOR)?;
+        Self::deserialize(&mut &data[8..])
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize).into())
+    }
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamCoauthors {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub count: u8,
+    pub co_dreamers: [Pubkey; MAX_CO_DREAMERS],
+    pub confirmed: [bool; MAX_CO_DREAMERS],
+    pub share: u64,
+    pub recorded_at: i64,
+}
+
+impl AccountLayout for DreamCoauthors {
+    const DISCRIMINATOR: [u8; 8] = program::DreamCoauthors::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Charity {
+    pub wallet: Pubkey,
+    pub approved: bool,
+    pub updated_at: i64,
+    pub total_received: u64,
+}
+
+impl AccountLayout for Charity {
+    const DISCRIMINATOR: [u8; 8] = program::Charity::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct CharitySplit {
+    pub dreamer: Pubkey,
+    pub charity: Pubkey,
+    pub share_bps: u16,
+    pub total_donated: u64,
+}
+
+impl AccountLayout for CharitySplit {
+    const DISCRIMINATOR: [u8; 8] = program::CharitySplit::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ResearchPool {
+    pub consenting_dreamers: u64,
+    pub revenue_per_dreamer: u64,
+    pub licenses_sold: u64,
+    pub total_revenue: u64,
+}
+
+impl AccountLayout for ResearchPool {
+    const DISCRIMINATOR: [u8; 8] = program::ResearchPool::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ResearchParticipant {
+    pub dreamer: Pubkey,
+    pub revenue_checkpoint: u64,
+    pub accrued: u64,
+}
+
+impl AccountLayout for ResearchParticipant {
+    const DISCRIMINATOR: [u8; 8] = program::ResearchParticipant::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ResearchLicense {
+    pub licensee: Pubkey,
+    pub purchased_at: i64,
+    pub expires_at: i64,
+    pub total_paid: u64,
+}
+
+impl AccountLayout for ResearchLicense {
+    const DISCRIMINATOR: [u8; 8] = program::ResearchLicense::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamLicenseTerms {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub prices: [u64; DREAM_LICENSE_TYPE_COUNT],
+    pub co_author_count: u8,
+    pub co_authors: [RoyaltyShare; MAX_ROYALTY_CO_AUTHORS],
+    pub licenses_sold: u64,
+    pub total_revenue: u64,
+}
+
+impl AccountLayout for DreamLicenseTerms {
+    const DISCRIMINATOR: [u8; 8] = program::DreamLicenseTerms::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamLicense {
+    pub dream_record: Pubkey,
+    pub licensee: Pubkey,
+    pub license_type: DreamLicenseType,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub total_paid: u64,
+}
+
+impl AccountLayout for DreamLicense {
+    const DISCRIMINATOR: [u8; 8] = program::DreamLicense::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamAnnotation {
+    pub dream_record: Pubkey,
+    pub annotator: Pubkey,
+    pub language: [u8; 2],
+    pub cid: String,
+    pub created_at: i64,
+    pub approved: bool,
+    pub bounty_paid: u64,
+}
+
+impl AccountLayout for DreamAnnotation {
+    const DISCRIMINATOR: [u8; 8] = program::DreamAnnotation::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct TranslationBounty {
+    pub dream_record: Pubkey,
+    pub sponsor: Pubkey,
+    pub language: [u8; 2],
+    pub amount: u64,
+    pub paid_to: Pubkey,
+}
+
+impl AccountLayout for TranslationBounty {
+    const DISCRIMINATOR: [u8; 8] = program::TranslationBounty::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamInterpretation {
+    pub dream_record: Pubkey,
+    pub interpreter: Pubkey,
+    pub analysis_cid: String,
+    pub assigned_at: i64,
+    pub attached_at: i64,
+    pub reward_paid: u64,
+}
+
+impl AccountLayout for DreamInterpretation {
+    const DISCRIMINATOR: [u8; 8] = program::DreamInterpretation::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct PredictionMarket {
+    pub creator: Pubkey,
+    pub market_id: u64,
+    pub question_hash: [u8; 32],
+    pub outcome_count: u8,
+    pub closes_at: i64,
+    pub resolved: bool,
+    pub winning_outcome: u8,
+    pub outcome_pools: [u64; MAX_MARKET_OUTCOMES],
+    pub fees_collected: u64,
+}
+
+impl AccountLayout for PredictionMarket {
+    const DISCRIMINATOR: [u8; 8] = program::PredictionMarket::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct MarketPosition {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub stakes: [u64; MAX_MARKET_OUTCOMES],
+    pub claimed: bool,
+}
+
+impl AccountLayout for MarketPosition {
+    const DISCRIMINATOR: [u8; 8] = program::MarketPosition::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct RewardStream {
+    pub beneficiary: Pubkey,
+    pub source: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+}
+
+impl AccountLayout for RewardStream {
+    const DISCRIMINATOR: [u8; 8] = program::RewardStream::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamDuel {
+    pub challenger: Pubkey,
+    pub opponent: Pubkey,
+    pub duel_id: u64,
+    pub stake: u64,
+    pub status: DuelStatus,
+    pub started_at: i64,
+    pub submission_deadline: i64,
+    pub challenger_dream: Pubkey,
+    pub opponent_dream: Pubkey,
+    pub challenger_score: u8,
+    pub opponent_score: u8,
+    pub winner: Pubkey,
+}
+
+impl AccountLayout for DreamDuel {
+    const DISCRIMINATOR: [u8; 8] = program::DreamDuel::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Grant {
+    pub recipient: Pubkey,
+    pub grant_id: u64,
+    pub milestone_amounts: [u64; MAX_GRANT_MILESTONES],
+    pub milestone_count: u8,
+    pub milestones_paid: u8,
+    pub total_amount: u64,
+    pub released: u64,
+    pub status: GrantStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl AccountLayout for Grant {
+    const DISCRIMINATOR: [u8; 8] = program::Grant::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SeasonScore {
+    pub dreamer: Pubkey,
+    pub season: u32,
+    pub dream_count: u64,
+}
+
+impl AccountLayout for SeasonScore {
+    const DISCRIMINATOR: [u8; 8] = program::SeasonScore::DISCRIMINATOR;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct LeaderboardEntry {
+    pub dreamer: Pubkey,
+    pub dream_count: u64,
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct Leaderboard {
+    pub season: u32,
+    pub len: u32,
+    pub season_started_at: i64,
+    pub entries: [LeaderboardEntry; LEADERBOARD_SIZE],
+}
+
+impl ZeroCopyLayout for Leaderboard {
+    const DISCRIMINATOR: [u8; 8] = program::Leaderboard::DISCRIMINATOR;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct StatsSnapshot {
+    pub dreams_recorded: u64,
+    pub rewards_distributed: u64,
+    pub compressed_staked: u64,
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct StatsRing {
+    pub last_day: u32,
+    pub len: u32,
+    pub head: u64,
+    pub snapshots: [StatsSnapshot; STATS_RING_SIZE],
+}
+
+impl ZeroCopyLayout for StatsRing {
+    const DISCRIMINATOR: [u8; 8] = program::StatsRing::DISCRIMINATOR;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct ValidationQueueEntry {
+    pub dream_record: Pubkey,
+    pub stake: u64,
+    pub enqueued_at: i64,
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct ValidationQueue {
+    pub head: u32,
+    pub len: u32,
+    pub total_enqueued: u64,
+    pub entries: [ValidationQueueEntry; VALIDATION_QUEUE_SIZE],
+}
+
+impl ZeroCopyLayout for ValidationQueue {
+    const DISCRIMINATOR: [u8; 8] = program::ValidationQueue::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct EpochStats {
+    pub day: u32,
+    pub dream_count: u64,
+    pub unique_dreamers: u64,
+    pub rewards_minted: u64,
+}
+
+impl AccountLayout for EpochStats {
+    const DISCRIMINATOR: [u8; 8] = program::EpochStats::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct EpochCommitment {
+    pub day: u32,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u64,
+    pub committed_by: Pubkey,
+    pub committed_at: i64,
+}
+
+impl AccountLayout for EpochCommitment {
+    const DISCRIMINATOR: [u8; 8] = program::EpochCommitment::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamInterface {
+    pub dreamer: Pubkey,
+    pub index: u64,
+    pub ipfs_hash: String,
+    pub timestamp: i64,
+    pub access_level: u8,
+}
+
+impl AccountLayout for DreamInterface {
+    const DISCRIMINATOR: [u8; 8] = program::DreamInterface::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamCapsule {
+    pub dreamer: Pubkey,
+    pub capsule_id: u64,
+    pub content_hash: [u8; 32],
+    pub sealed_at: i64,
+    pub unlock_at: i64,
+    pub revealed_at: i64,
+    pub cid: String,
+}
+
+impl AccountLayout for DreamCapsule {
+    const DISCRIMINATOR: [u8; 8] = program::DreamCapsule::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LucidStake {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub access_level: u8,
+}
+
+impl AccountLayout for LucidStake {
+    const DISCRIMINATOR: [u8; 8] = program::LucidStake::DISCRIMINATOR;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct CompressedStakeTree {
+    pub root: [u8; 32],
+    pub next_index: u64,
+    pub total_staked: u64,
+    pub active_stakes: u64,
+    pub filled_subtrees: [[u8; 32]; COMPRESSED_STAKE_TREE_DEPTH],
+}
+
+impl ZeroCopyLayout for CompressedStakeTree {
+    const DISCRIMINATOR: [u8; 8] = program::CompressedStakeTree::DISCRIMINATOR;
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct DreamArchiveTree {
+    pub root: [u8; 32],
+    pub next_index: u64,
+    pub filled_subtrees: [[u8; 32]; COMPRESSED_STAKE_TREE_DEPTH],
+}
+
+impl ZeroCopyLayout for DreamArchiveTree {
+    const DISCRIMINATOR: [u8; 8] = program::DreamArchiveTree::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ArchivedDream {
+    pub dreamer: Pubkey,
+    pub dream_id: u64,
+    pub leaf_index: u64,
+    pub leaf: [u8; 32],
+    pub archived_at: i64,
+}
+
+impl AccountLayout for ArchivedDream {
+    const DISCRIMINATOR: [u8; 8] = program::ArchivedDream::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct CrankPool {
+    pub bounty_lamports: u64,
+    pub cooldown_secs: i64,
+    pub last_paid_at: [i64; CRANK_KIND_COUNT],
+    pub total_paid: u64,
+}
+
+impl AccountLayout for CrankPool {
+    const DISCRIMINATOR: [u8; 8] = program::CrankPool::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct FeeVault {
+    pub record_dream_fee: u64,
+    pub mint_oneirobot_fee: u64,
+    pub total_swept: u64,
+}
+
+impl AccountLayout for FeeVault {
+    const DISCRIMINATOR: [u8; 8] = program::FeeVault::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SponsorPool {
+    pub enabled: bool,
+    pub dreamers_sponsored: u64,
+    pub total_sponsored: u64,
+}
+
+impl AccountLayout for SponsorPool {
+    const DISCRIMINATOR: [u8; 8] = program::SponsorPool::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Sponsorship {
+    pub dreamer: Pubkey,
+    pub lamports: u64,
+    pub claimed_at: i64,
+}
+
+impl AccountLayout for Sponsorship {
+    const DISCRIMINATOR: [u8; 8] = program::Sponsorship::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct RewardPool {
+    pub period_start: i64,
+    pub period_minted: u64,
+    pub total_minted: u64,
+}
+
+impl AccountLayout for RewardPool {
+    const DISCRIMINATOR: [u8; 8] = program::RewardPool::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct FaucetClaim {
+    pub wallet: Pubkey,
+    pub claims: u32,
+    pub last_claimed_at: i64,
+}
+
+impl AccountLayout for FaucetClaim {
+    const DISCRIMINATOR: [u8; 8] = program::FaucetClaim::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct InsuranceFund {
+    pub fee_share_bps: u16,
+    pub lamports_received: u64,
+    pub dream_received: u64,
+    pub lamports_covered: u64,
+    pub dream_covered: u64,
+}
+
+impl AccountLayout for InsuranceFund {
+    const DISCRIMINATOR: [u8; 8] = program::InsuranceFund::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct TreasuryHealth {
+    pub pending_coauthor_shares: u64,
+    pub pending_streamed: u64,
+    pub healthy: bool,
+    pub checked_at: i64,
+}
+
+impl AccountLayout for TreasuryHealth {
+    const DISCRIMINATOR: [u8; 8] = program::TreasuryHealth::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct PinningOracle {
+    pub oracle: Pubkey,
+    pub approved: bool,
+    pub updated_at: i64,
+    pub attestations: u64,
+}
+
+impl AccountLayout for PinningOracle {
+    const DISCRIMINATOR: [u8; 8] = program::PinningOracle::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct OracleQuorum {
+    pub threshold: u8,
+    pub updated_at: i64,
+}
+
+impl AccountLayout for OracleQuorum {
+    const DISCRIMINATOR: [u8; 8] = program::OracleQuorum::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct PinConfirmation {
+    pub dream_record: Pubkey,
+    pub oracle: Pubkey,
+    pub confirmed_at: i64,
+}
+
+impl AccountLayout for PinConfirmation {
+    const DISCRIMINATOR: [u8; 8] = program::PinConfirmation::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamClassification {
+    pub dream_record: Pubkey,
+    pub oracle: Pubkey,
+    pub language: [u8; 2],
+    pub nsfw: bool,
+    pub topics: u64,
+    pub revision: u32,
+    pub classified_at: i64,
+}
+
+impl AccountLayout for DreamClassification {
+    const DISCRIMINATOR: [u8; 8] = program::DreamClassification::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SessionKey {
+    pub authority: Pubkey,
+    pub signer: Pubkey,
+    pub scope: u8,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl AccountLayout for SessionKey {
+    const DISCRIMINATOR: [u8; 8] = program::SessionKey::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct RelayNonce {
+    pub dreamer: Pubkey,
+    pub nonce: u64,
+}
+
+impl AccountLayout for RelayNonce {
+    const DISCRIMINATOR: [u8; 8] = program::RelayNonce::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct IdempotencyNonce {
+    pub dreamer: Pubkey,
+    pub key: u64,
+    pub payer: Pubkey,
+    pub dream_record: Pubkey,
+    pub created_at: i64,
+}
+
+impl AccountLayout for IdempotencyNonce {
+    const DISCRIMINATOR: [u8; 8] = program::IdempotencyNonce::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamFlag {
+    pub dream_record: Pubkey,
+    pub reason: DreamFlagReason,
+    pub flagged_by: Pubkey,
+    pub flagged_at: i64,
+}
+
+impl AccountLayout for DreamFlag {
+    const DISCRIMINATOR: [u8; 8] = program::DreamFlag::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamValidation {
+    pub dream_record: Pubkey,
+    pub validator: Pubkey,
+    pub score: u8,
+    pub validated_at: i64,
+}
+
+impl AccountLayout for DreamValidation {
+    const DISCRIMINATOR: [u8; 8] = program::DreamValidation::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ValidatorGate {
+    pub enabled: bool,
+    pub min_dream_level: u8,
+    pub nft_program: Pubkey,
+    pub updated_at: i64,
+}
+
+impl AccountLayout for ValidatorGate {
+    const DISCRIMINATOR: [u8; 8] = program::ValidatorGate::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ValidatorRegistration {
+    pub validator: Pubkey,
+    pub oneirobot_mint: Pubkey,
+    pub registered_at: i64,
+}
+
+impl AccountLayout for ValidatorRegistration {
+    const DISCRIMINATOR: [u8; 8] = program::ValidatorRegistration::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ValidatorRewardPool {
+    pub fee_share_bps: u16,
+    pub reward_per_validation: u64,
+    pub dream_received: u64,
+    pub total_credited: u64,
+    pub total_claimed: u64,
+    pub epoch_claim_cap: u64,
+}
+
+impl AccountLayout for ValidatorRewardPool {
+    const DISCRIMINATOR: [u8; 8] = program::ValidatorRewardPool::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ValidatorRewards {
+    pub validator: Pubkey,
+    pub claimable: u64,
+    pub total_earned: u64,
+    pub epoch: u32,
+    pub claimed_in_epoch: u64,
+}
+
+impl AccountLayout for ValidatorRewards {
+    const DISCRIMINATOR: [u8; 8] = program::ValidatorRewards::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ValidatorStats {
+    pub validator: Pubkey,
+    pub validations: u64,
+    pub disputes: u32,
+    pub disputes_lost: u32,
+    pub total_divergence: u64,
+    pub last_validated_at: i64,
+}
+
+impl AccountLayout for ValidatorStats {
+    const DISCRIMINATOR: [u8; 8] = program::ValidatorStats::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ValidationDispute {
+    pub dream_record: Pubkey,
+    pub validator: Pubkey,
+    pub score: u8,
+    pub consensus_score: u8,
+    pub lost: bool,
+    pub resolved_at: i64,
+}
+
+impl AccountLayout for ValidationDispute {
+    const DISCRIMINATOR: [u8; 8] = program::ValidationDispute::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct OptimisticScore {
+    pub dream_record: Pubkey,
+    pub oracle: Pubkey,
+    pub score: u8,
+    pub voided: bool,
+    pub bond: u64,
+    pub posted_at: i64,
+}
+
+impl AccountLayout for OptimisticScore {
+    const DISCRIMINATOR: [u8; 8] = program::OptimisticScore::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SpliceConfig {
+    pub nft_program: Pubkey,
+    pub updated_at: i64,
+}
+
+impl AccountLayout for SpliceConfig {
+    const DISCRIMINATOR: [u8; 8] = program::SpliceConfig::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SplicedDream {
+    pub dream_record: Pubkey,
+    pub oneirobot_mint: Pubkey,
+    pub spliced_at: i64,
+}
+
+impl AccountLayout for SplicedDream {
+    const DISCRIMINATOR: [u8; 8] = program::SplicedDream::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct DreamCertificate {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub oneirobot_mint: Pubkey,
+    pub score: u8,
+    pub certified_at: i64,
+}
+
+impl AccountLayout for DreamCertificate {
+    const DISCRIMINATOR: [u8; 8] = program::DreamCertificate::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Companion {
+    pub dreamer: Pubkey,
+    pub mint: Pubkey,
+    pub equipped_at: i64,
+}
+
+impl AccountLayout for Companion {
+    const DISCRIMINATOR: [u8; 8] = program::Companion::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct CompanionXp {
+    pub mint: Pubkey,
+    pub xp: u64,
+    pub dreams_recorded: u64,
+}
+
+impl AccountLayout for CompanionXp {
+    const DISCRIMINATOR: [u8; 8] = program::CompanionXp::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct CompanionStamp {
+    pub dream_record: Pubkey,
+    pub mint: Pubkey,
+}
+
+impl AccountLayout for CompanionStamp {
+    const DISCRIMINATOR: [u8; 8] = program::CompanionStamp::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ForeignEmitter {
+    pub chain: u16,
+    pub address: [u8; 32],
+}
+
+impl AccountLayout for ForeignEmitter {
+    const DISCRIMINATOR: [u8; 8] = program::ForeignEmitter::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct BridgeReceipt {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+impl AccountLayout for BridgeReceipt {
+    const DISCRIMINATOR: [u8; 8] = program::BridgeReceipt::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct MessageAdapter {
+    pub delivery_authority: Pubkey,
+    pub messages_received: u64,
+}
+
+impl AccountLayout for MessageAdapter {
+    const DISCRIMINATOR: [u8; 8] = program::MessageAdapter::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct TrustedRemote {
+    pub origin: u32,
+    pub sender: [u8; 32],
+    pub next_nonce: u64,
+}
+
+impl AccountLayout for TrustedRemote {
+    const DISCRIMINATOR: [u8; 8] = program::TrustedRemote::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct ProgramInfo {
+    pub version: String,
+    pub git_commit: String,
+    pub audit_uri: String,
+    pub updated_at: i64,
+}
+
+impl AccountLayout for ProgramInfo {
+    const DISCRIMINATOR: [u8; 8] = program::ProgramInfo::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct BuildInfo {
+    pub program_hash: [u8; 32],
+    pub git_commit: String,
+    pub deploy_slot: u64,
+    pub updated_at: i64,
+}
+
+impl AccountLayout for BuildInfo {
+    const DISCRIMINATOR: [u8; 8] = program::BuildInfo::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct UpgradeGovernance {
+    pub handed_over_by: Pubkey,
+    pub handed_over_at: i64,
+    pub approval_origin: u32,
+    pub approval_nonce: u64,
+    pub approved_at: i64,
+    pub finalized_at: i64,
+}
+
+impl AccountLayout for UpgradeGovernance {
+    const DISCRIMINATOR: [u8; 8] = program::UpgradeGovernance::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct GuardianCouncil {
+    pub guardians: [Pubkey; GUARDIAN_COUNCIL_SIZE],
+    pub rotated_at: i64,
+}
+
+impl AccountLayout for GuardianCouncil {
+    const DISCRIMINATOR: [u8; 8] = program::GuardianCouncil::DISCRIMINATOR;
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct LookupTableConfig {
+    pub lookup_table: Pubkey,
+    pub recent_slot: u64,
+}
+
+impl AccountLayout for LookupTableConfig {
+    const DISCRIMINATOR: [u8; 8] = program::LookupTableConfig::DISCRIMINATOR;
+}
@@ -0,0 +1,69 @@
+//! Client SDK for the Dream-Mind-Lucid program.
+//!
+//! Instruction data and account metas are produced from the program's own
+//! Anchor-generated types, so discriminators and layouts never drift from
+//! the on-chain program. [`layouts`] mirrors every account as a plain struct
+//! decoded straight from account data, for services that don't use Anchor's
+//! account types.
+//!
+//! The `rpc` feature (on by default) adds account fetchers over
+//! `solana-client`, transaction simulation, dispatch with priority fees and
+//! retries, durable-nonce offline signing, and sponsored-transaction relaying;
+//! disable it for `wasm32` builds.
+
+#[cfg(feature = "rpc")]
+pub mod accounts;
+#[cfg(feature = "rpc")]
+pub mod dispatch;
+pub mod instructions;
+pub mod layouts;
+#[cfg(feature = "rpc")]
+pub mod offline;
+pub mod pda;
+#[cfg(feature = "rpc")]
+pub mod relayer;
+#[cfg(feature = "rpc")]
+pub mod simulate;
+pub mod transaction;
+#[cfg(feature = "rpc")]
+pub mod views;
+pub mod wormhole;
+
+#[cfg(feature = "rpc")]
+pub use accounts::*;
+pub use pda::*;
+
+pub use dream_mind_lucid::ID as PROGRAM_ID;
+/// Instruction argument types, re-exported for callers without a direct
+/// dependency on the program crate.
+pub use dream_mind_lucid::{
+    DreamFlagReason, DreamMetadata, DreamRecordExtras, Mood, RewardToken, ShortfallTarget,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    #[cfg(feature = "rpc")]
+    #[error("RPC request failed: {0}")]
+    Rpc(#[from] solana_client::client_error::ClientError),
+    #[cfg(feature = "rpc")]
+    #[error("Nonce account is unusable: {0}")]
+    Nonce(#[from] solana_client::nonce_utils::Error),
+    #[cfg(feature = "rpc")]
+    #[error("Transaction could not be signed: {0}")]
+    Sign(#[from] solana_sdk::signer::SignerError),
+    #[error("Account could not be deserialized: {0}")]
+    Deserialize(#[from] anchor_lang::error::Error),
+    #[error("Lookup table could not be deserialized: {0}")]
+    LookupTable(anchor_lang::solana_program::instruction::InstructionError),
+    #[error("Transaction could not be compiled: {0}")]
+    Compile(#[from] anchor_lang::solana_program::message::CompileError),
+    #[error("Transaction could not be encoded or decoded: {0}")]
+    Decode(String),
+    #[error("View simulation failed: {0}")]
+    View(String),
+    #[cfg(feature = "rpc")]
+    #[error("Relayer refused the transaction: {0}")]
+    Relay(String),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
@@ -0,0 +1,54 @@
+//! Durable-nonce transactions for authority keys kept on an air-gapped machine.
+//!
+//! An online machine builds the transaction against a nonce account, whose
+//! stored value replaces the recent blockhash so the transaction doesn't expire
+//! while it travels. The fee payer may partially sign it there. The offline
+//! machine adds the authority's signature, and the online machine submits it.
+//! Transactions move between machines as base64-encoded wire bytes.
+
+use anchor_lang::solana_program::{hash::Hash, instruction::Instruction, pubkey::Pubkey};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{signature::Signature, transaction::Transaction};
+
+use crate::{
+    dispatch::{with_advance_nonce, DurableNonce},
+    ClientError, Result,
+};
+
+/// Unsigned transaction that advances `nonce` and then runs `instructions`.
+/// `nonce_blockhash` is the nonce account's current value (see
+/// [`crate::dispatch::fetch_nonce_blockhash`]).
+pub fn build_nonce_transaction(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    nonce: &DurableNonce,
+    nonce_blockhash: Hash,
+) -> Transaction {
+    let instructions = with_advance_nonce(instructions, nonce);
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(fee_payer));
+    transaction.message.recent_blockhash = nonce_blockhash;
+    transaction
+}
+
+/// Signers whose signature is still missing.
+pub fn missing_signers(transaction: &Transaction) -> Vec<Pubkey> {
+    let signers = usize::from(transaction.message.header.num_required_signatures);
+    transaction.message.account_keys[..signers]
+        .iter()
+        .zip(&transaction.signatures)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+pub fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    let bytes = bincode::serialize(transaction).map_err(|err| ClientError::Decode(err.to_string()))?;
+    Ok(STANDARD.encode(bytes))
+}
+
+pub fn decode_transaction(encoded: &str) -> Result<Transaction> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|err| ClientError::Decode(err.to_string()))?;
+    bincode::deserialize(&bytes).map_err(|err| ClientError::Decode(err.to_string()))
+}
@@ -0,0 +1,426 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{address_lookup_table, bpf_loader_upgradeable},
+};
+use dream_mind_lucid::{DreamLicenseType, ID};
+
+pub fn find_treasury_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"treasury"], &ID)
+}
+
+/// Mints created by `bootstrap`; deployments set up with `initialize_tokens`
+/// use whatever mints were passed there.
+pub fn find_dream_mint_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"dream_mint"], &ID)
+}
+
+pub fn find_smind_mint_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"smind_mint"], &ID)
+}
+
+pub fn find_lucid_mint_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"lucid_mint"], &ID)
+}
+
+pub fn find_dream_record_address(dreamer: &Pubkey, dream_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"dream", dreamer.as_ref(), &dream_id.to_le_bytes()], &ID)
+}
+
+pub fn find_interned_string_address(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"interned", &id.to_le_bytes()], &ID)
+}
+
+pub fn find_dreamer_profile_address(dreamer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"profile", dreamer.as_ref()], &ID)
+}
+
+pub fn find_dream_interface_address(dreamer: &Pubkey, interface_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"interface", dreamer.as_ref(), &interface_index.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// `day` is `EpochStats::day_of(unix_timestamp)`.
+pub fn find_sleep_session_address(dreamer: &Pubkey, day: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sleep_session", dreamer.as_ref(), &day.to_le_bytes()], &ID)
+}
+
+/// `day` is `EpochStats::day_of(unix_timestamp)`.
+pub fn find_epoch_stats_address(day: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"epoch_stats", &day.to_le_bytes()], &ID)
+}
+
+pub fn find_epoch_commitment_address(day: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"epoch_commitment", &day.to_le_bytes()], &ID)
+}
+
+pub fn find_research_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"research_pool"], &ID)
+}
+
+pub fn find_research_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"research_vault"], &ID)
+}
+
+pub fn find_research_participant_address(dreamer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"research_participant", dreamer.as_ref()], &ID)
+}
+
+pub fn find_research_license_address(licensee: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"research_license", licensee.as_ref()], &ID)
+}
+
+pub fn find_license_terms_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"license_terms", dream_record.as_ref()], &ID)
+}
+
+pub fn find_dream_license_address(
+    dream_record: &Pubkey,
+    licensee: &Pubkey,
+    license_type: DreamLicenseType,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"dream_license", dream_record.as_ref(), licensee.as_ref(), &[license_type as u8]],
+        &ID,
+    )
+}
+
+pub fn find_dream_coauthors_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"coauthors", dream_record.as_ref()], &ID)
+}
+
+pub fn find_annotation_address(
+    dream_record: &Pubkey,
+    annotator: &Pubkey,
+    language: [u8; 2],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"annotation", dream_record.as_ref(), annotator.as_ref(), &language],
+        &ID,
+    )
+}
+
+pub fn find_translation_bounty_address(dream_record: &Pubkey, language: [u8; 2]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"translation_bounty", dream_record.as_ref(), &language], &ID)
+}
+
+pub fn find_dream_interpretation_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"interpretation", dream_record.as_ref()], &ID)
+}
+
+pub fn find_translation_bounty_vault_address(
+    dream_record: &Pubkey,
+    language: [u8; 2],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"translation_bounty_vault", dream_record.as_ref(), &language],
+        &ID,
+    )
+}
+
+pub fn find_market_address(creator: &Pubkey, market_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"market", creator.as_ref(), &market_id.to_le_bytes()], &ID)
+}
+
+pub fn find_market_vault_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"market_vault", market.as_ref()], &ID)
+}
+
+pub fn find_market_position_address(market: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"market_position", market.as_ref(), owner.as_ref()], &ID)
+}
+
+pub fn find_reward_stream_address(source: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_stream", source.as_ref(), beneficiary.as_ref()], &ID)
+}
+
+pub fn find_reward_stream_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_stream_vault"], &ID)
+}
+
+pub fn find_duel_address(challenger: &Pubkey, duel_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"duel", challenger.as_ref(), &duel_id.to_le_bytes()], &ID)
+}
+
+pub fn find_duel_vault_address(duel: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"duel_vault", duel.as_ref()], &ID)
+}
+
+pub fn find_grant_address(recipient: &Pubkey, grant_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"grant", recipient.as_ref(), &grant_id.to_le_bytes()], &ID)
+}
+
+/// LUCID token account escrowing a grant; owned by the treasury.
+pub fn find_grant_vault_address(grant: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"grant_vault", grant.as_ref()], &ID)
+}
+
+pub fn find_capsule_address(dreamer: &Pubkey, capsule_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"capsule", dreamer.as_ref(), &capsule_id.to_le_bytes()], &ID)
+}
+
+pub fn find_charity_address(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"charity", wallet.as_ref()], &ID)
+}
+
+pub fn find_charity_split_address(dreamer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"charity_split", dreamer.as_ref()], &ID)
+}
+
+pub fn find_season_score_address(dreamer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"season_score", dreamer.as_ref()], &ID)
+}
+
+pub fn find_leaderboard_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"leaderboard"], &ID)
+}
+
+pub fn find_stats_ring_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stats_ring"], &ID)
+}
+
+pub fn find_validation_queue_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validation_queue"], &ID)
+}
+
+pub fn find_pinning_oracle_address(oracle: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pinning_oracle", oracle.as_ref()], &ID)
+}
+
+pub fn find_oracle_quorum_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"oracle_quorum"], &ID)
+}
+
+pub fn find_pin_confirmation_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pinned", dream_record.as_ref()], &ID)
+}
+
+pub fn find_dream_classification_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"classification", dream_record.as_ref()], &ID)
+}
+
+pub fn find_dream_validation_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validation", dream_record.as_ref()], &ID)
+}
+
+pub fn find_dream_flag_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"flag", dream_record.as_ref()], &ID)
+}
+
+pub fn find_session_key_address(authority: &Pubkey, signer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"session_key", authority.as_ref(), signer.as_ref()], &ID)
+}
+
+pub fn find_relay_nonce_address(dreamer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"relay_nonce", dreamer.as_ref()], &ID)
+}
+
+pub fn find_idempotency_nonce_address(dreamer: &Pubkey, key: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"idempotency", dreamer.as_ref(), &key.to_le_bytes()], &ID)
+}
+
+pub fn find_validator_gate_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validator_gate"], &ID)
+}
+
+pub fn find_validator_registration_address(validator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validator", validator.as_ref()], &ID)
+}
+
+pub fn find_validator_reward_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validator_reward_pool"], &ID)
+}
+
+/// DREAM token account that pays validator rewards; owned by the treasury.
+pub fn find_validator_reward_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validator_reward_vault"], &ID)
+}
+
+pub fn find_validator_rewards_address(validator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validator_rewards", validator.as_ref()], &ID)
+}
+
+pub fn find_validator_stats_address(validator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validator_stats", validator.as_ref()], &ID)
+}
+
+pub fn find_validation_dispute_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validation_dispute", dream_record.as_ref()], &ID)
+}
+
+pub fn find_optimistic_score_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"optimistic_score", dream_record.as_ref()], &ID)
+}
+
+pub fn find_splice_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"splice_config"], &ID)
+}
+
+pub fn find_splicer_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"splicer"], &ID)
+}
+
+pub fn find_spliced_dream_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"spliced_dream", dream_record.as_ref()], &ID)
+}
+
+pub fn find_dream_certificate_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"certificate", dream_record.as_ref()], &ID)
+}
+
+pub fn find_companion_address(dreamer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"companion", dreamer.as_ref()], &ID)
+}
+
+pub fn find_companion_xp_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"companion_xp", mint.as_ref()], &ID)
+}
+
+pub fn find_companion_stamp_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"companion_stamp", dream_record.as_ref()], &ID)
+}
+
+pub fn find_crank_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"crank_pool"], &ID)
+}
+
+pub fn find_sponsor_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sponsor_pool"], &ID)
+}
+
+pub fn find_sponsorship_address(dreamer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sponsorship", dreamer.as_ref()], &ID)
+}
+
+pub fn find_reward_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_pool"], &ID)
+}
+
+pub fn find_faucet_claim_address(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"faucet", wallet.as_ref()], &ID)
+}
+
+/// DREAM token account that pays dream rewards; owned by the treasury.
+pub fn find_reward_pool_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_pool_vault"], &ID)
+}
+
+pub fn find_conversion_rates_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"conversion_rates"], &ID)
+}
+
+pub fn find_reward_claim_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_claim", dream_record.as_ref()], &ID)
+}
+
+pub fn find_insurance_fund_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"insurance_fund"], &ID)
+}
+
+/// DREAM token account of the insurance fund; owned by the treasury.
+pub fn find_insurance_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"insurance_vault"], &ID)
+}
+
+/// Liabilities owed out of the treasury's vaults; see `reconcile_treasury`.
+pub fn find_treasury_health_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"treasury_health"], &ID)
+}
+
+/// Also where `mint_oneirobot` pays its fee.
+pub fn find_fee_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_vault"], &ID)
+}
+
+/// One stake per user and staked mint; the LUCID stake uses the treasury's `lucid_mint`.
+pub fn find_stake_address(user: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stake", user.as_ref(), mint.as_ref()], &ID)
+}
+
+/// Non-transferable credential mint of a LUCID access level (3 or 4).
+pub fn find_tier_credential_mint_address(access_level: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tier_credential", &[access_level]], &ID)
+}
+
+/// Where stakes lived before they were keyed by mint; see `migrate_stake`.
+pub fn find_legacy_stake_address(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stake", user.as_ref()], &ID)
+}
+
+pub fn find_compressed_stake_tree_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"compressed_stake_tree"], &ID)
+}
+
+pub fn find_compressed_stake_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"compressed_stake_vault"], &ID)
+}
+
+pub fn find_dream_archive_tree_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"dream_archive_tree"], &ID)
+}
+
+pub fn find_archived_dream_address(dream_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"archived_dream", dream_record.as_ref()], &ID)
+}
+
+pub fn find_emitter_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"emitter"], &ID)
+}
+
+pub fn find_dream_custody_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"dream_custody"], &ID)
+}
+
+pub fn find_foreign_emitter_address(chain: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"foreign_emitter", &chain.to_le_bytes()], &ID)
+}
+
+pub fn find_bridge_receipt_address(emitter_chain: u16, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"bridge_receipt", &emitter_chain.to_le_bytes(), &sequence.to_le_bytes()],
+        &ID,
+    )
+}
+
+pub fn find_message_adapter_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"message_adapter"], &ID)
+}
+
+pub fn find_trusted_remote_address(origin: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"trusted_remote", &origin.to_le_bytes()], &ID)
+}
+
+pub fn find_lookup_table_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"lookup_table"], &ID)
+}
+
+/// Address of the lookup table `sync_lookup_table` creates for `recent_slot`.
+pub fn find_lookup_table_address(recent_slot: u64) -> (Pubkey, u8) {
+    address_lookup_table::instruction::derive_lookup_table_address(&find_treasury_address().0, recent_slot)
+}
+
+pub fn find_program_info_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"program_info"], &ID)
+}
+
+pub fn find_build_info_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"build_info"], &ID)
+}
+
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// Governance PDA that holds the upgrade authority after the handover.
+pub fn find_upgrade_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"upgrade_authority"], &ID)
+}
+
+pub fn find_upgrade_governance_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"upgrade_governance"], &ID)
+}
+
+pub fn find_guardian_council_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"guardian_council"], &ID)
+}
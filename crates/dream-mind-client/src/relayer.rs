@@ -0,0 +1,143 @@
+//! Sponsored transactions, Octane-style: a relayer pays the network fee and
+//! the rent of user-paid instructions, and the user only signs.
+//!
+//! The app builds the transaction with [`build_sponsored_transaction`] and has
+//! the user partially sign it. The relayer then vets it with
+//! [`check_sponsored_transaction`], co-signs, and submits it with
+//! [`relay`]. Vetting matters because the relayer's signature covers the whole
+//! message: it must only pay, never act as the user in an instruction.
+
+use anchor_lang::solana_program::{hash::Hash, instruction::Instruction, pubkey::Pubkey};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{compute_budget, signature::Signature, signer::Signer, transaction::Transaction};
+
+use crate::{
+    dispatch::send_presigned,
+    instructions::{rent_payer_index, with_rent_payer},
+    offline::missing_signers,
+    simulate::simulate_transaction,
+    ClientError, Result, PROGRAM_ID,
+};
+
+#[derive(Clone, Debug)]
+pub struct RelayPolicy {
+    /// Most SOL the relayer may spend on one transaction, fee and rent
+    /// together, as measured by simulation.
+    pub max_lamports: u64,
+}
+
+impl Default for RelayPolicy {
+    fn default() -> Self {
+        Self {
+            // A first dream: record, profile, season score, DREAM account
+            max_lamports: 10_000_000,
+        }
+    }
+}
+
+/// Unsigned transaction running `instructions` with `relayer` as the fee
+/// payer and as the rent payer of every user-paid instruction.
+pub fn build_sponsored_transaction(
+    instructions: &[Instruction],
+    relayer: &Pubkey,
+    recent_blockhash: Hash,
+) -> Transaction {
+    let instructions: Vec<Instruction> = instructions
+        .iter()
+        .map(|ix| with_rent_payer(ix.clone(), relayer))
+        .collect();
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(relayer));
+    transaction.message.recent_blockhash = recent_blockhash;
+    transaction
+}
+
+/// Checks a user-signed transaction before `relayer` co-signs it: the relayer
+/// is the fee payer, only this program and the compute budget program run,
+/// the relayer appears only as a rent payer, and every other signature is
+/// present and valid.
+pub fn check_sponsored_transaction(transaction: &Transaction, relayer: &Pubkey) -> Result<()> {
+    let message = &transaction.message;
+    if message.account_keys.first() != Some(relayer) {
+        return Err(reject("relayer is not the fee payer"));
+    }
+    for compiled in &message.instructions {
+        let ix = Instruction {
+            program_id: *compiled.program_id(&message.account_keys),
+            accounts: vec![],
+            data: compiled.data.clone(),
+        };
+        if ix.program_id == compute_budget::ID {
+            continue;
+        }
+        if ix.program_id != PROGRAM_ID {
+            return Err(reject(&format!(
+                "program {} is not sponsored",
+                ix.program_id
+            )));
+        }
+        let payer_index = rent_payer_index(&ix);
+        let misused = compiled
+            .accounts
+            .iter()
+            .enumerate()
+            .any(|(position, &key)| {
+                message.account_keys[usize::from(key)] == *relayer && Some(position) != payer_index
+            });
+        if misused {
+            return Err(reject("relayer is used as more than a rent payer"));
+        }
+    }
+    if missing_signers(transaction) != [*relayer] {
+        return Err(reject("only the relayer's signature may be missing"));
+    }
+    let verified = transaction.verify_with_results();
+    let valid = message
+        .account_keys
+        .iter()
+        .zip(verified)
+        .all(|(key, ok)| ok || key == relayer);
+    if !valid {
+        return Err(reject("a user signature is invalid"));
+    }
+    Ok(())
+}
+
+/// Vet, simulate, co-sign and submit a user-signed sponsored transaction.
+/// Fails without signing if the relayer would spend more than the policy
+/// allows.
+pub fn relay(
+    client: &RpcClient,
+    mut transaction: Transaction,
+    relayer: &dyn Signer,
+    policy: &RelayPolicy,
+) -> Result<Signature> {
+    let relayer_key = relayer.pubkey();
+    check_sponsored_transaction(&transaction, &relayer_key)?;
+
+    let report = simulate_transaction(client, &transaction)?;
+    if let Some(error) = report.error {
+        return Err(reject(&format!("simulation failed: {error}")));
+    }
+    // Simulation doesn't charge the fee, so the relayer's delta is rent alone
+    let fee = client.get_fee_for_message(&transaction.message)?;
+    let rent: i128 = report
+        .sol_deltas
+        .iter()
+        .filter(|delta| delta.account == relayer_key)
+        .map(|delta| -delta.delta)
+        .sum();
+    let spent = u64::try_from(rent.max(0))
+        .unwrap_or(u64::MAX)
+        .saturating_add(fee);
+    if spent > policy.max_lamports {
+        return Err(reject(&format!("costs the relayer {spent} lamports")));
+    }
+
+    let blockhash = transaction.message.recent_blockhash;
+    transaction.try_partial_sign(&[relayer], blockhash)?;
+    send_presigned(client, &transaction)
+}
+
+fn reject(reason: &str) -> ClientError {
+    ClientError::Relay(reason.to_string())
+}
@@ -0,0 +1,159 @@
+//! Dry-run a transaction before sending it: run `simulateTransaction`, decode
+//! the program error (if any) into its `DreamError` message, and diff every
+//! writable account's SOL and token balance against its current state.
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+};
+use solana_sdk::{account::Account, transaction::Transaction};
+
+use crate::Result;
+
+/// Size of an SPL Token / Token-2022 account before any extensions. Both
+/// programs start with `mint(32) owner(32) amount(8)`.
+const TOKEN_ACCOUNT_LEN: usize = 165;
+/// Token-2022 `AccountType::Account`.
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+#[derive(Clone, Debug)]
+pub struct SolDelta {
+    pub account: Pubkey,
+    pub delta: i128,
+}
+
+#[derive(Clone, Debug)]
+pub struct TokenDelta {
+    pub account: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    /// In base units of `mint`.
+    pub delta: i128,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SimulationReport {
+    /// `None` if the transaction would succeed.
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    /// Accounts whose balance would change; unchanged accounts are omitted.
+    pub sol_deltas: Vec<SolDelta>,
+    pub token_deltas: Vec<TokenDelta>,
+}
+
+impl SimulationReport {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Simulate `transaction` without verifying signatures, so it can be checked
+/// before anyone signs it. The blockhash is replaced with a recent one.
+pub fn simulate_transaction(
+    client: &RpcClient,
+    transaction: &Transaction,
+) -> Result<SimulationReport> {
+    let message = &transaction.message;
+    let writable: Vec<Pubkey> = (0..message.account_keys.len())
+        .filter(|&i| message.is_writable(i))
+        .map(|i| message.account_keys[i])
+        .collect();
+    let before = client.get_multiple_accounts(&writable)?;
+
+    let result = client
+        .simulate_transaction_with_config(
+            transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: writable.iter().map(Pubkey::to_string).collect(),
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?
+        .value;
+
+    let logs = result.logs.unwrap_or_default();
+    let mut report = SimulationReport {
+        error: result.err.map(|err| decode_program_error(&logs).unwrap_or_else(|| err.to_string())),
+        logs,
+        units_consumed: result.units_consumed,
+        ..SimulationReport::default()
+    };
+    // A failed simulation returns no post-state; there is nothing to diff.
+    let Some(after) = result.accounts else {
+        return Ok(report);
+    };
+
+    for ((account, before), after) in writable.iter().zip(before).zip(after) {
+        let after: Option<Account> = after.and_then(|ui| ui.decode());
+        let lamports = |account: &Option<Account>| account.as_ref().map_or(0, |a| a.lamports);
+        let sol = i128::from(lamports(&after)) - i128::from(lamports(&before));
+        if sol != 0 {
+            report.sol_deltas.push(SolDelta {
+                account: *account,
+                delta: sol,
+            });
+        }
+
+        let (before_token, after_token) = (token_balance(&before), token_balance(&after));
+        if let Some((mint, owner, _)) = after_token.or(before_token) {
+            let amount = |balance: Option<(Pubkey, Pubkey, u64)>| balance.map_or(0, |b| b.2);
+            let delta = i128::from(amount(after_token)) - i128::from(amount(before_token));
+            if delta != 0 {
+                report.token_deltas.push(TokenDelta {
+                    account: *account,
+                    mint,
+                    owner,
+                    delta,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// `(mint, owner, amount)` of an SPL Token or Token-2022 account.
+fn token_balance(account: &Option<Account>) -> Option<(Pubkey, Pubkey, u64)> {
+    let account = account.as_ref()?;
+    if account.owner != anchor_spl::token::ID && account.owner != anchor_spl::token_2022::ID {
+        return None;
+    }
+    let data = &account.data;
+    // Token-2022 mints with extensions are padded past the account length and
+    // tell themselves apart by the type byte after it.
+    let is_token_account = match data.len() {
+        TOKEN_ACCOUNT_LEN => true,
+        len if len > TOKEN_ACCOUNT_LEN => data[TOKEN_ACCOUNT_LEN] == ACCOUNT_TYPE_ACCOUNT,
+        _ => false,
+    };
+    if !is_token_account {
+        return None;
+    }
+    Some((
+        Pubkey::try_from(&data[..32]).ok()?,
+        Pubkey::try_from(&data[32..64]).ok()?,
+        u64::from_le_bytes(data[64..72].try_into().ok()?),
+    ))
+}
+
+/// The program's error as `"Name (number): message"`, from the line Anchor
+/// logs when an instruction fails:
+/// `AnchorError ... Error Code: Name. Error Number: 6000. Error Message: msg.`
+pub fn decode_program_error(logs: &[String]) -> Option<String> {
+    let line = logs.iter().rev().find(|line| line.contains("AnchorError"))?;
+    let field = |label: &str| -> Option<&str> {
+        let start = line.find(label)? + label.len();
+        let rest = &line[start..];
+        Some(rest.find(". Error ").map_or(rest.trim_end_matches('.'), |end| &rest[..end]))
+    };
+    let code = field("Error Code: ")?;
+    let number = field("Error Number: ")?;
+    let message = field("Error Message: ")?;
+    Some(format!("{code} ({number}): {message}"))
+}
@@ -0,0 +1,22 @@
+use anchor_lang::solana_program::{
+    address_lookup_table::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+};
+
+use crate::Result;
+
+/// Compile `instructions` into a v0 message, resolving accounts through
+/// `lookup_tables` (typically the program's table from `fetch_lookup_table`).
+/// Sign it with `VersionedTransaction::try_new` from `solana-sdk`.
+pub fn compile_v0(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedMessage> {
+    let message = v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)?;
+    Ok(VersionedMessage::V0(message))
+}
@@ -0,0 +1,89 @@
+//! Read the program's view instructions by simulating them and decoding the
+//! return data. Nothing is signed or sent.
+
+use anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction, AnchorDeserialize};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use dream_mind_lucid::{
+    views::{DreamerStats, ProgramCapabilities, StakeInfo, TreasuryStats},
+    ID,
+};
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
+use solana_sdk::{message::Message, transaction::Transaction};
+
+use crate::{instructions, pda::*, ClientError, Result};
+
+/// Simulate a single view instruction with `payer` as fee payer and decode
+/// the value it returned. `payer` only needs to exist; it doesn't sign.
+pub fn simulate_view<T: AnchorDeserialize>(
+    client: &RpcClient,
+    payer: &Pubkey,
+    instruction: Instruction,
+) -> Result<T> {
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(payer)));
+    let result = client
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?
+        .value;
+    if let Some(err) = result.err {
+        return Err(ClientError::View(err.to_string()));
+    }
+    let return_data = result
+        .return_data
+        .filter(|data| data.program_id == ID.to_string())
+        .ok_or_else(|| ClientError::View("no return data".to_string()))?;
+    let bytes = STANDARD
+        .decode(&return_data.data.0)
+        .map_err(|err| ClientError::View(err.to_string()))?;
+    T::try_from_slice(&bytes).map_err(|err| ClientError::View(err.to_string()))
+}
+
+pub fn get_treasury_stats(client: &RpcClient, payer: &Pubkey) -> Result<TreasuryStats> {
+    let treasury = crate::accounts::fetch_treasury(client)?;
+    simulate_view(client, payer, instructions::get_treasury_stats(&treasury.dream_mint))
+}
+
+pub fn get_dreamer_stats(client: &RpcClient, payer: &Pubkey, dreamer: &Pubkey) -> Result<DreamerStats> {
+    let lucid_mint = crate::accounts::fetch_treasury(client)?.lucid_mint;
+    let existing = client.get_multiple_accounts(&[
+        find_dreamer_profile_address(dreamer).0,
+        find_stake_address(dreamer, &lucid_mint).0,
+    ])?;
+    let instruction = instructions::get_dreamer_stats(
+        dreamer,
+        &lucid_mint,
+        existing[0].is_some(),
+        existing[1].is_some(),
+    );
+    simulate_view(client, payer, instruction)
+}
+
+pub fn get_stake_info(
+    client: &RpcClient,
+    payer: &Pubkey,
+    user: &Pubkey,
+    mint: &Pubkey,
+) -> Result<StakeInfo> {
+    simulate_view(client, payer, instructions::get_stake_info(user, mint))
+}
+
+pub fn get_capabilities(client: &RpcClient, payer: &Pubkey) -> Result<ProgramCapabilities> {
+    let existing = client.get_multiple_accounts(&[
+        find_upgrade_governance_address().0,
+        find_guardian_council_address().0,
+        find_compressed_stake_tree_address().0,
+        find_dream_archive_tree_address().0,
+    ])?;
+    let instruction = instructions::get_capabilities(
+        existing[0].is_some(),
+        existing[1].is_some(),
+        existing[2].is_some(),
+        existing[3].is_some(),
+    );
+    simulate_view(client, payer, instruction)
+}
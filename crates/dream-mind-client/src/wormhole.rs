@@ -0,0 +1,78 @@
+//! Wormhole core bridge helpers for relaying DREAM transfers.
+//!
+//! Outbound messages are posted by the program itself; relayers only need the
+//! core bridge accounts. Inbound VAAs must first be verified and posted with
+//! the standard Wormhole tooling (`verify_signatures` + `post_vaa`), after
+//! which `complete_bridge_in` takes the resulting `PostedVAA` account.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{keccak, system_program, sysvar},
+};
+use dream_mind_lucid::{accounts, wormhole::WORMHOLE_CORE_BRIDGE_ID};
+
+use crate::pda::find_emitter_address;
+
+/// Size of one guardian signature: index(1) + secp256k1 signature(65).
+const GUARDIAN_SIGNATURE_LEN: usize = 66;
+
+pub fn find_bridge_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"Bridge"], &WORMHOLE_CORE_BRIDGE_ID)
+}
+
+pub fn find_fee_collector_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_collector"], &WORMHOLE_CORE_BRIDGE_ID)
+}
+
+pub fn find_sequence_address(emitter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"Sequence", emitter.as_ref()], &WORMHOLE_CORE_BRIDGE_ID)
+}
+
+/// Core bridge accounts for a `post_message` from the program emitter.
+pub fn post_message_accounts(message: &Pubkey) -> accounts::WormholeAccounts {
+    let emitter = find_emitter_address().0;
+    accounts::WormholeAccounts {
+        bridge: find_bridge_config_address().0,
+        message: *message,
+        emitter,
+        sequence: find_sequence_address(&emitter).0,
+        fee_collector: find_fee_collector_address().0,
+        core_bridge_program: WORMHOLE_CORE_BRIDGE_ID,
+        clock: sysvar::clock::ID,
+        rent: sysvar::rent::ID,
+        system_program: system_program::ID,
+    }
+}
+
+/// The parts of a guardian-signed VAA a relayer needs to redeem it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedVaa {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub payload: Vec<u8>,
+    /// Address of the `PostedVAA` account once the VAA has been posted.
+    pub posted_vaa: Pubkey,
+}
+
+/// Parse a signed VAA as returned by the guardian REST API
+/// (`/v1/signed_vaa/{chain}/{emitter}/{sequence}`, base64-decoded).
+pub fn parse_signed_vaa(vaa: &[u8]) -> Option<SignedVaa> {
+    // version(1) guardian_set_index(4) signature_count(1) signatures(66 * n)
+    let signature_count = *vaa.get(5)? as usize;
+    let body = vaa.get(6 + signature_count * GUARDIAN_SIGNATURE_LEN..)?;
+
+    // timestamp(4) nonce(4) emitter_chain(2) emitter_address(32) sequence(8) consistency_level(1)
+    if body.len() < 51 {
+        return None;
+    }
+    let body_hash = keccak::hash(body).to_bytes();
+
+    Some(SignedVaa {
+        emitter_chain: u16::from_be_bytes(body[8..10].try_into().ok()?),
+        emitter_address: body[10..42].try_into().ok()?,
+        sequence: u64::from_be_bytes(body[42..50].try_into().ok()?),
+        payload: body[51..].to_vec(),
+        posted_vaa: dream_mind_lucid::wormhole::find_posted_vaa_address(&body_hash).0,
+    })
+}
@@ -0,0 +1,246 @@
+// Canonical token parameters (from project spec). Every program and off-chain
+// client should read these instead of hard-coding its own values.
+
+#[cfg(all(feature = "mainnet", feature = "devnet"))]
+compile_error!("features `mainnet` and `devnet` are mutually exclusive");
+
+#[cfg(feature = "mainnet")]
+pub const CLUSTER: &str = "mainnet-beta";
+#[cfg(not(feature = "mainnet"))]
+pub const CLUSTER: &str = "devnet";
+
+pub const TOKEN_DECIMALS: u8 = 9;
+pub const TOKEN_UNIT: u64 = 1_000_000_000; // 10^TOKEN_DECIMALS
+
+pub const DREAM_TOTAL_SUPPLY: u64 = 777_777_777 * TOKEN_UNIT; // 777,777,777 DREAM
+pub const SMIND_TOTAL_SUPPLY: u64 = 777_777_777 * TOKEN_UNIT; // 777,777,777 SMIND
+pub const LUCID_TOTAL_SUPPLY: u64 = 333_333_333 * TOKEN_UNIT; // 333,333,333 LUCID
+
+#[cfg(feature = "mainnet")]
+pub const DREAM_REWARD_PER_RECORD: u64 = 10 * TOKEN_UNIT; // 10 DREAM tokens per dream record
+#[cfg(not(feature = "mainnet"))]
+pub const DREAM_REWARD_PER_RECORD: u64 = 100 * TOKEN_UNIT; // Devnet: 100 DREAM per record for faster end-to-end testing
+
+// DREAM rewards are paid from the RewardPool vault; outside the devnet faucet,
+// `top_up_pool` is the only mint path and may mint at most this much per period
+pub const REWARD_POOL_PERIOD_SECS: i64 = 30 * 24 * 60 * 60; // 30 days
+pub const MAX_REWARD_POOL_TOP_UP_PER_PERIOD: u64 = 10_000_000 * TOKEN_UNIT;
+
+// What `faucet_mint` gives a wallet, and how often; it is disabled unless the
+// program is built with the `devnet` feature
+pub const FAUCET_DREAM_AMOUNT: u64 = 1_000 * TOKEN_UNIT;
+pub const FAUCET_SMIND_AMOUNT: u64 = 100 * TOKEN_UNIT;
+pub const FAUCET_LUCID_AMOUNT: u64 = 10 * TOKEN_UNIT;
+pub const FAUCET_COOLDOWN_SECS: i64 = 24 * 60 * 60; // 1 day
+
+// Bounds for the self-reported dream record metadata
+pub const MAX_LUCIDITY_SCORE: u8 = 10;
+pub const MAX_SLEEP_DURATION_MINUTES: u16 = 24 * 60;
+
+// Length of an EpochStats bucket
+pub const EPOCH_STATS_PERIOD_SECS: i64 = 24 * 60 * 60; // 1 day
+
+// Dreamers kept on the seasonal leaderboard
+pub const LEADERBOARD_SIZE: usize = 100;
+
+// Daily snapshots kept in the StatsRing
+pub const STATS_RING_SIZE: usize = 365;
+
+// Co-dreamers a shared dream can list besides its recorder
+pub const MAX_CO_DREAMERS: usize = 4;
+
+// Dreams one `record_dreams_batch` can record
+pub const MAX_DREAMS_PER_BATCH: usize = 4;
+
+// Dreams waiting for validation. Each whole staked token counts as this
+// many seconds of waiting when ranking the queue.
+pub const VALIDATION_QUEUE_SIZE: usize = 128;
+pub const VALIDATION_PRIORITY_SECS_PER_STAKED_TOKEN: u64 = 10 * 60; // 10 minutes
+// Highest score `validate_dream` accepts; 0 is reserved for unscored
+pub const MAX_VALIDATION_SCORE: u8 = 100;
+// A disputed verdict further than this from the consensus score is lost
+pub const VALIDATION_DISPUTE_TOLERANCE: u8 = 20;
+// Lamports a pinning oracle bonds to post an optimistic score, and how long
+// anyone may void the score with a fraud proof before it can be finalized
+pub const OPTIMISTIC_SCORE_BOND: u64 = 100_000_000; // 0.1 SOL
+pub const OPTIMISTIC_CHALLENGE_WINDOW_SECS: i64 = 24 * 60 * 60; // 1 day
+// Validated dreams `splice_from_dreams` consumes to mint one Oneirobot; more
+// would not fit in a legacy transaction
+pub const SPLICE_DREAM_COUNT: usize = 3;
+// Lowest validation score that earns a dream an Oneirobot certificate
+pub const CERTIFICATE_MIN_SCORE: u8 = 95;
+// XP an equipped companion Oneirobot gains for each dream recorded with it
+pub const COMPANION_XP_PER_DREAM: u64 = 10;
+
+// Dreamer reputation runs from -MAX_REPUTATION to MAX_REPUTATION and starts
+// neutral at 0. It drifts back toward 0 by REPUTATION_DECAY_PER_DAY.
+pub const MAX_REPUTATION: i16 = 1_000;
+pub const REPUTATION_DECAY_PER_DAY: i16 = 5;
+// A validation moves reputation by half the score's distance from this
+pub const REPUTATION_NEUTRAL_VALIDATION_SCORE: u8 = 50;
+// Reputation lost when the authority flags a dream
+pub const REPUTATION_FLAG_PENALTY: i16 = 200;
+// Reward multiplier (bps) per reputation band; a band starts at its floor
+pub const REPUTATION_BAND_FLOORS: [i16; 4] = [-MAX_REPUTATION, -500, 0, 500];
+pub const REPUTATION_BAND_REWARD_BPS: [u16; 4] = [2_500, 5_000, 10_000, 12_000];
+// Largest reward one dream can pay, at the top reputation band
+pub const MAX_DREAM_REWARD_PER_RECORD: u64 =
+    DREAM_REWARD_PER_RECORD * REPUTATION_BAND_REWARD_BPS[3] as u64 / 10_000;
+// A profile must be this old and have this many validated dreams before
+// its dreamer can open prediction markets, to keep out throwaway wallets
+pub const MIN_ESTABLISHED_ACCOUNT_AGE_SECS: i64 = 7 * 24 * 60 * 60; // 7 days
+pub const MIN_ESTABLISHED_VALIDATED_DREAMS: u32 = 1;
+// Multiplier on the reward of a dreamer's first validated dreams, paid as a
+// bonus at validation: 2x for the first, tapering off by the eleventh
+pub const ONBOARDING_REWARD_MULTIPLIER_BPS: [u16; 10] = [
+    20_000, 15_000, 14_000, 13_000, 12_500, 12_000, 11_500, 11_000, 10_500, 10_250,
+];
+// Share of a validated dream's rewards that goes to the interpreter who
+// attached an analysis, the dreamer keeping the rest (80/20)
+pub const INTERPRETER_SHARE_BPS: u16 = 2_000;
+
+// Levels in the compressed SMIND stake tree (2^20 stakes)
+pub const COMPRESSED_STAKE_TREE_DEPTH: usize = 20;
+
+// How old a validated dream must be before `archive_dream` can close it
+pub const DREAM_ARCHIVE_MIN_AGE_SECS: i64 = 180 * 24 * 60 * 60; // 180 days
+
+// How long a `record_dream` idempotency key blocks retries before anyone can
+// close its nonce
+pub const IDEMPOTENCY_NONCE_TTL_SECS: i64 = 24 * 60 * 60; // 1 day
+
+// Kinds of permissionless crank paid from the CrankPool; see `CrankKind`
+pub const CRANK_KIND_COUNT: usize = 2;
+
+// Ceiling on each per-instruction protocol fee in the FeeVault
+pub const MAX_PROTOCOL_FEE_LAMPORTS: u64 = 10_000_000; // 0.01 SOL
+
+// Ceiling on governance-set DREAM to SMIND/LUCID conversion rates for
+// `claim_reward_as`, in bps of the DREAM burned
+pub const MAX_REWARD_CONVERSION_RATE_BPS: u32 = 1_000_000; // 100x
+
+// Ceiling on the InsuranceFund's slice of swept SOL fees and of market fees
+pub const MAX_INSURANCE_FEE_SHARE_BPS: u16 = 5_000; // 50%
+
+// Actions a session key can sign for its wallet, as `SessionKey::scope` bits
+pub const SESSION_SCOPE_RECORD_DREAM: u8 = 1 << 0;
+pub const SESSION_SCOPES: u8 = SESSION_SCOPE_RECORD_DREAM;
+// Variable-length extras `set_dream_extras` appends to a DreamRecord. Solana
+// grows an account by at most 10 KiB per instruction
+pub const MAX_DREAM_EXTRAS_LEN: usize = 10 * 1024;
+pub const MAX_DREAM_TAGS: usize = 32;
+// Longest string `intern_string` stores, which bounds dream tags and
+// quantum core names
+pub const MAX_INTERNED_STRING_LEN: usize = 32;
+
+// Longest a session key can live
+pub const MAX_SESSION_KEY_DURATION_SECS: i64 = 7 * 24 * 60 * 60; // 1 week
+
+// Unclaimed co-dreamer shares keep their full value for the grace period,
+// then decay linearly and are forfeited at the expiry
+pub const REWARD_CLAIM_GRACE_SECS: i64 = 90 * 24 * 60 * 60; // 90 days
+pub const REWARD_CLAIM_EXPIRY_SECS: i64 = 180 * 24 * 60 * 60; // 180 days
+
+// Research dataset licensing, paid in LUCID. The rest of each payment is
+// shared equally among the dreamers consenting at the time of purchase.
+pub const RESEARCH_LICENSE_PRICE: u64 = 1_000 * TOKEN_UNIT;
+pub const RESEARCH_LICENSE_DURATION_SECS: i64 = 365 * 24 * 60 * 60; // 1 year
+pub const RESEARCH_TREASURY_SHARE_BPS: u64 = 3_000; // 30%
+
+// Per-dream commercial licenses, paid in LUCID at the dreamer's price. The
+// treasury takes its share first; co-authors split the rest with the dreamer.
+pub const DREAM_LICENSE_TYPE_COUNT: usize = 2;
+pub const DREAM_LICENSE_DURATION_SECS: i64 = 365 * 24 * 60 * 60; // 1 year
+pub const DREAM_LICENSE_TREASURY_SHARE_BPS: u64 = 1_000; // 10%
+pub const MAX_ROYALTY_CO_AUTHORS: usize = 4;
+
+// DREAM prediction markets. The fee is taken from each purchase and sent to
+// the treasury; the rest of every pool is paid out to the winning outcome.
+pub const MAX_MARKET_OUTCOMES: usize = 8;
+pub const MARKET_FEE_BPS: u64 = 200; // 2%
+// Registered validators' slice of market fees; at most half, so it and the
+// insurance fund's slice always fit in one fee
+pub const MAX_VALIDATOR_FEE_SHARE_BPS: u16 = 5_000; // 50%
+pub const MAX_VALIDATOR_REWARD_PER_VALIDATION: u64 = DREAM_REWARD_PER_RECORD;
+
+// DREAM payouts above the threshold are streamed: they vest linearly over
+// the duration and are withdrawn with `claim_streamed`
+pub const REWARD_STREAM_THRESHOLD: u64 = 10_000 * TOKEN_UNIT;
+pub const REWARD_STREAM_DURATION_SECS: i64 = 30 * 24 * 60 * 60; // 30 days
+
+// Dream duels: both dreamers stake LUCID and must record and submit a dream
+// within the window after the duel is accepted. The fee comes out of the pot.
+pub const DUEL_SUBMISSION_WINDOW_SECS: i64 = 24 * 60 * 60; // 1 day
+pub const DUEL_FEE_BPS: u64 = 500; // 5%
+
+// Milestones a treasury grant can be split into
+pub const MAX_GRANT_MILESTONES: usize = 8;
+
+// Largest share of a dreamer's DREAM rewards they can route to a charity
+pub const MAX_CHARITY_SHARE_BPS: u16 = 10_000; // 100%
+
+// Dreams per sleep session that earn DREAM; later ones are recorded unrewarded
+pub const MAX_REWARDED_DREAMS_PER_SESSION: u32 = 5;
+
+// LUCID stake thresholds for each access level
+pub const LUCID_PREMIUM_THRESHOLD: u64 = TOKEN_UNIT; // > 1 LUCID
+pub const LUCID_VIP_THRESHOLD: u64 = 10 * TOKEN_UNIT; // > 10 LUCID
+pub const LUCID_QUANTUM_THRESHOLD: u64 = 100 * TOKEN_UNIT; // > 100 LUCID
+
+// Access levels that come with a non-transferable credential token (VIP,
+// Quantum). A stake holds the credential of every level it reaches.
+pub const TIER_CREDENTIAL_LEVELS: [u8; 2] = [3, 4];
+
+// OneirobotNFT collection parameters
+pub const ONEIROBOT_MAX_SUPPLY: u64 = 10_000;
+pub const ONEIROBOT_ROYALTY_BPS: u16 = 500; // 5% royalty
+pub const ONEIROBOT_MAX_URI_LEN: usize = 200; // Token Metadata's URI limit
+// Quantum cores a trait season can offer, and the longest core name
+pub const ONEIROBOT_MAX_SEASON_CORES: usize = 8;
+pub const ONEIROBOT_MAX_CORE_NAME_LEN: usize = 32;
+// Quantum core of the certificates minted for top-scored dreams; no trait
+// season may offer it
+pub const ONEIROBOT_CERTIFICATE_CORE: &str = "Quantum Core Certificate";
+// SMIND locked per point of Oneirobot mind_strength boost, the most points
+// one boost adds, and how long the SMIND stays locked
+pub const ONEIROBOT_SMIND_PER_BOOST_POINT: u64 = 10 * TOKEN_UNIT;
+pub const ONEIROBOT_MAX_MIND_BOOST: u8 = 25;
+pub const ONEIROBOT_BOOST_DURATION_SECS: i64 = 7 * 24 * 60 * 60; // 7 days
+// Most edition prints governance can allow of one legendary Oneirobot
+pub const ONEIROBOT_MAX_EDITION_PRINTS: u64 = 100;
+// Longest Oneirobot name and lore CID, the DREAM burned to name one, and
+// how long a name stays before it can change
+pub const ONEIROBOT_MAX_NAME_LEN: usize = 32;
+pub const ONEIROBOT_MAX_LORE_CID_LEN: usize = 64;
+pub const ONEIROBOT_NAME_BURN: u64 = 100 * TOKEN_UNIT;
+pub const ONEIROBOT_RENAME_COOLDOWN_SECS: i64 = 30 * 24 * 60 * 60; // 30 days
+
+// DREAM bridge (Wormhole core messages). Payloads follow the Token Bridge
+// layouts so EVM contracts can reuse its parsers.
+pub const DREAM_TOKEN_SYMBOL: &str = "DREAM";
+pub const DREAM_TOKEN_NAME: &str = "Dream-Mind-Lucid DREAM";
+pub const WORMHOLE_CHAIN_ID_SOLANA: u16 = 1;
+pub const BRIDGE_PAYLOAD_TRANSFER: u8 = 1;
+pub const BRIDGE_PAYLOAD_ATTESTATION: u8 = 2;
+pub const BRIDGE_PAYLOAD_DREAM_MIRROR: u8 = 3;
+
+// Delay between a governance vote approving the upgrade-authority freeze
+// and `finalize_immutable` becoming callable
+pub const FINALIZE_IMMUTABLE_TIMELOCK_SECS: i64 = 2 * 24 * 60 * 60; // 48 hours
+// Guardian council: seats, and how many guardians must co-sign a pause or a
+// cancellation of the queued freeze
+pub const GUARDIAN_COUNCIL_SIZE: usize = 5;
+pub const GUARDIAN_THRESHOLD: usize = 3;
+
+// Most approved oracles an oracle quorum can require to co-sign one
+// attestation
+pub const MAX_ORACLE_QUORUM: u8 = 5;
+
+// Bits of `ProgramCapabilities::capabilities`, so clients can feature-detect
+// a deployment. New modules take the next free bit; bits are never reused.
+pub const CAPABILITY_GOVERNANCE: u64 = 1 << 0;
+pub const CAPABILITY_COMPRESSION: u64 = 1 << 1;
+// Reserved; the program doesn't draw VRF randomness yet
+pub const CAPABILITY_VRF: u64 = 1 << 2;
+pub const CAPABILITY_MARKETPLACE: u64 = 1 << 3;
+pub const CAPABILITY_DEVNET_FAUCET: u64 = 1 << 4;
@@ -0,0 +1,309 @@
+use anchor_lang::prelude::*;
+
+/// Unified error codes for every Dream-Mind-Lucid program.
+/// New variants are appended so existing codes stay stable.
+#[error_code]
+pub enum DreamError {
+    #[msg("Insufficient LUCID tokens for access")]
+    InsufficientLucidAccess,
+    #[msg("Dream content too large")]
+    DreamContentTooLarge,
+    #[msg("MEV protection failed")]
+    MevProtectionFailed,
+    #[msg("Invalid access level")]
+    InvalidAccessLevel,
+    #[msg("Unauthorized access - not the program authority")]
+    UnauthorizedAuthority,
+    #[msg("Legacy dream storage account is invalid")]
+    InvalidLegacyStorage,
+    #[msg("Legacy dream storage already migrated")]
+    LegacyAlreadyMigrated,
+    #[msg("Invalid dreamer")]
+    InvalidDreamer,
+    #[msg("Storage not initialized")]
+    StorageNotInitialized,
+    #[msg("Not a syndicate master - minting restricted")]
+    NotSyndicateMaster,
+    #[msg("Maximum supply reached")]
+    MaxSupplyReached,
+    #[msg("Minting is currently disabled")]
+    MintingDisabled,
+    #[msg("Invalid metadata URI")]
+    InvalidMetadataUri,
+    #[msg("NFT attributes not found")]
+    AttributesNotFound,
+    #[msg("Foreign emitter is not registered or does not match")]
+    InvalidForeignEmitter,
+    #[msg("Wormhole message is malformed or not addressed to this program")]
+    InvalidBridgeMessage,
+    #[msg("Bridge amount must be greater than zero")]
+    InvalidBridgeAmount,
+    #[msg("Message was not delivered by the configured messaging adapter")]
+    InvalidDeliveryAuthority,
+    #[msg("Remote sender is not trusted for this origin")]
+    UntrustedRemote,
+    #[msg("Remote message is malformed or was already processed")]
+    InvalidRemoteMessage,
+    #[msg("Lookup table does not match the program's lookup table")]
+    InvalidLookupTable,
+    #[msg("Program info field exceeds its maximum length")]
+    InvalidProgramInfo,
+    #[msg("Making the program immutable has not been approved by governance")]
+    FinalizeNotApproved,
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Dream metadata is out of range")]
+    InvalidDreamMetadata,
+    #[msg("Sleep session is already closed")]
+    SessionClosed,
+    #[msg("Season score does not belong to the current season")]
+    SeasonMismatch,
+    #[msg("Epoch is not finished or its leaf count does not match its stats")]
+    InvalidEpochCommitment,
+    #[msg("No dreamers have consented to research use")]
+    NoConsentingDreamers,
+    #[msg("Research consent is already set to this value")]
+    ResearchConsentUnchanged,
+    #[msg("Market needs 2 to 8 outcomes and a future close time")]
+    InvalidMarket,
+    #[msg("Outcome is out of range for this market")]
+    InvalidOutcome,
+    #[msg("Market is closed to new positions")]
+    MarketClosed,
+    #[msg("Market is still open")]
+    MarketStillOpen,
+    #[msg("Market is already resolved")]
+    MarketAlreadyResolved,
+    #[msg("Market is not resolved yet")]
+    MarketNotResolved,
+    #[msg("Market position was already claimed")]
+    PositionAlreadyClaimed,
+    #[msg("Duel opponent, stake, or dream is invalid")]
+    InvalidDuel,
+    #[msg("Duel is not in a state that allows this action")]
+    DuelUnavailable,
+    #[msg("Duel dream was already submitted or the window has closed")]
+    DuelSubmissionClosed,
+    #[msg("Duel submission window is still open")]
+    DuelStillOpen,
+    #[msg("Capsule unlock time must be in the future")]
+    InvalidUnlockTime,
+    #[msg("Dream capsule is still locked or already revealed")]
+    CapsuleLocked,
+    #[msg("CID does not match the sealed content hash")]
+    CapsuleCommitmentMismatch,
+    #[msg("Charity is not approved or does not match the dreamer's split")]
+    InvalidCharity,
+    #[msg("Charity share exceeds 100%")]
+    InvalidCharityShare,
+    #[msg("Payout is above the streaming threshold and needs a reward stream")]
+    RewardStreamRequired,
+    #[msg("Validation queue is empty")]
+    ValidationQueueEmpty,
+    #[msg("Stake amount must be greater than zero")]
+    InvalidStakeAmount,
+    #[msg("Compressed stake tree is full")]
+    StakeTreeFull,
+    #[msg("Compressed stake proof does not match the tree")]
+    InvalidStakeProof,
+    #[msg("The dreamer does not offer this license type")]
+    LicenseNotOffered,
+    #[msg("Royalty shares exceed the co-author limit or 100%")]
+    InvalidRoyaltySplit,
+    #[msg("Royalty accounts do not match the license terms")]
+    InvalidRoyaltyAccount,
+    #[msg("Co-dreamers must be distinct, exclude the dreamer, and number at most 4")]
+    InvalidCoDreamers,
+    #[msg("Signer is not a co-dreamer of this dream")]
+    NotACoDreamer,
+    #[msg("Co-authorship already confirmed")]
+    CoauthorshipAlreadyConfirmed,
+    #[msg("Language must be a lowercase ISO 639-1 code")]
+    InvalidLanguageCode,
+    #[msg("Bounty amount must be greater than zero")]
+    InvalidBountyAmount,
+    #[msg("Annotation already approved")]
+    AnnotationAlreadyApproved,
+    #[msg("Translation bounty already paid")]
+    TranslationBountyClaimed,
+    #[msg("Reward claim window has expired")]
+    RewardExpired,
+    #[msg("Rewards have not expired yet")]
+    RewardNotExpired,
+    #[msg("Protocol fee exceeds the maximum")]
+    ProtocolFeeTooHigh,
+    #[msg("Validation score is out of range")]
+    InvalidValidationScore,
+    #[msg("Pinning oracle is not approved")]
+    PinningOracleNotApproved,
+    #[msg("Missing or mismatched ed25519 pin attestation")]
+    InvalidPinAttestation,
+    #[msg("Reward pool top-up exceeds this period's emission cap")]
+    RewardPoolCapExceeded,
+    #[msg("Reward pool cannot cover this reward")]
+    RewardPoolDepleted,
+    #[msg("Insurance fee share exceeds the maximum")]
+    InsuranceShareTooHigh,
+    #[msg("Shortfall target is not depleted")]
+    NoShortfall,
+    #[msg("Insurance fund cannot cover this amount")]
+    InsufficientInsuranceFunds,
+    #[msg("Shortfall target account is missing")]
+    MissingShortfallTarget,
+    #[msg("Conversion rate exceeds the maximum")]
+    ConversionRateTooHigh,
+    #[msg("Conversion to this token is disabled")]
+    ConversionDisabled,
+    #[msg("Claim amount is zero or exceeds the dream's reward")]
+    InvalidClaimAmount,
+    #[msg("Account is not a legacy stake")]
+    InvalidLegacyStake,
+    #[msg("Missing or mismatched ed25519 dreamer signature")]
+    InvalidDreamerSignature,
+    #[msg("Relay nonce does not match the dreamer's next nonce")]
+    InvalidRelayNonce,
+    #[msg("Session scope is empty or has unknown bits")]
+    InvalidSessionScope,
+    #[msg("Session expiry is in the past or beyond the maximum duration")]
+    InvalidSessionExpiry,
+    #[msg("Session key is expired or not scoped for this action")]
+    SessionKeyNotAuthorized,
+    #[msg("Dream extras exceed the tag or size limits")]
+    DreamExtrasTooLarge,
+    #[msg("Treasury bump is already stored")]
+    TreasuryBumpAlreadyStored,
+    #[msg("Dream sponsorship is disabled")]
+    SponsorshipDisabled,
+    #[msg("Sponsor pool cannot cover this dreamer")]
+    SponsorPoolEmpty,
+    #[msg("Only dreamers without a profile can be sponsored")]
+    NotNewDreamer,
+    #[msg("Sponsorship must be followed by the dreamer's record_dream")]
+    SponsoredDreamMissing,
+    #[msg("Batch needs 1 to MAX_DREAMS_PER_BATCH dreams and their record accounts")]
+    InvalidDreamBatch,
+    #[msg("No tier credential exists for this access level")]
+    InvalidCredentialLevel,
+    #[msg("Trait season needs 1 to 8 named quantum cores and ranges within 1-100")]
+    InvalidTraitSeason,
+    #[msg("A boost locks at least ONEIROBOT_SMIND_PER_BOOST_POINT SMIND")]
+    InvalidBoostAmount,
+    #[msg("Signer does not hold this Oneirobot")]
+    NotNftOwner,
+    #[msg("Boost has not expired yet")]
+    BoostNotExpired,
+    #[msg("Snapshot needs 1 to total_minted holders, a past slot and a nonzero amount")]
+    InvalidHolderSnapshot,
+    #[msg("Holder proof does not match the snapshot root")]
+    InvalidHolderProof,
+    #[msg("Validator gate dream level must be at most 100")]
+    InvalidValidatorGate,
+    #[msg("Oneirobot token account or attributes are missing or don't match")]
+    InvalidOneirobotAccounts,
+    #[msg("Validator does not hold an Oneirobot with the gate's dream level")]
+    ValidatorNotEligible,
+    #[msg("Validator still meets the validator gate")]
+    ValidatorStillEligible,
+    #[msg("Edition prints must number 1 to ONEIROBOT_MAX_EDITION_PRINTS")]
+    InvalidEditionConfig,
+    #[msg("A legendary Oneirobot must be minted to its edition config")]
+    LegendaryNotEscrowed,
+    #[msg("All allowed editions have been printed")]
+    EditionsSoldOut,
+    #[msg("Creator accounts must match the master metadata's creators")]
+    CreatorAccountsMismatch,
+    #[msg("Names are 1 to ONEIROBOT_MAX_NAME_LEN letters, digits, spaces, - or _")]
+    InvalidOneirobotName,
+    #[msg("Lore CIDs are at most ONEIROBOT_MAX_LORE_CID_LEN bytes")]
+    InvalidLoreCid,
+    #[msg("Another Oneirobot already has this name")]
+    OneirobotNameTaken,
+    #[msg("This Oneirobot was named too recently")]
+    RenameOnCooldown,
+    #[msg("On-chain metadata doesn't fit in a metadata URI")]
+    OnchainMetadataTooLong,
+    #[msg("Splicing takes SPLICE_DREAM_COUNT of the dreamer's validated dreams")]
+    InvalidSpliceDreams,
+    #[msg("Dream has already been spliced into an Oneirobot")]
+    DreamAlreadySpliced,
+    #[msg("Spliced attributes must come from Dream-Mind-Lucid and be 1 to 100")]
+    InvalidSpliceAttributes,
+    #[msg("Co-dreamer shares and reward streams are tracked in the treasury health account")]
+    TreasuryHealthRequired,
+    #[msg("Treasury failed its last reconciliation")]
+    TreasuryUnhealthy,
+    #[msg("Dreamer profile is too new or has no validated dream")]
+    DreamerNotEstablished,
+    #[msg("Dreamer profile already has the current layout")]
+    DreamerProfileCurrent,
+    #[msg("Language must be an ISO 639-1 code in lowercase letters")]
+    InvalidDreamLanguage,
+    #[msg("Missing or mismatched ed25519 classification attestation")]
+    InvalidClassificationAttestation,
+    #[msg("Validator fee share or per-validation reward is above its maximum")]
+    InvalidValidatorRewards,
+    #[msg("Validator reward and stats accounts need the validator's registration")]
+    MissingValidatorRegistration,
+    #[msg("Grant needs 1 to MAX_GRANT_MILESTONES milestones, each above zero")]
+    InvalidGrantMilestones,
+    #[msg("Grant is completed or clawed back")]
+    GrantNotActive,
+    #[msg("Guardians must be GUARDIAN_COUNCIL_SIZE distinct, non-default keys")]
+    InvalidGuardians,
+    #[msg("Fewer than GUARDIAN_THRESHOLD guardians signed")]
+    GuardianThresholdNotMet,
+    #[msg("No approved freeze is waiting on its timelock")]
+    NoQueuedFinalize,
+    #[msg("Dream archive tree is full")]
+    DreamArchiveTreeFull,
+    #[msg("Dream is too recent to archive")]
+    DreamTooRecentToArchive,
+    #[msg("Stats were already rolled for today")]
+    StatsAlreadyRolled,
+    #[msg("Validator already claimed this epoch's cap")]
+    ValidatorClaimCapReached,
+    #[msg("Pass the idempotency nonce account exactly when giving an idempotency key")]
+    InvalidIdempotencyKey,
+    #[msg("Idempotency nonce has not expired")]
+    IdempotencyNonceLive,
+    #[msg("Program is paused by the guardian council")]
+    ProgramPaused,
+    #[msg("Dreamer does not hold the companion Oneirobot")]
+    CompanionNotHeld,
+    #[msg("Optimistic score's challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Optimistic score's challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("Optimistic score was voided by a fraud proof")]
+    OptimisticScoreVoided,
+    #[msg("Fraud proof does not show the score is invalid")]
+    InvalidFraudProof,
+    #[msg("Dream has already been validated")]
+    DreamAlreadyValidated,
+    #[msg("Oracle quorum must be 1 to MAX_ORACLE_QUORUM")]
+    InvalidOracleQuorum,
+    #[msg("Fewer approved oracles signed than the oracle quorum requires")]
+    OracleQuorumNotMet,
+    #[msg("The faucet only exists in devnet builds")]
+    FaucetDisabled,
+    #[msg("This wallet used the faucet too recently")]
+    FaucetCooldown,
+    #[msg("The interpreter has already attached an analysis")]
+    InterpretationLocked,
+    #[msg("Analysis CID must be non-empty and fit an IPFS hash")]
+    InvalidAnalysisCid,
+    #[msg("Pass the interpreter's DREAM account to pay the interpretation")]
+    MissingInterpreterAccount,
+    #[msg("Dream's validation score is below CERTIFICATE_MIN_SCORE")]
+    ScoreBelowCertificateThreshold,
+    #[msg("Interned strings must be 1 to MAX_INTERNED_STRING_LEN bytes")]
+    InvalidInternedString,
+    #[msg("A different string is already interned under this id")]
+    InternedStringCollision,
+    #[msg("Nothing is claimable")]
+    NothingToClaim,
+    #[msg("Name an interpreter other than the dreamer, with the interpretation account")]
+    InvalidInterpreter,
+    #[msg("Dream record already has the current layout")]
+    DreamRecordCurrent,
+}
@@ -0,0 +1,5 @@
+pub mod constants;
+pub mod error;
+
+pub use constants::*;
+pub use error::*;
@@ -0,0 +1,55 @@
+//! Typed wrappers over `dream_mind_lucid::cpi`.
+
+use anchor_lang::prelude::*;
+use dream_mind_common::{
+    constants::{
+        MAX_CO_DREAMERS, MAX_LUCIDITY_SCORE, MAX_SLEEP_DURATION_MINUTES, MAX_VALIDATION_SCORE,
+    },
+    DreamError,
+};
+use dream_mind_lucid::{cpi, DreamMetadata};
+
+pub use dream_mind_lucid::cpi::accounts;
+
+fn check_program(program: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*program.key, dream_mind_lucid::ID, ErrorCode::InvalidProgramId);
+    Ok(())
+}
+
+/// Record a dream of `ctx.accounts.dreamer`, who must sign, e.g. as a PDA of
+/// the calling program. The record PDA is seeded by the treasury's
+/// `total_dreams_recorded`, so read the treasury just before the call. With
+/// `idempotency_key`, pass its nonce PDA as `ctx.accounts.idempotency_nonce`;
+/// with `interpreter`, pass `interpretation` and `treasury_health`.
+pub fn record_dream<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::RecordDream<'info>>,
+    content_hash: [u8; 32],
+    metadata: DreamMetadata,
+    co_dreamers: Vec<Pubkey>,
+    idempotency_key: Option<u64>,
+    interpreter: Option<Pubkey>,
+) -> Result<()> {
+    check_program(&ctx.program)?;
+    require!(
+        metadata.lucidity_score <= MAX_LUCIDITY_SCORE
+            && metadata.sleep_duration_minutes <= MAX_SLEEP_DURATION_MINUTES,
+        DreamError::InvalidDreamMetadata
+    );
+    require!(co_dreamers.len() <= MAX_CO_DREAMERS, DreamError::InvalidCoDreamers);
+    require!(interpreter != Some(*ctx.accounts.dreamer.key), DreamError::InvalidInterpreter);
+    cpi::record_dream(ctx, content_hash, metadata, co_dreamers, idempotency_key, interpreter)
+}
+
+/// Score a dream that has been confirmed pinned. `ctx.accounts.authority`
+/// must be the treasury authority, which stands in for validators.
+pub fn validate_dream<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::ValidateDream<'info>>,
+    score: u8,
+) -> Result<()> {
+    check_program(&ctx.program)?;
+    require!(
+        (1..=MAX_VALIDATION_SCORE).contains(&score),
+        DreamError::InvalidValidationScore
+    );
+    cpi::validate_dream(ctx, score)
+}
@@ -0,0 +1,16 @@
+//! CPI interface for Anchor programs that compose with Dream-Mind-Lucid and
+//! OneirobotNFT, without vendoring either program.
+//!
+//! [`dream`] wraps the core program's Anchor-generated `cpi` module, whose
+//! account structs are re-exported as [`dream::accounts`]. OneirobotNFT links
+//! Metaplex, so [`oneirobot`] builds its instruction by hand in the program's
+//! account order instead of depending on that crate.
+//!
+//! The wrappers check the target program and reject arguments the program
+//! would, with the program's own errors, before spending compute on the CPI.
+
+pub mod dream;
+pub mod oneirobot;
+
+pub use dream_mind_common::DreamError;
+pub use dream_mind_lucid::{DreamMetadata, ID};
@@ -0,0 +1,123 @@
+//! OneirobotNFT's `mint_oneirobot`, built by hand in the program's
+//! `MintOneirobot` account order.
+//!
+//! Minting is limited to syndicate masters, so the caller's signer (usually
+//! a PDA of the calling program) must have been added with
+//! `add_syndicate_master`. Legendary and spliced mints aren't covered: the
+//! optional `edition_config` and `splicer` accounts are always left out.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{hash::hash, instruction::Instruction, program::invoke_signed},
+};
+
+pub mod accounts {
+    use anchor_lang::prelude::*;
+
+    /// `MintOneirobot` without its optional accounts.
+    pub struct MintOneirobot<'info> {
+        pub oneirobot_state: AccountInfo<'info>,
+        pub nft_attributes: AccountInfo<'info>,
+        /// New mint keypair or PDA; must sign
+        pub mint: AccountInfo<'info>,
+        pub token_account: AccountInfo<'info>,
+        pub metadata: AccountInfo<'info>,
+        pub master_edition: AccountInfo<'info>,
+        /// A syndicate master; pays for the mint and the fee
+        pub minter: AccountInfo<'info>,
+        pub recipient: AccountInfo<'info>,
+        /// Signs as mint and update authority
+        pub mint_authority: AccountInfo<'info>,
+        pub fee_vault: AccountInfo<'info>,
+        pub rent: AccountInfo<'info>,
+        pub system_program: AccountInfo<'info>,
+        pub token_program: AccountInfo<'info>,
+        pub associated_token_program: AccountInfo<'info>,
+        pub metadata_program: AccountInfo<'info>,
+    }
+
+    impl ToAccountMetas for MintOneirobot<'_> {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(*self.oneirobot_state.key, false),
+                AccountMeta::new(*self.nft_attributes.key, false),
+                AccountMeta::new(*self.mint.key, true),
+                AccountMeta::new(*self.token_account.key, false),
+                AccountMeta::new(*self.metadata.key, false),
+                AccountMeta::new(*self.master_edition.key, false),
+                AccountMeta::new(*self.minter.key, true),
+                AccountMeta::new_readonly(*self.recipient.key, false),
+                AccountMeta::new_readonly(*self.mint_authority.key, true),
+                AccountMeta::new(*self.fee_vault.key, false),
+                AccountMeta::new_readonly(*self.rent.key, false),
+                AccountMeta::new_readonly(*self.system_program.key, false),
+                AccountMeta::new_readonly(*self.token_program.key, false),
+                AccountMeta::new_readonly(*self.associated_token_program.key, false),
+                AccountMeta::new_readonly(*self.metadata_program.key, false),
+            ]
+        }
+    }
+
+    impl<'info> ToAccountInfos<'info> for MintOneirobot<'info> {
+        fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+            vec![
+                self.oneirobot_state.clone(),
+                self.nft_attributes.clone(),
+                self.mint.clone(),
+                self.token_account.clone(),
+                self.metadata.clone(),
+                self.master_edition.clone(),
+                self.minter.clone(),
+                self.recipient.clone(),
+                self.mint_authority.clone(),
+                self.fee_vault.clone(),
+                self.rent.clone(),
+                self.system_program.clone(),
+                self.token_program.clone(),
+                self.associated_token_program.clone(),
+                self.metadata_program.clone(),
+            ]
+        }
+    }
+}
+
+#[derive(AnchorSerialize)]
+struct MintOneirobotData {
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+}
+
+/// Mint an Oneirobot through `ctx.program`, the OneirobotNFT deployment the
+/// caller trusts. An empty `metadata_uri` builds the metadata on-chain.
+pub fn mint_oneirobot<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::MintOneirobot<'info>>,
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+) -> Result<()> {
+    require!(ctx.program.executable, ErrorCode::InvalidProgramExecutable);
+    let mut data = hash(b"global:mint_oneirobot").to_bytes()[..8].to_vec();
+    MintOneirobotData {
+        metadata_uri,
+        name,
+        symbol,
+    }
+    .serialize(&mut data)?;
+
+    let mut accounts = ctx.accounts.to_account_metas(None);
+    // `edition_config` and `splicer` left out: Anchor reads the program id as `None`
+    accounts.push(AccountMeta::new_readonly(*ctx.program.key, false));
+    accounts.push(AccountMeta::new_readonly(*ctx.program.key, false));
+    accounts.extend(ctx.remaining_accounts.to_account_metas(None));
+    let instruction = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data,
+    };
+
+    let mut account_infos = ctx.to_account_infos();
+    account_infos.push(ctx.program.clone());
+    invoke_signed(&instruction, &account_infos, ctx.signer_seeds)?;
+    Ok(())
+}
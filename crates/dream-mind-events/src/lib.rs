@@ -0,0 +1,641 @@
+//! Event decoding for indexers.
+//!
+//! Events reach clients two ways: `emit!` writes a `Program data:` log line,
+//! and `emit_cpi!` issues a self-invocation whose instruction data carries the
+//! event. Both payloads are `discriminator || borsh(event)` and decode into
+//! [`DreamEvent`].
+
+pub mod logs;
+pub mod replay;
+
+use anchor_lang::{event::EVENT_IX_TAG_LE, AnchorDeserialize, Discriminator};
+use serde::{Deserialize, Serialize};
+
+pub use dream_mind_state::{
+    AnnotationApprovedEvent, BuildInfoUpdatedEvent, CharityApprovalChangedEvent,
+    CharityDonationEvent, CoauthorSharesExpiredEvent, CoauthorshipConfirmedEvent,
+    CompanionEquippedEvent, CompressedStakedEvent, CompressedUnstakedEvent, ConversionRatesSetEvent,
+    CrankRewardPaidEvent, DreamAnnotatedEvent, DreamArchivedEvent, DreamBridgedInEvent,
+    DreamBridgedOutEvent, DreamCertifiedEvent, DreamClassifiedEvent, DreamDequeuedEvent,
+    DreamExtrasUpdatedEvent, DreamFlaggedEvent, DreamInterpretedEvent, DreamLicensedEvent,
+    DreamMirroredEvent, DreamPinnedEvent, DreamRecordMigratedEvent, DreamRecordedEvent,
+    DreamRelayedEvent, DreamRevealedEvent, DreamSealedEvent, DreamValidatedEvent,
+    DreamerProfileMigratedEvent, DreamerSponsoredEvent, DuelSettledEvent, EditionsAllowedEvent,
+    EpochRootCommittedEvent, FaucetMintedEvent, FeesSweptEvent, FinalizeApprovalCancelledEvent,
+    GrantClawedBackEvent, GrantCreatedEvent, GrantMilestoneApprovedEvent, GuardiansRotatedEvent,
+    HolderAirdropClaimedEvent, HolderSnapshotTakenEvent, InsuranceFundedEvent,
+    InterpretationExpiredEvent, InterpreterAssignedEvent, InterpreterPaidEvent,
+    LegacyStorageMigratedEvent, LucidStakedEvent, MarketCreatedEvent, MarketOutcomeBoughtEvent,
+    MarketResolvedEvent, MarketWinningsClaimedEvent, MevProtectionUpdatedEvent,
+    OnboardingBonusPaidEvent, OneirobotBoostEndedEvent, OneirobotBoostedEvent,
+    OneirobotEditionPrintedEvent, OneirobotMintedEvent, OneirobotNamedEvent, OneirobotSplicedEvent,
+    OptimisticScorePostedEvent, OptimisticScoreVoidedEvent, OracleQuorumSetEvent,
+    PinningOracleChangedEvent, ProgramFinalizedEvent, ProgramInfoUpdatedEvent,
+    ProgramPauseChangedEvent, RemoteMessageReceivedEvent, ResearchConsentChangedEvent,
+    ResearchLicensePurchasedEvent, ResearchRevenueClaimedEvent, RewardClaimedEvent,
+    RewardPoolToppedUpEvent, RewardStreamClaimedEvent, RewardStreamCreatedEvent, SeasonStartedEvent,
+    SessionKeyCreatedEvent, SessionKeyRevokedEvent, ShortfallCoveredEvent, SleepSessionClosedEvent,
+    SpliceConfigSetEvent, StakeMigratedEvent, StatsRolledEvent, StringInternedEvent,
+    TierCredentialUpdatedEvent, TraitSeasonStartedEvent, TreasuryHealthEvent,
+    UpgradeAuthorityHandedOverEvent, ValidationDisputeResolvedEvent, ValidatorClaimCapReachedEvent,
+    ValidatorGateSetEvent, ValidatorRegisteredEvent, ValidatorRevokedEvent,
+    ValidatorRewardCreditedEvent, ValidatorRewardsClaimedEvent,
+};
+pub use logs::parse_logs;
+pub use replay::{replay_events, ReplayedEvent};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "data")]
+pub enum DreamEvent {
+    DreamRecorded(DreamRecordedEvent),
+    LucidStaked(LucidStakedEvent),
+    MevProtectionUpdated(MevProtectionUpdatedEvent),
+    LegacyStorageMigrated(LegacyStorageMigratedEvent),
+    OneirobotMinted(OneirobotMintedEvent),
+    DreamBridgedOut(DreamBridgedOutEvent),
+    DreamBridgedIn(DreamBridgedInEvent),
+    DreamMirrored(DreamMirroredEvent),
+    RemoteMessageReceived(RemoteMessageReceivedEvent),
+    ProgramInfoUpdated(ProgramInfoUpdatedEvent),
+    UpgradeAuthorityHandedOver(UpgradeAuthorityHandedOverEvent),
+    ProgramFinalized(ProgramFinalizedEvent),
+    SleepSessionClosed(SleepSessionClosedEvent),
+    SeasonStarted(SeasonStartedEvent),
+    EpochRootCommitted(EpochRootCommittedEvent),
+    ResearchConsentChanged(ResearchConsentChangedEvent),
+    ResearchLicensePurchased(ResearchLicensePurchasedEvent),
+    ResearchRevenueClaimed(ResearchRevenueClaimedEvent),
+    MarketCreated(MarketCreatedEvent),
+    MarketOutcomeBought(MarketOutcomeBoughtEvent),
+    MarketResolved(MarketResolvedEvent),
+    MarketWinningsClaimed(MarketWinningsClaimedEvent),
+    DuelSettled(DuelSettledEvent),
+    DreamSealed(DreamSealedEvent),
+    DreamRevealed(DreamRevealedEvent),
+    CharityApprovalChanged(CharityApprovalChangedEvent),
+    CharityDonation(CharityDonationEvent),
+    RewardStreamCreated(RewardStreamCreatedEvent),
+    RewardStreamClaimed(RewardStreamClaimedEvent),
+    DreamDequeued(DreamDequeuedEvent),
+    CompressedStaked(CompressedStakedEvent),
+    CompressedUnstaked(CompressedUnstakedEvent),
+    CrankRewardPaid(CrankRewardPaidEvent),
+    DreamLicensed(DreamLicensedEvent),
+    CoauthorshipConfirmed(CoauthorshipConfirmedEvent),
+    DreamAnnotated(DreamAnnotatedEvent),
+    AnnotationApproved(AnnotationApprovedEvent),
+    CoauthorSharesExpired(CoauthorSharesExpiredEvent),
+    FeesSwept(FeesSweptEvent),
+    DreamValidated(DreamValidatedEvent),
+    PinningOracleChanged(PinningOracleChangedEvent),
+    DreamPinned(DreamPinnedEvent),
+    RewardPoolToppedUp(RewardPoolToppedUpEvent),
+    DreamFlagged(DreamFlaggedEvent),
+    InsuranceFunded(InsuranceFundedEvent),
+    ShortfallCovered(ShortfallCoveredEvent),
+    ConversionRatesSet(ConversionRatesSetEvent),
+    RewardClaimed(RewardClaimedEvent),
+    StakeMigrated(StakeMigratedEvent),
+    DreamRelayed(DreamRelayedEvent),
+    SessionKeyCreated(SessionKeyCreatedEvent),
+    SessionKeyRevoked(SessionKeyRevokedEvent),
+    DreamExtrasUpdated(DreamExtrasUpdatedEvent),
+    BuildInfoUpdated(BuildInfoUpdatedEvent),
+    DreamerSponsored(DreamerSponsoredEvent),
+    TierCredentialUpdated(TierCredentialUpdatedEvent),
+    TraitSeasonStarted(TraitSeasonStartedEvent),
+    OneirobotBoosted(OneirobotBoostedEvent),
+    OneirobotBoostEnded(OneirobotBoostEndedEvent),
+    HolderSnapshotTaken(HolderSnapshotTakenEvent),
+    HolderAirdropClaimed(HolderAirdropClaimedEvent),
+    ValidatorGateSet(ValidatorGateSetEvent),
+    ValidatorRegistered(ValidatorRegisteredEvent),
+    ValidatorRevoked(ValidatorRevokedEvent),
+    EditionsAllowed(EditionsAllowedEvent),
+    OneirobotEditionPrinted(OneirobotEditionPrintedEvent),
+    OneirobotNamed(OneirobotNamedEvent),
+    SpliceConfigSet(SpliceConfigSetEvent),
+    OneirobotSpliced(OneirobotSplicedEvent),
+    TreasuryHealth(TreasuryHealthEvent),
+    DreamerProfileMigrated(DreamerProfileMigratedEvent),
+    DreamClassified(DreamClassifiedEvent),
+    ValidatorRewardCredited(ValidatorRewardCreditedEvent),
+    ValidatorRewardsClaimed(ValidatorRewardsClaimedEvent),
+    ValidationDisputeResolved(ValidationDisputeResolvedEvent),
+    OnboardingBonusPaid(OnboardingBonusPaidEvent),
+    GrantCreated(GrantCreatedEvent),
+    GrantMilestoneApproved(GrantMilestoneApprovedEvent),
+    GrantClawedBack(GrantClawedBackEvent),
+    GuardiansRotated(GuardiansRotatedEvent),
+    ProgramPauseChanged(ProgramPauseChangedEvent),
+    FinalizeApprovalCancelled(FinalizeApprovalCancelledEvent),
+    DreamArchived(DreamArchivedEvent),
+    StatsRolled(StatsRolledEvent),
+    ValidatorClaimCapReached(ValidatorClaimCapReachedEvent),
+    CompanionEquipped(CompanionEquippedEvent),
+    OptimisticScorePosted(OptimisticScorePostedEvent),
+    OptimisticScoreVoided(OptimisticScoreVoidedEvent),
+    OracleQuorumSet(OracleQuorumSetEvent),
+    FaucetMinted(FaucetMintedEvent),
+    InterpreterAssigned(InterpreterAssignedEvent),
+    DreamInterpreted(DreamInterpretedEvent),
+    InterpreterPaid(InterpreterPaidEvent),
+    DreamCertified(DreamCertifiedEvent),
+    StringInterned(StringInternedEvent),
+    InterpretationExpired(InterpretationExpiredEvent),
+    DreamRecordMigrated(DreamRecordMigratedEvent),
+}
+
+impl DreamEvent {
+    /// Event name, matching the serialized `type` tag.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::DreamRecorded(_) => "DreamRecorded",
+            Self::LucidStaked(_) => "LucidStaked",
+            Self::MevProtectionUpdated(_) => "MevProtectionUpdated",
+            Self::LegacyStorageMigrated(_) => "LegacyStorageMigrated",
+            Self::OneirobotMinted(_) => "OneirobotMinted",
+            Self::DreamBridgedOut(_) => "DreamBridgedOut",
+            Self::DreamBridgedIn(_) => "DreamBridgedIn",
+            Self::DreamMirrored(_) => "DreamMirrored",
+            Self::RemoteMessageReceived(_) => "RemoteMessageReceived",
+            Self::ProgramInfoUpdated(_) => "ProgramInfoUpdated",
+            Self::UpgradeAuthorityHandedOver(_) => "UpgradeAuthorityHandedOver",
+            Self::ProgramFinalized(_) => "ProgramFinalized",
+            Self::SleepSessionClosed(_) => "SleepSessionClosed",
+            Self::SeasonStarted(_) => "SeasonStarted",
+            Self::EpochRootCommitted(_) => "EpochRootCommitted",
+            Self::ResearchConsentChanged(_) => "ResearchConsentChanged",
+            Self::ResearchLicensePurchased(_) => "ResearchLicensePurchased",
+            Self::ResearchRevenueClaimed(_) => "ResearchRevenueClaimed",
+            Self::MarketCreated(_) => "MarketCreated",
+            Self::MarketOutcomeBought(_) => "MarketOutcomeBought",
+            Self::MarketResolved(_) => "MarketResolved",
+            Self::MarketWinningsClaimed(_) => "MarketWinningsClaimed",
+            Self::DuelSettled(_) => "DuelSettled",
+            Self::DreamSealed(_) => "DreamSealed",
+            Self::DreamRevealed(_) => "DreamRevealed",
+            Self::CharityApprovalChanged(_) => "CharityApprovalChanged",
+            Self::CharityDonation(_) => "CharityDonation",
+            Self::RewardStreamCreated(_) => "RewardStreamCreated",
+            Self::RewardStreamClaimed(_) => "RewardStreamClaimed",
+            Self::DreamDequeued(_) => "DreamDequeued",
+            Self::CompressedStaked(_) => "CompressedStaked",
+            Self::CompressedUnstaked(_) => "CompressedUnstaked",
+            Self::CrankRewardPaid(_) => "CrankRewardPaid",
+            Self::DreamLicensed(_) => "DreamLicensed",
+            Self::CoauthorshipConfirmed(_) => "CoauthorshipConfirmed",
+            Self::DreamAnnotated(_) => "DreamAnnotated",
+            Self::AnnotationApproved(_) => "AnnotationApproved",
+            Self::CoauthorSharesExpired(_) => "CoauthorSharesExpired",
+            Self::FeesSwept(_) => "FeesSwept",
+            Self::DreamValidated(_) => "DreamValidated",
+            Self::PinningOracleChanged(_) => "PinningOracleChanged",
+            Self::DreamPinned(_) => "DreamPinned",
+            Self::RewardPoolToppedUp(_) => "RewardPoolToppedUp",
+            Self::DreamFlagged(_) => "DreamFlagged",
+            Self::InsuranceFunded(_) => "InsuranceFunded",
+            Self::ShortfallCovered(_) => "ShortfallCovered",
+            Self::ConversionRatesSet(_) => "ConversionRatesSet",
+            Self::RewardClaimed(_) => "RewardClaimed",
+            Self::StakeMigrated(_) => "StakeMigrated",
+            Self::DreamRelayed(_) => "DreamRelayed",
+            Self::SessionKeyCreated(_) => "SessionKeyCreated",
+            Self::SessionKeyRevoked(_) => "SessionKeyRevoked",
+            Self::DreamExtrasUpdated(_) => "DreamExtrasUpdated",
+            Self::BuildInfoUpdated(_) => "BuildInfoUpdated",
+            Self::DreamerSponsored(_) => "DreamerSponsored",
+            Self::TierCredentialUpdated(_) => "TierCredentialUpdated",
+            Self::TraitSeasonStarted(_) => "TraitSeasonStarted",
+            Self::OneirobotBoosted(_) => "OneirobotBoosted",
+            Self::OneirobotBoostEnded(_) => "OneirobotBoostEnded",
+            Self::HolderSnapshotTaken(_) => "HolderSnapshotTaken",
+            Self::HolderAirdropClaimed(_) => "HolderAirdropClaimed",
+            Self::ValidatorGateSet(_) => "ValidatorGateSet",
+            Self::ValidatorRegistered(_) => "ValidatorRegistered",
+            Self::ValidatorRevoked(_) => "ValidatorRevoked",
+            Self::EditionsAllowed(_) => "EditionsAllowed",
+            Self::OneirobotEditionPrinted(_) => "OneirobotEditionPrinted",
+            Self::OneirobotNamed(_) => "OneirobotNamed",
+            Self::SpliceConfigSet(_) => "SpliceConfigSet",
+            Self::OneirobotSpliced(_) => "OneirobotSpliced",
+            Self::TreasuryHealth(_) => "TreasuryHealth",
+            Self::DreamerProfileMigrated(_) => "DreamerProfileMigrated",
+            Self::DreamClassified(_) => "DreamClassified",
+            Self::ValidatorRewardCredited(_) => "ValidatorRewardCredited",
+            Self::ValidatorRewardsClaimed(_) => "ValidatorRewardsClaimed",
+            Self::ValidationDisputeResolved(_) => "ValidationDisputeResolved",
+            Self::OnboardingBonusPaid(_) => "OnboardingBonusPaid",
+            Self::GrantCreated(_) => "GrantCreated",
+            Self::GrantMilestoneApproved(_) => "GrantMilestoneApproved",
+            Self::GrantClawedBack(_) => "GrantClawedBack",
+            Self::GuardiansRotated(_) => "GuardiansRotated",
+            Self::ProgramPauseChanged(_) => "ProgramPauseChanged",
+            Self::FinalizeApprovalCancelled(_) => "FinalizeApprovalCancelled",
+            Self::DreamArchived(_) => "DreamArchived",
+            Self::StatsRolled(_) => "StatsRolled",
+            Self::ValidatorClaimCapReached(_) => "ValidatorClaimCapReached",
+            Self::CompanionEquipped(_) => "CompanionEquipped",
+            Self::OptimisticScorePosted(_) => "OptimisticScorePosted",
+            Self::OptimisticScoreVoided(_) => "OptimisticScoreVoided",
+            Self::OracleQuorumSet(_) => "OracleQuorumSet",
+            Self::FaucetMinted(_) => "FaucetMinted",
+            Self::InterpreterAssigned(_) => "InterpreterAssigned",
+            Self::DreamInterpreted(_) => "DreamInterpreted",
+            Self::InterpreterPaid(_) => "InterpreterPaid",
+            Self::DreamCertified(_) => "DreamCertified",
+            Self::StringInterned(_) => "StringInterned",
+            Self::InterpretationExpired(_) => "InterpretationExpired",
+            Self::DreamRecordMigrated(_) => "DreamRecordMigrated",
+        }
+    }
+
+    /// Decode a discriminator-prefixed event payload. Returns `None` for
+    /// payloads that don't belong to a known event.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, payload) = data.split_at(8);
+
+        match discriminator {
+            d if d == DreamRecordedEvent::DISCRIMINATOR => {
+                decode_dream_recorded(payload).map(Self::DreamRecorded)
+            }
+            d if d == LucidStakedEvent::DISCRIMINATOR => decode_as(payload).map(Self::LucidStaked),
+            d if d == MevProtectionUpdatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::MevProtectionUpdated)
+            }
+            d if d == LegacyStorageMigratedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::LegacyStorageMigrated)
+            }
+            d if d == OneirobotMintedEvent::DISCRIMINATOR => decode_as(payload).map(Self::OneirobotMinted),
+            d if d == DreamBridgedOutEvent::DISCRIMINATOR => decode_as(payload).map(Self::DreamBridgedOut),
+            d if d == DreamBridgedInEvent::DISCRIMINATOR => decode_as(payload).map(Self::DreamBridgedIn),
+            d if d == DreamMirroredEvent::DISCRIMINATOR => decode_as(payload).map(Self::DreamMirrored),
+            d if d == RemoteMessageReceivedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::RemoteMessageReceived)
+            }
+            d if d == ProgramInfoUpdatedEvent::DISCRIMINATOR => decode_as(payload).map(Self::ProgramInfoUpdated),
+            d if d == UpgradeAuthorityHandedOverEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::UpgradeAuthorityHandedOver)
+            }
+            d if d == ProgramFinalizedEvent::DISCRIMINATOR => decode_as(payload).map(Self::ProgramFinalized),
+            d if d == SleepSessionClosedEvent::DISCRIMINATOR => decode_as(payload).map(Self::SleepSessionClosed),
+            d if d == SeasonStartedEvent::DISCRIMINATOR => decode_as(payload).map(Self::SeasonStarted),
+            d if d == EpochRootCommittedEvent::DISCRIMINATOR => decode_as(payload).map(Self::EpochRootCommitted),
+            d if d == ResearchConsentChangedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ResearchConsentChanged)
+            }
+            d if d == ResearchLicensePurchasedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ResearchLicensePurchased)
+            }
+            d if d == ResearchRevenueClaimedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ResearchRevenueClaimed)
+            }
+            d if d == MarketCreatedEvent::DISCRIMINATOR => decode_as(payload).map(Self::MarketCreated),
+            d if d == MarketOutcomeBoughtEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::MarketOutcomeBought)
+            }
+            d if d == MarketResolvedEvent::DISCRIMINATOR => decode_as(payload).map(Self::MarketResolved),
+            d if d == MarketWinningsClaimedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::MarketWinningsClaimed)
+            }
+            d if d == DuelSettledEvent::DISCRIMINATOR => decode_as(payload).map(Self::DuelSettled),
+            d if d == DreamSealedEvent::DISCRIMINATOR => decode_as(payload).map(Self::DreamSealed),
+            d if d == DreamRevealedEvent::DISCRIMINATOR => decode_as(payload).map(Self::DreamRevealed),
+            d if d == CharityApprovalChangedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::CharityApprovalChanged)
+            }
+            d if d == CharityDonationEvent::DISCRIMINATOR => decode_as(payload).map(Self::CharityDonation),
+            d if d == RewardStreamCreatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::RewardStreamCreated)
+            }
+            d if d == RewardStreamClaimedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::RewardStreamClaimed)
+            }
+            d if d == DreamDequeuedEvent::DISCRIMINATOR => decode_as(payload).map(Self::DreamDequeued),
+            d if d == CompressedStakedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::CompressedStaked)
+            }
+            d if d == CompressedUnstakedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::CompressedUnstaked)
+            }
+            d if d == CrankRewardPaidEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::CrankRewardPaid)
+            }
+            d if d == DreamLicensedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamLicensed)
+            }
+            d if d == CoauthorshipConfirmedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::CoauthorshipConfirmed)
+            }
+            d if d == DreamAnnotatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamAnnotated)
+            }
+            d if d == AnnotationApprovedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::AnnotationApproved)
+            }
+            d if d == CoauthorSharesExpiredEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::CoauthorSharesExpired)
+            }
+            d if d == FeesSweptEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::FeesSwept)
+            }
+            d if d == DreamValidatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamValidated)
+            }
+            d if d == PinningOracleChangedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::PinningOracleChanged)
+            }
+            d if d == DreamPinnedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamPinned)
+            }
+            d if d == RewardPoolToppedUpEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::RewardPoolToppedUp)
+            }
+            d if d == DreamFlaggedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamFlagged)
+            }
+            d if d == InsuranceFundedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::InsuranceFunded)
+            }
+            d if d == ShortfallCoveredEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ShortfallCovered)
+            }
+            d if d == ConversionRatesSetEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ConversionRatesSet)
+            }
+            d if d == RewardClaimedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::RewardClaimed)
+            }
+            d if d == StakeMigratedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::StakeMigrated)
+            }
+            d if d == DreamRelayedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamRelayed)
+            }
+            d if d == SessionKeyCreatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::SessionKeyCreated)
+            }
+            d if d == SessionKeyRevokedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::SessionKeyRevoked)
+            }
+            d if d == DreamExtrasUpdatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamExtrasUpdated)
+            }
+            d if d == BuildInfoUpdatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::BuildInfoUpdated)
+            }
+            d if d == DreamerSponsoredEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamerSponsored)
+            }
+            d if d == TierCredentialUpdatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::TierCredentialUpdated)
+            }
+            d if d == TraitSeasonStartedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::TraitSeasonStarted)
+            }
+            d if d == OneirobotBoostedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OneirobotBoosted)
+            }
+            d if d == OneirobotBoostEndedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OneirobotBoostEnded)
+            }
+            d if d == HolderSnapshotTakenEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::HolderSnapshotTaken)
+            }
+            d if d == HolderAirdropClaimedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::HolderAirdropClaimed)
+            }
+            d if d == ValidatorGateSetEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ValidatorGateSet)
+            }
+            d if d == ValidatorRegisteredEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ValidatorRegistered)
+            }
+            d if d == ValidatorRevokedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ValidatorRevoked)
+            }
+            d if d == EditionsAllowedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::EditionsAllowed)
+            }
+            d if d == OneirobotEditionPrintedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OneirobotEditionPrinted)
+            }
+            d if d == OneirobotNamedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OneirobotNamed)
+            }
+            d if d == SpliceConfigSetEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::SpliceConfigSet)
+            }
+            d if d == OneirobotSplicedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OneirobotSpliced)
+            }
+            d if d == TreasuryHealthEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::TreasuryHealth)
+            }
+            d if d == DreamerProfileMigratedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamerProfileMigrated)
+            }
+            d if d == DreamClassifiedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamClassified)
+            }
+            d if d == ValidatorRewardCreditedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ValidatorRewardCredited)
+            }
+            d if d == ValidatorRewardsClaimedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ValidatorRewardsClaimed)
+            }
+            d if d == ValidationDisputeResolvedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ValidationDisputeResolved)
+            }
+            d if d == OnboardingBonusPaidEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OnboardingBonusPaid)
+            }
+            d if d == GrantCreatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::GrantCreated)
+            }
+            d if d == GrantMilestoneApprovedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::GrantMilestoneApproved)
+            }
+            d if d == GrantClawedBackEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::GrantClawedBack)
+            }
+            d if d == GuardiansRotatedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::GuardiansRotated)
+            }
+            d if d == ProgramPauseChangedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ProgramPauseChanged)
+            }
+            d if d == FinalizeApprovalCancelledEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::FinalizeApprovalCancelled)
+            }
+            d if d == DreamArchivedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamArchived)
+            }
+            d if d == StatsRolledEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::StatsRolled)
+            }
+            d if d == ValidatorClaimCapReachedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::ValidatorClaimCapReached)
+            }
+            d if d == CompanionEquippedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::CompanionEquipped)
+            }
+            d if d == OptimisticScorePostedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OptimisticScorePosted)
+            }
+            d if d == OptimisticScoreVoidedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OptimisticScoreVoided)
+            }
+            d if d == OracleQuorumSetEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::OracleQuorumSet)
+            }
+            d if d == FaucetMintedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::FaucetMinted)
+            }
+            d if d == InterpreterAssignedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::InterpreterAssigned)
+            }
+            d if d == DreamInterpretedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamInterpreted)
+            }
+            d if d == InterpreterPaidEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::InterpreterPaid)
+            }
+            d if d == DreamCertifiedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamCertified)
+            }
+            d if d == StringInternedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::StringInterned)
+            }
+            d if d == InterpretationExpiredEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::InterpretationExpired)
+            }
+            d if d == DreamRecordMigratedEvent::DISCRIMINATOR => {
+                decode_as(payload).map(Self::DreamRecordMigrated)
+            }
+            _ => None,
+        }
+    }
+
+    /// Decode the instruction data of an `emit_cpi!` self-invocation.
+    pub fn decode_event_cpi(ix_data: &[u8]) -> Option<Self> {
+        ix_data
+            .strip_prefix(EVENT_IX_TAG_LE.as_slice())
+            .and_then(Self::decode)
+    }
+}
+
+fn decode_as<T: AnchorDeserialize>(mut payload: &[u8]) -> Option<T> {
+    T::deserialize(&mut payload).ok()
+}
+
+/// Events emitted before `metadata` was added end after `timestamp`; zero
+/// bytes decode as unreported metadata.
+fn decode_dream_recorded(payload: &[u8]) -> Option<DreamRecordedEvent> {
+    decode_as(payload).or_else(|| {
+        let mut padded = payload.to_vec();
+        padded.extend_from_slice(&[0; 4]);
+        decode_as(&padded)
+    })
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum EventError {
+    /// Boxed because `ClientError` would otherwise bloat every `Result`.
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    #[error("Transaction {0} could not be decoded")]
+    UndecodableTransaction(String),
+}
+
+impl From<solana_client::client_error::ClientError> for EventError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(Box::new(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{prelude::Pubkey, AnchorSerialize};
+    use dream_mind_state::DreamMetadata;
+
+    use super::*;
+
+    pub(crate) fn event_bytes<T: AnchorSerialize + Discriminator>(event: &T) -> Vec<u8> {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        data.extend(event.try_to_vec().unwrap());
+        data
+    }
+
+    pub(crate) fn validated_event(score: u8) -> DreamValidatedEvent {
+        DreamValidatedEvent {
+            dream_record: Pubkey::new_unique(),
+            dreamer: Pubkey::new_unique(),
+            validator: Pubkey::new_unique(),
+            score,
+            reputation: -3,
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn decodes_events_by_discriminator() {
+        let event = validated_event(87);
+        let Some(DreamEvent::DreamValidated(decoded)) = DreamEvent::decode(&event_bytes(&event))
+        else {
+            panic!("expected a DreamValidated event");
+        };
+        assert_eq!(decoded.dream_record, event.dream_record);
+        assert_eq!(decoded.validator, event.validator);
+        assert_eq!(decoded.score, 87);
+        assert_eq!(decoded.reputation, -3);
+    }
+
+    #[test]
+    fn rejects_unknown_and_truncated_payloads() {
+        let mut data = event_bytes(&validated_event(50));
+        assert!(DreamEvent::decode(&data[..7]).is_none());
+        assert!(DreamEvent::decode(&data[..data.len() - 1]).is_none());
+        data[..8].copy_from_slice(&[0xff; 8]);
+        assert!(DreamEvent::decode(&data).is_none());
+    }
+
+    #[test]
+    fn decodes_event_cpi_only_behind_the_event_tag() {
+        let data = event_bytes(&validated_event(12));
+        assert!(DreamEvent::decode_event_cpi(&data).is_none());
+
+        let mut ix_data = EVENT_IX_TAG_LE.to_vec();
+        ix_data.extend(&data);
+        assert!(matches!(
+            DreamEvent::decode_event_cpi(&ix_data),
+            Some(DreamEvent::DreamValidated(event)) if event.score == 12
+        ));
+    }
+
+    #[test]
+    fn decodes_dream_recorded_events_from_before_metadata() {
+        let event = DreamRecordedEvent {
+            id: 7,
+            dreamer: Pubkey::new_unique(),
+            content_hash: [9; 32],
+            token_reward: 1_000,
+            mev_protected: true,
+            timestamp: 1_700_000_000,
+            metadata: DreamMetadata::default(),
+        };
+        let mut legacy = event_bytes(&event);
+        legacy.truncate(legacy.len() - DreamMetadata::default().try_to_vec().unwrap().len());
+
+        let Some(DreamEvent::DreamRecorded(decoded)) = DreamEvent::decode(&legacy) else {
+            panic!("expected a DreamRecorded event");
+        };
+        assert_eq!(decoded.id, 7);
+        assert_eq!(decoded.dreamer, event.dreamer);
+        assert_eq!(decoded.token_reward, 1_000);
+        assert_eq!(decoded.metadata, DreamMetadata::default());
+    }
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::DreamEvent;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Decode every event emitted via `emit!` by one of `program_ids`.
+///
+/// The invoke/success/failed log lines are tracked as a call stack so
+/// `Program data:` lines are only attributed to the program that wrote them.
+pub fn parse_logs(logs: &[String], program_ids: &[Pubkey]) -> Vec<DreamEvent> {
+    let mut stack: Vec<Pubkey> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            let emitted_by_watched = stack.last().is_some_and(|id| program_ids.contains(id));
+            if !emitted_by_watched {
+                continue;
+            }
+            if let Some(event) = STANDARD.decode(data).ok().and_then(|bytes| DreamEvent::decode(&bytes)) {
+                events.push(event);
+            }
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        if parts.next() != Some("Program") {
+            continue;
+        }
+        let (Some(program), Some(action)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+
+        match action {
+            "invoke" => stack.push(program.parse().unwrap_or_default()),
+            "success" | "failed:" => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{event_bytes, validated_event};
+
+    fn program_data<T: anchor_lang::AnchorSerialize + anchor_lang::Discriminator>(
+        event: &T,
+    ) -> String {
+        format!("{PROGRAM_DATA}{}", STANDARD.encode(event_bytes(event)))
+    }
+
+    #[test]
+    fn attributes_program_data_to_the_innermost_program() {
+        let watched = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {watched} invoke [1]"),
+            program_data(&validated_event(1)),
+            format!("Program {other} invoke [2]"),
+            program_data(&validated_event(2)),
+            format!("Program {other} success"),
+            program_data(&validated_event(3)),
+            format!("Program {watched} success"),
+            program_data(&validated_event(4)),
+        ];
+
+        let scores: Vec<u8> = parse_logs(&logs, &[watched])
+            .into_iter()
+            .map(|event| match event {
+                DreamEvent::DreamValidated(event) => event.score,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        assert_eq!(scores, [1, 3]);
+    }
+
+    #[test]
+    fn pops_failed_invocations_and_skips_undecodable_data() {
+        let watched = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {watched} invoke [1]"),
+            format!("Program {other} invoke [2]"),
+            format!("Program {other} failed: custom program error: 0x1"),
+            format!("{PROGRAM_DATA}not base64!"),
+            format!("{PROGRAM_DATA}{}", STANDARD.encode([0u8; 4])),
+            program_data(&validated_event(5)),
+        ];
+
+        let events = parse_logs(&logs, &[watched]);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], DreamEvent::DreamValidated(event) if event.score == 5));
+    }
+}
@@ -0,0 +1,238 @@
+use anchor_lang::prelude::Pubkey;
+use serde::{Deserialize, Serialize};
+use solana_client::{
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta, UiInstruction,
+    UiTransactionEncoding,
+};
+
+use crate::{logs::parse_logs, DreamEvent, EventError};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReplayedEvent {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub event: DreamEvent,
+}
+
+/// Walk `getSignaturesForAddress` for `address` from newest to oldest and
+/// decode every event emitted by `program_ids`, stopping at `until` (exclusive).
+/// Results are returned in chronological order, so a caller can persist the
+/// newest signature as its checkpoint and pass it as `until` on the next run.
+pub fn replay_events(
+    client: &RpcClient,
+    address: &Pubkey,
+    program_ids: &[Pubkey],
+    until: Option<Signature>,
+) -> Result<Vec<ReplayedEvent>, EventError> {
+    let mut events = Vec::new();
+    let mut before = None;
+
+    loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until,
+            limit: None,
+            commitment: None,
+        };
+        let signatures = client.get_signatures_for_address_with_config(address, config)?;
+        let Some(last) = signatures.last() else {
+            break;
+        };
+        before = Some(parse_signature(&last.signature)?);
+
+        for status in signatures.iter().filter(|status| status.err.is_none()) {
+            let signature = parse_signature(&status.signature)?;
+            let tx = client.get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: None,
+                    max_supported_transaction_version: Some(0),
+                },
+            )?;
+
+            for event in transaction_events(&tx, program_ids) {
+                events.push(ReplayedEvent {
+                    signature: status.signature.clone(),
+                    slot: tx.slot,
+                    block_time: tx.block_time,
+                    event,
+                });
+            }
+        }
+    }
+
+    events.reverse();
+    Ok(events)
+}
+
+fn parse_signature(signature: &str) -> Result<Signature, EventError> {
+    signature
+        .parse()
+        .map_err(|_| EventError::UndecodableTransaction(signature.to_string()))
+}
+
+/// Decode both log-emitted and CPI-emitted events from a fetched transaction.
+pub fn transaction_events(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    program_ids: &[Pubkey],
+) -> Vec<DreamEvent> {
+    let Some(meta) = &tx.transaction.meta else {
+        return Vec::new();
+    };
+
+    let mut events = match &meta.log_messages {
+        OptionSerializer::Some(logs) => parse_logs(logs, program_ids),
+        _ => Vec::new(),
+    };
+
+    let (Some(decoded), OptionSerializer::Some(inner)) =
+        (tx.transaction.transaction.decode(), &meta.inner_instructions)
+    else {
+        return events;
+    };
+
+    // Account keys for program_id_index: static keys, then ALT-loaded writable, then readonly
+    let mut keys: Vec<Pubkey> = decoded.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for address in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            keys.push(address.parse().unwrap_or_default());
+        }
+    }
+
+    for instruction in inner.iter().flat_map(|set| set.instructions.iter()) {
+        let UiInstruction::Compiled(compiled) = instruction else {
+            continue;
+        };
+        let watched = keys
+            .get(compiled.program_id_index as usize)
+            .is_some_and(|id| program_ids.contains(id));
+        if !watched {
+            continue;
+        }
+        if let Some(event) = bs58::decode(&compiled.data)
+            .into_vec()
+            .ok()
+            .and_then(|data| DreamEvent::decode_event_cpi(&data))
+        {
+            events.push(event);
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::event::EVENT_IX_TAG_LE;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use solana_sdk::{
+        instruction::{CompiledInstruction, Instruction},
+        message::Message,
+        transaction::Transaction,
+    };
+    use solana_transaction_status::{
+        Encodable, EncodedTransactionWithStatusMeta, InnerInstruction, InnerInstructions,
+        TransactionStatusMeta, UiTransactionStatusMeta,
+    };
+
+    use super::*;
+    use crate::tests::{event_bytes, validated_event};
+
+    fn confirmed_transaction(
+        program_id: Pubkey,
+        logs: Vec<String>,
+        cpi_data: Vec<u8>,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        let payer = Pubkey::new_unique();
+        let message = Message::new(
+            &[Instruction::new_with_bytes(program_id, &[], vec![])],
+            Some(&payer),
+        );
+        let program_id_index = message
+            .account_keys
+            .iter()
+            .position(|key| *key == program_id)
+            .unwrap() as u8;
+        let meta = TransactionStatusMeta {
+            log_messages: Some(logs),
+            inner_instructions: Some(vec![InnerInstructions {
+                index: 0,
+                instructions: vec![InnerInstruction {
+                    instruction: CompiledInstruction::new_from_raw_parts(
+                        program_id_index,
+                        cpi_data,
+                        vec![],
+                    ),
+                    stack_height: Some(2),
+                }],
+            }]),
+            ..TransactionStatusMeta::default()
+        };
+
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 42,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: Transaction::new_unsigned(message)
+                    .encode(UiTransactionEncoding::Base64),
+                meta: Some(UiTransactionStatusMeta::from(meta)),
+                version: None,
+            },
+            block_time: Some(1_700_000_000),
+        }
+    }
+
+    fn cpi_data(score: u8) -> Vec<u8> {
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend(event_bytes(&validated_event(score)));
+        data
+    }
+
+    #[test]
+    fn decodes_log_and_cpi_events_from_a_transaction() {
+        let program_id = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {program_id} invoke [1]"),
+            format!("Program data: {}", STANDARD.encode(event_bytes(&validated_event(10)))),
+            format!("Program {program_id} success"),
+        ];
+        let tx = confirmed_transaction(program_id, logs, cpi_data(20));
+
+        let scores: Vec<u8> = transaction_events(&tx, &[program_id])
+            .into_iter()
+            .map(|event| match event {
+                DreamEvent::DreamValidated(event) => event.score,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        assert_eq!(scores, [10, 20]);
+    }
+
+    #[test]
+    fn ignores_events_from_unwatched_programs() {
+        let program_id = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {program_id} invoke [1]"),
+            format!("Program data: {}", STANDARD.encode(event_bytes(&validated_event(10)))),
+            format!("Program {program_id} success"),
+        ];
+        let tx = confirmed_transaction(program_id, logs, cpi_data(20));
+
+        assert!(transaction_events(&tx, &[Pubkey::new_unique()]).is_empty());
+    }
+
+    #[test]
+    fn transactions_without_meta_have_no_events() {
+        let program_id = Pubkey::new_unique();
+        let mut tx = confirmed_transaction(program_id, vec![], cpi_data(20));
+        tx.transaction.meta = None;
+
+        assert!(transaction_events(&tx, &[program_id]).is_empty());
+    }
+}
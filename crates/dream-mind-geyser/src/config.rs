@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use serde::Deserialize;
+use solana_geyser_plugin_interface::geyser_plugin_interface::{GeyserPluginError, Result};
+
+/// Contents of the JSON file the validator passes to `on_load`, e.g.
+///
+/// ```json
+/// {
+///   "libpath": "/opt/geyser/libdream_mind_geyser.so",
+///   "program_ids": ["<OneirobotNFT program ID>"],
+///   "nats": { "url": "nats://127.0.0.1:4222", "subject_prefix": "dream" },
+///   "kafka": { "brokers": "127.0.0.1:9092", "topic": "dream-accounts" }
+/// }
+/// ```
+///
+/// Either sink may be omitted; updates go to every sink that is configured.
+#[derive(Deserialize)]
+pub struct Config {
+    /// Extra program IDs to stream besides Dream-Mind-Lucid (e.g. OneirobotNFT)
+    #[serde(default)]
+    pub program_ids: Vec<String>,
+    /// Skip the snapshot replay at startup and only stream live updates
+    #[serde(default)]
+    pub skip_startup: bool,
+    pub nats: Option<NatsConfig>,
+    pub kafka: Option<KafkaConfig>,
+}
+
+#[derive(Deserialize)]
+pub struct NatsConfig {
+    pub url: String,
+    /// Updates are published to `{subject_prefix}.{account type}`, e.g. `dream.DreamRecord`
+    #[serde(default = "default_subject_prefix")]
+    pub subject_prefix: String,
+}
+
+#[derive(Deserialize)]
+pub struct KafkaConfig {
+    /// Comma-separated `bootstrap.servers`
+    pub brokers: String,
+    /// Every account type goes to one topic, keyed by account address
+    pub topic: String,
+}
+
+fn default_subject_prefix() -> String {
+    "dream".to_string()
+}
+
+impl Config {
+    pub fn load(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|err| GeyserPluginError::ConfigFileReadError { msg: err.to_string() })
+    }
+
+    /// Dream-Mind-Lucid plus the configured extra programs.
+    pub fn program_ids(&self) -> Result<Vec<Pubkey>> {
+        let mut program_ids = vec![dream_mind_state::ID];
+        for id in &self.program_ids {
+            program_ids.push(Pubkey::from_str(id).map_err(|_| {
+                GeyserPluginError::ConfigFileReadError {
+                    msg: format!("invalid program ID in program_ids: {id}"),
+                }
+            })?);
+        }
+        Ok(program_ids)
+    }
+}
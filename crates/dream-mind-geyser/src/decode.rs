@@ -0,0 +1,155 @@
+//! Decode raw account data into the JSON payloads published downstream.
+//! Accounts are recognised by their Anchor discriminator; anything else owned
+//! by a streamed program is ignored.
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use dream_mind_state::{oneirobot::OneirobotAttributes, DreamMetadata, DreamRecord, Treasury};
+use serde::Serialize;
+
+/// One published account update. Serialized with its type under `"type"`.
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum AccountUpdate {
+    DreamRecord(DreamRecordRow),
+    Treasury(TreasuryRow),
+    NftAttributes(NftAttributesRow),
+}
+
+#[derive(Serialize)]
+pub struct DreamRecordRow {
+    pub address: String,
+    pub slot: u64,
+    pub id: u64,
+    pub dreamer: String,
+    pub content_hash: String,
+    pub timestamp: i64,
+    pub token_reward: u64,
+    pub mev_protected: bool,
+    pub origin_chain: u16,
+    pub metadata: DreamMetadata,
+    pub session: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TreasuryRow {
+    pub address: String,
+    pub slot: u64,
+    pub authority: String,
+    pub total_dreams_recorded: u64,
+    pub total_rewards_distributed: u64,
+    pub total_rewards_expired: u64,
+    pub total_bridged_out: u64,
+    pub total_bridged_in: u64,
+    pub total_dreams_mirrored: u64,
+    pub season: u32,
+}
+
+#[derive(Serialize)]
+pub struct NftAttributesRow {
+    pub address: String,
+    pub slot: u64,
+    pub mint: String,
+    pub owner: String,
+    pub token_id: u64,
+    pub quantum_core_id: u64,
+    pub dream_level: u8,
+    pub lucid_power: u8,
+    pub mind_strength: u8,
+    pub metadata_uri: String,
+    pub mint_timestamp: i64,
+    pub random_seed: u64,
+    pub season: u32,
+    pub name: String,
+    pub lore_cid: String,
+}
+
+impl AccountUpdate {
+    /// Subject suffix / record type name.
+    pub fn account_type(&self) -> &'static str {
+        match self {
+            AccountUpdate::DreamRecord(_) => "DreamRecord",
+            AccountUpdate::Treasury(_) => "Treasury",
+            AccountUpdate::NftAttributes(_) => "NftAttributes",
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        match self {
+            AccountUpdate::DreamRecord(row) => &row.address,
+            AccountUpdate::Treasury(row) => &row.address,
+            AccountUpdate::NftAttributes(row) => &row.address,
+        }
+    }
+}
+
+/// `None` for accounts of other types and data that doesn't deserialize.
+pub fn decode(address: &Pubkey, slot: u64, data: &[u8]) -> Option<AccountUpdate> {
+    let discriminator = data.get(..8)?;
+    let address = address.to_string();
+
+    if discriminator == DreamRecord::DISCRIMINATOR {
+        // Records created before sleep sessions existed are shorter than the
+        // current layout; zero-pad them so `session` reads as unset.
+        let mut data = data.to_vec();
+        let len = 8 + std::mem::size_of::<DreamRecord>();
+        if data.len() < len {
+            data.resize(len, 0);
+        }
+        let record = DreamRecord::try_deserialize(&mut data.as_slice()).ok()?;
+        return Some(AccountUpdate::DreamRecord(DreamRecordRow {
+            address,
+            slot,
+            id: record.id,
+            dreamer: record.dreamer.to_string(),
+            content_hash: to_hex(&record.content_hash),
+            timestamp: record.timestamp,
+            token_reward: record.token_reward,
+            mev_protected: record.mev_protected,
+            origin_chain: record.origin_chain,
+            metadata: record.metadata,
+            session: (record.session != Pubkey::default()).then(|| record.session.to_string()),
+        }));
+    }
+
+    if discriminator == Treasury::DISCRIMINATOR {
+        let treasury = Treasury::try_deserialize(&mut &data[..]).ok()?;
+        return Some(AccountUpdate::Treasury(TreasuryRow {
+            address,
+            slot,
+            authority: treasury.authority.to_string(),
+            total_dreams_recorded: treasury.total_dreams_recorded,
+            total_rewards_distributed: treasury.total_rewards_distributed,
+            total_rewards_expired: treasury.total_rewards_expired,
+            total_bridged_out: treasury.total_bridged_out,
+            total_bridged_in: treasury.total_bridged_in,
+            total_dreams_mirrored: treasury.total_dreams_mirrored,
+            season: treasury.season,
+        }));
+    }
+
+    if let Some(nft) = OneirobotAttributes::try_from_data(data) {
+        return Some(AccountUpdate::NftAttributes(NftAttributesRow {
+            address,
+            slot,
+            mint: nft.mint.to_string(),
+            owner: nft.owner.to_string(),
+            token_id: nft.token_id,
+            quantum_core_id: nft.quantum_core_id,
+            dream_level: nft.dream_level,
+            lucid_power: nft.lucid_power,
+            mind_strength: nft.mind_strength,
+            metadata_uri: nft.metadata_uri,
+            mint_timestamp: nft.mint_timestamp,
+            random_seed: nft.random_seed,
+            season: nft.season,
+            name: nft.name,
+            lore_cid: nft.lore_cid,
+        }));
+    }
+
+    None
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,115 @@
+//! Geyser plugin that streams Dream-Mind-Lucid account updates straight from a
+//! validator. Updates for the configured programs are decoded (`DreamRecord`,
+//! `Treasury`, OneirobotNFT `NftAttributes`) and published as JSON to NATS
+//! and/or Kafka, so consumers see changes within the slot instead of polling RPC.
+//!
+//! Load it by pointing the validator's `--geyser-plugin-config` at a JSON file
+//! in the shape of [`config::Config`].
+
+pub mod config;
+pub mod decode;
+pub mod nats;
+pub mod publisher;
+
+use std::collections::HashSet;
+
+use anchor_lang::prelude::Pubkey;
+use solana_geyser_plugin_interface::geyser_plugin_interface::{
+    GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions, Result,
+};
+
+use crate::{config::Config, publisher::Publisher};
+
+#[derive(Default)]
+pub struct DreamGeyserPlugin {
+    program_ids: HashSet<Pubkey>,
+    skip_startup: bool,
+    publisher: Option<Publisher>,
+}
+
+impl std::fmt::Debug for DreamGeyserPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DreamGeyserPlugin")
+            .field("program_ids", &self.program_ids)
+            .field("skip_startup", &self.skip_startup)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GeyserPlugin for DreamGeyserPlugin {
+    fn name(&self) -> &'static str {
+        "dream-mind-geyser"
+    }
+
+    fn setup_logger(&self, logger: &'static dyn log::Log, level: log::LevelFilter) -> Result<()> {
+        log::set_max_level(level);
+        log::set_logger(logger).map_err(|err| GeyserPluginError::Custom(Box::new(err)))
+    }
+
+    fn on_load(&mut self, config_file: &str, _is_reload: bool) -> Result<()> {
+        let config = Config::load(config_file)?;
+        self.program_ids = config.program_ids()?.into_iter().collect();
+        self.skip_startup = config.skip_startup;
+        self.publisher = Some(Publisher::connect(&config)?);
+        log::info!("dream-mind-geyser streaming {} program(s)", self.program_ids.len());
+        Ok(())
+    }
+
+    fn on_unload(&mut self) {
+        if let Some(publisher) = self.publisher.take() {
+            publisher.close();
+        }
+    }
+
+    fn update_account(
+        &self,
+        account: ReplicaAccountInfoVersions,
+        slot: u64,
+        is_startup: bool,
+    ) -> Result<()> {
+        if is_startup && self.skip_startup {
+            return Ok(());
+        }
+        let Some(publisher) = &self.publisher else {
+            return Ok(());
+        };
+
+        let (pubkey, owner, data) = match account {
+            ReplicaAccountInfoVersions::V0_0_1(info) => (info.pubkey, info.owner, info.data),
+            ReplicaAccountInfoVersions::V0_0_2(info) => (info.pubkey, info.owner, info.data),
+            ReplicaAccountInfoVersions::V0_0_3(info) => (info.pubkey, info.owner, info.data),
+        };
+        let (Ok(pubkey), Ok(owner)) = (Pubkey::try_from(pubkey), Pubkey::try_from(owner)) else {
+            return Ok(());
+        };
+        if !self.program_ids.contains(&owner) {
+            return Ok(());
+        }
+
+        match decode::decode(&pubkey, slot, data) {
+            Some(update) => publisher.publish(&update),
+            None => Ok(()),
+        }
+    }
+
+    fn account_data_notifications_enabled(&self) -> bool {
+        true
+    }
+
+    fn transaction_notifications_enabled(&self) -> bool {
+        false
+    }
+}
+
+/// Entry point the validator's plugin manager looks up in the shared library.
+///
+/// # Safety
+///
+/// The returned pointer is owned by the caller, which must free it as a
+/// `Box<dyn GeyserPlugin>`.
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+pub unsafe extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
+    let plugin: Box<dyn GeyserPlugin> = Box::<DreamGeyserPlugin>::default();
+    Box::into_raw(plugin)
+}
@@ -0,0 +1,177 @@
+//! Minimal core-NATS publisher speaking the text protocol (`CONNECT`, `PUB`,
+//! `PONG`) over a plain TCP socket. The async-nats client's key dependencies
+//! conflict with the `zeroize` pin of the Solana 1.18 crates, and publishing
+//! is all the plugin needs.
+//!
+//! Messages go through a bounded queue to a writer thread, which reconnects
+//! with backoff. Updates that arrive while the queue is full or the server is
+//! unreachable are dropped and logged; the validator is never slowed down.
+
+use std::{
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    net::{Shutdown, TcpStream},
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::config::NatsConfig;
+
+const QUEUE_CAPACITY: usize = 65_536;
+const DEFAULT_PORT: u16 = 4222;
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const CONNECT: &[u8] =
+    b"CONNECT {\"verbose\":false,\"pedantic\":false,\"name\":\"dream-mind-geyser\"}\r\n";
+
+enum Command {
+    Publish { subject: String, payload: Vec<u8> },
+    /// Reply to a server `PING`, sent by the connection's reader thread.
+    Pong,
+    Close,
+}
+
+pub struct NatsPublisher {
+    sender: SyncSender<Command>,
+    subject_prefix: String,
+    writer: JoinHandle<()>,
+}
+
+impl NatsPublisher {
+    pub fn start(config: &NatsConfig) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+        let address = server_address(&config.url);
+        let pong_sender = sender.clone();
+        let writer = thread::Builder::new()
+            .name("dream-geyser-nats".to_string())
+            .spawn(move || run(&address, receiver, pong_sender))
+            .expect("failed to spawn NATS writer thread");
+        Self {
+            sender,
+            subject_prefix: config.subject_prefix.clone(),
+            writer,
+        }
+    }
+
+    /// Publishes to `{subject_prefix}.{account_type}`.
+    pub fn publish(&self, account_type: &str, payload: Vec<u8>) {
+        let subject = format!("{}.{account_type}", self.subject_prefix);
+        let command = Command::Publish { subject, payload };
+        if let Err(TrySendError::Full(_)) = self.sender.try_send(command) {
+            log::warn!("NATS queue full; dropping {account_type} update");
+        }
+    }
+
+    /// Write out everything queued so far, then stop the writer thread.
+    pub fn close(self) {
+        if self.sender.send(Command::Close).is_ok() && self.writer.join().is_err() {
+            log::warn!("NATS writer thread panicked");
+        }
+    }
+}
+
+/// `nats://host:port` or `host[:port]` to a socket address.
+fn server_address(url: &str) -> String {
+    let host = url.strip_prefix("nats://").unwrap_or(url).trim_end_matches('/');
+    if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{host}:{DEFAULT_PORT}")
+    }
+}
+
+fn run(address: &str, receiver: Receiver<Command>, pong_sender: SyncSender<Command>) {
+    let mut connection: Option<BufWriter<TcpStream>> = None;
+    let mut backoff = Duration::from_secs(1);
+    let mut next_attempt = Instant::now();
+
+    for command in receiver {
+        let (subject, payload) = match command {
+            Command::Publish { subject, payload } => (subject, payload),
+            Command::Pong => {
+                if let Some(writer) = &mut connection {
+                    if writer.write_all(b"PONG\r\n").and_then(|_| writer.flush()).is_err() {
+                        disconnect(&mut connection);
+                    }
+                }
+                continue;
+            }
+            Command::Close => break,
+        };
+
+        if connection.is_none() && Instant::now() >= next_attempt {
+            match connect(address, pong_sender.clone()) {
+                Ok(writer) => {
+                    log::info!("connected to NATS at {address}");
+                    connection = Some(writer);
+                    backoff = Duration::from_secs(1);
+                }
+                Err(err) => {
+                    log::warn!("NATS connect to {address} failed: {err}; retrying in {backoff:?}");
+                    next_attempt = Instant::now() + backoff;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+        let Some(writer) = &mut connection else {
+            continue;
+        };
+        if let Err(err) = write_pub(writer, &subject, &payload) {
+            log::warn!("NATS publish failed: {err}; reconnecting");
+            disconnect(&mut connection);
+        }
+    }
+
+    if let Some(writer) = &mut connection {
+        let _ = writer.flush();
+    }
+    disconnect(&mut connection);
+}
+
+/// Shut the socket down rather than just dropping the writer, so the reader
+/// thread's cloned handle sees EOF and exits.
+fn disconnect(connection: &mut Option<BufWriter<TcpStream>>) {
+    if let Some(writer) = connection.take() {
+        let _ = writer.get_ref().shutdown(Shutdown::Both);
+    }
+}
+
+/// Open a connection and spawn a reader that answers server `PING`s through
+/// the writer thread, so the server doesn't drop us as a stale client.
+fn connect(address: &str, pong_sender: SyncSender<Command>) -> io::Result<BufWriter<TcpStream>> {
+    let stream = TcpStream::connect(address)?;
+    stream.set_nodelay(true)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut info = String::new();
+    reader.read_line(&mut info)?;
+    if !info.starts_with("INFO") {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "expected INFO from server"));
+    }
+
+    let mut writer = BufWriter::new(stream);
+    writer.write_all(CONNECT)?;
+    writer.flush()?;
+
+    thread::spawn(move || {
+        let mut line = String::new();
+        while matches!(reader.read_line(&mut line), Ok(n) if n > 0) {
+            if line.starts_with("PING") && pong_sender.send(Command::Pong).is_err() {
+                break;
+            }
+            if line.starts_with("-ERR") {
+                log::warn!("NATS server error: {}", line.trim_end());
+            }
+            line.clear();
+        }
+    });
+
+    Ok(writer)
+}
+
+/// Flushed per message so an update never waits in the buffer for the next one.
+fn write_pub(writer: &mut BufWriter<TcpStream>, subject: &str, payload: &[u8]) -> io::Result<()> {
+    write!(writer, "PUB {subject} {}\r\n", payload.len())?;
+    writer.write_all(payload)?;
+    writer.write_all(b"\r\n")?;
+    writer.flush()
+}
@@ -0,0 +1,104 @@
+//! Sinks for decoded account updates. Publishing must not block the
+//! validator's replay thread: NATS messages are queued for a writer thread and
+//! Kafka records are queued on rdkafka's background producer.
+
+use solana_geyser_plugin_interface::geyser_plugin_interface::{GeyserPluginError, Result};
+
+use crate::{config::Config, decode::AccountUpdate, nats::NatsPublisher};
+
+pub struct Publisher {
+    nats: Option<NatsPublisher>,
+    #[cfg(feature = "kafka")]
+    kafka: Option<kafka::KafkaPublisher>,
+}
+
+impl Publisher {
+    pub fn connect(config: &Config) -> Result<Self> {
+        #[cfg(not(feature = "kafka"))]
+        if config.kafka.is_some() {
+            return Err(GeyserPluginError::ConfigFileReadError {
+                msg: "kafka is configured but the plugin was built without the `kafka` feature"
+                    .to_string(),
+            });
+        }
+
+        Ok(Self {
+            nats: config.nats.as_ref().map(NatsPublisher::start),
+            #[cfg(feature = "kafka")]
+            kafka: config.kafka.as_ref().map(kafka::KafkaPublisher::connect).transpose()?,
+        })
+    }
+
+    pub fn publish(&self, update: &AccountUpdate) -> Result<()> {
+        let payload = serde_json::to_vec(update)
+            .map_err(|err| GeyserPluginError::AccountsUpdateError { msg: err.to_string() })?;
+        #[cfg(feature = "kafka")]
+        if let Some(kafka) = &self.kafka {
+            kafka.publish(update, &payload)?;
+        }
+        if let Some(nats) = &self.nats {
+            nats.publish(update.account_type(), payload);
+        }
+        Ok(())
+    }
+
+    /// Deliver anything still queued before the plugin is unloaded.
+    pub fn close(self) {
+        if let Some(nats) = self.nats {
+            nats.close();
+        }
+        #[cfg(feature = "kafka")]
+        if let Some(kafka) = self.kafka {
+            kafka.flush();
+        }
+    }
+}
+
+#[cfg(feature = "kafka")]
+mod kafka {
+    use std::time::Duration;
+
+    use rdkafka::{
+        config::ClientConfig,
+        producer::{BaseRecord, DefaultProducerContext, Producer, ThreadedProducer},
+    };
+    use solana_geyser_plugin_interface::geyser_plugin_interface::{GeyserPluginError, Result};
+
+    use crate::{config::KafkaConfig, decode::AccountUpdate};
+
+    const FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub struct KafkaPublisher {
+        producer: ThreadedProducer<DefaultProducerContext>,
+        topic: String,
+    }
+
+    impl KafkaPublisher {
+        pub fn connect(config: &KafkaConfig) -> Result<Self> {
+            let producer = ClientConfig::new()
+                .set("bootstrap.servers", &config.brokers)
+                .set("linger.ms", "5")
+                .create()
+                .map_err(|err| GeyserPluginError::Custom(Box::new(err)))?;
+            Ok(Self {
+                producer,
+                topic: config.topic.clone(),
+            })
+        }
+
+        /// Keyed by account address so every update to one account lands on
+        /// the same partition, in order.
+        pub fn publish(&self, update: &AccountUpdate, payload: &[u8]) -> Result<()> {
+            let record = BaseRecord::to(&self.topic).key(update.address()).payload(payload);
+            self.producer
+                .send(record)
+                .map_err(|(err, _)| GeyserPluginError::AccountsUpdateError { msg: err.to_string() })
+        }
+
+        pub fn flush(&self) {
+            if let Err(err) = self.producer.flush(FLUSH_TIMEOUT) {
+                log::warn!("Kafka flush failed: {err}");
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+-- Portable across SQLite and Postgres; integers are stored as BIGINT (u64 values cast to i64).
+
+CREATE TABLE IF NOT EXISTS dreams (
+    id BIGINT PRIMARY KEY,
+    dreamer TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    token_reward BIGINT NOT NULL,
+    mev_protected BOOLEAN NOT NULL,
+    recorded_at BIGINT NOT NULL,
+    signature TEXT NOT NULL,
+    slot BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS dreams_dreamer_idx ON dreams (dreamer);
+
+CREATE TABLE IF NOT EXISTS stakes (
+    staker TEXT PRIMARY KEY,
+    amount BIGINT NOT NULL,
+    access_level SMALLINT NOT NULL,
+    staked_at BIGINT NOT NULL,
+    signature TEXT NOT NULL,
+    slot BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mints (
+    mint TEXT PRIMARY KEY,
+    owner TEXT NOT NULL,
+    token_id BIGINT NOT NULL,
+    quantum_core TEXT NOT NULL,
+    dream_level SMALLINT NOT NULL,
+    lucid_power SMALLINT NOT NULL,
+    mind_strength SMALLINT NOT NULL,
+    metadata_uri TEXT NOT NULL,
+    minted_at BIGINT NOT NULL,
+    signature TEXT NOT NULL,
+    slot BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS mints_owner_idx ON mints (owner);
+
+CREATE TABLE IF NOT EXISTS checkpoints (
+    program_id TEXT PRIMARY KEY,
+    signature TEXT NOT NULL,
+    slot BIGINT NOT NULL
+);
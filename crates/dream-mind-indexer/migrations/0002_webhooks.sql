@@ -0,0 +1,11 @@
+-- Webhook deliveries that exhausted their retries, kept for inspection and manual replay.
+
+CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+    delivery_id TEXT PRIMARY KEY,
+    endpoint TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    attempts SMALLINT NOT NULL,
+    last_error TEXT NOT NULL,
+    failed_at BIGINT NOT NULL
+);
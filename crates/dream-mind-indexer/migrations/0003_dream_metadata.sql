@@ -0,0 +1,10 @@
+-- Self-reported dream metadata, one row per dream. Kept out of `dreams` so the
+-- migration stays idempotent on SQLite, which has no ADD COLUMN IF NOT EXISTS.
+-- 0 means the dreamer didn't report the value.
+
+CREATE TABLE IF NOT EXISTS dream_metadata (
+    id BIGINT PRIMARY KEY,
+    mood SMALLINT NOT NULL,
+    lucidity_score SMALLINT NOT NULL,
+    sleep_duration_minutes BIGINT NOT NULL
+);
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+
+pub struct Config {
+    pub rpc_url: String,
+    pub ws_url: String,
+    pub database_url: String,
+    pub program_ids: Vec<Pubkey>,
+    pub webhooks_config: Option<PathBuf>,
+}
+
+impl Config {
+    /// Read configuration from the environment (same variable names as `.env`):
+    /// - `SOLANA_RPC_URL`: HTTP endpoint used for backfill
+    /// - `SOLANA_WS_URL`: websocket endpoint (derived from the RPC URL if unset)
+    /// - `INDEXER_DATABASE_URL`: `sqlite://...` or `postgres://...`
+    /// - `INDEXER_PROGRAM_IDS`: extra comma-separated program IDs to index (e.g. OneirobotNFT)
+    /// - `INDEXER_WEBHOOKS_CONFIG`: optional path to a webhook notifier config (JSON)
+    pub fn from_env() -> Result<Self> {
+        let rpc_url = std::env::var("SOLANA_RPC_URL")
+            .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
+        let ws_url = std::env::var("SOLANA_WS_URL").unwrap_or_else(|_| {
+            rpc_url
+                .replacen("https://", "wss://", 1)
+                .replacen("http://", "ws://", 1)
+        });
+        let database_url = std::env::var("INDEXER_DATABASE_URL")
+            .unwrap_or_else(|_| "sqlite://indexer.db".to_string());
+
+        let mut program_ids = vec![dream_mind_state::ID];
+        if let Ok(extra) = std::env::var("INDEXER_PROGRAM_IDS") {
+            for id in extra.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+                program_ids.push(
+                    id.parse()
+                        .with_context(|| format!("invalid program ID in INDEXER_PROGRAM_IDS: {id}"))?,
+                );
+            }
+        }
+
+        let webhooks_config = std::env::var_os("INDEXER_WEBHOOKS_CONFIG").map(PathBuf::from);
+
+        Ok(Self {
+            rpc_url,
+            ws_url,
+            database_url,
+            program_ids,
+            webhooks_config,
+        })
+    }
+}
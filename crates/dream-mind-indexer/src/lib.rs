@@ -0,0 +1,8 @@
+//! Dream-Mind-Lucid indexer library: configuration, the relational sink the
+//! `indexer` binary writes to, the webhook notifier, and typed read queries
+//! for services built on top of the indexed data.
+
+pub mod config;
+pub mod notifier;
+pub mod query;
+pub mod sink;
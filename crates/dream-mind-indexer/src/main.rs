@@ -0,0 +1,89 @@
+//! Dream-Mind-Lucid indexer: backfills program events over RPC, then follows
+//! new transactions over websocket, writing both into a relational database.
+//! Restarts resume from the per-program checkpoint. With
+//! `INDEXER_WEBHOOKS_CONFIG` set, new events are also pushed to webhooks.
+
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use dream_mind_events::{parse_logs, replay_events};
+use futures_util::{stream::select_all, StreamExt};
+use solana_client::{
+    nonblocking::pubsub_client::PubsubClient,
+    rpc_client::RpcClient,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use dream_mind_indexer::{config::Config, notifier::Notifier, sink::Sink};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::init();
+    let config = Config::from_env()?;
+    let sink = Arc::new(Sink::connect(&config.database_url).await?);
+    let notifier = match &config.webhooks_config {
+        Some(path) => Some(Arc::new(Notifier::from_file(path, sink.clone())?)),
+        None => None,
+    };
+
+    // Subscribe before backfilling so nothing confirmed in between is missed;
+    // sink inserts are idempotent, so the overlap is harmless.
+    let pubsub = PubsubClient::new(&config.ws_url).await?;
+    let mut subscriptions = Vec::new();
+    for program_id in &config.program_ids {
+        let (stream, _unsubscribe) = pubsub
+            .logs_subscribe(
+                RpcTransactionLogsFilter::Mentions(vec![program_id.to_string()]),
+                RpcTransactionLogsConfig {
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )
+            .await?;
+        let program_id = *program_id;
+        subscriptions.push(stream.map(move |response| (program_id, response)).boxed());
+    }
+
+    for program_id in &config.program_ids {
+        let until = sink.checkpoint(program_id).await?;
+        let rpc_url = config.rpc_url.clone();
+        let address = *program_id;
+        let program_ids = config.program_ids.clone();
+        let events = tokio::task::spawn_blocking(move || {
+            let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+            replay_events(&client, &address, &program_ids, until)
+        })
+        .await??;
+
+        log::info!("Backfilled {} events for {}", events.len(), program_id);
+        // Without a checkpoint this is full history, which is not news to webhooks
+        let notify = notifier.as_ref().filter(|_| until.is_some());
+        for replayed in &events {
+            sink.ingest(&replayed.signature, replayed.slot, &replayed.event).await?;
+            if let Some(notifier) = notify {
+                notifier.notify(&replayed.signature, replayed.slot, &replayed.event);
+            }
+        }
+        if let Some(newest) = events.last() {
+            sink.save_checkpoint(program_id, &newest.signature, newest.slot).await?;
+        }
+    }
+
+    let mut live = select_all(subscriptions);
+    while let Some((program_id, response)) = live.next().await {
+        let logs = response.value;
+        if logs.err.is_some() {
+            continue;
+        }
+        let slot = response.context.slot;
+        for event in parse_logs(&logs.logs, &config.program_ids) {
+            sink.ingest(&logs.signature, slot, &event).await?;
+            if let Some(notifier) = &notifier {
+                notifier.notify(&logs.signature, slot, &event);
+            }
+        }
+        sink.save_checkpoint(&program_id, &logs.signature, slot).await?;
+    }
+
+    bail!("websocket subscription closed; restart to resume from checkpoint")
+}
@@ -0,0 +1,211 @@
+//! Webhook notifier: POSTs selected events to configured HTTPS endpoints with
+//! an HMAC-SHA256 signature, retrying with backoff and recording deliveries
+//! that exhaust their retries in `webhook_dead_letters`.
+//!
+//! Each request carries:
+//! - `X-Dream-Event`: event type, e.g. `DreamRecorded`
+//! - `X-Dream-Delivery`: stable ID for the (endpoint, payload) pair; the same
+//!   event can be delivered twice around restarts, so receivers should dedupe on it
+//! - `X-Dream-Timestamp`: unix seconds when the attempt was signed
+//! - `X-Dream-Signature`: `sha256=` + hex HMAC of `"{timestamp}.{body}"`
+
+use std::{
+    collections::HashSet,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+use dream_mind_events::DreamEvent;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::sink::{to_hex, DeadLetter, Sink};
+
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Contents of the file named by `INDEXER_WEBHOOKS_CONFIG`.
+#[derive(Deserialize)]
+pub struct WebhookConfig {
+    pub endpoints: Vec<EndpointConfig>,
+    /// Attempts per delivery before it is dead-lettered
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+#[derive(Deserialize)]
+pub struct EndpointConfig {
+    /// Must be `https://`
+    pub url: String,
+    /// Name of the environment variable holding the endpoint's HMAC secret
+    pub secret_env: String,
+    /// Event types to deliver (`DreamEvent` variant names)
+    #[serde(default = "default_events")]
+    pub events: Vec<String>,
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+fn default_events() -> Vec<String> {
+    vec!["DreamRecorded".to_string(), "OneirobotMinted".to_string()]
+}
+
+struct Endpoint {
+    url: String,
+    secret: Vec<u8>,
+    events: HashSet<String>,
+}
+
+pub struct Notifier {
+    endpoints: Vec<Endpoint>,
+    client: reqwest::Client,
+    sink: Arc<Sink>,
+    max_attempts: u32,
+}
+
+impl Notifier {
+    pub fn from_file(path: &Path, sink: Arc<Sink>) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading webhook config {}", path.display()))?;
+        let config: WebhookConfig = serde_json::from_str(&raw)
+            .with_context(|| format!("parsing webhook config {}", path.display()))?;
+        Self::new(config, sink)
+    }
+
+    pub fn new(config: WebhookConfig, sink: Arc<Sink>) -> Result<Self> {
+        let mut endpoints = Vec::with_capacity(config.endpoints.len());
+        for endpoint in config.endpoints {
+            if !endpoint.url.starts_with("https://") {
+                bail!("webhook endpoint must use https: {}", endpoint.url);
+            }
+            let secret = std::env::var(&endpoint.secret_env).with_context(|| {
+                format!("{} is not set (secret for {})", endpoint.secret_env, endpoint.url)
+            })?;
+            endpoints.push(Endpoint {
+                url: endpoint.url,
+                secret: secret.into_bytes(),
+                events: endpoint.events.into_iter().collect(),
+            });
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()?;
+
+        Ok(Self {
+            endpoints,
+            client,
+            sink,
+            max_attempts: config.max_attempts.max(1),
+        })
+    }
+
+    /// Queue `event` for every endpoint subscribed to its type. Returns
+    /// immediately; delivery and retries run on background tasks.
+    pub fn notify(self: &Arc<Self>, signature: &str, slot: u64, event: &DreamEvent) {
+        let event_type = event.event_type();
+        let body = serde_json::json!({
+            "signature": signature,
+            "slot": slot,
+            "event": event,
+        })
+        .to_string();
+
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            if !endpoint.events.contains(event_type) {
+                continue;
+            }
+            let notifier = self.clone();
+            let body = body.clone();
+            tokio::spawn(async move { notifier.deliver(index, event_type, body).await });
+        }
+    }
+
+    async fn deliver(&self, index: usize, event_type: &'static str, body: String) {
+        let endpoint = &self.endpoints[index];
+        let delivery_id = to_hex(&Sha256::new().chain_update(&endpoint.url).chain_update(&body).finalize());
+
+        let mut last_error = String::new();
+        for attempt in 1..=self.max_attempts {
+            match self.attempt(endpoint, event_type, &delivery_id, &body).await {
+                Ok(()) => return,
+                Err(e) => {
+                    last_error = format!("{e:#}");
+                    log::warn!(
+                        "Webhook {} attempt {}/{} failed: {}",
+                        endpoint.url,
+                        attempt,
+                        self.max_attempts,
+                        last_error
+                    );
+                }
+            }
+            if attempt < self.max_attempts {
+                let backoff = Duration::from_secs(1 << attempt.min(6)).min(MAX_BACKOFF);
+                tokio::time::sleep(backoff).await;
+            }
+        }
+
+        let letter = DeadLetter {
+            delivery_id,
+            endpoint: endpoint.url.clone(),
+            event_type: event_type.to_string(),
+            payload: body,
+            attempts: self.max_attempts,
+            last_error,
+            failed_at: unix_now() as i64,
+        };
+        if let Err(e) = self.sink.save_dead_letter(&letter).await {
+            log::error!("Failed to dead-letter webhook for {}: {}", letter.endpoint, e);
+        }
+    }
+
+    async fn attempt(
+        &self,
+        endpoint: &Endpoint,
+        event_type: &str,
+        delivery_id: &str,
+        body: &str,
+    ) -> Result<()> {
+        let timestamp = unix_now().to_string();
+        let mut mac = Hmac::<Sha256>::new_from_slice(&endpoint.secret)?;
+        mac.update(timestamp.as_bytes());
+        mac.update(b".");
+        mac.update(body.as_bytes());
+        let signature = format!("sha256={}", to_hex(&mac.finalize().into_bytes()));
+
+        let response = self
+            .client
+            .post(&endpoint.url)
+            .header("Content-Type", "application/json")
+            .header("X-Dream-Event", event_type)
+            .header("X-Dream-Delivery", delivery_id)
+            .header("X-Dream-Timestamp", timestamp)
+            .header("X-Dream-Signature", signature)
+            .body(body.to_string())
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            bail!("endpoint responded {}", status);
+        }
+        Ok(())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
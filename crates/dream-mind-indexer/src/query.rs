@@ -0,0 +1,237 @@
+//! Read side of the indexer database. Integers come back as the `i64`/`i16`
+//! the sink stored and are widened to their on-chain types here.
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::sink::{FromRow, Param, Row, Sink};
+
+#[derive(Serialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DreamRow {
+    pub id: u64,
+    pub dreamer: String,
+    /// Hex-encoded 32-byte content hash.
+    pub content_hash: String,
+    pub token_reward: u64,
+    pub mev_protected: bool,
+    pub recorded_at: i64,
+    pub signature: String,
+    pub slot: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MintRow {
+    pub mint: String,
+    pub owner: String,
+    pub token_id: u64,
+    pub quantum_core: String,
+    pub dream_level: u8,
+    pub lucid_power: u8,
+    pub mind_strength: u8,
+    pub metadata_uri: String,
+    pub minted_at: i64,
+    pub signature: String,
+    pub slot: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StakeRow {
+    pub staker: String,
+    pub amount: u64,
+    pub access_level: u8,
+    pub staked_at: i64,
+    pub signature: String,
+    pub slot: u64,
+}
+
+/// Aggregate dream activity for one dreamer.
+#[derive(Serialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DreamerStats {
+    pub dreamer: String,
+    pub dream_count: u64,
+    pub total_rewards: u64,
+    pub first_dream_at: Option<i64>,
+    pub last_dream_at: Option<i64>,
+}
+
+const DREAM_COLUMNS: &str =
+    "id, dreamer, content_hash, token_reward, mev_protected, recorded_at, signature, slot";
+
+const STAKE_COLUMNS: &str = "staker, amount, access_level, staked_at, signature, slot";
+
+// SUM over BIGINT is NUMERIC in Postgres, so cast back for a portable i64 column
+const DREAMER_STATS_COLUMNS: &str = "dreamer, COUNT(*), CAST(COALESCE(SUM(token_reward), 0) AS BIGINT), \
+     MIN(recorded_at), MAX(recorded_at)";
+
+const MINT_COLUMNS: &str = "mint, owner, token_id, quantum_core, dream_level, lucid_power, \
+     mind_strength, metadata_uri, minted_at, signature, slot";
+
+impl FromRow for DreamRow {
+    fn from_row(row: &dyn Row) -> Result<Self> {
+        Ok(Self {
+            id: row.int(0)? as u64,
+            dreamer: row.text(1)?,
+            content_hash: row.text(2)?,
+            token_reward: row.int(3)? as u64,
+            mev_protected: row.boolean(4)?,
+            recorded_at: row.int(5)?,
+            signature: row.text(6)?,
+            slot: row.int(7)? as u64,
+        })
+    }
+}
+
+impl FromRow for StakeRow {
+    fn from_row(row: &dyn Row) -> Result<Self> {
+        Ok(Self {
+            staker: row.text(0)?,
+            amount: row.int(1)? as u64,
+            access_level: row.small_int(2)? as u8,
+            staked_at: row.int(3)?,
+            signature: row.text(4)?,
+            slot: row.int(5)? as u64,
+        })
+    }
+}
+
+impl FromRow for DreamerStats {
+    fn from_row(row: &dyn Row) -> Result<Self> {
+        Ok(Self {
+            dreamer: row.text(0)?,
+            dream_count: row.int(1)? as u64,
+            total_rewards: row.int(2)? as u64,
+            first_dream_at: row.opt_int(3)?,
+            last_dream_at: row.opt_int(4)?,
+        })
+    }
+}
+
+impl FromRow for MintRow {
+    fn from_row(row: &dyn Row) -> Result<Self> {
+        Ok(Self {
+            mint: row.text(0)?,
+            owner: row.text(1)?,
+            token_id: row.int(2)? as u64,
+            quantum_core: row.text(3)?,
+            dream_level: row.small_int(4)? as u8,
+            lucid_power: row.small_int(5)? as u8,
+            mind_strength: row.small_int(6)? as u8,
+            metadata_uri: row.text(7)?,
+            minted_at: row.int(8)?,
+            signature: row.text(9)?,
+            slot: row.int(10)? as u64,
+        })
+    }
+}
+
+impl Sink {
+    /// Dreams with an ID greater than `after`, oldest first.
+    pub async fn dreams_after(&self, after: Option<u64>, limit: u32) -> Result<Vec<DreamRow>> {
+        self.query_rows(
+            &format!("SELECT {DREAM_COLUMNS} FROM dreams WHERE id > $1 ORDER BY id ASC LIMIT $2"),
+            &[cursor(after), Param::Int(limit as i64)],
+        )
+        .await
+    }
+
+    /// OneirobotNFT mints with a token ID greater than `after`, oldest first.
+    pub async fn mints_after(&self, after: Option<u64>, limit: u32) -> Result<Vec<MintRow>> {
+        self.query_rows(
+            &format!("SELECT {MINT_COLUMNS} FROM mints WHERE token_id > $1 ORDER BY token_id ASC LIMIT $2"),
+            &[cursor(after), Param::Int(limit as i64)],
+        )
+        .await
+    }
+
+    /// Dreams with an ID lower than `before` (or the newest, if unset), newest first.
+    pub async fn dreams_before(&self, before: Option<u64>, limit: u32) -> Result<Vec<DreamRow>> {
+        self.query_rows(
+            &format!("SELECT {DREAM_COLUMNS} FROM dreams WHERE id < $1 ORDER BY id DESC LIMIT $2"),
+            &[
+                Param::Int(before.map_or(i64::MAX, |id| id as i64)),
+                Param::Int(limit as i64),
+            ],
+        )
+        .await
+    }
+
+    /// Dreams recorded by `dreamer` with an ID lower than `before`, newest first.
+    pub async fn dreams_by_dreamer(
+        &self,
+        dreamer: &str,
+        before: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<DreamRow>> {
+        self.query_rows(
+            &format!(
+                "SELECT {DREAM_COLUMNS} FROM dreams WHERE dreamer = $1 AND id < $2 ORDER BY id DESC LIMIT $3"
+            ),
+            &[
+                Param::Text(dreamer.to_string()),
+                Param::Int(before.map_or(i64::MAX, |id| id as i64)),
+                Param::Int(limit as i64),
+            ],
+        )
+        .await
+    }
+
+    /// Stats for `dreamer`, or `None` if they have never recorded a dream.
+    pub async fn dreamer_stats(&self, dreamer: &str) -> Result<Option<DreamerStats>> {
+        let rows = self
+            .query_rows(
+                &format!("SELECT {DREAMER_STATS_COLUMNS} FROM dreams WHERE dreamer = $1 GROUP BY dreamer"),
+                &[Param::Text(dreamer.to_string())],
+            )
+            .await?;
+        Ok(rows.into_iter().next())
+    }
+
+    /// Dreamers ranked by number of dreams recorded, ties broken by rewards.
+    pub async fn leaderboard(&self, limit: u32) -> Result<Vec<DreamerStats>> {
+        self.query_rows(
+            &format!(
+                "SELECT {DREAMER_STATS_COLUMNS} FROM dreams GROUP BY dreamer \
+                 ORDER BY COUNT(*) DESC, SUM(token_reward) DESC, dreamer ASC LIMIT $1"
+            ),
+            &[Param::Int(limit as i64)],
+        )
+        .await
+    }
+
+    pub async fn stake(&self, staker: &str) -> Result<Option<StakeRow>> {
+        let rows = self
+            .query_rows(
+                &format!("SELECT {STAKE_COLUMNS} FROM stakes WHERE staker = $1"),
+                &[Param::Text(staker.to_string())],
+            )
+            .await?;
+        Ok(rows.into_iter().next())
+    }
+
+    pub async fn mint(&self, mint: &str) -> Result<Option<MintRow>> {
+        let rows = self
+            .query_rows(
+                &format!("SELECT {MINT_COLUMNS} FROM mints WHERE mint = $1"),
+                &[Param::Text(mint.to_string())],
+            )
+            .await?;
+        Ok(rows.into_iter().next())
+    }
+
+    pub async fn mints_by_owner(&self, owner: &str) -> Result<Vec<MintRow>> {
+        self.query_rows(
+            &format!("SELECT {MINT_COLUMNS} FROM mints WHERE owner = $1 ORDER BY token_id ASC"),
+            &[Param::Text(owner.to_string())],
+        )
+        .await
+    }
+}
+
+/// IDs start at 0, so "no cursor" has to sort below every stored value.
+fn cursor(after: Option<u64>) -> Param {
+    Param::Int(after.map_or(-1, |id| id as i64))
+}
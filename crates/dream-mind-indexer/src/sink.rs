@@ -0,0 +1,436 @@
+use std::{error::Error, sync::Mutex};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{bail, Result};
+use bytes::BytesMut;
+use dream_mind_events::DreamEvent;
+use postgres_types::{to_sql_checked, IsNull, ToSql, Type};
+use rusqlite::types::ToSqlOutput;
+use solana_sdk::signature::Signature;
+use tokio_postgres::NoTls;
+
+/// Applied in order on every connect; each file must be idempotent.
+const MIGRATIONS: &[&str] = &[
+    include_str!("../migrations/0001_init.sql"),
+    include_str!("../migrations/0002_webhooks.sql"),
+    include_str!("../migrations/0003_dream_metadata.sql"),
+];
+
+/// Relational sink backed by either SQLite or Postgres, chosen by URL scheme.
+/// Every insert is idempotent so replays and live updates can overlap.
+pub enum Sink {
+    Sqlite(Mutex<rusqlite::Connection>),
+    Postgres(tokio_postgres::Client),
+}
+
+pub struct DeadLetter {
+    pub delivery_id: String,
+    pub endpoint: String,
+    pub event_type: String,
+    pub payload: String,
+    pub attempts: u32,
+    pub last_error: String,
+    pub failed_at: i64,
+}
+
+/// Column value that binds to both backends. Statements are written with
+/// Postgres `$N` placeholders, which SQLite accepts as `?N`.
+#[derive(Debug)]
+pub(crate) enum Param {
+    Int(i64),
+    SmallInt(i16),
+    Bool(bool),
+    Text(String),
+}
+
+impl Sink {
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        if let Some(path) = database_url.strip_prefix("sqlite://") {
+            let connection = rusqlite::Connection::open(path)?;
+            for migration in MIGRATIONS {
+                connection.execute_batch(migration)?;
+            }
+            return Ok(Self::Sqlite(Mutex::new(connection)));
+        }
+
+        if database_url.starts_with("postgres://") || database_url.starts_with("postgresql://") {
+            let (client, connection) = tokio_postgres::connect(database_url, NoTls).await?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    log::error!("Postgres connection closed: {}", e);
+                }
+            });
+            for migration in MIGRATIONS {
+                client.batch_execute(migration).await?;
+            }
+            return Ok(Self::Postgres(client));
+        }
+
+        bail!("unsupported database URL (expected sqlite:// or postgres://): {database_url}")
+    }
+
+    pub async fn checkpoint(&self, program_id: &Pubkey) -> Result<Option<Signature>> {
+        let signature = self
+            .query_text(
+                "SELECT signature FROM checkpoints WHERE program_id = $1",
+                &[Param::Text(program_id.to_string())],
+            )
+            .await?;
+
+        Ok(match signature {
+            Some(signature) => Some(signature.parse()?),
+            None => None,
+        })
+    }
+
+    /// Advance the checkpoint for `program_id`; never moves it backwards.
+    pub async fn save_checkpoint(&self, program_id: &Pubkey, signature: &str, slot: u64) -> Result<()> {
+        self.execute(
+            "INSERT INTO checkpoints (program_id, signature, slot) VALUES ($1, $2, $3)
+             ON CONFLICT (program_id) DO UPDATE SET signature = excluded.signature, slot = excluded.slot
+             WHERE checkpoints.slot <= excluded.slot",
+            &[
+                Param::Text(program_id.to_string()),
+                Param::Text(signature.to_string()),
+                Param::Int(slot as i64),
+            ],
+        )
+        .await
+    }
+
+    pub async fn ingest(&self, signature: &str, slot: u64, event: &DreamEvent) -> Result<()> {
+        let signature = Param::Text(signature.to_string());
+        let slot = Param::Int(slot as i64);
+
+        match event {
+            DreamEvent::DreamRecorded(e) => {
+                self.execute(
+                    "INSERT INTO dreams (id, dreamer, content_hash, token_reward, mev_protected, recorded_at, signature, slot)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (id) DO NOTHING",
+                    &[
+                        Param::Int(e.id as i64),
+                        Param::Text(e.dreamer.to_string()),
+                        Param::Text(to_hex(&e.content_hash)),
+                        Param::Int(e.token_reward as i64),
+                        Param::Bool(e.mev_protected),
+                        Param::Int(e.timestamp),
+                        signature,
+                        slot,
+                    ],
+                )
+                .await?;
+                self.execute(
+                    "INSERT INTO dream_metadata (id, mood, lucidity_score, sleep_duration_minutes)
+                     VALUES ($1, $2, $3, $4) ON CONFLICT (id) DO NOTHING",
+                    &[
+                        Param::Int(e.id as i64),
+                        Param::SmallInt(e.metadata.mood as i16),
+                        Param::SmallInt(e.metadata.lucidity_score as i16),
+                        Param::Int(e.metadata.sleep_duration_minutes as i64),
+                    ],
+                )
+                .await
+            }
+            DreamEvent::LucidStaked(e) => {
+                self.execute(
+                    "INSERT INTO stakes (staker, amount, access_level, staked_at, signature, slot)
+                     VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (staker) DO NOTHING",
+                    &[
+                        Param::Text(e.user.to_string()),
+                        Param::Int(e.amount as i64),
+                        Param::SmallInt(e.access_level as i16),
+                        Param::Int(e.timestamp),
+                        signature,
+                        slot,
+                    ],
+                )
+                .await
+            }
+            DreamEvent::OneirobotMinted(e) => {
+                self.execute(
+                    "INSERT INTO mints (mint, owner, token_id, quantum_core, dream_level, lucid_power, mind_strength, metadata_uri, minted_at, signature, slot)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) ON CONFLICT (mint) DO NOTHING",
+                    &[
+                        Param::Text(e.mint.to_string()),
+                        Param::Text(e.owner.to_string()),
+                        Param::Int(e.token_id as i64),
+                        Param::Text(e.quantum_core.clone()),
+                        Param::SmallInt(e.dream_level as i16),
+                        Param::SmallInt(e.lucid_power as i16),
+                        Param::SmallInt(e.mind_strength as i16),
+                        Param::Text(e.metadata_uri.clone()),
+                        Param::Int(e.timestamp),
+                        signature,
+                        slot,
+                    ],
+                )
+                .await
+            }
+            // Governance and migration events are not part of the relational schema
+            DreamEvent::MevProtectionUpdated(_)
+            | DreamEvent::LegacyStorageMigrated(_)
+            | DreamEvent::DreamBridgedOut(_)
+            | DreamEvent::DreamBridgedIn(_)
+            | DreamEvent::DreamMirrored(_)
+            | DreamEvent::RemoteMessageReceived(_)
+            | DreamEvent::ProgramInfoUpdated(_)
+            | DreamEvent::UpgradeAuthorityHandedOver(_)
+            | DreamEvent::ProgramFinalized(_)
+            | DreamEvent::SleepSessionClosed(_)
+            | DreamEvent::SeasonStarted(_)
+            | DreamEvent::EpochRootCommitted(_)
+            | DreamEvent::ResearchConsentChanged(_)
+            | DreamEvent::ResearchLicensePurchased(_)
+            | DreamEvent::ResearchRevenueClaimed(_)
+            | DreamEvent::MarketCreated(_)
+            | DreamEvent::MarketOutcomeBought(_)
+            | DreamEvent::MarketResolved(_)
+            | DreamEvent::MarketWinningsClaimed(_)
+            | DreamEvent::DuelSettled(_)
+            | DreamEvent::DreamSealed(_)
+            | DreamEvent::DreamRevealed(_)
+            | DreamEvent::CharityApprovalChanged(_)
+            | DreamEvent::CharityDonation(_)
+            | DreamEvent::RewardStreamCreated(_)
+            | DreamEvent::RewardStreamClaimed(_)
+            | DreamEvent::DreamDequeued(_)
+            | DreamEvent::CompressedStaked(_)
+            | DreamEvent::CompressedUnstaked(_)
+            | DreamEvent::CrankRewardPaid(_)
+            | DreamEvent::DreamLicensed(_)
+            | DreamEvent::CoauthorshipConfirmed(_)
+            | DreamEvent::DreamAnnotated(_)
+            | DreamEvent::AnnotationApproved(_)
+            | DreamEvent::CoauthorSharesExpired(_)
+            | DreamEvent::FeesSwept(_)
+            | DreamEvent::DreamValidated(_)
+            | DreamEvent::PinningOracleChanged(_)
+            | DreamEvent::DreamPinned(_)
+            | DreamEvent::RewardPoolToppedUp(_)
+            | DreamEvent::DreamFlagged(_)
+            | DreamEvent::InsuranceFunded(_)
+            | DreamEvent::ShortfallCovered(_)
+            | DreamEvent::ConversionRatesSet(_)
+            | DreamEvent::RewardClaimed(_)
+            | DreamEvent::StakeMigrated(_)
+            | DreamEvent::DreamRelayed(_)
+            | DreamEvent::SessionKeyCreated(_)
+            | DreamEvent::SessionKeyRevoked(_)
+            | DreamEvent::DreamExtrasUpdated(_)
+            | DreamEvent::BuildInfoUpdated(_)
+            | DreamEvent::DreamerSponsored(_)
+            | DreamEvent::TierCredentialUpdated(_)
+            | DreamEvent::TraitSeasonStarted(_)
+            | DreamEvent::OneirobotBoosted(_)
+            | DreamEvent::OneirobotBoostEnded(_)
+            | DreamEvent::HolderSnapshotTaken(_)
+            | DreamEvent::HolderAirdropClaimed(_)
+            | DreamEvent::ValidatorGateSet(_)
+            | DreamEvent::ValidatorRegistered(_)
+            | DreamEvent::ValidatorRevoked(_)
+            | DreamEvent::EditionsAllowed(_)
+            | DreamEvent::OneirobotEditionPrinted(_)
+            | DreamEvent::OneirobotNamed(_)
+            | DreamEvent::SpliceConfigSet(_)
+            | DreamEvent::OneirobotSpliced(_)
+            | DreamEvent::TreasuryHealth(_)
+            | DreamEvent::DreamerProfileMigrated(_)
+            | DreamEvent::DreamClassified(_)
+            | DreamEvent::ValidatorRewardCredited(_)
+            | DreamEvent::ValidatorRewardsClaimed(_)
+            | DreamEvent::ValidationDisputeResolved(_)
+            | DreamEvent::OnboardingBonusPaid(_)
+            | DreamEvent::GrantCreated(_)
+            | DreamEvent::GrantMilestoneApproved(_)
+            | DreamEvent::GrantClawedBack(_)
+            | DreamEvent::GuardiansRotated(_)
+            | DreamEvent::ProgramPauseChanged(_)
+            | DreamEvent::FinalizeApprovalCancelled(_)
+            | DreamEvent::DreamArchived(_)
+            | DreamEvent::StatsRolled(_)
+            | DreamEvent::ValidatorClaimCapReached(_)
+            | DreamEvent::CompanionEquipped(_)
+            | DreamEvent::OptimisticScorePosted(_)
+            | DreamEvent::OptimisticScoreVoided(_)
+            | DreamEvent::OracleQuorumSet(_)
+            | DreamEvent::FaucetMinted(_)
+            | DreamEvent::InterpreterAssigned(_)
+            | DreamEvent::DreamInterpreted(_)
+            | DreamEvent::InterpreterPaid(_)
+            | DreamEvent::DreamCertified(_)
+            | DreamEvent::StringInterned(_)
+            | DreamEvent::InterpretationExpired(_)
+            | DreamEvent::DreamRecordMigrated(_) => Ok(()),
+        }
+    }
+
+    async fn execute(&self, sql: &str, params: &[Param]) -> Result<()> {
+        match self {
+            Self::Sqlite(connection) => {
+                let sql = sql.replace('$', "?");
+                let connection = connection.lock().expect("sqlite connection poisoned");
+                connection.execute(&sql, rusqlite::params_from_iter(params))?;
+            }
+            Self::Postgres(client) => {
+                client.execute(sql, &postgres_params(params)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a webhook delivery that exhausted its retries.
+    pub async fn save_dead_letter(&self, letter: &DeadLetter) -> Result<()> {
+        self.execute(
+            "INSERT INTO webhook_dead_letters (delivery_id, endpoint, event_type, payload, attempts, last_error, failed_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (delivery_id) DO NOTHING",
+            &[
+                Param::Text(letter.delivery_id.clone()),
+                Param::Text(letter.endpoint.clone()),
+                Param::Text(letter.event_type.clone()),
+                Param::Text(letter.payload.clone()),
+                Param::SmallInt(letter.attempts as i16),
+                Param::Text(letter.last_error.clone()),
+                Param::Int(letter.failed_at),
+            ],
+        )
+        .await
+    }
+
+    pub(crate) async fn query_rows<T: FromRow>(&self, sql: &str, params: &[Param]) -> Result<Vec<T>> {
+        match self {
+            Self::Sqlite(connection) => {
+                let sql = sql.replace('$', "?");
+                let connection = connection.lock().expect("sqlite connection poisoned");
+                let mut statement = connection.prepare(&sql)?;
+                let mut rows = statement.query(rusqlite::params_from_iter(params))?;
+                let mut out = Vec::new();
+                while let Some(row) = rows.next()? {
+                    out.push(T::from_row(row)?);
+                }
+                Ok(out)
+            }
+            Self::Postgres(client) => client
+                .query(sql, &postgres_params(params))
+                .await?
+                .iter()
+                .map(|row| T::from_row(row))
+                .collect(),
+        }
+    }
+
+    async fn query_text(&self, sql: &str, params: &[Param]) -> Result<Option<String>> {
+        match self {
+            Self::Sqlite(connection) => {
+                let sql = sql.replace('$', "?");
+                let connection = connection.lock().expect("sqlite connection poisoned");
+                let mut statement = connection.prepare(&sql)?;
+                let mut rows = statement.query(rusqlite::params_from_iter(params))?;
+                Ok(match rows.next()? {
+                    Some(row) => Some(row.get(0)?),
+                    None => None,
+                })
+            }
+            Self::Postgres(client) => {
+                let row = client.query_opt(sql, &postgres_params(params)).await?;
+                Ok(row.map(|row| row.get(0)))
+            }
+        }
+    }
+}
+
+/// Positional column access shared by SQLite and Postgres rows.
+pub(crate) trait Row {
+    fn int(&self, idx: usize) -> Result<i64>;
+    fn opt_int(&self, idx: usize) -> Result<Option<i64>>;
+    fn small_int(&self, idx: usize) -> Result<i16>;
+    fn boolean(&self, idx: usize) -> Result<bool>;
+    fn text(&self, idx: usize) -> Result<String>;
+}
+
+/// Row type that can be read from either backend.
+pub(crate) trait FromRow: Sized {
+    fn from_row(row: &dyn Row) -> Result<Self>;
+}
+
+impl Row for rusqlite::Row<'_> {
+    fn int(&self, idx: usize) -> Result<i64> {
+        Ok(self.get(idx)?)
+    }
+
+    fn opt_int(&self, idx: usize) -> Result<Option<i64>> {
+        Ok(self.get(idx)?)
+    }
+
+    fn small_int(&self, idx: usize) -> Result<i16> {
+        Ok(self.get(idx)?)
+    }
+
+    fn boolean(&self, idx: usize) -> Result<bool> {
+        Ok(self.get(idx)?)
+    }
+
+    fn text(&self, idx: usize) -> Result<String> {
+        Ok(self.get(idx)?)
+    }
+}
+
+impl Row for tokio_postgres::Row {
+    fn int(&self, idx: usize) -> Result<i64> {
+        Ok(self.try_get(idx)?)
+    }
+
+    fn opt_int(&self, idx: usize) -> Result<Option<i64>> {
+        Ok(self.try_get(idx)?)
+    }
+
+    fn small_int(&self, idx: usize) -> Result<i16> {
+        Ok(self.try_get(idx)?)
+    }
+
+    fn boolean(&self, idx: usize) -> Result<bool> {
+        Ok(self.try_get(idx)?)
+    }
+
+    fn text(&self, idx: usize) -> Result<String> {
+        Ok(self.try_get(idx)?)
+    }
+}
+
+fn postgres_params(params: &[Param]) -> Vec<&(dyn ToSql + Sync)> {
+    params.iter().map(|p| p as &(dyn ToSql + Sync)).collect()
+}
+
+impl rusqlite::ToSql for Param {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        match self {
+            Self::Int(v) => rusqlite::ToSql::to_sql(v),
+            Self::SmallInt(v) => rusqlite::ToSql::to_sql(v),
+            Self::Bool(v) => rusqlite::ToSql::to_sql(v),
+            Self::Text(v) => rusqlite::ToSql::to_sql(v),
+        }
+    }
+}
+
+impl ToSql for Param {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Self::Int(v) => v.to_sql(ty, out),
+            Self::SmallInt(v) => v.to_sql(ty, out),
+            Self::Bool(v) => v.to_sql(ty, out),
+            Self::Text(v) => v.to_sql(ty, out),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as ToSql>::accepts(ty)
+            || <i16 as ToSql>::accepts(ty)
+            || <bool as ToSql>::accepts(ty)
+            || <String as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,87 @@
+//! `#[traced]` wraps every instruction handler of the program module so a
+//! failure on mainnet can be read straight from the transaction logs. Anchor
+//! already logs the instruction name and the error; the trace adds the
+//! handler's key arguments next to the error code, in one line:
+//!
+//! ```text
+//! Program log: trace top_up_pool amount=10000000000000000 failed code=6067
+//! ```
+//!
+//! Key arguments are integers, `bool`s and `Pubkey`s; anything larger is left
+//! out. They are formatted only when the handler fails, so the success path
+//! pays no compute for them.
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, FnArg, Item, ItemFn, ItemMod, Pat, ReturnType, Type};
+
+const KEY_PARAM_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "bool", "Pubkey",
+];
+
+/// Place above `#[program]`. Each handler's body runs in a closure whose
+/// error, if any, is passed to `crate::trace::log_failure` with the handler
+/// name and its key arguments before being returned unchanged.
+#[proc_macro_attribute]
+pub fn traced(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut module = parse_macro_input!(input as ItemMod);
+    if let Some((_, items)) = module.content.as_mut() {
+        for item in items.iter_mut() {
+            if let Item::Fn(handler) = item {
+                if is_handler(handler) {
+                    trace_handler(handler);
+                }
+            }
+        }
+    }
+    quote!(#module).into()
+}
+
+/// Handlers take a `Context` first, like Anchor's own parser expects.
+fn is_handler(handler: &ItemFn) -> bool {
+    match handler.sig.inputs.first() {
+        Some(FnArg::Typed(arg)) => type_name(&arg.ty).is_some_and(|name| name == "Context"),
+        _ => false,
+    }
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn trace_handler(handler: &mut ItemFn) {
+    let ReturnType::Type(_, output) = &handler.sig.output else {
+        return;
+    };
+    let params: Vec<_> = handler
+        .sig
+        .inputs
+        .iter()
+        .skip(1)
+        .filter_map(|arg| match arg {
+            FnArg::Typed(arg) => match (&*arg.pat, type_name(&arg.ty)) {
+                (Pat::Ident(pat), Some(name)) if KEY_PARAM_TYPES.contains(&name.as_str()) => {
+                    Some(pat.ident.clone())
+                }
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    // Copied up front: the body may shadow or mutate its arguments
+    let copies: Vec<_> = params.iter().map(|param| format_ident!("__trace_{}", param)).collect();
+    let format: String = params.iter().map(|param| format!(" {param}={{}}")).collect();
+    let tag = handler.sig.ident.to_string();
+    let body = &handler.block;
+    *handler.block = parse_quote!({
+        #(let #copies = #params;)*
+        let result: #output = (move || -> #output #body)();
+        result.map_err(|err| {
+            crate::trace::log_failure(#tag, format_args!(#format, #(#copies),*), &err);
+            err
+        })
+    });
+}
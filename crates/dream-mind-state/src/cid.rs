@@ -0,0 +1,104 @@
+//! IPFS CIDs for dream content. A dream's `content_hash` is the SHA-256 of its
+//! content, which is also the multihash digest of the CIDv1 that
+//! `ipfs add --cid-version 1 --raw-leaves` gives a single-block file
+//! (under the 256 KiB default chunk size). The CID can therefore be derived
+//! from the record without storing it.
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::DreamMetadata;
+
+const CID_VERSION_1: u8 = 0x01;
+const CODEC_RAW: u8 = 0x55;
+const MULTIHASH_SHA2_256: u8 = 0x12;
+const SHA2_256_LEN: u8 = 32;
+/// Multibase prefix for lowercase RFC 4648 base32 without padding.
+const MULTIBASE_BASE32: char = 'b';
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Base32 CIDv1 of the raw block whose SHA-256 is `content_hash`.
+pub fn raw_cid(content_hash: &[u8; 32]) -> String {
+    let mut bytes = vec![CID_VERSION_1, CODEC_RAW, MULTIHASH_SHA2_256, SHA2_256_LEN];
+    bytes.extend_from_slice(content_hash);
+
+    let mut out = String::with_capacity(1 + (bytes.len() * 8).div_ceil(5));
+    out.push(MULTIBASE_BASE32);
+    let (mut buffer, mut bits) = (0u16, 0u8);
+    for byte in bytes {
+        buffer = (buffer << 8) | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[usize::from((buffer >> bits) & 0x1f)] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)] as char);
+    }
+    out
+}
+
+/// Prefix of the message a pinning oracle signs, so the signature can't be
+/// replayed as anything else.
+pub const PIN_ATTESTATION_DOMAIN: &[u8] = b"dream-mind-lucid:pinned:v1";
+
+/// What a pinning oracle signs (ed25519) to attest that `raw_cid(content_hash)`
+/// is retrievable for `dream_record`: the domain, the record address and the
+/// content hash.
+pub fn pin_attestation_message(dream_record: &Pubkey, content_hash: &[u8; 32]) -> Vec<u8> {
+    [PIN_ATTESTATION_DOMAIN, dream_record.as_ref(), content_hash].concat()
+}
+
+/// Prefix of the message an oracle signs to classify a dream.
+pub const CLASSIFICATION_ATTESTATION_DOMAIN: &[u8] = b"dream-mind-lucid:classified:v1";
+
+/// What an approved oracle signs (ed25519) to classify `dream_record` with
+/// `classify_dream`: the domain, the record address, its content hash, the
+/// language, the NSFW flag, the topic bits and the `revision` being written.
+pub fn classification_attestation_message(
+    dream_record: &Pubkey,
+    content_hash: &[u8; 32],
+    language: &[u8; 2],
+    nsfw: bool,
+    topics: u64,
+    revision: u32,
+) -> Vec<u8> {
+    [
+        CLASSIFICATION_ATTESTATION_DOMAIN,
+        dream_record.as_ref(),
+        content_hash,
+        language,
+        &[u8::from(nsfw)],
+        &topics.to_le_bytes(),
+        &revision.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// Prefix of the message a pinning oracle signs when a dream's CID no longer
+/// resolves.
+pub const DEAD_CID_ATTESTATION_DOMAIN: &[u8] = b"dream-mind-lucid:dead-cid:v1";
+
+/// What an approved oracle signs (ed25519) to attest that `raw_cid(content_hash)`
+/// can't be retrieved for `dream_record`; a `FraudProof::DeadCid` against an
+/// optimistic score.
+pub fn dead_cid_attestation_message(dream_record: &Pubkey, content_hash: &[u8; 32]) -> Vec<u8> {
+    [DEAD_CID_ATTESTATION_DOMAIN, dream_record.as_ref(), content_hash].concat()
+}
+
+/// Prefix of the message a dreamer signs for a relayed dream.
+pub const DELEGATED_DREAM_DOMAIN: &[u8] = b"dream-mind-lucid:delegated-dream:v1";
+
+/// What a dreamer signs (ed25519) so a relayer can record a dream for them
+/// with `record_dream_delegated`: the domain, the content hash (and with it
+/// `raw_cid(content_hash)`), the metadata as borsh lays it out and the
+/// dreamer's `RelayNonce`.
+pub fn delegated_dream_message(
+    content_hash: &[u8; 32],
+    metadata: &DreamMetadata,
+    nonce: u64,
+) -> Vec<u8> {
+    let [minutes_lo, minutes_hi] = metadata.sleep_duration_minutes.to_le_bytes();
+    let metadata = [metadata.mood as u8, metadata.lucidity_score, minutes_lo, minutes_hi];
+    [DELEGATED_DREAM_DOMAIN, content_hash, &metadata, &nonce.to_le_bytes()].concat()
+}
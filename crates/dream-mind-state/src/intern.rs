@@ -0,0 +1,12 @@
+//! Content-addressed string interning. A dream tag or quantum core name is
+//! stored once, in an `InternedString` PDA seeded by its id, and accounts
+//! that use it hold only the 8-byte id. The id is derived from the string,
+//! so a writer needs no lookup and a reader checks what it resolves.
+
+use anchor_lang::solana_program::hash::hash;
+
+/// Id of `value`: the first 8 bytes of its SHA-256, little-endian.
+pub fn string_id(value: &str) -> u64 {
+    let digest = hash(value.as_bytes()).to_bytes();
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+/// Program ID declared by the legacy native dream program (`solana/programs/src/lib.rs`).
+/// Accounts owned by this program can be imported into the canonical program
+/// through `migrate_legacy_storage`.
+#[cfg(feature = "mainnet")]
+pub const LEGACY_PROGRAM_ID: Pubkey = pubkey!("2LdwKfX1aS5bjTjPGqKt14Y9p6hXrsHt7RqDZm7VanUd");
+#[cfg(not(feature = "mainnet"))]
+pub const LEGACY_PROGRAM_ID: Pubkey = pubkey!("2MF7fnpjrUzs6rQSwqsgqgPKxCBv5G3DdJxLEVZwQbM3");
+
+/// Borsh layout of the `DreamStorage` account written by the legacy native program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DreamStorage {
+    pub dreamer: Pubkey,
+    pub dream_count: u64,
+    pub total_tokens_earned: u64,
+    pub is_initialized: bool,
+}
+
+impl DreamStorage {
+    pub const SPACE: usize = 32 + 8 + 8 + 1;
+}
+
+/// Layout of `LucidStake` before stakes were keyed by mint, at
+/// `[b"stake", user]`. `migrate_stake` moves these to the mint-keyed address.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LucidStakeV1 {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub access_level: u8,
+}
+
+/// Layout of `DreamerProfile` before it recorded its creation time and
+/// validated dreams. `migrate_dreamer_profile` grows these in place.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DreamerProfileV1 {
+    pub dreamer: Pubkey,
+    pub interface_count: u64,
+    pub legacy_migrated: bool,
+    pub legacy_dream_count: u64,
+    pub legacy_tokens_earned: u64,
+    pub last_active_day: u32,
+    pub research_consent: bool,
+    pub reputation: i16,
+}
@@ -0,0 +1,2653 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+use dream_mind_common::constants::{
+    COMPRESSED_STAKE_TREE_DEPTH, CRANK_KIND_COUNT, DREAM_LICENSE_TYPE_COUNT,
+    EPOCH_STATS_PERIOD_SECS, FAUCET_COOLDOWN_SECS, GUARDIAN_COUNCIL_SIZE, INTERPRETER_SHARE_BPS,
+    LEADERBOARD_SIZE, MAX_CO_DREAMERS, MAX_GRANT_MILESTONES, MAX_MARKET_OUTCOMES, MAX_REPUTATION,
+    MAX_REWARDED_DREAMS_PER_SESSION, MAX_ROYALTY_CO_AUTHORS, MIN_ESTABLISHED_ACCOUNT_AGE_SECS,
+    MIN_ESTABLISHED_VALIDATED_DREAMS, ONBOARDING_REWARD_MULTIPLIER_BPS,
+    OPTIMISTIC_CHALLENGE_WINDOW_SECS, REPUTATION_BAND_FLOORS, REPUTATION_BAND_REWARD_BPS,
+    REPUTATION_DECAY_PER_DAY, REPUTATION_NEUTRAL_VALIDATION_SCORE, REWARD_CLAIM_EXPIRY_SECS,
+    REWARD_CLAIM_GRACE_SECS, REWARD_POOL_PERIOD_SECS, STATS_RING_SIZE, TOKEN_UNIT,
+    VALIDATION_PRIORITY_SECS_PER_STAKED_TOKEN, VALIDATION_QUEUE_SIZE,
+};
+
+pub mod cid;
+pub mod intern;
+pub mod legacy;
+pub mod merkle;
+pub mod messaging;
+pub mod oneirobot;
+pub mod stake_tree;
+pub mod views;
+pub mod wormhole;
+
+// Canonical Dream-Mind-Lucid program ID (packages/core/programs/dream-mind-lucid).
+// Account owner checks for every struct below resolve against this ID.
+#[cfg(feature = "mainnet")]
+declare_id!("5oDxEKGa78LjcE9zMFqz1vLLgKYj4Drd6k1Vq2GJ6YNm");
+#[cfg(not(feature = "mainnet"))]
+declare_id!("AXDRuY5MwRGJ4HfxMgyARhC1XxoMKgCXGhnVTWTfMeHc");
+
+// ===================== STATE STRUCTURES =====================
+
+#[account]
+pub struct Treasury {
+    pub authority: Pubkey,
+    pub dream_mint: Pubkey,
+    pub smind_mint: Pubkey,
+    pub lucid_mint: Pubkey,
+    pub total_dreams_recorded: u64,
+    pub total_rewards_distributed: u64,
+    pub mev_protection_enabled: bool,
+    pub total_bridged_out: u64,
+    pub total_bridged_in: u64,
+    pub bridge_messages_sent: u64,
+    pub total_dreams_mirrored: u64,
+    /// Current leaderboard season, advanced by `start_season`.
+    pub season: u32,
+    /// Co-dreamer shares forfeited by decay or expiry; never minted.
+    pub total_rewards_expired: u64,
+    /// Canonical PDA bump, stored at init so constraints and signer seeds
+    /// don't re-derive it. Fits in the struct padding; treasuries created
+    /// before it read 0 until `store_treasury_bump`.
+    pub bump: u8,
+    /// Set by the guardian council, cleared by governance. Also fits in the
+    /// padding, so existing treasuries read unpaused.
+    pub paused: bool,
+}
+
+impl Treasury {
+    pub fn reward_mint(&self, token: RewardToken) -> Pubkey {
+        match token {
+            RewardToken::Smind => self.smind_mint,
+            RewardToken::Lucid => self.lucid_mint,
+        }
+    }
+}
+
+/// Token a dream's DREAM reward can be converted into by `claim_reward_as`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RewardToken {
+    Smind,
+    Lucid,
+}
+
+/// Governance-set rates for `claim_reward_as`, in bps of the DREAM burned:
+/// 10_000 mints one SMIND or LUCID per DREAM. 0 disables a token.
+#[account]
+#[derive(Debug)]
+pub struct RewardConversionRates {
+    pub smind_rate_bps: u32,
+    pub lucid_rate_bps: u32,
+    pub updated_at: i64,
+    pub total_dream_burned: u64,
+}
+
+impl RewardConversionRates {
+    pub fn rate_bps(&self, token: RewardToken) -> u32 {
+        match token {
+            RewardToken::Smind => self.smind_rate_bps,
+            RewardToken::Lucid => self.lucid_rate_bps,
+        }
+    }
+}
+
+/// A dream's converted reward. Seeded by the record, so each dream's reward
+/// is converted at most once.
+#[account]
+#[derive(Debug)]
+pub struct RewardClaim {
+    pub dream_record: Pubkey,
+    pub token: RewardToken,
+    /// DREAM burned.
+    pub dream_amount: u64,
+    /// SMIND or LUCID minted.
+    pub amount_out: u64,
+    pub claimed_at: i64,
+}
+
+/// Self-reported mood on waking.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mood {
+    #[default]
+    Unspecified,
+    Peaceful,
+    Joyful,
+    Excited,
+    Neutral,
+    Confused,
+    Sad,
+    Anxious,
+    Fearful,
+}
+
+/// Optional structured fields of a dream record. 0 / `Unspecified` means
+/// the dreamer didn't report the value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamMetadata {
+    pub mood: Mood,
+    /// 1 (barely aware) to MAX_LUCIDITY_SCORE (fully lucid).
+    pub lucidity_score: u8,
+    pub sleep_duration_minutes: u16,
+}
+
+/// One dream of a `record_dreams_batch`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchedDream {
+    pub content_hash: [u8; 32],
+    pub metadata: DreamMetadata,
+}
+
+#[account]
+pub struct DreamRecord {
+    pub id: u64,
+    pub dreamer: Pubkey,
+    pub content_hash: [u8; 32],
+    pub timestamp: i64,
+    pub token_reward: u64,
+    pub mev_protected: bool,
+    /// Wormhole chain the dream was recorded on. 0 for records created before
+    /// mirroring existed, which are all native.
+    pub origin_chain: u16,
+    /// Fits in the struct padding of older records, which read as unreported.
+    pub metadata: DreamMetadata,
+    /// `SleepSession` the dream was counted in, or the default key for mirrored
+    /// dreams. Records created before sessions existed are shorter and end
+    /// before this field until `migrate_dream_record` grows them.
+    pub session: Pubkey,
+}
+
+impl DreamRecord {
+    /// Where `DreamRecordExtras` start: right after the fixed fields, at the
+    /// size `record_dream` allocates. Pinned, since grown records already hold
+    /// their extras here; per-dream data that comes later gets its own PDA.
+    pub const EXTRAS_OFFSET: usize = 136;
+
+    /// Extras in a record account's data; a record that was never grown has none.
+    pub fn extras(data: &[u8]) -> Result<DreamRecordExtras> {
+        match data.get(Self::EXTRAS_OFFSET..) {
+            Some(extras) if !extras.is_empty() => DreamRecordExtras::deserialize(&mut &extras[..])
+                .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize)),
+            _ => Ok(DreamRecordExtras::default()),
+        }
+    }
+}
+
+// A new fixed field would move the extras of records already grown
+const _: () = assert!(8 + std::mem::size_of::<DreamRecord>() == DreamRecord::EXTRAS_OFFSET);
+
+/// Optional fields a dreamer adds after recording. They live past the fixed
+/// `DreamRecord` fields, and `set_dream_extras` reallocs the record to fit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamRecordExtras {
+    /// Up to MAX_DREAM_TAGS tag ids; each resolves through the
+    /// `InternedString` of `intern::string_id(tag)`.
+    pub tags: Vec<u64>,
+    /// `DreamAnnotation` accounts the dreamer points readers to.
+    pub annotations: Vec<Pubkey>,
+}
+
+impl DreamRecordExtras {
+    /// Borsh-encoded length.
+    pub fn space(&self) -> usize {
+        4 + 8 * self.tags.len() + 4 + 32 * self.annotations.len()
+    }
+}
+
+/// A string stored once for every account that refers to it by id. Seeds
+/// `[b"interned", id.to_le_bytes()]`, where `id` is `intern::string_id(value)`.
+#[account]
+#[derive(Debug)]
+pub struct InternedString {
+    pub id: u64,
+    pub value: String,
+}
+
+impl InternedString {
+    /// Account size holding `value`, discriminator included.
+    pub fn space(value: &str) -> usize {
+        8 + 8 + 4 + value.len()
+    }
+}
+
+/// One UTC day of a dreamer's sleep, seeded by the day. Every dream recorded
+/// that day counts towards its reward cap.
+#[account]
+pub struct SleepSession {
+    pub dreamer: Pubkey,
+    /// `EpochStats::day_of` the session's day. Sessions opened before they
+    /// were per-day carry a client-chosen id.
+    pub session_id: u64,
+    pub started_at: i64,
+    /// 0 while the session is open.
+    pub ended_at: i64,
+    /// Hash of the sleep tracker's attestation, if the device provides one.
+    pub device_attestation: [u8; 32],
+    pub dream_count: u32,
+    pub rewards_earned: u64,
+}
+
+impl SleepSession {
+    /// Starts the session if this is its first use on `day`.
+    pub fn start(&mut self, dreamer: Pubkey, day: u32, now: i64) {
+        if self.dreamer == Pubkey::default() {
+            self.dreamer = dreamer;
+            self.session_id = u64::from(day);
+            self.started_at = now;
+        }
+    }
+
+    /// Counts a dream and returns the reward it earns: `reward`, or 0 once
+    /// `MAX_REWARDED_DREAMS_PER_SESSION` dreams have been counted.
+    pub fn count_dream(&mut self, reward: u64) -> u64 {
+        let reward = if self.dream_count < MAX_REWARDED_DREAMS_PER_SESSION {
+            reward
+        } else {
+            0
+        };
+        self.dream_count += 1;
+        self.rewards_earned += reward;
+        reward
+    }
+}
+
+#[account]
+pub struct DreamerProfile {
+    pub dreamer: Pubkey,
+    pub interface_count: u64,
+    pub legacy_migrated: bool,
+    pub legacy_dream_count: u64,
+    pub legacy_tokens_earned: u64,
+    /// Last `EpochStats` day the dreamer recorded a dream in. Fits in the
+    /// struct padding, so older profiles read 0.
+    pub last_active_day: u32,
+    /// Opt-in for licensed research snapshots; also padding-backed.
+    pub research_consent: bool,
+    /// -MAX_REPUTATION to MAX_REPUTATION; scales dream rewards by band. Takes
+    /// the last of the padding, so older profiles read neutral.
+    pub reputation: i16,
+    /// Set on the profile's first write. Profiles from before this field
+    /// must be grown with `migrate_dreamer_profile`, which stamps its time.
+    pub account_created_at: i64,
+    /// Dreams of this dreamer that `validate_dream` has scored.
+    pub validated_dreams: u32,
+}
+
+impl DreamerProfile {
+    /// Record the owner and, the first time the profile is written, when it
+    /// was created. Every instruction that may create the profile calls this.
+    pub fn set_dreamer(&mut self, dreamer: Pubkey, now: i64) {
+        self.dreamer = dreamer;
+        if self.account_created_at == 0 {
+            self.account_created_at = now;
+        }
+    }
+
+    /// Whether the profile is old enough and has a validated dream, which
+    /// sybil-prone actions like `create_market` require.
+    pub fn is_established(&self, now: i64) -> bool {
+        self.account_created_at > 0
+            && now - self.account_created_at >= MIN_ESTABLISHED_ACCOUNT_AGE_SECS
+            && self.validated_dreams >= MIN_ESTABLISHED_VALIDATED_DREAMS
+    }
+
+    /// Reputation on `day`, decayed toward neutral for every day since the
+    /// dreamer last recorded a dream. Decay is only applied when they record,
+    /// so changes in between decay from their last dream too.
+    pub fn reputation_on(&self, day: u32) -> i16 {
+        let days = day.saturating_sub(self.last_active_day);
+        let decay = i64::from(days) * i64::from(REPUTATION_DECAY_PER_DAY);
+        let reputation = i64::from(self.reputation);
+        (reputation.signum() * (reputation.abs() - decay).max(0)) as i16
+    }
+
+    pub fn adjust_reputation(&mut self, change: i16) {
+        self.reputation =
+            self.reputation.saturating_add(change).clamp(-MAX_REPUTATION, MAX_REPUTATION);
+    }
+
+    /// Reward multiplier of `reputation`'s band, in bps.
+    pub fn band_reward_bps(reputation: i16) -> u16 {
+        let band = REPUTATION_BAND_FLOORS
+            .iter()
+            .rposition(|floor| reputation >= *floor)
+            .unwrap_or(0);
+        REPUTATION_BAND_REWARD_BPS[band]
+    }
+
+    /// Onboarding multiplier for the dreamer's next validated dream, in bps;
+    /// 10_000 once past `ONBOARDING_REWARD_MULTIPLIER_BPS`.
+    pub fn onboarding_multiplier_bps(&self) -> u16 {
+        ONBOARDING_REWARD_MULTIPLIER_BPS
+            .get(self.validated_dreams as usize)
+            .copied()
+            .unwrap_or(10_000)
+    }
+
+    /// Reputation change for a validation `score`.
+    pub fn validation_change(score: u8) -> i16 {
+        (i16::from(score) - i16::from(REPUTATION_NEUTRAL_VALIDATION_SCORE)) / 2
+    }
+}
+
+/// Co-dreamers listed on a shared dream. The reward is split equally between
+/// the recorder and every co-dreamer; each co-dreamer's share is minted when
+/// they confirm with `confirm_coauthorship`, less any decay (see `claimable`).
+#[account]
+#[derive(Debug)]
+pub struct DreamCoauthors {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub count: u8,
+    pub co_dreamers: [Pubkey; MAX_CO_DREAMERS],
+    pub confirmed: [bool; MAX_CO_DREAMERS],
+    /// DREAM owed to each co-dreamer.
+    pub share: u64,
+    pub recorded_at: i64,
+}
+
+impl DreamCoauthors {
+    pub fn co_dreamers(&self) -> &[Pubkey] {
+        &self.co_dreamers[..self.count as usize]
+    }
+
+    /// Part of a share still claimable at `now`: all of it during the grace
+    /// period, then falling linearly to nothing at the expiry.
+    pub fn claimable(&self, now: i64) -> u64 {
+        let elapsed = now - self.recorded_at;
+        if elapsed <= REWARD_CLAIM_GRACE_SECS {
+            return self.share;
+        }
+        let remaining = (REWARD_CLAIM_EXPIRY_SECS - elapsed).max(0) as u128;
+        let window = (REWARD_CLAIM_EXPIRY_SECS - REWARD_CLAIM_GRACE_SECS) as u128;
+        (self.share as u128 * remaining / window) as u64
+    }
+
+    /// Shares of co-dreamers who haven't confirmed.
+    pub fn unconfirmed(&self) -> u64 {
+        let pending = self.confirmed[..self.count as usize].iter().filter(|c| !**c).count();
+        self.share * pending as u64
+    }
+}
+
+/// A charity wallet the authority has approved to receive reward splits.
+/// Revoking approval stops new donations without touching dreamers' splits.
+#[account]
+pub struct Charity {
+    pub wallet: Pubkey,
+    pub approved: bool,
+    pub updated_at: i64,
+    pub total_received: u64,
+}
+
+/// Share of a dreamer's DREAM rewards minted to `charity` instead of to them.
+#[account]
+pub struct CharitySplit {
+    pub dreamer: Pubkey,
+    /// Wallet of the chosen `Charity`.
+    pub charity: Pubkey,
+    pub share_bps: u16,
+    pub total_donated: u64,
+}
+
+/// License revenue owed to consenting dreamers, tracked as a running LUCID
+/// amount per consenting dreamer; the tokens sit in the `research_vault`.
+#[account]
+pub struct ResearchPool {
+    pub consenting_dreamers: u64,
+    pub revenue_per_dreamer: u64,
+    pub licenses_sold: u64,
+    pub total_revenue: u64,
+}
+
+#[account]
+pub struct ResearchParticipant {
+    pub dreamer: Pubkey,
+    /// `revenue_per_dreamer` when the dreamer last consented or claimed.
+    pub revenue_checkpoint: u64,
+    /// Revenue earned before consent was last withdrawn, not yet claimed.
+    pub accrued: u64,
+}
+
+#[account]
+pub struct ResearchLicense {
+    pub licensee: Pubkey,
+    pub purchased_at: i64,
+    pub expires_at: i64,
+    pub total_paid: u64,
+}
+
+/// Uses a buyer can license a single dream for.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DreamLicenseType {
+    Commercial,
+    Derivative,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoyaltyShare {
+    pub recipient: Pubkey,
+    /// Share of what is left after the treasury's cut.
+    pub share_bps: u16,
+}
+
+/// The dreamer's licensing offer for one dream.
+#[account]
+#[derive(Debug)]
+pub struct DreamLicenseTerms {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    /// LUCID per year for each `DreamLicenseType`; 0 means not offered.
+    pub prices: [u64; DREAM_LICENSE_TYPE_COUNT],
+    pub co_author_count: u8,
+    pub co_authors: [RoyaltyShare; MAX_ROYALTY_CO_AUTHORS],
+    pub licenses_sold: u64,
+    pub total_revenue: u64,
+}
+
+impl DreamLicenseTerms {
+    pub fn co_authors(&self) -> &[RoyaltyShare] {
+        &self.co_authors[..self.co_author_count as usize]
+    }
+
+    /// Each co-author's cut of `amount`, in `co_authors()` order, and the
+    /// dreamer's remainder.
+    pub fn split(&self, amount: u64) -> (Vec<u64>, u64) {
+        let shares: Vec<u64> = self
+            .co_authors()
+            .iter()
+            .map(|co_author| amount * u64::from(co_author.share_bps) / 10_000)
+            .collect();
+        let dreamer_share = amount - shares.iter().sum::<u64>();
+        (shares, dreamer_share)
+    }
+}
+
+#[account]
+#[derive(Debug)]
+pub struct DreamLicense {
+    pub dream_record: Pubkey,
+    pub licensee: Pubkey,
+    pub license_type: DreamLicenseType,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub total_paid: u64,
+}
+
+/// A translation or commentary on a dream, stored off-chain at `cid`. One per
+/// annotator and language for each dream.
+#[account]
+#[derive(Debug)]
+pub struct DreamAnnotation {
+    pub dream_record: Pubkey,
+    pub annotator: Pubkey,
+    /// ISO 639-1 code, lowercase ASCII (e.g. `*b"es"`).
+    pub language: [u8; 2],
+    pub cid: String,
+    pub created_at: i64,
+    pub approved: bool,
+    /// LUCID paid out of a `TranslationBounty` on approval.
+    pub bounty_paid: u64,
+}
+
+impl DreamAnnotation {
+    pub const SPACE: usize = 32 + 32 + 2 + (4 + DreamInterface::MAX_IPFS_HASH_LEN) + 8 + 1 + 8;
+
+    pub fn is_valid_language(language: &[u8; 2]) -> bool {
+        language.iter().all(u8::is_ascii_lowercase)
+    }
+}
+
+/// LUCID escrowed for the first approved translation of a dream into
+/// `language`. The tokens sit in the bounty's vault.
+#[account]
+#[derive(Debug)]
+pub struct TranslationBounty {
+    pub dream_record: Pubkey,
+    pub sponsor: Pubkey,
+    pub language: [u8; 2],
+    pub amount: u64,
+    /// Annotation that claimed the bounty; default until then.
+    pub paid_to: Pubkey,
+}
+
+/// An analyst the dreamer named, when recording a dream, to interpret it.
+/// `record_dream` holds back the interpreter's share of the reward. Once they
+/// attach an analysis, stored off-chain at `analysis_cid`, `validate_dream`
+/// pays them that share and the same share of any onboarding bonus.
+#[account]
+#[derive(Debug)]
+pub struct DreamInterpretation {
+    pub dream_record: Pubkey,
+    pub interpreter: Pubkey,
+    /// Empty until the interpreter attaches the analysis.
+    pub analysis_cid: String,
+    pub assigned_at: i64,
+    pub attached_at: i64,
+    /// DREAM paid to the interpreter at validation.
+    pub reward_paid: u64,
+}
+
+impl DreamInterpretation {
+    pub const SPACE: usize = 32 + 32 + (4 + DreamInterface::MAX_IPFS_HASH_LEN) + 8 + 8 + 8;
+
+    /// The interpreter's `INTERPRETER_SHARE_BPS` of `amount`, the dream's
+    /// reward or its onboarding bonus.
+    pub fn interpreter_share(amount: u64) -> u64 {
+        (u128::from(amount) * u128::from(INTERPRETER_SHARE_BPS) / 10_000) as u64
+    }
+}
+
+/// A question with up to `MAX_MARKET_OUTCOMES` outcomes, staked in DREAM.
+/// Only the question's hash is stored; the text lives off-chain. The stakes
+/// sit in the market's vault, net of the treasury fee.
+#[account]
+pub struct PredictionMarket {
+    pub creator: Pubkey,
+    pub market_id: u64,
+    pub question_hash: [u8; 32],
+    pub outcome_count: u8,
+    pub closes_at: i64,
+    pub resolved: bool,
+    pub winning_outcome: u8,
+    pub outcome_pools: [u64; MAX_MARKET_OUTCOMES],
+    pub fees_collected: u64,
+}
+
+impl PredictionMarket {
+    pub fn total_pool(&self) -> u64 {
+        self.outcome_pools.iter().sum()
+    }
+
+    /// What a resolved market owes a position: its pro-rata share of the whole
+    /// pool, or a refund of every stake if nobody backed the winning outcome.
+    pub fn payout(&self, position: &MarketPosition) -> u64 {
+        let winning_pool = self.outcome_pools[self.winning_outcome as usize];
+        if winning_pool == 0 {
+            return position.stakes.iter().sum();
+        }
+        let stake = position.stakes[self.winning_outcome as usize];
+        (stake as u128 * self.total_pool() as u128 / winning_pool as u128) as u64
+    }
+}
+
+#[account]
+pub struct MarketPosition {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    /// Net DREAM staked on each outcome, after the fee.
+    pub stakes: [u64; MAX_MARKET_OUTCOMES],
+    pub claimed: bool,
+}
+
+/// A large DREAM payout vesting linearly from `start_at` to `end_at`.
+/// `source` is the account the payout came from, e.g. a prediction market;
+/// the tokens sit in the shared `reward_stream_vault`.
+#[account]
+pub struct RewardStream {
+    pub beneficiary: Pubkey,
+    pub source: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+    pub start_at: i64,
+    pub end_at: i64,
+}
+
+impl RewardStream {
+    pub fn vested(&self, now: i64) -> u64 {
+        if now >= self.end_at {
+            return self.total;
+        }
+        let elapsed = (now - self.start_at).max(0) as u128;
+        (self.total as u128 * elapsed / (self.end_at - self.start_at) as u128) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuelStatus {
+    /// Waiting for the opponent to match the stake.
+    #[default]
+    Open,
+    Active,
+    Settled,
+    Cancelled,
+}
+
+/// Two dreamers staking the same amount of LUCID on whose next dream scores
+/// higher. Each submits one `DreamRecord` recorded after the duel started.
+#[account]
+pub struct DreamDuel {
+    pub challenger: Pubkey,
+    pub opponent: Pubkey,
+    pub duel_id: u64,
+    /// LUCID staked by each side; the pot is twice this.
+    pub stake: u64,
+    pub status: DuelStatus,
+    pub started_at: i64,
+    pub submission_deadline: i64,
+    /// Submitted `DreamRecord` addresses; the default key until submitted.
+    pub challenger_dream: Pubkey,
+    pub opponent_dream: Pubkey,
+    pub challenger_score: u8,
+    pub opponent_score: u8,
+    /// Default key for a tie or a refund.
+    pub winner: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GrantStatus {
+    /// Milestones are still being paid out.
+    #[default]
+    Active,
+    Completed,
+    ClawedBack,
+}
+
+/// Treasury LUCID escrowed for an ecosystem builder and released milestone
+/// by milestone. Seeds `[b"grant", recipient, grant_id]`; the escrow is the
+/// treasury-owned token account at `[b"grant_vault", grant]`.
+#[account]
+#[derive(Debug)]
+pub struct Grant {
+    pub recipient: Pubkey,
+    pub grant_id: u64,
+    /// LUCID paid per milestone, in order; only the first `milestone_count`
+    /// are used.
+    pub milestone_amounts: [u64; MAX_GRANT_MILESTONES],
+    pub milestone_count: u8,
+    pub milestones_paid: u8,
+    pub total_amount: u64,
+    pub released: u64,
+    pub status: GrantStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Dreams a dreamer recorded in `season`; reset by the first dream of a new season.
+#[account]
+pub struct SeasonScore {
+    pub dreamer: Pubkey,
+    pub season: u32,
+    pub dream_count: u64,
+}
+
+#[zero_copy]
+#[derive(Debug)]
+pub struct LeaderboardEntry {
+    pub dreamer: Pubkey,
+    pub dream_count: u64,
+}
+
+/// Top dreamers of the season by dreams recorded, highest first.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct Leaderboard {
+    pub season: u32,
+    pub len: u32,
+    pub season_started_at: i64,
+    pub entries: [LeaderboardEntry; LEADERBOARD_SIZE],
+}
+
+impl Leaderboard {
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries[..self.len as usize]
+    }
+
+    pub fn reset(&mut self, season: u32, started_at: i64) {
+        self.season = season;
+        self.len = 0;
+        self.season_started_at = started_at;
+    }
+
+    /// Raise `dreamer` to `dream_count` and move it up past lower entries.
+    /// Returns false if the count is stale or too low to make the board.
+    pub fn update(&mut self, dreamer: Pubkey, dream_count: u64) -> bool {
+        let len = self.len as usize;
+        let mut i = match self.entries[..len].iter().position(|e| e.dreamer == dreamer) {
+            Some(i) if self.entries[i].dream_count >= dream_count => return false,
+            Some(i) => i,
+            None if len < LEADERBOARD_SIZE => {
+                self.len += 1;
+                len
+            }
+            None if dream_count > self.entries[len - 1].dream_count => len - 1,
+            None => return false,
+        };
+        self.entries[i] = LeaderboardEntry { dreamer, dream_count };
+        while i > 0 && self.entries[i - 1].dream_count < dream_count {
+            self.entries.swap(i - 1, i);
+            i -= 1;
+        }
+        true
+    }
+}
+
+/// Treasury totals at the end of a day, as `roll_stats` found them.
+#[zero_copy]
+#[derive(Debug)]
+pub struct StatsSnapshot {
+    pub dreams_recorded: u64,
+    pub rewards_distributed: u64,
+    /// SMIND in the compressed stake vault.
+    pub compressed_staked: u64,
+}
+
+/// The last STATS_RING_SIZE daily snapshots, oldest overwritten first, so
+/// dashboards and other programs can chart trends without an indexer.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct StatsRing {
+    /// `EpochStats` day of the newest snapshot.
+    pub last_day: u32,
+    /// Snapshots written so far, capped at STATS_RING_SIZE.
+    pub len: u32,
+    /// Slot the next snapshot is written to.
+    pub head: u64,
+    pub snapshots: [StatsSnapshot; STATS_RING_SIZE],
+}
+
+impl StatsRing {
+    /// Record `snapshot` for `day`. Days the crank missed since the last roll
+    /// get the same snapshot, so the ring stays one slot per day. Returns
+    /// false if `day` already has one.
+    pub fn roll(&mut self, day: u32, snapshot: StatsSnapshot) -> bool {
+        if self.len > 0 && day <= self.last_day {
+            return false;
+        }
+        let days = if self.len == 0 {
+            1
+        } else {
+            (day - self.last_day).min(STATS_RING_SIZE as u32)
+        };
+        for _ in 0..days {
+            self.snapshots[self.head as usize] = snapshot;
+            self.head = (self.head + 1) % STATS_RING_SIZE as u64;
+        }
+        self.len = (self.len + days).min(STATS_RING_SIZE as u32);
+        self.last_day = day;
+        true
+    }
+
+    /// Snapshot from `days_ago` days before `last_day`, if the ring goes back
+    /// that far.
+    pub fn get(&self, days_ago: u32) -> Option<&StatsSnapshot> {
+        if days_ago >= self.len {
+            return None;
+        }
+        let newest = self.head as usize + STATS_RING_SIZE - 1;
+        Some(&self.snapshots[(newest - days_ago as usize) % STATS_RING_SIZE])
+    }
+}
+
+#[zero_copy]
+#[derive(Debug)]
+pub struct ValidationQueueEntry {
+    pub dream_record: Pubkey,
+    /// The dreamer's staked amount when the dream was recorded.
+    pub stake: u64,
+    pub enqueued_at: i64,
+}
+
+impl ValidationQueueEntry {
+    /// Seconds waited, plus a head start for every whole staked token.
+    pub fn priority(&self, now: i64) -> u64 {
+        (now - self.enqueued_at).max(0) as u64
+            + self.stake / TOKEN_UNIT * VALIDATION_PRIORITY_SECS_PER_STAKED_TOKEN
+    }
+}
+
+/// Recorded dreams waiting for validation, in a fixed-size ring buffer.
+/// Entries are added at the tail and the highest-priority one is popped.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct ValidationQueue {
+    pub head: u32,
+    pub len: u32,
+    pub total_enqueued: u64,
+    pub entries: [ValidationQueueEntry; VALIDATION_QUEUE_SIZE],
+}
+
+impl ValidationQueue {
+    /// Live entries in ring order (oldest first until entries are popped).
+    pub fn iter(&self) -> impl Iterator<Item = &ValidationQueueEntry> {
+        let head = self.head as usize;
+        (0..self.len as usize).map(move |i| &self.entries[(head + i) % VALIDATION_QUEUE_SIZE])
+    }
+
+    /// Returns false if the queue is full.
+    pub fn push(&mut self, entry: ValidationQueueEntry) -> bool {
+        if self.len as usize == VALIDATION_QUEUE_SIZE {
+            return false;
+        }
+        let tail = (self.head + self.len) as usize % VALIDATION_QUEUE_SIZE;
+        self.entries[tail] = entry;
+        self.len += 1;
+        self.total_enqueued += 1;
+        true
+    }
+
+    /// Remove the highest-priority entry; ties go to the older dream. The
+    /// head entry moves into the freed slot so the live entries stay contiguous.
+    pub fn pop(&mut self, now: i64) -> Option<ValidationQueueEntry> {
+        let head = self.head as usize;
+        let slot = (0..self.len as usize)
+            .map(|i| (head + i) % VALIDATION_QUEUE_SIZE)
+            .max_by_key(|&slot| {
+                let entry = &self.entries[slot];
+                (entry.priority(now), std::cmp::Reverse(entry.enqueued_at))
+            })?;
+        let entry = self.entries[slot];
+        self.entries[slot] = self.entries[head];
+        self.head = ((head + 1) % VALIDATION_QUEUE_SIZE) as u32;
+        self.len -= 1;
+        Some(entry)
+    }
+}
+
+/// Running totals for one UTC day, so dashboards don't have to replay events.
+#[account]
+pub struct EpochStats {
+    /// Days since the Unix epoch.
+    pub day: u32,
+    pub dream_count: u64,
+    pub unique_dreamers: u64,
+    pub rewards_minted: u64,
+}
+
+/// Merkle root over the dream records of one finished `EpochStats` day, built
+/// with [`merkle::dream_leaf`] in dream id order.
+#[account]
+pub struct EpochCommitment {
+    pub day: u32,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u64,
+    pub committed_by: Pubkey,
+    pub committed_at: i64,
+}
+
+impl EpochStats {
+    pub fn day_of(unix_timestamp: i64) -> u32 {
+        (unix_timestamp / EPOCH_STATS_PERIOD_SECS) as u32
+    }
+}
+
+#[account]
+pub struct DreamInterface {
+    pub dreamer: Pubkey,
+    pub index: u64,
+    pub ipfs_hash: String,
+    pub timestamp: i64,
+    pub access_level: u8,
+}
+
+impl DreamInterface {
+    pub const MAX_IPFS_HASH_LEN: usize = 64;
+    pub const SPACE: usize = 32 + 8 + (4 + Self::MAX_IPFS_HASH_LEN) + 8 + 1;
+}
+
+/// A dream sealed until `unlock_at`. Only the hash is stored at first; the
+/// CID is attached by `reveal_dream` and must hash to `content_hash`.
+#[account]
+pub struct DreamCapsule {
+    pub dreamer: Pubkey,
+    pub capsule_id: u64,
+    pub content_hash: [u8; 32],
+    pub sealed_at: i64,
+    pub unlock_at: i64,
+    /// 0 until revealed.
+    pub revealed_at: i64,
+    pub cid: String,
+}
+
+impl DreamCapsule {
+    pub const SPACE: usize = 32 + 8 + 32 + 8 + 8 + 8 + (4 + DreamInterface::MAX_IPFS_HASH_LEN);
+
+    /// Keccak-256 of the CID, which the capsule commits to when sealed.
+    pub fn commitment(cid: &str) -> [u8; 32] {
+        keccak::hash(cid.as_bytes()).to_bytes()
+    }
+}
+
+#[account]
+pub struct LucidStake {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub access_level: u8,
+}
+
+/// Root of every compressed SMIND stake, so small stakers don't each pay rent
+/// for a stake account. Leaves live off-chain; see `stake_tree`.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct CompressedStakeTree {
+    pub root: [u8; 32],
+    pub next_index: u64,
+    /// SMIND in all live stakes; the aggregate governance weight.
+    pub total_staked: u64,
+    pub active_stakes: u64,
+    /// Latest left node at each level, which the next append hashes against.
+    pub filled_subtrees: [[u8; 32]; COMPRESSED_STAKE_TREE_DEPTH],
+}
+
+impl CompressedStakeTree {
+    pub const CAPACITY: u64 = 1 << COMPRESSED_STAKE_TREE_DEPTH;
+
+    /// Returns the new leaf's index, or `None` if the tree is full.
+    pub fn append(&mut self, leaf: [u8; 32]) -> Option<u64> {
+        let index = self.next_index;
+        if index >= Self::CAPACITY {
+            return None;
+        }
+        self.root = stake_tree::append(&mut self.filled_subtrees, index, leaf);
+        self.next_index += 1;
+        Some(index)
+    }
+
+    /// Swap `leaf` at `index` for `new_leaf`. Returns false if the proof
+    /// doesn't lead from `leaf` to the current root.
+    pub fn replace(
+        &mut self,
+        index: u64,
+        leaf: &[u8; 32],
+        new_leaf: [u8; 32],
+        proof: &[[u8; 32]],
+    ) -> bool {
+        if proof.len() != COMPRESSED_STAKE_TREE_DEPTH
+            || index >= self.next_index
+            || stake_tree::root_from_proof(leaf, index, proof) != self.root
+        {
+            return false;
+        }
+        let mut node = new_leaf;
+        for (level, sibling) in proof.iter().enumerate() {
+            let position = index >> level;
+            // Keep the frontier in step, since later appends hash against it
+            if position == (self.next_index >> level) & !1 {
+                self.filled_subtrees[level] = node;
+            }
+            node = if position & 1 == 0 {
+                stake_tree::hash_pair(&node, sibling)
+            } else {
+                stake_tree::hash_pair(sibling, &node)
+            };
+        }
+        self.root = node;
+        true
+    }
+}
+
+/// Root of every archived dream record. Leaves are the hashes of the closed
+/// record accounts; `DreamArchivedEvent` carries the data behind each one.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct DreamArchiveTree {
+    pub root: [u8; 32],
+    pub next_index: u64,
+    /// Latest left node at each level, which the next append hashes against.
+    pub filled_subtrees: [[u8; 32]; COMPRESSED_STAKE_TREE_DEPTH],
+}
+
+impl DreamArchiveTree {
+    pub const CAPACITY: u64 = CompressedStakeTree::CAPACITY;
+
+    /// Returns the new leaf's index, or `None` if the tree is full.
+    pub fn append(&mut self, leaf: [u8; 32]) -> Option<u64> {
+        let index = self.next_index;
+        if index >= Self::CAPACITY {
+            return None;
+        }
+        self.root = stake_tree::append(&mut self.filled_subtrees, index, leaf);
+        self.next_index += 1;
+        Some(index)
+    }
+}
+
+/// What remains of an archived `DreamRecord`: where its hash sits in the
+/// `DreamArchiveTree`. Seeds `[b"archived_dream", dream_record]`.
+#[account]
+#[derive(Debug)]
+pub struct ArchivedDream {
+    pub dreamer: Pubkey,
+    pub dream_id: u64,
+    pub leaf_index: u64,
+    pub leaf: [u8; 32],
+    pub archived_at: i64,
+}
+
+/// Permissionless instructions that earn a bounty from the `CrankPool`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CrankKind {
+    UpdateLeaderboard,
+    ExpireCoauthorShares,
+}
+
+/// SOL bounties for permissionless cranks, paid from this account's own
+/// lamports above rent. Each kind pays at most once per cooldown, however
+/// many callers race for it.
+#[account]
+#[derive(Debug)]
+pub struct CrankPool {
+    pub bounty_lamports: u64,
+    pub cooldown_secs: i64,
+    pub last_paid_at: [i64; CRANK_KIND_COUNT],
+    pub total_paid: u64,
+}
+
+impl CrankPool {
+    pub fn ready(&self, kind: CrankKind, now: i64) -> bool {
+        now >= self.last_paid_at[kind as usize] + self.cooldown_secs
+    }
+}
+
+/// SOL protocol fees, held as this account's own lamports above rent until
+/// `sweep_fees`. The OneirobotNFT program reads its mint fee here as well
+/// and pays it into this account.
+#[account]
+#[derive(Debug)]
+pub struct FeeVault {
+    pub record_dream_fee: u64,
+    pub mint_oneirobot_fee: u64,
+    pub total_swept: u64,
+}
+
+/// SOL for new dreamers' first `record_dream`, so a wallet can dream before it
+/// holds any. Anyone can fund it with lamports above its rent reserve.
+#[account]
+#[derive(Debug)]
+pub struct SponsorPool {
+    /// Set by the authority; claims fail while it is off.
+    pub enabled: bool,
+    pub dreamers_sponsored: u64,
+    pub total_sponsored: u64,
+}
+
+/// Marks a wallet as sponsored; its existence is the one-time limit.
+#[account]
+pub struct Sponsorship {
+    pub dreamer: Pubkey,
+    pub lamports: u64,
+    pub claimed_at: i64,
+}
+
+/// Emissions into the reward pool vault, which pays DREAM rewards. Periods
+/// are aligned to multiples of `REWARD_POOL_PERIOD_SECS`.
+#[account]
+pub struct RewardPool {
+    pub period_start: i64,
+    /// Minted by `top_up_pool` since `period_start`.
+    pub period_minted: u64,
+    pub total_minted: u64,
+}
+
+impl RewardPool {
+    pub fn period_of(unix_timestamp: i64) -> i64 {
+        unix_timestamp - unix_timestamp.rem_euclid(REWARD_POOL_PERIOD_SECS)
+    }
+}
+
+/// A wallet's use of the devnet faucet. Seeds `[b"faucet", wallet]`.
+#[account]
+#[derive(Debug)]
+pub struct FaucetClaim {
+    pub wallet: Pubkey,
+    pub claims: u32,
+    pub last_claimed_at: i64,
+}
+
+impl FaucetClaim {
+    pub fn next_claim_at(&self) -> i64 {
+        if self.claims == 0 {
+            i64::MIN
+        } else {
+            self.last_claimed_at + FAUCET_COOLDOWN_SECS
+        }
+    }
+}
+
+/// Backstop for the crank pool and the reward pool. Its SOL is the
+/// account's own lamports above rent, paid in from its slice of swept fees;
+/// its DREAM sits in the `["insurance_vault"]` token account, paid in from
+/// its slice of market fees.
+#[account]
+#[derive(Debug)]
+pub struct InsuranceFund {
+    /// Slice of swept SOL fees and of market fees paid in, in bps.
+    pub fee_share_bps: u16,
+    pub lamports_received: u64,
+    pub dream_received: u64,
+    pub lamports_covered: u64,
+    pub dream_covered: u64,
+}
+
+/// Where `cover_shortfall` sends insurance funds.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShortfallTarget {
+    /// SOL for the crank pool, which pays keepers back for their cranks.
+    CrankPool,
+    /// DREAM for the reward pool vault.
+    RewardPool,
+}
+
+/// A pinning service the authority trusts to attest that dream content is
+/// retrievable. Revoking approval stops new attestations; existing
+/// confirmations stand.
+/// What the treasury's vaults owe, kept current by the instructions that
+/// create and settle it, and the outcome of the last `reconcile_treasury`.
+/// `sweep_fees` needs `healthy`.
+#[account]
+#[derive(Debug)]
+pub struct TreasuryHealth {
+    /// DREAM held back in the reward pool for co-dreamers who haven't
+    /// confirmed or expired, and for interpreters of dreams not yet validated.
+    /// Shares held back before this account existed aren't counted.
+    pub pending_coauthor_shares: u64,
+    /// DREAM in reward streams not yet claimed.
+    pub pending_streamed: u64,
+    pub healthy: bool,
+    pub checked_at: i64,
+}
+
+#[account]
+pub struct PinningOracle {
+    pub oracle: Pubkey,
+    pub approved: bool,
+    pub updated_at: i64,
+    pub attestations: u64,
+}
+
+/// How many approved oracles must sign each pin confirmation and
+/// classification. Seeds `[b"oracle_quorum"]`; until it exists, one will do.
+#[account]
+#[derive(Debug)]
+pub struct OracleQuorum {
+    pub threshold: u8,
+    pub updated_at: i64,
+}
+
+/// An oracle's attestation that a dream's CID resolves to content matching its
+/// `content_hash`. Seeded by the record; `validate_dream` requires it.
+#[account]
+#[derive(Debug)]
+pub struct PinConfirmation {
+    pub dream_record: Pubkey,
+    pub oracle: Pubkey,
+    pub confirmed_at: i64,
+}
+
+/// An approved oracle's classification of a dream's content, so clients can
+/// filter feeds and region-gate content. Seeded by the record; any approved
+/// oracle may replace it with a newer `revision`.
+#[account]
+#[derive(Debug)]
+pub struct DreamClassification {
+    pub dream_record: Pubkey,
+    /// Oracle that signed the current classification.
+    pub oracle: Pubkey,
+    /// ISO 639-1 code in lowercase ASCII, e.g. `*b"en"`.
+    pub language: [u8; 2],
+    pub nsfw: bool,
+    /// Bit i set for topic i of the oracles' published taxonomy.
+    pub topics: u64,
+    /// Number of classifications so far; signed over, so an old attestation
+    /// can't be replayed over a newer one.
+    pub revision: u32,
+    pub classified_at: i64,
+}
+
+impl DreamClassification {
+    pub fn is_valid_language(language: &[u8; 2]) -> bool {
+        language.iter().all(u8::is_ascii_lowercase)
+    }
+}
+
+/// An ephemeral key a wallet lets sign some actions on its behalf until
+/// `expires_at`, so apps don't prompt the wallet for each one. Seeded by the
+/// wallet and the key.
+#[account]
+#[derive(Debug)]
+pub struct SessionKey {
+    pub authority: Pubkey,
+    pub signer: Pubkey,
+    /// `SESSION_SCOPE_*` bits.
+    pub scope: u8,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl SessionKey {
+    pub fn allows(&self, scope: u8, now: i64) -> bool {
+        self.scope & scope == scope && now < self.expires_at
+    }
+}
+
+/// Next nonce a dreamer must sign for `record_dream_delegated`, so a relayer
+/// can't replay a signed dream.
+#[account]
+#[derive(Debug)]
+pub struct RelayNonce {
+    pub dreamer: Pubkey,
+    pub nonce: u64,
+}
+
+/// Client-generated key a `record_dream` was sent under, so a client that
+/// times out and resends can't record the dream twice. Seeds
+/// `[b"idempotency", dreamer, key]`; anyone can close it for the payer once
+/// IDEMPOTENCY_NONCE_TTL_SECS have passed.
+#[account]
+#[derive(Debug)]
+pub struct IdempotencyNonce {
+    pub dreamer: Pubkey,
+    pub key: u64,
+    /// Paid the rent, and gets it back on close.
+    pub payer: Pubkey,
+    pub dream_record: Pubkey,
+    pub created_at: i64,
+}
+
+/// Why the authority flagged a dream.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DreamFlagReason {
+    Spam,
+    Plagiarism,
+    /// A dispute over the dream was decided against the dreamer.
+    DisputeLost,
+}
+
+/// A flag on one dream. Seeded by the record, so a dream costs its dreamer
+/// reputation at most once.
+#[account]
+#[derive(Debug)]
+pub struct DreamFlag {
+    pub dream_record: Pubkey,
+    pub reason: DreamFlagReason,
+    pub flagged_by: Pubkey,
+    pub flagged_at: i64,
+}
+
+/// A validator's verdict on one dream. Seeded by the record, so each dream
+/// is validated at most once.
+#[account]
+#[derive(Debug)]
+pub struct DreamValidation {
+    pub dream_record: Pubkey,
+    pub validator: Pubkey,
+    /// 1 to MAX_VALIDATION_SCORE.
+    pub score: u8,
+    pub validated_at: i64,
+}
+
+/// Governance switch limiting validator registration to holders of an
+/// Oneirobot with at least `min_dream_level`.
+#[account]
+#[derive(Debug)]
+pub struct ValidatorGate {
+    pub enabled: bool,
+    pub min_dream_level: u8,
+    /// OneirobotNFT program whose `NftAttributes` are trusted.
+    pub nft_program: Pubkey,
+    pub updated_at: i64,
+}
+
+impl ValidatorGate {
+    /// Whether a validator holding an Oneirobot of `dream_level` (`None`
+    /// without one) may register, or stay registered.
+    pub fn admits(&self, dream_level: Option<u8>) -> bool {
+        !self.enabled || dream_level.is_some_and(|level| level >= self.min_dream_level)
+    }
+}
+
+/// A registered validator; closed by a successful `challenge_validator`.
+#[account]
+#[derive(Debug)]
+pub struct ValidatorRegistration {
+    pub validator: Pubkey,
+    /// Oneirobot shown at registration; the default key if none was.
+    pub oneirobot_mint: Pubkey,
+    pub registered_at: i64,
+}
+
+/// DREAM for registered validators. Its slice of market fees is paid into
+/// the `["validator_reward_vault"]` token account, and every validation
+/// credited to a validator adds `reward_per_validation` to their
+/// `ValidatorRewards`, as long as the vault covers all unclaimed credits.
+#[account]
+#[derive(Debug)]
+pub struct ValidatorRewardPool {
+    /// Slice of market fees paid in, in bps.
+    pub fee_share_bps: u16,
+    pub reward_per_validation: u64,
+    pub dream_received: u64,
+    pub total_credited: u64,
+    pub total_claimed: u64,
+    /// Most DREAM one validator can claim per `EpochStats` day; 0 is uncapped.
+    pub epoch_claim_cap: u64,
+}
+
+impl ValidatorRewardPool {
+    /// Credited DREAM not yet claimed, which the vault must hold.
+    pub fn unclaimed(&self) -> u64 {
+        self.total_credited - self.total_claimed
+    }
+}
+
+/// One validator's claimable DREAM. Seeds `[b"validator_rewards", validator]`;
+/// kept when a registration is challenged, so earned rewards stay claimable.
+#[account]
+#[derive(Debug)]
+pub struct ValidatorRewards {
+    pub validator: Pubkey,
+    pub claimable: u64,
+    pub total_earned: u64,
+    /// `EpochStats` day of the last claim, and DREAM claimed in it.
+    pub epoch: u32,
+    pub claimed_in_epoch: u64,
+}
+
+impl ValidatorRewards {
+    /// What can be claimed in `epoch` under `epoch_claim_cap`, starting the
+    /// epoch's tally afresh if it's a new one.
+    pub fn claimable_in(&mut self, epoch: u32, epoch_claim_cap: u64) -> u64 {
+        if self.epoch != epoch {
+            self.epoch = epoch;
+            self.claimed_in_epoch = 0;
+        }
+        if epoch_claim_cap == 0 {
+            return self.claimable;
+        }
+        self.claimable.min(epoch_claim_cap.saturating_sub(self.claimed_in_epoch))
+    }
+}
+
+/// One validator's track record, for governance to judge them by. Seeds
+/// `[b"validator_stats", validator]`; like `ValidatorRewards`, it outlives
+/// the registration.
+#[account]
+#[derive(Debug)]
+pub struct ValidatorStats {
+    pub validator: Pubkey,
+    pub validations: u64,
+    pub disputes: u32,
+    /// Disputes whose consensus score was more than
+    /// `VALIDATION_DISPUTE_TOLERANCE` away from the validator's.
+    pub disputes_lost: u32,
+    /// Sum of the distances from consensus over all disputes.
+    pub total_divergence: u64,
+    pub last_validated_at: i64,
+}
+
+impl ValidatorStats {
+    /// Mean distance from consensus over disputed verdicts; 0 before any.
+    pub fn average_divergence(&self) -> u64 {
+        self.total_divergence.checked_div(u64::from(self.disputes)).unwrap_or(0)
+    }
+}
+
+/// The settled dispute of one verdict. Seeded by the record, so each
+/// verdict is disputed at most once.
+#[account]
+#[derive(Debug)]
+pub struct ValidationDispute {
+    pub dream_record: Pubkey,
+    pub validator: Pubkey,
+    pub score: u8,
+    pub consensus_score: u8,
+    pub lost: bool,
+    pub resolved_at: i64,
+}
+
+/// A pinning oracle's score for a dream, backed by OPTIMISTIC_SCORE_BOND.
+/// Becomes the dream's `DreamValidation` once the challenge window passes
+/// unless a fraud proof voids it first. Seeds `[b"optimistic_score", record]`.
+#[account]
+#[derive(Debug)]
+pub struct OptimisticScore {
+    pub dream_record: Pubkey,
+    pub oracle: Pubkey,
+    pub score: u8,
+    /// Set by a successful challenge, which takes the bond.
+    pub voided: bool,
+    pub bond: u64,
+    pub posted_at: i64,
+}
+
+impl OptimisticScore {
+    pub fn challenge_ends_at(&self) -> i64 {
+        self.posted_at + OPTIMISTIC_CHALLENGE_WINDOW_SECS
+    }
+}
+
+/// What a challenger shows to void an optimistic score.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FraudProof {
+    /// An earlier record, passed as `original_record`, has the same content.
+    DuplicateContent,
+    /// An approved oracle signed `cid::dead_cid_attestation_message` for the
+    /// dream, verified by the preceding ed25519 instruction.
+    DeadCid,
+}
+
+/// Where `splice_from_dreams` mints. Seeds `[b"splice_config"]`.
+#[account]
+#[derive(Debug)]
+pub struct SpliceConfig {
+    /// OneirobotNFT program the splicer PDA signs for.
+    pub nft_program: Pubkey,
+    pub updated_at: i64,
+}
+
+/// Marks a dream consumed by `splice_from_dreams`. Seeded by the record, so
+/// each dream is spliced at most once.
+#[account]
+#[derive(Debug)]
+pub struct SplicedDream {
+    pub dream_record: Pubkey,
+    pub oneirobot_mint: Pubkey,
+    pub spliced_at: i64,
+}
+
+/// Oneirobot certificate minted for a dream scored at least
+/// CERTIFICATE_MIN_SCORE. Seeded by the record, so each dream is certified
+/// once.
+#[account]
+#[derive(Debug)]
+pub struct DreamCertificate {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub oneirobot_mint: Pubkey,
+    pub score: u8,
+    pub certified_at: i64,
+}
+
+/// The Oneirobot a dreamer has equipped as their companion. Seeds
+/// `[b"companion", dreamer]`; equipping another replaces it.
+#[account]
+#[derive(Debug)]
+pub struct Companion {
+    pub dreamer: Pubkey,
+    pub mint: Pubkey,
+    pub equipped_at: i64,
+}
+
+/// The companion stamped on a dream when it was recorded. Seeds
+/// `[b"companion_stamp", dream_record]`; dreams recorded without a companion
+/// have none.
+#[account]
+#[derive(Debug)]
+pub struct CompanionStamp {
+    pub dream_record: Pubkey,
+    pub mint: Pubkey,
+}
+
+/// XP an Oneirobot has earned as a companion. Seeded by the mint, so it
+/// stays with the NFT when it changes hands.
+#[account]
+#[derive(Debug)]
+pub struct CompanionXp {
+    pub mint: Pubkey,
+    pub xp: u64,
+    pub dreams_recorded: u64,
+}
+
+/// Registered bridge contract on another Wormhole chain.
+#[account]
+pub struct ForeignEmitter {
+    pub chain: u16,
+    pub address: [u8; 32],
+}
+
+/// Marks an inbound Wormhole message as processed, keyed by emitter chain and sequence.
+#[account]
+pub struct BridgeReceipt {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+/// Cross-chain messaging adapter (Hyperlane, LayerZero, ...). The bridge's
+/// delivery authority for this program must sign `receive_message`.
+#[account]
+pub struct MessageAdapter {
+    pub delivery_authority: Pubkey,
+    pub messages_received: u64,
+}
+
+/// Sender on another chain whose messages `receive_message` accepts. `origin`
+/// is the messaging protocol's domain/endpoint ID for that chain.
+#[account]
+pub struct TrustedRemote {
+    pub origin: u32,
+    pub sender: [u8; 32],
+    /// Messages must carry a nonce at or above this.
+    pub next_nonce: u64,
+}
+
+/// Build metadata for the deployed program, written by the upgrade authority
+/// after each deploy so explorers and auditors can verify what's running.
+#[account]
+pub struct ProgramInfo {
+    pub version: String,
+    pub git_commit: String,
+    pub audit_uri: String,
+    pub updated_at: i64,
+}
+
+impl ProgramInfo {
+    pub const MAX_VERSION_LEN: usize = 32;
+    pub const MAX_GIT_COMMIT_LEN: usize = 40;
+    pub const MAX_AUDIT_URI_LEN: usize = 200;
+    pub const SPACE: usize = (4 + Self::MAX_VERSION_LEN)
+        + (4 + Self::MAX_GIT_COMMIT_LEN)
+        + (4 + Self::MAX_AUDIT_URI_LEN)
+        + 8;
+}
+
+/// Executable hash of the verifiable build that was deployed, written by the
+/// authority after each upgrade. It describes the running bytecode only while
+/// `deploy_slot` matches the program data account's slot; after a newer deploy
+/// it is stale until updated.
+#[account]
+pub struct BuildInfo {
+    /// `solana-verify get-executable-hash` of the reproducible build.
+    pub program_hash: [u8; 32],
+    pub git_commit: String,
+    pub deploy_slot: u64,
+    pub updated_at: i64,
+}
+
+impl BuildInfo {
+    pub const SPACE: usize = 32 + (4 + ProgramInfo::MAX_GIT_COMMIT_LEN) + 8 + 8;
+}
+
+/// On-chain record of the program's upgrade-authority handover to the
+/// governance PDA and, later, of the vote that made it immutable.
+#[account]
+pub struct UpgradeGovernance {
+    pub handed_over_by: Pubkey,
+    pub handed_over_at: i64,
+    /// Origin domain and nonce of the governance message that approved the
+    /// freeze; `approved_at` is 0 until then.
+    pub approval_origin: u32,
+    pub approval_nonce: u64,
+    pub approved_at: i64,
+    pub finalized_at: i64,
+}
+
+/// Guardians that can pause the program or cancel a queued freeze, but never
+/// move funds. Rotated by governance; seeds `[b"guardian_council"]`.
+#[account]
+#[derive(Debug)]
+pub struct GuardianCouncil {
+    pub guardians: [Pubkey; GUARDIAN_COUNCIL_SIZE],
+    pub rotated_at: i64,
+}
+
+impl GuardianCouncil {
+    /// Number of guardians among `signers`; duplicates count once.
+    pub fn approvals(&self, signers: &[Pubkey]) -> usize {
+        self.guardians
+            .iter()
+            .filter(|guardian| signers.contains(guardian))
+            .count()
+    }
+}
+
+/// Address lookup table of the program's static accounts, for v0 transactions.
+#[account]
+pub struct LookupTableConfig {
+    pub lookup_table: Pubkey,
+    pub recent_slot: u64,
+}
+
+// ===================== EVENTS =====================
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamExtrasUpdatedEvent {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub tag_count: u32,
+    pub annotation_count: u32,
+    pub record_len: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StringInternedEvent {
+    pub id: u64,
+    pub value: String,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionKeyCreatedEvent {
+    pub authority: Pubkey,
+    pub signer: Pubkey,
+    pub scope: u8,
+    pub expires_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionKeyRevokedEvent {
+    pub authority: Pubkey,
+    pub signer: Pubkey,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamRelayedEvent {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub relayer: Pubkey,
+    pub nonce: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakeMigratedEvent {
+    pub user: Pubkey,
+    pub legacy_stake: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConversionRatesSetEvent {
+    pub smind_rate_bps: u32,
+    pub lucid_rate_bps: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardClaimedEvent {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub token: RewardToken,
+    pub dream_amount: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsuranceFundedEvent {
+    pub lamports: u64,
+    pub dream: u64,
+    /// Fund balances afterwards: SOL above rent, and the DREAM vault.
+    pub lamports_balance: u64,
+    pub dream_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortfallCoveredEvent {
+    pub target: ShortfallTarget,
+    /// Lamports for the crank pool, DREAM base units for the reward pool.
+    pub amount: u64,
+    /// The target's balance afterwards.
+    pub target_balance: u64,
+    pub lamports_balance: u64,
+    pub dream_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreasuryHealthEvent {
+    pub healthy: bool,
+    pub reward_pool_balance: u64,
+    pub pending_coauthor_shares: u64,
+    pub reward_stream_balance: u64,
+    pub pending_streamed: u64,
+    /// Insurance fund SOL above rent and DREAM, against what it has received
+    /// less what it has covered.
+    pub insurance_lamports: u64,
+    pub insurance_lamports_expected: u64,
+    pub insurance_dream: u64,
+    pub insurance_dream_expected: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamFlaggedEvent {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub reason: DreamFlagReason,
+    /// The dreamer's reputation after the penalty.
+    pub reputation: i16,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardPoolToppedUpEvent {
+    pub amount: u64,
+    pub period_start: i64,
+    pub period_minted: u64,
+    pub total_minted: u64,
+    /// Vault balance after the top-up.
+    pub pool_balance: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamClassifiedEvent {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub oracle: Pubkey,
+    pub language: [u8; 2],
+    pub nsfw: bool,
+    pub topics: u64,
+    pub revision: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FaucetMintedEvent {
+    pub wallet: Pubkey,
+    pub claims: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterpreterAssignedEvent {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub interpreter: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamInterpretedEvent {
+    pub dream_record: Pubkey,
+    pub interpreter: Pubkey,
+    pub analysis_cid: String,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterpreterPaidEvent {
+    pub dream_record: Pubkey,
+    pub interpreter: Pubkey,
+    /// Out of the dreamer's onboarding bonus; the rest was held back from the
+    /// dream's reward.
+    pub from_bonus: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterpretationExpiredEvent {
+    pub dream_record: Pubkey,
+    pub interpreter: Pubkey,
+    /// The held-back share, returned to the dreamer.
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleQuorumSetEvent {
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PinningOracleChangedEvent {
+    pub oracle: Pubkey,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamPinnedEvent {
+    pub dream_record: Pubkey,
+    pub dream_id: u64,
+    pub dreamer: Pubkey,
+    pub oracle: Pubkey,
+    pub content_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamValidatedEvent {
+    pub dream_record: Pubkey,
+    pub dreamer: Pubkey,
+    pub validator: Pubkey,
+    pub score: u8,
+    /// The dreamer's reputation after this validation.
+    pub reputation: i16,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnboardingBonusPaidEvent {
+    pub dreamer: Pubkey,
+    pub dream_record: Pubkey,
+    /// The dreamer's validated dreams, counting this one.
+    pub validated_dreams: u32,
+    pub multiplier_bps: u16,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorGateSetEvent {
+    pub enabled: bool,
+    pub min_dream_level: u8,
+    pub nft_program: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpliceConfigSetEvent {
+    pub nft_program: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneirobotSplicedEvent {
+    pub dreamer: Pubkey,
+    pub oneirobot_mint: Pubkey,
+    pub dream_records: Vec<Pubkey>,
+    pub dream_level: u8,
+    pub lucid_power: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamCertifiedEvent {
+    pub dreamer: Pubkey,
+    pub dream_record: Pubkey,
+    pub oneirobot_mint: Pubkey,
+    pub score: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompanionEquippedEvent {
+    pub dreamer: Pubkey,
+    pub mint: Pubkey,
+    pub xp: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorRegisteredEvent {
+    pub validator: Pubkey,
+    pub oneirobot_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorRevokedEvent {
+    pub validator: Pubkey,
+    pub challenger: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptimisticScorePostedEvent {
+    pub dream_record: Pubkey,
+    pub oracle: Pubkey,
+    pub score: u8,
+    pub bond: u64,
+    pub challenge_ends_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptimisticScoreVoidedEvent {
+    pub dream_record: Pubkey,
+    pub oracle: Pubkey,
+    pub challenger: Pubkey,
+    pub proof: FraudProof,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationDisputeResolvedEvent {
+    pub dream_record: Pubkey,
+    pub validator: Pubkey,
+    pub score: u8,
+    pub consensus_score: u8,
+    pub lost: bool,
+    pub disputes_lost: u32,
+    pub average_divergence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorRewardCreditedEvent {
+    pub validator: Pubkey,
+    pub dream_record: Pubkey,
+    pub amount: u64,
+    pub claimable: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorRewardsClaimedEvent {
+    pub validator: Pubkey,
+    pub amount: u64,
+    pub total_earned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatorClaimCapReachedEvent {
+    pub validator: Pubkey,
+    pub epoch: u32,
+    pub epoch_claim_cap: u64,
+    /// Credits left for later epochs.
+    pub deferred: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeesSweptEvent {
+    pub recipient: Pubkey,
+    pub lamports: u64,
+    pub total_swept: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamAnnotatedEvent {
+    pub annotation: Pubkey,
+    pub dream_record: Pubkey,
+    pub annotator: Pubkey,
+    pub language: [u8; 2],
+    pub cid: String,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnotationApprovedEvent {
+    pub annotation: Pubkey,
+    pub annotator: Pubkey,
+    pub bounty_paid: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoauthorshipConfirmedEvent {
+    pub dream_record: Pubkey,
+    pub co_dreamer: Pubkey,
+    pub reward: u64,
+    /// Part of the share lost to decay.
+    pub expired: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoauthorSharesExpiredEvent {
+    pub dream_record: Pubkey,
+    pub forfeited: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamLicensedEvent {
+    pub dream_record: Pubkey,
+    pub licensee: Pubkey,
+    pub license_type: DreamLicenseType,
+    pub price: u64,
+    pub treasury_share: u64,
+    pub co_author_share: u64,
+    pub dreamer_share: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamerSponsoredEvent {
+    pub dreamer: Pubkey,
+    pub payer: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrankRewardPaidEvent {
+    pub cranker: Pubkey,
+    pub kind: CrankKind,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamArchivedEvent {
+    pub dreamer: Pubkey,
+    pub dream_id: u64,
+    pub dream_record: Pubkey,
+    pub leaf_index: u64,
+    pub leaf: [u8; 32],
+    /// The closed account's full data, which hashes to `leaf`.
+    pub record_data: Vec<u8>,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatsRolledEvent {
+    pub day: u32,
+    pub dreams_recorded: u64,
+    pub rewards_distributed: u64,
+    pub compressed_staked: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedStakedEvent {
+    pub staker: Pubkey,
+    pub leaf_index: u64,
+    pub amount: u64,
+    pub staked_at: i64,
+    pub total_staked: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedUnstakedEvent {
+    pub staker: Pubkey,
+    pub leaf_index: u64,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamDequeuedEvent {
+    pub dream_record: Pubkey,
+    pub validator: Pubkey,
+    pub stake: u64,
+    pub waited_secs: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardStreamCreatedEvent {
+    pub stream: Pubkey,
+    pub beneficiary: Pubkey,
+    pub source: Pubkey,
+    pub total: u64,
+    pub end_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardStreamClaimedEvent {
+    pub stream: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub claimed: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharityApprovalChangedEvent {
+    pub charity: Pubkey,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharityDonationEvent {
+    pub dreamer: Pubkey,
+    pub charity: Pubkey,
+    pub dream_id: u64,
+    pub amount: u64,
+    pub total_donated: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamSealedEvent {
+    pub capsule: Pubkey,
+    pub dreamer: Pubkey,
+    pub content_hash: [u8; 32],
+    pub unlock_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamRevealedEvent {
+    pub capsule: Pubkey,
+    pub dreamer: Pubkey,
+    pub cid: String,
+    pub sealed_at: i64,
+    pub revealed_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuelSettledEvent {
+    pub duel: Pubkey,
+    pub winner: Pubkey,
+    pub challenger_score: u8,
+    pub opponent_score: u8,
+    pub challenger_payout: u64,
+    pub opponent_payout: u64,
+    pub fee: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrantCreatedEvent {
+    pub grant: Pubkey,
+    pub recipient: Pubkey,
+    pub grant_id: u64,
+    pub total_amount: u64,
+    pub milestone_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrantMilestoneApprovedEvent {
+    pub grant: Pubkey,
+    pub recipient: Pubkey,
+    /// Zero-based index of the approved milestone.
+    pub milestone: u8,
+    pub amount: u64,
+    pub released: u64,
+    pub status: GrantStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrantClawedBackEvent {
+    pub grant: Pubkey,
+    pub recipient: Pubkey,
+    /// Unreleased LUCID returned to the treasury.
+    pub amount: u64,
+    pub released: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketCreatedEvent {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub market_id: u64,
+    pub question_hash: [u8; 32],
+    pub outcome_count: u8,
+    pub closes_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketOutcomeBoughtEvent {
+    pub market: Pubkey,
+    pub buyer: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketResolvedEvent {
+    pub market: Pubkey,
+    pub winning_outcome: u8,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketWinningsClaimedEvent {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResearchConsentChangedEvent {
+    pub dreamer: Pubkey,
+    pub consent: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResearchLicensePurchasedEvent {
+    pub licensee: Pubkey,
+    pub amount: u64,
+    pub dreamer_share: u64,
+    pub consenting_dreamers: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResearchRevenueClaimedEvent {
+    pub dreamer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpochRootCommittedEvent {
+    pub day: u32,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeasonStartedEvent {
+    pub season: u32,
+    pub started_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SleepSessionClosedEvent {
+    pub session: Pubkey,
+    pub dreamer: Pubkey,
+    pub session_id: u64,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub dream_count: u32,
+    pub rewards_earned: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamRecordedEvent {
+    pub id: u64,
+    pub dreamer: Pubkey,
+    pub content_hash: [u8; 32],
+    pub token_reward: u64,
+    pub mev_protected: bool,
+    pub timestamp: i64,
+    pub metadata: DreamMetadata,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LucidStakedEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub access_level: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TierCredentialUpdatedEvent {
+    pub user: Pubkey,
+    pub access_level: u8,
+    pub held: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MevProtectionUpdatedEvent {
+    pub authority: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegacyStorageMigratedEvent {
+    pub dreamer: Pubkey,
+    pub legacy_storage: Pubkey,
+    pub dream_count: u64,
+    pub tokens_earned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamerProfileMigratedEvent {
+    pub dreamer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamRecordMigratedEvent {
+    pub dream_record: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneirobotMintedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_id: u64,
+    pub quantum_core: String,
+    pub dream_level: u8,
+    pub lucid_power: u8,
+    pub mind_strength: u8,
+    pub metadata_uri: String,
+    pub timestamp: i64,
+    pub season: u32,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneirobotBoostedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub mind_boost: u8,
+    pub expires_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneirobotBoostEndedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolderSnapshotTakenEvent {
+    pub snapshot_id: u64,
+    pub merkle_root: [u8; 32],
+    pub holder_count: u64,
+    pub slot: u64,
+    pub reward_mint: Pubkey,
+    pub amount_per_nft: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolderAirdropClaimedEvent {
+    pub snapshot_id: u64,
+    pub nft_mint: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EditionsAllowedEvent {
+    pub mint: Pubkey,
+    pub max_prints: u64,
+    pub price_lamports: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneirobotEditionPrintedEvent {
+    pub master_mint: Pubkey,
+    pub edition_mint: Pubkey,
+    pub buyer: Pubkey,
+    pub edition: u64,
+    pub price_lamports: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneirobotNamedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub name: String,
+    pub lore_cid: String,
+    pub burned: u64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraitSeasonStartedEvent {
+    pub season: u32,
+    pub quantum_cores: Vec<String>,
+    pub started_at: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamBridgedOutEvent {
+    pub sender: Pubkey,
+    pub amount: u64,
+    pub recipient_chain: u16,
+    pub recipient: [u8; 32],
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamBridgedInEvent {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamMirroredEvent {
+    pub id: u64,
+    pub dreamer: Pubkey,
+    pub content_hash: [u8; 32],
+    pub origin_chain: u16,
+    pub origin_dream_id: u64,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemoteMessageReceivedEvent {
+    pub origin: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramInfoUpdatedEvent {
+    pub version: String,
+    pub git_commit: String,
+    pub audit_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildInfoUpdatedEvent {
+    pub program_hash: [u8; 32],
+    pub git_commit: String,
+    pub deploy_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpgradeAuthorityHandedOverEvent {
+    pub previous_authority: Pubkey,
+    pub upgrade_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramFinalizedEvent {
+    pub approval_origin: u32,
+    pub approval_nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GuardiansRotatedEvent {
+    pub guardians: [Pubkey; GUARDIAN_COUNCIL_SIZE],
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramPauseChangedEvent {
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinalizeApprovalCancelledEvent {
+    pub approval_origin: u32,
+    pub approval_nonce: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,61 @@
+//! Merkle trees over dream records, committed per day by `commit_epoch_root`,
+//! and over Oneirobot holders, committed by OneirobotNFT's `snapshot_holders`.
+//! Pairs are hashed in sorted order (keccak-256), so proofs carry no
+//! left/right flags; an odd node is carried up to the next level unchanged.
+
+use anchor_lang::{prelude::*, solana_program::keccak};
+
+/// Leaf for one dream record.
+pub fn dream_leaf(dream_id: u64, dreamer: &Pubkey, content_hash: &[u8; 32]) -> [u8; 32] {
+    keccak::hashv(&[&dream_id.to_le_bytes(), dreamer.as_ref(), content_hash]).to_bytes()
+}
+
+/// Leaf for one Oneirobot and the wallet holding it at snapshot time.
+pub fn holder_leaf(nft_mint: &Pubkey, holder: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[nft_mint.as_ref(), holder.as_ref()]).to_bytes()
+}
+
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[lo, hi]).to_bytes()
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => hash_pair(a, b),
+            _ => pair[0],
+        })
+        .collect()
+}
+
+/// Root of `leaves`, or all zeroes for an empty tree.
+pub fn root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    if level.is_empty() {
+        return [0; 32];
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Sibling hashes from `leaves[index]` up to the root.
+pub fn proof(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut level = leaves.to_vec();
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    proof
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: &[u8; 32]) -> bool {
+    proof.iter().fold(*leaf, |node, sibling| hash_pair(&node, sibling)) == *root
+}
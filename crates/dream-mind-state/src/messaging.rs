@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// Parameter update carried by a cross-chain governance message. The message
+/// body is `borsh(GovernanceAction)`; the adapter on the sending chain must
+/// encode it the same way.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum GovernanceAction {
+    SetMevProtection { enabled: bool },
+    /// Approve revoking the program's upgrade authority. Needs the
+    /// `UpgradeGovernance` account; `finalize_immutable` opens after the timelock.
+    ApproveFinalizeImmutable,
+}
+
+impl GovernanceAction {
+    /// Returns `None` for unknown actions or trailing bytes.
+    pub fn decode(message: &[u8]) -> Option<Self> {
+        Self::try_from_slice(message).ok()
+    }
+}
@@ -0,0 +1,87 @@
+//! OneirobotNFT accounts, for programs and services that can't link the NFT
+//! program crate because it pulls in Metaplex.
+
+use anchor_lang::{prelude::*, solana_program::hash::hash};
+
+/// Metaplex Token Metadata, which holds every Oneirobot's metadata and
+/// master edition.
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// OneirobotNFT's `NftAttributes` layout, after the discriminator.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OneirobotAttributes {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_id: u64,
+    /// `intern::string_id` of the quantum core.
+    pub quantum_core_id: u64,
+    pub dream_level: u8,
+    pub lucid_power: u8,
+    pub mind_strength: u8,
+    pub metadata_uri: String,
+    pub mint_timestamp: i64,
+    pub random_seed: u64,
+    pub bump: u8,
+    pub season: u32,
+    pub name: String,
+    pub lore_cid: String,
+    pub named_at: i64,
+}
+
+impl OneirobotAttributes {
+    pub fn discriminator() -> [u8; 8] {
+        hash(b"account:NftAttributes").to_bytes()[..8].try_into().unwrap()
+    }
+
+    /// `mint`'s attributes PDA under the OneirobotNFT program `nft_program`.
+    pub fn address(nft_program: &Pubkey, mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"nft_attributes", mint.as_ref()], nft_program).0
+    }
+
+    /// Decodes discriminator-prefixed account data; `None` for any other account.
+    pub fn try_from_data(data: &[u8]) -> Option<Self> {
+        if data.get(..8)? != Self::discriminator() {
+            return None;
+        }
+        Self::deserialize(&mut &data[8..]).ok()
+    }
+}
+
+/// The OneirobotNFT program's state PDA.
+pub fn state_address(nft_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"oneirobot_state"], nft_program).0
+}
+
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    let seeds: &[&[u8]] = &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()];
+    Pubkey::find_program_address(seeds, &TOKEN_METADATA_PROGRAM_ID).0
+}
+
+pub fn master_edition_address(mint: &Pubkey) -> Pubkey {
+    let seeds: &[&[u8]] =
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref(), b"edition"];
+    Pubkey::find_program_address(seeds, &TOKEN_METADATA_PROGRAM_ID).0
+}
+
+/// `dream_level` and `lucid_power` of an Oneirobot spliced from dreams with
+/// these validation `scores` (1-100), recorded on these `days`: the average
+/// score, and the longest run of consecutive days as a share of the dreams.
+pub fn spliced_attributes(scores: &[u8], days: &[u32]) -> (u8, u8) {
+    if scores.is_empty() || days.is_empty() {
+        return (1, 1);
+    }
+    let total: u32 = scores.iter().map(|&score| u32::from(score)).sum();
+    let dream_level = (total / scores.len() as u32).clamp(1, 100) as u8;
+
+    let mut days = days.to_vec();
+    days.sort_unstable();
+    days.dedup();
+    let (mut longest, mut run) = (1, 1);
+    for pair in days.windows(2) {
+        run = if pair[1] == pair[0] + 1 { run + 1 } else { 1 };
+        longest = longest.max(run);
+    }
+    let lucid_power = (longest * 100 / scores.len()).clamp(1, 100) as u8;
+    (dream_level, lucid_power)
+}
@@ -0,0 +1,87 @@
+//! Fixed-depth Merkle tree of compressed SMIND stakes, also used for archived
+//! dream records. Unlike `merkle`, pairs are hashed in (left, right) order so
+//! a leaf can be replaced in place given its proof. Unused and unstaked
+//! leaves are all zeroes.
+
+use anchor_lang::{prelude::*, solana_program::keccak};
+use dream_mind_common::constants::COMPRESSED_STAKE_TREE_DEPTH;
+
+pub const EMPTY_LEAF: [u8; 32] = [0; 32];
+
+/// Leaf for one compressed stake.
+pub fn stake_leaf(staker: &Pubkey, amount: u64, staked_at: i64) -> [u8; 32] {
+    keccak::hashv(&[staker.as_ref(), &amount.to_le_bytes(), &staked_at.to_le_bytes()]).to_bytes()
+}
+
+/// Leaf for an archived dream: the hash of the record account's full data,
+/// discriminator and extras included.
+pub fn archived_dream_leaf(record_data: &[u8]) -> [u8; 32] {
+    keccak::hash(record_data).to_bytes()
+}
+
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    keccak::hashv(&[left, right]).to_bytes()
+}
+
+/// Root of an empty subtree at each level, from the leaves up.
+pub fn zero_hashes() -> [[u8; 32]; COMPRESSED_STAKE_TREE_DEPTH] {
+    let mut zeros = [EMPTY_LEAF; COMPRESSED_STAKE_TREE_DEPTH];
+    for level in 1..COMPRESSED_STAKE_TREE_DEPTH {
+        zeros[level] = hash_pair(&zeros[level - 1], &zeros[level - 1]);
+    }
+    zeros
+}
+
+/// Append `leaf` at `index`, the next free leaf, updating the frontier in
+/// `filled_subtrees`. Returns the new root.
+pub fn append(
+    filled_subtrees: &mut [[u8; 32]; COMPRESSED_STAKE_TREE_DEPTH],
+    index: u64,
+    leaf: [u8; 32],
+) -> [u8; 32] {
+    let mut node = leaf;
+    for (level, zero) in zero_hashes().iter().enumerate() {
+        if (index >> level) & 1 == 0 {
+            filled_subtrees[level] = node;
+            node = hash_pair(&node, zero);
+        } else {
+            node = hash_pair(&filled_subtrees[level], &node);
+        }
+    }
+    node
+}
+
+/// Root reached by hashing `leaf` at `index` up through `proof`.
+pub fn root_from_proof(leaf: &[u8; 32], index: u64, proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().enumerate().fold(*leaf, |node, (level, sibling)| {
+        if (index >> level) & 1 == 0 {
+            hash_pair(&node, sibling)
+        } else {
+            hash_pair(sibling, &node)
+        }
+    })
+}
+
+/// Root of the tree whose first leaves are `leaves`; the rest are empty.
+pub fn root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let zeros = zero_hashes();
+    let proof = proof(leaves, 0);
+    let leaf = leaves.first().unwrap_or(&zeros[0]);
+    root_from_proof(leaf, 0, &proof)
+}
+
+/// Sibling hashes from `leaves[index]` up to the root, as `compressed_unstake` expects.
+pub fn proof(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let zeros = zero_hashes();
+    let mut level = leaves.to_vec();
+    let mut proof = Vec::with_capacity(COMPRESSED_STAKE_TREE_DEPTH);
+    for zero in zeros {
+        proof.push(level.get(index ^ 1).copied().unwrap_or(zero));
+        level = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&zero)))
+            .collect();
+        index /= 2;
+    }
+    proof
+}
@@ -0,0 +1,63 @@
+//! Return types of the read-only view instructions. Anchor encodes them with
+//! borsh into the transaction's return data, which clients read by simulating.
+
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreasuryStats {
+    pub total_dreams_recorded: u64,
+    pub total_dreams_mirrored: u64,
+    pub total_rewards_distributed: u64,
+    pub total_rewards_expired: u64,
+    pub dream_supply: u64,
+    /// DREAM that can still be minted before the supply cap.
+    pub dream_supply_remaining: u64,
+    pub total_bridged_out: u64,
+    pub total_bridged_in: u64,
+    pub mev_protection_enabled: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DreamerStats {
+    pub dreamer: Pubkey,
+    pub interface_count: u64,
+    pub legacy_migrated: bool,
+    pub legacy_dream_count: u64,
+    pub legacy_tokens_earned: u64,
+    pub staked_amount: u64,
+    /// 0 without a stake, otherwise 1 (Basic) to 4 (Quantum).
+    pub access_level: u8,
+    /// Decayed to today, as the next `record_dream` would see it.
+    pub reputation: i16,
+    /// Reward multiplier of the reputation's band, in bps.
+    pub reward_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakeInfo {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub access_level: u8,
+    pub staked_at: i64,
+    pub staked_for_secs: i64,
+    /// Additional LUCID needed to reach the next access level; 0 at the top level.
+    pub amount_to_next_level: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramCapabilities {
+    /// Semver of the running program build, e.g. "0.1.0".
+    pub version: String,
+    /// `CAPABILITY_*` bits of the modules this deployment has turned on.
+    pub capabilities: u64,
+}
+
+impl ProgramCapabilities {
+    pub fn has(&self, capability: u64) -> bool {
+        self.capabilities & capability == capability
+    }
+}
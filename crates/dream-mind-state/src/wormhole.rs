@@ -0,0 +1,183 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+use dream_mind_common::constants::{
+    BRIDGE_PAYLOAD_ATTESTATION, BRIDGE_PAYLOAD_DREAM_MIRROR, BRIDGE_PAYLOAD_TRANSFER,
+};
+
+/// Wormhole core bridge program.
+#[cfg(feature = "mainnet")]
+pub const WORMHOLE_CORE_BRIDGE_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+#[cfg(not(feature = "mainnet"))]
+pub const WORMHOLE_CORE_BRIDGE_ID: Pubkey = pubkey!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+
+/// Core bridge `post_message` instruction index.
+pub const POST_MESSAGE_INSTRUCTION: u8 = 1;
+
+/// Offset of the message fee (u64) in the core bridge's `Bridge` account.
+pub const BRIDGE_FEE_OFFSET: usize = 16;
+
+/// Body of a core bridge `PostedVAA` account, written only after the guardian
+/// signatures have been verified. The account data starts with the `b"vaa"` magic.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PostedVaa {
+    pub vaa_version: u8,
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    pub vaa_signature_account: Pubkey,
+    pub submission_time: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+impl PostedVaa {
+    pub const MAGIC: &'static [u8] = b"vaa";
+
+    pub fn try_from_account_data(data: &[u8]) -> Option<Self> {
+        let mut body = data.strip_prefix(Self::MAGIC)?;
+        Self::deserialize(&mut body).ok()
+    }
+
+    /// Keccak-256 of the signed VAA body, which seeds the `PostedVAA` address.
+    pub fn body_hash(&self) -> [u8; 32] {
+        keccak::hashv(&[
+            &self.vaa_time.to_be_bytes(),
+            &self.nonce.to_be_bytes(),
+            &self.emitter_chain.to_be_bytes(),
+            &self.emitter_address,
+            &self.sequence.to_be_bytes(),
+            &[self.consistency_level],
+            &self.payload,
+        ])
+        .to_bytes()
+    }
+}
+
+pub fn find_posted_vaa_address(body_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"PostedVAA", body_hash], &WORMHOLE_CORE_BRIDGE_ID)
+}
+
+/// DREAM transfer between chains, in the Token Bridge `Transfer` layout:
+/// `id(1) amount(32) token_address(32) token_chain(2) to(32) to_chain(2) fee(32)`,
+/// big-endian. Amounts are raw base units; they are not normalized to 8 decimals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BridgeTransfer {
+    pub amount: u64,
+    pub token_address: [u8; 32],
+    pub token_chain: u16,
+    pub recipient: [u8; 32],
+    pub recipient_chain: u16,
+}
+
+impl BridgeTransfer {
+    pub const LEN: usize = 1 + 32 + 32 + 2 + 32 + 2 + 32;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::LEN);
+        out.push(BRIDGE_PAYLOAD_TRANSFER);
+        out.extend_from_slice(&u256_be(self.amount));
+        out.extend_from_slice(&self.token_address);
+        out.extend_from_slice(&self.token_chain.to_be_bytes());
+        out.extend_from_slice(&self.recipient);
+        out.extend_from_slice(&self.recipient_chain.to_be_bytes());
+        out.extend_from_slice(&u256_be(0));
+        out
+    }
+
+    /// Returns `None` for other payload types, wrong lengths, or amounts that
+    /// don't fit in a u64.
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        if payload.len() != Self::LEN || payload[0] != BRIDGE_PAYLOAD_TRANSFER {
+            return None;
+        }
+        Some(Self {
+            amount: u64_from_u256_be(&payload[1..33])?,
+            token_address: payload[33..65].try_into().ok()?,
+            token_chain: u16::from_be_bytes(payload[65..67].try_into().ok()?),
+            recipient: payload[67..99].try_into().ok()?,
+            recipient_chain: u16::from_be_bytes(payload[99..101].try_into().ok()?),
+        })
+    }
+}
+
+/// DREAM mint metadata, in the Token Bridge `AssetMeta` layout:
+/// `id(1) token_address(32) token_chain(2) decimals(1) symbol(32) name(32)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BridgeAttestation {
+    pub token_address: [u8; 32],
+    pub token_chain: u16,
+    pub decimals: u8,
+    pub symbol: [u8; 32],
+    pub name: [u8; 32],
+}
+
+impl BridgeAttestation {
+    pub const LEN: usize = 1 + 32 + 2 + 1 + 32 + 32;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::LEN);
+        out.push(BRIDGE_PAYLOAD_ATTESTATION);
+        out.extend_from_slice(&self.token_address);
+        out.extend_from_slice(&self.token_chain.to_be_bytes());
+        out.push(self.decimals);
+        out.extend_from_slice(&self.symbol);
+        out.extend_from_slice(&self.name);
+        out
+    }
+}
+
+/// A dream recorded on another chain's deployment:
+/// `id(1) dream_id(8) dreamer(32) content_hash(32)`, big-endian. `dreamer` is
+/// the Solana address the origin-chain dreamer registered for mirroring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DreamMirror {
+    pub dream_id: u64,
+    pub dreamer: [u8; 32],
+    pub content_hash: [u8; 32],
+}
+
+impl DreamMirror {
+    pub const LEN: usize = 1 + 8 + 32 + 32;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::LEN);
+        out.push(BRIDGE_PAYLOAD_DREAM_MIRROR);
+        out.extend_from_slice(&self.dream_id.to_be_bytes());
+        out.extend_from_slice(&self.dreamer);
+        out.extend_from_slice(&self.content_hash);
+        out
+    }
+
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        if payload.len() != Self::LEN || payload[0] != BRIDGE_PAYLOAD_DREAM_MIRROR {
+            return None;
+        }
+        Some(Self {
+            dream_id: u64::from_be_bytes(payload[1..9].try_into().ok()?),
+            dreamer: payload[9..41].try_into().ok()?,
+            content_hash: payload[41..73].try_into().ok()?,
+        })
+    }
+}
+
+/// Left-align `value` in a zero-padded 32-byte field, truncating if longer.
+pub fn fixed_bytes32(value: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let len = value.len().min(32);
+    out[..len].copy_from_slice(&value.as_bytes()[..len]);
+    out
+}
+
+fn u256_be(value: u64) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[24..].copy_from_slice(&value.to_be_bytes());
+    out
+}
+
+fn u64_from_u256_be(bytes: &[u8]) -> Option<u64> {
+    if bytes[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    Some(u64::from_be_bytes(bytes[24..32].try_into().ok()?))
+}
@@ -0,0 +1,272 @@
+//! Browser bindings for the Dream-Mind-Lucid client SDK.
+//!
+//! Build with `wasm-pack build crates/dream-mind-wasm --target web`. Every
+//! instruction builder returns `{ programId, keys, data }`, which maps directly
+//! onto `new TransactionInstruction(...)` in `@solana/web3.js` once the
+//! base58 strings are wrapped in `PublicKey`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AnchorDeserialize;
+use dream_mind_client::{
+    instructions::{self, OneirobotHolding, RecordDreamOptions},
+    pda, DreamMetadata, Mood,
+};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsAccountMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsInstruction {
+    program_id: String,
+    keys: Vec<JsAccountMeta>,
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+/// `{ mood, lucidityScore, sleepDurationMinutes }`; `mood` is the `Mood`
+/// variant index and 0 means unreported for every field.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsDreamMetadata {
+    mood: u8,
+    lucidity_score: u8,
+    sleep_duration_minutes: u16,
+}
+
+/// `{ charity?, enqueue?, lucidMint?, coDreamers?, idempotencyKey?, companion?,
+/// interpreter? }`, all optional; see `RecordDreamOptions`.
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsRecordDreamOptions {
+    charity: Option<String>,
+    enqueue: bool,
+    lucid_mint: Option<String>,
+    co_dreamers: Vec<String>,
+    idempotency_key: Option<u64>,
+    companion: Option<JsOneirobotHolding>,
+    interpreter: Option<String>,
+}
+
+/// `{ nftProgram, mint, tokenAccount }`; see `OneirobotHolding`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsOneirobotHolding {
+    nft_program: String,
+    mint: String,
+    token_account: String,
+}
+
+impl JsOneirobotHolding {
+    fn to_holding(&self) -> Result<OneirobotHolding, JsError> {
+        Ok(OneirobotHolding {
+            nft_program: pubkey(&self.nft_program)?,
+            mint: pubkey(&self.mint)?,
+            token_account: pubkey(&self.token_account)?,
+        })
+    }
+}
+
+fn to_js(ix: Instruction) -> Result<JsValue, JsError> {
+    let ix = JsInstruction {
+        program_id: ix.program_id.to_string(),
+        keys: ix
+            .accounts
+            .into_iter()
+            .map(|meta| JsAccountMeta {
+                pubkey: meta.pubkey.to_string(),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: ix.data,
+    };
+    Ok(serde_wasm_bindgen::to_value(&ix)?)
+}
+
+fn pubkey(value: &str) -> Result<Pubkey, JsError> {
+    value
+        .parse()
+        .map_err(|_| JsError::new(&format!("invalid public key: {value}")))
+}
+
+// ===================== PDA HELPERS =====================
+
+#[wasm_bindgen(js_name = programId)]
+pub fn program_id() -> String {
+    dream_mind_client::PROGRAM_ID.to_string()
+}
+
+#[wasm_bindgen(js_name = findTreasuryAddress)]
+pub fn find_treasury_address() -> String {
+    pda::find_treasury_address().0.to_string()
+}
+
+#[wasm_bindgen(js_name = findDreamRecordAddress)]
+pub fn find_dream_record_address(dreamer: &str, dream_id: u64) -> Result<String, JsError> {
+    Ok(pda::find_dream_record_address(&pubkey(dreamer)?, dream_id).0.to_string())
+}
+
+#[wasm_bindgen(js_name = findDreamerProfileAddress)]
+pub fn find_dreamer_profile_address(dreamer: &str) -> Result<String, JsError> {
+    Ok(pda::find_dreamer_profile_address(&pubkey(dreamer)?).0.to_string())
+}
+
+#[wasm_bindgen(js_name = findDreamInterfaceAddress)]
+pub fn find_dream_interface_address(dreamer: &str, interface_index: u64) -> Result<String, JsError> {
+    Ok(pda::find_dream_interface_address(&pubkey(dreamer)?, interface_index).0.to_string())
+}
+
+#[wasm_bindgen(js_name = findStakeAddress)]
+pub fn find_stake_address(user: &str, mint: &str) -> Result<String, JsError> {
+    Ok(pda::find_stake_address(&pubkey(user)?, &pubkey(mint)?).0.to_string())
+}
+
+// ===================== INSTRUCTION BUILDERS =====================
+
+#[wasm_bindgen(js_name = initializeTokens)]
+pub fn initialize_tokens(
+    authority: &str,
+    dream_mint: &str,
+    smind_mint: &str,
+    lucid_mint: &str,
+) -> Result<JsValue, JsError> {
+    to_js(instructions::initialize_tokens(
+        &pubkey(authority)?,
+        &pubkey(dream_mint)?,
+        &pubkey(smind_mint)?,
+        &pubkey(lucid_mint)?,
+    ))
+}
+
+#[wasm_bindgen(js_name = recordDream)]
+pub fn record_dream(
+    dreamer: &str,
+    dream_mint: &str,
+    dream_id: u64,
+    dream_content_hash: &[u8],
+    metadata: JsValue,
+    options: JsValue,
+    unix_timestamp: i64,
+) -> Result<JsValue, JsError> {
+    let dream_content_hash: [u8; 32] = dream_content_hash
+        .try_into()
+        .map_err(|_| JsError::new("dream content hash must be 32 bytes"))?;
+    let metadata: JsDreamMetadata = serde_wasm_bindgen::from_value(metadata)?;
+    let mood = Mood::try_from_slice(&[metadata.mood]).map_err(|_| JsError::new("unknown mood"))?;
+    let options: JsRecordDreamOptions = if options.is_undefined() || options.is_null() {
+        JsRecordDreamOptions::default()
+    } else {
+        serde_wasm_bindgen::from_value(options)?
+    };
+    to_js(instructions::record_dream(
+        &pubkey(dreamer)?,
+        &pubkey(dream_mint)?,
+        dream_id,
+        dream_content_hash,
+        DreamMetadata {
+            mood,
+            lucidity_score: metadata.lucidity_score,
+            sleep_duration_minutes: metadata.sleep_duration_minutes,
+        },
+        RecordDreamOptions {
+            charity: options.charity.as_deref().map(pubkey).transpose()?,
+            enqueue: options.enqueue,
+            lucid_mint: options.lucid_mint.as_deref().map(pubkey).transpose()?,
+            co_dreamers: options
+                .co_dreamers
+                .iter()
+                .map(String::as_str)
+                .map(pubkey)
+                .collect::<Result<_, _>>()?,
+            idempotency_key: options.idempotency_key,
+            companion: options.companion.as_ref().map(JsOneirobotHolding::to_holding).transpose()?,
+            interpreter: options.interpreter.as_deref().map(pubkey).transpose()?,
+        },
+        unix_timestamp,
+    ))
+}
+
+/// `oneirobot` is `{ nftProgram, mint, tokenAccount }`, with the NFT program
+/// named in the `SpliceConfig`.
+#[wasm_bindgen(js_name = equipOneirobotToProfile)]
+pub fn equip_oneirobot_to_profile(dreamer: &str, oneirobot: JsValue) -> Result<JsValue, JsError> {
+    let oneirobot: JsOneirobotHolding = serde_wasm_bindgen::from_value(oneirobot)?;
+    to_js(instructions::equip_oneirobot_to_profile(&pubkey(dreamer)?, &oneirobot.to_holding()?))
+}
+
+#[wasm_bindgen(js_name = interfaceDream)]
+pub fn interface_dream(dreamer: &str, interface_index: u64, ipfs_hash: String) -> Result<JsValue, JsError> {
+    to_js(instructions::interface_dream(&pubkey(dreamer)?, interface_index, ipfs_hash))
+}
+
+#[wasm_bindgen(js_name = updateInterface)]
+pub fn update_interface(dreamer: &str, interface_index: u64, ipfs_hash: String) -> Result<JsValue, JsError> {
+    to_js(instructions::update_interface(&pubkey(dreamer)?, interface_index, ipfs_hash))
+}
+
+#[wasm_bindgen(js_name = closeInterface)]
+pub fn close_interface(dreamer: &str, interface_index: u64) -> Result<JsValue, JsError> {
+    to_js(instructions::close_interface(&pubkey(dreamer)?, interface_index))
+}
+
+#[wasm_bindgen(js_name = stakeForLucidAccess)]
+pub fn stake_for_lucid_access(
+    user: &str,
+    stake_mint: &str,
+    user_token_account: &str,
+    stake_vault: &str,
+    amount: u64,
+) -> Result<JsValue, JsError> {
+    to_js(instructions::stake_for_lucid_access(
+        &pubkey(user)?,
+        &pubkey(stake_mint)?,
+        &pubkey(user_token_account)?,
+        &pubkey(stake_vault)?,
+        amount,
+    ))
+}
+
+/// Devnet builds of the program only, once a day per wallet.
+#[wasm_bindgen(js_name = faucetMint)]
+pub fn faucet_mint(
+    wallet: &str,
+    dream_mint: &str,
+    smind_mint: &str,
+    lucid_mint: &str,
+) -> Result<JsValue, JsError> {
+    to_js(instructions::faucet_mint(
+        &pubkey(wallet)?,
+        &pubkey(dream_mint)?,
+        &pubkey(smind_mint)?,
+        &pubkey(lucid_mint)?,
+    ))
+}
+
+#[wasm_bindgen(js_name = migrateStake)]
+pub fn migrate_stake(user: &str, lucid_mint: &str) -> Result<JsValue, JsError> {
+    to_js(instructions::migrate_stake(&pubkey(user)?, &pubkey(lucid_mint)?))
+}
+
+#[wasm_bindgen(js_name = setMevProtection)]
+pub fn set_mev_protection(authority: &str, enabled: bool) -> Result<JsValue, JsError> {
+    to_js(instructions::set_mev_protection(&pubkey(authority)?, enabled))
+}
+
+#[wasm_bindgen(js_name = migrateLegacyStorage)]
+pub fn migrate_legacy_storage(dreamer: &str, legacy_storage: &str) -> Result<JsValue, JsError> {
+    to_js(instructions::migrate_legacy_storage(&pubkey(dreamer)?, &pubkey(legacy_storage)?))
+}
+
+#[wasm_bindgen(js_name = migrateDreamerProfile)]
+pub fn migrate_dreamer_profile(payer: &str, dreamer: &str) -> Result<JsValue, JsError> {
+    to_js(instructions::migrate_dreamer_profile(&pubkey(payer)?, &pubkey(dreamer)?))
+}
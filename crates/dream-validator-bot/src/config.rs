@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+pub struct Config {
+    pub rpc_url: String,
+    pub ws_url: String,
+    pub keypair_path: String,
+    pub ipfs_gateway_url: String,
+    pub scoring_backend: String,
+    pub batch_size: usize,
+    pub batch_window: Duration,
+    pub max_tx_per_min: u32,
+    pub concurrency: usize,
+}
+
+impl Config {
+    /// Read configuration from the environment (same variable names as `.env`):
+    /// - `SOLANA_RPC_URL`: HTTP endpoint used to send transactions
+    /// - `SOLANA_WS_URL`: websocket endpoint (derived from the RPC URL if unset)
+    /// - `VALIDATOR_KEYPAIR`: keypair file of the validating authority
+    /// - `IPFS_GATEWAY_URL`: gateway dream content is fetched from
+    /// - `SCORING_BACKEND`: `http(s)://...` to POST content to a scoring
+    ///   service, or `command:<shell command>` to run a local model
+    /// - `VALIDATOR_BATCH_SIZE`: most `validate_dream` instructions per transaction
+    /// - `VALIDATOR_BATCH_WINDOW_MS`: how long to wait to fill a batch
+    /// - `VALIDATOR_MAX_TX_PER_MIN`: transaction rate limit
+    /// - `VALIDATOR_CONCURRENCY`: dreams fetched and scored at once
+    pub fn from_env() -> Result<Self> {
+        let rpc_url = std::env::var("SOLANA_RPC_URL")
+            .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string());
+        let ws_url = std::env::var("SOLANA_WS_URL").unwrap_or_else(|_| {
+            rpc_url
+                .replacen("https://", "wss://", 1)
+                .replacen("http://", "ws://", 1)
+        });
+        let keypair_path =
+            std::env::var("VALIDATOR_KEYPAIR").context("VALIDATOR_KEYPAIR is not set")?;
+        let ipfs_gateway_url = std::env::var("IPFS_GATEWAY_URL")
+            .unwrap_or_else(|_| "https://ipfs.io".to_string())
+            .trim_end_matches('/')
+            .to_string();
+        let scoring_backend =
+            std::env::var("SCORING_BACKEND").context("SCORING_BACKEND is not set")?;
+
+        Ok(Self {
+            rpc_url,
+            ws_url,
+            keypair_path,
+            ipfs_gateway_url,
+            scoring_backend,
+            batch_size: parse_var("VALIDATOR_BATCH_SIZE", 4)?.max(1),
+            batch_window: Duration::from_millis(parse_var("VALIDATOR_BATCH_WINDOW_MS", 2_000)?),
+            max_tx_per_min: parse_var("VALIDATOR_MAX_TX_PER_MIN", 30)?.max(1),
+            concurrency: parse_var("VALIDATOR_CONCURRENCY", 4)?.max(1),
+        })
+    }
+}
+
+fn parse_var<T: std::str::FromStr>(name: &str, default: T) -> Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse()
+            .with_context(|| format!("invalid {name}: {value}")),
+        Err(_) => Ok(default),
+    }
+}
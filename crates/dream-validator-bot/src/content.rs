@@ -0,0 +1,35 @@
+//! Dream content from an IPFS gateway, checked against the on-chain hash.
+
+use anyhow::{bail, Result};
+use dream_mind_state::cid::raw_cid;
+use sha2::{Digest, Sha256};
+
+/// Dreams are stored as a single raw block, so anything larger isn't one.
+const MAX_CONTENT_LEN: usize = 256 * 1024;
+
+/// Fetch the block whose SHA-256 is `content_hash` and check that it is.
+pub async fn fetch(
+    http: &reqwest::Client,
+    gateway_url: &str,
+    content_hash: &[u8; 32],
+) -> Result<Vec<u8>> {
+    let cid = raw_cid(content_hash);
+    let mut response = http
+        .get(format!("{gateway_url}/ipfs/{cid}"))
+        .header("Accept", "application/vnd.ipld.raw")
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let mut content = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if content.len() + chunk.len() > MAX_CONTENT_LEN {
+            bail!("{cid} is larger than {MAX_CONTENT_LEN} bytes");
+        }
+        content.extend_from_slice(&chunk);
+    }
+    if Sha256::digest(&content).as_slice() != content_hash {
+        bail!("content served for {cid} does not match its hash");
+    }
+    Ok(content)
+}
@@ -0,0 +1,121 @@
+//! Dream-Mind-Lucid validator bot: follows `DreamPinned` events over
+//! websocket, since only dreams an oracle has confirmed pinned can be
+//! validated. It fetches each dream's record and its content from IPFS, has
+//! the configured scoring backend rate it, and submits the scores as
+//! `validate_dream` transactions in rate-limited batches.
+
+mod config;
+mod content;
+mod scoring;
+mod submitter;
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use dream_mind_client::{fetch_dream_record, fetch_treasury};
+use dream_mind_events::{parse_logs, DreamEvent};
+use dream_mind_state::DreamPinnedEvent;
+use futures_util::StreamExt;
+use solana_client::{
+    nonblocking::pubsub_client::PubsubClient,
+    rpc_client::RpcClient,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file};
+use tokio::sync::{mpsc, Semaphore};
+
+use crate::{
+    config::Config,
+    scoring::Scorer,
+    submitter::{Submitter, Verdict},
+};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::init();
+    let config = Config::from_env()?;
+    let authority = read_keypair_file(&config.keypair_path)
+        .map_err(|err| anyhow!("could not read keypair {}: {err}", config.keypair_path))?;
+    let http = reqwest::Client::new();
+    let scorer = Arc::new(Scorer::parse(&config.scoring_backend, http.clone())?);
+
+    let client = Arc::new(RpcClient::new_with_commitment(
+        config.rpc_url.clone(),
+        CommitmentConfig::confirmed(),
+    ));
+
+    // Onboarding bonuses are paid in DREAM at validation
+    let treasury_client = client.clone();
+    let treasury = tokio::task::spawn_blocking(move || fetch_treasury(&treasury_client)).await??;
+
+    let (verdicts, receiver) = mpsc::channel(config.batch_size * 4);
+    let submitter = Submitter {
+        client: client.clone(),
+        authority: Arc::new(authority),
+        dream_mint: treasury.dream_mint,
+        batch_size: config.batch_size,
+        batch_window: config.batch_window,
+        max_tx_per_min: config.max_tx_per_min,
+    };
+    tokio::spawn(submitter.run(receiver));
+
+    let pubsub = PubsubClient::new(&config.ws_url).await?;
+    let (mut logs, _unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![dream_mind_state::ID.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+
+    let permits = Arc::new(Semaphore::new(config.concurrency));
+    let gateway_url: Arc<str> = config.ipfs_gateway_url.into();
+    while let Some(response) = logs.next().await {
+        if response.value.err.is_some() {
+            continue;
+        }
+        for event in parse_logs(&response.value.logs, &[dream_mind_state::ID]) {
+            let DreamEvent::DreamPinned(dream) = event else {
+                continue;
+            };
+            let permit = permits.clone().acquire_owned().await?;
+            let (client, http, gateway_url) = (client.clone(), http.clone(), gateway_url.clone());
+            let (scorer, verdicts) = (scorer.clone(), verdicts.clone());
+            tokio::spawn(async move {
+                let _permit = permit;
+                match judge(client, &http, &gateway_url, &scorer, &dream).await {
+                    Ok(verdict) => {
+                        // Only fails once the submitter is gone, i.e. on shutdown.
+                        let _ = verdicts.send(verdict).await;
+                    }
+                    Err(err) => {
+                        log::warn!("Skipping dream {}: {err:#}", dream.dream_record)
+                    }
+                }
+            });
+        }
+    }
+
+    bail!("websocket subscription closed")
+}
+
+async fn judge(
+    client: Arc<RpcClient>,
+    http: &reqwest::Client,
+    gateway_url: &str,
+    scorer: &Scorer,
+    pinned: &DreamPinnedEvent,
+) -> Result<Verdict> {
+    let (dreamer, dream_id) = (pinned.dreamer, pinned.dream_id);
+    let record =
+        tokio::task::spawn_blocking(move || fetch_dream_record(&client, &dreamer, dream_id))
+            .await??;
+    let content = content::fetch(http, gateway_url, &record.content_hash).await?;
+    let score = scorer.score(&record, &content).await?;
+    Ok(Verdict {
+        dreamer,
+        dream_record: pinned.dream_record,
+        score,
+    })
+}
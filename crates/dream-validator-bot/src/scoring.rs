@@ -0,0 +1,120 @@
+//! Pluggable scoring backends. Both get the dream's content and metadata and
+//! answer with a score from 1 to `MAX_VALIDATION_SCORE`.
+//!
+//! - HTTP: the dream is POSTed as JSON (`dreamer`, `id`, `content_hash`,
+//!   `metadata`, `content`) and the response must be `{"score": n}`.
+//! - Command: the command runs under `sh -c` with the content on stdin and
+//!   `DREAM_ID`, `DREAM_DREAMER`, `DREAM_CONTENT_HASH`, `DREAM_MOOD` and
+//!   `DREAM_LUCIDITY` set; it prints the score on stdout. This is how a local
+//!   model is plugged in.
+
+use std::{process::Stdio, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use dream_mind_common::constants::MAX_VALIDATION_SCORE;
+use dream_mind_state::DreamRecord;
+use serde::{Deserialize, Serialize};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub enum Scorer {
+    Http {
+        client: reqwest::Client,
+        url: String,
+    },
+    Command(String),
+}
+
+#[derive(Serialize)]
+struct ScoreRequest<'a> {
+    dreamer: String,
+    id: u64,
+    content_hash: String,
+    metadata: &'a dream_mind_state::DreamMetadata,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct ScoreResponse {
+    score: u8,
+}
+
+impl Scorer {
+    /// `http(s)://...` or `command:<shell command>`.
+    pub fn parse(backend: &str, client: reqwest::Client) -> Result<Self> {
+        if let Some(command) = backend.strip_prefix("command:") {
+            return Ok(Self::Command(command.to_string()));
+        }
+        if backend.starts_with("http://") || backend.starts_with("https://") {
+            return Ok(Self::Http {
+                client,
+                url: backend.to_string(),
+            });
+        }
+        bail!("SCORING_BACKEND must be an http(s) URL or command:<cmd>, got {backend}")
+    }
+
+    pub async fn score(&self, dream: &DreamRecord, content: &[u8]) -> Result<u8> {
+        let score = match self {
+            Self::Http { client, url } => {
+                let request = ScoreRequest {
+                    dreamer: dream.dreamer.to_string(),
+                    id: dream.id,
+                    content_hash: hex(&dream.content_hash),
+                    metadata: &dream.metadata,
+                    content: String::from_utf8_lossy(content).into_owned(),
+                };
+                let response: ScoreResponse = client
+                    .post(url)
+                    .timeout(HTTP_TIMEOUT)
+                    .json(&request)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                response.score
+            }
+            Self::Command(command) => run_command(command, dream, content).await?,
+        };
+        if !(1..=MAX_VALIDATION_SCORE).contains(&score) {
+            bail!("scoring backend returned {score}, outside 1..={MAX_VALIDATION_SCORE}");
+        }
+        Ok(score)
+    }
+}
+
+async fn run_command(command: &str, dream: &DreamRecord, content: &[u8]) -> Result<u8> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("DREAM_ID", dream.id.to_string())
+        .env("DREAM_DREAMER", dream.dreamer.to_string())
+        .env("DREAM_CONTENT_HASH", hex(&dream.content_hash))
+        .env("DREAM_MOOD", format!("{:?}", dream.metadata.mood))
+        .env("DREAM_LUCIDITY", dream.metadata.lucidity_score.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("could not run scoring command {command}"))?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    stdin.write_all(content).await?;
+    drop(stdin);
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        bail!("scoring command exited with {}", output.status);
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .trim()
+        .parse()
+        .with_context(|| format!("scoring command printed {:?}, not a score", stdout.trim()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,132 @@
+//! Batches scores into `validate_dream` transactions under a rate limit.
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use dream_mind_client::{
+    dispatch::{self, DispatchConfig},
+    find_dream_interpretation_address, find_dream_validation_address, instructions,
+    layouts::{AccountLayout, DreamInterpretation},
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use tokio::{
+    sync::mpsc,
+    time::{sleep_until, timeout_at, Instant},
+};
+
+pub struct Verdict {
+    pub dreamer: Pubkey,
+    pub dream_record: Pubkey,
+    pub score: u8,
+}
+
+pub struct Submitter {
+    pub client: Arc<RpcClient>,
+    pub authority: Arc<Keypair>,
+    pub dream_mint: Pubkey,
+    pub batch_size: usize,
+    pub batch_window: Duration,
+    pub max_tx_per_min: u32,
+}
+
+impl Submitter {
+    /// Send verdicts from `verdicts` until the channel closes. A batch goes out
+    /// once it is full or `batch_window` after its first verdict, whichever is
+    /// sooner, and never sooner than the rate limit allows.
+    pub async fn run(self, mut verdicts: mpsc::Receiver<Verdict>) {
+        let spacing = Duration::from_secs(60) / self.max_tx_per_min;
+        let mut next_send = Instant::now();
+        while let Some(first) = verdicts.recv().await {
+            let mut batch = vec![first];
+            let deadline = Instant::now() + self.batch_window;
+            while batch.len() < self.batch_size {
+                match timeout_at(deadline, verdicts.recv()).await {
+                    Ok(Some(verdict)) => batch.push(verdict),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+
+            sleep_until(next_send).await;
+            next_send = Instant::now() + spacing;
+            if let Err(err) = self.submit(batch).await {
+                log::error!("validate_dream batch failed: {err:#}");
+            }
+        }
+    }
+
+    async fn submit(&self, batch: Vec<Verdict>) -> Result<()> {
+        let client = self.client.clone();
+        let authority = self.authority.clone();
+        let dream_mint = self.dream_mint;
+        tokio::task::spawn_blocking(move || {
+            // Another instance, or an earlier run, may have got there first; the
+            // init would fail the whole transaction.
+            let validations: Vec<Pubkey> = batch
+                .iter()
+                .map(|verdict| find_dream_validation_address(&verdict.dream_record).0)
+                .collect();
+            let existing = client.get_multiple_accounts(&validations)?;
+            let pending: Vec<&Verdict> = batch
+                .iter()
+                .zip(existing)
+                .filter(|(_, account)| account.is_none())
+                .map(|(verdict, _)| verdict)
+                .collect();
+            if pending.is_empty() {
+                return Ok(());
+            }
+
+            // Dreams with an attached analysis pay their interpreter, whose
+            // DREAM account the instruction then needs
+            let interpretations: Vec<Pubkey> = pending
+                .iter()
+                .map(|verdict| find_dream_interpretation_address(&verdict.dream_record).0)
+                .collect();
+            let interpreters = client
+                .get_multiple_accounts(&interpretations)?
+                .into_iter()
+                .map(|account| {
+                    account
+                        .and_then(|account| {
+                            DreamInterpretation::try_from_account_data(&account.data).ok()
+                        })
+                        .filter(|interpretation| !interpretation.analysis_cid.is_empty())
+                        .map(|interpretation| interpretation.interpreter)
+                });
+            let ixs: Vec<_> = pending
+                .iter()
+                .zip(interpreters)
+                .map(|(verdict, interpreter)| match interpreter {
+                    Some(interpreter) => instructions::validate_interpreted_dream(
+                        &authority.pubkey(),
+                        &verdict.dreamer,
+                        &dream_mint,
+                        &verdict.dream_record,
+                        None,
+                        verdict.score,
+                        &interpreter,
+                    ),
+                    None => instructions::validate_dream(
+                        &authority.pubkey(),
+                        &verdict.dreamer,
+                        &dream_mint,
+                        &verdict.dream_record,
+                        None,
+                        verdict.score,
+                    ),
+                })
+                .collect();
+            let signature = dispatch::send_with_retry(
+                &client,
+                &ixs,
+                authority.as_ref(),
+                &[],
+                &DispatchConfig::default(),
+            )?;
+            log::info!("Validated {} dreams in {signature}", pending.len());
+            Ok(())
+        })
+        .await?
+    }
+}
@@ -0,0 +1,88 @@
+//! Fuzz the canonical dream-mind-lucid program's instructions, with emphasis on
+//! the string-carrying interface instructions.
+
+use anchor_lang::{prelude::Pubkey, InstructionData};
+use arbitrary::Arbitrary;
+use dream_mind_client::pda;
+use dream_mind_fuzz::{process, user_keys, FuzzAccount};
+use dream_mind_lucid::{instruction, legacy::LEGACY_PROGRAM_ID};
+use honggfuzz::fuzz;
+
+#[derive(Arbitrary, Debug)]
+enum CoreInstruction {
+    /// Raw bytes, exercising discriminator dispatch and Borsh argument decoding.
+    Raw(Vec<u8>),
+    InitializeTokens,
+    RecordDream { dream_content_hash: [u8; 32] },
+    InterfaceDream { ipfs_hash: String },
+    UpdateInterface { interface_index: u64, ipfs_hash: String },
+    CloseInterface { interface_index: u64 },
+    StakeForLucidAccess { amount: u64 },
+    SetMevProtection { enabled: bool },
+    MigrateLegacyStorage,
+}
+
+impl CoreInstruction {
+    fn data(self) -> Vec<u8> {
+        match self {
+            Self::Raw(data) => data,
+            Self::InitializeTokens => instruction::InitializeTokens {}.data(),
+            Self::RecordDream { dream_content_hash } => {
+                instruction::RecordDream { dream_content_hash }.data()
+            }
+            Self::InterfaceDream { ipfs_hash } => instruction::InterfaceDream { ipfs_hash }.data(),
+            Self::UpdateInterface { interface_index, ipfs_hash } => {
+                instruction::UpdateInterface { interface_index, ipfs_hash }.data()
+            }
+            Self::CloseInterface { interface_index } => {
+                instruction::CloseInterface { interface_index }.data()
+            }
+            Self::StakeForLucidAccess { amount } => {
+                instruction::StakeForLucidAccess { amount }.data()
+            }
+            Self::SetMevProtection { enabled } => instruction::SetMevProtection { enabled }.data(),
+            Self::MigrateLegacyStorage => instruction::MigrateLegacyStorage {}.data(),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    instruction: CoreInstruction,
+    accounts: Vec<FuzzAccount>,
+}
+
+fn key_pool() -> Vec<Pubkey> {
+    let [dreamer, ..] = user_keys();
+    let mut pool = vec![
+        dream_mind_lucid::ID,
+        anchor_lang::solana_program::system_program::ID,
+        anchor_spl::token_2022::ID,
+        anchor_spl::associated_token::ID,
+        LEGACY_PROGRAM_ID,
+        pda::find_treasury_address().0,
+        pda::find_dream_record_address(&dreamer, 0).0,
+        pda::find_dreamer_profile_address(&dreamer).0,
+        pda::find_dream_interface_address(&dreamer, 0).0,
+        pda::find_legacy_stake_address(&dreamer).0,
+    ];
+    pool.extend(user_keys());
+    pool
+}
+
+fn main() {
+    let pool = key_pool();
+
+    loop {
+        fuzz!(|input: Input| {
+            let data = input.instruction.data();
+            let _ = process(
+                dream_mind_lucid::entry,
+                &dream_mind_lucid::ID,
+                &pool,
+                input.accounts,
+                &data,
+            );
+        });
+    }
+}
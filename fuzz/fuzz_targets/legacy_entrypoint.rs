@@ -0,0 +1,73 @@
+//! Fuzz the Borsh-decoded native entrypoint of the legacy dream program.
+
+use arbitrary::Arbitrary;
+use dream_mind_fuzz::{process, user_keys, FuzzAccount};
+use honggfuzz::fuzz;
+use oneirobot_nft::{process_instruction, DreamInstruction, DreamStorage};
+
+#[derive(Arbitrary, Debug)]
+enum LegacyInput {
+    /// Raw bytes straight into `DreamInstruction::try_from_slice`.
+    Raw(Vec<u8>),
+    RecordDream { dream_content: String },
+    InitializeDreamStorage,
+    /// A well-formed `DreamStorage` body for the account at `index`, so
+    /// `record_dream` gets past deserialization.
+    RecordDreamWithStorage {
+        dream_content: String,
+        index: u8,
+        dream_count: u64,
+        total_tokens_earned: u64,
+        is_initialized: bool,
+    },
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    instruction: LegacyInput,
+    accounts: Vec<FuzzAccount>,
+}
+
+fn main() {
+    let program_id = oneirobot_nft::ID;
+    let mut pool = vec![
+        program_id,
+        anchor_lang::solana_program::system_program::ID,
+    ];
+    pool.extend(user_keys());
+
+    loop {
+        fuzz!(|input: Input| {
+            let Input { instruction, mut accounts } = input;
+            let data = match instruction {
+                LegacyInput::Raw(data) => data,
+                LegacyInput::RecordDream { dream_content } => {
+                    borsh::to_vec(&DreamInstruction::RecordDream { dream_content }).unwrap()
+                }
+                LegacyInput::InitializeDreamStorage => {
+                    borsh::to_vec(&DreamInstruction::InitializeDreamStorage).unwrap()
+                }
+                LegacyInput::RecordDreamWithStorage {
+                    dream_content,
+                    index,
+                    dream_count,
+                    total_tokens_earned,
+                    is_initialized,
+                } => {
+                    if !accounts.is_empty() {
+                        let len = accounts.len();
+                        let storage = DreamStorage {
+                            dreamer: dream_mind_fuzz::pick(&pool, accounts[0].key),
+                            dream_count,
+                            total_tokens_earned,
+                            is_initialized,
+                        };
+                        accounts[index as usize % len].data = borsh::to_vec(&storage).unwrap();
+                    }
+                    borsh::to_vec(&DreamInstruction::RecordDream { dream_content }).unwrap()
+                }
+            };
+            let _ = process(process_instruction, &program_id, &pool, accounts, &data);
+        });
+    }
+}
@@ -0,0 +1,64 @@
+//! Fuzz the OneirobotNFT program, mainly `mint_oneirobot`'s metadata strings.
+
+use anchor_lang::{prelude::Pubkey, InstructionData};
+use arbitrary::Arbitrary;
+use dream_mind_fuzz::{pick, process, user_keys, FuzzAccount};
+use honggfuzz::fuzz;
+use oneirobot_nft::oneirobot_nft::instruction;
+
+#[derive(Arbitrary, Debug)]
+enum OneirobotInstruction {
+    /// Raw bytes, exercising discriminator dispatch and Borsh argument decoding.
+    Raw(Vec<u8>),
+    Initialize,
+    AddSyndicateMaster { new_master: u8 },
+    MintOneirobot { metadata_uri: String, name: String, symbol: String },
+    GetNftAttributes,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    instruction: OneirobotInstruction,
+    accounts: Vec<FuzzAccount>,
+}
+
+fn key_pool() -> Vec<Pubkey> {
+    let program_id = oneirobot_nft::oneirobot_nft::ID;
+    let mut pool = vec![
+        program_id,
+        anchor_lang::solana_program::system_program::ID,
+        anchor_lang::solana_program::sysvar::rent::ID,
+        anchor_spl::token::ID,
+        anchor_spl::associated_token::ID,
+        Pubkey::find_program_address(&[b"oneirobot_state"], &program_id).0,
+    ];
+    pool.extend(user_keys());
+    pool
+}
+
+fn main() {
+    let pool = key_pool();
+
+    loop {
+        fuzz!(|input: Input| {
+            let data = match input.instruction {
+                OneirobotInstruction::Raw(data) => data,
+                OneirobotInstruction::Initialize => instruction::Initialize {}.data(),
+                OneirobotInstruction::AddSyndicateMaster { new_master } => {
+                    instruction::AddSyndicateMaster { new_master: pick(&pool, new_master) }.data()
+                }
+                OneirobotInstruction::MintOneirobot { metadata_uri, name, symbol } => {
+                    instruction::MintOneirobot { metadata_uri, name, symbol }.data()
+                }
+                OneirobotInstruction::GetNftAttributes => instruction::GetNftAttributes {}.data(),
+            };
+            let _ = process(
+                oneirobot_nft::oneirobot_nft::entry,
+                &oneirobot_nft::oneirobot_nft::ID,
+                &pool,
+                input.accounts,
+                &data,
+            );
+        });
+    }
+}
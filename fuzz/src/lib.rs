@@ -0,0 +1,82 @@
+//! Shared pieces for the honggfuzz targets in `fuzz_targets/`.
+//!
+//! Each target turns fuzzer bytes into an instruction plus a list of
+//! [`FuzzAccount`]s and runs it through the program's entrypoint in-process.
+//! Returned errors are expected; only panics and aborts count as findings.
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+use arbitrary::Arbitrary;
+
+/// Account description driven by the fuzzer.
+///
+/// Keys and owners are indices into a per-target key pool rather than raw
+/// pubkeys, so mutations reorder and reuse the addresses the program actually
+/// checks (program IDs, PDAs, signers) instead of producing random keys that
+/// fail the first constraint.
+#[derive(Arbitrary, Debug)]
+pub struct FuzzAccount {
+    pub key: u8,
+    pub owner: u8,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+}
+
+/// Fixed user keys shared by every target's key pool.
+pub fn user_keys() -> [Pubkey; 4] {
+    [
+        Pubkey::new_from_array([1; 32]),
+        Pubkey::new_from_array([2; 32]),
+        Pubkey::new_from_array([3; 32]),
+        Pubkey::new_from_array([4; 32]),
+    ]
+}
+
+/// Pick a pool entry for a fuzzer-chosen index.
+pub fn pick(pool: &[Pubkey], index: u8) -> Pubkey {
+    pool[index as usize % pool.len()]
+}
+
+/// Program entrypoint signature. Anchor's generated `entry` ties the slice and
+/// `AccountInfo` lifetimes together; native entrypoints coerce to it.
+pub type Entry = for<'info> fn(&Pubkey, &'info [AccountInfo<'info>], &[u8]) -> ProgramResult;
+
+/// Materialize `accounts` as `AccountInfo`s and invoke `entry` with them.
+pub fn process(
+    entry: Entry,
+    program_id: &Pubkey,
+    pool: &[Pubkey],
+    accounts: Vec<FuzzAccount>,
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let keys: Vec<Pubkey> = accounts.iter().map(|a| pick(pool, a.key)).collect();
+    let owners: Vec<Pubkey> = accounts.iter().map(|a| pick(pool, a.owner)).collect();
+    let mut lamports: Vec<u64> = accounts.iter().map(|a| a.lamports).collect();
+    let mut data: Vec<Vec<u8>> = accounts.iter().map(|a| a.data.clone()).collect();
+
+    let infos: Vec<AccountInfo> = accounts
+        .iter()
+        .zip(&keys)
+        .zip(&owners)
+        .zip(lamports.iter_mut())
+        .zip(data.iter_mut())
+        .map(|((((account, key), owner), lamports), data)| {
+            AccountInfo::new(
+                key,
+                account.is_signer,
+                account.is_writable,
+                lamports,
+                data,
+                owner,
+                account.executable,
+                0,
+            )
+        })
+        .collect();
+
+    entry(program_id, &infos, instruction_data)
+}
@@ -0,0 +1,176 @@
+//! Compute units and rent per dream on the account-based path, where the
+//! `DreamRecord` stays open, against the compressed path, where
+//! `archive_dream` folds it into the archive tree and leaves an
+//! `ArchivedDream` stub. Dreams are measured with growing `DreamRecordExtras`.
+//! Results go to stdout as JSON for regression tracking.
+//!
+//! Compute units are only metered for the SBF build, so build it first:
+//!
+//! ```text
+//! cargo build-sbf --manifest-path packages/core/programs/dream-mind-lucid/Cargo.toml
+//! SBF_OUT_DIR=target/deploy cargo bench -p dream-mind-lucid --bench dream_paths > dream_paths.json
+//! ```
+
+use dream_mind_client::{
+    instructions::{self, RecordDreamOptions},
+    pda,
+};
+use dream_mind_common::constants::{DREAM_ARCHIVE_MIN_AGE_SECS, TOKEN_UNIT};
+use dream_mind_lucid::{cid, DreamMetadata, DreamRecordExtras};
+use serde_json::{json, Value};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction, transaction::Transaction,
+};
+
+/// Interned tag ids set on each measured dream.
+const TAG_COUNTS: [usize; 4] = [0, 1, 4, 16];
+
+/// Send `instructions` and return the compute units they consumed.
+async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> u64 {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    let processed = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    processed.result.unwrap();
+    processed.metadata.unwrap().compute_units_consumed
+}
+
+async fn lamports(context: &mut ProgramTestContext, address: &Pubkey) -> (u64, usize) {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap();
+    (account.lamports, account.data.len())
+}
+
+/// Bootstrap the treasury and mints, fill the reward pool and create the
+/// archive tree. Returns the DREAM mint and the approved pinning oracle.
+async fn setup(context: &mut ProgramTestContext) -> (Pubkey, Keypair) {
+    let authority = context.payer.pubkey();
+    let dream_mint = pda::find_dream_mint_address().0;
+    let oracle = Keypair::new();
+    process(context, &[instructions::bootstrap(&authority)], &[]).await;
+    let ixs = [
+        instructions::set_insurance_fee_share(&authority, &dream_mint, 0),
+        instructions::set_validator_rewards(&authority, &dream_mint, 0, 0),
+        instructions::reconcile_treasury(&authority),
+        instructions::top_up_pool(&authority, &dream_mint, 1_000_000 * TOKEN_UNIT),
+        instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true),
+        instructions::initialize_dream_archive_tree(&authority),
+    ];
+    process(context, &ixs, &[]).await;
+    (dream_mint, oracle)
+}
+
+/// Record, grow, validate and archive one dream with `tag_count` tags.
+async fn measure(
+    context: &mut ProgramTestContext,
+    dream_mint: &Pubkey,
+    oracle: &Keypair,
+    tag_count: usize,
+) -> Value {
+    let authority = context.payer.pubkey();
+    let dreamer = Keypair::new();
+    let ix = system_instruction::transfer(&authority, &dreamer.pubkey(), 1_000_000_000);
+    process(context, &[ix], &[]).await;
+
+    let content_hash = [tag_count as u8; 32];
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        dream_mint,
+        0,
+        content_hash,
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        clock.unix_timestamp,
+    );
+    let mut recorded = process(context, &[ix], &[&dreamer]).await;
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    if tag_count > 0 {
+        let extras = DreamRecordExtras {
+            tags: (0..tag_count as u64).collect(),
+            annotations: Vec::new(),
+        };
+        let ix = instructions::set_dream_extras(&dreamer.pubkey(), &record, extras);
+        recorded += process(context, &[ix], &[&dreamer]).await;
+    }
+    let (record_rent, record_bytes) = lamports(context, &record).await;
+
+    // Only validated dreams old enough can be archived
+    let message = cid::pin_attestation_message(&record, &content_hash);
+    let signature: [u8; 64] = oracle.sign_message(&message).as_ref().try_into().unwrap();
+    let ixs = [
+        instructions::ed25519_verify(&oracle.pubkey(), &signature, &message),
+        instructions::confirm_pinned(&authority, &oracle.pubkey(), &record),
+        instructions::validate_dream(&authority, &dreamer.pubkey(), dream_mint, &record, None, 80),
+    ];
+    process(context, &ixs, &[]).await;
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += DREAM_ARCHIVE_MIN_AGE_SECS;
+    context.set_sysvar(&clock);
+
+    let ix = instructions::archive_dream(&dreamer.pubkey(), 0);
+    let archived = process(context, &[ix], &[&dreamer]).await;
+    let stub = pda::find_archived_dream_address(&record).0;
+    let (stub_rent, _) = lamports(context, &stub).await;
+
+    json!({
+        "tags": tag_count,
+        "record_bytes": record_bytes,
+        "account": {
+            "compute_units": recorded,
+            "rent_lamports": record_rent,
+        },
+        "compressed": {
+            "compute_units": recorded + archived,
+            "rent_lamports": stub_rent,
+        },
+    })
+}
+
+async fn run() -> Value {
+    let mut program_test = ProgramTest::new("dream_mind_lucid", dream_mind_lucid::ID, None);
+    program_test.prefer_bpf(true);
+    let mut context = program_test.start_with_context().await;
+    let (dream_mint, oracle) = setup(&mut context).await;
+    let (tree_rent, _) = lamports(&mut context, &pda::find_dream_archive_tree_address().0).await;
+
+    let mut cases = Vec::new();
+    for tag_count in TAG_COUNTS {
+        cases.push(measure(&mut context, &dream_mint, &oracle, tag_count).await);
+    }
+    json!({
+        // Paid once and shared by every compressed dream
+        "archive_tree_rent_lamports": tree_rent,
+        "cases": cases,
+    })
+}
+
+fn main() {
+    let results = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(run());
+    println!("{}", serde_json::to_string_pretty(&results).unwrap());
+}
@@ -0,0 +1,146 @@
+//! CPI into the Wormhole core bridge. Outbound DREAM transfers and mint
+//! attestations are posted as plain core messages; inbound messages arrive as
+//! `PostedVAA` accounts that the core bridge has already verified.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::{invoke, invoke_signed},
+        system_instruction,
+    },
+};
+use dream_mind_common::DreamError;
+use dream_mind_state::{
+    wormhole::{
+        find_posted_vaa_address, PostedVaa, BRIDGE_FEE_OFFSET, POST_MESSAGE_INSTRUCTION,
+        WORMHOLE_CORE_BRIDGE_ID,
+    },
+    ForeignEmitter,
+};
+
+/// Guardians sign only once the Solana block is finalized.
+const CONSISTENCY_LEVEL_FINALIZED: u8 = 1;
+
+#[derive(AnchorSerialize)]
+struct PostMessageData {
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: u8,
+}
+
+pub struct PostMessageAccounts<'a, 'info> {
+    pub bridge: &'a AccountInfo<'info>,
+    pub message: &'a AccountInfo<'info>,
+    pub emitter: &'a AccountInfo<'info>,
+    pub sequence: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub fee_collector: &'a AccountInfo<'info>,
+    pub clock: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Sequence the emitter's next message will be assigned (0 before its first message).
+pub fn next_sequence(sequence: &AccountInfo) -> Result<u64> {
+    let data = sequence.try_borrow_data()?;
+    Ok(match data.get(..8) {
+        Some(bytes) => u64::from_le_bytes(bytes.try_into().unwrap()),
+        None => 0,
+    })
+}
+
+/// Pay the core bridge fee and post `payload`. `signer_seeds` must cover the
+/// emitter PDA; the message account signs the transaction itself.
+pub fn post_message(
+    accounts: &PostMessageAccounts,
+    nonce: u32,
+    payload: Vec<u8>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let fee = {
+        let data = accounts.bridge.try_borrow_data()?;
+        let bytes = data
+            .get(BRIDGE_FEE_OFFSET..BRIDGE_FEE_OFFSET + 8)
+            .ok_or(DreamError::InvalidBridgeMessage)?;
+        u64::from_le_bytes(bytes.try_into().unwrap())
+    };
+    if fee > 0 {
+        invoke(
+            &system_instruction::transfer(accounts.payer.key, accounts.fee_collector.key, fee),
+            &[
+                accounts.payer.clone(),
+                accounts.fee_collector.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+    }
+
+    let mut data = vec![POST_MESSAGE_INSTRUCTION];
+    PostMessageData {
+        nonce,
+        payload,
+        consistency_level: CONSISTENCY_LEVEL_FINALIZED,
+    }
+    .serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: WORMHOLE_CORE_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(*accounts.bridge.key, false),
+            AccountMeta::new(*accounts.message.key, true),
+            AccountMeta::new_readonly(*accounts.emitter.key, true),
+            AccountMeta::new(*accounts.sequence.key, false),
+            AccountMeta::new(*accounts.payer.key, true),
+            AccountMeta::new(*accounts.fee_collector.key, false),
+            AccountMeta::new_readonly(*accounts.clock.key, false),
+            AccountMeta::new_readonly(*accounts.rent.key, false),
+            AccountMeta::new_readonly(*accounts.system_program.key, false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.bridge.clone(),
+            accounts.message.clone(),
+            accounts.emitter.clone(),
+            accounts.sequence.clone(),
+            accounts.payer.clone(),
+            accounts.fee_collector.clone(),
+            accounts.clock.clone(),
+            accounts.rent.clone(),
+            accounts.system_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Load a `PostedVAA` and check it is the canonical account for its body and
+/// was emitted by `foreign_emitter` with the expected chain and sequence.
+/// The account owner is checked by the caller's account constraints.
+pub fn verify_posted_vaa(
+    posted_vaa: &AccountInfo,
+    foreign_emitter: &ForeignEmitter,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Result<PostedVaa> {
+    let vaa = PostedVaa::try_from_account_data(&posted_vaa.try_borrow_data()?)
+        .ok_or(DreamError::InvalidBridgeMessage)?;
+    require_keys_eq!(
+        find_posted_vaa_address(&vaa.body_hash()).0,
+        posted_vaa.key(),
+        DreamError::InvalidBridgeMessage
+    );
+    require!(
+        vaa.emitter_chain == emitter_chain && vaa.sequence == sequence,
+        DreamError::InvalidBridgeMessage
+    );
+    require!(
+        vaa.emitter_address == foreign_emitter.address,
+        DreamError::InvalidForeignEmitter
+    );
+    Ok(vaa)
+}
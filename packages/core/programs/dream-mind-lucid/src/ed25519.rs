@@ -0,0 +1,78 @@
+//! Checks that the instruction before the current one is an ed25519
+//! precompile verification of a signature over the expected message, as
+//! `confirm_pinned` and `classify_dream` (oracle attestations) and
+//! `record_dream_delegated` (dreamer signatures) require, or of several
+//! signatures over one message for an oracle quorum. The precompile fails the
+//! whole transaction on a bad signature, so what is left is making sure it
+//! verified the right keys and message.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+use dream_mind_common::DreamError;
+
+/// `num_signatures` and padding.
+const HEADER_LEN: usize = 2;
+/// One `Ed25519SignatureOffsets`: seven u16s.
+const OFFSETS_LEN: usize = 7 * 2;
+/// Offsets index: the data is in the precompile instruction itself.
+const THIS_INSTRUCTION: u16 = u16::MAX;
+
+/// Fails with `error` unless the previous instruction verified `signer`'s
+/// signature over `message`, and nothing else.
+pub fn verify_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+    error: DreamError,
+) -> Result<()> {
+    let signers = verified_signers(instructions_sysvar, message, error)?;
+    if signers != [*signer] {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Keys whose signatures over `message` the previous instruction verified,
+/// in order. A quorum signs the same message in one precompile instruction,
+/// so one transaction carries every signature. Fails with `error` if any
+/// signature covers another message or its data lives outside the
+/// precompile instruction.
+pub fn verified_signers(
+    instructions_sysvar: &AccountInfo,
+    message: &[u8],
+    error: DreamError,
+) -> Result<Vec<Pubkey>> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    let previous = current.checked_sub(1).ok_or(error)?;
+    let ix = load_instruction_at_checked(usize::from(previous), instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, error);
+
+    let data = &ix.data;
+    let count = usize::from(*data.first().ok_or(error)?);
+    if count == 0 || data.len() < HEADER_LEN + count * OFFSETS_LEN {
+        return Err(error.into());
+    }
+    let read = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let slice = |offset: u16, len: usize| data.get(usize::from(offset)..usize::from(offset) + len);
+    (0..count)
+        .map(|index| {
+            let start = HEADER_LEN + index * OFFSETS_LEN;
+            let (signature_ix, public_key_offset, public_key_ix) =
+                (read(start + 2), read(start + 4), read(start + 6));
+            let (message_offset, message_len, message_ix) =
+                (read(start + 8), read(start + 10), read(start + 12));
+            if [signature_ix, public_key_ix, message_ix] != [THIS_INSTRUCTION; 3]
+                || slice(message_offset, usize::from(message_len)) != Some(message)
+            {
+                return Err(error.into());
+            }
+            let public_key = slice(public_key_offset, 32).ok_or(error)?;
+            Ok(Pubkey::try_from(public_key).map_err(|_| error)?)
+        })
+        .collect()
+}
@@ -1,20 +1,60 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{self, Token2022, TokenAccount, Mint};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::{
+    address_lookup_table, bpf_loader_upgradeable, sysvar::instructions::get_instruction_relative,
+};
+use anchor_spl::token_2022::{self, spl_token_2022, Token2022};
+use anchor_spl::token_2022_extensions::{
+    non_transferable_mint_initialize, permanent_delegate_initialize,
+    NonTransferableMintInitialize, PermanentDelegateInitialize,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, AssociatedToken};
+use dream_mind_common::constants::*;
+use dream_mind_macros::traced;
+use dream_mind_state::legacy::{DreamStorage, DreamerProfileV1, LucidStakeV1, LEGACY_PROGRAM_ID};
+use dream_mind_state::cid::{
+    classification_attestation_message, dead_cid_attestation_message, delegated_dream_message,
+    pin_attestation_message,
+};
+use dream_mind_state::messaging::GovernanceAction;
+use dream_mind_state::oneirobot::OneirobotAttributes;
+use dream_mind_state::views::{DreamerStats, ProgramCapabilities, StakeInfo, TreasuryStats};
+use dream_mind_state::wormhole::{
+    fixed_bytes32, BridgeAttestation, BridgeTransfer, DreamMirror, WORMHOLE_CORE_BRIDGE_ID,
+};
 
-declare_id!("5oDxEKGa78LjcE9zMFqz1vLLgKYj4Drd6k1Vq2GJ6YNm");
+mod bridge;
+mod ed25519;
+mod lookup_table;
+mod splice;
+mod trace;
+mod upgrade;
+
+pub use dream_mind_common::DreamError;
+pub use dream_mind_state::*;
 
-// Token supply constants (from project spec)
-const DREAM_TOTAL_SUPPLY: u64 = 777_777_777 * 1_000_000_000; // 777,777,777 DREAM with 9 decimals
-const SMIND_TOTAL_SUPPLY: u64 = 777_777_777 * 1_000_000_000; // 777,777,777 SMIND with 9 decimals  
-const LUCID_TOTAL_SUPPLY: u64 = 333_333_333 * 1_000_000_000; // 333,333,333 LUCID with 9 decimals
+#[cfg(feature = "mainnet")]
+declare_id!("5oDxEKGa78LjcE9zMFqz1vLLgKYj4Drd6k1Vq2GJ6YNm");
+#[cfg(not(feature = "mainnet"))]
+declare_id!("AXDRuY5MwRGJ4HfxMgyARhC1XxoMKgCXGhnVTWTfMeHc");
 
-const DREAM_REWARD_PER_RECORD: u64 = 10 * 1_000_000_000; // 10 DREAM tokens per dream record
+#[cfg(not(feature = "no-entrypoint"))]
+solana_security_txt::security_txt! {
+    name: "Dream-Mind-Lucid",
+    project_url: "https://github.com/imfromfuture3000-Android/Dream-mind-lucid",
+    contacts: "link:https://github.com/imfromfuture3000-Android/Dream-mind-lucid/security/advisories/new",
+    policy: "https://github.com/imfromfuture3000-Android/Dream-mind-lucid/blob/main/SECURITY.md",
+    source_code: "https://github.com/imfromfuture3000-Android/Dream-mind-lucid"
+}
 
+#[traced]
 #[program]
 pub mod dream_mind_lucid {
     use super::*;
 
+    /// Creates the treasury for existing DREAM, SMIND and LUCID mints, whose
+    /// mint authority must already be the treasury PDA.
     pub fn initialize_tokens(ctx: Context<InitializeTokens>) -> Result<()> {
         msg!("Initializing Dream-Mind-Lucid token ecosystem");
         
@@ -27,236 +67,9512 @@ pub mod dream_mind_lucid {
         treasury.total_dreams_recorded = 0;
         treasury.total_rewards_distributed = 0;
         treasury.mev_protection_enabled = true;
+        treasury.bump = ctx.bumps.treasury;
         
         msg!("Token ecosystem initialized with treasury: {}", treasury.authority);
         Ok(())
     }
 
-    pub fn record_dream(ctx: Context<RecordDream>, dream_content_hash: [u8; 32]) -> Result<()> {
+    /// Create the three Token-2022 mints (treasury PDA as mint and freeze
+    /// authority), the treasury, and its ATAs in one transaction. Alternative
+    /// to `initialize_tokens` for fresh deployments.
+    pub fn bootstrap(ctx: Context<Bootstrap>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.authority = ctx.accounts.authority.key();
+        treasury.dream_mint = ctx.accounts.dream_mint.key();
+        treasury.smind_mint = ctx.accounts.smind_mint.key();
+        treasury.lucid_mint = ctx.accounts.lucid_mint.key();
+        treasury.total_dreams_recorded = 0;
+        treasury.total_rewards_distributed = 0;
+        treasury.mev_protection_enabled = true;
+        treasury.bump = ctx.bumps.treasury;
+        // A fresh treasury owes nothing
+        ctx.accounts.treasury_health.healthy = true;
+
+        msg!("Bootstrapped token ecosystem; DREAM mint: {}", treasury.dream_mint);
+        Ok(())
+    }
+
+    /// Mint `amount` DREAM into the reward pool vault, creating the pool on
+    /// first use. The only instruction that mints DREAM, capped at
+    /// `MAX_REWARD_POOL_TOP_UP_PER_PERIOD` per period so emissions can be
+    /// audited from the pool's history.
+    pub fn top_up_pool(ctx: Context<TopUpPool>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.reward_pool;
+        let period_start = RewardPool::period_of(now);
+        if pool.period_start != period_start {
+            pool.period_start = period_start;
+            pool.period_minted = 0;
+        }
+        require!(
+            pool.period_minted + amount <= MAX_REWARD_POOL_TOP_UP_PER_PERIOD,
+            DreamError::RewardPoolCapExceeded
+        );
+        require!(
+            ctx.accounts.dream_mint.supply + amount <= DREAM_TOTAL_SUPPLY,
+            DreamError::MaxSupplyReached
+        );
+        pool.period_minted += amount;
+        pool.total_minted += amount;
+
+        let cpi_accounts = token_2022::MintTo {
+            mint: ctx.accounts.dream_mint.to_account_info(),
+            to: ctx.accounts.reward_pool_vault.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::mint_to(cpi_ctx, amount)?;
+        ctx.accounts.reward_pool_vault.reload()?;
+
+        let pool = &ctx.accounts.reward_pool;
+        emit!(RewardPoolToppedUpEvent {
+            amount,
+            period_start: pool.period_start,
+            period_minted: pool.period_minted,
+            total_minted: pool.total_minted,
+            pool_balance: ctx.accounts.reward_pool_vault.amount,
+        });
+        Ok(())
+    }
+
+    /// Mint the caller `FAUCET_DREAM_AMOUNT`, `FAUCET_SMIND_AMOUNT` and
+    /// `FAUCET_LUCID_AMOUNT`, at most once per `FAUCET_COOLDOWN_SECS` per
+    /// wallet, so tests and builders need no treasury key. Fails unless the
+    /// program is built with the `devnet` feature.
+    pub fn faucet_mint(ctx: Context<FaucetMint>) -> Result<()> {
+        require!(cfg!(feature = "devnet"), DreamError::FaucetDisabled);
+        let now = Clock::get()?.unix_timestamp;
+        let faucet_claim = &mut ctx.accounts.faucet_claim;
+        require!(now >= faucet_claim.next_claim_at(), DreamError::FaucetCooldown);
+        faucet_claim.wallet = ctx.accounts.wallet.key();
+        faucet_claim.claims += 1;
+        faucet_claim.last_claimed_at = now;
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let accounts = &ctx.accounts;
+        let grants = [
+            (&accounts.dream_mint, &accounts.wallet_dream_account, FAUCET_DREAM_AMOUNT),
+            (&accounts.smind_mint, &accounts.wallet_smind_account, FAUCET_SMIND_AMOUNT),
+            (&accounts.lucid_mint, &accounts.wallet_lucid_account, FAUCET_LUCID_AMOUNT),
+        ];
+        let max_supplies = [DREAM_TOTAL_SUPPLY, SMIND_TOTAL_SUPPLY, LUCID_TOTAL_SUPPLY];
+        for ((mint, to, amount), max_supply) in grants.into_iter().zip(max_supplies) {
+            require!(mint.supply + amount <= max_supply, DreamError::MaxSupplyReached);
+            let cpi_accounts = token_2022::MintTo {
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: accounts.treasury.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_2022::mint_to(cpi_ctx, amount)?;
+        }
+
+        emit!(FaucetMintedEvent {
+            wallet: accounts.wallet.key(),
+            claims: accounts.faucet_claim.claims,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// `co_dreamers` (at most `MAX_CO_DREAMERS`) share the reward equally
+    /// with the recorder once each confirms; pass `dream_coauthors` and
+    /// `treasury_health` with them. An `interpreter` is held back their
+    /// `INTERPRETER_SHARE_BPS` of the reward until validation, before the
+    /// co-dreamers' shares; pass `interpretation` and `treasury_health`.
+    pub fn record_dream(
+        ctx: Context<RecordDream>,
+        dream_content_hash: [u8; 32],
+        metadata: DreamMetadata,
+        co_dreamers: Vec<Pubkey>,
+        idempotency_key: Option<u64>,
+        interpreter: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            metadata.lucidity_score <= MAX_LUCIDITY_SCORE
+                && metadata.sleep_duration_minutes <= MAX_SLEEP_DURATION_MINUTES,
+            DreamError::InvalidDreamMetadata
+        );
+        require!(
+            idempotency_key.is_some() == ctx.accounts.idempotency_nonce.is_some(),
+            DreamError::InvalidIdempotencyKey
+        );
+        let fee = ctx.accounts.fee_vault.record_dream_fee;
+        if fee > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, fee)?;
+        }
+        let dreamer = ctx.accounts.dreamer.key();
+        require!(
+            co_dreamers.len() <= MAX_CO_DREAMERS
+                && co_dreamers.is_empty() == ctx.accounts.dream_coauthors.is_none()
+                && co_dreamers
+                    .iter()
+                    .enumerate()
+                    .all(|(i, co)| *co != dreamer && !co_dreamers[..i].contains(co)),
+            DreamError::InvalidCoDreamers
+        );
+        
         let treasury = &mut ctx.accounts.treasury;
         let dream_record = &mut ctx.accounts.dream_record;
         let clock = Clock::get()?;
         
+        // Reputation decays toward neutral from the dreamer's last dream, and
+        // its band scales the reward
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        dreamer_profile.reputation =
+            dreamer_profile.reputation_on(EpochStats::day_of(clock.unix_timestamp));
+        let reward_bps = DreamerProfile::band_reward_bps(dreamer_profile.reputation);
+        
+        // Dreams past the day's session cap are still recorded, without a reward
+        let session = &mut ctx.accounts.sleep_session;
+        session.start(dreamer, EpochStats::day_of(clock.unix_timestamp), clock.unix_timestamp);
+        let reward = session.count_dream(DREAM_REWARD_PER_RECORD * u64::from(reward_bps) / 10_000);
+        dream_record.session = session.key();
+        
+        // Hold back the interpreter's share until the dream is validated
+        let mut dreamer_reward = reward;
+        match (interpreter, ctx.accounts.interpretation.as_mut()) {
+            (Some(interpreter), Some(interpretation)) => {
+                require_keys_neq!(interpreter, dreamer, DreamError::InvalidInterpreter);
+                let held = DreamInterpretation::interpreter_share(reward);
+                dreamer_reward -= held;
+                interpretation.dream_record = dream_record.key();
+                interpretation.interpreter = interpreter;
+                interpretation.assigned_at = clock.unix_timestamp;
+                let health = ctx
+                    .accounts
+                    .treasury_health
+                    .as_mut()
+                    .ok_or(DreamError::TreasuryHealthRequired)?;
+                health.pending_coauthor_shares += held;
+                emit!(InterpreterAssignedEvent {
+                    dream_record: interpretation.dream_record,
+                    dreamer,
+                    interpreter,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            (None, None) => {}
+            _ => return err!(DreamError::InvalidInterpreter),
+        }
+        
+        // Hold back an equal share of the rest for each co-dreamer until they
+        // confirm
+        if let Some(coauthors) = ctx.accounts.dream_coauthors.as_mut() {
+            let share = dreamer_reward / (co_dreamers.len() as u64 + 1);
+            dreamer_reward -= share * co_dreamers.len() as u64;
+            coauthors.dream_record = dream_record.key();
+            coauthors.dreamer = dreamer;
+            coauthors.count = co_dreamers.len() as u8;
+            coauthors.co_dreamers[..co_dreamers.len()].copy_from_slice(&co_dreamers);
+            coauthors.share = share;
+            coauthors.recorded_at = clock.unix_timestamp;
+            let health = ctx
+                .accounts
+                .treasury_health
+                .as_mut()
+                .ok_or(DreamError::TreasuryHealthRequired)?;
+            health.pending_coauthor_shares += share * co_dreamers.len() as u64;
+        }
+        
         // Record dream metadata
         dream_record.dreamer = ctx.accounts.dreamer.key();
         dream_record.content_hash = dream_content_hash;
         dream_record.timestamp = clock.unix_timestamp;
-        dream_record.token_reward = DREAM_REWARD_PER_RECORD;
+        dream_record.token_reward = reward;
         dream_record.mev_protected = treasury.mev_protection_enabled;
         dream_record.id = treasury.total_dreams_recorded;
+        dream_record.origin_chain = WORMHOLE_CHAIN_ID_SOLANA;
+        dream_record.metadata = metadata;
+        
+        // Stamp the equipped companion while the dreamer still holds it
+        match (
+            ctx.accounts.companion.as_ref(),
+            ctx.accounts.companion_token_account.as_ref(),
+            ctx.accounts.companion_xp.as_mut(),
+            ctx.accounts.companion_stamp.as_mut(),
+        ) {
+            (Some(companion), Some(token_account), Some(companion_xp), Some(stamp)) => {
+                require!(
+                    token_account.mint == companion.mint
+                        && token_account.owner == dreamer
+                        && token_account.amount == 1,
+                    DreamError::CompanionNotHeld
+                );
+                require_keys_eq!(
+                    companion_xp.mint,
+                    companion.mint,
+                    DreamError::InvalidOneirobotAccounts
+                );
+                companion_xp.xp += COMPANION_XP_PER_DREAM;
+                companion_xp.dreams_recorded += 1;
+                stamp.dream_record = dream_record.key();
+                stamp.mint = companion.mint;
+            }
+            (None, None, None, None) => {}
+            _ => return err!(DreamError::InvalidOneirobotAccounts),
+        }
+        
+        let idempotency_nonce = ctx.accounts.idempotency_nonce.as_mut();
+        if let (Some(nonce), Some(key)) = (idempotency_nonce, idempotency_key) {
+            nonce.dreamer = dreamer;
+            nonce.key = key;
+            nonce.payer = ctx.accounts.payer.key();
+            nonce.dream_record = dream_record.key();
+            nonce.created_at = clock.unix_timestamp;
+        }
         
         // Update treasury stats
         treasury.total_dreams_recorded += 1;
-        treasury.total_rewards_distributed += DREAM_REWARD_PER_RECORD;
+        treasury.total_rewards_distributed += dreamer_reward;
         
-        // Mint DREAM tokens as reward (implementation via CPI to token program)
-        let cpi_accounts = token_2022::MintTo {
-            mint: ctx.accounts.dream_mint.to_account_info(),
-            to: ctx.accounts.dreamer_dream_account.to_account_info(),
-            authority: ctx.accounts.treasury.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let epoch_stats = &mut ctx.accounts.epoch_stats;
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        epoch_stats.day = EpochStats::day_of(clock.unix_timestamp);
+        epoch_stats.dream_count += 1;
+        epoch_stats.rewards_minted += dreamer_reward;
+        if dreamer_profile.last_active_day != epoch_stats.day {
+            epoch_stats.unique_dreamers += 1;
+            dreamer_profile.last_active_day = epoch_stats.day;
+        }
+        dreamer_profile.set_dreamer(ctx.accounts.dreamer.key(), clock.unix_timestamp);
+        
+        let season_score = &mut ctx.accounts.season_score;
+        if season_score.season != treasury.season {
+            season_score.season = treasury.season;
+            season_score.dream_count = 0;
+        }
+        season_score.dreamer = ctx.accounts.dreamer.key();
+        season_score.dream_count += 1;
         
-        token_2022::mint_to(cpi_ctx, DREAM_REWARD_PER_RECORD)?;
+        // Queue for validation, ranked by the dreamer's LUCID stake
+        if let Some(queue) = &ctx.accounts.validation_queue {
+            let stake = ctx.accounts.lucid_stake.as_ref().map_or(0, |stake| stake.amount);
+            let queued = queue.load_mut()?.push(ValidationQueueEntry {
+                dream_record: dream_record.key(),
+                stake,
+                enqueued_at: clock.unix_timestamp,
+            });
+            if !queued {
+                msg!("Validation queue full; dream {} not queued", dream_record.id);
+            }
+        }
         
-        msg!("Dream recorded! ID: {}, Reward: {} DREAM", dream_record.id, DREAM_REWARD_PER_RECORD / 1_000_000_000);
+        // Route the dreamer's chosen share to their charity while it stays approved
+        let mut donation = 0;
+        if let (Some(split), Some(charity), Some(charity_account)) = (
+            ctx.accounts.charity_split.as_mut(),
+            ctx.accounts.charity.as_mut(),
+            ctx.accounts.charity_dream_account.as_ref(),
+        ) {
+            require!(
+                charity.wallet == split.charity && charity_account.owner == charity.wallet,
+                DreamError::InvalidCharity
+            );
+            if charity.approved {
+                donation = dreamer_reward * u64::from(split.share_bps) / 10_000;
+                split.total_donated += donation;
+                charity.total_received += donation;
+            }
+            if donation > 0 {
+                emit!(CharityDonationEvent {
+                    dreamer: split.dreamer,
+                    charity: charity.wallet,
+                    dream_id: dream_record.id,
+                    amount: donation,
+                    total_donated: split.total_donated,
+                });
+            }
+        }
+        
+        // Pay the DREAM reward out of the pre-minted reward pool
+        require!(
+            ctx.accounts.reward_pool_vault.amount >= dreamer_reward,
+            DreamError::RewardPoolDepleted
+        );
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let decimals = ctx.accounts.dream_mint.decimals;
+        let dreamer_account = ctx.accounts.dreamer_dream_account.to_account_info();
+        let mut payouts = vec![(dreamer_account, dreamer_reward - donation)];
+        if let Some(charity_account) = &ctx.accounts.charity_dream_account {
+            payouts.push((charity_account.to_account_info(), donation));
+        }
+        for (to, amount) in payouts {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.reward_pool_vault.to_account_info(),
+                mint: ctx.accounts.dream_mint.to_account_info(),
+                to,
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_2022::transfer_checked(cpi_ctx, amount, decimals)?;
+        }
+        
+        emit!(DreamRecordedEvent {
+            id: dream_record.id,
+            dreamer: dream_record.dreamer,
+            content_hash: dream_record.content_hash,
+            token_reward: dream_record.token_reward,
+            mev_protected: dream_record.mev_protected,
+            timestamp: dream_record.timestamp,
+            metadata,
+        });
+        
+        msg!("Dream recorded! ID: {}, Reward: {} DREAM", dream_record.id, reward / TOKEN_UNIT);
         Ok(())
     }
 
-    pub fn interface_dream(ctx: Context<InterfaceDream>, ipfs_hash: String) -> Result<()> {
-        let dream_interface = &mut ctx.accounts.dream_interface;
+    /// Permissionless: close an idempotency nonce past its TTL, refunding the
+    /// payer. A retry after this records the dream again.
+    pub fn close_idempotency_nonce(ctx: Context<CloseIdempotencyNonce>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp
+                >= ctx.accounts.idempotency_nonce.created_at + IDEMPOTENCY_NONCE_TTL_SECS,
+            DreamError::IdempotencyNonceLive
+        );
+        Ok(())
+    }
+
+    /// Record a dream for a dreamer who signed it off-chain, with the relayer
+    /// paying the fee and rent. The previous instruction must be an ed25519
+    /// verification of the dreamer's signature over `delegated_dream_message`,
+    /// unless the relayer is one of the dreamer's session keys scoped for
+    /// recording; `nonce` is then ignored. Relayed dreams count against the
+    /// day's sleep session and take no charity split, queue slot or
+    /// co-dreamers.
+    pub fn record_dream_delegated(
+        ctx: Context<RecordDreamDelegated>,
+        dream_content_hash: [u8; 32],
+        metadata: DreamMetadata,
+        nonce: u64,
+    ) -> Result<()> {
+        require!(
+            metadata.lucidity_score <= MAX_LUCIDITY_SCORE
+                && metadata.sleep_duration_minutes <= MAX_SLEEP_DURATION_MINUTES,
+            DreamError::InvalidDreamMetadata
+        );
+        let dreamer = ctx.accounts.dreamer.key();
         let clock = Clock::get()?;
-        
-        dream_interface.dreamer = ctx.accounts.dreamer.key();
-        dream_interface.ipfs_hash = ipfs_hash;
-        dream_interface.timestamp = clock.unix_timestamp;
-        dream_interface.access_level = 1; // Basic access
-        
-        msg!("Dream interfaced via IPFS: {}", dream_interface.ipfs_hash);
+        let relay_nonce = &mut ctx.accounts.relay_nonce;
+        relay_nonce.dreamer = dreamer;
+        if let Some(session_key) = &ctx.accounts.session_key {
+            require!(
+                session_key.allows(SESSION_SCOPE_RECORD_DREAM, clock.unix_timestamp),
+                DreamError::SessionKeyNotAuthorized
+            );
+        } else {
+            require!(nonce == relay_nonce.nonce, DreamError::InvalidRelayNonce);
+            ed25519::verify_signature(
+                &ctx.accounts.instructions_sysvar,
+                &dreamer,
+                &delegated_dream_message(&dream_content_hash, &metadata, nonce),
+                DreamError::InvalidDreamerSignature,
+            )?;
+            relay_nonce.nonce += 1;
+        }
+
+        let fee = ctx.accounts.fee_vault.record_dream_fee;
+        if fee > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.relayer.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, fee)?;
+        }
+
+        let treasury = &mut ctx.accounts.treasury;
+        let dream_record = &mut ctx.accounts.dream_record;
+        let day = EpochStats::day_of(clock.unix_timestamp);
+
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        dreamer_profile.reputation = dreamer_profile.reputation_on(day);
+        let reward_bps = DreamerProfile::band_reward_bps(dreamer_profile.reputation);
+        let session = &mut ctx.accounts.sleep_session;
+        session.start(dreamer, day, clock.unix_timestamp);
+        let reward = session.count_dream(DREAM_REWARD_PER_RECORD * u64::from(reward_bps) / 10_000);
+
+        dream_record.dreamer = dreamer;
+        dream_record.content_hash = dream_content_hash;
+        dream_record.timestamp = clock.unix_timestamp;
+        dream_record.token_reward = reward;
+        dream_record.mev_protected = treasury.mev_protection_enabled;
+        dream_record.id = treasury.total_dreams_recorded;
+        dream_record.origin_chain = WORMHOLE_CHAIN_ID_SOLANA;
+        dream_record.session = session.key();
+        dream_record.metadata = metadata;
+
+        treasury.total_dreams_recorded += 1;
+        treasury.total_rewards_distributed += reward;
+
+        let epoch_stats = &mut ctx.accounts.epoch_stats;
+        epoch_stats.day = day;
+        epoch_stats.dream_count += 1;
+        epoch_stats.rewards_minted += reward;
+        if dreamer_profile.last_active_day != day {
+            epoch_stats.unique_dreamers += 1;
+            dreamer_profile.last_active_day = day;
+        }
+        dreamer_profile.set_dreamer(dreamer, clock.unix_timestamp);
+
+        let season_score = &mut ctx.accounts.season_score;
+        if season_score.season != treasury.season {
+            season_score.season = treasury.season;
+            season_score.dream_count = 0;
+        }
+        season_score.dreamer = dreamer;
+        season_score.dream_count += 1;
+
+        require!(
+            ctx.accounts.reward_pool_vault.amount >= reward,
+            DreamError::RewardPoolDepleted
+        );
+        if reward > 0 {
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.reward_pool_vault.to_account_info(),
+                mint: ctx.accounts.dream_mint.to_account_info(),
+                to: ctx.accounts.dreamer_dream_account.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+            let signer_seeds = &[treasury_seeds];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_2022::transfer_checked(cpi_ctx, reward, ctx.accounts.dream_mint.decimals)?;
+        }
+
+        emit!(DreamRecordedEvent {
+            id: dream_record.id,
+            dreamer,
+            content_hash: dream_record.content_hash,
+            token_reward: dream_record.token_reward,
+            mev_protected: dream_record.mev_protected,
+            timestamp: dream_record.timestamp,
+            metadata,
+        });
+        emit!(DreamRelayedEvent {
+            dream_record: dream_record.key(),
+            dreamer,
+            relayer: ctx.accounts.relayer.key(),
+            nonce,
+        });
+
+        msg!("Dream relayed! ID: {}, Reward: {} DREAM", dream_record.id, reward / TOKEN_UNIT);
         Ok(())
     }
 
-    pub fn stake_for_lucid_access(ctx: Context<StakeLucid>, amount: u64) -> Result<()> {
-        let stake_account = &mut ctx.accounts.stake_account;
+    /// Record up to `MAX_DREAMS_PER_BATCH` dreams in one instruction, e.g.
+    /// several nights synced from a journal at once. Pass each dream's record
+    /// PDA as remaining accounts, with ids counting up from the treasury's
+    /// `total_dreams_recorded`. Batched dreams count against the day's sleep
+    /// session and take no charity split, queue slot or co-dreamers.
+    pub fn record_dreams_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordDreamsBatch<'info>>,
+        dreams: Vec<BatchedDream>,
+    ) -> Result<()> {
+        let records = ctx.remaining_accounts;
+        require!(
+            !dreams.is_empty()
+                && dreams.len() <= MAX_DREAMS_PER_BATCH
+                && records.len() == dreams.len(),
+            DreamError::InvalidDreamBatch
+        );
+        require!(
+            dreams.iter().all(|dream| {
+                dream.metadata.lucidity_score <= MAX_LUCIDITY_SCORE
+                    && dream.metadata.sleep_duration_minutes <= MAX_SLEEP_DURATION_MINUTES
+            }),
+            DreamError::InvalidDreamMetadata
+        );
+        let count = dreams.len() as u64;
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let fee = ctx.accounts.fee_vault.record_dream_fee * count;
+        if fee > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: payer.clone(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+            system_program::transfer(cpi_ctx, fee)?;
+        }
+
+        let dreamer = ctx.accounts.dreamer.key();
         let clock = Clock::get()?;
-        
-        // Transfer LUCID tokens to stake account
-        let cpi_accounts = token_2022::Transfer {
-            from: ctx.accounts.user_lucid_account.to_account_info(),
-            to: ctx.accounts.lucid_stake_vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
+        let day = EpochStats::day_of(clock.unix_timestamp);
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        dreamer_profile.reputation = dreamer_profile.reputation_on(day);
+        let reward_bps = DreamerProfile::band_reward_bps(dreamer_profile.reputation);
+        let full_reward = DREAM_REWARD_PER_RECORD * u64::from(reward_bps) / 10_000;
+        let session = &mut ctx.accounts.sleep_session;
+        session.start(dreamer, day, clock.unix_timestamp);
+
+        let treasury = &mut ctx.accounts.treasury;
+        let first_id = treasury.total_dreams_recorded;
+        let mut total_reward = 0;
+        for (id, (dream, record)) in (first_id..).zip(dreams.iter().zip(records)) {
+            create_dream_record(record, &payer, &system_program, &dreamer, id)?;
+            let reward = session.count_dream(full_reward);
+            total_reward += reward;
+            let dream_record = DreamRecord {
+                id,
+                dreamer,
+                content_hash: dream.content_hash,
+                timestamp: clock.unix_timestamp,
+                token_reward: reward,
+                mev_protected: treasury.mev_protection_enabled,
+                origin_chain: WORMHOLE_CHAIN_ID_SOLANA,
+                metadata: dream.metadata,
+                session: session.key(),
+            };
+            dream_record.try_serialize(&mut &mut record.try_borrow_mut_data()?[..])?;
+            emit!(DreamRecordedEvent {
+                id,
+                dreamer,
+                content_hash: dream.content_hash,
+                token_reward: reward,
+                mev_protected: dream_record.mev_protected,
+                timestamp: clock.unix_timestamp,
+                metadata: dream.metadata,
+            });
+        }
+
+        // One update for the whole batch
+        treasury.total_dreams_recorded += count;
+        treasury.total_rewards_distributed += total_reward;
+
+        let epoch_stats = &mut ctx.accounts.epoch_stats;
+        epoch_stats.day = day;
+        epoch_stats.dream_count += count;
+        epoch_stats.rewards_minted += total_reward;
+        if dreamer_profile.last_active_day != day {
+            epoch_stats.unique_dreamers += 1;
+            dreamer_profile.last_active_day = day;
+        }
+        dreamer_profile.set_dreamer(dreamer, clock.unix_timestamp);
+
+        let season_score = &mut ctx.accounts.season_score;
+        if season_score.season != treasury.season {
+            season_score.season = treasury.season;
+            season_score.dream_count = 0;
+        }
+        season_score.dreamer = dreamer;
+        season_score.dream_count += count;
+
+        require!(
+            ctx.accounts.reward_pool_vault.amount >= total_reward,
+            DreamError::RewardPoolDepleted
+        );
+        if total_reward > 0 {
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.reward_pool_vault.to_account_info(),
+                mint: ctx.accounts.dream_mint.to_account_info(),
+                to: ctx.accounts.dreamer_dream_account.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+            let signer_seeds = &[treasury_seeds];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            let decimals = ctx.accounts.dream_mint.decimals;
+            token_2022::transfer_checked(cpi_ctx, total_reward, decimals)?;
+        }
+
+        msg!(
+            "{} dreams recorded! First ID: {}, Reward: {} DREAM",
+            count,
+            first_id,
+            total_reward / TOKEN_UNIT
+        );
+        Ok(())
+    }
+
+    /// Let `session_signer` sign the `scope` actions for the wallet until
+    /// `expires_at`, and move `lamports` to it for transaction fees and rent.
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        scope: u8,
+        expires_at: i64,
+        lamports: u64,
+    ) -> Result<()> {
+        require!(
+            scope != 0 && scope & !SESSION_SCOPES == 0,
+            DreamError::InvalidSessionScope
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            expires_at > now && expires_at - now <= MAX_SESSION_KEY_DURATION_SECS,
+            DreamError::InvalidSessionExpiry
+        );
+        if lamports > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.session_signer.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, lamports)?;
+        }
+
+        let session_key = &mut ctx.accounts.session_key;
+        session_key.authority = ctx.accounts.authority.key();
+        session_key.signer = ctx.accounts.session_signer.key();
+        session_key.scope = scope;
+        session_key.created_at = now;
+        session_key.expires_at = expires_at;
+
+        emit!(SessionKeyCreatedEvent {
+            authority: session_key.authority,
+            signer: session_key.signer,
+            scope,
+            expires_at,
+        });
+        Ok(())
+    }
+
+    /// Close a session key before it expires. Lamports already moved to the
+    /// ephemeral key stay there.
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        emit!(SessionKeyRevokedEvent {
+            authority: ctx.accounts.authority.key(),
+            signer: ctx.accounts.session_key.signer,
+        });
+        Ok(())
+    }
+
+    /// Store `value` once under its content id, so dream tags and quantum
+    /// cores can refer to it by 8-byte id. Anyone can intern a string, and
+    /// interning it again is a no-op.
+    pub fn intern_string(ctx: Context<InternString>, value: String) -> Result<()> {
+        require!(
+            (1..=MAX_INTERNED_STRING_LEN).contains(&value.len()),
+            DreamError::InvalidInternedString
+        );
+        let interned = &mut ctx.accounts.interned_string;
+        if !interned.value.is_empty() {
+            require!(interned.value == value, DreamError::InternedStringCollision);
+            return Ok(());
+        }
+        interned.id = intern::string_id(&value);
+        interned.value = value;
+
+        emit!(StringInternedEvent {
+            id: interned.id,
+            value: interned.value.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Replace the extras stored past the record's fixed fields, growing or
+    /// shrinking the account to fit. The dreamer pays for growth and gets
+    /// rent back when it shrinks. Tags are interned ids; intern each tag's
+    /// string first so readers can resolve it.
+    pub fn set_dream_extras(ctx: Context<SetDreamExtras>, extras: DreamRecordExtras) -> Result<()> {
+        require!(
+            extras.space() <= MAX_DREAM_EXTRAS_LEN && extras.tags.len() <= MAX_DREAM_TAGS,
+            DreamError::DreamExtrasTooLarge
+        );
+        let dream_record = ctx.accounts.dream_record.to_account_info();
+        let mut data = dream_record.try_borrow_mut_data()?;
+        extras.serialize(&mut &mut data[DreamRecord::EXTRAS_OFFSET..])?;
+
+        emit!(DreamExtrasUpdatedEvent {
+            dream_record: dream_record.key(),
+            dreamer: ctx.accounts.dreamer.key(),
+            tag_count: extras.tags.len() as u32,
+            annotation_count: extras.annotations.len() as u32,
+            record_len: data.len() as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Confirm co-authorship of a shared dream: pays the co-dreamer's share
+    /// of the reward, less decay, and credits the dream to their season score.
+    pub fn confirm_coauthorship(ctx: Context<ConfirmCoauthorship>) -> Result<()> {
+        let co_dreamer = ctx.accounts.co_dreamer.key();
+        let now = Clock::get()?.unix_timestamp;
+        let coauthors = &mut ctx.accounts.dream_coauthors;
+        require!(
+            now < coauthors.recorded_at + REWARD_CLAIM_EXPIRY_SECS,
+            DreamError::RewardExpired
+        );
+        let index = coauthors
+            .co_dreamers()
+            .iter()
+            .position(|key| *key == co_dreamer)
+            .ok_or(DreamError::NotACoDreamer)?;
+        require!(!coauthors.confirmed[index], DreamError::CoauthorshipAlreadyConfirmed);
+        coauthors.confirmed[index] = true;
+        let reward = coauthors.claimable(now);
+        let expired = coauthors.share - reward;
+
+        let treasury = &mut ctx.accounts.treasury;
+        let season_score = &mut ctx.accounts.season_score;
+        if season_score.season != treasury.season {
+            season_score.season = treasury.season;
+            season_score.dream_count = 0;
+        }
+        season_score.dreamer = co_dreamer;
+        season_score.dream_count += 1;
+        treasury.total_rewards_distributed += reward;
+        treasury.total_rewards_expired += expired;
+        let health = &mut ctx.accounts.treasury_health;
+        health.pending_coauthor_shares =
+            health.pending_coauthor_shares.saturating_sub(reward + expired);
+
+        if reward > 0 {
+            require!(
+                ctx.accounts.reward_pool_vault.amount >= reward,
+                DreamError::RewardPoolDepleted
+            );
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.reward_pool_vault.to_account_info(),
+                mint: ctx.accounts.dream_mint.to_account_info(),
+                to: ctx.accounts.co_dreamer_dream_account.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+            let signer_seeds = &[treasury_seeds];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_2022::transfer_checked(cpi_ctx, reward, ctx.accounts.dream_mint.decimals)?;
+        }
+
+        emit!(CoauthorshipConfirmedEvent {
+            dream_record: ctx.accounts.dream_coauthors.dream_record,
+            co_dreamer,
+            reward,
+            expired,
+        });
+        Ok(())
+    }
+
+    /// Permissionless crank once a shared dream's claim window has closed:
+    /// forfeits the unconfirmed shares and returns the rent to the dreamer.
+    pub fn expire_coauthor_shares(ctx: Context<ExpireCoauthorShares>) -> Result<()> {
+        let coauthors = &ctx.accounts.dream_coauthors;
+        require!(
+            Clock::get()?.unix_timestamp >= coauthors.recorded_at + REWARD_CLAIM_EXPIRY_SECS,
+            DreamError::RewardNotExpired
+        );
+        let forfeited = coauthors.unconfirmed();
+        ctx.accounts.treasury.total_rewards_expired += forfeited;
+        let health = &mut ctx.accounts.treasury_health;
+        health.pending_coauthor_shares =
+            health.pending_coauthor_shares.saturating_sub(forfeited);
+        if let (Some(pool), Some(cranker)) =
+            (ctx.accounts.crank_pool.as_mut(), ctx.accounts.cranker.as_ref())
+        {
+            pay_crank_bounty(pool, cranker, CrankKind::ExpireCoauthorShares)?;
+        }
+
+        emit!(CoauthorSharesExpiredEvent {
+            dream_record: coauthors.dream_record,
+            forfeited,
+        });
+        Ok(())
+    }
+
+    /// Authority only, standing in for governance. Sets the rates at which
+    /// `claim_reward_as` converts DREAM into SMIND and LUCID, creating the
+    /// account on first use.
+    pub fn set_conversion_rates(
+        ctx: Context<SetConversionRates>,
+        smind_rate_bps: u32,
+        lucid_rate_bps: u32,
+    ) -> Result<()> {
+        require!(
+            smind_rate_bps <= MAX_REWARD_CONVERSION_RATE_BPS
+                && lucid_rate_bps <= MAX_REWARD_CONVERSION_RATE_BPS,
+            DreamError::ConversionRateTooHigh
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let rates = &mut ctx.accounts.conversion_rates;
+        rates.smind_rate_bps = smind_rate_bps;
+        rates.lucid_rate_bps = lucid_rate_bps;
+        rates.updated_at = now;
+
+        emit!(ConversionRatesSetEvent {
+            smind_rate_bps,
+            lucid_rate_bps,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Take `dream_amount` of a dream's DREAM reward as SMIND or LUCID
+    /// instead: the DREAM is burned and the other token minted at the
+    /// governance-set rate. Once per dream, for at most its reward; rewards
+    /// kept as DREAM need no claim.
+    pub fn claim_reward_as(
+        ctx: Context<ClaimRewardAs>,
+        token: RewardToken,
+        dream_amount: u64,
+    ) -> Result<()> {
+        require!(
+            dream_amount > 0 && dream_amount <= ctx.accounts.dream_record.token_reward,
+            DreamError::InvalidClaimAmount
+        );
+        let rate_bps = ctx.accounts.conversion_rates.rate_bps(token);
+        require!(rate_bps > 0, DreamError::ConversionDisabled);
+        let amount_out = dream_amount * u64::from(rate_bps) / 10_000;
+        let max_supply = match token {
+            RewardToken::Smind => SMIND_TOTAL_SUPPLY,
+            RewardToken::Lucid => LUCID_TOTAL_SUPPLY,
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        
-        token_2022::transfer(cpi_ctx, amount)?;
-        
-        stake_account.user = ctx.accounts.user.key();
-        stake_account.amount = amount;
-        stake_account.timestamp = clock.unix_timestamp;
-        stake_account.access_level = calculate_access_level(amount);
-        
-        msg!("LUCID tokens staked: {}, Access level: {}", amount, stake_account.access_level);
+        require!(
+            ctx.accounts.reward_mint.supply + amount_out <= max_supply,
+            DreamError::MaxSupplyReached
+        );
+
+        let cpi_accounts = token_2022::Burn {
+            mint: ctx.accounts.dream_mint.to_account_info(),
+            from: ctx.accounts.dreamer_dream_account.to_account_info(),
+            authority: ctx.accounts.dreamer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::burn(cpi_ctx, dream_amount)?;
+
+        let cpi_accounts = token_2022::MintTo {
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.dreamer_reward_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::mint_to(cpi_ctx, amount_out)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.conversion_rates.total_dream_burned += dream_amount;
+        let claim = &mut ctx.accounts.reward_claim;
+        claim.dream_record = ctx.accounts.dream_record.key();
+        claim.token = token;
+        claim.dream_amount = dream_amount;
+        claim.amount_out = amount_out;
+        claim.claimed_at = now;
+
+        emit!(RewardClaimedEvent {
+            dream_record: claim.dream_record,
+            dreamer: ctx.accounts.dreamer.key(),
+            token,
+            dream_amount,
+            amount_out,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Creates the zero-copy season leaderboard.
+    pub fn initialize_leaderboard(ctx: Context<InitializeLeaderboard>) -> Result<()> {
+        let mut leaderboard = ctx.accounts.leaderboard.load_init()?;
+        leaderboard.reset(ctx.accounts.treasury.season, Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    /// Authority only. Empties the leaderboard; season scores restart at each
+    /// dreamer's next dream.
+    pub fn start_season(ctx: Context<StartSeason>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.season += 1;
+        let started_at = Clock::get()?.unix_timestamp;
+        ctx.accounts.leaderboard.load_mut()?.reset(treasury.season, started_at);
+
+        emit!(SeasonStartedEvent {
+            season: treasury.season,
+            started_at,
+        });
+        Ok(())
+    }
+
+    /// Permissionless crank: move a dreamer's current season score onto the leaderboard.
+    pub fn update_leaderboard(ctx: Context<UpdateLeaderboard>) -> Result<()> {
+        let season_score = &ctx.accounts.season_score;
+        require!(
+            season_score.season == ctx.accounts.treasury.season,
+            DreamError::SeasonMismatch
+        );
+        let updated = ctx
+            .accounts
+            .leaderboard
+            .load_mut()?
+            .update(season_score.dreamer, season_score.dream_count);
+        // Only a crank that moved the board earns the bounty
+        if let (true, Some(pool), Some(cranker)) =
+            (updated, ctx.accounts.crank_pool.as_mut(), ctx.accounts.cranker.as_ref())
+        {
+            pay_crank_bounty(pool, cranker, CrankKind::UpdateLeaderboard)?;
+        }
+        Ok(())
+    }
+
+    /// Authority only. Creates the empty stats ring.
+    pub fn initialize_stats_ring(ctx: Context<InitializeStatsRing>) -> Result<()> {
+        ctx.accounts.stats_ring.load_init()?;
+        Ok(())
+    }
+
+    /// Permissionless crank: snapshot the treasury totals for today into the
+    /// stats ring. Once per day.
+    pub fn roll_stats(ctx: Context<RollStats>) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let day = EpochStats::day_of(Clock::get()?.unix_timestamp);
+        let snapshot = StatsSnapshot {
+            dreams_recorded: treasury.total_dreams_recorded,
+            rewards_distributed: treasury.total_rewards_distributed,
+            compressed_staked: vault_balance(&ctx.accounts.compressed_stake_vault)?,
+        };
+        require!(
+            ctx.accounts.stats_ring.load_mut()?.roll(day, snapshot),
+            DreamError::StatsAlreadyRolled
+        );
+
+        emit!(StatsRolledEvent {
+            day,
+            dreams_recorded: snapshot.dreams_recorded,
+            rewards_distributed: snapshot.rewards_distributed,
+            compressed_staked: snapshot.compressed_staked,
+        });
+        Ok(())
+    }
+
+    /// Authority only. Creates the crank pool on first use; a zero bounty
+    /// pauses payouts.
+    pub fn set_crank_bounty(
+        ctx: Context<SetCrankBounty>,
+        bounty_lamports: u64,
+        cooldown_secs: i64,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.crank_pool;
+        pool.bounty_lamports = bounty_lamports;
+        pool.cooldown_secs = cooldown_secs;
+        Ok(())
+    }
+
+    /// Authority only. Creates the fee vault for deployments that predate it.
+    pub fn set_protocol_fees(
+        ctx: Context<SetProtocolFees>,
+        record_dream_fee: u64,
+        mint_oneirobot_fee: u64,
+    ) -> Result<()> {
+        require!(
+            record_dream_fee <= MAX_PROTOCOL_FEE_LAMPORTS
+                && mint_oneirobot_fee <= MAX_PROTOCOL_FEE_LAMPORTS,
+            DreamError::ProtocolFeeTooHigh
+        );
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        fee_vault.record_dream_fee = record_dream_fee;
+        fee_vault.mint_oneirobot_fee = mint_oneirobot_fee;
+        Ok(())
+    }
+
+    /// Authority only, while the treasury is healthy. Sweeps every collected
+    /// fee above the vault's rent reserve: the insurance fund's slice to the
+    /// fund, the rest to `recipient`.
+    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+        let fee_vault = &mut ctx.accounts.fee_vault;
+        let rent = Rent::get()?.minimum_balance(fee_vault.to_account_info().data_len());
+        let lamports = fee_vault.get_lamports().saturating_sub(rent);
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        let insured = lamports * u64::from(insurance_fund.fee_share_bps) / 10_000;
+        fee_vault.sub_lamports(lamports)?;
+        insurance_fund.add_lamports(insured)?;
+        ctx.accounts.recipient.add_lamports(lamports - insured)?;
+        fee_vault.total_swept += lamports;
+
+        emit!(FeesSweptEvent {
+            recipient: ctx.accounts.recipient.key(),
+            lamports,
+            total_swept: fee_vault.total_swept,
+        });
+        if insured > 0 {
+            insurance_fund.lamports_received += insured;
+            emit!(InsuranceFundedEvent {
+                lamports: insured,
+                dream: 0,
+                lamports_balance: insurance_lamports(insurance_fund)?,
+                dream_balance: ctx.accounts.insurance_vault.amount,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        Ok(())
+    }
+
+    /// Authority only, standing in for governance. Sets the insurance fund's
+    /// slice of swept fees and market fees, creating the fund and its DREAM
+    /// vault on first use.
+    pub fn set_insurance_fee_share(
+        ctx: Context<SetInsuranceFeeShare>,
+        fee_share_bps: u16,
+    ) -> Result<()> {
+        require!(
+            fee_share_bps <= MAX_INSURANCE_FEE_SHARE_BPS,
+            DreamError::InsuranceShareTooHigh
+        );
+        ctx.accounts.insurance_fund.fee_share_bps = fee_share_bps;
+        Ok(())
+    }
+
+    /// Authority only, standing in for governance. Moves `amount` from the
+    /// insurance fund into `target` once it can't cover a single payout: one
+    /// crank bounty for the crank pool, one top-band dream reward for the
+    /// reward pool.
+    pub fn cover_shortfall(
+        ctx: Context<CoverShortfall>,
+        target: ShortfallTarget,
+        amount: u64,
+    ) -> Result<()> {
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        let target_balance = match target {
+            ShortfallTarget::CrankPool => {
+                let pool = ctx
+                    .accounts
+                    .crank_pool
+                    .as_mut()
+                    .ok_or(DreamError::MissingShortfallTarget)?;
+                let rent = Rent::get()?.minimum_balance(pool.to_account_info().data_len());
+                let available = pool.get_lamports().saturating_sub(rent);
+                require!(available < pool.bounty_lamports, DreamError::NoShortfall);
+                require!(
+                    amount <= insurance_lamports(insurance_fund)?,
+                    DreamError::InsufficientInsuranceFunds
+                );
+                insurance_fund.sub_lamports(amount)?;
+                pool.add_lamports(amount)?;
+                insurance_fund.lamports_covered += amount;
+                available + amount
+            }
+            ShortfallTarget::RewardPool => {
+                let vault = ctx
+                    .accounts
+                    .reward_pool_vault
+                    .as_ref()
+                    .ok_or(DreamError::MissingShortfallTarget)?;
+                require!(vault.amount < MAX_DREAM_REWARD_PER_RECORD, DreamError::NoShortfall);
+                require!(
+                    amount <= ctx.accounts.insurance_vault.amount,
+                    DreamError::InsufficientInsuranceFunds
+                );
+                let cpi_accounts = token_2022::TransferChecked {
+                    from: ctx.accounts.insurance_vault.to_account_info(),
+                    mint: ctx.accounts.dream_mint.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                };
+                let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+                let signer_seeds = &[treasury_seeds];
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.dream_mint.decimals)?;
+                ctx.accounts.insurance_vault.reload()?;
+                insurance_fund.dream_covered += amount;
+                vault.amount + amount
+            }
+        };
+
+        emit!(ShortfallCoveredEvent {
+            target,
+            amount,
+            target_balance,
+            lamports_balance: insurance_lamports(insurance_fund)?,
+            dream_balance: ctx.accounts.insurance_vault.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Permissionless. Checks that the reward pool covers the co-dreamer
+    /// shares held back in it, the stream vault covers unclaimed streams, and
+    /// the insurance fund holds what it has received less what it has
+    /// covered, and records whether all hold. Creates the health account for
+    /// deployments that predate it.
+    pub fn reconcile_treasury(ctx: Context<ReconcileTreasury>) -> Result<()> {
+        let reward_pool_balance = vault_balance(&ctx.accounts.reward_pool_vault)?;
+        let reward_stream_balance = vault_balance(&ctx.accounts.reward_stream_vault)?;
+        let fund = &ctx.accounts.insurance_fund;
+        let insurance_lamports = insurance_lamports(fund)?;
+        let insurance_lamports_expected =
+            fund.lamports_received.saturating_sub(fund.lamports_covered);
+        let insurance_dream = ctx.accounts.insurance_vault.amount;
+        let insurance_dream_expected = fund.dream_received.saturating_sub(fund.dream_covered);
+
+        let now = Clock::get()?.unix_timestamp;
+        let health = &mut ctx.accounts.treasury_health;
+        health.healthy = reward_pool_balance >= health.pending_coauthor_shares
+            && reward_stream_balance >= health.pending_streamed
+            && insurance_lamports >= insurance_lamports_expected
+            && insurance_dream >= insurance_dream_expected;
+        health.checked_at = now;
+
+        emit!(TreasuryHealthEvent {
+            healthy: health.healthy,
+            reward_pool_balance,
+            pending_coauthor_shares: health.pending_coauthor_shares,
+            reward_stream_balance,
+            pending_streamed: health.pending_streamed,
+            insurance_lamports,
+            insurance_lamports_expected,
+            insurance_dream,
+            insurance_dream_expected,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Anyone can top up the crank pool.
+    pub fn fund_crank_pool(ctx: Context<FundCrankPool>, lamports: u64) -> Result<()> {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.crank_pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, lamports)
+    }
+
+    /// Authority only. Creates the sponsor pool on first use.
+    pub fn set_sponsor_pool(ctx: Context<SetSponsorPool>, enabled: bool) -> Result<()> {
+        ctx.accounts.sponsor_pool.enabled = enabled;
+        Ok(())
+    }
+
+    /// Anyone can top up the sponsor pool.
+    pub fn fund_sponsor_pool(ctx: Context<FundSponsorPool>, lamports: u64) -> Result<()> {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.sponsor_pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, lamports)
+    }
+
+    /// Pays a new dreamer what their first `record_dream` costs them: rent
+    /// for the record, profile and season score, for their DREAM account,
+    /// today's `EpochStats` and their `SleepSession` unless those exist, and
+    /// the record fee. The
+    /// `record_dream` must follow in the same transaction. `payer` covers
+    /// the `Sponsorship` marker and the pool refunds it.
+    pub fn claim_sponsorship(ctx: Context<ClaimSponsorship>) -> Result<()> {
+        let dreamer = ctx.accounts.dreamer.key();
+        require!(ctx.accounts.sponsor_pool.enabled, DreamError::SponsorshipDisabled);
+        require!(ctx.accounts.dreamer_profile.data_is_empty(), DreamError::NotNewDreamer);
+        require!(
+            records_dream_later(&ctx.accounts.instructions_sysvar, &dreamer),
+            DreamError::SponsoredDreamMissing
+        );
+
+        let rent = Rent::get()?;
+        let mut dream_cost = rent.minimum_balance(8 + std::mem::size_of::<DreamRecord>())
+            + rent.minimum_balance(8 + std::mem::size_of::<DreamerProfile>())
+            + rent.minimum_balance(8 + std::mem::size_of::<SeasonScore>())
+            + ctx.accounts.fee_vault.record_dream_fee;
+        if ctx.accounts.dreamer_dream_account.data_is_empty() {
+            dream_cost += rent.minimum_balance(dream_account_len()?);
+        }
+        if ctx.accounts.epoch_stats.data_is_empty() {
+            dream_cost += rent.minimum_balance(8 + std::mem::size_of::<EpochStats>());
+        }
+        if ctx.accounts.sleep_session.data_is_empty() {
+            dream_cost += rent.minimum_balance(8 + std::mem::size_of::<SleepSession>());
+        }
+        let marker_rent = ctx.accounts.sponsorship.get_lamports();
+        let lamports = dream_cost + marker_rent;
+
+        let pool = &mut ctx.accounts.sponsor_pool;
+        let reserve = rent.minimum_balance(pool.to_account_info().data_len());
+        require!(
+            pool.get_lamports().saturating_sub(reserve) >= lamports,
+            DreamError::SponsorPoolEmpty
+        );
+        pool.sub_lamports(lamports)?;
+        ctx.accounts.dreamer.add_lamports(dream_cost)?;
+        ctx.accounts.payer.add_lamports(marker_rent)?;
+        pool.dreamers_sponsored += 1;
+        pool.total_sponsored += lamports;
+
+        let now = Clock::get()?.unix_timestamp;
+        let sponsorship = &mut ctx.accounts.sponsorship;
+        sponsorship.dreamer = dreamer;
+        sponsorship.lamports = lamports;
+        sponsorship.claimed_at = now;
+
+        emit!(DreamerSponsoredEvent {
+            dreamer,
+            payer: ctx.accounts.payer.key(),
+            lamports,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Authority only, once per finished day. `leaf_count` must match the
+    /// day's `EpochStats`, so a root can't silently leave out dreams.
+    pub fn commit_epoch_root(
+        ctx: Context<CommitEpochRoot>,
+        day: u32,
+        merkle_root: [u8; 32],
+        leaf_count: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            day < EpochStats::day_of(clock.unix_timestamp)
+                && leaf_count == ctx.accounts.epoch_stats.dream_count,
+            DreamError::InvalidEpochCommitment
+        );
+
+        let commitment = &mut ctx.accounts.epoch_commitment;
+        commitment.day = day;
+        commitment.merkle_root = merkle_root;
+        commitment.leaf_count = leaf_count;
+        commitment.committed_by = ctx.accounts.authority.key();
+        commitment.committed_at = clock.unix_timestamp;
+
+        emit!(EpochRootCommittedEvent {
+            day,
+            merkle_root,
+            leaf_count,
+        });
+        Ok(())
+    }
+
+    /// Opt in or out of licensed research snapshots. Revenue earned while
+    /// consenting stays claimable after opting out.
+    pub fn set_research_consent(ctx: Context<SetResearchConsent>, consent: bool) -> Result<()> {
+        let profile = &mut ctx.accounts.dreamer_profile;
+        let pool = &mut ctx.accounts.research_pool;
+        let participant = &mut ctx.accounts.research_participant;
+        require!(profile.research_consent != consent, DreamError::ResearchConsentUnchanged);
+
+        if consent {
+            pool.consenting_dreamers += 1;
+        } else {
+            participant.accrued += pool.revenue_per_dreamer - participant.revenue_checkpoint;
+            pool.consenting_dreamers -= 1;
+        }
+        participant.dreamer = ctx.accounts.dreamer.key();
+        participant.revenue_checkpoint = pool.revenue_per_dreamer;
+        profile.set_dreamer(ctx.accounts.dreamer.key(), Clock::get()?.unix_timestamp);
+        profile.research_consent = consent;
+
+        emit!(ResearchConsentChangedEvent {
+            dreamer: profile.dreamer,
+            consent,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Buy (or extend by a year) a research dataset license. The treasury
+    /// keeps its share plus any rounding remainder; the rest is split equally
+    /// among currently consenting dreamers.
+    pub fn purchase_research_license(ctx: Context<PurchaseResearchLicense>) -> Result<()> {
+        let pool = &mut ctx.accounts.research_pool;
+        require!(pool.consenting_dreamers > 0, DreamError::NoConsentingDreamers);
+
+        let per_dreamer = RESEARCH_LICENSE_PRICE * (10_000 - RESEARCH_TREASURY_SHARE_BPS)
+            / 10_000
+            / pool.consenting_dreamers;
+        let dreamer_share = per_dreamer * pool.consenting_dreamers;
+        let treasury_share = RESEARCH_LICENSE_PRICE - dreamer_share;
+        pool.revenue_per_dreamer += per_dreamer;
+        pool.licenses_sold += 1;
+        pool.total_revenue += RESEARCH_LICENSE_PRICE;
+
+        let decimals = ctx.accounts.lucid_mint.decimals;
+        for (to, amount) in [
+            (ctx.accounts.treasury_lucid_account.to_account_info(), treasury_share),
+            (ctx.accounts.research_vault.to_account_info(), dreamer_share),
+        ] {
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.licensee_lucid_account.to_account_info(),
+                mint: ctx.accounts.lucid_mint.to_account_info(),
+                to,
+                authority: ctx.accounts.licensee.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token_2022::transfer_checked(cpi_ctx, amount, decimals)?;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let license = &mut ctx.accounts.research_license;
+        license.licensee = ctx.accounts.licensee.key();
+        license.purchased_at = now;
+        license.expires_at = license.expires_at.max(now) + RESEARCH_LICENSE_DURATION_SECS;
+        license.total_paid += RESEARCH_LICENSE_PRICE;
+
+        emit!(ResearchLicensePurchasedEvent {
+            licensee: license.licensee,
+            amount: RESEARCH_LICENSE_PRICE,
+            dreamer_share,
+            consenting_dreamers: pool.consenting_dreamers,
+            expires_at: license.expires_at,
+        });
+        Ok(())
+    }
+
+    /// Withdraws the dreamer's share of research license revenue, including
+    /// revenue accrued before they opted out.
+    pub fn claim_research_revenue(ctx: Context<ClaimResearchRevenue>) -> Result<()> {
+        let pool = &ctx.accounts.research_pool;
+        let participant = &mut ctx.accounts.research_participant;
+        let mut amount = participant.accrued;
+        if ctx.accounts.dreamer_profile.research_consent {
+            amount += pool.revenue_per_dreamer - participant.revenue_checkpoint;
+        }
+        participant.accrued = 0;
+        participant.revenue_checkpoint = pool.revenue_per_dreamer;
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.research_vault.to_account_info(),
+            mint: ctx.accounts.lucid_mint.to_account_info(),
+            to: ctx.accounts.dreamer_lucid_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.lucid_mint.decimals)?;
+
+        emit!(ResearchRevenueClaimedEvent {
+            dreamer: participant.dreamer,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Offer licenses for one of the dreamer's dreams. `prices` are LUCID per
+    /// year for each `DreamLicenseType` (0 = not offered); `co_authors` share
+    /// the royalties left after the treasury's cut.
+    pub fn set_license_terms(
+        ctx: Context<SetLicenseTerms>,
+        prices: [u64; DREAM_LICENSE_TYPE_COUNT],
+        co_authors: Vec<RoyaltyShare>,
+    ) -> Result<()> {
+        require!(
+            co_authors.len() <= MAX_ROYALTY_CO_AUTHORS
+                && co_authors.iter().map(|c| u64::from(c.share_bps)).sum::<u64>() <= 10_000,
+            DreamError::InvalidRoyaltySplit
+        );
+
+        let terms = &mut ctx.accounts.license_terms;
+        terms.dream_record = ctx.accounts.dream_record.key();
+        terms.dreamer = ctx.accounts.dreamer.key();
+        terms.prices = prices;
+        terms.co_author_count = co_authors.len() as u8;
+        terms.co_authors = [RoyaltyShare::default(); MAX_ROYALTY_CO_AUTHORS];
+        terms.co_authors[..co_authors.len()].copy_from_slice(&co_authors);
+        Ok(())
+    }
+
+    /// Buy or extend a one-year license for a dream. Pass each co-author's
+    /// LUCID ATA as remaining accounts, in the order of the terms.
+    pub fn license_dream<'info>(
+        ctx: Context<'_, '_, 'info, 'info, LicenseDream<'info>>,
+        license_type: DreamLicenseType,
+    ) -> Result<()> {
+        let terms = &mut ctx.accounts.license_terms;
+        let price = terms.prices[license_type as usize];
+        require!(price > 0, DreamError::LicenseNotOffered);
+
+        let co_author_accounts = ctx.remaining_accounts;
+        require!(
+            co_author_accounts.len() == terms.co_authors().len(),
+            DreamError::InvalidRoyaltyAccount
+        );
+        let lucid_mint = ctx.accounts.lucid_mint.key();
+        for (account, co_author) in co_author_accounts.iter().zip(terms.co_authors()) {
+            let expected = get_associated_token_address_with_program_id(
+                &co_author.recipient,
+                &lucid_mint,
+                &token_2022::ID,
+            );
+            require_keys_eq!(account.key(), expected, DreamError::InvalidRoyaltyAccount);
+        }
+
+        let treasury_share = price * DREAM_LICENSE_TREASURY_SHARE_BPS / 10_000;
+        let (co_author_shares, dreamer_share) = terms.split(price - treasury_share);
+        terms.licenses_sold += 1;
+        terms.total_revenue += price;
+
+        let payouts = [
+            (ctx.accounts.treasury_lucid_account.to_account_info(), treasury_share),
+            (ctx.accounts.dreamer_lucid_account.to_account_info(), dreamer_share),
+        ]
+        .into_iter()
+        .chain(co_author_accounts.iter().cloned().zip(co_author_shares.iter().copied()));
+        let decimals = ctx.accounts.lucid_mint.decimals;
+        for (to, amount) in payouts {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.licensee_lucid_account.to_account_info(),
+                mint: ctx.accounts.lucid_mint.to_account_info(),
+                to,
+                authority: ctx.accounts.licensee.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token_2022::transfer_checked(cpi_ctx, amount, decimals)?;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let license = &mut ctx.accounts.dream_license;
+        license.dream_record = terms.dream_record;
+        license.licensee = ctx.accounts.licensee.key();
+        license.license_type = license_type;
+        license.issued_at = now;
+        license.expires_at = license.expires_at.max(now) + DREAM_LICENSE_DURATION_SECS;
+        license.total_paid += price;
+
+        emit!(DreamLicensedEvent {
+            dream_record: license.dream_record,
+            licensee: license.licensee,
+            license_type,
+            price,
+            treasury_share,
+            co_author_share: co_author_shares.iter().sum(),
+            dreamer_share,
+            expires_at: license.expires_at,
+        });
+        Ok(())
+    }
+
+    /// Escrow `amount` LUCID for the first approved translation of a dream
+    /// into `language`.
+    pub fn post_translation_bounty(
+        ctx: Context<PostTranslationBounty>,
+        language: [u8; 2],
+        amount: u64,
+    ) -> Result<()> {
+        require!(DreamAnnotation::is_valid_language(&language), DreamError::InvalidLanguageCode);
+        require!(amount > 0, DreamError::InvalidBountyAmount);
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.sponsor_lucid_account.to_account_info(),
+            mint: ctx.accounts.lucid_mint.to_account_info(),
+            to: ctx.accounts.bounty_vault.to_account_info(),
+            authority: ctx.accounts.sponsor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.lucid_mint.decimals)?;
+
+        let bounty = &mut ctx.accounts.translation_bounty;
+        bounty.dream_record = ctx.accounts.dream_record.key();
+        bounty.sponsor = ctx.accounts.sponsor.key();
+        bounty.language = language;
+        bounty.amount = amount;
+        Ok(())
+    }
+
+    /// Attach a translation or commentary, stored off-chain at `cid`, to a dream.
+    pub fn attach_annotation(
+        ctx: Context<AttachAnnotation>,
+        language: [u8; 2],
+        cid: String,
+    ) -> Result<()> {
+        require!(DreamAnnotation::is_valid_language(&language), DreamError::InvalidLanguageCode);
+        require!(
+            cid.len() <= DreamInterface::MAX_IPFS_HASH_LEN,
+            DreamError::DreamContentTooLarge
+        );
+
+        let annotation = &mut ctx.accounts.annotation;
+        annotation.dream_record = ctx.accounts.dream_record.key();
+        annotation.annotator = ctx.accounts.annotator.key();
+        annotation.language = language;
+        annotation.cid = cid;
+        annotation.created_at = Clock::get()?.unix_timestamp;
+
+        emit!(DreamAnnotatedEvent {
+            annotation: annotation.key(),
+            dream_record: annotation.dream_record,
+            annotator: annotation.annotator,
+            language,
+            cid: annotation.cid.clone(),
+        });
         Ok(())
     }
+
+    /// Approve an annotation. The program has no validator set, so the
+    /// treasury authority approves. Passing the language's bounty pays it to
+    /// the annotator.
+    pub fn approve_annotation(ctx: Context<ApproveAnnotation>) -> Result<()> {
+        let annotation = &mut ctx.accounts.annotation;
+        require!(!annotation.approved, DreamError::AnnotationAlreadyApproved);
+        annotation.approved = true;
+
+        if let (Some(bounty), Some(vault), Some(annotator_account)) = (
+            ctx.accounts.translation_bounty.as_mut(),
+            ctx.accounts.bounty_vault.as_ref(),
+            ctx.accounts.annotator_lucid_account.as_ref(),
+        ) {
+            require!(bounty.paid_to == Pubkey::default(), DreamError::TranslationBountyClaimed);
+            bounty.paid_to = annotation.key();
+            annotation.bounty_paid = bounty.amount;
+
+            let cpi_accounts = token_2022::TransferChecked {
+                from: vault.to_account_info(),
+                mint: ctx.accounts.lucid_mint.to_account_info(),
+                to: annotator_account.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+            let signer_seeds = &[treasury_seeds];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_2022::transfer_checked(cpi_ctx, bounty.amount, ctx.accounts.lucid_mint.decimals)?;
+        }
+
+        emit!(AnnotationApprovedEvent {
+            annotation: annotation.key(),
+            annotator: annotation.annotator,
+            bounty_paid: annotation.bounty_paid,
+        });
+        Ok(())
+    }
+
+    /// Name someone else to interpret a dream recorded with an interpreter,
+    /// until the named one attaches an analysis. The held-back share goes to
+    /// whoever attaches it.
+    pub fn assign_interpreter(ctx: Context<AssignInterpreter>, interpreter: Pubkey) -> Result<()> {
+        require_keys_neq!(interpreter, ctx.accounts.dreamer.key(), DreamError::InvalidInterpreter);
+        let interpretation = &mut ctx.accounts.interpretation;
+        require!(interpretation.analysis_cid.is_empty(), DreamError::InterpretationLocked);
+        let now = Clock::get()?.unix_timestamp;
+        interpretation.dream_record = ctx.accounts.dream_record.key();
+        interpretation.interpreter = interpreter;
+        interpretation.assigned_at = now;
+
+        emit!(InterpreterAssignedEvent {
+            dream_record: interpretation.dream_record,
+            dreamer: ctx.accounts.dreamer.key(),
+            interpreter,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// The named interpreter attaches their analysis, stored off-chain at
+    /// `analysis_cid`, or replaces it, until the dream is validated. Opens
+    /// the interpreter's DREAM account for `validate_dream` to pay into.
+    pub fn attach_interpretation(
+        ctx: Context<AttachInterpretation>,
+        analysis_cid: String,
+    ) -> Result<()> {
+        require!(
+            !analysis_cid.is_empty() && analysis_cid.len() <= DreamInterface::MAX_IPFS_HASH_LEN,
+            DreamError::InvalidAnalysisCid
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let interpretation = &mut ctx.accounts.interpretation;
+        interpretation.analysis_cid = analysis_cid;
+        interpretation.attached_at = now;
+
+        emit!(DreamInterpretedEvent {
+            dream_record: interpretation.dream_record,
+            interpreter: interpretation.interpreter,
+            analysis_cid: interpretation.analysis_cid.clone(),
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Opens a prediction market on a hashed question with 2 to
+    /// `MAX_MARKET_OUTCOMES` outcomes, betting until `closes_at`. The creator
+    /// needs an established profile (see `DreamerProfile::is_established`).
+    pub fn create_market(
+        ctx: Context<CreateMarket>,
+        market_id: u64,
+        question_hash: [u8; 32],
+        outcome_count: u8,
+        closes_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.creator_profile.is_established(now),
+            DreamError::DreamerNotEstablished
+        );
+        require!(
+            (2..=MAX_MARKET_OUTCOMES as u8).contains(&outcome_count) && closes_at > now,
+            DreamError::InvalidMarket
+        );
+
+        let market = &mut ctx.accounts.market;
+        market.creator = ctx.accounts.creator.key();
+        market.market_id = market_id;
+        market.question_hash = question_hash;
+        market.outcome_count = outcome_count;
+        market.closes_at = closes_at;
+
+        emit!(MarketCreatedEvent {
+            market: market.key(),
+            creator: market.creator,
+            market_id,
+            question_hash,
+            outcome_count,
+            closes_at,
+        });
+        Ok(())
+    }
+
+    /// Stake `amount` DREAM on `outcome`. `MARKET_FEE_BPS` of it is split
+    /// between the insurance fund and the validator reward vault, at their fee
+    /// shares, and the treasury's DREAM account; the rest joins the outcome's
+    /// pool.
+    pub fn buy_outcome(ctx: Context<BuyOutcome>, outcome: u8, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(outcome < market.outcome_count, DreamError::InvalidOutcome);
+        require!(
+            !market.resolved && Clock::get()?.unix_timestamp < market.closes_at,
+            DreamError::MarketClosed
+        );
+
+        let fee = amount * MARKET_FEE_BPS / 10_000;
+        let insured = fee * u64::from(ctx.accounts.insurance_fund.fee_share_bps) / 10_000;
+        let for_validators =
+            fee * u64::from(ctx.accounts.validator_reward_pool.fee_share_bps) / 10_000;
+        let stake = amount - fee;
+        let decimals = ctx.accounts.dream_mint.decimals;
+        for (to, amount) in [
+            (ctx.accounts.treasury_dream_account.to_account_info(), fee - insured - for_validators),
+            (ctx.accounts.insurance_vault.to_account_info(), insured),
+            (ctx.accounts.validator_reward_vault.to_account_info(), for_validators),
+            (ctx.accounts.market_vault.to_account_info(), stake),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.buyer_dream_account.to_account_info(),
+                mint: ctx.accounts.dream_mint.to_account_info(),
+                to,
+                authority: ctx.accounts.buyer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token_2022::transfer_checked(cpi_ctx, amount, decimals)?;
+        }
+        market.outcome_pools[outcome as usize] += stake;
+        market.fees_collected += fee;
+        ctx.accounts.validator_reward_pool.dream_received += for_validators;
+
+        let position = &mut ctx.accounts.position;
+        position.market = market.key();
+        position.owner = ctx.accounts.buyer.key();
+        position.stakes[outcome as usize] += stake;
+
+        emit!(MarketOutcomeBoughtEvent {
+            market: position.market,
+            buyer: position.owner,
+            outcome,
+            amount,
+            fee,
+        });
+        if insured > 0 {
+            let insurance_fund = &mut ctx.accounts.insurance_fund;
+            insurance_fund.dream_received += insured;
+            ctx.accounts.insurance_vault.reload()?;
+            emit!(InsuranceFundedEvent {
+                lamports: 0,
+                dream: insured,
+                lamports_balance: insurance_lamports(insurance_fund)?,
+                dream_balance: ctx.accounts.insurance_vault.amount,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        Ok(())
+    }
+
+    /// Settle a closed market. There is no on-chain oracle committee, so the
+    /// treasury authority reports the outcome.
+    pub fn resolve_market(ctx: Context<ResolveMarket>, winning_outcome: u8) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+        require!(!market.resolved, DreamError::MarketAlreadyResolved);
+        require!(now >= market.closes_at, DreamError::MarketStillOpen);
+        require!(winning_outcome < market.outcome_count, DreamError::InvalidOutcome);
+
+        market.resolved = true;
+        market.winning_outcome = winning_outcome;
+
+        emit!(MarketResolvedEvent {
+            market: market.key(),
+            winning_outcome,
+            total_pool: market.total_pool(),
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Winnings above `REWARD_STREAM_THRESHOLD` go into a `RewardStream`
+    /// instead, which needs the optional stream accounts.
+    pub fn claim_market_winnings(ctx: Context<ClaimMarketWinnings>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let position = &mut ctx.accounts.position;
+        require!(market.resolved, DreamError::MarketNotResolved);
+        require!(!position.claimed, DreamError::PositionAlreadyClaimed);
+        let amount = market.payout(position);
+        position.claimed = true;
+
+        let to = if amount > REWARD_STREAM_THRESHOLD {
+            let (Some(stream), Some(stream_vault)) = (
+                ctx.accounts.reward_stream.as_mut(),
+                ctx.accounts.reward_stream_vault.as_ref(),
+            ) else {
+                return err!(DreamError::RewardStreamRequired);
+            };
+            let health = ctx
+                .accounts
+                .treasury_health
+                .as_mut()
+                .ok_or(DreamError::TreasuryHealthRequired)?;
+            health.pending_streamed += amount;
+            let now = Clock::get()?.unix_timestamp;
+            stream.beneficiary = position.owner;
+            stream.source = market.key();
+            stream.total = amount;
+            stream.start_at = now;
+            stream.end_at = now + REWARD_STREAM_DURATION_SECS;
+            emit!(RewardStreamCreatedEvent {
+                stream: stream.key(),
+                beneficiary: stream.beneficiary,
+                source: stream.source,
+                total: amount,
+                end_at: stream.end_at,
+            });
+            stream_vault.to_account_info()
+        } else {
+            ctx.accounts.owner_dream_account.to_account_info()
+        };
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.market_vault.to_account_info(),
+            mint: ctx.accounts.dream_mint.to_account_info(),
+            to,
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.dream_mint.decimals)?;
+
+        emit!(MarketWinningsClaimedEvent {
+            market: position.market,
+            owner: position.owner,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Withdraw whatever has vested on a reward stream so far.
+    pub fn claim_streamed(ctx: Context<ClaimStreamed>) -> Result<()> {
+        let stream = &mut ctx.accounts.reward_stream;
+        let amount = stream.vested(Clock::get()?.unix_timestamp) - stream.claimed;
+        stream.claimed += amount;
+        let health = &mut ctx.accounts.treasury_health;
+        health.pending_streamed = health.pending_streamed.saturating_sub(amount);
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.reward_stream_vault.to_account_info(),
+            mint: ctx.accounts.dream_mint.to_account_info(),
+            to: ctx.accounts.beneficiary_dream_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.dream_mint.decimals)?;
+
+        emit!(RewardStreamClaimedEvent {
+            stream: stream.key(),
+            beneficiary: stream.beneficiary,
+            amount,
+            claimed: stream.claimed,
+        });
+        Ok(())
+    }
+
+    /// Challenge `opponent` to a duel, staking `stake` LUCID into the duel vault.
+    pub fn create_duel(
+        ctx: Context<CreateDuel>,
+        duel_id: u64,
+        opponent: Pubkey,
+        stake: u64,
+    ) -> Result<()> {
+        let challenger = ctx.accounts.challenger.key();
+        require!(stake > 0 && opponent != challenger, DreamError::InvalidDuel);
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.challenger_lucid_account.to_account_info(),
+            mint: ctx.accounts.lucid_mint.to_account_info(),
+            to: ctx.accounts.duel_vault.to_account_info(),
+            authority: ctx.accounts.challenger.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::transfer_checked(cpi_ctx, stake, ctx.accounts.lucid_mint.decimals)?;
+
+        let duel = &mut ctx.accounts.duel;
+        duel.challenger = challenger;
+        duel.opponent = opponent;
+        duel.duel_id = duel_id;
+        duel.stake = stake;
+        duel.status = DuelStatus::Open;
+        Ok(())
+    }
+
+    /// Match the challenger's stake, which opens the submission window.
+    pub fn accept_duel(ctx: Context<AcceptDuel>) -> Result<()> {
+        let duel = &mut ctx.accounts.duel;
+        require!(duel.status == DuelStatus::Open, DreamError::DuelUnavailable);
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.opponent_lucid_account.to_account_info(),
+            mint: ctx.accounts.lucid_mint.to_account_info(),
+            to: ctx.accounts.duel_vault.to_account_info(),
+            authority: ctx.accounts.opponent.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::transfer_checked(cpi_ctx, duel.stake, ctx.accounts.lucid_mint.decimals)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        duel.status = DuelStatus::Active;
+        duel.started_at = now;
+        duel.submission_deadline = now + DUEL_SUBMISSION_WINDOW_SECS;
+        Ok(())
+    }
+
+    /// Withdraw a challenge that hasn't been accepted yet.
+    pub fn cancel_duel(ctx: Context<CancelDuel>) -> Result<()> {
+        let duel = &mut ctx.accounts.duel;
+        require!(duel.status == DuelStatus::Open, DreamError::DuelUnavailable);
+        duel.status = DuelStatus::Cancelled;
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.duel_vault.to_account_info(),
+            mint: ctx.accounts.lucid_mint.to_account_info(),
+            to: ctx.accounts.challenger_lucid_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, duel.stake, ctx.accounts.lucid_mint.decimals)
+    }
+
+    /// Enter one of the dreamer's own records, recorded after the duel started.
+    pub fn submit_duel_dream(ctx: Context<SubmitDuelDream>) -> Result<()> {
+        let duel = &mut ctx.accounts.duel;
+        let dreamer = ctx.accounts.dreamer.key();
+        let record = &ctx.accounts.dream_record;
+        require!(duel.status == DuelStatus::Active, DreamError::DuelUnavailable);
+        require!(
+            Clock::get()?.unix_timestamp < duel.submission_deadline,
+            DreamError::DuelSubmissionClosed
+        );
+        require!(record.timestamp >= duel.started_at, DreamError::InvalidDuel);
+
+        let slot = if dreamer == duel.challenger {
+            &mut duel.challenger_dream
+        } else if dreamer == duel.opponent {
+            &mut duel.opponent_dream
+        } else {
+            return err!(DreamError::InvalidDreamer);
+        };
+        require!(*slot == Pubkey::default(), DreamError::DuelSubmissionClosed);
+        *slot = record.key();
+        Ok(())
+    }
+
+    /// Score the submitted dreams and pay out the pot. The program has no
+    /// validator set, so the treasury authority reports the scores. A side
+    /// that didn't submit forfeits; if neither did, both stakes are refunded
+    /// without a fee.
+    pub fn settle_duel(
+        ctx: Context<SettleDuel>,
+        challenger_score: u8,
+        opponent_score: u8,
+    ) -> Result<()> {
+        let duel = &mut ctx.accounts.duel;
+        require!(duel.status == DuelStatus::Active, DreamError::DuelUnavailable);
+        let challenger_in = duel.challenger_dream != Pubkey::default();
+        let opponent_in = duel.opponent_dream != Pubkey::default();
+        require!(
+            (challenger_in && opponent_in)
+                || Clock::get()?.unix_timestamp >= duel.submission_deadline,
+            DreamError::DuelStillOpen
+        );
+
+        let pot = duel.stake * 2;
+        let prize = if challenger_in || opponent_in {
+            pot - pot * DUEL_FEE_BPS / 10_000
+        } else {
+            pot
+        };
+        let ordering = match (challenger_in, opponent_in) {
+            (true, true) => Some(challenger_score.cmp(&opponent_score)),
+            (true, false) => Some(std::cmp::Ordering::Greater),
+            (false, true) => Some(std::cmp::Ordering::Less),
+            (false, false) => None,
+        };
+        let (challenger_payout, opponent_payout, winner) = match ordering {
+            Some(std::cmp::Ordering::Greater) => (prize, 0, duel.challenger),
+            Some(std::cmp::Ordering::Less) => (0, prize, duel.opponent),
+            _ => (prize / 2, prize / 2, Pubkey::default()),
+        };
+        // An odd split leaves one base unit, which goes to the treasury
+        let fee = pot - challenger_payout - opponent_payout;
+
+        duel.status = DuelStatus::Settled;
+        duel.challenger_score = challenger_score;
+        duel.opponent_score = opponent_score;
+        duel.winner = winner;
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        for (to, amount) in [
+            (ctx.accounts.challenger_lucid_account.to_account_info(), challenger_payout),
+            (ctx.accounts.opponent_lucid_account.to_account_info(), opponent_payout),
+            (ctx.accounts.treasury_lucid_account.to_account_info(), fee),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.duel_vault.to_account_info(),
+                mint: ctx.accounts.lucid_mint.to_account_info(),
+                to,
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.lucid_mint.decimals)?;
+        }
+
+        emit!(DuelSettledEvent {
+            duel: duel.key(),
+            winner: duel.winner,
+            challenger_score,
+            opponent_score,
+            challenger_payout,
+            opponent_payout,
+            fee,
+        });
+        Ok(())
+    }
+
+    /// Authority only, standing in for the DAO. Escrows the sum of
+    /// `milestone_amounts` from the treasury's LUCID into a new grant's vault,
+    /// to be released to `recipient` one milestone at a time.
+    pub fn create_grant(
+        ctx: Context<CreateGrant>,
+        grant_id: u64,
+        milestone_amounts: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            (1..=MAX_GRANT_MILESTONES).contains(&milestone_amounts.len())
+                && milestone_amounts.iter().all(|amount| *amount > 0),
+            DreamError::InvalidGrantMilestones
+        );
+        let total_amount = milestone_amounts
+            .iter()
+            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+            .ok_or(DreamError::InvalidGrantMilestones)?;
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.treasury_lucid_account.to_account_info(),
+            mint: ctx.accounts.lucid_mint.to_account_info(),
+            to: ctx.accounts.grant_vault.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, total_amount, ctx.accounts.lucid_mint.decimals)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let grant = &mut ctx.accounts.grant;
+        grant.recipient = ctx.accounts.recipient.key();
+        grant.grant_id = grant_id;
+        grant.milestone_amounts[..milestone_amounts.len()].copy_from_slice(&milestone_amounts);
+        grant.milestone_count = milestone_amounts.len() as u8;
+        grant.milestones_paid = 0;
+        grant.total_amount = total_amount;
+        grant.released = 0;
+        grant.status = GrantStatus::Active;
+        grant.created_at = now;
+        grant.updated_at = now;
+
+        emit!(GrantCreatedEvent {
+            grant: grant.key(),
+            recipient: grant.recipient,
+            grant_id,
+            total_amount,
+            milestone_count: grant.milestone_count,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Authority only. Releases the grant's next milestone to its recipient;
+    /// the last one completes the grant.
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        require!(grant.status == GrantStatus::Active, DreamError::GrantNotActive);
+        let milestone = grant.milestones_paid;
+        let amount = grant.milestone_amounts[milestone as usize];
+        let now = Clock::get()?.unix_timestamp;
+        grant.milestones_paid += 1;
+        grant.released += amount;
+        grant.updated_at = now;
+        if grant.milestones_paid == grant.milestone_count {
+            grant.status = GrantStatus::Completed;
+        }
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.grant_vault.to_account_info(),
+            mint: ctx.accounts.lucid_mint.to_account_info(),
+            to: ctx.accounts.recipient_lucid_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.lucid_mint.decimals)?;
+
+        emit!(GrantMilestoneApprovedEvent {
+            grant: grant.key(),
+            recipient: grant.recipient,
+            milestone,
+            amount,
+            released: grant.released,
+            status: grant.status,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Authority only. Ends an active grant and returns its unreleased LUCID
+    /// to the treasury; milestones already paid stay with the recipient.
+    pub fn claw_back(ctx: Context<ClawBack>) -> Result<()> {
+        let grant = &mut ctx.accounts.grant;
+        require!(grant.status == GrantStatus::Active, DreamError::GrantNotActive);
+        let amount = grant.total_amount - grant.released;
+        let now = Clock::get()?.unix_timestamp;
+        grant.status = GrantStatus::ClawedBack;
+        grant.updated_at = now;
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.grant_vault.to_account_info(),
+            mint: ctx.accounts.lucid_mint.to_account_info(),
+            to: ctx.accounts.treasury_lucid_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.lucid_mint.decimals)?;
+
+        emit!(GrantClawedBackEvent {
+            grant: grant.key(),
+            recipient: grant.recipient,
+            amount,
+            released: grant.released,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Seal a dream until `unlock_at`, committing to `DreamCapsule::commitment(cid)`.
+    pub fn record_timelocked_dream(
+        ctx: Context<RecordTimelockedDream>,
+        capsule_id: u64,
+        content_hash: [u8; 32],
+        unlock_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(unlock_at > now, DreamError::InvalidUnlockTime);
+
+        let capsule = &mut ctx.accounts.capsule;
+        capsule.dreamer = ctx.accounts.dreamer.key();
+        capsule.capsule_id = capsule_id;
+        capsule.content_hash = content_hash;
+        capsule.sealed_at = now;
+        capsule.unlock_at = unlock_at;
+
+        emit!(DreamSealedEvent {
+            capsule: capsule.key(),
+            dreamer: capsule.dreamer,
+            content_hash,
+            unlock_at,
+        });
+        Ok(())
+    }
+
+    /// Publishes a time capsule's CID once its unlock time has passed.
+    pub fn reveal_dream(ctx: Context<RevealDream>, cid: String) -> Result<()> {
+        let capsule = &mut ctx.accounts.capsule;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= capsule.unlock_at && capsule.revealed_at == 0,
+            DreamError::CapsuleLocked
+        );
+        require!(
+            cid.len() <= DreamInterface::MAX_IPFS_HASH_LEN,
+            DreamError::DreamContentTooLarge
+        );
+        require!(
+            DreamCapsule::commitment(&cid) == capsule.content_hash,
+            DreamError::CapsuleCommitmentMismatch
+        );
+        capsule.revealed_at = now;
+        capsule.cid = cid;
+
+        emit!(DreamRevealedEvent {
+            capsule: capsule.key(),
+            dreamer: capsule.dreamer,
+            cid: capsule.cid.clone(),
+            sealed_at: capsule.sealed_at,
+            revealed_at: now,
+        });
+        Ok(())
+    }
+
+    /// Approve or revoke a charity wallet for reward splits, creating its
+    /// DREAM account on first approval.
+    pub fn set_charity_approval(ctx: Context<SetCharityApproval>, approved: bool) -> Result<()> {
+        let charity = &mut ctx.accounts.charity;
+        charity.wallet = ctx.accounts.charity_wallet.key();
+        charity.approved = approved;
+        charity.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(CharityApprovalChangedEvent {
+            charity: charity.wallet,
+            approved,
+            timestamp: charity.updated_at,
+        });
+        Ok(())
+    }
+
+    /// Route `share_bps` of future dream rewards to an approved charity; 0 stops it.
+    pub fn set_charity_split(ctx: Context<SetCharitySplit>, share_bps: u16) -> Result<()> {
+        require!(share_bps <= MAX_CHARITY_SHARE_BPS, DreamError::InvalidCharityShare);
+        require!(ctx.accounts.charity.approved, DreamError::InvalidCharity);
+
+        let split = &mut ctx.accounts.charity_split;
+        split.dreamer = ctx.accounts.dreamer.key();
+        split.charity = ctx.accounts.charity.wallet;
+        split.share_bps = share_bps;
+        Ok(())
+    }
+
+    /// Creates the zero-copy validation queue that `record_dream` can enqueue into.
+    pub fn initialize_validation_queue(ctx: Context<InitializeValidationQueue>) -> Result<()> {
+        ctx.accounts.validation_queue.load_init()?;
+        Ok(())
+    }
+
+    /// Authority only, standing in for validators. Takes the queued dream with
+    /// the highest priority: seconds waited plus a head start per staked LUCID.
+    pub fn pop_validation(ctx: Context<PopValidation>) -> Result<Pubkey> {
+        let now = Clock::get()?.unix_timestamp;
+        let entry = ctx
+            .accounts
+            .validation_queue
+            .load_mut()?
+            .pop(now)
+            .ok_or(DreamError::ValidationQueueEmpty)?;
+
+        emit!(DreamDequeuedEvent {
+            dream_record: entry.dream_record,
+            validator: ctx.accounts.authority.key(),
+            stake: entry.stake,
+            waited_secs: now - entry.enqueued_at,
+        });
+        Ok(entry.dream_record)
+    }
+
+    /// Approve or revoke a pinning service as an oracle for `confirm_pinned`.
+    pub fn set_pinning_oracle(ctx: Context<SetPinningOracle>, approved: bool) -> Result<()> {
+        let pinning_oracle = &mut ctx.accounts.pinning_oracle;
+        pinning_oracle.oracle = ctx.accounts.oracle.key();
+        pinning_oracle.approved = approved;
+        pinning_oracle.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(PinningOracleChangedEvent {
+            oracle: pinning_oracle.oracle,
+            approved,
+            timestamp: pinning_oracle.updated_at,
+        });
+        Ok(())
+    }
+
+    /// Set how many approved oracles must sign each pin confirmation and
+    /// classification, up to MAX_ORACLE_QUORUM.
+    pub fn set_oracle_quorum(ctx: Context<SetOracleQuorum>, threshold: u8) -> Result<()> {
+        require!(
+            (1..=MAX_ORACLE_QUORUM).contains(&threshold),
+            DreamError::InvalidOracleQuorum
+        );
+        let oracle_quorum = &mut ctx.accounts.oracle_quorum;
+        oracle_quorum.threshold = threshold;
+        oracle_quorum.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(OracleQuorumSetEvent {
+            threshold,
+            timestamp: oracle_quorum.updated_at,
+        });
+        Ok(())
+    }
+
+    /// Record an approved oracle's attestation that the dream's CID is
+    /// retrievable and matches its content hash. The preceding instruction must
+    /// be an ed25519 verification of the oracle's signature over
+    /// `pin_attestation_message`; anyone may relay it. Under an oracle quorum,
+    /// the same instruction carries every co-signer's signature, and their
+    /// `PinningOracle` accounts follow as remaining accounts.
+    pub fn confirm_pinned<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfirmPinned<'info>>,
+    ) -> Result<()> {
+        let pinning_oracle = &mut ctx.accounts.pinning_oracle;
+        require!(pinning_oracle.approved, DreamError::PinningOracleNotApproved);
+        let dream_record = &ctx.accounts.dream_record;
+        let message = pin_attestation_message(&dream_record.key(), &dream_record.content_hash);
+        require_oracle_quorum(
+            &ctx.accounts.oracle_quorum,
+            &ctx.accounts.instructions_sysvar,
+            pinning_oracle,
+            ctx.remaining_accounts,
+            &message,
+            DreamError::InvalidPinAttestation,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let confirmation = &mut ctx.accounts.pin_confirmation;
+        confirmation.dream_record = dream_record.key();
+        confirmation.oracle = pinning_oracle.oracle;
+        confirmation.confirmed_at = now;
+
+        emit!(DreamPinnedEvent {
+            dream_record: confirmation.dream_record,
+            dream_id: dream_record.id,
+            dreamer: dream_record.dreamer,
+            oracle: confirmation.oracle,
+            content_hash: dream_record.content_hash,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Record an approved oracle's language, NSFW and topic classification of
+    /// a dream, replacing any earlier one. The preceding instruction must be
+    /// an ed25519 verification of the oracle's signature over
+    /// `classification_attestation_message` for the next revision; anyone may
+    /// relay it. Co-signers of an oracle quorum are passed as in
+    /// `confirm_pinned`.
+    pub fn classify_dream<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClassifyDream<'info>>,
+        language: [u8; 2],
+        nsfw: bool,
+        topics: u64,
+    ) -> Result<()> {
+        require!(
+            DreamClassification::is_valid_language(&language),
+            DreamError::InvalidDreamLanguage
+        );
+        let pinning_oracle = &mut ctx.accounts.pinning_oracle;
+        require!(pinning_oracle.approved, DreamError::PinningOracleNotApproved);
+        let dream_record = &ctx.accounts.dream_record;
+        let classification = &mut ctx.accounts.dream_classification;
+        let revision = classification.revision + 1;
+        let message = classification_attestation_message(
+            &dream_record.key(),
+            &dream_record.content_hash,
+            &language,
+            nsfw,
+            topics,
+            revision,
+        );
+        require_oracle_quorum(
+            &ctx.accounts.oracle_quorum,
+            &ctx.accounts.instructions_sysvar,
+            pinning_oracle,
+            ctx.remaining_accounts,
+            &message,
+            DreamError::InvalidClassificationAttestation,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        classification.dream_record = dream_record.key();
+        classification.oracle = pinning_oracle.oracle;
+        classification.language = language;
+        classification.nsfw = nsfw;
+        classification.topics = topics;
+        classification.revision = revision;
+        classification.classified_at = now;
+
+        emit!(DreamClassifiedEvent {
+            dream_record: classification.dream_record,
+            dreamer: dream_record.dreamer,
+            oracle: classification.oracle,
+            language,
+            nsfw,
+            topics,
+            revision,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Authority only, standing in for validators. Scores a recorded dream
+    /// once; the `DreamValidation` PDA makes a second verdict fail. The dream
+    /// must have been confirmed pinned, so dead links earn nothing. A
+    /// dreamer's first validated dreams also pay an onboarding bonus. With a
+    /// `validator_registration`, the verdict is the registered validator's
+    /// and earns them the pool's per-validation reward if the vault covers it.
+    /// If an interpreter attached an analysis, they are paid the share of the
+    /// reward held back for them and their `INTERPRETER_SHARE_BPS` of the
+    /// bonus into `interpreter_dream_account`.
+    pub fn validate_dream(ctx: Context<ValidateDream>, score: u8) -> Result<()> {
+        require!(
+            (1..=MAX_VALIDATION_SCORE).contains(&score),
+            DreamError::InvalidValidationScore
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let validator = ctx
+            .accounts
+            .validator_registration
+            .as_ref()
+            .map_or(ctx.accounts.authority.key(), |registration| registration.validator);
+        let validation = &mut ctx.accounts.dream_validation;
+        validation.dream_record = ctx.accounts.dream_record.key();
+        validation.validator = validator;
+        validation.score = score;
+        validation.validated_at = now;
+
+        if let (Some(_), Some(pool), Some(vault), Some(rewards)) = (
+            &ctx.accounts.validator_registration,
+            ctx.accounts.validator_reward_pool.as_mut(),
+            &ctx.accounts.validator_reward_vault,
+            ctx.accounts.validator_rewards.as_mut(),
+        ) {
+            credit_validator(pool, vault, rewards, validator, validation.dream_record, now)?;
+        }
+        if let (Some(_), Some(stats)) = (
+            &ctx.accounts.validator_registration,
+            ctx.accounts.validator_stats.as_mut(),
+        ) {
+            stats.validator = validator;
+            stats.validations += 1;
+            stats.last_validated_at = now;
+        }
+
+        // A dreamer's first validated dreams pay an onboarding bonus on top
+        // of the dream's reward, while the reward pool covers it
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        let multiplier_bps = dreamer_profile.onboarding_multiplier_bps();
+        let dream_reward = ctx.accounts.dream_record.token_reward;
+        let bonus = dream_reward * u64::from(multiplier_bps - 10_000) / 10_000;
+        dreamer_profile.adjust_reputation(DreamerProfile::validation_change(score));
+        dreamer_profile.validated_dreams += 1;
+
+        // An interpreter who attached an analysis gets the share of the reward
+        // held back at recording and the same share of the bonus. Without an
+        // analysis, the held-back share goes back to the dreamer.
+        let mut interpretation = None;
+        if !ctx.accounts.interpretation.data_is_empty() {
+            let data = ctx.accounts.interpretation.try_borrow_data()?;
+            interpretation = Some(DreamInterpretation::try_deserialize(&mut &data[..])?);
+        }
+        let held = match &interpretation {
+            Some(_) => DreamInterpretation::interpreter_share(dream_reward),
+            None => 0,
+        };
+        let health = &mut ctx.accounts.treasury_health;
+        health.pending_coauthor_shares = health.pending_coauthor_shares.saturating_sub(held);
+        let mut interpretation = interpretation.filter(|loaded| !loaded.analysis_cid.is_empty());
+
+        let bonus = if held + bonus > ctx.accounts.reward_pool_vault.amount {
+            msg!("Reward pool depleted; {} of onboarding bonus not paid", bonus);
+            0
+        } else {
+            bonus
+        };
+        let (interpreter_amount, from_bonus) = match &interpretation {
+            Some(_) => {
+                let from_bonus = DreamInterpretation::interpreter_share(bonus);
+                (held + from_bonus, from_bonus)
+            }
+            None => (0, 0),
+        };
+        let dreamer_bonus = bonus - from_bonus;
+        let returned = if interpretation.is_some() { 0 } else { held };
+        let dreamer_amount = dreamer_bonus + returned;
+
+        let mut payouts = Vec::new();
+        if dreamer_amount > 0 {
+            payouts.push((ctx.accounts.dreamer_dream_account.to_account_info(), dreamer_amount));
+        }
+        if let Some(interpretation) = interpretation.as_mut() {
+            let interpreter_account = ctx
+                .accounts
+                .interpreter_dream_account
+                .as_ref()
+                .ok_or(DreamError::MissingInterpreterAccount)?;
+            require_keys_eq!(
+                interpreter_account.owner,
+                interpretation.interpreter,
+                DreamError::MissingInterpreterAccount
+            );
+            require_keys_eq!(
+                interpreter_account.mint,
+                ctx.accounts.dream_mint.key(),
+                DreamError::MissingInterpreterAccount
+            );
+            interpretation.reward_paid = interpreter_amount;
+            let mut data = ctx.accounts.interpretation.try_borrow_mut_data()?;
+            interpretation.try_serialize(&mut &mut data[..])?;
+            payouts.push((interpreter_account.to_account_info(), interpreter_amount));
+        }
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        for (to, amount) in payouts {
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.reward_pool_vault.to_account_info(),
+                mint: ctx.accounts.dream_mint.to_account_info(),
+                to,
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.dream_mint.decimals)?;
+        }
+        ctx.accounts.treasury.total_rewards_distributed += dreamer_amount + interpreter_amount;
+
+        if dreamer_bonus > 0 {
+            emit!(OnboardingBonusPaidEvent {
+                dreamer: ctx.accounts.dream_record.dreamer,
+                dream_record: validation.dream_record,
+                validated_dreams: dreamer_profile.validated_dreams,
+                multiplier_bps,
+                amount: dreamer_bonus,
+                timestamp: now,
+            });
+        }
+        if let Some(interpretation) = &interpretation {
+            emit!(InterpreterPaidEvent {
+                dream_record: validation.dream_record,
+                interpreter: interpretation.interpreter,
+                from_bonus,
+                amount: interpreter_amount,
+                timestamp: now,
+            });
+        }
+
+        emit!(DreamValidatedEvent {
+            dream_record: validation.dream_record,
+            dreamer: ctx.accounts.dream_record.dreamer,
+            validator: validation.validator,
+            score,
+            reputation: dreamer_profile.reputation,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Authority only, standing in for governance. Settles a dispute over a
+    /// verdict against the `consensus_score`, recording the validator's
+    /// divergence in their `ValidatorStats`. A verdict further than
+    /// `VALIDATION_DISPUTE_TOLERANCE` away loses the dispute. The
+    /// `ValidationDispute` PDA makes a second dispute of the verdict fail.
+    pub fn resolve_validation_dispute(
+        ctx: Context<ResolveValidationDispute>,
+        consensus_score: u8,
+    ) -> Result<()> {
+        require!(
+            (1..=MAX_VALIDATION_SCORE).contains(&consensus_score),
+            DreamError::InvalidValidationScore
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let validation = &ctx.accounts.dream_validation;
+        let divergence = validation.score.abs_diff(consensus_score);
+        let lost = divergence > VALIDATION_DISPUTE_TOLERANCE;
+
+        let dispute = &mut ctx.accounts.validation_dispute;
+        dispute.dream_record = validation.dream_record;
+        dispute.validator = validation.validator;
+        dispute.score = validation.score;
+        dispute.consensus_score = consensus_score;
+        dispute.lost = lost;
+        dispute.resolved_at = now;
+
+        let stats = &mut ctx.accounts.validator_stats;
+        stats.validator = validation.validator;
+        stats.disputes += 1;
+        stats.disputes_lost += u32::from(lost);
+        stats.total_divergence += u64::from(divergence);
+
+        emit!(ValidationDisputeResolvedEvent {
+            dream_record: validation.dream_record,
+            validator: validation.validator,
+            score: validation.score,
+            consensus_score,
+            lost,
+            disputes_lost: stats.disputes_lost,
+            average_divergence: stats.average_divergence(),
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Post an approved pinning oracle's score for a pinned, unvalidated
+    /// dream, bonding OPTIMISTIC_SCORE_BOND lamports. Unless a fraud proof
+    /// voids it within OPTIMISTIC_CHALLENGE_WINDOW_SECS, anyone can then
+    /// finalize it into the dream's `DreamValidation` without the authority.
+    pub fn post_optimistic_score(ctx: Context<PostOptimisticScore>, score: u8) -> Result<()> {
+        require!(
+            (1..=MAX_VALIDATION_SCORE).contains(&score),
+            DreamError::InvalidValidationScore
+        );
+        require!(ctx.accounts.pinning_oracle.approved, DreamError::PinningOracleNotApproved);
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.oracle.to_account_info(),
+                to: ctx.accounts.optimistic_score.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_ctx, OPTIMISTIC_SCORE_BOND)?;
+
+        let optimistic_score = &mut ctx.accounts.optimistic_score;
+        optimistic_score.dream_record = ctx.accounts.dream_record.key();
+        optimistic_score.oracle = ctx.accounts.oracle.key();
+        optimistic_score.score = score;
+        optimistic_score.bond = OPTIMISTIC_SCORE_BOND;
+        optimistic_score.posted_at = Clock::get()?.unix_timestamp;
+
+        emit!(OptimisticScorePostedEvent {
+            dream_record: optimistic_score.dream_record,
+            oracle: optimistic_score.oracle,
+            score,
+            bond: optimistic_score.bond,
+            challenge_ends_at: optimistic_score.challenge_ends_at(),
+        });
+        Ok(())
+    }
+
+    /// Permissionless within the challenge window. Voids an optimistic score
+    /// with a fraud proof and pays its bond to the challenger. For
+    /// `DuplicateContent`, pass the earlier record with the same content as
+    /// `original_record`. For `DeadCid`, pass the attesting oracle as
+    /// `witness_oracle`, after an ed25519 verification of its signature over
+    /// `dead_cid_attestation_message`.
+    pub fn challenge_optimistic_score(
+        ctx: Context<ChallengeOptimisticScore>,
+        proof: FraudProof,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let optimistic_score = &mut ctx.accounts.optimistic_score;
+        require!(!optimistic_score.voided, DreamError::OptimisticScoreVoided);
+        require!(now < optimistic_score.challenge_ends_at(), DreamError::ChallengeWindowClosed);
+
+        let dream_record = &ctx.accounts.dream_record;
+        match proof {
+            FraudProof::DuplicateContent => {
+                let original = ctx
+                    .accounts
+                    .original_record
+                    .as_ref()
+                    .ok_or(DreamError::InvalidFraudProof)?;
+                let earlier = (original.timestamp, original.id)
+                    < (dream_record.timestamp, dream_record.id);
+                require!(
+                    earlier && original.content_hash == dream_record.content_hash,
+                    DreamError::InvalidFraudProof
+                );
+            }
+            FraudProof::DeadCid => {
+                let (Some(witness), Some(instructions_sysvar)) =
+                    (&ctx.accounts.witness_oracle, &ctx.accounts.instructions_sysvar)
+                else {
+                    return err!(DreamError::InvalidFraudProof);
+                };
+                require!(witness.approved, DreamError::PinningOracleNotApproved);
+                let message =
+                    dead_cid_attestation_message(&dream_record.key(), &dream_record.content_hash);
+                ed25519::verify_signature(
+                    instructions_sysvar,
+                    &witness.oracle,
+                    &message,
+                    DreamError::InvalidFraudProof,
+                )?;
+            }
+        }
+
+        let bond = optimistic_score.bond;
+        optimistic_score.voided = true;
+        optimistic_score.bond = 0;
+        optimistic_score.sub_lamports(bond)?;
+        ctx.accounts.challenger.add_lamports(bond)?;
+
+        emit!(OptimisticScoreVoidedEvent {
+            dream_record: optimistic_score.dream_record,
+            oracle: optimistic_score.oracle,
+            challenger: ctx.accounts.challenger.key(),
+            proof,
+            bond,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Permissionless once the challenge window has passed. Writes an
+    /// unchallenged optimistic score as the dream's `DreamValidation`,
+    /// attributed to the oracle, and refunds its bond. If the authority
+    /// validated the dream in the meantime, that verdict stands and only the
+    /// bond is refunded. Unlike `validate_dream`, no onboarding bonus or
+    /// validator reward is paid, but the interpreter's held-back share is
+    /// released the same way.
+    pub fn finalize_optimistic_score(ctx: Context<FinalizeOptimisticScore>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let optimistic_score = &ctx.accounts.optimistic_score;
+        require!(!optimistic_score.voided, DreamError::OptimisticScoreVoided);
+        require!(now >= optimistic_score.challenge_ends_at(), DreamError::ChallengeWindowOpen);
+
+        let validation = &mut ctx.accounts.dream_validation;
+        if validation.dream_record != Pubkey::default() {
+            return Ok(());
+        }
+        let score = optimistic_score.score;
+        validation.dream_record = optimistic_score.dream_record;
+        validation.validator = optimistic_score.oracle;
+        validation.score = score;
+        validation.validated_at = now;
+
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        dreamer_profile.adjust_reputation(DreamerProfile::validation_change(score));
+        dreamer_profile.validated_dreams += 1;
+
+        // The held-back share goes to an interpreter who attached an analysis,
+        // otherwise back to the dreamer
+        if !ctx.accounts.interpretation.data_is_empty() {
+            let mut interpretation = {
+                let data = ctx.accounts.interpretation.try_borrow_data()?;
+                DreamInterpretation::try_deserialize(&mut &data[..])?
+            };
+            let dream_reward = ctx.accounts.dream_record.token_reward;
+            let held = DreamInterpretation::interpreter_share(dream_reward);
+            let health = &mut ctx.accounts.treasury_health;
+            health.pending_coauthor_shares = health.pending_coauthor_shares.saturating_sub(held);
+            let to = if interpretation.analysis_cid.is_empty() {
+                ctx.accounts.dreamer_dream_account.to_account_info()
+            } else {
+                let interpreter_account = ctx
+                    .accounts
+                    .interpreter_dream_account
+                    .as_ref()
+                    .ok_or(DreamError::MissingInterpreterAccount)?;
+                require_keys_eq!(
+                    interpreter_account.owner,
+                    interpretation.interpreter,
+                    DreamError::MissingInterpreterAccount
+                );
+                require_keys_eq!(
+                    interpreter_account.mint,
+                    ctx.accounts.dream_mint.key(),
+                    DreamError::MissingInterpreterAccount
+                );
+                interpretation.reward_paid = held;
+                let mut data = ctx.accounts.interpretation.try_borrow_mut_data()?;
+                interpretation.try_serialize(&mut &mut data[..])?;
+                emit!(InterpreterPaidEvent {
+                    dream_record: validation.dream_record,
+                    interpreter: interpretation.interpreter,
+                    from_bonus: 0,
+                    amount: held,
+                    timestamp: now,
+                });
+                interpreter_account.to_account_info()
+            };
+            if held > 0 {
+                let cpi_accounts = token_2022::TransferChecked {
+                    from: ctx.accounts.reward_pool_vault.to_account_info(),
+                    mint: ctx.accounts.dream_mint.to_account_info(),
+                    to,
+                    authority: ctx.accounts.treasury.to_account_info(),
+                };
+                let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+                let signer_seeds = &[treasury_seeds];
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_2022::transfer_checked(cpi_ctx, held, ctx.accounts.dream_mint.decimals)?;
+                ctx.accounts.treasury.total_rewards_distributed += held;
+            }
+        }
+
+        emit!(DreamValidatedEvent {
+            dream_record: validation.dream_record,
+            dreamer: ctx.accounts.dream_record.dreamer,
+            validator: validation.validator,
+            score,
+            reputation: dreamer_profile.reputation,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Permissionless crank once a dream recorded with an interpreter has gone
+    /// unvalidated for REWARD_CLAIM_EXPIRY_SECS: returns the interpreter's
+    /// held-back share to the dreamer and closes the `DreamInterpretation`,
+    /// whose rent goes to the dreamer too.
+    pub fn expire_interpretation(ctx: Context<ExpireInterpretation>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let dream_record = &ctx.accounts.dream_record;
+        require!(
+            now >= dream_record.timestamp + REWARD_CLAIM_EXPIRY_SECS,
+            DreamError::RewardNotExpired
+        );
+        require!(
+            ctx.accounts.dream_validation.data_is_empty(),
+            DreamError::DreamAlreadyValidated
+        );
+
+        let refunded = DreamInterpretation::interpreter_share(dream_record.token_reward);
+        let health = &mut ctx.accounts.treasury_health;
+        health.pending_coauthor_shares = health.pending_coauthor_shares.saturating_sub(refunded);
+        if refunded > 0 {
+            let cpi_accounts = token_2022::TransferChecked {
+                from: ctx.accounts.reward_pool_vault.to_account_info(),
+                mint: ctx.accounts.dream_mint.to_account_info(),
+                to: ctx.accounts.dreamer_dream_account.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+            let signer_seeds = &[treasury_seeds];
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_2022::transfer_checked(cpi_ctx, refunded, ctx.accounts.dream_mint.decimals)?;
+            ctx.accounts.treasury.total_rewards_distributed += refunded;
+        }
+
+        emit!(InterpretationExpiredEvent {
+            dream_record: ctx.accounts.interpretation.dream_record,
+            interpreter: ctx.accounts.interpretation.interpreter,
+            refunded,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Turn the validator gate on or off. While on, `register_validator`
+    /// needs an Oneirobot of at least `min_dream_level` from `nft_program`,
+    /// and registrations that no longer meet it can be challenged.
+    pub fn set_validator_gate(
+        ctx: Context<SetValidatorGate>,
+        enabled: bool,
+        min_dream_level: u8,
+        nft_program: Pubkey,
+    ) -> Result<()> {
+        require!(min_dream_level <= 100, DreamError::InvalidValidatorGate);
+        let now = Clock::get()?.unix_timestamp;
+        let gate = &mut ctx.accounts.validator_gate;
+        gate.enabled = enabled;
+        gate.min_dream_level = min_dream_level;
+        gate.nft_program = nft_program;
+        gate.updated_at = now;
+
+        emit!(ValidatorGateSetEvent {
+            enabled,
+            min_dream_level,
+            nft_program,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Register the signer as a validator. With the gate on, the signer must
+    /// hold the Oneirobot in `nft_token_account` and its `nft_attributes` must
+    /// meet the gate's dream level. Validation itself stays with the treasury
+    /// authority until validators are wired in.
+    pub fn register_validator(ctx: Context<RegisterValidator>) -> Result<()> {
+        let validator = ctx.accounts.validator.key();
+        let (oneirobot_mint, dream_level) = match (
+            &ctx.accounts.nft_token_account,
+            &ctx.accounts.nft_attributes,
+        ) {
+            (Some(token_account), Some(attributes)) => {
+                require!(
+                    token_account.owner == validator && token_account.amount == 1,
+                    DreamError::ValidatorNotEligible
+                );
+                let gate = &ctx.accounts.validator_gate;
+                let level = oneirobot_dream_level(gate, attributes, &token_account.mint)?;
+                (token_account.mint, Some(level))
+            }
+            _ => (Pubkey::default(), None),
+        };
+        require!(
+            ctx.accounts.validator_gate.admits(dream_level),
+            DreamError::ValidatorNotEligible
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let registration = &mut ctx.accounts.validator_registration;
+        registration.validator = validator;
+        registration.oneirobot_mint = oneirobot_mint;
+        registration.registered_at = now;
+
+        emit!(ValidatorRegisteredEvent {
+            validator,
+            oneirobot_mint,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Permissionless. Closes a registration the validator gate no longer
+    /// admits, paying its rent to the challenger: the validator registered
+    /// without an Oneirobot, its Oneirobot now sits in someone else's
+    /// `nft_token_account`, or its dream level is below a raised minimum.
+    pub fn challenge_validator(ctx: Context<ChallengeValidator>) -> Result<()> {
+        let gate = &ctx.accounts.validator_gate;
+        let registration = &ctx.accounts.validator_registration;
+        let dream_level = if registration.oneirobot_mint == Pubkey::default() {
+            None
+        } else {
+            let (Some(token_account), Some(attributes)) =
+                (&ctx.accounts.nft_token_account, &ctx.accounts.nft_attributes)
+            else {
+                return err!(DreamError::InvalidOneirobotAccounts);
+            };
+            // An NFT's only funded token account is its holder's
+            require!(
+                token_account.mint == registration.oneirobot_mint && token_account.amount == 1,
+                DreamError::InvalidOneirobotAccounts
+            );
+            let level = oneirobot_dream_level(gate, attributes, &token_account.mint)?;
+            (token_account.owner == registration.validator).then_some(level)
+        };
+        require!(!gate.admits(dream_level), DreamError::ValidatorStillEligible);
+
+        emit!(ValidatorRevokedEvent {
+            validator: registration.validator,
+            challenger: ctx.accounts.challenger.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Authority only, standing in for governance. Sets the validators' slice
+    /// of market fees and the DREAM each credited validation pays, creating
+    /// the reward pool and its vault on first use.
+    pub fn set_validator_rewards(
+        ctx: Context<SetValidatorRewards>,
+        fee_share_bps: u16,
+        reward_per_validation: u64,
+    ) -> Result<()> {
+        require!(
+            fee_share_bps <= MAX_VALIDATOR_FEE_SHARE_BPS
+                && reward_per_validation <= MAX_VALIDATOR_REWARD_PER_VALIDATION,
+            DreamError::InvalidValidatorRewards
+        );
+        let pool = &mut ctx.accounts.validator_reward_pool;
+        pool.fee_share_bps = fee_share_bps;
+        pool.reward_per_validation = reward_per_validation;
+        Ok(())
+    }
+
+    /// Authority only. Caps what one validator can claim per epoch, so a
+    /// single farm can't drain the vault; 0 lifts the cap.
+    pub fn set_validator_claim_cap(
+        ctx: Context<SetValidatorClaimCap>,
+        epoch_claim_cap: u64,
+    ) -> Result<()> {
+        ctx.accounts.validator_reward_pool.epoch_claim_cap = epoch_claim_cap;
+        Ok(())
+    }
+
+    /// Pay a validator everything credited to them so far, up to what's left
+    /// of the epoch's claim cap. The rest stays claimable in later epochs.
+    pub fn claim_validator_rewards(ctx: Context<ClaimValidatorRewards>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let epoch = EpochStats::day_of(now);
+        let pool = &mut ctx.accounts.validator_reward_pool;
+        let rewards = &mut ctx.accounts.validator_rewards;
+        let amount = rewards.claimable_in(epoch, pool.epoch_claim_cap);
+        if amount == 0 {
+            let error = if rewards.claimable == 0 {
+                DreamError::NothingToClaim
+            } else {
+                DreamError::ValidatorClaimCapReached
+            };
+            return Err(error.into());
+        }
+        rewards.claimable -= amount;
+        rewards.claimed_in_epoch += amount;
+        pool.total_claimed += amount;
+        if pool.epoch_claim_cap > 0 && rewards.claimed_in_epoch == pool.epoch_claim_cap {
+            emit!(ValidatorClaimCapReachedEvent {
+                validator: rewards.validator,
+                epoch,
+                epoch_claim_cap: pool.epoch_claim_cap,
+                deferred: rewards.claimable,
+                timestamp: now,
+            });
+        }
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.validator_reward_vault.to_account_info(),
+            mint: ctx.accounts.dream_mint.to_account_info(),
+            to: ctx.accounts.validator_dream_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.dream_mint.decimals)?;
+
+        let rewards = &ctx.accounts.validator_rewards;
+        emit!(ValidatorRewardsClaimedEvent {
+            validator: rewards.validator,
+            amount,
+            total_earned: rewards.total_earned,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Name the OneirobotNFT program `splice_from_dreams` mints through.
+    pub fn set_splice_config(ctx: Context<SetSpliceConfig>, nft_program: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let splice_config = &mut ctx.accounts.splice_config;
+        splice_config.nft_program = nft_program;
+        splice_config.updated_at = now;
+
+        emit!(SpliceConfigSetEvent {
+            nft_program,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Gene-splicing: consume `SPLICE_DREAM_COUNT` of the signer's validated
+    /// dreams to mint an Oneirobot whose `dream_level` is their average
+    /// validation score and whose `lucid_power` is their longest daily streak.
+    /// Pass each dream as remaining accounts `(dream_record, dream_validation,
+    /// spliced_dream)`. The `SplicedDream` PDAs mark the dreams consumed. The
+    /// mint is a CPI signed by the splicer PDA, which is the only caller the
+    /// NFT program's `mint_spliced_oneirobot` accepts.
+    pub fn splice_from_dreams<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SpliceFromDreams<'info>>,
+        metadata_uri: String,
+        name: String,
+        symbol: String,
+    ) -> Result<()> {
+        let dreams = ctx.remaining_accounts;
+        require!(
+            dreams.len() == 3 * SPLICE_DREAM_COUNT,
+            DreamError::InvalidSpliceDreams
+        );
+        let dreamer = ctx.accounts.dreamer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let oneirobot_mint = ctx.accounts.oneirobot_mint.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let mut dream_records = Vec::with_capacity(SPLICE_DREAM_COUNT);
+        let mut scores = Vec::with_capacity(SPLICE_DREAM_COUNT);
+        let mut days = Vec::with_capacity(SPLICE_DREAM_COUNT);
+        for accounts in dreams.chunks(3) {
+            let (record, validation, spliced) = (&accounts[0], &accounts[1], &accounts[2]);
+            let dream_record = Account::<DreamRecord>::try_from(record)?;
+            let dream_validation = Account::<DreamValidation>::try_from(validation)?;
+            require!(
+                dream_record.dreamer == dreamer.key()
+                    && dream_validation.dream_record == record.key(),
+                DreamError::InvalidSpliceDreams
+            );
+
+            // Creating the marker fails for a dream spliced before or listed twice
+            create_spliced_dream(spliced, &dreamer, &system_program, &record.key())?;
+            let spliced_dream = SplicedDream {
+                dream_record: record.key(),
+                oneirobot_mint,
+                spliced_at: now,
+            };
+            spliced_dream.try_serialize(&mut &mut spliced.try_borrow_mut_data()?[..])?;
+
+            dream_records.push(record.key());
+            scores.push(dream_validation.score);
+            days.push(EpochStats::day_of(dream_record.timestamp));
+        }
+        let (dream_level, lucid_power) = oneirobot::spliced_attributes(&scores, &days);
+
+        let splicer_seeds: &[&[u8]] = &[b"splicer", &[ctx.bumps.splicer]];
+        let accounts = splice::MintOneirobotAccounts {
+            nft_program: &ctx.accounts.nft_program.to_account_info(),
+            oneirobot_state: &ctx.accounts.oneirobot_state.to_account_info(),
+            nft_attributes: &ctx.accounts.nft_attributes.to_account_info(),
+            mint: &ctx.accounts.oneirobot_mint.to_account_info(),
+            token_account: &ctx.accounts.token_account.to_account_info(),
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            master_edition: &ctx.accounts.master_edition.to_account_info(),
+            dreamer: &dreamer,
+            splicer: &ctx.accounts.splicer.to_account_info(),
+            fee_vault: &ctx.accounts.fee_vault.to_account_info(),
+            treasury: &ctx.accounts.treasury.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+            system_program: &system_program,
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            metadata_program: &ctx.accounts.metadata_program.to_account_info(),
+        };
+        let args = splice::MintSplicedOneirobotData {
+            metadata_uri,
+            name,
+            symbol,
+            dream_level,
+            lucid_power,
+        };
+        splice::mint_spliced_oneirobot(&accounts, args, &[splicer_seeds])?;
+
+        emit!(OneirobotSplicedEvent {
+            dreamer: dreamer.key(),
+            oneirobot_mint,
+            dream_records,
+            dream_level,
+            lucid_power,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Mint a commemorative Oneirobot for one of the signer's dreams scored
+    /// at least CERTIFICATE_MIN_SCORE. The NFT program's
+    /// `mint_certificate_oneirobot` gives it the certificate quantum core and
+    /// the score as `dream_level`; the `DreamCertificate` PDA links it to the
+    /// record, so each dream is certified once.
+    pub fn claim_dream_certificate(
+        ctx: Context<ClaimDreamCertificate>,
+        metadata_uri: String,
+        name: String,
+        symbol: String,
+    ) -> Result<()> {
+        let score = ctx.accounts.dream_validation.score;
+        require!(
+            score >= CERTIFICATE_MIN_SCORE,
+            DreamError::ScoreBelowCertificateThreshold
+        );
+        let dreamer = ctx.accounts.dreamer.key();
+        let dream_record = ctx.accounts.dream_record.key();
+        let oneirobot_mint = ctx.accounts.oneirobot_mint.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let splicer_seeds: &[&[u8]] = &[b"splicer", &[ctx.bumps.splicer]];
+        let accounts = splice::MintOneirobotAccounts {
+            nft_program: &ctx.accounts.nft_program.to_account_info(),
+            oneirobot_state: &ctx.accounts.oneirobot_state.to_account_info(),
+            nft_attributes: &ctx.accounts.nft_attributes.to_account_info(),
+            mint: &ctx.accounts.oneirobot_mint.to_account_info(),
+            token_account: &ctx.accounts.token_account.to_account_info(),
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            master_edition: &ctx.accounts.master_edition.to_account_info(),
+            dreamer: &ctx.accounts.dreamer.to_account_info(),
+            splicer: &ctx.accounts.splicer.to_account_info(),
+            fee_vault: &ctx.accounts.fee_vault.to_account_info(),
+            treasury: &ctx.accounts.treasury.to_account_info(),
+            rent: &ctx.accounts.rent.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            metadata_program: &ctx.accounts.metadata_program.to_account_info(),
+        };
+        let args = splice::MintCertificateOneirobotData {
+            metadata_uri,
+            name,
+            symbol,
+            score,
+        };
+        splice::mint_certificate_oneirobot(&accounts, args, &[splicer_seeds])?;
+
+        let certificate = &mut ctx.accounts.certificate;
+        certificate.dream_record = dream_record;
+        certificate.dreamer = dreamer;
+        certificate.oneirobot_mint = oneirobot_mint;
+        certificate.score = score;
+        certificate.certified_at = now;
+
+        emit!(DreamCertifiedEvent {
+            dreamer,
+            dream_record,
+            oneirobot_mint,
+            score,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Equip an Oneirobot the dreamer holds as their companion. Until they
+    /// equip another, `record_dream` stamps it on their records and credits
+    /// it COMPANION_XP_PER_DREAM per dream. Only Oneirobots of the splice
+    /// config's NFT program qualify.
+    pub fn equip_oneirobot_to_profile(ctx: Context<EquipOneirobotToProfile>) -> Result<()> {
+        let dreamer = ctx.accounts.dreamer.key();
+        let token_account = &ctx.accounts.nft_token_account;
+        require!(
+            token_account.owner == dreamer && token_account.amount == 1,
+            DreamError::CompanionNotHeld
+        );
+        let nft_program = ctx.accounts.splice_config.nft_program;
+        let attributes = &ctx.accounts.nft_attributes;
+        let mint = token_account.mint;
+        require!(
+            *attributes.owner == nft_program
+                && attributes.key() == OneirobotAttributes::address(&nft_program, &mint)
+                && OneirobotAttributes::try_from_data(&attributes.try_borrow_data()?).is_some(),
+            DreamError::InvalidOneirobotAccounts
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let companion = &mut ctx.accounts.companion;
+        companion.dreamer = dreamer;
+        companion.mint = mint;
+        companion.equipped_at = now;
+        let companion_xp = &mut ctx.accounts.companion_xp;
+        companion_xp.mint = mint;
+
+        emit!(CompanionEquippedEvent {
+            dreamer,
+            mint,
+            xp: companion_xp.xp,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Authority only. Flags a dream as spam, plagiarism, or as the losing
+    /// side of a dispute, costing its dreamer reputation. The `DreamFlag` PDA
+    /// makes a second flag on the same dream fail.
+    pub fn flag_dream(ctx: Context<FlagDream>, reason: DreamFlagReason) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let flag = &mut ctx.accounts.dream_flag;
+        flag.dream_record = ctx.accounts.dream_record.key();
+        flag.reason = reason;
+        flag.flagged_by = ctx.accounts.authority.key();
+        flag.flagged_at = now;
+
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        dreamer_profile.adjust_reputation(-REPUTATION_FLAG_PENALTY);
+
+        emit!(DreamFlaggedEvent {
+            dream_record: flag.dream_record,
+            dreamer: ctx.accounts.dream_record.dreamer,
+            reason,
+            reputation: dreamer_profile.reputation,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Starts today's `SleepSession` with the sleep tracker's attestation.
+    /// Recording a dream starts it too; every dream recorded today shares its
+    /// reward cap.
+    pub fn open_session(ctx: Context<OpenSession>, device_attestation: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let session = &mut ctx.accounts.sleep_session;
+        require!(session.ended_at == 0, DreamError::SessionClosed);
+        session.start(ctx.accounts.dreamer.key(), EpochStats::day_of(now), now);
+        session.device_attestation = device_attestation;
+        Ok(())
+    }
+
+    /// Ends the dreamer's session of `day`. Dreams can still be recorded
+    /// against today's closed session, since they are usually written down
+    /// after waking. A past day's session takes no more dreams, so closing it
+    /// also closes the account and returns its rent to the dreamer.
+    pub fn close_session(ctx: Context<CloseSession>, day: u32) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let past_day = day < EpochStats::day_of(now);
+        let session = &mut ctx.accounts.sleep_session;
+        if session.ended_at == 0 {
+            session.ended_at = now;
+            emit!(SleepSessionClosedEvent {
+                session: session.key(),
+                dreamer: session.dreamer,
+                session_id: session.session_id,
+                started_at: session.started_at,
+                ended_at: session.ended_at,
+                dream_count: session.dream_count,
+                rewards_earned: session.rewards_earned,
+            });
+        } else {
+            require!(past_day, DreamError::SessionClosed);
+        }
+
+        if past_day {
+            session.close(ctx.accounts.dreamer.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    /// Stores an IPFS-hosted dream interface under the dreamer's next
+    /// interface index.
+    pub fn interface_dream(ctx: Context<InterfaceDream>, ipfs_hash: String) -> Result<()> {
+        require!(
+            ipfs_hash.len() <= DreamInterface::MAX_IPFS_HASH_LEN,
+            DreamError::DreamContentTooLarge
+        );
+
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        let dream_interface = &mut ctx.accounts.dream_interface;
+        let clock = Clock::get()?;
+        
+        dream_interface.dreamer = ctx.accounts.dreamer.key();
+        dream_interface.index = dreamer_profile.interface_count;
+        dream_interface.ipfs_hash = ipfs_hash;
+        dream_interface.timestamp = clock.unix_timestamp;
+        dream_interface.access_level = 1; // Basic access
+        
+        // Interface indices are never reused, so closed interfaces don't collide with new ones
+        dreamer_profile.set_dreamer(ctx.accounts.dreamer.key(), clock.unix_timestamp);
+        dreamer_profile.interface_count += 1;
+        
+        msg!("Dream interfaced via IPFS: {}, Index: {}", dream_interface.ipfs_hash, dream_interface.index);
+        Ok(())
+    }
+
+    /// Replaces the IPFS hash of one of the dreamer's interfaces.
+    pub fn update_interface(
+        ctx: Context<UpdateInterface>,
+        interface_index: u64,
+        ipfs_hash: String,
+    ) -> Result<()> {
+        require!(
+            ipfs_hash.len() <= DreamInterface::MAX_IPFS_HASH_LEN,
+            DreamError::DreamContentTooLarge
+        );
+
+        let dream_interface = &mut ctx.accounts.dream_interface;
+        dream_interface.ipfs_hash = ipfs_hash;
+        dream_interface.timestamp = Clock::get()?.unix_timestamp;
+        
+        msg!("Dream interface {} updated: {}", interface_index, dream_interface.ipfs_hash);
+        Ok(())
+    }
+
+    /// Closes a dream interface, refunding its rent to the dreamer.
+    pub fn close_interface(_ctx: Context<CloseInterface>, interface_index: u64) -> Result<()> {
+        msg!("Dream interface {} closed", interface_index);
+        Ok(())
+    }
+
+    /// Stakes are keyed by mint, so a user holds one stake per staked asset.
+    /// Only the LUCID stake counts toward queue priority and dreamer stats.
+    pub fn stake_for_lucid_access(ctx: Context<StakeLucid>, amount: u64) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        let clock = Clock::get()?;
+        
+        // Transfer tokens to stake account
+        let cpi_accounts = token_2022::Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        
+        token_2022::transfer(cpi_ctx, amount)?;
+        
+        stake_account.user = ctx.accounts.user.key();
+        stake_account.mint = ctx.accounts.stake_mint.key();
+        stake_account.amount = amount;
+        stake_account.timestamp = clock.unix_timestamp;
+        stake_account.access_level = calculate_access_level(amount);
+        
+        emit!(LucidStakedEvent {
+            user: stake_account.user,
+            mint: stake_account.mint,
+            amount,
+            access_level: stake_account.access_level,
+            timestamp: stake_account.timestamp,
+        });
+        
+        msg!("LUCID tokens staked: {}, Access level: {}", amount, stake_account.access_level);
+        Ok(())
+    }
+
+    /// Move a stake from its pre-mint `[b"stake", user]` address to the LUCID
+    /// stake address, closing the old account. Staked tokens stay in their vault.
+    pub fn migrate_stake(ctx: Context<MigrateStake>) -> Result<()> {
+        let legacy = {
+            let data = ctx.accounts.legacy_stake.try_borrow_data()?;
+            let discriminator = <LucidStake as anchor_lang::Discriminator>::DISCRIMINATOR;
+            require!(data.get(..8) == Some(&discriminator[..]), DreamError::InvalidLegacyStake);
+            LucidStakeV1::deserialize(&mut &data[8..])
+                .map_err(|_| error!(DreamError::InvalidLegacyStake))?
+        };
+        require_keys_eq!(legacy.user, ctx.accounts.user.key(), DreamError::InvalidLegacyStake);
+
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.user = legacy.user;
+        stake_account.mint = ctx.accounts.treasury.lucid_mint;
+        stake_account.amount = legacy.amount;
+        stake_account.timestamp = legacy.timestamp;
+        stake_account.access_level = legacy.access_level;
+
+        // Close the legacy account the way Anchor's `close` constraint does
+        let legacy_stake = ctx.accounts.legacy_stake.to_account_info();
+        let user = ctx.accounts.user.to_account_info();
+        **user.try_borrow_mut_lamports()? += legacy_stake.lamports();
+        **legacy_stake.try_borrow_mut_lamports()? = 0;
+        legacy_stake.assign(&system_program::ID);
+        legacy_stake.realloc(0, false)?;
+
+        emit!(StakeMigratedEvent {
+            user: legacy.user,
+            legacy_stake: ctx.accounts.legacy_stake.key(),
+            mint: stake_account.mint,
+            amount: legacy.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Stake migrated: {}", legacy.amount);
+        Ok(())
+    }
+
+    /// Authority only. Create the credential mint of one of
+    /// `TIER_CREDENTIAL_LEVELS`: a non-transferable, zero-decimal Token-2022
+    /// mint with the treasury as mint authority and permanent delegate.
+    pub fn create_tier_credential_mint(
+        ctx: Context<CreateTierCredentialMint>,
+        access_level: u8,
+    ) -> Result<()> {
+        require!(
+            TIER_CREDENTIAL_LEVELS.contains(&access_level),
+            DreamError::InvalidCredentialLevel
+        );
+        use spl_token_2022::{extension::ExtensionType, state};
+        let space = ExtensionType::try_calculate_account_len::<state::Mint>(&[
+            ExtensionType::NonTransferable,
+            ExtensionType::PermanentDelegate,
+        ])?;
+        let mint = ctx.accounts.credential_mint.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let mint_seeds: &[&[u8]] =
+            &[b"tier_credential", &[access_level], &[ctx.bumps.credential_mint]];
+        let signer_seeds = &[mint_seeds];
+        let cpi_accounts = system_program::CreateAccount {
+            from: ctx.accounts.authority.to_account_info(),
+            to: mint.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        let lamports = Rent::get()?.minimum_balance(space);
+        system_program::create_account(cpi_ctx, lamports, space as u64, &token_2022::ID)?;
+
+        let treasury = ctx.accounts.treasury.key();
+        let cpi_accounts = NonTransferableMintInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        };
+        non_transferable_mint_initialize(CpiContext::new(token_program.clone(), cpi_accounts))?;
+        let cpi_accounts = PermanentDelegateInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
+        permanent_delegate_initialize(cpi_ctx, &treasury)?;
+        let cpi_accounts = token_2022::InitializeMint2 { mint };
+        let cpi_ctx = CpiContext::new(token_program, cpi_accounts);
+        token_2022::initialize_mint2(cpi_ctx, 0, &treasury, Some(&treasury))?;
+
+        msg!("Tier credential mint created for access level {}", access_level);
+        Ok(())
+    }
+
+    /// Mint the user the credential of each tier their LUCID stake reaches
+    /// and burn the ones it no longer does. Anyone can crank it, so apps can
+    /// token-gate on the credentials instead of reading stake accounts.
+    pub fn sync_tier_credentials(ctx: Context<SyncTierCredentials>) -> Result<()> {
+        let user = ctx.accounts.user.key();
+        let stake = &ctx.accounts.lucid_stake;
+        let access_level = if stake.data_is_empty() {
+            0
+        } else {
+            let stake = LucidStake::try_deserialize(&mut &stake.try_borrow_data()?[..])?;
+            calculate_access_level(stake.amount)
+        };
+
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let now = Clock::get()?.unix_timestamp;
+        let credentials = [
+            (&ctx.accounts.vip_credential_mint, &ctx.accounts.vip_credential_account),
+            (&ctx.accounts.quantum_credential_mint, &ctx.accounts.quantum_credential_account),
+        ];
+        for (level, (mint, account)) in TIER_CREDENTIAL_LEVELS.into_iter().zip(credentials) {
+            let held = access_level >= level;
+            if held == (account.amount > 0) {
+                continue;
+            }
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            if held {
+                let cpi_accounts = token_2022::MintTo {
+                    mint: mint.to_account_info(),
+                    to: account.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_2022::mint_to(cpi_ctx, 1)?;
+            } else {
+                // The treasury is the mint's permanent delegate
+                let cpi_accounts = token_2022::Burn {
+                    mint: mint.to_account_info(),
+                    from: account.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_2022::burn(cpi_ctx, account.amount)?;
+            }
+            emit!(TierCredentialUpdatedEvent { user, access_level: level, held, timestamp: now });
+        }
+        Ok(())
+    }
+
+    /// Creates the compressed stake tree with the root of an empty tree.
+    pub fn initialize_compressed_stake_tree(
+        ctx: Context<InitializeCompressedStakeTree>,
+    ) -> Result<()> {
+        ctx.accounts.compressed_stake_tree.load_init()?.root = stake_tree::root(&[]);
+        Ok(())
+    }
+
+    /// Stake SMIND as a leaf of the compressed stake tree instead of a rent-paying
+    /// account. Indexers rebuild the leaves from `CompressedStakedEvent`.
+    pub fn compressed_stake(ctx: Context<CompressedStake>, amount: u64) -> Result<()> {
+        require!(amount > 0, DreamError::InvalidStakeAmount);
+        let staker = ctx.accounts.staker.key();
+        let staked_at = Clock::get()?.unix_timestamp;
+        let mut tree = ctx.accounts.compressed_stake_tree.load_mut()?;
+        let leaf_index = tree
+            .append(stake_tree::stake_leaf(&staker, amount, staked_at))
+            .ok_or(DreamError::StakeTreeFull)?;
+        tree.total_staked += amount;
+        tree.active_stakes += 1;
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.staker_smind_account.to_account_info(),
+            mint: ctx.accounts.smind_mint.to_account_info(),
+            to: ctx.accounts.compressed_stake_vault.to_account_info(),
+            authority: ctx.accounts.staker.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.smind_mint.decimals)?;
+
+        emit!(CompressedStakedEvent {
+            staker,
+            leaf_index,
+            amount,
+            staked_at,
+            total_staked: tree.total_staked,
+        });
+        Ok(())
+    }
+
+    /// Withdraw a compressed stake in full. `proof` must be against the current
+    /// root, so it has to be rebuilt if another stake landed first.
+    pub fn compressed_unstake(
+        ctx: Context<CompressedUnstake>,
+        leaf_index: u64,
+        amount: u64,
+        staked_at: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let staker = ctx.accounts.staker.key();
+        let leaf = stake_tree::stake_leaf(&staker, amount, staked_at);
+        let mut tree = ctx.accounts.compressed_stake_tree.load_mut()?;
+        require!(
+            tree.replace(leaf_index, &leaf, stake_tree::EMPTY_LEAF, &proof),
+            DreamError::InvalidStakeProof
+        );
+        tree.total_staked -= amount;
+        tree.active_stakes -= 1;
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.compressed_stake_vault.to_account_info(),
+            mint: ctx.accounts.smind_mint.to_account_info(),
+            to: ctx.accounts.staker_smind_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.smind_mint.decimals)?;
+
+        emit!(CompressedUnstakedEvent {
+            staker,
+            leaf_index,
+            amount,
+            total_staked: tree.total_staked,
+        });
+        Ok(())
+    }
+
+    /// Creates the dream archive tree with the root of an empty tree.
+    pub fn initialize_dream_archive_tree(ctx: Context<InitializeDreamArchiveTree>) -> Result<()> {
+        ctx.accounts.dream_archive_tree.load_init()?.root = stake_tree::root(&[]);
+        Ok(())
+    }
+
+    /// Move a validated dream at least DREAM_ARCHIVE_MIN_AGE_SECS old into the
+    /// archive tree: its hash becomes a leaf, the record closes with its rent
+    /// going back to the dreamer, and an `ArchivedDream` stub keeps the leaf.
+    pub fn archive_dream(ctx: Context<ArchiveDream>) -> Result<()> {
+        let dream_record = &ctx.accounts.dream_record;
+        let timestamp = Clock::get()?.unix_timestamp;
+        require!(
+            timestamp >= dream_record.timestamp + DREAM_ARCHIVE_MIN_AGE_SECS,
+            DreamError::DreamTooRecentToArchive
+        );
+
+        let record_data = dream_record.to_account_info().try_borrow_data()?.to_vec();
+        let leaf = stake_tree::archived_dream_leaf(&record_data);
+        let leaf_index = ctx
+            .accounts
+            .dream_archive_tree
+            .load_mut()?
+            .append(leaf)
+            .ok_or(DreamError::DreamArchiveTreeFull)?;
+
+        let archived_dream = &mut ctx.accounts.archived_dream;
+        archived_dream.dreamer = dream_record.dreamer;
+        archived_dream.dream_id = dream_record.id;
+        archived_dream.leaf_index = leaf_index;
+        archived_dream.leaf = leaf;
+        archived_dream.archived_at = timestamp;
+
+        emit!(DreamArchivedEvent {
+            dreamer: dream_record.dreamer,
+            dream_id: dream_record.id,
+            dream_record: dream_record.key(),
+            leaf_index,
+            leaf,
+            record_data,
+            timestamp,
+        });
+
+        msg!("Dream {} archived at leaf {}", dream_record.id, leaf_index);
+        Ok(())
+    }
+
+    /// Permissionless one-off for treasuries created before the bump was
+    /// stored; every other treasury constraint checks the stored bump.
+    pub fn store_treasury_bump(ctx: Context<StoreTreasuryBump>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        require!(treasury.bump == 0, DreamError::TreasuryBumpAlreadyStored);
+        treasury.bump = ctx.bumps.treasury;
+        Ok(())
+    }
+
+    /// Authority only. Toggles the treasury's MEV protection flag.
+    pub fn set_mev_protection(ctx: Context<SetMevProtection>, enabled: bool) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(
+            ctx.accounts.authority.key() == treasury.authority,
+            DreamError::UnauthorizedAuthority
+        );
+
+        treasury.mev_protection_enabled = enabled;
+
+        emit!(MevProtectionUpdatedEvent {
+            authority: treasury.authority,
+            enabled,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("MEV protection enabled: {}", enabled);
+        Ok(())
+    }
+
+    /// Moves a dreamer's pre-Anchor `DreamStorage` account into their
+    /// `DreamerProfile`.
+    pub fn migrate_legacy_storage(ctx: Context<MigrateLegacyStorage>) -> Result<()> {
+        let legacy_storage = DreamStorage::deserialize(
+            &mut &ctx.accounts.legacy_storage.try_borrow_data()?[..],
+        )
+        .map_err(|_| error!(DreamError::InvalidLegacyStorage))?;
+
+        require!(legacy_storage.is_initialized, DreamError::InvalidLegacyStorage);
+        require!(
+            legacy_storage.dreamer == ctx.accounts.dreamer.key(),
+            DreamError::InvalidLegacyStorage
+        );
+
+        let dreamer_profile = &mut ctx.accounts.dreamer_profile;
+        require!(!dreamer_profile.legacy_migrated, DreamError::LegacyAlreadyMigrated);
+
+        // Legacy rewards were only ever counted, never minted, so migration imports
+        // the counters without issuing DREAM.
+        let now = Clock::get()?.unix_timestamp;
+        dreamer_profile.set_dreamer(ctx.accounts.dreamer.key(), now);
+        dreamer_profile.legacy_migrated = true;
+        dreamer_profile.legacy_dream_count = legacy_storage.dream_count;
+        dreamer_profile.legacy_tokens_earned = legacy_storage.total_tokens_earned;
+
+        emit!(LegacyStorageMigratedEvent {
+            dreamer: ctx.accounts.dreamer.key(),
+            legacy_storage: ctx.accounts.legacy_storage.key(),
+            dream_count: legacy_storage.dream_count,
+            tokens_earned: legacy_storage.total_tokens_earned,
+            timestamp: now,
+        });
+
+        msg!("Legacy storage migrated: {} dreams", legacy_storage.dream_count);
+        Ok(())
+    }
+
+    /// Grow a profile written before profiles recorded their creation time.
+    /// Anyone can migrate one; `payer` covers the extra rent. The profile's
+    /// age counts from the migration, and earlier validations aren't counted.
+    pub fn migrate_dreamer_profile(ctx: Context<MigrateDreamerProfile>) -> Result<()> {
+        let profile = ctx.accounts.dreamer_profile.to_account_info();
+        let space = 8 + std::mem::size_of::<DreamerProfile>();
+        require!(profile.data_len() < space, DreamError::DreamerProfileCurrent);
+        let legacy = {
+            let data = profile.try_borrow_data()?;
+            let discriminator = <DreamerProfile as anchor_lang::Discriminator>::DISCRIMINATOR;
+            require!(
+                data.get(..8) == Some(&discriminator[..]),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            DreamerProfileV1::deserialize(&mut &data[8..])?
+        };
+
+        let rent = Rent::get()?.minimum_balance(space).saturating_sub(profile.lamports());
+        if rent > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: profile.clone(),
+                },
+            );
+            system_program::transfer(cpi_ctx, rent)?;
+        }
+        profile.realloc(space, true)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let migrated = DreamerProfile {
+            dreamer: legacy.dreamer,
+            interface_count: legacy.interface_count,
+            legacy_migrated: legacy.legacy_migrated,
+            legacy_dream_count: legacy.legacy_dream_count,
+            legacy_tokens_earned: legacy.legacy_tokens_earned,
+            last_active_day: legacy.last_active_day,
+            research_consent: legacy.research_consent,
+            reputation: legacy.reputation,
+            account_created_at: now,
+            validated_dreams: 0,
+        };
+        migrated.try_serialize(&mut &mut profile.try_borrow_mut_data()?[..])?;
+
+        emit!(DreamerProfileMigratedEvent {
+            dreamer: legacy.dreamer,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Grow a record written before records linked their `SleepSession`, so
+    /// it loads as a `DreamRecord` again. Anyone can migrate one; `payer`
+    /// covers the extra rent. Its `session` reads as the default key.
+    pub fn migrate_dream_record(ctx: Context<MigrateDreamRecord>) -> Result<()> {
+        let record = ctx.accounts.dream_record.to_account_info();
+        require!(record.data_len() < DreamRecord::EXTRAS_OFFSET, DreamError::DreamRecordCurrent);
+        {
+            let data = record.try_borrow_data()?;
+            let discriminator = <DreamRecord as anchor_lang::Discriminator>::DISCRIMINATOR;
+            require!(
+                data.get(..8) == Some(&discriminator[..]),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+        }
+
+        let space = DreamRecord::EXTRAS_OFFSET;
+        let rent = Rent::get()?.minimum_balance(space).saturating_sub(record.lamports());
+        if rent > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: record.clone(),
+                },
+            );
+            system_program::transfer(cpi_ctx, rent)?;
+        }
+        record.realloc(space, true)?;
+
+        emit!(DreamRecordMigratedEvent {
+            dream_record: record.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Register the bridge contract on another Wormhole chain. Messages from it
+    /// are accepted by `complete_bridge_in` and outbound transfers may target it.
+    pub fn register_foreign_emitter(
+        ctx: Context<RegisterForeignEmitter>,
+        chain: u16,
+        address: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury.authority,
+            DreamError::UnauthorizedAuthority
+        );
+        require!(
+            chain != 0 && chain != WORMHOLE_CHAIN_ID_SOLANA && address != [0u8; 32],
+            DreamError::InvalidForeignEmitter
+        );
+
+        let foreign_emitter = &mut ctx.accounts.foreign_emitter;
+        foreign_emitter.chain = chain;
+        foreign_emitter.address = address;
+
+        msg!("Foreign emitter registered for chain {}", chain);
+        Ok(())
+    }
+
+    /// Publish the DREAM mint's metadata so the EVM side can deploy wrapped DREAM.
+    pub fn attest_dream_mint(ctx: Context<AttestDreamMint>, nonce: u32) -> Result<()> {
+        let payload = BridgeAttestation {
+            token_address: ctx.accounts.dream_mint.key().to_bytes(),
+            token_chain: WORMHOLE_CHAIN_ID_SOLANA,
+            decimals: ctx.accounts.dream_mint.decimals,
+            symbol: fixed_bytes32(DREAM_TOKEN_SYMBOL),
+            name: fixed_bytes32(DREAM_TOKEN_NAME),
+        }
+        .encode();
+
+        post_bridge_message(
+            &ctx.accounts.wormhole,
+            &ctx.accounts.payer,
+            &mut ctx.accounts.treasury,
+            &ctx.bumps.wormhole,
+            nonce,
+            payload,
+        )?;
+
+        msg!("DREAM mint attested");
+        Ok(())
+    }
+
+    /// Lock DREAM in the program custody account and post a transfer message
+    /// for the registered bridge contract on `recipient_chain` to mint wrapped DREAM.
+    pub fn bridge_dream_out(
+        ctx: Context<BridgeDreamOut>,
+        amount: u64,
+        recipient_chain: u16,
+        recipient: [u8; 32],
+        nonce: u32,
+    ) -> Result<()> {
+        require!(amount > 0, DreamError::InvalidBridgeAmount);
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.sender_dream_account.to_account_info(),
+            mint: ctx.accounts.dream_mint.to_account_info(),
+            to: ctx.accounts.dream_custody.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token_2022::transfer_checked(cpi_ctx, amount, ctx.accounts.dream_mint.decimals)?;
+
+        let payload = BridgeTransfer {
+            amount,
+            token_address: ctx.accounts.dream_mint.key().to_bytes(),
+            token_chain: WORMHOLE_CHAIN_ID_SOLANA,
+            recipient,
+            recipient_chain,
+        }
+        .encode();
+
+        let sequence = bridge::next_sequence(&ctx.accounts.wormhole.sequence)?;
+        post_bridge_message(
+            &ctx.accounts.wormhole,
+            &ctx.accounts.sender,
+            &mut ctx.accounts.treasury,
+            &ctx.bumps.wormhole,
+            nonce,
+            payload,
+        )?;
+
+        ctx.accounts.treasury.total_bridged_out += amount;
+
+        emit!(DreamBridgedOutEvent {
+            sender: ctx.accounts.sender.key(),
+            amount,
+            recipient_chain,
+            recipient,
+            sequence,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Bridged {} DREAM base units to chain {}, sequence {}",
+            amount,
+            recipient_chain,
+            sequence
+        );
+        Ok(())
+    }
+
+    /// Release DREAM from custody for a transfer verified by the Wormhole guardians.
+    /// The VAA must already be posted to the core bridge; each (chain, sequence)
+    /// can be redeemed once.
+    pub fn complete_bridge_in(
+        ctx: Context<CompleteBridgeIn>,
+        emitter_chain: u16,
+        sequence: u64,
+    ) -> Result<()> {
+        let vaa = bridge::verify_posted_vaa(
+            &ctx.accounts.posted_vaa,
+            &ctx.accounts.foreign_emitter,
+            emitter_chain,
+            sequence,
+        )?;
+
+        let transfer = BridgeTransfer::decode(&vaa.payload).ok_or(DreamError::InvalidBridgeMessage)?;
+        require!(
+            transfer.token_chain == WORMHOLE_CHAIN_ID_SOLANA
+                && transfer.token_address == ctx.accounts.dream_mint.key().to_bytes()
+                && transfer.recipient_chain == WORMHOLE_CHAIN_ID_SOLANA
+                && transfer.recipient == ctx.accounts.recipient.key().to_bytes(),
+            DreamError::InvalidBridgeMessage
+        );
+        require!(transfer.amount > 0, DreamError::InvalidBridgeAmount);
+
+        let cpi_accounts = token_2022::TransferChecked {
+            from: ctx.accounts.dream_custody.to_account_info(),
+            mint: ctx.accounts.dream_mint.to_account_info(),
+            to: ctx.accounts.recipient_dream_account.to_account_info(),
+            authority: ctx.accounts.treasury.to_account_info(),
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_2022::transfer_checked(cpi_ctx, transfer.amount, ctx.accounts.dream_mint.decimals)?;
+
+        let clock = Clock::get()?;
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.emitter_chain = emitter_chain;
+        receipt.sequence = sequence;
+        receipt.timestamp = clock.unix_timestamp;
+
+        ctx.accounts.treasury.total_bridged_in += transfer.amount;
+
+        emit!(DreamBridgedInEvent {
+            recipient: ctx.accounts.recipient.key(),
+            amount: transfer.amount,
+            emitter_chain,
+            sequence,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Redeemed {} DREAM base units from chain {}, sequence {}",
+            transfer.amount,
+            emitter_chain,
+            sequence
+        );
+        Ok(())
+    }
+
+    /// Mirror a dream recorded on another chain's deployment, proven by a
+    /// posted VAA from that chain's registered emitter. The mirrored record
+    /// shares the native id sequence so stats stay unified; the reward was
+    /// already paid on the origin chain, so none is minted here.
+    pub fn record_mirrored_dream(
+        ctx: Context<RecordMirroredDream>,
+        emitter_chain: u16,
+        sequence: u64,
+    ) -> Result<()> {
+        let vaa = bridge::verify_posted_vaa(
+            &ctx.accounts.posted_vaa,
+            &ctx.accounts.foreign_emitter,
+            emitter_chain,
+            sequence,
+        )?;
+        let mirror = DreamMirror::decode(&vaa.payload).ok_or(DreamError::InvalidBridgeMessage)?;
+        require!(
+            mirror.dreamer == ctx.accounts.dreamer.key().to_bytes(),
+            DreamError::InvalidBridgeMessage
+        );
+
+        let treasury = &mut ctx.accounts.treasury;
+        let dream_record = &mut ctx.accounts.dream_record;
+        let clock = Clock::get()?;
+
+        dream_record.id = treasury.total_dreams_recorded;
+        dream_record.dreamer = ctx.accounts.dreamer.key();
+        dream_record.content_hash = mirror.content_hash;
+        dream_record.timestamp = clock.unix_timestamp;
+        dream_record.token_reward = 0;
+        dream_record.mev_protected = false;
+        dream_record.origin_chain = emitter_chain;
+
+        treasury.total_dreams_recorded += 1;
+        treasury.total_dreams_mirrored += 1;
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.emitter_chain = emitter_chain;
+        receipt.sequence = sequence;
+        receipt.timestamp = clock.unix_timestamp;
+
+        emit!(DreamRecordedEvent {
+            id: dream_record.id,
+            dreamer: dream_record.dreamer,
+            content_hash: dream_record.content_hash,
+            token_reward: 0,
+            mev_protected: false,
+            timestamp: dream_record.timestamp,
+            metadata: dream_record.metadata,
+        });
+        emit!(DreamMirroredEvent {
+            id: dream_record.id,
+            dreamer: dream_record.dreamer,
+            content_hash: dream_record.content_hash,
+            origin_chain: emitter_chain,
+            origin_dream_id: mirror.dream_id,
+            sequence,
+            timestamp: dream_record.timestamp,
+        });
+
+        msg!("Dream mirrored from chain {}! ID: {}", emitter_chain, dream_record.id);
+        Ok(())
+    }
+
+    /// Set the signer that delivers cross-chain messages, e.g. the Hyperlane
+    /// mailbox's process authority or the LayerZero endpoint's delegate for
+    /// this program.
+    pub fn configure_message_adapter(
+        ctx: Context<ConfigureMessageAdapter>,
+        delivery_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.message_adapter.delivery_authority = delivery_authority;
+
+        msg!("Message adapter delivery authority: {}", delivery_authority);
+        Ok(())
+    }
+
+    /// Authority only. Sets the sender accepted from a bridge origin chain.
+    pub fn set_trusted_remote(
+        ctx: Context<SetTrustedRemote>,
+        origin: u32,
+        sender: [u8; 32],
+    ) -> Result<()> {
+        require!(sender != [0u8; 32], DreamError::UntrustedRemote);
+
+        let trusted_remote = &mut ctx.accounts.trusted_remote;
+        trusted_remote.origin = origin;
+        trusted_remote.sender = sender;
+
+        msg!("Trusted remote set for origin {}", origin);
+        Ok(())
+    }
+
+    /// Apply a governance action sent by a trusted remote. Nonces must increase
+    /// per remote, so a message can't be replayed after a newer one lands.
+    pub fn receive_message(
+        ctx: Context<ReceiveMessage>,
+        origin: u32,
+        sender: [u8; 32],
+        nonce: u64,
+        message: Vec<u8>,
+    ) -> Result<()> {
+        let trusted_remote = &mut ctx.accounts.trusted_remote;
+        require!(trusted_remote.sender == sender, DreamError::UntrustedRemote);
+        require!(nonce >= trusted_remote.next_nonce, DreamError::InvalidRemoteMessage);
+
+        let action = GovernanceAction::decode(&message).ok_or(DreamError::InvalidRemoteMessage)?;
+        trusted_remote.next_nonce = nonce + 1;
+        ctx.accounts.message_adapter.messages_received += 1;
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        match action {
+            GovernanceAction::SetMevProtection { enabled } => {
+                let treasury = &mut ctx.accounts.treasury;
+                treasury.mev_protection_enabled = enabled;
+                emit!(MevProtectionUpdatedEvent {
+                    authority: treasury.authority,
+                    enabled,
+                    timestamp,
+                });
+            }
+            GovernanceAction::ApproveFinalizeImmutable => {
+                let upgrade_governance = ctx
+                    .accounts
+                    .upgrade_governance
+                    .as_mut()
+                    .ok_or(DreamError::InvalidRemoteMessage)?;
+                require!(upgrade_governance.finalized_at == 0, DreamError::InvalidRemoteMessage);
+                upgrade_governance.approval_origin = origin;
+                upgrade_governance.approval_nonce = nonce;
+                upgrade_governance.approved_at = timestamp;
+            }
+        }
+
+        emit!(RemoteMessageReceivedEvent {
+            origin,
+            sender,
+            nonce,
+            timestamp,
+        });
+
+        msg!("Remote message {} from origin {} applied", nonce, origin);
+        Ok(())
+    }
+
+    /// Create the program's address lookup table on first use, then add any
+    /// static accounts it is missing. Safe to re-run after new accounts are
+    /// introduced; `recent_slot` is only used when creating.
+    pub fn sync_lookup_table(ctx: Context<SyncLookupTable>, recent_slot: u64) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury.authority,
+            DreamError::UnauthorizedAuthority
+        );
+
+        let accounts = lookup_table::LookupTableAccounts {
+            lookup_table: &ctx.accounts.lookup_table,
+            authority: &ctx.accounts.treasury.to_account_info(),
+            payer: &ctx.accounts.authority,
+            system_program: &ctx.accounts.system_program,
+        };
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        let signer_seeds = &[treasury_seeds];
+
+        let config = &mut ctx.accounts.lookup_table_config;
+        if config.lookup_table == Pubkey::default() {
+            lookup_table::create(&accounts, recent_slot, signer_seeds)?;
+            config.lookup_table = ctx.accounts.lookup_table.key();
+            config.recent_slot = recent_slot;
+        }
+        require_keys_eq!(
+            config.lookup_table,
+            ctx.accounts.lookup_table.key(),
+            DreamError::InvalidLookupTable
+        );
+
+        let addresses = lookup_table::static_addresses(&ctx.accounts.treasury);
+        let added = lookup_table::extend_missing(&accounts, addresses, signer_seeds)?;
+
+        msg!("Lookup table {} synced, {} addresses added", config.lookup_table, added);
+        Ok(())
+    }
+
+    /// Record the deployed version, source commit, and audit report. Run by
+    /// the treasury authority after each deploy or upgrade.
+    pub fn upgrade_program_info(
+        ctx: Context<UpgradeProgramInfo>,
+        version: String,
+        git_commit: String,
+        audit_uri: String,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury.authority,
+            DreamError::UnauthorizedAuthority
+        );
+        require!(
+            version.len() <= ProgramInfo::MAX_VERSION_LEN
+                && git_commit.len() <= ProgramInfo::MAX_GIT_COMMIT_LEN
+                && audit_uri.len() <= ProgramInfo::MAX_AUDIT_URI_LEN,
+            DreamError::InvalidProgramInfo
+        );
+
+        let program_info = &mut ctx.accounts.program_info;
+        program_info.version = version;
+        program_info.git_commit = git_commit;
+        program_info.audit_uri = audit_uri;
+        program_info.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(ProgramInfoUpdatedEvent {
+            version: program_info.version.clone(),
+            git_commit: program_info.git_commit.clone(),
+            audit_uri: program_info.audit_uri.clone(),
+            timestamp: program_info.updated_at,
+        });
+
+        msg!("Program info: {} ({})", program_info.version, program_info.git_commit);
+        Ok(())
+    }
+
+    /// Record the executable hash of the verifiable build just deployed, tied
+    /// to the program data's deploy slot. Run by the treasury authority after
+    /// each upgrade, so anyone can check the running bytecode against source.
+    pub fn set_build_info(
+        ctx: Context<SetBuildInfo>,
+        program_hash: [u8; 32],
+        git_commit: String,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury.authority,
+            DreamError::UnauthorizedAuthority
+        );
+        require!(
+            git_commit.len() <= ProgramInfo::MAX_GIT_COMMIT_LEN,
+            DreamError::InvalidProgramInfo
+        );
+
+        let build_info = &mut ctx.accounts.build_info;
+        build_info.program_hash = program_hash;
+        build_info.git_commit = git_commit;
+        build_info.deploy_slot = ctx.accounts.program_data.slot;
+        build_info.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(BuildInfoUpdatedEvent {
+            program_hash,
+            git_commit: build_info.git_commit.clone(),
+            deploy_slot: build_info.deploy_slot,
+            timestamp: build_info.updated_at,
+        });
+
+        msg!("Build info: {} at slot {}", build_info.git_commit, build_info.deploy_slot);
+        Ok(())
+    }
+
+    /// Move the BPF upgrade authority from the current holder to the
+    /// program's governance PDA. From then on only a governance-approved
+    /// `finalize_immutable` can change it.
+    pub fn hand_over_upgrade_authority(ctx: Context<HandOverUpgradeAuthority>) -> Result<()> {
+        upgrade::set_upgrade_authority(
+            &upgrade::SetUpgradeAuthorityAccounts {
+                program_data: &ctx.accounts.program_data.to_account_info(),
+                current_authority: &ctx.accounts.current_authority,
+            },
+            Some(&ctx.accounts.upgrade_authority),
+            &[],
+        )?;
+
+        let clock = Clock::get()?;
+        let upgrade_governance = &mut ctx.accounts.upgrade_governance;
+        upgrade_governance.handed_over_by = ctx.accounts.current_authority.key();
+        upgrade_governance.handed_over_at = clock.unix_timestamp;
+
+        emit!(UpgradeAuthorityHandedOverEvent {
+            previous_authority: ctx.accounts.current_authority.key(),
+            upgrade_authority: ctx.accounts.upgrade_authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Upgrade authority handed over to {}", ctx.accounts.upgrade_authority.key());
+        Ok(())
+    }
+
+    /// Revoke the upgrade authority, making the program immutable. Callable
+    /// by anyone once governance approved it and the timelock has elapsed.
+    pub fn finalize_immutable(ctx: Context<FinalizeImmutable>) -> Result<()> {
+        let upgrade_governance = &mut ctx.accounts.upgrade_governance;
+        require!(upgrade_governance.approved_at > 0, DreamError::FinalizeNotApproved);
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= upgrade_governance.approved_at + FINALIZE_IMMUTABLE_TIMELOCK_SECS,
+            DreamError::TimelockNotElapsed
+        );
+
+        let authority_seeds: &[&[u8]] = &[b"upgrade_authority", &[ctx.bumps.upgrade_authority]];
+        upgrade::set_upgrade_authority(
+            &upgrade::SetUpgradeAuthorityAccounts {
+                program_data: &ctx.accounts.program_data.to_account_info(),
+                current_authority: &ctx.accounts.upgrade_authority,
+            },
+            None,
+            &[authority_seeds],
+        )?;
+
+        upgrade_governance.finalized_at = clock.unix_timestamp;
+
+        emit!(ProgramFinalizedEvent {
+            approval_origin: upgrade_governance.approval_origin,
+            approval_nonce: upgrade_governance.approval_nonce,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Program is now immutable");
+        Ok(())
+    }
+
+    /// Seat or rotate the guardian council. Authority only, standing in for a
+    /// full governance vote.
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: [Pubkey; GUARDIAN_COUNCIL_SIZE],
+    ) -> Result<()> {
+        require!(
+            guardians.iter().enumerate().all(|(i, guardian)| {
+                *guardian != Pubkey::default() && !guardians[..i].contains(guardian)
+            }),
+            DreamError::InvalidGuardians
+        );
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let guardian_council = &mut ctx.accounts.guardian_council;
+        guardian_council.guardians = guardians;
+        guardian_council.rotated_at = timestamp;
+
+        emit!(GuardiansRotatedEvent { guardians, timestamp });
+
+        msg!("Guardian council rotated");
+        Ok(())
+    }
+
+    /// Pause recording, claims, markets, staking and bridging. Needs
+    /// GUARDIAN_THRESHOLD guardians signing, passed as remaining accounts.
+    pub fn guardian_pause(ctx: Context<GuardianPause>) -> Result<()> {
+        require_guardians(&ctx.accounts.guardian_council, ctx.remaining_accounts)?;
+
+        ctx.accounts.treasury.paused = true;
+
+        emit!(ProgramPauseChangedEvent {
+            paused: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Program paused by the guardian council");
+        Ok(())
+    }
+
+    /// Lift a guardian pause. Authority only, standing in for governance.
+    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
+        ctx.accounts.treasury.paused = false;
+
+        emit!(ProgramPauseChangedEvent {
+            paused: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Program unpaused");
+        Ok(())
+    }
+
+    /// Cancel governance's approval of `finalize_immutable` while its timelock
+    /// runs. Same guardian threshold as `guardian_pause`; governance can send
+    /// a fresh approval afterwards.
+    pub fn guardian_cancel_finalize(ctx: Context<GuardianCancelFinalize>) -> Result<()> {
+        require_guardians(&ctx.accounts.guardian_council, ctx.remaining_accounts)?;
+
+        let upgrade_governance = &mut ctx.accounts.upgrade_governance;
+        require!(
+            upgrade_governance.approved_at > 0 && upgrade_governance.finalized_at == 0,
+            DreamError::NoQueuedFinalize
+        );
+
+        emit!(FinalizeApprovalCancelledEvent {
+            approval_origin: upgrade_governance.approval_origin,
+            approval_nonce: upgrade_governance.approval_nonce,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        upgrade_governance.approval_origin = 0;
+        upgrade_governance.approval_nonce = 0;
+        upgrade_governance.approved_at = 0;
+
+        msg!("Queued finalize_immutable cancelled by the guardian council");
+        Ok(())
+    }
+
+    // ===================== VIEWS =====================
+    // Read-only; call through simulateTransaction and decode the return data.
+
+    /// Treasury totals and the current DREAM supply.
+    pub fn get_treasury_stats(ctx: Context<GetTreasuryStats>) -> Result<TreasuryStats> {
+        let treasury = &ctx.accounts.treasury;
+        let dream_supply = ctx.accounts.dream_mint.supply;
+        Ok(TreasuryStats {
+            total_dreams_recorded: treasury.total_dreams_recorded,
+            total_dreams_mirrored: treasury.total_dreams_mirrored,
+            total_rewards_distributed: treasury.total_rewards_distributed,
+            total_rewards_expired: treasury.total_rewards_expired,
+            dream_supply,
+            dream_supply_remaining: DREAM_TOTAL_SUPPLY.saturating_sub(dream_supply),
+            total_bridged_out: treasury.total_bridged_out,
+            total_bridged_in: treasury.total_bridged_in,
+            mev_protection_enabled: treasury.mev_protection_enabled,
+        })
+    }
+
+    /// A dreamer's profile counters, reputation and LUCID stake; missing
+    /// accounts read as zero.
+    pub fn get_dreamer_stats(ctx: Context<GetDreamerStats>, dreamer: Pubkey) -> Result<DreamerStats> {
+        let profile = ctx.accounts.dreamer_profile.as_ref();
+        let stake = ctx.accounts.stake_account.as_ref();
+        let today = EpochStats::day_of(Clock::get()?.unix_timestamp);
+        let reputation = profile.map_or(0, |p| p.reputation_on(today));
+        Ok(DreamerStats {
+            dreamer,
+            interface_count: profile.map_or(0, |p| p.interface_count),
+            legacy_migrated: profile.is_some_and(|p| p.legacy_migrated),
+            legacy_dream_count: profile.map_or(0, |p| p.legacy_dream_count),
+            legacy_tokens_earned: profile.map_or(0, |p| p.legacy_tokens_earned),
+            staked_amount: stake.map_or(0, |s| s.amount),
+            access_level: stake.map_or(0, |s| calculate_access_level(s.amount)),
+            reputation,
+            reward_bps: DreamerProfile::band_reward_bps(reputation),
+        })
+    }
+
+    /// A stake's amount, access level and how much more reaches the next level.
+    pub fn get_stake_info(ctx: Context<GetStakeInfo>) -> Result<StakeInfo> {
+        let stake = &ctx.accounts.stake_account;
+        Ok(StakeInfo {
+            user: stake.user,
+            amount: stake.amount,
+            access_level: calculate_access_level(stake.amount),
+            staked_at: stake.timestamp,
+            staked_for_secs: Clock::get()?.unix_timestamp - stake.timestamp,
+            amount_to_next_level: amount_to_next_access_level(stake.amount),
+        })
+    }
+
+    /// The program's semver and which optional modules this deployment has
+    /// set up; a module's bit is set once its root account exists.
+    pub fn get_capabilities(ctx: Context<GetCapabilities>) -> Result<ProgramCapabilities> {
+        let accounts = &ctx.accounts;
+        let mut capabilities = CAPABILITY_MARKETPLACE;
+        if accounts.upgrade_governance.is_some() || accounts.guardian_council.is_some() {
+            capabilities |= CAPABILITY_GOVERNANCE;
+        }
+        if accounts.compressed_stake_tree.is_some() || accounts.dream_archive_tree.is_some() {
+            capabilities |= CAPABILITY_COMPRESSION;
+        }
+        if cfg!(feature = "devnet") {
+            capabilities |= CAPABILITY_DEVNET_FAUCET;
+        }
+        Ok(ProgramCapabilities {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            capabilities,
+        })
+    }
+}
+
+/// Post `payload` from the program emitter and count it on the treasury.
+fn post_bridge_message<'info>(
+    wormhole: &WormholeAccounts<'info>,
+    payer: &Signer<'info>,
+    treasury: &mut Account<'info, Treasury>,
+    bumps: &WormholeAccountsBumps,
+    nonce: u32,
+    payload: Vec<u8>,
+) -> Result<()> {
+    let emitter_seeds: &[&[u8]] = &[b"emitter", &[bumps.emitter]];
+
+    bridge::post_message(
+        &bridge::PostMessageAccounts {
+            bridge: &wormhole.bridge,
+            message: &wormhole.message,
+            emitter: &wormhole.emitter,
+            sequence: &wormhole.sequence,
+            payer,
+            fee_collector: &wormhole.fee_collector,
+            clock: &wormhole.clock.to_account_info(),
+            rent: &wormhole.rent.to_account_info(),
+            system_program: &wormhole.system_program.to_account_info(),
+        },
+        nonce,
+        payload,
+        &[emitter_seeds],
+    )?;
+
+    treasury.bridge_messages_sent += 1;
+    Ok(())
+}
+
+fn calculate_access_level(amount: u64) -> u8 {
+    // Calculate access level based on LUCID stake amount
+    match amount {
+        0..=LUCID_PREMIUM_THRESHOLD => 1,          // 0-1 LUCID: Basic
+        a if a <= LUCID_VIP_THRESHOLD => 2,        // 1-10 LUCID: Premium
+        a if a <= LUCID_QUANTUM_THRESHOLD => 3,    // 10-100 LUCID: VIP
+        _ => 4,                                    // 100+ LUCID: Quantum
+    }
+}
+
+/// The insurance fund's SOL: its lamports above rent.
+fn insurance_lamports(fund: &Account<InsuranceFund>) -> Result<u64> {
+    let rent = Rent::get()?.minimum_balance(fund.to_account_info().data_len());
+    Ok(fund.get_lamports().saturating_sub(rent))
+}
+
+/// Balance of a token vault PDA, or 0 if it hasn't been created yet.
+fn vault_balance(vault: &AccountInfo) -> Result<u64> {
+    if vault.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(*vault.owner, token_2022::ID, ErrorCode::AccountOwnedByWrongProgram);
+    Ok(TokenAccount::try_deserialize(&mut &vault.try_borrow_data()?[..])?.amount)
+}
+
+/// Pay the crank bounty unless the kind is cooling down or the pool can't
+/// cover it without dipping into rent. The crank itself succeeds either way.
+fn pay_crank_bounty(
+    pool: &mut Account<CrankPool>,
+    cranker: &Signer,
+    kind: CrankKind,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let bounty = pool.bounty_lamports;
+    let rent = Rent::get()?.minimum_balance(pool.to_account_info().data_len());
+    let available = pool.get_lamports().saturating_sub(rent);
+    if bounty == 0 || bounty > available || !pool.ready(kind, now) {
+        return Ok(());
+    }
+    pool.sub_lamports(bounty)?;
+    cranker.add_lamports(bounty)?;
+    pool.last_paid_at[kind as usize] = now;
+    pool.total_paid += bounty;
+
+    emit!(CrankRewardPaidEvent {
+        cranker: cranker.key(),
+        kind,
+        lamports: bounty,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Credit `validator` the pool's per-validation reward unless the vault
+/// can't cover it on top of everything already credited and unclaimed. The
+/// validation itself succeeds either way.
+fn credit_validator(
+    pool: &mut Account<ValidatorRewardPool>,
+    vault: &InterfaceAccount<TokenAccount>,
+    rewards: &mut Account<ValidatorRewards>,
+    validator: Pubkey,
+    dream_record: Pubkey,
+    now: i64,
+) -> Result<()> {
+    rewards.validator = validator;
+    let amount = pool.reward_per_validation;
+    if amount == 0 || vault.amount < pool.unclaimed() + amount {
+        return Ok(());
+    }
+    pool.total_credited += amount;
+    rewards.claimable += amount;
+    rewards.total_earned += amount;
+
+    emit!(ValidatorRewardCreditedEvent {
+        validator,
+        dream_record,
+        amount,
+        claimable: rewards.claimable,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Require GUARDIAN_THRESHOLD of the council's guardians among the signers
+/// in `accounts`.
+fn require_guardians(guardian_council: &GuardianCouncil, accounts: &[AccountInfo]) -> Result<()> {
+    let signers: Vec<Pubkey> = accounts
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| account.key())
+        .collect();
+    require!(
+        guardian_council.approvals(&signers) >= GUARDIAN_THRESHOLD,
+        DreamError::GuardianThresholdNotMet
+    );
+    Ok(())
+}
+
+/// Require the previous ed25519 instruction to carry signatures over `message`
+/// by `lead` and by enough approved `co_signers` (`PinningOracle` accounts) to
+/// meet the oracle quorum, crediting each with an attestation.
+fn require_oracle_quorum<'info>(
+    oracle_quorum: &AccountInfo<'info>,
+    instructions_sysvar: &AccountInfo<'info>,
+    lead: &mut PinningOracle,
+    co_signers: &'info [AccountInfo<'info>],
+    message: &[u8],
+    error: DreamError,
+) -> Result<()> {
+    let threshold = if oracle_quorum.data_is_empty() {
+        1
+    } else {
+        OracleQuorum::try_deserialize(&mut &oracle_quorum.try_borrow_data()?[..])?.threshold
+    };
+    let signers = ed25519::verified_signers(instructions_sysvar, message, error)?;
+    if !signers.contains(&lead.oracle) {
+        return Err(error.into());
+    }
+    lead.attestations += 1;
+
+    let mut counted = vec![lead.oracle];
+    for account in co_signers {
+        let mut co_signer = Account::<PinningOracle>::try_from(account)?;
+        require!(co_signer.approved, DreamError::PinningOracleNotApproved);
+        if !signers.contains(&co_signer.oracle) || counted.contains(&co_signer.oracle) {
+            return Err(error.into());
+        }
+        co_signer.attestations += 1;
+        co_signer.exit(&crate::ID)?;
+        counted.push(co_signer.oracle);
+    }
+    require!(
+        counted.len() >= usize::from(threshold),
+        DreamError::OracleQuorumNotMet
+    );
+    Ok(())
+}
+
+/// Create the `[b"dream", dreamer, id]` record at `record` the way `init`
+/// would, with `payer` covering the rent.
+fn create_dream_record<'info>(
+    record: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    dreamer: &Pubkey,
+    id: u64,
+) -> Result<()> {
+    let id_bytes = id.to_le_bytes();
+    let (address, bump) =
+        Pubkey::find_program_address(&[b"dream", dreamer.as_ref(), &id_bytes], &crate::ID);
+    require_keys_eq!(record.key(), address, DreamError::InvalidDreamBatch);
+
+    let record_seeds: &[&[u8]] = &[b"dream", dreamer.as_ref(), &id_bytes, &[bump]];
+    create_pda(record, payer, system_program, record_seeds, DreamRecord::EXTRAS_OFFSET)
+}
+
+/// Create the `SplicedDream` marker of `dream_record` at `marker`.
+fn create_spliced_dream<'info>(
+    marker: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    dream_record: &Pubkey,
+) -> Result<()> {
+    let (address, bump) =
+        Pubkey::find_program_address(&[b"spliced_dream", dream_record.as_ref()], &crate::ID);
+    require_keys_eq!(marker.key(), address, DreamError::InvalidSpliceDreams);
+    require!(
+        marker.owner == &system_program::ID && marker.data_is_empty(),
+        DreamError::DreamAlreadySpliced
+    );
+    let marker_seeds: &[&[u8]] = &[b"spliced_dream", dream_record.as_ref(), &[bump]];
+    let space = 8 + std::mem::size_of::<SplicedDream>();
+    create_pda(marker, payer, system_program, marker_seeds, space)
+}
+
+/// Allocate `space` bytes at the PDA `account` and assign it to this program.
+fn create_pda<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    // Top up rather than create, so lamports sent to the address first can't block it
+    let lamports = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if lamports > 0 {
+        let cpi_accounts = system_program::Transfer { from: payer.clone(), to: account.clone() };
+        let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+        system_program::transfer(cpi_ctx, lamports)?;
+    }
+    let signer_seeds = &[seeds];
+    let cpi_accounts = system_program::Allocate { account_to_allocate: account.clone() };
+    let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+    system_program::allocate(cpi_ctx, space as u64)?;
+    let cpi_accounts = system_program::Assign { account_to_assign: account.clone() };
+    let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+    system_program::assign(cpi_ctx, &crate::ID)?;
+    Ok(())
+}
+
+/// Whether a later instruction in this transaction is a `record_dream` by
+/// `dreamer` that `dreamer` also pays for. Transactions are atomic, so
+/// sponsored lamports can only land alongside the dream they pay for.
+fn records_dream_later(instructions_sysvar: &AccountInfo, dreamer: &Pubkey) -> bool {
+    (1..)
+        .map_while(|offset| get_instruction_relative(offset, instructions_sysvar).ok())
+        .any(|ix| {
+            ix.program_id == crate::ID
+                && ix.data.starts_with(
+                    &<instruction::RecordDream as anchor_lang::Discriminator>::DISCRIMINATOR,
+                )
+                && ix.accounts.len() > 1
+                && ix.accounts[..2].iter().all(|meta| meta.pubkey == *dreamer)
+        })
+}
+
+/// Size of a DREAM associated token account, which Token-2022 creates with
+/// the immutable-owner extension.
+fn dream_account_len() -> Result<usize> {
+    use spl_token_2022::extension::ExtensionType;
+    Ok(ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[
+        ExtensionType::ImmutableOwner,
+    ])?)
+}
+
+/// `dream_level` of `mint` read from its `NftAttributes`, which must be the
+/// PDA owned by the gate's OneirobotNFT program.
+fn oneirobot_dream_level(
+    gate: &ValidatorGate,
+    attributes: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<u8> {
+    require!(
+        *attributes.owner == gate.nft_program
+            && attributes.key() == OneirobotAttributes::address(&gate.nft_program, mint),
+        DreamError::InvalidOneirobotAccounts
+    );
+    let data = attributes.try_borrow_data()?;
+    let attributes =
+        OneirobotAttributes::try_from_data(&data).ok_or(DreamError::InvalidOneirobotAccounts)?;
+    Ok(attributes.dream_level)
+}
+
+fn amount_to_next_access_level(amount: u64) -> u64 {
+    // Each level starts just above the previous threshold
+    [LUCID_PREMIUM_THRESHOLD, LUCID_VIP_THRESHOLD, LUCID_QUANTUM_THRESHOLD]
+        .into_iter()
+        .find(|threshold| amount <= *threshold)
+        .map_or(0, |threshold| threshold + 1 - amount)
+}
+
+#[derive(Accounts)]
+pub struct InitializeTokens<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Treasury>(),
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<FeeVault>(),
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+    
+    pub dream_mint: InterfaceAccount<'info, Mint>,
+    pub smind_mint: InterfaceAccount<'info, Mint>,
+    pub lucid_mint: InterfaceAccount<'info, Mint>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct Bootstrap<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Treasury>(),
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<FeeVault>(),
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<TreasuryHealth>(),
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Box<Account<'info, TreasuryHealth>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"dream_mint"],
+        bump,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = treasury,
+        mint::freeze_authority = treasury,
+        mint::token_program = token_program
+    )]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"smind_mint"],
+        bump,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = treasury,
+        mint::freeze_authority = treasury,
+        mint::token_program = token_program
+    )]
+    pub smind_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"lucid_mint"],
+        bump,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = treasury,
+        mint::freeze_authority = treasury,
+        mint::token_program = token_program
+    )]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = dream_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = smind_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_smind_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct TopUpPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(
+        mut,
+        address = treasury.dream_mint
+    )]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<RewardPool>(),
+        seeds = [b"reward_pool"],
+        bump
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = dream_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct FaucetMint<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + std::mem::size_of::<FaucetClaim>(),
+        seeds = [b"faucet", wallet.key().as_ref()],
+        bump
+    )]
+    pub faucet_claim: Box<Account<'info, FaucetClaim>>,
+    
+    #[account(
+        mut,
+        address = treasury.dream_mint
+    )]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        address = treasury.smind_mint
+    )]
+    pub smind_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        address = treasury.lucid_mint
+    )]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        associated_token::mint = dream_mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program
+    )]
+    pub wallet_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        associated_token::mint = smind_mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program
+    )]
+    pub wallet_smind_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program
+    )]
+    pub wallet_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    dream_content_hash: [u8; 32],
+    metadata: DreamMetadata,
+    co_dreamers: Vec<Pubkey>,
+    idempotency_key: Option<u64>
+)]
+pub struct RecordDream<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    /// Pays rent; the dreamer or a relayer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamRecord>(),
+        seeds = [b"dream", dreamer.key().as_ref(), &treasury.total_dreams_recorded.to_le_bytes()],
+        bump
+    )]
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Created on the first dream so the reward never fails on a missing ATA
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_dream_account: InterfaceAccount<'info, TokenAccount>,
+    
+    // Every rewarded dream counts against the day's session cap
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<SleepSession>(),
+        seeds = [
+            b"sleep_session".as_ref(),
+            dreamer.key().as_ref(),
+            &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub sleep_session: Box<Account<'info, SleepSession>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamerProfile>(),
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Box<Account<'info, DreamerProfile>>,
+    
+    // Today's bucket; clients derive it from the cluster time
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<EpochStats>(),
+        seeds = [b"epoch_stats".as_ref(), &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Box<Account<'info, EpochStats>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<SeasonScore>(),
+        seeds = [b"season_score", dreamer.key().as_ref()],
+        bump
+    )]
+    pub season_score: Box<Account<'info, SeasonScore>>,
+    
+    // Pass all three to apply the dreamer's charity split; the charity's
+    // DREAM ATA is created when the charity is approved
+    #[account(
+        mut,
+        has_one = dreamer
+    )]
+    pub charity_split: Option<Box<Account<'info, CharitySplit>>>,
+    
+    #[account(mut)]
+    pub charity: Option<Box<Account<'info, Charity>>>,
+    
+    #[account(mut)]
+    pub charity_dream_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validation_queue"],
+        bump
+    )]
+    pub validation_queue: Option<AccountLoader<'info, ValidationQueue>>,
+    
+    // The dreamer's LUCID stake; raises the dream's place in the validation queue
+    #[account(
+        seeds = [b"stake", dreamer.key().as_ref(), treasury.lucid_mint.as_ref()],
+        bump
+    )]
+    pub lucid_stake: Option<Box<Account<'info, LucidStake>>>,
+    
+    // Required when the dream lists co-dreamers
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamCoauthors>(),
+        seeds = [b"coauthors", dream_record.key().as_ref()],
+        bump
+    )]
+    pub dream_coauthors: Option<Box<Account<'info, DreamCoauthors>>>,
+    
+    // Also required with co-dreamers or an interpreter, to count their
+    // held-back shares
+    #[account(
+        mut,
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Option<Box<Account<'info, TreasuryHealth>>>,
+    
+    // Required when the dream names an interpreter
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DreamInterpretation::SPACE,
+        seeds = [b"interpretation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub interpretation: Option<Box<Account<'info, DreamInterpretation>>>,
+    
+    // The dreamer's equipped companion, stamped on the record; pass all
+    // four companion accounts or none
+    #[account(seeds = [b"companion", dreamer.key().as_ref()], bump)]
+    pub companion: Option<Box<Account<'info, Companion>>>,
+    
+    pub companion_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        seeds = [b"companion_xp", companion_xp.mint.as_ref()],
+        bump
+    )]
+    pub companion_xp: Option<Box<Account<'info, CompanionXp>>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<CompanionStamp>(),
+        seeds = [b"companion_stamp", dream_record.key().as_ref()],
+        bump
+    )]
+    pub companion_stamp: Option<Box<Account<'info, CompanionStamp>>>,
+    
+    // Required with an idempotency key; a retry under the same key fails here
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<IdempotencyNonce>(),
+        seeds = [
+            b"idempotency",
+            dreamer.key().as_ref(),
+            &idempotency_key.unwrap_or_default().to_le_bytes(),
+        ],
+        bump
+    )]
+    pub idempotency_nonce: Option<Box<Account<'info, IdempotencyNonce>>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct CloseIdempotencyNonce<'info> {
+    /// CHECK: Refunded the rent; must be the payer the nonce recorded
+    #[account(mut, address = idempotency_nonce.payer)]
+    pub payer: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            b"idempotency",
+            idempotency_nonce.dreamer.as_ref(),
+            &idempotency_nonce.key.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub idempotency_nonce: Account<'info, IdempotencyNonce>,
+}
+
+#[derive(Accounts)]
+pub struct RecordDreamsBatch<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+    
+    pub dream_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_dream_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamerProfile>(),
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Box<Account<'info, DreamerProfile>>,
+    
+    // Every rewarded dream counts against the day's session cap
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<SleepSession>(),
+        seeds = [
+            b"sleep_session".as_ref(),
+            dreamer.key().as_ref(),
+            &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub sleep_session: Box<Account<'info, SleepSession>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<EpochStats>(),
+        seeds = [b"epoch_stats".as_ref(), &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Box<Account<'info, EpochStats>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<SeasonScore>(),
+        seeds = [b"season_score", dreamer.key().as_ref()],
+        bump
+    )]
+    pub season_score: Box<Account<'info, SeasonScore>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct RecordDreamDelegated<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Attributed the dream; its signature is checked against the instructions sysvar
+    pub dreamer: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<DreamRecord>(),
+        seeds = [b"dream", dreamer.key().as_ref(), &treasury.total_dreams_recorded.to_le_bytes()],
+        bump
+    )]
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<RelayNonce>(),
+        seeds = [b"relay_nonce", dreamer.key().as_ref()],
+        bump
+    )]
+    pub relay_nonce: Box<Account<'info, RelayNonce>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_dream_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<DreamerProfile>(),
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Box<Account<'info, DreamerProfile>>,
+    
+    // Every rewarded dream counts against the day's session cap
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<SleepSession>(),
+        seeds = [
+            b"sleep_session".as_ref(),
+            dreamer.key().as_ref(),
+            &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub sleep_session: Box<Account<'info, SleepSession>>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<EpochStats>(),
+        seeds = [b"epoch_stats".as_ref(), &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Box<Account<'info, EpochStats>>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + std::mem::size_of::<SeasonScore>(),
+        seeds = [b"season_score", dreamer.key().as_ref()],
+        bump
+    )]
+    pub season_score: Box<Account<'info, SeasonScore>>,
+    
+    // Pass when the relayer signs as one of the dreamer's session keys
+    #[account(
+        seeds = [b"session_key", dreamer.key().as_ref(), relayer.key().as_ref()],
+        bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+    
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSessionKey<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: The ephemeral key; only funded and recorded
+    #[account(mut)]
+    pub session_signer: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<SessionKey>(),
+        seeds = [b"session_key", authority.key().as_ref(), session_signer.key().as_ref()],
+        bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = authority,
+        close = authority,
+        seeds = [b"session_key", authority.key().as_ref(), session_key.signer.as_ref()],
+        bump
+    )]
+    pub session_key: Account<'info, SessionKey>,
+}
+
+#[derive(Accounts)]
+#[instruction(value: String)]
+pub struct InternString<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = InternedString::space(&value),
+        seeds = [b"interned", intern::string_id(&value).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub interned_string: Account<'info, InternedString>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(extras: DreamRecordExtras)]
+pub struct SetDreamExtras<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = dreamer,
+        realloc = DreamRecord::EXTRAS_OFFSET + extras.space(),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmCoauthorship<'info> {
+    #[account(mut)]
+    pub co_dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"coauthors", dream_coauthors.dream_record.as_ref()],
+        bump
+    )]
+    pub dream_coauthors: Box<Account<'info, DreamCoauthors>>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Box<Account<'info, TreasuryHealth>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = co_dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub co_dreamer_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<SeasonScore>(),
+        seeds = [b"season_score", co_dreamer.key().as_ref()],
+        bump
+    )]
+    pub season_score: Box<Account<'info, SeasonScore>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SetConversionRates<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<RewardConversionRates>(),
+        seeds = [b"conversion_rates"],
+        bump
+    )]
+    pub conversion_rates: Account<'info, RewardConversionRates>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(token: RewardToken)]
+pub struct ClaimRewardAs<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(has_one = dreamer)]
+    pub dream_record: Box<Account<'info, DreamRecord>>,
+    
+    #[account(
+        mut,
+        seeds = [b"conversion_rates"],
+        bump
+    )]
+    pub conversion_rates: Box<Account<'info, RewardConversionRates>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<RewardClaim>(),
+        seeds = [b"reward_claim", dream_record.key().as_ref()],
+        bump
+    )]
+    pub reward_claim: Box<Account<'info, RewardClaim>>,
+    
+    #[account(
+        mut,
+        address = treasury.dream_mint
+    )]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = dream_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = treasury.reward_mint(token)
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_reward_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireCoauthorShares<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(
+        mut,
+        has_one = dreamer,
+        close = dreamer,
+        seeds = [b"coauthors", dream_coauthors.dream_record.as_ref()],
+        bump
+    )]
+    pub dream_coauthors: Box<Account<'info, DreamCoauthors>>,
+    
+    /// CHECK: Recorder of the dream, who paid the rent; checked by `has_one`
+    #[account(mut)]
+    pub dreamer: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Box<Account<'info, TreasuryHealth>>,
+    
+    // Pass both to collect the crank bounty
+    #[account(
+        mut,
+        seeds = [b"crank_pool"],
+        bump
+    )]
+    pub crank_pool: Option<Account<'info, CrankPool>>,
+    
+    #[account(mut)]
+    pub cranker: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(day: u32)]
+pub struct CommitEpochRoot<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [b"epoch_stats".as_ref(), &day.to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: Account<'info, EpochStats>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<EpochCommitment>(),
+        seeds = [b"epoch_commitment".as_ref(), &day.to_le_bytes()],
+        bump
+    )]
+    pub epoch_commitment: Account<'info, EpochCommitment>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetResearchConsent<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamerProfile>(),
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Account<'info, DreamerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<ResearchPool>(),
+        seeds = [b"research_pool"],
+        bump
+    )]
+    pub research_pool: Account<'info, ResearchPool>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<ResearchParticipant>(),
+        seeds = [b"research_participant", dreamer.key().as_ref()],
+        bump
+    )]
+    pub research_participant: Account<'info, ResearchParticipant>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseResearchLicense<'info> {
+    #[account(mut)]
+    pub licensee: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = licensee,
+        associated_token::token_program = token_program
+    )]
+    pub licensee_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        token::mint = lucid_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"research_vault"],
+        bump
+    )]
+    pub research_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"research_pool"],
+        bump
+    )]
+    pub research_pool: Box<Account<'info, ResearchPool>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<ResearchLicense>(),
+        seeds = [b"research_license", licensee.key().as_ref()],
+        bump
+    )]
+    pub research_license: Box<Account<'info, ResearchLicense>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SetLicenseTerms<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(has_one = dreamer)]
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamLicenseTerms>(),
+        seeds = [b"license_terms", dream_record.key().as_ref()],
+        bump
+    )]
+    pub license_terms: Box<Account<'info, DreamLicenseTerms>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(license_type: DreamLicenseType)]
+pub struct LicenseDream<'info> {
+    #[account(mut)]
+    pub licensee: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        has_one = dreamer,
+        seeds = [b"license_terms", license_terms.dream_record.as_ref()],
+        bump
+    )]
+    pub license_terms: Box<Account<'info, DreamLicenseTerms>>,
+    
+    /// CHECK: The dream's author, checked against the terms
+    pub dreamer: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = licensee,
+        associated_token::token_program = token_program
+    )]
+    pub licensee_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamLicense>(),
+        seeds = [
+            b"dream_license",
+            license_terms.dream_record.as_ref(),
+            licensee.key().as_ref(),
+            &[license_type as u8],
+        ],
+        bump
+    )]
+    pub dream_license: Box<Account<'info, DreamLicense>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(language: [u8; 2])]
+pub struct PostTranslationBounty<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    pub dream_record: Box<Account<'info, DreamRecord>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<TranslationBounty>(),
+        seeds = [b"translation_bounty", dream_record.key().as_ref(), &language],
+        bump
+    )]
+    pub translation_bounty: Box<Account<'info, TranslationBounty>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        token::mint = lucid_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"translation_bounty_vault", dream_record.key().as_ref(), &language],
+        bump
+    )]
+    pub bounty_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = sponsor,
+        associated_token::token_program = token_program
+    )]
+    pub sponsor_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+#[instruction(language: [u8; 2])]
+pub struct AttachAnnotation<'info> {
+    #[account(mut)]
+    pub annotator: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub dream_record: Box<Account<'info, DreamRecord>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DreamAnnotation::SPACE,
+        seeds = [
+            b"annotation",
+            dream_record.key().as_ref(),
+            annotator.key().as_ref(),
+            &language,
+        ],
+        bump
+    )]
+    pub annotation: Box<Account<'info, DreamAnnotation>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAnnotation<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub annotation: Box<Account<'info, DreamAnnotation>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"translation_bounty",
+            annotation.dream_record.as_ref(),
+            &annotation.language,
+        ],
+        bump
+    )]
+    pub translation_bounty: Option<Box<Account<'info, TranslationBounty>>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"translation_bounty_vault",
+            annotation.dream_record.as_ref(),
+            &annotation.language,
+        ],
+        bump
+    )]
+    pub bounty_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = annotation.annotator,
+        associated_token::token_program = token_program
+    )]
+    pub annotator_lucid_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct AssignInterpreter<'info> {
+    pub dreamer: Signer<'info>,
+    
+    #[account(has_one = dreamer)]
+    pub dream_record: Box<Account<'info, DreamRecord>>,
+    
+    /// CHECK: The dream's validation PDA, which must not exist yet
+    #[account(
+        seeds = [b"validation", dream_record.key().as_ref()],
+        bump,
+        constraint = dream_validation.data_is_empty() @ DreamError::DreamAlreadyValidated
+    )]
+    pub dream_validation: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"interpretation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub interpretation: Box<Account<'info, DreamInterpretation>>,
+}
+
+#[derive(Accounts)]
+pub struct AttachInterpretation<'info> {
+    pub interpreter: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub dream_record: Box<Account<'info, DreamRecord>>,
+    
+    /// CHECK: The dream's validation PDA, which must not exist yet
+    #[account(
+        seeds = [b"validation", dream_record.key().as_ref()],
+        bump,
+        constraint = dream_validation.data_is_empty() @ DreamError::DreamAlreadyValidated
+    )]
+    pub dream_validation: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"interpretation", dream_record.key().as_ref()],
+        bump,
+        has_one = interpreter
+    )]
+    pub interpretation: Box<Account<'info, DreamInterpretation>>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = interpreter,
+        associated_token::token_program = token_program
+    )]
+    pub interpreter_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimResearchRevenue<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Box<Account<'info, DreamerProfile>>,
+    
+    #[account(
+        seeds = [b"research_pool"],
+        bump
+    )]
+    pub research_pool: Box<Account<'info, ResearchPool>>,
+    
+    #[account(
+        mut,
+        seeds = [b"research_participant", dreamer.key().as_ref()],
+        bump
+    )]
+    pub research_participant: Box<Account<'info, ResearchParticipant>>,
+    
+    #[account(
+        mut,
+        seeds = [b"research_vault"],
+        bump
+    )]
+    pub research_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CreateMarket<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [b"profile", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Box<Account<'info, DreamerProfile>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<PredictionMarket>(),
+        seeds = [b"market", creator.key().as_ref(), &market_id.to_le_bytes()],
+        bump
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        token::mint = dream_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct BuyOutcome<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub market: Box<Account<'info, PredictionMarket>>,
+    
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<MarketPosition>(),
+        seeds = [b"market_position", market.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, MarketPosition>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = dream_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program
+    )]
+    pub buyer_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator_reward_pool"],
+        bump
+    )]
+    pub validator_reward_pool: Box<Account<'info, ValidatorRewardPool>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator_reward_vault"],
+        bump
+    )]
+    pub validator_reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut)]
+    pub market: Account<'info, PredictionMarket>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimMarketWinnings<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    pub market: Box<Account<'info, PredictionMarket>>,
+    
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"market_position", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, MarketPosition>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<RewardStream>(),
+        seeds = [b"reward_stream", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub reward_stream: Option<Box<Account<'info, RewardStream>>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        token::mint = dream_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"reward_stream_vault"],
+        bump
+    )]
+    pub reward_stream_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    // Also required with the stream accounts
+    #[account(
+        mut,
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Option<Box<Account<'info, TreasuryHealth>>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStreamed<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        has_one = beneficiary
+    )]
+    pub reward_stream: Box<Account<'info, RewardStream>>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_stream_vault"],
+        bump
+    )]
+    pub reward_stream_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Box<Account<'info, TreasuryHealth>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(duel_id: u64)]
+pub struct CreateDuel<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamDuel>(),
+        seeds = [b"duel", challenger.key().as_ref(), &duel_id.to_le_bytes()],
+        bump
+    )]
+    pub duel: Box<Account<'info, DreamDuel>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        token::mint = lucid_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"duel_vault", duel.key().as_ref()],
+        bump
+    )]
+    pub duel_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = challenger,
+        associated_token::token_program = token_program
+    )]
+    pub challenger_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptDuel<'info> {
+    pub opponent: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        has_one = opponent
+    )]
+    pub duel: Box<Account<'info, DreamDuel>>,
+    
+    #[account(
+        mut,
+        seeds = [b"duel_vault", duel.key().as_ref()],
+        bump
+    )]
+    pub duel_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = opponent,
+        associated_token::token_program = token_program
+    )]
+    pub opponent_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDuel<'info> {
+    pub challenger: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        has_one = challenger
+    )]
+    pub duel: Box<Account<'info, DreamDuel>>,
+    
+    #[account(
+        mut,
+        seeds = [b"duel_vault", duel.key().as_ref()],
+        bump
+    )]
+    pub duel_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = challenger,
+        associated_token::token_program = token_program
+    )]
+    pub challenger_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitDuelDream<'info> {
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub duel: Account<'info, DreamDuel>,
+    
+    #[account(
+        constraint = dream_record.dreamer == dreamer.key() @ DreamError::InvalidDreamer
+    )]
+    pub dream_record: Account<'info, DreamRecord>,
+}
+
+#[derive(Accounts)]
+pub struct SettleDuel<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub duel: Box<Account<'info, DreamDuel>>,
+    
+    #[account(
+        mut,
+        seeds = [b"duel_vault", duel.key().as_ref()],
+        bump
+    )]
+    pub duel_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = duel.challenger,
+        associated_token::token_program = token_program
+    )]
+    pub challenger_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = duel.opponent,
+        associated_token::token_program = token_program
+    )]
+    pub opponent_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(grant_id: u64)]
+pub struct CreateGrant<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: any wallet can receive a grant
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Grant>(),
+        seeds = [b"grant", recipient.key().as_ref(), &grant_id.to_le_bytes()],
+        bump
+    )]
+    pub grant: Box<Account<'info, Grant>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = lucid_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"grant_vault", grant.key().as_ref()],
+        bump
+    )]
+    pub grant_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMilestone<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: the grant's recipient, owner of the ATA the milestone goes to
+    #[account(address = grant.recipient)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub grant: Box<Account<'info, Grant>>,
+    
+    #[account(
+        mut,
+        seeds = [b"grant_vault", grant.key().as_ref()],
+        bump
+    )]
+    pub grant_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ClawBack<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.lucid_mint)]
+    pub lucid_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub grant: Box<Account<'info, Grant>>,
+    
+    #[account(
+        mut,
+        seeds = [b"grant_vault", grant.key().as_ref()],
+        bump
+    )]
+    pub grant_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = lucid_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_lucid_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+#[instruction(capsule_id: u64)]
+pub struct RecordTimelockedDream<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DreamCapsule::SPACE,
+        seeds = [b"capsule", dreamer.key().as_ref(), &capsule_id.to_le_bytes()],
+        bump
+    )]
+    pub capsule: Account<'info, DreamCapsule>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealDream<'info> {
+    pub dreamer: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = dreamer
+    )]
+    pub capsule: Account<'info, DreamCapsule>,
+}
+
+#[derive(Accounts)]
+pub struct SetCharityApproval<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Any wallet; only used as the charity PDA seed and ATA owner
+    pub charity_wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Charity>(),
+        seeds = [b"charity", charity_wallet.key().as_ref()],
+        bump
+    )]
+    pub charity: Box<Account<'info, Charity>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = dream_mint,
+        associated_token::authority = charity_wallet,
+        associated_token::token_program = token_program
+    )]
+    pub charity_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SetCharitySplit<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub charity: Account<'info, Charity>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<CharitySplit>(),
+        seeds = [b"charity_split", dreamer.key().as_ref()],
+        bump
+    )]
+    pub charity_split: Account<'info, CharitySplit>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeValidationQueue<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ValidationQueue>(),
+        seeds = [b"validation_queue"],
+        bump
+    )]
+    pub validation_queue: AccountLoader<'info, ValidationQueue>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PopValidation<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"validation_queue"],
+        bump
+    )]
+    pub validation_queue: AccountLoader<'info, ValidationQueue>,
+}
+
+#[derive(Accounts)]
+pub struct SetPinningOracle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: The oracle's signing key; only used as the PDA seed
+    pub oracle: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<PinningOracle>(),
+        seeds = [b"pinning_oracle", oracle.key().as_ref()],
+        bump
+    )]
+    pub pinning_oracle: Account<'info, PinningOracle>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleQuorum<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<OracleQuorum>(),
+        seeds = [b"oracle_quorum"],
+        bump
+    )]
+    pub oracle_quorum: Account<'info, OracleQuorum>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmPinned<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"pinning_oracle", pinning_oracle.oracle.as_ref()],
+        bump
+    )]
+    pub pinning_oracle: Account<'info, PinningOracle>,
+    
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<PinConfirmation>(),
+        seeds = [b"pinned", dream_record.key().as_ref()],
+        bump
+    )]
+    pub pin_confirmation: Account<'info, PinConfirmation>,
+    
+    /// CHECK: The `OracleQuorum` PDA; one oracle suffices while it doesn't exist
+    #[account(seeds = [b"oracle_quorum"], bump)]
+    pub oracle_quorum: UncheckedAccount<'info>,
+    
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClassifyDream<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"pinning_oracle", pinning_oracle.oracle.as_ref()],
+        bump
+    )]
+    pub pinning_oracle: Account<'info, PinningOracle>,
+    
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamClassification>(),
+        seeds = [b"classification", dream_record.key().as_ref()],
+        bump
+    )]
+    pub dream_classification: Account<'info, DreamClassification>,
+    
+    /// CHECK: The `OracleQuorum` PDA; one oracle suffices while it doesn't exist
+    #[account(seeds = [b"oracle_quorum"], bump)]
+    pub oracle_quorum: UncheckedAccount<'info>,
+    
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ValidateDream<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    /// CHECK: the dream's dreamer, owner of the ATA the onboarding bonus goes to
+    #[account(address = dream_record.dreamer)]
+    pub dreamer: UncheckedAccount<'info>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = dream_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"pinned", dream_record.key().as_ref()],
+        bump
+    )]
+    pub pin_confirmation: Account<'info, PinConfirmation>,
+    
+    #[account(
+        mut,
+        seeds = [b"profile", dream_record.dreamer.as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Account<'info, DreamerProfile>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<DreamValidation>(),
+        seeds = [b"validation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub dream_validation: Account<'info, DreamValidation>,
+    
+    /// The validator the verdict is attributed to
+    #[account(seeds = [b"validator", validator_registration.validator.as_ref()], bump)]
+    pub validator_registration: Option<Account<'info, ValidatorRegistration>>,
+    
+    /// Pass all three with `validator_registration` to credit its reward
+    #[account(mut, seeds = [b"validator_reward_pool"], bump)]
+    pub validator_reward_pool: Option<Account<'info, ValidatorRewardPool>>,
+    
+    #[account(seeds = [b"validator_reward_vault"], bump)]
+    pub validator_reward_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ValidatorRewards>(),
+        seeds = [
+            b"validator_rewards",
+            validator_registration
+                .as_ref()
+                .ok_or(DreamError::MissingValidatorRegistration)?
+                .validator
+                .as_ref()
+        ],
+        bump
+    )]
+    pub validator_rewards: Option<Account<'info, ValidatorRewards>>,
+    
+    /// Pass with `validator_registration` to count the verdict
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ValidatorStats>(),
+        seeds = [
+            b"validator_stats",
+            validator_registration
+                .as_ref()
+                .ok_or(DreamError::MissingValidatorRegistration)?
+                .validator
+                .as_ref()
+        ],
+        bump
+    )]
+    pub validator_stats: Option<Account<'info, ValidatorStats>>,
+    
+    /// CHECK: The dream's `DreamInterpretation`, read and updated when it exists
+    #[account(
+        mut,
+        seeds = [b"interpretation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub interpretation: UncheckedAccount<'info>,
+    
+    /// Required once the interpreter has attached an analysis
+    #[account(mut)]
+    pub interpreter_dream_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Releases the interpreter's held-back share
+    #[account(
+        mut,
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Box<Account<'info, TreasuryHealth>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveValidationDispute<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [b"validation", dream_validation.dream_record.as_ref()],
+        bump
+    )]
+    pub dream_validation: Account<'info, DreamValidation>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ValidationDispute>(),
+        seeds = [b"validation_dispute", dream_validation.dream_record.as_ref()],
+        bump
+    )]
+    pub validation_dispute: Account<'info, ValidationDispute>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ValidatorStats>(),
+        seeds = [b"validator_stats", dream_validation.validator.as_ref()],
+        bump
+    )]
+    pub validator_stats: Account<'info, ValidatorStats>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PostOptimisticScore<'info> {
+    /// Signs directly and pays the bond and rent
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(seeds = [b"pinning_oracle", oracle.key().as_ref()], bump)]
+    pub pinning_oracle: Account<'info, PinningOracle>,
+    
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    #[account(
+        seeds = [b"pinned", dream_record.key().as_ref()],
+        bump
+    )]
+    pub pin_confirmation: Account<'info, PinConfirmation>,
+    
+    /// CHECK: The dream's validation PDA, which must not exist yet
+    #[account(
+        seeds = [b"validation", dream_record.key().as_ref()],
+        bump,
+        constraint = dream_validation.data_is_empty() @ DreamError::DreamAlreadyValidated
+    )]
+    pub dream_validation: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + std::mem::size_of::<OptimisticScore>(),
+        seeds = [b"optimistic_score", dream_record.key().as_ref()],
+        bump
+    )]
+    pub optimistic_score: Account<'info, OptimisticScore>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeOptimisticScore<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(
+        mut,
+        seeds = [b"optimistic_score", dream_record.key().as_ref()],
+        bump
+    )]
+    pub optimistic_score: Account<'info, OptimisticScore>,
+    
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    /// Required for `FraudProof::DuplicateContent`
+    #[account(
+        constraint = original_record.key() != dream_record.key() @ DreamError::InvalidFraudProof
+    )]
+    pub original_record: Option<Account<'info, DreamRecord>>,
+    
+    /// Required for `FraudProof::DeadCid`, with the instructions sysvar
+    #[account(seeds = [b"pinning_oracle", witness_oracle.oracle.as_ref()], bump)]
+    pub witness_oracle: Option<Account<'info, PinningOracle>>,
+    
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeOptimisticScore<'info> {
+    /// Pays the validation's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    /// CHECK: The oracle that posted the score; gets the bond and rent back
+    #[account(mut, address = optimistic_score.oracle)]
+    pub oracle: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        close = oracle,
+        seeds = [b"optimistic_score", dream_record.key().as_ref()],
+        bump
+    )]
+    pub optimistic_score: Account<'info, OptimisticScore>,
+    
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    #[account(
+        mut,
+        seeds = [b"profile", dream_record.dreamer.as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Account<'info, DreamerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamValidation>(),
+        seeds = [b"validation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub dream_validation: Account<'info, DreamValidation>,
+    
+    /// CHECK: The dream's `DreamInterpretation`, read and updated when it exists
+    #[account(
+        mut,
+        seeds = [b"interpretation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub interpretation: UncheckedAccount<'info>,
+    
+    /// CHECK: the dream's dreamer, who gets the held-back share without an analysis
+    #[account(address = dream_record.dreamer)]
+    pub dreamer: UncheckedAccount<'info>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = dream_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Required once the interpreter has attached an analysis
+    #[account(mut)]
+    pub interpreter_dream_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Releases the interpreter's held-back share
+    #[account(
+        mut,
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Box<Account<'info, TreasuryHealth>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireInterpretation<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    pub dream_record: Box<Account<'info, DreamRecord>>,
+    
+    #[account(
+        mut,
+        close = dreamer,
+        seeds = [b"interpretation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub interpretation: Box<Account<'info, DreamInterpretation>>,
+    
+    /// CHECK: the dream's dreamer, who gets the held-back share and the rent
+    #[account(mut, address = dream_record.dreamer)]
+    pub dreamer: UncheckedAccount<'info>,
+    
+    /// CHECK: The dream's `DreamValidation`, which must not exist
+    #[account(seeds = [b"validation", dream_record.key().as_ref()], bump)]
+    pub dream_validation: UncheckedAccount<'info>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = dream_mint,
+        associated_token::authority = dreamer,
+        associated_token::token_program = token_program
+    )]
+    pub dreamer_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Box<Account<'info, TreasuryHealth>>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SetValidatorGate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ValidatorGate>(),
+        seeds = [b"validator_gate"],
+        bump
+    )]
+    pub validator_gate: Account<'info, ValidatorGate>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterValidator<'info> {
+    #[account(mut)]
+    pub validator: Signer<'info>,
+    
+    #[account(seeds = [b"validator_gate"], bump)]
+    pub validator_gate: Account<'info, ValidatorGate>,
+    
+    #[account(
+        init,
+        payer = validator,
+        space = 8 + std::mem::size_of::<ValidatorRegistration>(),
+        seeds = [b"validator", validator.key().as_ref()],
+        bump
+    )]
+    pub validator_registration: Account<'info, ValidatorRegistration>,
+    
+    /// The validator's Oneirobot, required while the gate is on
+    pub nft_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: checked against the gate's NFT program in `oneirobot_dream_level`
+    pub nft_attributes: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeValidator<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    
+    #[account(seeds = [b"validator_gate"], bump)]
+    pub validator_gate: Account<'info, ValidatorGate>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator", validator_registration.validator.as_ref()],
+        bump,
+        close = challenger
+    )]
+    pub validator_registration: Account<'info, ValidatorRegistration>,
+    
+    /// Whichever token account holds the registered Oneirobot now
+    pub nft_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: checked against the gate's NFT program in `oneirobot_dream_level`
+    pub nft_attributes: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct SetValidatorRewards<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ValidatorRewardPool>(),
+        seeds = [b"validator_reward_pool"],
+        bump
+    )]
+    pub validator_reward_pool: Box<Account<'info, ValidatorRewardPool>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = dream_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"validator_reward_vault"],
+        bump
+    )]
+    pub validator_reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SetValidatorClaimCap<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator_reward_pool"],
+        bump
+    )]
+    pub validator_reward_pool: Account<'info, ValidatorRewardPool>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimValidatorRewards<'info> {
+    #[account(mut)]
+    pub validator: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator_rewards", validator.key().as_ref()],
+        bump,
+        has_one = validator
+    )]
+    pub validator_rewards: Box<Account<'info, ValidatorRewards>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator_reward_pool"],
+        bump
+    )]
+    pub validator_reward_pool: Box<Account<'info, ValidatorRewardPool>>,
+    
+    #[account(
+        mut,
+        seeds = [b"validator_reward_vault"],
+        bump
+    )]
+    pub validator_reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = validator,
+        associated_token::token_program = token_program
+    )]
+    pub validator_dream_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SetSpliceConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<SpliceConfig>(),
+        seeds = [b"splice_config"],
+        bump
+    )]
+    pub splice_config: Account<'info, SpliceConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SpliceFromDreams<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(seeds = [b"splice_config"], bump)]
+    pub splice_config: Account<'info, SpliceConfig>,
+    
+    /// CHECK: PDA that signs the spliced mint; holds nothing
+    #[account(seeds = [b"splicer"], bump)]
+    pub splicer: UncheckedAccount<'info>,
+    
+    /// CHECK: The OneirobotNFT program named in the splice config
+    #[account(executable, address = splice_config.nft_program)]
+    pub nft_program: UncheckedAccount<'info>,
+    
+    /// CHECK: The NFT program's state, checked by the NFT program
+    #[account(mut)]
+    pub oneirobot_state: UncheckedAccount<'info>,
+    
+    /// CHECK: Created by the NFT program
+    #[account(mut)]
+    pub nft_attributes: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub oneirobot_mint: Signer<'info>,
+    
+    /// CHECK: The dreamer's associated token account, created by the NFT program
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Created by Token Metadata
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Created by Token Metadata
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+    
+    /// CHECK: Collects the mint fee
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: UncheckedAccount<'info>,
+    
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: SPL Token, which Oneirobots are minted with
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: UncheckedAccount<'info>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
+    /// CHECK: Token Metadata
+    #[account(address = oneirobot::TOKEN_METADATA_PROGRAM_ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDreamCertificate<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(has_one = dreamer)]
+    pub dream_record: Box<Account<'info, DreamRecord>>,
+    
+    #[account(
+        seeds = [b"validation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub dream_validation: Box<Account<'info, DreamValidation>>,
+    
+    #[account(
+        init,
+        payer = dreamer,
+        space = 8 + std::mem::size_of::<DreamCertificate>(),
+        seeds = [b"certificate", dream_record.key().as_ref()],
+        bump
+    )]
+    pub certificate: Box<Account<'info, DreamCertificate>>,
+    
+    #[account(seeds = [b"splice_config"], bump)]
+    pub splice_config: Box<Account<'info, SpliceConfig>>,
+    
+    /// CHECK: PDA that signs the certificate mint; holds nothing
+    #[account(seeds = [b"splicer"], bump)]
+    pub splicer: UncheckedAccount<'info>,
+    
+    /// CHECK: The OneirobotNFT program named in the splice config
+    #[account(executable, address = splice_config.nft_program)]
+    pub nft_program: UncheckedAccount<'info>,
+    
+    /// CHECK: The NFT program's state, checked by the NFT program
+    #[account(mut)]
+    pub oneirobot_state: UncheckedAccount<'info>,
+    
+    /// CHECK: Created by the NFT program
+    #[account(mut)]
+    pub nft_attributes: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub oneirobot_mint: Signer<'info>,
+    
+    /// CHECK: The dreamer's associated token account, created by the NFT program
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Created by Token Metadata
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Created by Token Metadata
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+    
+    /// CHECK: Collects the mint fee
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    pub fee_vault: UncheckedAccount<'info>,
+    
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: SPL Token, which Oneirobots are minted with
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: UncheckedAccount<'info>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
+    /// CHECK: Token Metadata
+    #[account(address = oneirobot::TOKEN_METADATA_PROGRAM_ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EquipOneirobotToProfile<'info> {
+    pub dreamer: Signer<'info>,
+    
+    /// Pays rent; the dreamer or a relayer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(seeds = [b"splice_config"], bump)]
+    pub splice_config: Account<'info, SpliceConfig>,
+    
+    pub nft_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: checked against the splice config's NFT program in the handler
+    pub nft_attributes: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<Companion>(),
+        seeds = [b"companion", dreamer.key().as_ref()],
+        bump
+    )]
+    pub companion: Account<'info, Companion>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<CompanionXp>(),
+        seeds = [b"companion_xp", nft_token_account.mint.as_ref()],
+        bump
+    )]
+    pub companion_xp: Account<'info, CompanionXp>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FlagDream<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    #[account(
+        mut,
+        seeds = [b"profile", dream_record.dreamer.as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Account<'info, DreamerProfile>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<DreamFlag>(),
+        seeds = [b"flag", dream_record.key().as_ref()],
+        bump
+    )]
+    pub dream_flag: Account<'info, DreamFlag>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeLeaderboard<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Leaderboard>(),
+        seeds = [b"leaderboard"],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StartSeason<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"leaderboard"],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLeaderboard<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [b"season_score", season_score.dreamer.as_ref()],
+        bump
+    )]
+    pub season_score: Account<'info, SeasonScore>,
+    
+    #[account(
+        mut,
+        seeds = [b"leaderboard"],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    
+    // Pass both to collect the crank bounty
+    #[account(
+        mut,
+        seeds = [b"crank_pool"],
+        bump
+    )]
+    pub crank_pool: Option<Account<'info, CrankPool>>,
+    
+    #[account(mut)]
+    pub cranker: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStatsRing<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<StatsRing>(),
+        seeds = [b"stats_ring"],
+        bump
+    )]
+    pub stats_ring: AccountLoader<'info, StatsRing>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RollStats<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: May not exist yet; read by `vault_balance`
+    #[account(
+        seeds = [b"compressed_stake_vault"],
+        bump
+    )]
+    pub compressed_stake_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"stats_ring"],
+        bump
+    )]
+    pub stats_ring: AccountLoader<'info, StatsRing>,
+}
+
+#[derive(Accounts)]
+pub struct SetCrankBounty<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<CrankPool>(),
+        seeds = [b"crank_pool"],
+        bump
+    )]
+    pub crank_pool: Account<'info, CrankPool>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolFees<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<FeeVault>(),
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"treasury_health"],
+        bump,
+        constraint = treasury_health.healthy @ DreamError::TreasuryUnhealthy
+    )]
+    pub treasury_health: Account<'info, TreasuryHealth>,
+    
+    /// CHECK: Any account chosen by the authority to receive the fees
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReconcileTreasury<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<TreasuryHealth>(),
+        seeds = [b"treasury_health"],
+        bump
+    )]
+    pub treasury_health: Box<Account<'info, TreasuryHealth>>,
+    
+    /// CHECK: May not exist yet; read by `vault_balance`
+    #[account(
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: May not exist yet; read by `vault_balance`
+    #[account(
+        seeds = [b"reward_stream_vault"],
+        bump
+    )]
+    pub reward_stream_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"insurance_fund"],
+        bump
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceFeeShare<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<InsuranceFund>(),
+        seeds = [b"insurance_fund"],
+        bump
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = dream_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CoverShortfall<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Pass the one named by `target`
+    #[account(
+        mut,
+        seeds = [b"crank_pool"],
+        bump
+    )]
+    pub crank_pool: Option<Account<'info, CrankPool>>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_pool_vault"],
+        bump
+    )]
+    pub reward_pool_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct FundCrankPool<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(
+        mut,
+        seeds = [b"crank_pool"],
+        bump
+    )]
+    pub crank_pool: Account<'info, CrankPool>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSponsorPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<SponsorPool>(),
+        seeds = [b"sponsor_pool"],
+        bump
+    )]
+    pub sponsor_pool: Account<'info, SponsorPool>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundSponsorPool<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(
+        mut,
+        seeds = [b"sponsor_pool"],
+        bump
+    )]
+    pub sponsor_pool: Account<'info, SponsorPool>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSponsorship<'info> {
+    /// Usually the app's fee payer; the dreamer may hold no SOL at all
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"sponsor_pool"],
+        bump
+    )]
+    pub sponsor_pool: Account<'info, SponsorPool>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<Sponsorship>(),
+        seeds = [b"sponsorship", dreamer.key().as_ref()],
+        bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: The dreamer's DREAM account; its rent is covered if it doesn't exist yet
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &dreamer.key(),
+            &treasury.dream_mint,
+            &token_2022::ID
+        )
+    )]
+    pub dreamer_dream_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Must not exist yet; only new dreamers are sponsored
+    #[account(
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: UncheckedAccount<'info>,
+    
+    /// CHECK: Today's bucket, whose rent is covered if it doesn't exist yet
+    #[account(
+        seeds = [b"epoch_stats".as_ref(), &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes()],
+        bump
+    )]
+    pub epoch_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: The dreamer's session of today, whose rent is covered if it
+    /// doesn't exist yet
+    #[account(
+        seeds = [
+            b"sleep_session".as_ref(),
+            dreamer.key().as_ref(),
+            &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub sleep_session: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    
+    /// CHECK: Instructions sysvar, read to find the sponsored record_dream
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenSession<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<SleepSession>(),
+        seeds = [
+            b"sleep_session".as_ref(),
+            dreamer.key().as_ref(),
+            &EpochStats::day_of(Clock::get()?.unix_timestamp).to_le_bytes(),
+        ],
+        bump
+    )]
+    pub sleep_session: Account<'info, SleepSession>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day: u32)]
+pub struct CloseSession<'info> {
+    /// Gets the rent back when the session is from a past day
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = dreamer,
+        seeds = [b"sleep_session".as_ref(), dreamer.key().as_ref(), &day.to_le_bytes()],
+        bump
+    )]
+    pub sleep_session: Account<'info, SleepSession>,
+}
+
+#[derive(Accounts)]
+pub struct InterfaceDream<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamerProfile>(),
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Account<'info, DreamerProfile>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DreamInterface::SPACE,
+        seeds = [b"interface", dreamer.key().as_ref(), &dreamer_profile.interface_count.to_le_bytes()],
+        bump
+    )]
+    pub dream_interface: Account<'info, DreamInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(interface_index: u64)]
+pub struct UpdateInterface<'info> {
+    pub dreamer: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = dreamer,
+        seeds = [b"interface", dreamer.key().as_ref(), &interface_index.to_le_bytes()],
+        bump
+    )]
+    pub dream_interface: Account<'info, DreamInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(interface_index: u64)]
+pub struct CloseInterface<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = dreamer,
+        close = dreamer,
+        seeds = [b"interface", dreamer.key().as_ref(), &interface_index.to_le_bytes()],
+        bump
+    )]
+    pub dream_interface: Account<'info, DreamInterface>,
+}
+
+#[derive(Accounts)]
+pub struct StakeLucid<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<LucidStake>(),
+        seeds = [b"stake", user.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, LucidStake>,
+    
+    #[account(mut, token::mint = stake_mint)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = stake_mint)]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: Pre-mint stake layout; verified on deserialization
+    #[account(
+        mut,
+        owner = crate::ID @ DreamError::InvalidLegacyStake,
+        seeds = [b"stake", user.key().as_ref()],
+        bump
+    )]
+    pub legacy_stake: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<LucidStake>(),
+        seeds = [b"stake", user.key().as_ref(), treasury.lucid_mint.as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, LucidStake>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(access_level: u8)]
+pub struct CreateTierCredentialMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: Created and initialized as a Token-2022 mint in the handler
+    #[account(
+        mut,
+        seeds = [b"tier_credential", &[access_level]],
+        bump
+    )]
+    pub credential_mint: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncTierCredentials<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Owner of the stake and the credentials
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: The user's LUCID stake; empty if they never staked
+    #[account(
+        seeds = [b"stake", user.key().as_ref(), treasury.lucid_mint.as_ref()],
+        bump
+    )]
+    pub lucid_stake: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"tier_credential", &[TIER_CREDENTIAL_LEVELS[0]]],
+        bump
+    )]
+    pub vip_credential_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = vip_credential_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub vip_credential_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"tier_credential", &[TIER_CREDENTIAL_LEVELS[1]]],
+        bump
+    )]
+    pub quantum_credential_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = quantum_credential_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub quantum_credential_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCompressedStakeTree<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.smind_mint)]
+    pub smind_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<CompressedStakeTree>(),
+        seeds = [b"compressed_stake_tree"],
+        bump
+    )]
+    pub compressed_stake_tree: AccountLoader<'info, CompressedStakeTree>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = smind_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"compressed_stake_vault"],
+        bump
+    )]
+    pub compressed_stake_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CompressedStake<'info> {
+    pub staker: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.smind_mint)]
+    pub smind_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"compressed_stake_tree"],
+        bump
+    )]
+    pub compressed_stake_tree: AccountLoader<'info, CompressedStakeTree>,
+    
+    #[account(
+        mut,
+        seeds = [b"compressed_stake_vault"],
+        bump
+    )]
+    pub compressed_stake_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = smind_mint,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program
+    )]
+    pub staker_smind_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CompressedUnstake<'info> {
+    pub staker: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    
+    #[account(address = treasury.smind_mint)]
+    pub smind_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"compressed_stake_tree"],
+        bump
+    )]
+    pub compressed_stake_tree: AccountLoader<'info, CompressedStakeTree>,
+    
+    #[account(
+        mut,
+        seeds = [b"compressed_stake_vault"],
+        bump
+    )]
+    pub compressed_stake_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = smind_mint,
+        associated_token::authority = staker,
+        associated_token::token_program = token_program
+    )]
+    pub staker_smind_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeDreamArchiveTree<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<DreamArchiveTree>(),
+        seeds = [b"dream_archive_tree"],
+        bump
+    )]
+    pub dream_archive_tree: AccountLoader<'info, DreamArchiveTree>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ArchiveDream<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = dreamer,
+        seeds = [b"dream", dreamer.key().as_ref(), &dream_record.id.to_le_bytes()],
+        bump
+    )]
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    /// Only validated dreams are archived.
+    #[account(
+        seeds = [b"validation", dream_record.key().as_ref()],
+        bump
+    )]
+    pub dream_validation: Account<'info, DreamValidation>,
+    
+    #[account(
+        mut,
+        seeds = [b"dream_archive_tree"],
+        bump
+    )]
+    pub dream_archive_tree: AccountLoader<'info, DreamArchiveTree>,
+    
+    #[account(
+        init,
+        payer = dreamer,
+        space = 8 + std::mem::size_of::<ArchivedDream>(),
+        seeds = [b"archived_dream", dream_record.key().as_ref()],
+        bump
+    )]
+    pub archived_dream: Account<'info, ArchivedDream>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StoreTreasuryBump<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(Accounts)]
+pub struct SetMevProtection<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateLegacyStorage<'info> {
+    #[account(mut)]
+    pub dreamer: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Owned by the legacy program; layout is verified on deserialization
+    #[account(
+        owner = LEGACY_PROGRAM_ID @ DreamError::InvalidLegacyStorage,
+        constraint = legacy_storage.data_len() >= DreamStorage::SPACE @ DreamError::InvalidLegacyStorage
+    )]
+    pub legacy_storage: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamerProfile>(),
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Account<'info, DreamerProfile>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateDreamerProfile<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Only used to derive the profile address
+    pub dreamer: UncheckedAccount<'info>,
+    
+    /// CHECK: `DreamerProfileV1` layout; verified on deserialization
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"profile", dreamer.key().as_ref()],
+        bump
+    )]
+    pub dreamer_profile: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateDreamRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: A `DreamRecord` that may predate `session`; checked by discriminator
+    #[account(mut, owner = crate::ID)]
+    pub dream_record: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct RegisterForeignEmitter<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ForeignEmitter>(),
+        seeds = [b"foreign_emitter".as_ref(), &chain.to_le_bytes()],
+        bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Core bridge accounts needed to post a message from the program emitter.
+#[derive(Accounts)]
+pub struct WormholeAccounts<'info> {
+    /// CHECK: Core bridge config; address checked by seeds
+    #[account(
+        mut,
+        seeds = [b"Bridge"],
+        bump,
+        seeds::program = WORMHOLE_CORE_BRIDGE_ID
+    )]
+    pub bridge: UncheckedAccount<'info>,
+    
+    /// New message account, created by the core bridge
+    #[account(mut)]
+    pub message: Signer<'info>,
+    
+    /// CHECK: Program emitter PDA; signs `post_message`
+    #[account(
+        seeds = [b"emitter"],
+        bump
+    )]
+    pub emitter: UncheckedAccount<'info>,
+    
+    /// CHECK: Emitter sequence tracker owned by the core bridge
+    #[account(
+        mut,
+        seeds = [b"Sequence", emitter.key().as_ref()],
+        bump,
+        seeds::program = WORMHOLE_CORE_BRIDGE_ID
+    )]
+    pub sequence: UncheckedAccount<'info>,
+    
+    /// CHECK: Core bridge fee collector
+    #[account(
+        mut,
+        seeds = [b"fee_collector"],
+        bump,
+        seeds::program = WORMHOLE_CORE_BRIDGE_ID
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+    
+    /// CHECK: Wormhole core bridge program
+    #[account(address = WORMHOLE_CORE_BRIDGE_ID)]
+    pub core_bridge_program: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestDreamMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: InterfaceAccount<'info, Mint>,
+    
+    pub wormhole: WormholeAccounts<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, recipient_chain: u16)]
+pub struct BridgeDreamOut<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [b"foreign_emitter".as_ref(), &recipient_chain.to_le_bytes()],
+        bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = dream_mint,
+        associated_token::authority = sender,
+        associated_token::token_program = token_program
+    )]
+    pub sender_dream_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        token::mint = dream_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+        seeds = [b"dream_custody"],
+        bump
+    )]
+    pub dream_custody: InterfaceAccount<'info, TokenAccount>,
+    
+    pub wormhole: WormholeAccounts<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(emitter_chain: u16, sequence: u64)]
+pub struct CompleteBridgeIn<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: Verified `PostedVAA` owned by the core bridge; address and
+    /// contents are checked in the handler
+    #[account(owner = WORMHOLE_CORE_BRIDGE_ID @ DreamError::InvalidBridgeMessage)]
+    pub posted_vaa: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"foreign_emitter".as_ref(), &emitter_chain.to_le_bytes()],
+        bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<BridgeReceipt>(),
+        seeds = [b"bridge_receipt".as_ref(), &emitter_chain.to_le_bytes(), &sequence.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, BridgeReceipt>,
+    
+    /// CHECK: Must match the transfer's recipient; checked in the handler
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = dream_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_dream_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"dream_custody"],
+        bump
+    )]
+    pub dream_custody: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-fn calculate_access_level(amount: u64) -> u8 {
-    // Calculate access level based on LUCID stake amount
-    match amount {
-        0..=1_000_000_000 => 1,           // 0-1 LUCID: Basic
-        1_000_000_001..=10_000_000_000 => 2,  // 1-10 LUCID: Premium  
-        10_000_000_001..=100_000_000_000 => 3, // 10-100 LUCID: VIP
-        _ => 4,                                  // 100+ LUCID: Quantum
-    }
+#[derive(Accounts)]
+#[instruction(emitter_chain: u16, sequence: u64)]
+pub struct RecordMirroredDream<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Must match the mirrored dream's dreamer; checked in the handler
+    pub dreamer: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<DreamRecord>(),
+        seeds = [b"dream", dreamer.key().as_ref(), &treasury.total_dreams_recorded.to_le_bytes()],
+        bump
+    )]
+    pub dream_record: Account<'info, DreamRecord>,
+    
+    /// CHECK: Verified `PostedVAA` owned by the core bridge; address and
+    /// contents are checked in the handler
+    #[account(owner = WORMHOLE_CORE_BRIDGE_ID @ DreamError::InvalidBridgeMessage)]
+    pub posted_vaa: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"foreign_emitter".as_ref(), &emitter_chain.to_le_bytes()],
+        bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<BridgeReceipt>(),
+        seeds = [b"bridge_receipt".as_ref(), &emitter_chain.to_le_bytes(), &sequence.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Account<'info, BridgeReceipt>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeTokens<'info> {
+pub struct ConfigureMessageAdapter<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
     #[account(
-        init,
-        payer = authority,
-        space = 8 + std::mem::size_of::<Treasury>(),
         seeds = [b"treasury"],
-        bump
+        bump = treasury.bump,
+        has_one = authority
     )]
     pub treasury: Account<'info, Treasury>,
     
-    pub dream_mint: Account<'info, Mint>,
-    pub smind_mint: Account<'info, Mint>,
-    pub lucid_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<MessageAdapter>(),
+        seeds = [b"message_adapter"],
+        bump
+    )]
+    pub message_adapter: Account<'info, MessageAdapter>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
-pub struct RecordDream<'info> {
+#[instruction(origin: u32)]
+pub struct SetTrustedRemote<'info> {
     #[account(mut)]
-    pub dreamer: Signer<'info>,
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<TrustedRemote>(),
+        seeds = [b"trusted_remote".as_ref(), &origin.to_le_bytes()],
+        bump
+    )]
+    pub trusted_remote: Account<'info, TrustedRemote>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(origin: u32)]
+pub struct ReceiveMessage<'info> {
+    #[account(address = message_adapter.delivery_authority @ DreamError::InvalidDeliveryAuthority)]
+    pub delivery_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"message_adapter"],
+        bump
+    )]
+    pub message_adapter: Account<'info, MessageAdapter>,
+    
+    #[account(
+        mut,
+        seeds = [b"trusted_remote".as_ref(), &origin.to_le_bytes()],
+        bump
+    )]
+    pub trusted_remote: Account<'info, TrustedRemote>,
     
     #[account(
         mut,
         seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Only needed for `ApproveFinalizeImmutable`
+    #[account(
+        mut,
+        seeds = [b"upgrade_governance"],
         bump
     )]
+    pub upgrade_governance: Option<Account<'info, UpgradeGovernance>>,
+}
+
+#[derive(Accounts)]
+pub struct SyncLookupTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
     pub treasury: Account<'info, Treasury>,
     
     #[account(
-        init,
-        payer = dreamer,
-        space = 8 + std::mem::size_of::<DreamRecord>(),
-        seeds = [b"dream", dreamer.key().as_ref(), &treasury.total_dreams_recorded.to_le_bytes()],
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<LookupTableConfig>(),
+        seeds = [b"lookup_table"],
         bump
     )]
-    pub dream_record: Account<'info, DreamRecord>,
+    pub lookup_table_config: Account<'info, LookupTableConfig>,
     
+    /// CHECK: Derived from the treasury and `recent_slot` on creation, then
+    /// checked against `lookup_table_config`
     #[account(mut)]
-    pub dream_mint: Account<'info, Mint>,
+    pub lookup_table: UncheckedAccount<'info>,
+    
+    /// CHECK: Address lookup table program
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpgradeProgramInfo<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
     
     #[account(
-        mut,
-        associated_token::mint = dream_mint,
-        associated_token::authority = dreamer,
-        associated_token::token_program = token_program
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ProgramInfo::SPACE,
+        seeds = [b"program_info"],
+        bump
     )]
-    pub dreamer_dream_account: Account<'info, TokenAccount>,
+    pub program_info: Account<'info, ProgramInfo>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token2022>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(Accounts)]
-pub struct InterfaceDream<'info> {
+pub struct SetBuildInfo<'info> {
     #[account(mut)]
-    pub dreamer: Signer<'info>,
+    pub authority: Signer<'info>,
     
     #[account(
-        init,
-        payer = dreamer,
-        space = 8 + std::mem::size_of::<DreamInterface>(),
-        seeds = [b"interface", dreamer.key().as_ref()],
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::DreamMindLucid>,
+    
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + BuildInfo::SPACE,
+        seeds = [b"build_info"],
         bump
     )]
-    pub dream_interface: Account<'info, DreamInterface>,
+    pub build_info: Account<'info, BuildInfo>,
     
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct StakeLucid<'info> {
+pub struct HandOverUpgradeAuthority<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub current_authority: Signer<'info>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::DreamMindLucid>,
+    
+    #[account(
+        mut,
+        constraint = program_data.upgrade_authority_address == Some(current_authority.key())
+            @ DreamError::UnauthorizedAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    /// CHECK: Governance PDA that becomes the upgrade authority
+    #[account(
+        seeds = [b"upgrade_authority"],
+        bump
+    )]
+    pub upgrade_authority: UncheckedAccount<'info>,
     
     #[account(
         init,
-        payer = user,
-        space = 8 + std::mem::size_of::<LucidStake>(),
-        seeds = [b"stake", user.key().as_ref()],
+        payer = current_authority,
+        space = 8 + std::mem::size_of::<UpgradeGovernance>(),
+        seeds = [b"upgrade_governance"],
         bump
     )]
-    pub stake_account: Account<'info, LucidStake>,
+    pub upgrade_governance: Account<'info, UpgradeGovernance>,
+    
+    /// CHECK: Upgradeable BPF loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeImmutable<'info> {
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::DreamMindLucid>,
     
     #[account(mut)]
-    pub user_lucid_account: Account<'info, TokenAccount>,
+    pub program_data: Account<'info, ProgramData>,
+    
+    /// CHECK: Governance PDA holding the upgrade authority; signs the loader CPI
+    #[account(
+        seeds = [b"upgrade_authority"],
+        bump
+    )]
+    pub upgrade_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"upgrade_governance"],
+        bump
+    )]
+    pub upgrade_governance: Account<'info, UpgradeGovernance>,
     
+    /// CHECK: Upgradeable BPF loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
     #[account(mut)]
-    pub lucid_stake_vault: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<GuardianCouncil>(),
+        seeds = [b"guardian_council"],
+        bump
+    )]
+    pub guardian_council: Account<'info, GuardianCouncil>,
     
-    pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
 
-#[account]
-pub struct Treasury {
-    pub authority: Pubkey,
-    pub dream_mint: Pubkey,
-    pub smind_mint: Pubkey,
-    pub lucid_mint: Pubkey,
-    pub total_dreams_recorded: u64,
-    pub total_rewards_distributed: u64,
-    pub mev_protection_enabled: bool,
+#[derive(Accounts)]
+pub struct GuardianPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [b"guardian_council"],
+        bump
+    )]
+    pub guardian_council: Account<'info, GuardianCouncil>,
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority
+    )]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianCancelFinalize<'info> {
+    #[account(
+        seeds = [b"guardian_council"],
+        bump
+    )]
+    pub guardian_council: Account<'info, GuardianCouncil>,
+    
+    #[account(
+        mut,
+        seeds = [b"upgrade_governance"],
+        bump
+    )]
+    pub upgrade_governance: Account<'info, UpgradeGovernance>,
 }
 
-#[account]
-pub struct DreamRecord {
-    pub id: u64,
-    pub dreamer: Pubkey,
-    pub content_hash: [u8; 32],
-    pub timestamp: i64,
-    pub token_reward: u64,
-    pub mev_protected: bool,
+#[derive(Accounts)]
+pub struct GetTreasuryStats<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(address = treasury.dream_mint)]
+    pub dream_mint: InterfaceAccount<'info, Mint>,
 }
 
-#[account]
-pub struct DreamInterface {
-    pub dreamer: Pubkey,
-    pub ipfs_hash: String,
-    pub timestamp: i64,
-    pub access_level: u8,
+#[derive(Accounts)]
+#[instruction(dreamer: Pubkey)]
+pub struct GetDreamerStats<'info> {
+    #[account(
+        seeds = [b"profile", dreamer.as_ref()],
+        bump
+    )]
+    pub dreamer_profile: Option<Account<'info, DreamerProfile>>,
+    
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [b"stake", dreamer.as_ref(), treasury.lucid_mint.as_ref()],
+        bump
+    )]
+    pub stake_account: Option<Account<'info, LucidStake>>,
 }
 
-#[account]
-pub struct LucidStake {
-    pub user: Pubkey,
-    pub amount: u64,
-    pub timestamp: i64,
-    pub access_level: u8,
+#[derive(Accounts)]
+pub struct GetStakeInfo<'info> {
+    /// CHECK: Only used to derive the stake address
+    pub user: UncheckedAccount<'info>,
+    
+    /// CHECK: Only used to derive the stake address
+    pub mint: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"stake", user.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, LucidStake>,
 }
 
-#[error_code]
-pub enum DreamError {
-    #[msg("Insufficient LUCID tokens for access")]
-    InsufficientLucidAccess,
-    #[msg("Dream content too large")]
-    DreamContentTooLarge,
-    #[msg("MEV protection failed")]
-    MevProtectionFailed,
-    #[msg("Invalid access level")]
-    InvalidAccessLevel,
+#[derive(Accounts)]
+pub struct GetCapabilities<'info> {
+    #[account(
+        seeds = [b"upgrade_governance"],
+        bump
+    )]
+    pub upgrade_governance: Option<Account<'info, UpgradeGovernance>>,
+    
+    #[account(
+        seeds = [b"guardian_council"],
+        bump
+    )]
+    pub guardian_council: Option<Account<'info, GuardianCouncil>>,
+    
+    #[account(
+        seeds = [b"compressed_stake_tree"],
+        bump
+    )]
+    pub compressed_stake_tree: Option<AccountLoader<'info, CompressedStakeTree>>,
+    
+    #[account(
+        seeds = [b"dream_archive_tree"],
+        bump
+    )]
+    pub dream_archive_tree: Option<AccountLoader<'info, DreamArchiveTree>>,
 }
\ No newline at end of file
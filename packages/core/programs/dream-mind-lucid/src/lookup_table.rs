@@ -0,0 +1,115 @@
+//! Address lookup table holding the program's static accounts, so clients can
+//! fit large instructions into v0 transactions. The treasury PDA is the
+//! table authority.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        address_lookup_table::{self, state::AddressLookupTable},
+        program::invoke_signed,
+        sysvar,
+    },
+};
+use anchor_spl::{associated_token, token_2022};
+use dream_mind_common::DreamError;
+use dream_mind_state::{wormhole::WORMHOLE_CORE_BRIDGE_ID, Treasury};
+
+pub struct LookupTableAccounts<'a, 'info> {
+    pub lookup_table: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Accounts that appear in most transactions against the program.
+pub fn static_addresses(treasury: &Treasury) -> Vec<Pubkey> {
+    let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &crate::ID).0;
+    let find_core = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &WORMHOLE_CORE_BRIDGE_ID).0;
+    let emitter = find(&[b"emitter"]);
+
+    vec![
+        crate::ID,
+        find(&[b"treasury"]),
+        treasury.dream_mint,
+        treasury.smind_mint,
+        treasury.lucid_mint,
+        token_2022::ID,
+        associated_token::ID,
+        System::id(),
+        sysvar::clock::ID,
+        sysvar::rent::ID,
+        find(&[b"dream_custody"]),
+        find(&[b"reward_pool_vault"]),
+        find(&[b"message_adapter"]),
+        WORMHOLE_CORE_BRIDGE_ID,
+        find_core(&[b"Bridge"]),
+        find_core(&[b"fee_collector"]),
+        find_core(&[b"Sequence", emitter.as_ref()]),
+        emitter,
+    ]
+}
+
+pub fn create(
+    accounts: &LookupTableAccounts,
+    recent_slot: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let (instruction, lookup_table) = address_lookup_table::instruction::create_lookup_table(
+        *accounts.authority.key,
+        *accounts.payer.key,
+        recent_slot,
+    );
+    require_keys_eq!(lookup_table, *accounts.lookup_table.key, DreamError::InvalidLookupTable);
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.lookup_table.clone(),
+            accounts.authority.clone(),
+            accounts.payer.clone(),
+            accounts.system_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Append whichever of `addresses` the table doesn't hold yet. Returns how
+/// many were added.
+pub fn extend_missing(
+    accounts: &LookupTableAccounts,
+    addresses: Vec<Pubkey>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<usize> {
+    let missing: Vec<Pubkey> = {
+        let data = accounts.lookup_table.try_borrow_data()?;
+        let table = AddressLookupTable::deserialize(&data)
+            .map_err(|_| error!(DreamError::InvalidLookupTable))?;
+        addresses
+            .into_iter()
+            .filter(|address| !table.addresses.contains(address))
+            .collect()
+    };
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    let added = missing.len();
+    let instruction = address_lookup_table::instruction::extend_lookup_table(
+        *accounts.lookup_table.key,
+        *accounts.authority.key,
+        Some(*accounts.payer.key),
+        missing,
+    );
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.lookup_table.clone(),
+            accounts.authority.clone(),
+            accounts.payer.clone(),
+            accounts.system_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(added)
+}
@@ -0,0 +1,130 @@
+//! CPI into the OneirobotNFT program's `mint_spliced_oneirobot` and
+//! `mint_certificate_oneirobot`. The NFT program pulls in Metaplex, so it
+//! isn't linked here and the instructions are built by hand in its
+//! `MintOneirobot` account order.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+pub struct MintOneirobotAccounts<'a, 'info> {
+    pub nft_program: &'a AccountInfo<'info>,
+    pub oneirobot_state: &'a AccountInfo<'info>,
+    pub nft_attributes: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub token_account: &'a AccountInfo<'info>,
+    pub metadata: &'a AccountInfo<'info>,
+    pub master_edition: &'a AccountInfo<'info>,
+    /// Pays for the mint and receives the NFT
+    pub dreamer: &'a AccountInfo<'info>,
+    /// Signs as the NFT program's `splicer` and as the mint authority
+    pub splicer: &'a AccountInfo<'info>,
+    pub fee_vault: &'a AccountInfo<'info>,
+    pub treasury: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub metadata_program: &'a AccountInfo<'info>,
+}
+
+/// `mint_spliced_oneirobot`'s arguments
+#[derive(AnchorSerialize)]
+pub struct MintSplicedOneirobotData {
+    pub metadata_uri: String,
+    pub name: String,
+    pub symbol: String,
+    pub dream_level: u8,
+    pub lucid_power: u8,
+}
+
+/// `mint_certificate_oneirobot`'s arguments
+#[derive(AnchorSerialize)]
+pub struct MintCertificateOneirobotData {
+    pub metadata_uri: String,
+    pub name: String,
+    pub symbol: String,
+    pub score: u8,
+}
+
+pub fn mint_spliced_oneirobot(
+    accounts: &MintOneirobotAccounts,
+    args: MintSplicedOneirobotData,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = hash(b"global:mint_spliced_oneirobot").to_bytes()[..8].to_vec();
+    args.serialize(&mut data)?;
+    invoke_mint(accounts, data, signer_seeds)
+}
+
+pub fn mint_certificate_oneirobot(
+    accounts: &MintOneirobotAccounts,
+    args: MintCertificateOneirobotData,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = hash(b"global:mint_certificate_oneirobot").to_bytes()[..8].to_vec();
+    args.serialize(&mut data)?;
+    invoke_mint(accounts, data, signer_seeds)
+}
+
+fn invoke_mint(
+    accounts: &MintOneirobotAccounts,
+    data: Vec<u8>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let instruction = Instruction {
+        program_id: *accounts.nft_program.key,
+        accounts: vec![
+            AccountMeta::new(*accounts.oneirobot_state.key, false),
+            AccountMeta::new(*accounts.nft_attributes.key, false),
+            AccountMeta::new(*accounts.mint.key, true),
+            AccountMeta::new(*accounts.token_account.key, false),
+            AccountMeta::new(*accounts.metadata.key, false),
+            AccountMeta::new(*accounts.master_edition.key, false),
+            // minter, recipient and mint authority
+            AccountMeta::new(*accounts.dreamer.key, true),
+            AccountMeta::new_readonly(*accounts.dreamer.key, false),
+            AccountMeta::new_readonly(*accounts.splicer.key, true),
+            AccountMeta::new(*accounts.fee_vault.key, false),
+            AccountMeta::new_readonly(*accounts.treasury.key, false),
+            AccountMeta::new_readonly(*accounts.rent.key, false),
+            AccountMeta::new_readonly(*accounts.system_program.key, false),
+            AccountMeta::new_readonly(*accounts.token_program.key, false),
+            AccountMeta::new_readonly(*accounts.associated_token_program.key, false),
+            AccountMeta::new_readonly(*accounts.metadata_program.key, false),
+            // No edition config: Anchor reads the program id as `None`
+            AccountMeta::new_readonly(*accounts.nft_program.key, false),
+            AccountMeta::new_readonly(*accounts.splicer.key, true),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.oneirobot_state.clone(),
+            accounts.nft_attributes.clone(),
+            accounts.mint.clone(),
+            accounts.token_account.clone(),
+            accounts.metadata.clone(),
+            accounts.master_edition.clone(),
+            accounts.dreamer.clone(),
+            accounts.splicer.clone(),
+            accounts.fee_vault.clone(),
+            accounts.treasury.clone(),
+            accounts.rent.clone(),
+            accounts.system_program.clone(),
+            accounts.token_program.clone(),
+            accounts.associated_token_program.clone(),
+            accounts.metadata_program.clone(),
+            accounts.nft_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! Failure context for mainnet debugging. `#[traced]` on the program module
+//! routes every handler error through [`log_failure`]; account validation
+//! errors are raised before a handler runs and keep Anchor's own log, which
+//! names the failing account.
+
+use std::fmt;
+
+use anchor_lang::prelude::*;
+
+/// Log `tag`, the handler's key arguments and the numeric error code, e.g.
+/// `trace top_up_pool amount=10000000000000000 failed code=6067`.
+pub(crate) fn log_failure(tag: &str, params: fmt::Arguments, err: &Error) {
+    let code = match err {
+        Error::AnchorError(err) => u64::from(err.error_code_number),
+        Error::ProgramError(err) => u64::from(err.program_error.clone()),
+    };
+    msg!("trace {}{} failed code={}", tag, params, code);
+}
+
+/// Replaces the default panic hook: along with the message and location it
+/// logs the compute units left, so an arithmetic panic can be placed within
+/// the instruction.
+#[cfg(all(feature = "custom-panic", not(feature = "no-entrypoint"), target_os = "solana"))]
+#[no_mangle]
+fn custom_panic(info: &core::panic::PanicInfo<'_>) {
+    msg!("panic {}", info);
+    anchor_lang::solana_program::log::sol_log_compute_units();
+}
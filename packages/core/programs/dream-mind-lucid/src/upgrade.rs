@@ -0,0 +1,35 @@
+//! CPI into the upgradeable BPF loader to move or revoke the program's
+//! upgrade authority.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{bpf_loader_upgradeable, program::invoke_signed},
+};
+
+pub struct SetUpgradeAuthorityAccounts<'a, 'info> {
+    pub program_data: &'a AccountInfo<'info>,
+    pub current_authority: &'a AccountInfo<'info>,
+}
+
+/// Set the upgrade authority to `new_authority`, or make the program
+/// immutable with `None`. `signer_seeds` are needed when the current
+/// authority is a PDA.
+pub fn set_upgrade_authority<'info>(
+    accounts: &SetUpgradeAuthorityAccounts<'_, 'info>,
+    new_authority: Option<&AccountInfo<'info>>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let instruction = bpf_loader_upgradeable::set_upgrade_authority(
+        &crate::ID,
+        accounts.current_authority.key,
+        new_authority.map(|authority| authority.key),
+    );
+
+    let mut account_infos = vec![
+        accounts.program_data.clone(),
+        accounts.current_authority.clone(),
+    ];
+    account_infos.extend(new_authority.cloned());
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+    Ok(())
+}
@@ -0,0 +1,4839 @@
+//! Integration tests for the dream-mind-lucid program on solana-program-test.
+//! Instructions are built with `dream-mind-client`, so these also cover the SDK.
+
+use anchor_lang::{
+    prelude::{AccountInfo, Clock, Pubkey},
+    solana_program::{
+        entrypoint::ProgramResult, hash::hash, program_error::ProgramError, program_pack::Pack,
+    },
+    AccountDeserialize, AnchorDeserialize, AnchorSerialize, Discriminator,
+};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, spl_associated_token_account},
+    token_2022::spl_token_2022::{self, extension::StateWithExtensions},
+};
+use dream_mind_client::{
+    instructions::{self, OneirobotHolding, RecordDreamOptions, SignedDream},
+    pda,
+};
+use dream_mind_common::constants::{
+    CAPABILITY_COMPRESSION, CAPABILITY_DEVNET_FAUCET, CAPABILITY_GOVERNANCE, CAPABILITY_MARKETPLACE,
+    CAPABILITY_VRF, CERTIFICATE_MIN_SCORE, COMPANION_XP_PER_DREAM, DREAM_ARCHIVE_MIN_AGE_SECS,
+    DREAM_LICENSE_DURATION_SECS, DREAM_LICENSE_TREASURY_SHARE_BPS, DREAM_REWARD_PER_RECORD,
+    DREAM_TOTAL_SUPPLY, DUEL_FEE_BPS, EPOCH_STATS_PERIOD_SECS, FAUCET_COOLDOWN_SECS,
+    FAUCET_DREAM_AMOUNT, FAUCET_LUCID_AMOUNT, FAUCET_SMIND_AMOUNT, GUARDIAN_COUNCIL_SIZE,
+    IDEMPOTENCY_NONCE_TTL_SECS, MARKET_FEE_BPS, MAX_DREAMS_PER_BATCH, MAX_DREAM_TAGS,
+    MAX_GRANT_MILESTONES, MAX_INSURANCE_FEE_SHARE_BPS, MAX_LUCIDITY_SCORE, MAX_ORACLE_QUORUM,
+    MAX_PROTOCOL_FEE_LAMPORTS, MAX_REWARDED_DREAMS_PER_SESSION, MAX_REWARD_CONVERSION_RATE_BPS,
+    MAX_REWARD_POOL_TOP_UP_PER_PERIOD, MAX_SESSION_KEY_DURATION_SECS, MAX_VALIDATION_SCORE,
+    MAX_VALIDATOR_FEE_SHARE_BPS, MAX_VALIDATOR_REWARD_PER_VALIDATION,
+    MIN_ESTABLISHED_ACCOUNT_AGE_SECS, MIN_ESTABLISHED_VALIDATED_DREAMS,
+    OPTIMISTIC_CHALLENGE_WINDOW_SECS, OPTIMISTIC_SCORE_BOND, REPUTATION_FLAG_PENALTY,
+    RESEARCH_LICENSE_DURATION_SECS, RESEARCH_LICENSE_PRICE, RESEARCH_TREASURY_SHARE_BPS,
+    REWARD_CLAIM_EXPIRY_SECS, REWARD_CLAIM_GRACE_SECS, REWARD_POOL_PERIOD_SECS,
+    REWARD_STREAM_DURATION_SECS, REWARD_STREAM_THRESHOLD, SESSION_SCOPE_RECORD_DREAM,
+    SMIND_TOTAL_SUPPLY, SPLICE_DREAM_COUNT, TIER_CREDENTIAL_LEVELS, TOKEN_DECIMALS, TOKEN_UNIT,
+    VALIDATION_DISPUTE_TOLERANCE, WORMHOLE_CHAIN_ID_SOLANA,
+};
+use dream_mind_lucid::{
+    cid,
+    intern,
+    legacy::{DreamStorage, DreamerProfileV1, LucidStakeV1, LEGACY_PROGRAM_ID},
+    merkle,
+    messaging::GovernanceAction,
+    oneirobot::OneirobotAttributes,
+    stake_tree,
+    views::{ProgramCapabilities, StakeInfo},
+    wormhole::{find_posted_vaa_address, DreamMirror, PostedVaa, WORMHOLE_CORE_BRIDGE_ID},
+    ArchivedDream, BatchedDream, Charity, CharitySplit, CompanionStamp, CompanionXp,
+    CompressedStakeTree, CrankPool, DreamAnnotation, DreamArchiveTree, DreamCapsule,
+    DreamCertificate, DreamClassification, DreamCoauthors, DreamDuel, DreamError, DreamFlag,
+    DreamFlagReason, DreamInterface, DreamInterpretation, DreamLicense, DreamLicenseType,
+    DreamMetadata, DreamRecord, DreamRecordExtras, DreamValidation, DreamerProfile, DuelStatus,
+    EpochCommitment, EpochStats, FaucetClaim, FeeVault, Grant, GrantStatus, IdempotencyNonce,
+    InsuranceFund, InternedString, Leaderboard, LucidStake, MarketPosition, Mood, OptimisticScore,
+    PinConfirmation, PinningOracle, PredictionMarket, ProgramInfo, RelayNonce, ResearchLicense,
+    RewardClaim, RewardConversionRates, RewardPool, RewardStream, RewardToken, RoyaltyShare,
+    SeasonScore, SessionKey, ShortfallTarget, SleepSession, SplicedDream, SponsorPool, Sponsorship,
+    StatsRing, TranslationBounty, Treasury, TreasuryHealth, ValidationDispute, ValidationQueue,
+    ValidatorRegistration, ValidatorRewardPool, ValidatorRewards, ValidatorStats,
+};
+use proptest::prelude::*;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+// Anchor's entrypoint ties the account slice and AccountInfo lifetimes together,
+// which the program-test processor signature can't express.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    dream_mind_lucid::entry(program_id, accounts, data)
+}
+
+fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "dream_mind_lucid",
+        dream_mind_lucid::ID,
+        processor!(process_instruction),
+    );
+    program_test.prefer_bpf(false);
+    program_test
+}
+
+async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn fetch<T: AccountDeserialize>(context: &mut ProgramTestContext, address: &Pubkey) -> T {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .expect("account not found");
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn assert_dream_error(result: Result<(), BanksClientError>, expected: DreamError) {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, u32::from(expected), "unexpected error code"),
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
+
+async fn simulate_view<T: AnchorDeserialize>(context: &mut ProgramTestContext, ix: Instruction) -> T {
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    simulation.result.unwrap().unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, dream_mind_lucid::ID);
+    T::try_from_slice(&return_data.data).unwrap()
+}
+
+async fn cluster_time(context: &mut ProgramTestContext) -> i64 {
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp
+}
+
+async fn fund(context: &mut ProgramTestContext, to: &Pubkey) {
+    let ix = system_instruction::transfer(&context.payer.pubkey(), to, 1_000_000_000);
+    send(context, &[ix], &[]).await.unwrap();
+}
+
+async fn create_mint(context: &mut ProgramTestContext, mint_authority: &Pubkey) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let space = spl_token_2022::state::Mint::LEN;
+    let ixs = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            &spl_token_2022::ID,
+        ),
+        spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            mint_authority,
+            None,
+            TOKEN_DECIMALS,
+        )
+        .unwrap(),
+    ];
+    send(context, &ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+async fn create_ata(context: &mut ProgramTestContext, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &context.payer.pubkey(),
+        owner,
+        mint,
+        &spl_token_2022::ID,
+    );
+    send(context, &[ix], &[]).await.unwrap();
+    get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::ID)
+}
+
+async fn token_balance(context: &mut ProgramTestContext, token_account: &Pubkey) -> u64 {
+    let account = context.banks_client.get_account(*token_account).await.unwrap().unwrap();
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+        .unwrap()
+        .base
+        .amount
+}
+
+/// DREAM minted into the reward pool by `initialize`.
+const INITIAL_REWARD_POOL: u64 = 1_000_000 * TOKEN_UNIT;
+
+/// Initialize the treasury with a DREAM mint whose authority is the treasury
+/// PDA, and fill the reward pool.
+async fn initialize(context: &mut ProgramTestContext) -> Pubkey {
+    let lucid_mint = create_mint(context, &pda::find_treasury_address().0).await;
+    initialize_with_lucid_mint(context, &lucid_mint).await
+}
+
+/// Tests that stake LUCID pass a mint the payer can mint from.
+async fn initialize_with_lucid_mint(context: &mut ProgramTestContext, lucid_mint: &Pubkey) -> Pubkey {
+    let dream_mint = initialize_treasury(context, lucid_mint).await;
+    let ix = instructions::top_up_pool(&context.payer.pubkey(), &dream_mint, INITIAL_REWARD_POOL);
+    send(context, &[ix], &[]).await.unwrap();
+    dream_mint
+}
+
+async fn initialize_without_pool(context: &mut ProgramTestContext) -> Pubkey {
+    let lucid_mint = create_mint(context, &pda::find_treasury_address().0).await;
+    initialize_treasury(context, &lucid_mint).await
+}
+
+async fn initialize_treasury(context: &mut ProgramTestContext, lucid_mint: &Pubkey) -> Pubkey {
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(context, &treasury).await;
+    let smind_mint = create_mint(context, &treasury).await;
+    let authority = context.payer.pubkey();
+
+    let ix = instructions::initialize_tokens(&authority, &dream_mint, &smind_mint, lucid_mint);
+    let insurance_ix = instructions::set_insurance_fee_share(&authority, &dream_mint, 0);
+    let validator_rewards_ix = instructions::set_validator_rewards(&authority, &dream_mint, 0, 0);
+    let reconcile_ix = instructions::reconcile_treasury(&authority);
+    send(context, &[ix, insurance_ix, validator_rewards_ix, reconcile_ix], &[]).await.unwrap();
+    dream_mint
+}
+
+// ===================== INITIALIZE / GOVERNANCE =====================
+
+#[tokio::test]
+async fn initialize_tokens_creates_treasury() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert_eq!(treasury.authority, context.payer.pubkey());
+    assert_eq!(treasury.dream_mint, dream_mint);
+    assert_eq!(treasury.total_dreams_recorded, 0);
+    assert!(treasury.mev_protection_enabled);
+    assert_eq!(treasury.bump, pda::find_treasury_address().1);
+}
+
+#[tokio::test]
+async fn store_treasury_bump_rejects_stored_bump() {
+    let mut context = program_test().start_with_context().await;
+    initialize(&mut context).await;
+
+    let result = send(&mut context, &[instructions::store_treasury_bump()], &[]).await;
+    assert_dream_error(result, DreamError::TreasuryBumpAlreadyStored);
+}
+
+#[tokio::test]
+async fn bootstrap_creates_mints_treasury_and_accounts() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    send(&mut context, &[instructions::bootstrap(&payer)], &[]).await.unwrap();
+
+    let treasury_address = pda::find_treasury_address().0;
+    let treasury: Treasury = fetch(&mut context, &treasury_address).await;
+    assert_eq!(treasury.authority, payer);
+    for (mint, expected) in [
+        (treasury.dream_mint, pda::find_dream_mint_address().0),
+        (treasury.smind_mint, pda::find_smind_mint_address().0),
+        (treasury.lucid_mint, pda::find_lucid_mint_address().0),
+    ] {
+        assert_eq!(mint, expected);
+        let data = context.banks_client.get_account(mint).await.unwrap().unwrap().data;
+        let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data).unwrap().base;
+        assert_eq!(state.decimals, TOKEN_DECIMALS);
+        assert_eq!(state.mint_authority, Some(treasury_address).into());
+        let ata = get_associated_token_address_with_program_id(&treasury_address, &mint, &spl_token_2022::ID);
+        assert_eq!(token_balance(&mut context, &ata).await, 0);
+    }
+    let health: TreasuryHealth = fetch(&mut context, &pda::find_treasury_health_address().0).await;
+    assert!(health.healthy);
+}
+
+#[tokio::test]
+async fn set_mev_protection_toggles_flag() {
+    let mut context = program_test().start_with_context().await;
+    initialize(&mut context).await;
+
+    let authority = context.payer.pubkey();
+    send(&mut context, &[instructions::set_mev_protection(&authority, false)], &[])
+        .await
+        .unwrap();
+
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert!(!treasury.mev_protection_enabled);
+}
+
+#[tokio::test]
+async fn set_mev_protection_rejects_non_authority() {
+    let mut context = program_test().start_with_context().await;
+    initialize(&mut context).await;
+
+    let intruder = Keypair::new();
+    let result = send(
+        &mut context,
+        &[instructions::set_mev_protection(&intruder.pubkey(), false)],
+        &[&intruder],
+    )
+    .await;
+    assert_dream_error(result, DreamError::UnauthorizedAuthority);
+}
+
+#[tokio::test]
+async fn guardians_pause_until_governance_unpauses() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let guardians: Vec<Keypair> = (0..GUARDIAN_COUNCIL_SIZE).map(|_| Keypair::new()).collect();
+    let keys: [Pubkey; GUARDIAN_COUNCIL_SIZE] = std::array::from_fn(|i| guardians[i].pubkey());
+    let mut repeated = keys;
+    repeated[1] = repeated[0];
+    let ix = instructions::set_guardians(&authority, repeated);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidGuardians);
+    send(&mut context, &[instructions::set_guardians(&authority, keys)], &[])
+        .await
+        .unwrap();
+
+    // Two guardians, or three signers with an outsider among them, fall short
+    let outsider = Keypair::new();
+    for signers in [
+        vec![&guardians[0], &guardians[1]],
+        vec![&guardians[0], &guardians[1], &outsider],
+    ] {
+        let keys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+        let result = send(&mut context, &[instructions::guardian_pause(&keys)], &signers).await;
+        assert_dream_error(result, DreamError::GuardianThresholdNotMet);
+    }
+    let signers = [&guardians[0], &guardians[2], &guardians[4]];
+    let keys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+    send(&mut context, &[instructions::guardian_pause(&keys)], &signers)
+        .await
+        .unwrap();
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert!(treasury.paused);
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let metadata = DreamMetadata {
+        mood: Mood::Peaceful,
+        lucidity_score: 7,
+        sleep_duration_minutes: 450,
+    };
+    let now = cluster_time(&mut context).await;
+    let record = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        metadata,
+        RecordDreamOptions::default(),
+        now,
+    );
+    let result = send(&mut context, &[record.clone()], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::ProgramPaused);
+    let result = send(
+        &mut context,
+        &[instructions::fund_crank_pool(&dreamer.pubkey(), 1_000_000)],
+        &[&dreamer],
+    )
+    .await;
+    assert_dream_error(result, DreamError::ProgramPaused);
+
+    // Guardians can't lift the pause; governance can
+    let guardian = &guardians[0];
+    let result = send(
+        &mut context,
+        &[instructions::unpause(&guardian.pubkey())],
+        &[guardian],
+    )
+    .await;
+    assert!(result.is_err());
+    send(&mut context, &[instructions::unpause(&authority)], &[])
+        .await
+        .unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[record], &[&dreamer]).await.unwrap();
+}
+
+#[tokio::test]
+async fn upgrade_program_info_records_build_metadata() {
+    let mut context = program_test().start_with_context().await;
+    initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let ix = instructions::upgrade_program_info(
+        &authority,
+        "0.2.0".to_string(),
+        "a".repeat(40),
+        "https://example.com/audit.pdf".to_string(),
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let info: ProgramInfo = fetch(&mut context, &pda::find_program_info_address().0).await;
+    assert_eq!(info.version, "0.2.0");
+    assert_eq!(info.git_commit, "a".repeat(40));
+
+    let ix = instructions::upgrade_program_info(&authority, "0.2.0".to_string(), "a".repeat(41), String::new());
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidProgramInfo);
+}
+
+// ===================== RECORD DREAM =====================
+
+#[tokio::test]
+async fn record_dream_mints_reward() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let dreamer_ata = create_ata(&mut context, &dreamer.pubkey(), &dream_mint).await;
+
+    let metadata = DreamMetadata {
+        mood: Mood::Peaceful,
+        lucidity_score: 7,
+        sleep_duration_minutes: 450,
+    };
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        metadata,
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let record: DreamRecord =
+        fetch(&mut context, &pda::find_dream_record_address(&dreamer.pubkey(), 0).0).await;
+    assert_eq!(record.id, 0);
+    assert_eq!(record.dreamer, dreamer.pubkey());
+    assert_eq!(record.content_hash, [7u8; 32]);
+    assert!(record.mev_protected);
+    assert_eq!(record.origin_chain, WORMHOLE_CHAIN_ID_SOLANA);
+    assert_eq!(record.metadata, metadata);
+    assert_eq!(token_balance(&mut context, &dreamer_ata).await, DREAM_REWARD_PER_RECORD);
+
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert_eq!(treasury.total_dreams_recorded, 1);
+    assert_eq!(treasury.total_rewards_distributed, DREAM_REWARD_PER_RECORD);
+}
+
+#[tokio::test]
+async fn record_dream_creates_missing_reward_account() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    let metadata = DreamMetadata::default();
+    let now = cluster_time(&mut context).await;
+
+    // The reward must be paid in the treasury's mint
+    let payer = context.payer.pubkey();
+    let other_mint = create_mint(&mut context, &payer).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &other_mint,
+        0,
+        [7u8; 32],
+        metadata,
+        RecordDreamOptions::default(),
+        now,
+    );
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert!(result.is_err());
+
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        metadata,
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let dreamer_ata =
+        get_associated_token_address_with_program_id(&dreamer.pubkey(), &dream_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &dreamer_ata).await, DREAM_REWARD_PER_RECORD);
+}
+
+#[tokio::test]
+async fn rewards_are_paid_from_capped_reward_pool() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize_without_pool(&mut context).await;
+    let authority = context.payer.pubkey();
+    let vault = pda::find_reward_pool_vault_address().0;
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let record = |dream_id| {
+        instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id,
+            [7u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        )
+    };
+    send(&mut context, &[instructions::top_up_pool(&authority, &dream_mint, 0)], &[])
+        .await
+        .unwrap();
+    let result = send(&mut context, &[record(0)], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::RewardPoolDepleted);
+
+    let ix = instructions::top_up_pool(&authority, &dream_mint, MAX_REWARD_POOL_TOP_UP_PER_PERIOD + 1);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::RewardPoolCapExceeded);
+    let ix = instructions::top_up_pool(&authority, &dream_mint, MAX_REWARD_POOL_TOP_UP_PER_PERIOD);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[record(0)], &[&dreamer]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &vault).await,
+        MAX_REWARD_POOL_TOP_UP_PER_PERIOD - DREAM_REWARD_PER_RECORD
+    );
+
+    // The cap resets in the next period
+    let ix = instructions::top_up_pool(&authority, &dream_mint, 1);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::RewardPoolCapExceeded);
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += REWARD_POOL_PERIOD_SECS;
+    context.set_sysvar(&clock);
+    send(&mut context, &[instructions::top_up_pool(&authority, &dream_mint, 1)], &[])
+        .await
+        .unwrap();
+
+    let pool: RewardPool = fetch(&mut context, &pda::find_reward_pool_address().0).await;
+    assert_eq!(pool.period_minted, 1);
+    assert_eq!(pool.total_minted, MAX_REWARD_POOL_TOP_UP_PER_PERIOD + 1);
+}
+
+#[tokio::test]
+async fn failed_instructions_log_a_trace_with_key_params() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let amount = MAX_REWARD_POOL_TOP_UP_PER_PERIOD;
+    let ix = instructions::top_up_pool(&context.payer.pubkey(), &dream_mint, amount);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    assert!(simulation.result.unwrap().is_err());
+    let logs = simulation.simulation_details.unwrap().logs;
+    let code = u32::from(DreamError::RewardPoolCapExceeded);
+    let trace = format!("Program log: trace top_up_pool amount={amount} failed code={code}");
+    assert!(logs.contains(&trace), "no trace line in {logs:#?}");
+}
+
+#[tokio::test]
+async fn faucet_mints_test_tokens_once_per_cooldown_on_devnet() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    let wallet = Keypair::new();
+    fund(&mut context, &wallet.pubkey()).await;
+
+    let faucet = || {
+        instructions::faucet_mint(
+            &wallet.pubkey(),
+            &dream_mint,
+            &treasury.smind_mint,
+            &treasury.lucid_mint,
+        )
+    };
+    let result = send(&mut context, &[faucet()], &[&wallet]).await;
+    if !cfg!(feature = "devnet") {
+        assert_dream_error(result, DreamError::FaucetDisabled);
+        return;
+    }
+    result.unwrap();
+    let grants = [
+        (dream_mint, FAUCET_DREAM_AMOUNT),
+        (treasury.smind_mint, FAUCET_SMIND_AMOUNT),
+        (treasury.lucid_mint, FAUCET_LUCID_AMOUNT),
+    ];
+    for (mint, amount) in grants {
+        let ata =
+            get_associated_token_address_with_program_id(&wallet.pubkey(), &mint, &spl_token_2022::ID);
+        assert_eq!(token_balance(&mut context, &ata).await, amount);
+    }
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[faucet()], &[&wallet]).await;
+    assert_dream_error(result, DreamError::FaucetCooldown);
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += FAUCET_COOLDOWN_SECS;
+    context.set_sysvar(&clock);
+    send(&mut context, &[faucet()], &[&wallet]).await.unwrap();
+
+    let claim: FaucetClaim =
+        fetch(&mut context, &pda::find_faucet_claim_address(&wallet.pubkey()).0).await;
+    assert_eq!((claim.wallet, claim.claims), (wallet.pubkey(), 2));
+    let ata =
+        get_associated_token_address_with_program_id(&wallet.pubkey(), &dream_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &ata).await, 2 * FAUCET_DREAM_AMOUNT);
+}
+
+#[tokio::test]
+async fn rewards_convert_to_smind_or_lucid_at_claim() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    let claim = |token, mint, dream_amount| {
+        instructions::claim_reward_as(&dreamer.pubkey(), &dream_mint, mint, &record, token, dream_amount)
+    };
+
+    // No rates set yet
+    let half = DREAM_REWARD_PER_RECORD / 2;
+    let ix = claim(RewardToken::Smind, &treasury.smind_mint, half);
+    assert!(send(&mut context, &[ix], &[&dreamer]).await.is_err());
+    let ix = instructions::set_conversion_rates(&authority, MAX_REWARD_CONVERSION_RATE_BPS + 1, 0);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::ConversionRateTooHigh);
+    send(&mut context, &[instructions::set_conversion_rates(&authority, 20_000, 0)], &[])
+        .await
+        .unwrap();
+
+    let ix = claim(RewardToken::Lucid, &treasury.lucid_mint, half);
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::ConversionDisabled);
+    let ix = claim(RewardToken::Smind, &treasury.smind_mint, DREAM_REWARD_PER_RECORD + 1);
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidClaimAmount);
+    // The mint must match the token
+    let ix = claim(RewardToken::Smind, &treasury.lucid_mint, half);
+    assert!(send(&mut context, &[ix], &[&dreamer]).await.is_err());
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let ix = claim(RewardToken::Smind, &treasury.smind_mint, half);
+    send(&mut context, &[ix.clone()], &[&dreamer]).await.unwrap();
+    let ata = |mint| get_associated_token_address_with_program_id(&dreamer.pubkey(), mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &ata(&dream_mint)).await, DREAM_REWARD_PER_RECORD - half);
+    assert_eq!(token_balance(&mut context, &ata(&treasury.smind_mint)).await, 2 * half);
+    let reward_claim: RewardClaim = fetch(&mut context, &pda::find_reward_claim_address(&record).0).await;
+    assert_eq!(
+        (reward_claim.token, reward_claim.dream_amount, reward_claim.amount_out),
+        (RewardToken::Smind, half, 2 * half)
+    );
+
+    // Once per dream
+    context.get_new_latest_blockhash().await.unwrap();
+    assert!(send(&mut context, &[ix], &[&dreamer]).await.is_err());
+}
+
+#[tokio::test]
+async fn record_dream_routes_charity_split() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let charity = Keypair::new().pubkey();
+    let ix = instructions::set_charity_approval(&authority, &dream_mint, &charity, true);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let ix = instructions::set_charity_split(&dreamer.pubkey(), &charity, 2_500);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let now = cluster_time(&mut context).await;
+    let options = RecordDreamOptions {
+        charity: Some(charity),
+        ..Default::default()
+    };
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        options,
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let donation = DREAM_REWARD_PER_RECORD / 4;
+    let dreamer_ata = get_associated_token_address_with_program_id(
+        &dreamer.pubkey(),
+        &dream_mint,
+        &spl_token_2022::ID,
+    );
+    let charity_ata =
+        get_associated_token_address_with_program_id(&charity, &dream_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &dreamer_ata).await, DREAM_REWARD_PER_RECORD - donation);
+    assert_eq!(token_balance(&mut context, &charity_ata).await, donation);
+    let split: CharitySplit =
+        fetch(&mut context, &pda::find_charity_split_address(&dreamer.pubkey()).0).await;
+    assert_eq!(split.total_donated, donation);
+    let registry: Charity = fetch(&mut context, &pda::find_charity_address(&charity).0).await;
+    assert_eq!(registry.total_received, donation);
+}
+
+#[tokio::test]
+async fn co_dreamers_confirm_their_reward_share() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+
+    let dreamer = Keypair::new();
+    let co_dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    fund(&mut context, &co_dreamer.pubkey()).await;
+
+    // Record and co-sign in one transaction
+    let now = cluster_time(&mut context).await;
+    let options = RecordDreamOptions {
+        co_dreamers: vec![co_dreamer.pubkey()],
+        ..Default::default()
+    };
+    let dream_record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    let record_ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        options,
+        now,
+    );
+    let confirm_ix =
+        instructions::confirm_coauthorship(&co_dreamer.pubkey(), &dream_mint, &dream_record);
+    send(&mut context, &[record_ix, confirm_ix.clone()], &[&dreamer, &co_dreamer])
+        .await
+        .unwrap();
+
+    let share = DREAM_REWARD_PER_RECORD / 2;
+    for wallet in [dreamer.pubkey(), co_dreamer.pubkey()] {
+        let ata = get_associated_token_address_with_program_id(
+            &wallet,
+            &dream_mint,
+            &spl_token_2022::ID,
+        );
+        assert_eq!(token_balance(&mut context, &ata).await, share);
+        let score: SeasonScore =
+            fetch(&mut context, &pda::find_season_score_address(&wallet).0).await;
+        assert_eq!(score.dream_count, 1);
+    }
+    let coauthors: DreamCoauthors =
+        fetch(&mut context, &pda::find_dream_coauthors_address(&dream_record).0).await;
+    assert_eq!(coauthors.co_dreamers(), &[co_dreamer.pubkey()]);
+    assert!(coauthors.confirmed[0]);
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[confirm_ix], &[&co_dreamer]).await;
+    assert_dream_error(result, DreamError::CoauthorshipAlreadyConfirmed);
+
+    let stranger = Keypair::new();
+    fund(&mut context, &stranger.pubkey()).await;
+    let ix = instructions::confirm_coauthorship(&stranger.pubkey(), &dream_mint, &dream_record);
+    let result = send(&mut context, &[ix], &[&stranger]).await;
+    assert_dream_error(result, DreamError::NotACoDreamer);
+}
+
+#[tokio::test]
+async fn unconfirmed_co_dreamer_shares_decay_and_expire() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+
+    let dreamer = Keypair::new();
+    let late = Keypair::new();
+    let absent = Keypair::new();
+    for wallet in [&dreamer, &late, &absent] {
+        fund(&mut context, &wallet.pubkey()).await;
+    }
+    let now = cluster_time(&mut context).await;
+    let options = RecordDreamOptions {
+        co_dreamers: vec![late.pubkey(), absent.pubkey()],
+        ..Default::default()
+    };
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        options,
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let dream_record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    let coauthors_address = pda::find_dream_coauthors_address(&dream_record).0;
+    let coauthors: DreamCoauthors = fetch(&mut context, &coauthors_address).await;
+    let share = DREAM_REWARD_PER_RECORD / 3;
+
+    // Halfway through the decay window, half the share is left
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp = coauthors.recorded_at
+        + REWARD_CLAIM_GRACE_SECS
+        + (REWARD_CLAIM_EXPIRY_SECS - REWARD_CLAIM_GRACE_SECS) / 2;
+    context.set_sysvar(&clock);
+    let ix = instructions::confirm_coauthorship(&late.pubkey(), &dream_mint, &dream_record);
+    send(&mut context, &[ix], &[&late]).await.unwrap();
+    let late_ata = get_associated_token_address_with_program_id(
+        &late.pubkey(),
+        &dream_mint,
+        &spl_token_2022::ID,
+    );
+    assert_eq!(token_balance(&mut context, &late_ata).await, share / 2);
+
+    let expire_ix = instructions::expire_coauthor_shares(&dream_record, &dreamer.pubkey(), None);
+    let result = send(&mut context, &[expire_ix.clone()], &[]).await;
+    assert_dream_error(result, DreamError::RewardNotExpired);
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp = coauthors.recorded_at + REWARD_CLAIM_EXPIRY_SECS;
+    context.set_sysvar(&clock);
+    let ix = instructions::confirm_coauthorship(&absent.pubkey(), &dream_mint, &dream_record);
+    let result = send(&mut context, &[ix], &[&absent]).await;
+    assert_dream_error(result, DreamError::RewardExpired);
+
+    send(&mut context, &[expire_ix], &[]).await.unwrap();
+    let account = context.banks_client.get_account(coauthors_address).await.unwrap();
+    assert!(account.is_none());
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert_eq!(treasury.total_rewards_expired, (share - share / 2) + share);
+}
+
+#[tokio::test]
+async fn record_dreams_batch_records_several_nights_at_once() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let dreams: Vec<BatchedDream> = (1..=MAX_DREAMS_PER_BATCH as u8 + 1)
+        .map(|night| BatchedDream {
+            content_hash: [night; 32],
+            metadata: DreamMetadata { lucidity_score: night, ..Default::default() },
+        })
+        .collect();
+    let batch = |dreams: &[BatchedDream]| {
+        instructions::record_dreams_batch(&dreamer.pubkey(), &dream_mint, 0, dreams.to_vec(), now)
+    };
+    let result = send(&mut context, &[batch(&dreams)], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidDreamBatch);
+
+    let dreams = &dreams[..MAX_DREAMS_PER_BATCH];
+    send(&mut context, &[batch(dreams)], &[&dreamer]).await.unwrap();
+    for (id, dream) in dreams.iter().enumerate() {
+        let record: DreamRecord =
+            fetch(&mut context, &pda::find_dream_record_address(&dreamer.pubkey(), id as u64).0)
+                .await;
+        assert_eq!(record.id, id as u64);
+        assert_eq!(record.dreamer, dreamer.pubkey());
+        assert_eq!(record.content_hash, dream.content_hash);
+        assert_eq!(record.metadata, dream.metadata);
+    }
+
+    let count = MAX_DREAMS_PER_BATCH as u64;
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert_eq!(treasury.total_dreams_recorded, count);
+    let season_score: SeasonScore =
+        fetch(&mut context, &pda::find_season_score_address(&dreamer.pubkey()).0).await;
+    assert_eq!(season_score.dream_count, count);
+    let dreamer_ata =
+        get_associated_token_address_with_program_id(&dreamer.pubkey(), &dream_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &dreamer_ata).await, count * DREAM_REWARD_PER_RECORD);
+}
+
+#[tokio::test]
+async fn record_dream_rejects_out_of_range_metadata() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    let metadata = DreamMetadata {
+        lucidity_score: MAX_LUCIDITY_SCORE + 1,
+        ..DreamMetadata::default()
+    };
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        metadata,
+        RecordDreamOptions::default(),
+        now,
+    );
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidDreamMetadata);
+}
+
+#[tokio::test]
+async fn sleep_session_caps_rewarded_dreams() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::open_session(&dreamer.pubkey(), [3u8; 32], now);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    // Every dream of the day counts, with or without an explicit session
+    let dream_count = u64::from(MAX_REWARDED_DREAMS_PER_SESSION) + 1;
+    for dream_id in 0..dream_count {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id,
+            [7u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    }
+
+    // Batching doesn't get around the cap
+    let dreams = vec![BatchedDream { content_hash: [8u8; 32], metadata: DreamMetadata::default() }];
+    let ix =
+        instructions::record_dreams_batch(&dreamer.pubkey(), &dream_mint, dream_count, dreams, now);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let day = EpochStats::day_of(now);
+    let ix = instructions::close_session(&dreamer.pubkey(), day);
+    send(&mut context, &[ix.clone()], &[&dreamer]).await.unwrap();
+
+    let session_address = pda::find_sleep_session_address(&dreamer.pubkey(), day).0;
+    let session: SleepSession = fetch(&mut context, &session_address).await;
+    assert_eq!(session.session_id, u64::from(day));
+    assert_eq!(session.dream_count as u64, dream_count + 1);
+    assert_eq!(session.rewards_earned, (dream_count - 1) * DREAM_REWARD_PER_RECORD);
+    assert_eq!(session.device_attestation, [3u8; 32]);
+    assert!(session.ended_at >= session.started_at && session.ended_at > 0);
+
+    for dream_id in [dream_count - 1, dream_count] {
+        let address = pda::find_dream_record_address(&dreamer.pubkey(), dream_id).0;
+        let record: DreamRecord = fetch(&mut context, &address).await;
+        assert_eq!(record.session, session_address);
+        assert_eq!(record.token_reward, 0);
+    }
+    let dreamer_ata =
+        get_associated_token_address_with_program_id(&dreamer.pubkey(), &dream_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &dreamer_ata).await, session.rewards_earned);
+
+    // Today's session stays open to dreams; a past day's is closed for its rent
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix.clone()], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::SessionClosed);
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += EPOCH_STATS_PERIOD_SECS;
+    context.set_sysvar(&clock);
+    let session_rent = context.banks_client.get_balance(session_address).await.unwrap();
+    let before = context.banks_client.get_balance(dreamer.pubkey()).await.unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    assert!(context.banks_client.get_account(session_address).await.unwrap().is_none());
+    let after = context.banks_client.get_balance(dreamer.pubkey()).await.unwrap();
+    assert_eq!(after, before + session_rent);
+}
+
+#[tokio::test]
+async fn record_dream_updates_daily_epoch_stats() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let now = cluster_time(&mut context).await;
+
+    let first = Keypair::new();
+    let second = Keypair::new();
+    fund(&mut context, &first.pubkey()).await;
+    fund(&mut context, &second.pubkey()).await;
+    for (dream_id, dreamer) in [&first, &first, &second].into_iter().enumerate() {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id as u64,
+            [7u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[dreamer]).await.unwrap();
+    }
+
+    let day = EpochStats::day_of(now);
+    let stats: EpochStats = fetch(&mut context, &pda::find_epoch_stats_address(day).0).await;
+    assert_eq!(stats.day, day);
+    assert_eq!(stats.dream_count, 3);
+    assert_eq!(stats.unique_dreamers, 2);
+    assert_eq!(stats.rewards_minted, 3 * DREAM_REWARD_PER_RECORD);
+}
+
+#[tokio::test]
+async fn leaderboard_ranks_season_scores() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    send(&mut context, &[instructions::initialize_leaderboard(&authority)], &[]).await.unwrap();
+    let now = cluster_time(&mut context).await;
+
+    let first = Keypair::new();
+    let second = Keypair::new();
+    fund(&mut context, &first.pubkey()).await;
+    fund(&mut context, &second.pubkey()).await;
+    for (dream_id, dreamer) in [&first, &second, &second].into_iter().enumerate() {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id as u64,
+            [7u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[dreamer]).await.unwrap();
+    }
+    let cranks = [
+        instructions::update_leaderboard(&first.pubkey(), None),
+        instructions::update_leaderboard(&second.pubkey(), None),
+    ];
+    send(&mut context, &cranks, &[]).await.unwrap();
+
+    let leaderboard_address = pda::find_leaderboard_address().0;
+    let leaderboard: Leaderboard = fetch(&mut context, &leaderboard_address).await;
+    let ranking: Vec<_> = leaderboard.entries().iter().map(|e| (e.dreamer, e.dream_count)).collect();
+    assert_eq!(ranking, vec![(second.pubkey(), 2), (first.pubkey(), 1)]);
+
+    send(&mut context, &[instructions::start_season(&authority)], &[]).await.unwrap();
+    let leaderboard: Leaderboard = fetch(&mut context, &leaderboard_address).await;
+    assert_eq!(leaderboard.season, 1);
+    assert!(leaderboard.entries().is_empty());
+
+    let ix = instructions::update_leaderboard(&first.pubkey(), None);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::SeasonMismatch);
+}
+
+#[tokio::test]
+async fn leaderboard_crank_bounty_needs_progress_and_cooldown() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let bounty = 5_000;
+    let setup = [
+        instructions::initialize_leaderboard(&authority),
+        instructions::set_crank_bounty(&authority, bounty, 60),
+        instructions::fund_crank_pool(&authority, 100 * bounty),
+    ];
+    send(&mut context, &setup, &[]).await.unwrap();
+    let now = cluster_time(&mut context).await;
+
+    let first = Keypair::new();
+    let second = Keypair::new();
+    let cranker = Keypair::new();
+    for (dream_id, dreamer) in [&first, &second].into_iter().enumerate() {
+        fund(&mut context, &dreamer.pubkey()).await;
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id as u64,
+            [7u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[dreamer]).await.unwrap();
+    }
+    fund(&mut context, &cranker.pubkey()).await;
+    let start = context.banks_client.get_balance(cranker.pubkey()).await.unwrap();
+
+    // The second crank moves the board too, but the kind is cooling down
+    for dreamer in [&first, &second] {
+        let ix = instructions::update_leaderboard(&dreamer.pubkey(), Some(&cranker.pubkey()));
+        send(&mut context, &[ix], &[&cranker]).await.unwrap();
+    }
+    let balance = context.banks_client.get_balance(cranker.pubkey()).await.unwrap();
+    assert_eq!(balance, start + bounty);
+
+    // After the cooldown, a crank that changes nothing still earns nothing
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += 60;
+    context.set_sysvar(&clock);
+    let ix = instructions::update_leaderboard(&first.pubkey(), Some(&cranker.pubkey()));
+    send(&mut context, &[ix], &[&cranker]).await.unwrap();
+    let balance = context.banks_client.get_balance(cranker.pubkey()).await.unwrap();
+    assert_eq!(balance, start + bounty);
+
+    let pool: CrankPool = fetch(&mut context, &pda::find_crank_pool_address().0).await;
+    assert_eq!(pool.total_paid, bounty);
+}
+
+#[tokio::test]
+async fn stats_ring_snapshots_treasury_totals_daily() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    send(&mut context, &[instructions::initialize_stats_ring(&authority)], &[])
+        .await
+        .unwrap();
+    send(&mut context, &[instructions::roll_stats()], &[]).await.unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[instructions::roll_stats()], &[]).await;
+    assert_dream_error(result, DreamError::StatsAlreadyRolled);
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    // Three days later; the two missed days repeat the new snapshot
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += 3 * EPOCH_STATS_PERIOD_SECS;
+    context.set_sysvar(&clock);
+    send(&mut context, &[instructions::roll_stats()], &[]).await.unwrap();
+
+    let ring: StatsRing = fetch(&mut context, &pda::find_stats_ring_address().0).await;
+    assert_eq!(ring.len, 4);
+    assert_eq!(ring.last_day, EpochStats::day_of(clock.unix_timestamp));
+    for days_ago in 0..3 {
+        let snapshot = ring.get(days_ago).unwrap();
+        assert_eq!(snapshot.dreams_recorded, 1);
+        assert_eq!(snapshot.rewards_distributed, DREAM_REWARD_PER_RECORD);
+    }
+    assert_eq!(ring.get(3).unwrap().dreams_recorded, 0);
+    assert!(ring.get(4).is_none());
+}
+
+#[tokio::test]
+async fn idempotency_key_blocks_a_retried_record_until_it_expires() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let record = |dream_id| {
+        let options = RecordDreamOptions {
+            idempotency_key: Some(42),
+            ..Default::default()
+        };
+        instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id,
+            [7u8; 32],
+            DreamMetadata::default(),
+            options,
+            now,
+        )
+    };
+    send(&mut context, &[record(0)], &[&dreamer]).await.unwrap();
+
+    // The retry reads the treasury again and so gets a fresh dream id
+    assert!(send(&mut context, &[record(1)], &[&dreamer]).await.is_err());
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert_eq!(treasury.total_dreams_recorded, 1);
+    let nonce_address = pda::find_idempotency_nonce_address(&dreamer.pubkey(), 42).0;
+    let nonce: IdempotencyNonce = fetch(&mut context, &nonce_address).await;
+    assert_eq!(nonce.dream_record, pda::find_dream_record_address(&dreamer.pubkey(), 0).0);
+    assert_eq!(nonce.payer, dreamer.pubkey());
+
+    let close = instructions::close_idempotency_nonce(&dreamer.pubkey(), &dreamer.pubkey(), 42);
+    let result = send(&mut context, &[close.clone()], &[]).await;
+    assert_dream_error(result, DreamError::IdempotencyNonceLive);
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += IDEMPOTENCY_NONCE_TTL_SECS;
+    context.set_sysvar(&clock);
+    send(&mut context, &[close], &[]).await.unwrap();
+    assert!(context.banks_client.get_account(nonce_address).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn sponsor_pool_pays_a_new_dreamers_first_dream_once() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let payer = context.payer.pubkey();
+    let setup = [
+        instructions::set_sponsor_pool(&payer, true),
+        instructions::fund_sponsor_pool(&payer, 1_000_000_000),
+    ];
+    send(&mut context, &setup, &[]).await.unwrap();
+    let now = cluster_time(&mut context).await;
+
+    // The dreamer holds no SOL; the payer only pays the fee
+    let dreamer = Keypair::new();
+    let record = |dream_id| {
+        instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id,
+            [7u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        )
+    };
+    let claim = instructions::claim_sponsorship(&payer, &dreamer.pubkey(), &dream_mint, now);
+    assert!(send(&mut context, &[record(0)], &[&dreamer]).await.is_err());
+    let result = send(&mut context, &[claim.clone()], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::SponsoredDreamMissing);
+
+    send(&mut context, &[claim.clone(), record(0)], &[&dreamer]).await.unwrap();
+    let dream: DreamRecord =
+        fetch(&mut context, &pda::find_dream_record_address(&dreamer.pubkey(), 0).0).await;
+    assert_eq!(dream.dreamer, dreamer.pubkey());
+    // Every sponsored lamport went into the dream's accounts
+    assert_eq!(context.banks_client.get_balance(dreamer.pubkey()).await.unwrap(), 0);
+    let sponsorship: Sponsorship =
+        fetch(&mut context, &pda::find_sponsorship_address(&dreamer.pubkey()).0).await;
+    let pool: SponsorPool = fetch(&mut context, &pda::find_sponsor_pool_address().0).await;
+    assert_eq!(pool.dreamers_sponsored, 1);
+    assert_eq!(pool.total_sponsored, sponsorship.lamports);
+
+    // The wallet is sponsored once; its second dream is its own
+    context.get_new_latest_blockhash().await.unwrap();
+    assert!(send(&mut context, &[claim, record(1)], &[&dreamer]).await.is_err());
+}
+
+#[tokio::test]
+async fn relayer_pays_rent_for_a_dreamer_without_sol() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let relayer = context.payer.pubkey();
+    let now = cluster_time(&mut context).await;
+
+    let dreamer = Keypair::new();
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    assert!(send(&mut context, &[ix.clone()], &[&dreamer]).await.is_err());
+    let ix = instructions::with_rent_payer(ix, &relayer);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let dream: DreamRecord =
+        fetch(&mut context, &pda::find_dream_record_address(&dreamer.pubkey(), 0).0).await;
+    assert_eq!(dream.dreamer, dreamer.pubkey());
+    assert_eq!(context.banks_client.get_balance(dreamer.pubkey()).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn validation_queue_pops_staked_dreams_first() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+    let lucid_mint = create_mint(&mut context, &authority).await;
+    let dream_mint = initialize_with_lucid_mint(&mut context, &lucid_mint).await;
+    let ix = instructions::initialize_validation_queue(&authority);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let unstaked = Keypair::new();
+    let staked = Keypair::new();
+    fund(&mut context, &unstaked.pubkey()).await;
+    fund(&mut context, &staked.pubkey()).await;
+    let staked_lucid = create_ata(&mut context, &staked.pubkey(), &lucid_mint).await;
+    let vault = create_ata(&mut context, &Keypair::new().pubkey(), &lucid_mint).await;
+    let amount = 50 * TOKEN_UNIT;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &lucid_mint,
+        &staked_lucid,
+        &authority,
+        &[],
+        amount,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+    let ix =
+        instructions::stake_for_lucid_access(&staked.pubkey(), &lucid_mint, &staked_lucid, &vault, amount);
+    send(&mut context, &[ix], &[&staked]).await.unwrap();
+
+    // The unstaked dream is queued first but the stake outweighs its head start
+    let now = cluster_time(&mut context).await;
+    let dreamers = [(&unstaked, false), (&staked, true)];
+    for (dream_id, (dreamer, has_stake)) in dreamers.into_iter().enumerate() {
+        let options = RecordDreamOptions {
+            enqueue: true,
+            lucid_mint: has_stake.then_some(lucid_mint),
+            ..Default::default()
+        };
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id as u64,
+            [7u8; 32],
+            DreamMetadata::default(),
+            options,
+            now,
+        );
+        send(&mut context, &[ix], &[dreamer]).await.unwrap();
+    }
+    let staked_record = pda::find_dream_record_address(&staked.pubkey(), 1).0;
+    let unstaked_record = pda::find_dream_record_address(&unstaked.pubkey(), 0).0;
+
+    let popped: Pubkey = simulate_view(&mut context, instructions::pop_validation(&authority)).await;
+    assert_eq!(popped, staked_record);
+    send(&mut context, &[instructions::pop_validation(&authority)], &[]).await.unwrap();
+
+    let queue_address = pda::find_validation_queue_address().0;
+    let queue: ValidationQueue = fetch(&mut context, &queue_address).await;
+    assert_eq!(queue.total_enqueued, 2);
+    let remaining: Vec<_> = queue.iter().map(|e| (e.dream_record, e.stake)).collect();
+    assert_eq!(remaining, vec![(unstaked_record, 0)]);
+
+    let ixs = [
+        instructions::pop_validation(&authority),
+        instructions::pop_validation(&authority),
+    ];
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::ValidationQueueEmpty);
+}
+
+/// `oracle`'s ed25519 attestation that `record` is pinned, then `confirm_pinned`.
+fn pin_instructions(
+    payer: &Pubkey,
+    oracle: &Keypair,
+    record: &Pubkey,
+    content_hash: &[u8; 32],
+) -> [Instruction; 2] {
+    let message = cid::pin_attestation_message(record, content_hash);
+    let signature: [u8; 64] = oracle.sign_message(&message).as_ref().try_into().unwrap();
+    [
+        instructions::ed25519_verify(&oracle.pubkey(), &signature, &message),
+        instructions::confirm_pinned(payer, &oracle.pubkey(), record),
+    ]
+}
+
+#[tokio::test]
+async fn approved_oracle_confirms_dream_pinned() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+
+    let oracle = Keypair::new();
+    let ix = instructions::set_pinning_oracle(&authority, &oracle.pubkey(), false);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let ixs = pin_instructions(&authority, &oracle, &record, &[7u8; 32]);
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::PinningOracleNotApproved);
+
+    let ix = instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    // Signed over a different hash than the record's
+    let ixs = pin_instructions(&authority, &oracle, &record, &[8u8; 32]);
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::InvalidPinAttestation);
+    // No signature verification before it
+    let ix = instructions::confirm_pinned(&authority, &oracle.pubkey(), &record);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidPinAttestation);
+
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &pin_instructions(&authority, &oracle, &record, &[7u8; 32]), &[])
+        .await
+        .unwrap();
+    let confirmation: PinConfirmation =
+        fetch(&mut context, &pda::find_pin_confirmation_address(&record).0).await;
+    assert_eq!((confirmation.dream_record, confirmation.oracle), (record, oracle.pubkey()));
+    let pinning_oracle: PinningOracle =
+        fetch(&mut context, &pda::find_pinning_oracle_address(&oracle.pubkey()).0).await;
+    assert_eq!(pinning_oracle.attestations, 1);
+}
+
+/// Every one of `signers`' ed25519 attestations that `record` is pinned in one
+/// instruction, then `confirm_pinned` led by `lead` with `co_signers`.
+fn quorum_pin_instructions(
+    payer: &Pubkey,
+    signers: &[&Keypair],
+    lead: &Pubkey,
+    co_signers: &[Pubkey],
+    record: &Pubkey,
+) -> [Instruction; 2] {
+    let message = cid::pin_attestation_message(record, &[7u8; 32]);
+    let signatures: Vec<(Pubkey, [u8; 64])> = signers
+        .iter()
+        .map(|oracle| {
+            let signature: [u8; 64] = oracle.sign_message(&message).as_ref().try_into().unwrap();
+            (oracle.pubkey(), signature)
+        })
+        .collect();
+    [
+        instructions::ed25519_verify_quorum(&signatures, &message),
+        instructions::with_oracle_co_signers(
+            instructions::confirm_pinned(payer, lead, record),
+            co_signers,
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn oracle_quorum_requires_enough_approved_co_signers() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+
+    let oracles = [Keypair::new(), Keypair::new(), Keypair::new(), Keypair::new()];
+    for (index, oracle) in oracles.iter().enumerate() {
+        // The last oracle is registered but not approved
+        let ix = instructions::set_pinning_oracle(&authority, &oracle.pubkey(), index < 3);
+        send(&mut context, &[ix], &[]).await.unwrap();
+    }
+    let [a, b, c, unapproved] = &oracles;
+    let ix = instructions::set_oracle_quorum(&authority, MAX_ORACLE_QUORUM + 1);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidOracleQuorum);
+    let ix = instructions::set_oracle_quorum(&authority, 3);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    // A lone oracle no longer suffices
+    let ixs = pin_instructions(&authority, a, &record, &[7u8; 32]);
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::OracleQuorumNotMet);
+    // A co-signer whose signature isn't in the verification
+    let ixs = quorum_pin_instructions(
+        &authority,
+        &[a, b],
+        &a.pubkey(),
+        &[b.pubkey(), c.pubkey()],
+        &record,
+    );
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::InvalidPinAttestation);
+    // The lead counted twice
+    let ixs = quorum_pin_instructions(
+        &authority,
+        &[a, b],
+        &a.pubkey(),
+        &[b.pubkey(), a.pubkey()],
+        &record,
+    );
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::InvalidPinAttestation);
+    let ixs = quorum_pin_instructions(
+        &authority,
+        &[a, b, unapproved],
+        &a.pubkey(),
+        &[b.pubkey(), unapproved.pubkey()],
+        &record,
+    );
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::PinningOracleNotApproved);
+
+    let ixs = quorum_pin_instructions(
+        &authority,
+        &[c, a, b],
+        &a.pubkey(),
+        &[b.pubkey(), c.pubkey()],
+        &record,
+    );
+    send(&mut context, &ixs, &[]).await.unwrap();
+    let confirmation: PinConfirmation =
+        fetch(&mut context, &pda::find_pin_confirmation_address(&record).0).await;
+    assert_eq!(confirmation.oracle, a.pubkey());
+    for oracle in [a, b, c] {
+        let address = pda::find_pinning_oracle_address(&oracle.pubkey()).0;
+        let pinning_oracle: PinningOracle = fetch(&mut context, &address).await;
+        assert_eq!(pinning_oracle.attestations, 1);
+    }
+}
+
+#[tokio::test]
+async fn optimistic_scores_finalize_unless_a_fraud_proof_voids_them() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let oracle = Keypair::new();
+    fund(&mut context, &oracle.pubkey()).await;
+    let ix = instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    // Dreams 0 and 1 share their content; all three are pinned and scored
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let hashes = [[7u8; 32], [7u8; 32], [9u8; 32]];
+    let mut records = Vec::new();
+    for (id, hash) in hashes.iter().enumerate() {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            id as u64,
+            *hash,
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+        let record = pda::find_dream_record_address(&dreamer.pubkey(), id as u64).0;
+        send(&mut context, &pin_instructions(&authority, &oracle, &record, hash), &[])
+            .await
+            .unwrap();
+        let ix = instructions::post_optimistic_score(&oracle.pubkey(), &record, 80);
+        send(&mut context, &[ix], &[&oracle]).await.unwrap();
+        records.push(record);
+    }
+    let score: OptimisticScore =
+        fetch(&mut context, &pda::find_optimistic_score_address(&records[0]).0).await;
+    assert_eq!((score.oracle, score.score), (oracle.pubkey(), 80));
+    assert_eq!(score.bond, OPTIMISTIC_SCORE_BOND);
+
+    // Only the later of two identical dreams is a duplicate
+    let challenger = Keypair::new();
+    fund(&mut context, &challenger.pubkey()).await;
+    let duplicate = |record, original| {
+        instructions::challenge_duplicate_content(&challenger.pubkey(), record, original)
+    };
+    let result = send(&mut context, &[duplicate(&records[0], &records[1])], &[&challenger]).await;
+    assert_dream_error(result, DreamError::InvalidFraudProof);
+    send(&mut context, &[duplicate(&records[1], &records[0])], &[&challenger]).await.unwrap();
+    let balance = context.banks_client.get_balance(challenger.pubkey()).await.unwrap();
+    assert_eq!(balance, 1_000_000_000 + OPTIMISTIC_SCORE_BOND);
+
+    let message = cid::dead_cid_attestation_message(&records[2], &hashes[2]);
+    let signature: [u8; 64] = oracle.sign_message(&message).as_ref().try_into().unwrap();
+    let ixs = [
+        instructions::ed25519_verify(&oracle.pubkey(), &signature, &message),
+        instructions::challenge_dead_cid(&challenger.pubkey(), &records[2], &oracle.pubkey()),
+    ];
+    send(&mut context, &ixs, &[&challenger]).await.unwrap();
+    let score: OptimisticScore =
+        fetch(&mut context, &pda::find_optimistic_score_address(&records[2]).0).await;
+    assert!(score.voided);
+
+    let finalize = |record| {
+        let oracle = oracle.pubkey();
+        let dreamer = dreamer.pubkey();
+        instructions::finalize_optimistic_score(
+            &authority,
+            &oracle,
+            &dreamer,
+            &dream_mint,
+            record,
+            None,
+        )
+    };
+    let result = send(&mut context, &[finalize(&records[0])], &[]).await;
+    assert_dream_error(result, DreamError::ChallengeWindowOpen);
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += OPTIMISTIC_CHALLENGE_WINDOW_SECS;
+    context.set_sysvar(&clock);
+    let result = send(&mut context, &[finalize(&records[1])], &[]).await;
+    assert_dream_error(result, DreamError::OptimisticScoreVoided);
+    send(&mut context, &[finalize(&records[0])], &[]).await.unwrap();
+
+    let validation: DreamValidation =
+        fetch(&mut context, &pda::find_dream_validation_address(&records[0]).0).await;
+    assert_eq!((validation.validator, validation.score), (oracle.pubkey(), 80));
+    let closed = pda::find_optimistic_score_address(&records[0]).0;
+    assert!(context.banks_client.get_account(closed).await.unwrap().is_none());
+    let profile: DreamerProfile =
+        fetch(&mut context, &pda::find_dreamer_profile_address(&dreamer.pubkey()).0).await;
+    assert_eq!(profile.validated_dreams, 1);
+}
+
+/// `oracle`'s ed25519 classification of `record` at `revision`, then `classify_dream`.
+fn classify_instructions(
+    oracle: &Keypair,
+    record: &Pubkey,
+    content_hash: &[u8; 32],
+    (language, nsfw, topics): ([u8; 2], bool, u64),
+    revision: u32,
+) -> [Instruction; 2] {
+    let message = cid::classification_attestation_message(
+        record,
+        content_hash,
+        &language,
+        nsfw,
+        topics,
+        revision,
+    );
+    let signature: [u8; 64] = oracle.sign_message(&message).as_ref().try_into().unwrap();
+    [
+        instructions::ed25519_verify(&oracle.pubkey(), &signature, &message),
+        instructions::classify_dream(
+            &oracle.pubkey(),
+            &oracle.pubkey(),
+            record,
+            language,
+            nsfw,
+            topics,
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn approved_oracles_classify_dreams_by_revision() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+
+    let oracle = Keypair::new();
+    fund(&mut context, &oracle.pubkey()).await;
+    let ix = instructions::set_pinning_oracle(&authority, &oracle.pubkey(), false);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let english = (*b"en", false, 0b101);
+    let ixs = classify_instructions(&oracle, &record, &[7u8; 32], english, 1);
+    let result = send(&mut context, &ixs, &[&oracle]).await;
+    assert_dream_error(result, DreamError::PinningOracleNotApproved);
+
+    let ix = instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let ixs = classify_instructions(&oracle, &record, &[7u8; 32], (*b"EN", false, 0), 1);
+    let result = send(&mut context, &ixs, &[&oracle]).await;
+    assert_dream_error(result, DreamError::InvalidDreamLanguage);
+    // Signed for a revision other than the next one
+    let ixs = classify_instructions(&oracle, &record, &[7u8; 32], english, 2);
+    let result = send(&mut context, &ixs, &[&oracle]).await;
+    assert_dream_error(result, DreamError::InvalidClassificationAttestation);
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let first = classify_instructions(&oracle, &record, &[7u8; 32], english, 1);
+    send(&mut context, &first, &[&oracle]).await.unwrap();
+    let address = pda::find_dream_classification_address(&record).0;
+    let classification: DreamClassification = fetch(&mut context, &address).await;
+    assert_eq!((classification.dream_record, classification.oracle), (record, oracle.pubkey()));
+    assert_eq!((classification.language, classification.nsfw), (*b"en", false));
+    assert_eq!((classification.topics, classification.revision), (0b101, 1));
+
+    let ixs = classify_instructions(&oracle, &record, &[7u8; 32], (*b"de", true, 0b10), 2);
+    send(&mut context, &ixs, &[&oracle]).await.unwrap();
+    let classification: DreamClassification = fetch(&mut context, &address).await;
+    assert_eq!((classification.language, classification.nsfw), (*b"de", true));
+    assert_eq!(classification.revision, 2);
+
+    // The first attestation can't be replayed over the newer one
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &first, &[&oracle]).await;
+    assert_dream_error(result, DreamError::InvalidClassificationAttestation);
+}
+
+#[tokio::test]
+async fn authority_validates_each_dream_once() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+
+    // Not confirmed pinned yet
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &record,
+        None,
+        60,
+    );
+    assert!(send(&mut context, &[ix], &[]).await.is_err());
+    let oracle = Keypair::new();
+    send(&mut context, &[instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true)], &[])
+        .await
+        .unwrap();
+    send(&mut context, &pin_instructions(&authority, &oracle, &record, &[7u8; 32]), &[])
+        .await
+        .unwrap();
+
+    let score = MAX_VALIDATION_SCORE + 1;
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &record,
+        None,
+        score,
+    );
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidValidationScore);
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &record,
+        None,
+        80,
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let validation: DreamValidation =
+        fetch(&mut context, &pda::find_dream_validation_address(&record).0).await;
+    assert_eq!((validation.dream_record, validation.score), (record, 80));
+    // Scores above neutral raise the dreamer's reputation
+    let profile: DreamerProfile =
+        fetch(&mut context, &pda::find_dreamer_profile_address(&dreamer.pubkey()).0).await;
+    assert_eq!(profile.reputation, DreamerProfile::validation_change(80));
+    assert!(profile.reputation > 0);
+    assert_eq!(profile.validated_dreams, 1);
+    // The first validated dream pays its reward again as an onboarding bonus
+    let dreamer_ata = get_associated_token_address_with_program_id(
+        &dreamer.pubkey(),
+        &dream_mint,
+        &spl_token_2022::ID,
+    );
+    assert_eq!(token_balance(&mut context, &dreamer_ata).await, 2 * DREAM_REWARD_PER_RECORD);
+
+    // The validation PDA already exists
+    context.get_new_latest_blockhash().await.unwrap();
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &record,
+        None,
+        50,
+    );
+    let result = send(&mut context, &[ix], &[]).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn interpreter_splits_validated_rewards_with_the_dreamer() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let dreamer = Keypair::new();
+    let interpreter = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    fund(&mut context, &interpreter.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let record_with = |interpreter: &Keypair| {
+        instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            0,
+            [7u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions {
+                interpreter: Some(interpreter.pubkey()),
+                ..Default::default()
+            },
+            now,
+        )
+    };
+    let result = send(&mut context, &[record_with(&dreamer)], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidInterpreter);
+    send(&mut context, &[record_with(&interpreter)], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+
+    // The interpreter's share of the reward is held back in the pool
+    let reward = DREAM_REWARD_PER_RECORD;
+    let held = DreamInterpretation::interpreter_share(reward);
+    let dreamer_ata = get_associated_token_address_with_program_id(
+        &dreamer.pubkey(),
+        &dream_mint,
+        &spl_token_2022::ID,
+    );
+    let recorded = token_balance(&mut context, &dreamer_ata).await;
+    assert_eq!(recorded + held, reward);
+    let health: TreasuryHealth = fetch(&mut context, &pda::find_treasury_health_address().0).await;
+    assert_eq!(health.pending_coauthor_shares, held);
+    let oracle = Keypair::new();
+    send(&mut context, &[instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true)], &[])
+        .await
+        .unwrap();
+    send(&mut context, &pin_instructions(&authority, &oracle, &record, &[7u8; 32]), &[])
+        .await
+        .unwrap();
+
+    let attach = |cid: &str| {
+        let cid = cid.to_string();
+        instructions::attach_interpretation(&interpreter.pubkey(), &dream_mint, &record, cid)
+    };
+    // Only the dreamer renames the interpreter, never to themselves, and only
+    // the named interpreter can attach
+    let ix =
+        instructions::assign_interpreter(&interpreter.pubkey(), &record, &interpreter.pubkey());
+    let result = send(&mut context, &[ix], &[&interpreter]).await;
+    assert!(result.is_err());
+    let ix = instructions::assign_interpreter(&dreamer.pubkey(), &record, &dreamer.pubkey());
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidInterpreter);
+    let stranger = Keypair::new();
+    fund(&mut context, &stranger.pubkey()).await;
+    let ix = instructions::attach_interpretation(
+        &stranger.pubkey(),
+        &dream_mint,
+        &record,
+        "bafyanalysis".to_string(),
+    );
+    let result = send(&mut context, &[ix], &[&stranger]).await;
+    assert!(result.is_err());
+    let result = send(&mut context, &[attach("")], &[&interpreter]).await;
+    assert_dream_error(result, DreamError::InvalidAnalysisCid);
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[attach("bafyanalysis")], &[&interpreter]).await.unwrap();
+
+    // The attached analysis locks the interpreter in
+    let ix = instructions::assign_interpreter(&dreamer.pubkey(), &record, &stranger.pubkey());
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InterpretationLocked);
+
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &record,
+        None,
+        80,
+    );
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::MissingInterpreterAccount);
+    let ix = instructions::validate_interpreted_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &record,
+        None,
+        80,
+        &interpreter.pubkey(),
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    // The first validation pays the reward again as a bonus; dreamer and
+    // interpreter end up with 80% and 20% of reward and bonus together
+    let from_bonus = DreamInterpretation::interpreter_share(reward);
+    let interpreter_ata = get_associated_token_address_with_program_id(
+        &interpreter.pubkey(),
+        &dream_mint,
+        &spl_token_2022::ID,
+    );
+    let dreamer_total = token_balance(&mut context, &dreamer_ata).await;
+    let interpreter_total = token_balance(&mut context, &interpreter_ata).await;
+    assert_eq!(dreamer_total, recorded + reward - from_bonus);
+    assert_eq!(interpreter_total, held + from_bonus);
+    assert_eq!(dreamer_total + interpreter_total, 2 * reward);
+    assert_eq!(interpreter_total * 4, dreamer_total);
+    let health: TreasuryHealth = fetch(&mut context, &pda::find_treasury_health_address().0).await;
+    assert_eq!(health.pending_coauthor_shares, 0);
+    let interpretation: DreamInterpretation =
+        fetch(&mut context, &pda::find_dream_interpretation_address(&record).0).await;
+    assert_eq!(interpretation.reward_paid, interpreter_total);
+
+    // Nothing changes after validation
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[attach("bafyrevised")], &[&interpreter]).await;
+    assert_dream_error(result, DreamError::DreamAlreadyValidated);
+}
+
+#[tokio::test]
+async fn optimistic_finalization_pays_the_held_interpreter_share() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let dreamer = Keypair::new();
+    let interpreter = Keypair::new();
+    let oracle = Keypair::new();
+    for wallet in [&dreamer, &interpreter, &oracle] {
+        fund(&mut context, &wallet.pubkey()).await;
+    }
+    let ix = instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions {
+            interpreter: Some(interpreter.pubkey()),
+            ..Default::default()
+        },
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    send(&mut context, &pin_instructions(&authority, &oracle, &record, &[7u8; 32]), &[])
+        .await
+        .unwrap();
+    let ix = instructions::attach_interpretation(
+        &interpreter.pubkey(),
+        &dream_mint,
+        &record,
+        "bafyanalysis".to_string(),
+    );
+    send(&mut context, &[ix], &[&interpreter]).await.unwrap();
+    let ix = instructions::post_optimistic_score(&oracle.pubkey(), &record, 80);
+    send(&mut context, &[ix], &[&oracle]).await.unwrap();
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += OPTIMISTIC_CHALLENGE_WINDOW_SECS;
+    context.set_sysvar(&clock);
+    let finalize = |interpreter: Option<&Pubkey>| {
+        let dreamer = dreamer.pubkey();
+        let oracle = oracle.pubkey();
+        instructions::finalize_optimistic_score(
+            &authority,
+            &oracle,
+            &dreamer,
+            &dream_mint,
+            &record,
+            interpreter,
+        )
+    };
+    let result = send(&mut context, &[finalize(None)], &[]).await;
+    assert_dream_error(result, DreamError::MissingInterpreterAccount);
+    send(&mut context, &[finalize(Some(&interpreter.pubkey()))], &[]).await.unwrap();
+
+    let held = DreamInterpretation::interpreter_share(DREAM_REWARD_PER_RECORD);
+    let interpreter_ata = get_associated_token_address_with_program_id(
+        &interpreter.pubkey(),
+        &dream_mint,
+        &spl_token_2022::ID,
+    );
+    assert_eq!(token_balance(&mut context, &interpreter_ata).await, held);
+    let interpretation: DreamInterpretation =
+        fetch(&mut context, &pda::find_dream_interpretation_address(&record).0).await;
+    assert_eq!(interpretation.reward_paid, held);
+    let health: TreasuryHealth = fetch(&mut context, &pda::find_treasury_health_address().0).await;
+    assert_eq!(health.pending_coauthor_shares, 0);
+}
+
+#[tokio::test]
+async fn unvalidated_interpretations_expire_back_to_the_dreamer() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let dreamer = Keypair::new();
+    let interpreter = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let mut records = Vec::new();
+    for id in 0..2 {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            id,
+            [id as u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions {
+                interpreter: Some(interpreter.pubkey()),
+                ..Default::default()
+            },
+            now,
+        );
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+        records.push(pda::find_dream_record_address(&dreamer.pubkey(), id).0);
+    }
+    let held = DreamInterpretation::interpreter_share(DREAM_REWARD_PER_RECORD);
+    let health: TreasuryHealth = fetch(&mut context, &pda::find_treasury_health_address().0).await;
+    assert_eq!(health.pending_coauthor_shares, 2 * held);
+
+    // Dream 1 is validated without an analysis, which already returns its share
+    let oracle = Keypair::new();
+    send(&mut context, &[instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true)], &[])
+        .await
+        .unwrap();
+    send(&mut context, &pin_instructions(&authority, &oracle, &records[1], &[1u8; 32]), &[])
+        .await
+        .unwrap();
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &records[1],
+        None,
+        80,
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let expire =
+        |record| instructions::expire_interpretation(&dreamer.pubkey(), &dream_mint, record);
+    let result = send(&mut context, &[expire(&records[0])], &[]).await;
+    assert_dream_error(result, DreamError::RewardNotExpired);
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp = now + REWARD_CLAIM_EXPIRY_SECS;
+    context.set_sysvar(&clock);
+    let result = send(&mut context, &[expire(&records[1])], &[]).await;
+    assert_dream_error(result, DreamError::DreamAlreadyValidated);
+
+    let dreamer_ata = get_associated_token_address_with_program_id(
+        &dreamer.pubkey(),
+        &dream_mint,
+        &spl_token_2022::ID,
+    );
+    let before = token_balance(&mut context, &dreamer_ata).await;
+    send(&mut context, &[expire(&records[0])], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &dreamer_ata).await, before + held);
+    let interpretation = pda::find_dream_interpretation_address(&records[0]).0;
+    assert!(context.banks_client.get_account(interpretation).await.unwrap().is_none());
+    let health: TreasuryHealth = fetch(&mut context, &pda::find_treasury_health_address().0).await;
+    assert_eq!(health.pending_coauthor_shares, 0);
+}
+
+#[tokio::test]
+async fn old_validated_dreams_archive_into_the_tree() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let ix = instructions::initialize_dream_archive_tree(&authority);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+
+    // Unvalidated dreams stay put
+    let archive = instructions::archive_dream(&dreamer.pubkey(), 0);
+    assert!(send(&mut context, &[archive.clone()], &[&dreamer]).await.is_err());
+    let oracle = Keypair::new();
+    send(&mut context, &[instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true)], &[])
+        .await
+        .unwrap();
+    send(&mut context, &pin_instructions(&authority, &oracle, &record, &[7u8; 32]), &[])
+        .await
+        .unwrap();
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &record,
+        None,
+        80,
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[archive.clone()], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::DreamTooRecentToArchive);
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += DREAM_ARCHIVE_MIN_AGE_SECS;
+    context.set_sysvar(&clock);
+    let record_account = context.banks_client.get_account(record).await.unwrap().unwrap();
+    send(&mut context, &[archive], &[&dreamer]).await.unwrap();
+
+    assert!(context.banks_client.get_account(record).await.unwrap().is_none());
+    let leaf = stake_tree::archived_dream_leaf(&record_account.data);
+    let archived: ArchivedDream =
+        fetch(&mut context, &pda::find_archived_dream_address(&record).0).await;
+    assert_eq!((archived.dreamer, archived.dream_id), (dreamer.pubkey(), 0));
+    assert_eq!((archived.leaf_index, archived.leaf), (0, leaf));
+    let tree: DreamArchiveTree =
+        fetch(&mut context, &pda::find_dream_archive_tree_address().0).await;
+    assert_eq!(tree.root, stake_tree::root(&[leaf]));
+    assert_eq!(tree.next_index, 1);
+}
+
+/// Writes a stand-in OneirobotNFT `NftAttributes` account for `mint`.
+fn set_oneirobot_dream_level(
+    context: &mut ProgramTestContext,
+    nft_program: &Pubkey,
+    mint: &Pubkey,
+    dream_level: u8,
+) {
+    let attributes = OneirobotAttributes {
+        mint: *mint,
+        owner: Pubkey::default(),
+        token_id: 0,
+        quantum_core_id: intern::string_id("Quantum Core Alpha"),
+        dream_level,
+        lucid_power: 50,
+        mind_strength: 50,
+        metadata_uri: String::new(),
+        mint_timestamp: 0,
+        random_seed: 0,
+        bump: 0,
+        season: 0,
+        name: String::new(),
+        lore_cid: String::new(),
+        named_at: 0,
+    };
+    let mut data = OneirobotAttributes::discriminator().to_vec();
+    data.extend(attributes.try_to_vec().unwrap());
+    let account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: *nft_program,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&OneirobotAttributes::address(nft_program, mint), &account.into());
+}
+
+#[tokio::test]
+async fn validator_gate_requires_and_rechecks_an_oneirobot() {
+    let mut context = program_test().start_with_context().await;
+    initialize(&mut context).await;
+    let payer = context.payer.pubkey();
+    let nft_program = Pubkey::new_unique();
+    let ix = instructions::set_validator_gate(&payer, true, 50, &nft_program);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let validator = Keypair::new();
+    fund(&mut context, &validator.pubkey()).await;
+    let nft_mint = create_mint(&mut context, &payer).await;
+    let nft_account = create_ata(&mut context, &validator.pubkey(), &nft_mint).await;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &nft_mint,
+        &nft_account,
+        &payer,
+        &[],
+        1,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+    let holding = OneirobotHolding {
+        nft_program,
+        mint: nft_mint,
+        token_account: nft_account,
+    };
+
+    // Neither no Oneirobot nor one below the gate's dream level qualifies
+    set_oneirobot_dream_level(&mut context, &nft_program, &nft_mint, 40);
+    let ix = instructions::register_validator(&validator.pubkey(), None);
+    let result = send(&mut context, &[ix], &[&validator]).await;
+    assert_dream_error(result, DreamError::ValidatorNotEligible);
+    let register = instructions::register_validator(&validator.pubkey(), Some(&holding));
+    let result = send(&mut context, &[register.clone()], &[&validator]).await;
+    assert_dream_error(result, DreamError::ValidatorNotEligible);
+
+    set_oneirobot_dream_level(&mut context, &nft_program, &nft_mint, 60);
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[register], &[&validator]).await.unwrap();
+    let registration_address = pda::find_validator_registration_address(&validator.pubkey()).0;
+    let registration: ValidatorRegistration = fetch(&mut context, &registration_address).await;
+    assert_eq!(registration.oneirobot_mint, nft_mint);
+
+    let challenger = Keypair::new();
+    fund(&mut context, &challenger.pubkey()).await;
+    let challenge = instructions::challenge_validator(
+        &challenger.pubkey(),
+        &validator.pubkey(),
+        Some(&holding),
+    );
+    let result = send(&mut context, &[challenge], &[&challenger]).await;
+    assert_dream_error(result, DreamError::ValidatorStillEligible);
+
+    // Once the Oneirobot is sold, a challenge closes the registration
+    let buyer_account = create_ata(&mut context, &Keypair::new().pubkey(), &nft_mint).await;
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID,
+        &nft_account,
+        &nft_mint,
+        &buyer_account,
+        &validator.pubkey(),
+        &[],
+        1,
+        TOKEN_DECIMALS,
+    )
+    .unwrap();
+    send(&mut context, &[transfer], &[&validator]).await.unwrap();
+    let sold = OneirobotHolding {
+        token_account: buyer_account,
+        ..holding
+    };
+    let challenge =
+        instructions::challenge_validator(&challenger.pubkey(), &validator.pubkey(), Some(&sold));
+    send(&mut context, &[challenge], &[&challenger]).await.unwrap();
+    let closed = context.banks_client.get_account(registration_address).await.unwrap();
+    assert!(closed.is_none());
+}
+
+#[tokio::test]
+async fn equipped_companion_is_stamped_on_dreams_and_earns_xp() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let payer = context.payer.pubkey();
+    let nft_program = Pubkey::new_unique();
+    let ix = instructions::set_splice_config(&payer, &nft_program);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let nft_mint = create_mint(&mut context, &payer).await;
+    let nft_account = create_ata(&mut context, &dreamer.pubkey(), &nft_mint).await;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &nft_mint,
+        &nft_account,
+        &payer,
+        &[],
+        1,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+    let holding = OneirobotHolding {
+        nft_program,
+        mint: nft_mint,
+        token_account: nft_account,
+    };
+
+    // Only an NFT with attributes from the splice config's program qualifies
+    let equip = instructions::equip_oneirobot_to_profile(&dreamer.pubkey(), &holding);
+    let result = send(&mut context, &[equip.clone()], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidOneirobotAccounts);
+    set_oneirobot_dream_level(&mut context, &nft_program, &nft_mint, 50);
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[equip], &[&dreamer]).await.unwrap();
+
+    let now = cluster_time(&mut context).await;
+    let record = |dream_id| {
+        let options = RecordDreamOptions {
+            companion: Some(holding.clone()),
+            ..Default::default()
+        };
+        instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id,
+            [7u8; 32],
+            DreamMetadata::default(),
+            options,
+            now,
+        )
+    };
+    send(&mut context, &[record(0)], &[&dreamer]).await.unwrap();
+    let dream_record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    let stamp: CompanionStamp =
+        fetch(&mut context, &pda::find_companion_stamp_address(&dream_record).0).await;
+    assert_eq!((stamp.dream_record, stamp.mint), (dream_record, nft_mint));
+    let stored = context.banks_client.get_account(dream_record).await.unwrap().unwrap();
+    assert_eq!(stored.data.len(), DreamRecord::EXTRAS_OFFSET);
+    let xp: CompanionXp = fetch(&mut context, &pda::find_companion_xp_address(&nft_mint).0).await;
+    assert_eq!((xp.xp, xp.dreams_recorded), (COMPANION_XP_PER_DREAM, 1));
+
+    // A sold companion can't be stamped on later dreams
+    let buyer_account = create_ata(&mut context, &Keypair::new().pubkey(), &nft_mint).await;
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID,
+        &nft_account,
+        &nft_mint,
+        &buyer_account,
+        &dreamer.pubkey(),
+        &[],
+        1,
+        TOKEN_DECIMALS,
+    )
+    .unwrap();
+    send(&mut context, &[transfer], &[&dreamer]).await.unwrap();
+    let result = send(&mut context, &[record(1)], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::CompanionNotHeld);
+}
+
+#[tokio::test]
+async fn registered_validators_earn_market_fee_rewards() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let share = MAX_VALIDATOR_FEE_SHARE_BPS;
+    let reward = DREAM_REWARD_PER_RECORD * MARKET_FEE_BPS / 10_000 * u64::from(share) / 10_000;
+    let ix = instructions::set_validator_rewards(&authority, &dream_mint, share + 1, reward);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidValidatorRewards);
+    let reward_cap = MAX_VALIDATOR_REWARD_PER_VALIDATION + 1;
+    let ix = instructions::set_validator_rewards(&authority, &dream_mint, share, reward_cap);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidValidatorRewards);
+    let oracle = Keypair::new();
+    let ixs = [
+        instructions::set_validator_rewards(&authority, &dream_mint, share, reward),
+        instructions::set_validator_gate(&authority, false, 0, &Pubkey::default()),
+        instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true),
+    ];
+    send(&mut context, &ixs, &[]).await.unwrap();
+
+    let validator = Keypair::new();
+    fund(&mut context, &validator.pubkey()).await;
+    let ix = instructions::register_validator(&validator.pubkey(), None);
+    send(&mut context, &[ix], &[&validator]).await.unwrap();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let mut records = Vec::new();
+    for id in 0..2 {
+        let content_hash = [id as u8 + 1; 32];
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            id,
+            content_hash,
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+        let record = pda::find_dream_record_address(&dreamer.pubkey(), id).0;
+        send(&mut context, &pin_instructions(&authority, &oracle, &record, &content_hash), &[])
+            .await
+            .unwrap();
+        records.push(record);
+    }
+
+    // Nothing is credited until market fees fund the vault
+    let validator_key = validator.pubkey();
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &records[0],
+        Some(&validator_key),
+        80,
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let validation: DreamValidation =
+        fetch(&mut context, &pda::find_dream_validation_address(&records[0]).0).await;
+    assert_eq!(validation.validator, validator_key);
+    let rewards_address = pda::find_validator_rewards_address(&validator_key).0;
+    let rewards: ValidatorRewards = fetch(&mut context, &rewards_address).await;
+    assert_eq!((rewards.validator, rewards.claimable), (validator_key, 0));
+
+    set_creator_profile(&mut context, &authority, 1, MIN_ESTABLISHED_VALIDATED_DREAMS);
+    let ix = instructions::create_market(&authority, &dream_mint, 0, [3u8; 32], 2, now + 60);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let market = pda::find_market_address(&authority, 0).0;
+    let ix = instructions::buy_outcome(&dreamer.pubkey(), &dream_mint, &market, 0, DREAM_REWARD_PER_RECORD);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let vault = pda::find_validator_reward_vault_address().0;
+    assert_eq!(token_balance(&mut context, &vault).await, reward);
+
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &records[1],
+        Some(&validator_key),
+        80,
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let rewards: ValidatorRewards = fetch(&mut context, &rewards_address).await;
+    assert_eq!((rewards.claimable, rewards.total_earned), (reward, reward));
+
+    // Only the validator claims, at most the epoch cap per day, until the
+    // vault has paid out in full
+    let ix = instructions::claim_validator_rewards(&dreamer.pubkey(), &dream_mint);
+    assert!(send(&mut context, &[ix], &[&dreamer]).await.is_err());
+    let cap = reward.div_ceil(2);
+    let ix = instructions::set_validator_claim_cap(&authority, cap);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let claim = instructions::claim_validator_rewards(&validator_key, &dream_mint);
+    send(&mut context, &[claim.clone()], &[&validator]).await.unwrap();
+    let validator_ata = get_associated_token_address_with_program_id(
+        &validator_key,
+        &dream_mint,
+        &spl_token_2022::ID,
+    );
+    assert_eq!(token_balance(&mut context, &validator_ata).await, cap);
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[claim.clone()], &[&validator]).await;
+    assert_dream_error(result, DreamError::ValidatorClaimCapReached);
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += EPOCH_STATS_PERIOD_SECS;
+    context.set_sysvar(&clock);
+    send(&mut context, &[claim.clone()], &[&validator]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &validator_ata).await, reward);
+    assert_eq!(token_balance(&mut context, &vault).await, 0);
+    let pool: ValidatorRewardPool =
+        fetch(&mut context, &pda::find_validator_reward_pool_address().0).await;
+    assert_eq!(pool.dream_received, reward);
+    assert_eq!((pool.total_credited, pool.total_claimed), (reward, reward));
+    let rewards: ValidatorRewards = fetch(&mut context, &rewards_address).await;
+    assert_eq!(rewards.claimable, 0);
+
+    // With nothing owed there is no empty payout
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[claim], &[&validator]).await;
+    assert_dream_error(result, DreamError::NothingToClaim);
+}
+
+#[tokio::test]
+async fn validation_disputes_track_validator_divergence() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let oracle = Keypair::new();
+    let ixs = [
+        instructions::set_validator_gate(&authority, false, 0, &Pubkey::default()),
+        instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true),
+    ];
+    send(&mut context, &ixs, &[]).await.unwrap();
+    let validator = Keypair::new();
+    fund(&mut context, &validator.pubkey()).await;
+    let ix = instructions::register_validator(&validator.pubkey(), None);
+    send(&mut context, &[ix], &[&validator]).await.unwrap();
+    let validator_key = validator.pubkey();
+
+    // One verdict close to consensus and one far off it
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let verdicts = [(80u8, 70u8), (40, 40 + VALIDATION_DISPUTE_TOLERANCE + 10)];
+    let mut records = Vec::new();
+    for (id, (score, _)) in verdicts.iter().enumerate() {
+        let id = id as u64;
+        let content_hash = [id as u8 + 1; 32];
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            id,
+            content_hash,
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+        let record = pda::find_dream_record_address(&dreamer.pubkey(), id).0;
+        send(&mut context, &pin_instructions(&authority, &oracle, &record, &content_hash), &[])
+            .await
+            .unwrap();
+        let ix = instructions::validate_dream(
+            &authority,
+            &dreamer.pubkey(),
+            &dream_mint,
+            &record,
+            Some(&validator_key),
+            *score,
+        );
+        send(&mut context, &[ix], &[]).await.unwrap();
+        records.push(record);
+    }
+    let stats_address = pda::find_validator_stats_address(&validator_key).0;
+    let stats: ValidatorStats = fetch(&mut context, &stats_address).await;
+    assert_eq!((stats.validator, stats.validations, stats.disputes), (validator_key, 2, 0));
+
+    let ix = instructions::resolve_validation_dispute(&authority, &records[0], &validator_key, 0);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidValidationScore);
+    for (record, (_, consensus)) in records.iter().zip(verdicts) {
+        let ix =
+            instructions::resolve_validation_dispute(&authority, record, &validator_key, consensus);
+        send(&mut context, &[ix], &[]).await.unwrap();
+    }
+    let dispute: ValidationDispute =
+        fetch(&mut context, &pda::find_validation_dispute_address(&records[1]).0).await;
+    assert_eq!((dispute.score, dispute.consensus_score), verdicts[1]);
+    assert!(dispute.lost);
+    let stats: ValidatorStats = fetch(&mut context, &stats_address).await;
+    assert_eq!((stats.disputes, stats.disputes_lost), (2, 1));
+    assert_eq!(stats.average_divergence(), (10 + u64::from(VALIDATION_DISPUTE_TOLERANCE) + 10) / 2);
+
+    // Each verdict is disputed once
+    context.get_new_latest_blockhash().await.unwrap();
+    let ix = instructions::resolve_validation_dispute(&authority, &records[0], &validator_key, 70);
+    assert!(send(&mut context, &[ix], &[]).await.is_err());
+}
+
+/// Stand-in OneirobotNFT that accepts `mint_spliced_oneirobot` only when the
+/// splicer PDA signs and the dreams of `splice_from_dreams_consumes_validated_dreams`
+/// set dream level 70 and lucid power 33.
+fn stub_nft_program(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let discriminator = &hash(b"global:mint_spliced_oneirobot").to_bytes()[..8];
+    let splicer = &accounts[16];
+    let signed = splicer.is_signer && *splicer.key == pda::find_splicer_address().0;
+    if data.starts_with(discriminator) && signed && data.ends_with(&[70, 33]) {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+#[tokio::test]
+async fn splice_from_dreams_consumes_validated_dreams() {
+    let nft_program = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program("oneirobot_nft", nft_program, processor!(stub_nft_program));
+    let mut context = program_test.start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let ix = instructions::set_splice_config(&authority, &nft_program);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let oracle = Keypair::new();
+    send(&mut context, &[instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true)], &[])
+        .await
+        .unwrap();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let dream_ids: Vec<u64> = (0..SPLICE_DREAM_COUNT as u64).collect();
+    for (&id, score) in dream_ids.iter().zip([60, 70, 80]) {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            id,
+            [id as u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+        let record = pda::find_dream_record_address(&dreamer.pubkey(), id).0;
+        send(&mut context, &pin_instructions(&authority, &oracle, &record, &[id as u8; 32]), &[])
+            .await
+            .unwrap();
+        // The last dream is still unvalidated
+        if id + 1 < SPLICE_DREAM_COUNT as u64 {
+            let ix = instructions::validate_dream(
+                &authority,
+                &dreamer.pubkey(),
+                &dream_mint,
+                &record,
+                None,
+                score,
+            );
+            send(&mut context, &[ix], &[]).await.unwrap();
+        }
+    }
+
+    let splice = |oneirobot_mint: &Pubkey| {
+        instructions::splice_from_dreams(
+            &dreamer.pubkey(),
+            &nft_program,
+            oneirobot_mint,
+            &dream_ids,
+            String::new(),
+            "Spliced Oneirobot".to_string(),
+            "ONEIROBOT".to_string(),
+        )
+    };
+    let oneirobot_mint = Keypair::new();
+    let ix = splice(&oneirobot_mint.pubkey());
+    let result = send(&mut context, &[ix], &[&dreamer, &oneirobot_mint]).await;
+    assert!(result.is_err());
+
+    let last = pda::find_dream_record_address(&dreamer.pubkey(), SPLICE_DREAM_COUNT as u64 - 1).0;
+    let ix = instructions::validate_dream(
+        &authority,
+        &dreamer.pubkey(),
+        &dream_mint,
+        &last,
+        None,
+        80,
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+    send(&mut context, &[splice(&oneirobot_mint.pubkey())], &[&dreamer, &oneirobot_mint])
+        .await
+        .unwrap();
+    let spliced: SplicedDream =
+        fetch(&mut context, &pda::find_spliced_dream_address(&last).0).await;
+    assert_eq!(spliced.oneirobot_mint, oneirobot_mint.pubkey());
+
+    // Each dream splices once
+    let second_mint = Keypair::new();
+    let ix = splice(&second_mint.pubkey());
+    let result = send(&mut context, &[ix], &[&dreamer, &second_mint]).await;
+    assert_dream_error(result, DreamError::DreamAlreadySpliced);
+}
+
+/// Stand-in OneirobotNFT that accepts `mint_certificate_oneirobot` only when
+/// the splicer PDA signs for the dream scored 96.
+fn stub_certificate_program(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let discriminator = &hash(b"global:mint_certificate_oneirobot").to_bytes()[..8];
+    let splicer = &accounts[17];
+    let signed = splicer.is_signer && *splicer.key == pda::find_splicer_address().0;
+    if data.starts_with(discriminator) && signed && data.ends_with(&[96]) {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+#[tokio::test]
+async fn top_scored_dream_claims_one_certificate() {
+    let nft_program = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_program("oneirobot_nft", nft_program, processor!(stub_certificate_program));
+    let mut context = program_test.start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let ix = instructions::set_splice_config(&authority, &nft_program);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let oracle = Keypair::new();
+    send(&mut context, &[instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true)], &[])
+        .await
+        .unwrap();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    for (id, score) in [(0u64, CERTIFICATE_MIN_SCORE - 1), (1, 96)] {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            id,
+            [id as u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+        let record = pda::find_dream_record_address(&dreamer.pubkey(), id).0;
+        send(&mut context, &pin_instructions(&authority, &oracle, &record, &[id as u8; 32]), &[])
+            .await
+            .unwrap();
+        let ix = instructions::validate_dream(
+            &authority,
+            &dreamer.pubkey(),
+            &dream_mint,
+            &record,
+            None,
+            score,
+        );
+        send(&mut context, &[ix], &[]).await.unwrap();
+    }
+
+    let claim = |dream_id: u64, oneirobot_mint: &Pubkey| {
+        instructions::claim_dream_certificate(
+            &dreamer.pubkey(),
+            &nft_program,
+            oneirobot_mint,
+            dream_id,
+            String::new(),
+            "Dream Certificate".to_string(),
+            "ONEIROBOT".to_string(),
+        )
+    };
+    let oneirobot_mint = Keypair::new();
+    let ix = claim(0, &oneirobot_mint.pubkey());
+    let result = send(&mut context, &[ix], &[&dreamer, &oneirobot_mint]).await;
+    assert_dream_error(result, DreamError::ScoreBelowCertificateThreshold);
+
+    let ix = claim(1, &oneirobot_mint.pubkey());
+    send(&mut context, &[ix], &[&dreamer, &oneirobot_mint]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 1).0;
+    let certificate: DreamCertificate =
+        fetch(&mut context, &pda::find_dream_certificate_address(&record).0).await;
+    assert_eq!(certificate.oneirobot_mint, oneirobot_mint.pubkey());
+    assert_eq!((certificate.dreamer, certificate.score), (dreamer.pubkey(), 96));
+
+    // Each dream is certified once
+    let second_mint = Keypair::new();
+    let ix = claim(1, &second_mint.pubkey());
+    assert!(send(&mut context, &[ix], &[&dreamer, &second_mint]).await.is_err());
+}
+
+#[tokio::test]
+async fn flagged_dreamer_earns_reduced_rewards() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    for dream_id in 0..3 {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id,
+            [dream_id as u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    }
+    let records: Vec<_> =
+        (0..3).map(|id| pda::find_dream_record_address(&dreamer.pubkey(), id).0).collect();
+
+    let reasons = [DreamFlagReason::Spam, DreamFlagReason::DisputeLost];
+    for (record, reason) in records.iter().zip(reasons) {
+        let ix = instructions::flag_dream(&authority, &dreamer.pubkey(), record, reason);
+        send(&mut context, &[ix], &[]).await.unwrap();
+    }
+    let flag: DreamFlag = fetch(&mut context, &pda::find_dream_flag_address(&records[1]).0).await;
+    assert_eq!((flag.dream_record, flag.reason), (records[1], DreamFlagReason::DisputeLost));
+    let profile: DreamerProfile =
+        fetch(&mut context, &pda::find_dreamer_profile_address(&dreamer.pubkey()).0).await;
+    assert_eq!(profile.reputation, -2 * REPUTATION_FLAG_PENALTY);
+
+    // A dream is flagged at most once
+    context.get_new_latest_blockhash().await.unwrap();
+    let ix = instructions::flag_dream(&authority, &dreamer.pubkey(), &records[0], reasons[0]);
+    assert!(send(&mut context, &[ix], &[]).await.is_err());
+
+    // Only the authority flags
+    let ix = instructions::flag_dream(
+        &dreamer.pubkey(),
+        &dreamer.pubkey(),
+        &records[2],
+        DreamFlagReason::Plagiarism,
+    );
+    assert!(send(&mut context, &[ix], &[&dreamer]).await.is_err());
+
+    // The next dream pays at the lower band's rate
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        3,
+        [3u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record: DreamRecord =
+        fetch(&mut context, &pda::find_dream_record_address(&dreamer.pubkey(), 3).0).await;
+    let bps = DreamerProfile::band_reward_bps(-2 * REPUTATION_FLAG_PENALTY);
+    assert!(bps < 10_000);
+    assert_eq!(record.token_reward, DREAM_REWARD_PER_RECORD * u64::from(bps) / 10_000);
+}
+
+#[tokio::test]
+async fn commit_epoch_root_requires_finished_day_and_matching_count() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let now = cluster_time(&mut context).await;
+    let day = EpochStats::day_of(now);
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let root = merkle::root(&[merkle::dream_leaf(0, &dreamer.pubkey(), &[7u8; 32])]);
+
+    let ix = instructions::commit_epoch_root(&authority, day, root, 1);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidEpochCommitment);
+
+    // Warp for a fresh blockhash, then move the clock into the next day
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += EPOCH_STATS_PERIOD_SECS;
+    context.set_sysvar(&clock);
+
+    let ix = instructions::commit_epoch_root(&authority, day, root, 2);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidEpochCommitment);
+
+    let ix = instructions::commit_epoch_root(&authority, day, root, 1);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let commitment: EpochCommitment =
+        fetch(&mut context, &pda::find_epoch_commitment_address(day).0).await;
+    assert_eq!(commitment.merkle_root, root);
+    assert_eq!(commitment.leaf_count, 1);
+    assert_eq!(commitment.committed_by, authority);
+}
+
+#[tokio::test]
+async fn timelocked_dream_reveals_matching_cid_after_unlock() {
+    let mut context = program_test().start_with_context().await;
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    let cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string();
+    let unlock_at = cluster_time(&mut context).await + 3_600;
+    let commitment = DreamCapsule::commitment(&cid);
+    let ix = instructions::record_timelocked_dream(&dreamer.pubkey(), 0, commitment, unlock_at);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let ix = instructions::reveal_dream(&dreamer.pubkey(), 0, cid.clone());
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::CapsuleLocked);
+
+    // Warp for a fresh blockhash, since the reveal below repeats the rejected one
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp = unlock_at;
+    context.set_sysvar(&clock);
+
+    let ix = instructions::reveal_dream(&dreamer.pubkey(), 0, "bafy-other".to_string());
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::CapsuleCommitmentMismatch);
+
+    let ix = instructions::reveal_dream(&dreamer.pubkey(), 0, cid.clone());
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let capsule: DreamCapsule =
+        fetch(&mut context, &pda::find_capsule_address(&dreamer.pubkey(), 0).0).await;
+    assert_eq!(capsule.cid, cid);
+    assert_eq!(capsule.revealed_at, unlock_at);
+}
+
+#[tokio::test]
+async fn record_dream_fee_is_swept_by_authority() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let ix = instructions::set_protocol_fees(&authority, MAX_PROTOCOL_FEE_LAMPORTS + 1, 0);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::ProtocolFeeTooHigh);
+    let fee = 5_000;
+    let ix = instructions::set_protocol_fees(&authority, fee, 0);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    fund(&mut context, &recipient).await;
+    let start = context.banks_client.get_balance(recipient).await.unwrap();
+    let ix = instructions::sweep_fees(&authority, &recipient);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let balance = context.banks_client.get_balance(recipient).await.unwrap();
+    assert_eq!(balance, start + fee);
+    let vault: FeeVault = fetch(&mut context, &pda::find_fee_vault_address().0).await;
+    assert_eq!(vault.total_swept, fee);
+}
+
+#[tokio::test]
+async fn reconcile_treasury_blocks_sweeps_until_held_back_shares_are_covered() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize_without_pool(&mut context).await;
+    let authority = context.payer.pubkey();
+    let health_address = pda::find_treasury_health_address().0;
+    let ix = instructions::top_up_pool(&authority, &dream_mint, DREAM_REWARD_PER_RECORD * 3 / 2);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let dreamer = Keypair::new();
+    let co_dreamer = Keypair::new();
+    let solo = Keypair::new();
+    for wallet in [&dreamer, &co_dreamer, &solo] {
+        fund(&mut context, &wallet.pubkey()).await;
+    }
+    let now = cluster_time(&mut context).await;
+    let options = RecordDreamOptions {
+        co_dreamers: vec![co_dreamer.pubkey()],
+        ..Default::default()
+    };
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        options,
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let share = DREAM_REWARD_PER_RECORD / 2;
+    let health: TreasuryHealth = fetch(&mut context, &health_address).await;
+    assert_eq!(health.pending_coauthor_shares, share);
+
+    // The next reward is paid out of the held-back share
+    let ix = instructions::record_dream(
+        &solo.pubkey(),
+        &dream_mint,
+        1,
+        [8u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&solo]).await.unwrap();
+    let reconcile_ix = instructions::reconcile_treasury(&solo.pubkey());
+    send(&mut context, &[reconcile_ix.clone()], &[&solo]).await.unwrap();
+    let health: TreasuryHealth = fetch(&mut context, &health_address).await;
+    assert!(!health.healthy);
+
+    let recipient = Keypair::new().pubkey();
+    fund(&mut context, &recipient).await;
+    let sweep_ix = instructions::sweep_fees(&authority, &recipient);
+    let result = send(&mut context, &[sweep_ix.clone()], &[]).await;
+    assert_dream_error(result, DreamError::TreasuryUnhealthy);
+
+    let ix = instructions::top_up_pool(&authority, &dream_mint, share);
+    send(&mut context, &[ix, reconcile_ix, sweep_ix], &[&solo]).await.unwrap();
+    let health: TreasuryHealth = fetch(&mut context, &health_address).await;
+    assert!(health.healthy);
+
+    // Confirming settles the share
+    let dream_record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    let ix = instructions::confirm_coauthorship(&co_dreamer.pubkey(), &dream_mint, &dream_record);
+    send(&mut context, &[ix], &[&co_dreamer]).await.unwrap();
+    let health: TreasuryHealth = fetch(&mut context, &health_address).await;
+    assert_eq!(health.pending_coauthor_shares, 0);
+}
+
+#[tokio::test]
+async fn insurance_fund_covers_depleted_pools() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize_without_pool(&mut context).await;
+    let authority = context.payer.pubkey();
+    let fund_address = pda::find_insurance_fund_address().0;
+    let insurance_vault = pda::find_insurance_vault_address().0;
+    let reward_vault = pda::find_reward_pool_vault_address().0;
+
+    let share = MAX_INSURANCE_FEE_SHARE_BPS;
+    let ix = instructions::set_insurance_fee_share(&authority, &dream_mint, share + 1);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InsuranceShareTooHigh);
+    let fee = 5_000;
+    let ixs = [
+        instructions::set_insurance_fee_share(&authority, &dream_mint, share),
+        instructions::set_protocol_fees(&authority, fee, 0),
+        instructions::top_up_pool(&authority, &dream_mint, DREAM_REWARD_PER_RECORD),
+    ];
+    send(&mut context, &ixs, &[]).await.unwrap();
+
+    // The only reward empties the pool
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &reward_vault).await, 0);
+
+    // Swept SOL fees and market fees both pay in the fund's slice
+    let recipient = Keypair::new().pubkey();
+    fund(&mut context, &recipient).await;
+    let start = context.banks_client.get_balance(recipient).await.unwrap();
+    send(&mut context, &[instructions::sweep_fees(&authority, &recipient)], &[]).await.unwrap();
+    let insured_lamports = fee * u64::from(share) / 10_000;
+    let balance = context.banks_client.get_balance(recipient).await.unwrap();
+    assert_eq!(balance, start + fee - insured_lamports);
+
+    set_creator_profile(&mut context, &authority, 1, MIN_ESTABLISHED_VALIDATED_DREAMS);
+    let ix = instructions::create_market(&authority, &dream_mint, 0, [3u8; 32], 2, now + 60);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let market = pda::find_market_address(&authority, 0).0;
+    let ix = instructions::buy_outcome(&dreamer.pubkey(), &dream_mint, &market, 0, DREAM_REWARD_PER_RECORD);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let insured_dream = DREAM_REWARD_PER_RECORD * MARKET_FEE_BPS / 10_000 * u64::from(share) / 10_000;
+    assert_eq!(token_balance(&mut context, &insurance_vault).await, insured_dream);
+
+    let insurance_fund: InsuranceFund = fetch(&mut context, &fund_address).await;
+    assert_eq!(insurance_fund.lamports_received, insured_lamports);
+    assert_eq!(insurance_fund.dream_received, insured_dream);
+
+    // Only the authority covers shortfalls, and only up to the fund's balance
+    let ix = instructions::cover_shortfall(&dreamer.pubkey(), &dream_mint, ShortfallTarget::RewardPool, 1);
+    assert!(send(&mut context, &[ix], &[&dreamer]).await.is_err());
+    let ix = instructions::cover_shortfall(
+        &authority,
+        &dream_mint,
+        ShortfallTarget::RewardPool,
+        insured_dream + 1,
+    );
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InsufficientInsuranceFunds);
+    let ix = instructions::cover_shortfall(&authority, &dream_mint, ShortfallTarget::RewardPool, insured_dream);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &reward_vault).await, insured_dream);
+    assert_eq!(token_balance(&mut context, &insurance_vault).await, 0);
+
+    // An unfunded crank pool can't pay one bounty
+    let bounty = insured_lamports / 2;
+    send(&mut context, &[instructions::set_crank_bounty(&authority, bounty, 60)], &[])
+        .await
+        .unwrap();
+    let crank_pool = pda::find_crank_pool_address().0;
+    let pool_start = context.banks_client.get_balance(crank_pool).await.unwrap();
+    let ix = instructions::cover_shortfall(&authority, &dream_mint, ShortfallTarget::CrankPool, bounty);
+    send(&mut context, &[ix.clone()], &[]).await.unwrap();
+    let pool_balance = context.banks_client.get_balance(crank_pool).await.unwrap();
+    assert_eq!(pool_balance, pool_start + bounty);
+
+    // One bounty is covered now
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::NoShortfall);
+
+    let insurance_fund: InsuranceFund = fetch(&mut context, &fund_address).await;
+    assert_eq!((insurance_fund.lamports_covered, insurance_fund.dream_covered), (bounty, insured_dream));
+}
+
+#[tokio::test]
+async fn dream_extras_grow_and_shrink_the_record() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    let before = context.banks_client.get_account(record).await.unwrap().unwrap();
+    assert_eq!(before.data.len(), DreamRecord::EXTRAS_OFFSET);
+    assert_eq!(DreamRecord::extras(&before.data).unwrap(), DreamRecordExtras::default());
+
+    // Tags are interned in the same transaction and stored by id
+    let tags = ["flying", "ocean"];
+    let extras = DreamRecordExtras {
+        tags: tags.map(intern::string_id).to_vec(),
+        annotations: vec![Pubkey::new_unique()],
+    };
+    let mut ixs: Vec<Instruction> = tags
+        .iter()
+        .map(|tag| instructions::intern_string(&dreamer.pubkey(), tag.to_string()))
+        .collect();
+    ixs.push(instructions::set_dream_extras(&dreamer.pubkey(), &record, extras.clone()));
+    send(&mut context, &ixs, &[&dreamer]).await.unwrap();
+    let interned: InternedString =
+        fetch(&mut context, &pda::find_interned_string_address(extras.tags[1]).0).await;
+    assert_eq!((interned.id, interned.value.as_str()), (extras.tags[1], "ocean"));
+    let grown = context.banks_client.get_account(record).await.unwrap().unwrap();
+    assert_eq!(grown.data.len(), DreamRecord::EXTRAS_OFFSET + extras.space());
+    assert!(grown.lamports > before.lamports);
+    assert_eq!(DreamRecord::extras(&grown.data).unwrap(), extras);
+    // The fixed fields are untouched
+    let dream: DreamRecord = fetch(&mut context, &record).await;
+    assert_eq!(dream.content_hash, [7u8; 32]);
+
+    let too_many = DreamRecordExtras {
+        tags: vec![extras.tags[0]; MAX_DREAM_TAGS + 1],
+        annotations: vec![],
+    };
+    let ix = instructions::set_dream_extras(&dreamer.pubkey(), &record, too_many);
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::DreamExtrasTooLarge);
+
+    // Clearing them shrinks the record and refunds the rent
+    let ix = instructions::set_dream_extras(&dreamer.pubkey(), &record, DreamRecordExtras::default());
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let shrunk = context.banks_client.get_account(record).await.unwrap().unwrap();
+    assert_eq!(shrunk.data.len(), DreamRecord::EXTRAS_OFFSET + 8);
+    assert!(shrunk.lamports < grown.lamports);
+    assert_eq!(DreamRecord::extras(&shrunk.data).unwrap(), DreamRecordExtras::default());
+
+    // Interning a string again is a no-op; an empty one is rejected
+    let ix = instructions::intern_string(&dreamer.pubkey(), "ocean".to_string());
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let ix = instructions::intern_string(&dreamer.pubkey(), String::new());
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidInternedString);
+}
+
+/// `signer`'s ed25519 signature over `dream`, then `record_dream_delegated`.
+fn relay_instructions(
+    relayer: &Pubkey,
+    dream_mint: &Pubkey,
+    dream_id: u64,
+    signer: &Keypair,
+    dream: &SignedDream,
+    now: i64,
+) -> [Instruction; 2] {
+    let signature: [u8; 64] = signer.sign_message(&dream.message()).as_ref().try_into().unwrap();
+    [
+        instructions::ed25519_verify(&signer.pubkey(), &signature, &dream.message()),
+        instructions::record_dream_delegated(relayer, dream_mint, dream_id, dream, now),
+    ]
+}
+
+#[tokio::test]
+async fn relayer_records_dream_signed_by_dreamer() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let relayer = context.payer.pubkey();
+
+    // The dreamer holds no SOL; the relayer pays the fee and rent
+    let dreamer = Keypair::new();
+    let now = cluster_time(&mut context).await;
+    let mut dream = SignedDream {
+        dreamer: dreamer.pubkey(),
+        dream_content_hash: [7u8; 32],
+        metadata: DreamMetadata {
+            mood: Mood::Peaceful,
+            lucidity_score: 4,
+            sleep_duration_minutes: 420,
+        },
+        nonce: 0,
+    };
+    let ixs = relay_instructions(&relayer, &dream_mint, 0, &dreamer, &dream, now);
+    send(&mut context, &ixs, &[]).await.unwrap();
+
+    let record: DreamRecord =
+        fetch(&mut context, &pda::find_dream_record_address(&dreamer.pubkey(), 0).0).await;
+    assert_eq!(record.dreamer, dreamer.pubkey());
+    assert_eq!(record.metadata, dream.metadata);
+    let ata = get_associated_token_address_with_program_id(&dreamer.pubkey(), &dream_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &ata).await, DREAM_REWARD_PER_RECORD);
+    let nonce: RelayNonce = fetch(&mut context, &pda::find_relay_nonce_address(&dreamer.pubkey()).0).await;
+    assert_eq!(nonce.nonce, 1);
+
+    // The same signature can't be replayed for another dream
+    let ixs = relay_instructions(&relayer, &dream_mint, 1, &dreamer, &dream, now);
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::InvalidRelayNonce);
+
+    // Nor can the relayer sign for the dreamer, or change what they signed
+    dream.nonce = 1;
+    let impostor = Keypair::new();
+    let ixs = relay_instructions(&relayer, &dream_mint, 1, &impostor, &dream, now);
+    let result = send(&mut context, &ixs, &[]).await;
+    assert_dream_error(result, DreamError::InvalidDreamerSignature);
+    let [verify, _] = relay_instructions(&relayer, &dream_mint, 1, &dreamer, &dream, now);
+    dream.metadata.lucidity_score = 5;
+    let ix = instructions::record_dream_delegated(&relayer, &dream_mint, 1, &dream, now);
+    let result = send(&mut context, &[verify, ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidDreamerSignature);
+}
+
+#[tokio::test]
+async fn session_key_records_dreams_until_expiry_or_revocation() {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let wallet = Keypair::new();
+    fund(&mut context, &wallet.pubkey()).await;
+    let session = Keypair::new();
+    let now = cluster_time(&mut context).await;
+
+    let ix = instructions::create_session_key(&wallet.pubkey(), &session.pubkey(), 0, now + 3600, 0);
+    let result = send(&mut context, &[ix], &[&wallet]).await;
+    assert_dream_error(result, DreamError::InvalidSessionScope);
+    let expires_at = now + MAX_SESSION_KEY_DURATION_SECS + 1;
+    let ix = instructions::create_session_key(
+        &wallet.pubkey(),
+        &session.pubkey(),
+        SESSION_SCOPE_RECORD_DREAM,
+        expires_at,
+        0,
+    );
+    let result = send(&mut context, &[ix], &[&wallet]).await;
+    assert_dream_error(result, DreamError::InvalidSessionExpiry);
+
+    let ix = instructions::create_session_key(
+        &wallet.pubkey(),
+        &session.pubkey(),
+        SESSION_SCOPE_RECORD_DREAM,
+        now + 3600,
+        100_000_000,
+    );
+    send(&mut context, &[ix], &[&wallet]).await.unwrap();
+    let session_address = pda::find_session_key_address(&wallet.pubkey(), &session.pubkey()).0;
+    let session_key: SessionKey = fetch(&mut context, &session_address).await;
+    assert_eq!((session_key.signer, session_key.scope), (session.pubkey(), SESSION_SCOPE_RECORD_DREAM));
+
+    // The session key signs and pays; the dream is the wallet's
+    let record_with_session = |dream_id: u64, now: i64| {
+        instructions::record_dream_with_session(
+            &session.pubkey(),
+            &wallet.pubkey(),
+            &dream_mint,
+            dream_id,
+            [7u8; 32],
+            DreamMetadata::default(),
+            now,
+        )
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[record_with_session(0, now)],
+        Some(&session.pubkey()),
+        &[&session],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+    let record: DreamRecord =
+        fetch(&mut context, &pda::find_dream_record_address(&wallet.pubkey(), 0).0).await;
+    assert_eq!(record.dreamer, wallet.pubkey());
+
+    // Another wallet's session key can't record for this one
+    let other = Keypair::new();
+    fund(&mut context, &other.pubkey()).await;
+    let ix = instructions::record_dream_with_session(
+        &other.pubkey(),
+        &wallet.pubkey(),
+        &dream_mint,
+        1,
+        [7u8; 32],
+        DreamMetadata::default(),
+        now,
+    );
+    let result = send(&mut context, &[ix], &[&other]).await;
+    assert!(result.is_err());
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp += 3600;
+    context.set_sysvar(&clock);
+    let later = cluster_time(&mut context).await;
+    let result = send(&mut context, &[record_with_session(1, later)], &[&session]).await;
+    assert_dream_error(result, DreamError::SessionKeyNotAuthorized);
+
+    let ix = instructions::revoke_session_key(&wallet.pubkey(), &session.pubkey());
+    send(&mut context, &[ix], &[&wallet]).await.unwrap();
+    let revoked = context.banks_client.get_account(session_address).await.unwrap();
+    assert!(revoked.is_none());
+}
+
+// ===================== DREAM INTERFACES =====================
+
+#[tokio::test]
+async fn dream_interfaces_are_indexed_per_dreamer() {
+    let mut context = program_test().start_with_context().await;
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    for (index, cid) in ["QmFirst", "QmSecond"].into_iter().enumerate() {
+        let ix = instructions::interface_dream(&dreamer.pubkey(), index as u64, cid.to_string());
+        send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    }
+
+    let profile: DreamerProfile =
+        fetch(&mut context, &pda::find_dreamer_profile_address(&dreamer.pubkey()).0).await;
+    assert_eq!(profile.interface_count, 2);
+
+    let ix = instructions::update_interface(&dreamer.pubkey(), 1, "QmUpdated".to_string());
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let second = pda::find_dream_interface_address(&dreamer.pubkey(), 1).0;
+    let interface: DreamInterface = fetch(&mut context, &second).await;
+    assert_eq!(interface.index, 1);
+    assert_eq!(interface.ipfs_hash, "QmUpdated");
+
+    let ix = instructions::close_interface(&dreamer.pubkey(), 0);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let first = pda::find_dream_interface_address(&dreamer.pubkey(), 0).0;
+    assert!(context.banks_client.get_account(first).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn interface_dream_rejects_oversized_hash() {
+    let mut context = program_test().start_with_context().await;
+    let dreamer = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    let ipfs_hash = "Q".repeat(DreamInterface::MAX_IPFS_HASH_LEN + 1);
+    let ix = instructions::interface_dream(&dreamer.pubkey(), 0, ipfs_hash);
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::DreamContentTooLarge);
+}
+
+// ===================== LUCID STAKING =====================
+
+#[tokio::test]
+async fn stake_for_lucid_access_sets_access_level() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let lucid_mint = create_mint(&mut context, &payer).await;
+
+    let user = Keypair::new();
+    fund(&mut context, &user.pubkey()).await;
+    let user_lucid = create_ata(&mut context, &user.pubkey(), &lucid_mint).await;
+    let vault_owner = Keypair::new();
+    let vault = create_ata(&mut context, &vault_owner.pubkey(), &lucid_mint).await;
+
+    let amount = 50 * TOKEN_UNIT;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &lucid_mint,
+        &user_lucid,
+        &payer,
+        &[],
+        amount,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+
+    let ix = instructions::stake_for_lucid_access(&user.pubkey(), &lucid_mint, &user_lucid, &vault, amount);
+    send(&mut context, &[ix], &[&user]).await.unwrap();
+
+    let stake_address = pda::find_stake_address(&user.pubkey(), &lucid_mint).0;
+    let stake: LucidStake = fetch(&mut context, &stake_address).await;
+    assert_eq!(stake.mint, lucid_mint);
+    assert_eq!(stake.amount, amount);
+    assert_eq!(stake.access_level, 3);
+    assert_eq!(token_balance(&mut context, &vault).await, amount);
+
+    // The vault must hold the staked mint
+    let other_mint = create_mint(&mut context, &payer).await;
+    let ix = instructions::stake_for_lucid_access(&user.pubkey(), &other_mint, &user_lucid, &vault, amount);
+    let result = send(&mut context, &[ix], &[&user]).await;
+    assert!(result.is_err());
+
+    let view = instructions::get_stake_info(&user.pubkey(), &lucid_mint);
+    let info: StakeInfo = simulate_view(&mut context, view).await;
+    assert_eq!(info.amount, amount);
+    assert_eq!(info.access_level, 3);
+    assert_eq!(info.amount_to_next_level, 50 * TOKEN_UNIT + 1);
+}
+
+#[tokio::test]
+async fn get_capabilities_reports_version_and_set_up_modules() {
+    let mut context = program_test().start_with_context().await;
+    initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+
+    let view = instructions::get_capabilities(false, false, false, false);
+    let capabilities: ProgramCapabilities = simulate_view(&mut context, view).await;
+    assert_eq!(capabilities.version, env!("CARGO_PKG_VERSION"));
+    assert!(capabilities.has(CAPABILITY_MARKETPLACE));
+    assert!(!capabilities.has(CAPABILITY_GOVERNANCE));
+    assert!(!capabilities.has(CAPABILITY_COMPRESSION));
+    assert!(!capabilities.has(CAPABILITY_VRF));
+    assert_eq!(capabilities.has(CAPABILITY_DEVNET_FAUCET), cfg!(feature = "devnet"));
+
+    // Claiming a module's account exists before it does fails
+    let ix = instructions::get_capabilities(false, false, false, true);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert!(result.is_err());
+
+    let ix = instructions::initialize_dream_archive_tree(&authority);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let view = instructions::get_capabilities(false, false, false, true);
+    let capabilities: ProgramCapabilities = simulate_view(&mut context, view).await;
+    assert!(capabilities.has(CAPABILITY_COMPRESSION));
+    assert!(!capabilities.has(CAPABILITY_GOVERNANCE));
+}
+
+#[tokio::test]
+async fn tier_credentials_follow_the_lucid_stake() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let lucid_mint = create_mint(&mut context, &payer).await;
+    initialize_with_lucid_mint(&mut context, &lucid_mint).await;
+    let setup: Vec<Instruction> = TIER_CREDENTIAL_LEVELS
+        .into_iter()
+        .map(|level| instructions::create_tier_credential_mint(&payer, level))
+        .collect();
+    send(&mut context, &setup, &[]).await.unwrap();
+
+    let user = Keypair::new();
+    fund(&mut context, &user.pubkey()).await;
+    let user_lucid = create_ata(&mut context, &user.pubkey(), &lucid_mint).await;
+    let vault = create_ata(&mut context, &Keypair::new().pubkey(), &lucid_mint).await;
+    let amount = 50 * TOKEN_UNIT;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &lucid_mint,
+        &user_lucid,
+        &payer,
+        &[],
+        amount,
+    )
+    .unwrap();
+    let ix = instructions::stake_for_lucid_access(&user.pubkey(), &lucid_mint, &user_lucid, &vault, amount);
+    send(&mut context, &[mint_ix, ix], &[&user]).await.unwrap();
+
+    // A level 3 stake holds the VIP credential only
+    let sync = instructions::sync_tier_credentials(&payer, &user.pubkey(), &lucid_mint);
+    send(&mut context, &[sync.clone()], &[]).await.unwrap();
+    let [vip_mint, quantum_mint] =
+        TIER_CREDENTIAL_LEVELS.map(|level| pda::find_tier_credential_mint_address(level).0);
+    let credential =
+        |mint| get_associated_token_address_with_program_id(&user.pubkey(), &mint, &spl_token_2022::ID);
+    let vip = credential(vip_mint);
+    assert_eq!(token_balance(&mut context, &vip).await, 1);
+    assert_eq!(token_balance(&mut context, &credential(quantum_mint)).await, 0);
+
+    // Credentials can't be passed on
+    let other = create_ata(&mut context, &payer, &vip_mint).await;
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID,
+        &vip,
+        &vip_mint,
+        &other,
+        &user.pubkey(),
+        &[],
+        1,
+        0,
+    )
+    .unwrap();
+    assert!(send(&mut context, &[transfer], &[&user]).await.is_err());
+
+    // Once the stake drops below the VIP threshold the credential is burned
+    let stake_address = pda::find_stake_address(&user.pubkey(), &lucid_mint).0;
+    let mut stake: LucidStake = fetch(&mut context, &stake_address).await;
+    stake.amount = 5 * TOKEN_UNIT;
+    let mut account = context.banks_client.get_account(stake_address).await.unwrap().unwrap();
+    account.data = LucidStake::DISCRIMINATOR.to_vec();
+    account.data.extend(stake.try_to_vec().unwrap());
+    context.set_account(&stake_address, &account.into());
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[sync], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &vip).await, 0);
+}
+
+#[tokio::test]
+async fn migrate_stake_moves_legacy_stake_to_lucid_address() {
+    let user = Keypair::new();
+    let legacy_address = pda::find_legacy_stake_address(&user.pubkey()).0;
+    let amount = 20 * TOKEN_UNIT;
+    let legacy = LucidStakeV1 {
+        user: user.pubkey(),
+        amount,
+        timestamp: 1_700_000_000,
+        access_level: 2,
+    };
+    let mut data = LucidStake::DISCRIMINATOR.to_vec();
+    data.extend(legacy.try_to_vec().unwrap());
+    let mut program_test = program_test();
+    program_test.add_account(
+        legacy_address,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: dream_mind_lucid::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+    initialize(&mut context).await;
+    fund(&mut context, &user.pubkey()).await;
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+
+    let ix = instructions::migrate_stake(&user.pubkey(), &treasury.lucid_mint);
+    send(&mut context, &[ix.clone()], &[&user]).await.unwrap();
+
+    let stake_address = pda::find_stake_address(&user.pubkey(), &treasury.lucid_mint).0;
+    let stake: LucidStake = fetch(&mut context, &stake_address).await;
+    assert_eq!(stake.user, user.pubkey());
+    assert_eq!(stake.mint, treasury.lucid_mint);
+    assert_eq!((stake.amount, stake.timestamp, stake.access_level), (amount, 1_700_000_000, 2));
+    let closed = context.banks_client.get_account(legacy_address).await.unwrap();
+    assert!(closed.is_none());
+
+    // The legacy account is gone, so the stake can't be migrated twice
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix], &[&user]).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn compressed_stakes_unstake_against_current_root() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &treasury).await;
+    let smind_mint = create_mint(&mut context, &payer).await;
+    let lucid_mint = create_mint(&mut context, &treasury).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let ix = instructions::initialize_compressed_stake_tree(&payer, &smind_mint);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let first = Keypair::new();
+    let second = Keypair::new();
+    let mut leaves = Vec::new();
+    let mut atas = Vec::new();
+    let now = cluster_time(&mut context).await;
+    for (staker, amount) in [(&first, 10 * TOKEN_UNIT), (&second, 20 * TOKEN_UNIT)] {
+        fund(&mut context, &staker.pubkey()).await;
+        let ata = create_ata(&mut context, &staker.pubkey(), &smind_mint).await;
+        let mint_ix = spl_token_2022::instruction::mint_to(
+            &spl_token_2022::ID,
+            &smind_mint,
+            &ata,
+            &payer,
+            &[],
+            amount,
+        )
+        .unwrap();
+        send(&mut context, &[mint_ix], &[]).await.unwrap();
+        let ix = instructions::compressed_stake(&staker.pubkey(), &smind_mint, amount);
+        send(&mut context, &[ix], &[staker]).await.unwrap();
+        assert_eq!(token_balance(&mut context, &ata).await, 0);
+        leaves.push(stake_tree::stake_leaf(&staker.pubkey(), amount, now));
+        atas.push(ata);
+    }
+
+    let tree_address = pda::find_compressed_stake_tree_address().0;
+    let tree: CompressedStakeTree = fetch(&mut context, &tree_address).await;
+    assert_eq!(tree.root, stake_tree::root(&leaves));
+    assert_eq!(tree.total_staked, 30 * TOKEN_UNIT);
+    assert_eq!(tree.active_stakes, 2);
+
+    let stale_proof = stake_tree::proof(&leaves, 1);
+    let ix = instructions::compressed_unstake(
+        &first.pubkey(),
+        &smind_mint,
+        0,
+        10 * TOKEN_UNIT,
+        now,
+        stake_tree::proof(&leaves, 0),
+    );
+    send(&mut context, &[ix], &[&first]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &atas[0]).await, 10 * TOKEN_UNIT);
+    leaves[0] = stake_tree::EMPTY_LEAF;
+
+    // The first unstake changed the root, so the old proof no longer verifies
+    let second_unstake = |proof| {
+        let amount = 20 * TOKEN_UNIT;
+        instructions::compressed_unstake(&second.pubkey(), &smind_mint, 1, amount, now, proof)
+    };
+    let result = send(&mut context, &[second_unstake(stale_proof)], &[&second]).await;
+    assert_dream_error(result, DreamError::InvalidStakeProof);
+    let ix = second_unstake(stake_tree::proof(&leaves, 1));
+    send(&mut context, &[ix], &[&second]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &atas[1]).await, 20 * TOKEN_UNIT);
+
+    let tree: CompressedStakeTree = fetch(&mut context, &tree_address).await;
+    assert_eq!(tree.root, stake_tree::root(&[]));
+    assert_eq!(tree.total_staked, 0);
+    assert_eq!(tree.active_stakes, 0);
+}
+
+// ===================== RESEARCH LICENSING =====================
+
+#[tokio::test]
+async fn research_license_revenue_is_split_among_consenting_dreamers() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &treasury).await;
+    let smind_mint = create_mint(&mut context, &treasury).await;
+    let lucid_mint = create_mint(&mut context, &payer).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let first = Keypair::new();
+    let second = Keypair::new();
+    for dreamer in [&first, &second] {
+        fund(&mut context, &dreamer.pubkey()).await;
+        let ix = instructions::set_research_consent(&dreamer.pubkey(), true);
+        send(&mut context, &[ix], &[dreamer]).await.unwrap();
+    }
+
+    let licensee = Keypair::new();
+    fund(&mut context, &licensee.pubkey()).await;
+    let licensee_lucid = create_ata(&mut context, &licensee.pubkey(), &lucid_mint).await;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &lucid_mint,
+        &licensee_lucid,
+        &payer,
+        &[],
+        RESEARCH_LICENSE_PRICE,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+    let ix = instructions::purchase_research_license(&licensee.pubkey(), &lucid_mint);
+    send(&mut context, &[ix], &[&licensee]).await.unwrap();
+
+    let license: ResearchLicense =
+        fetch(&mut context, &pda::find_research_license_address(&licensee.pubkey()).0).await;
+    assert_eq!(license.total_paid, RESEARCH_LICENSE_PRICE);
+    assert_eq!(license.expires_at, license.purchased_at + RESEARCH_LICENSE_DURATION_SECS);
+
+    // Opting out keeps revenue earned while consenting
+    let ix = instructions::set_research_consent(&second.pubkey(), false);
+    send(&mut context, &[ix], &[&second]).await.unwrap();
+
+    let per_dreamer = RESEARCH_LICENSE_PRICE * (10_000 - RESEARCH_TREASURY_SHARE_BPS) / 10_000 / 2;
+    for dreamer in [&first, &second] {
+        let ix = instructions::claim_research_revenue(&dreamer.pubkey(), &lucid_mint);
+        send(&mut context, &[ix], &[dreamer]).await.unwrap();
+        let ata = get_associated_token_address_with_program_id(
+            &dreamer.pubkey(),
+            &lucid_mint,
+            &spl_token_2022::ID,
+        );
+        assert_eq!(token_balance(&mut context, &ata).await, per_dreamer);
+    }
+    let treasury_lucid =
+        get_associated_token_address_with_program_id(&treasury, &lucid_mint, &spl_token_2022::ID);
+    let treasury_share = RESEARCH_LICENSE_PRICE - 2 * per_dreamer;
+    assert_eq!(token_balance(&mut context, &treasury_lucid).await, treasury_share);
+}
+
+#[tokio::test]
+async fn license_dream_splits_royalties_with_co_authors() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &treasury).await;
+    let smind_mint = create_mint(&mut context, &treasury).await;
+    let lucid_mint = create_mint(&mut context, &payer).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let dreamer = Keypair::new();
+    let co_author = Keypair::new();
+    let licensee = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    fund(&mut context, &licensee.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let dream_record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+
+    let price = 100 * TOKEN_UNIT;
+    let co_authors = vec![RoyaltyShare {
+        recipient: co_author.pubkey(),
+        share_bps: 2_500,
+    }];
+    let ix =
+        instructions::set_license_terms(&dreamer.pubkey(), &dream_record, price, 0, co_authors);
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+
+    let co_author_ata = create_ata(&mut context, &co_author.pubkey(), &lucid_mint).await;
+    let licensee_ata = create_ata(&mut context, &licensee.pubkey(), &lucid_mint).await;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &lucid_mint,
+        &licensee_ata,
+        &payer,
+        &[],
+        price,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+
+    let license_dream = |co_authors: &[Pubkey], license_type| {
+        instructions::license_dream(
+            &licensee.pubkey(),
+            &lucid_mint,
+            &dream_record,
+            &dreamer.pubkey(),
+            co_authors,
+            license_type,
+        )
+    };
+    let ix = license_dream(&[co_author.pubkey()], DreamLicenseType::Derivative);
+    let result = send(&mut context, &[ix], &[&licensee]).await;
+    assert_dream_error(result, DreamError::LicenseNotOffered);
+    let ix = license_dream(&[], DreamLicenseType::Commercial);
+    let result = send(&mut context, &[ix], &[&licensee]).await;
+    assert_dream_error(result, DreamError::InvalidRoyaltyAccount);
+
+    let ix = license_dream(&[co_author.pubkey()], DreamLicenseType::Commercial);
+    send(&mut context, &[ix], &[&licensee]).await.unwrap();
+
+    let royalties = price - price * DREAM_LICENSE_TREASURY_SHARE_BPS / 10_000;
+    let dreamer_ata = get_associated_token_address_with_program_id(
+        &dreamer.pubkey(),
+        &lucid_mint,
+        &spl_token_2022::ID,
+    );
+    assert_eq!(token_balance(&mut context, &licensee_ata).await, 0);
+    assert_eq!(token_balance(&mut context, &co_author_ata).await, royalties / 4);
+    assert_eq!(token_balance(&mut context, &dreamer_ata).await, royalties - royalties / 4);
+
+    let license_address = pda::find_dream_license_address(
+        &dream_record,
+        &licensee.pubkey(),
+        DreamLicenseType::Commercial,
+    )
+    .0;
+    let license: DreamLicense = fetch(&mut context, &license_address).await;
+    assert_eq!(license.license_type, DreamLicenseType::Commercial);
+    assert_eq!(license.expires_at, license.issued_at + DREAM_LICENSE_DURATION_SECS);
+    assert_eq!(license.total_paid, price);
+}
+
+#[tokio::test]
+async fn approved_translation_claims_bounty() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &treasury).await;
+    let smind_mint = create_mint(&mut context, &treasury).await;
+    let lucid_mint = create_mint(&mut context, &payer).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let dreamer = Keypair::new();
+    let translator = Keypair::new();
+    fund(&mut context, &dreamer.pubkey()).await;
+    fund(&mut context, &translator.pubkey()).await;
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::record_dream(
+        &dreamer.pubkey(),
+        &dream_mint,
+        0,
+        [7u8; 32],
+        DreamMetadata::default(),
+        RecordDreamOptions::default(),
+        now,
+    );
+    send(&mut context, &[ix], &[&dreamer]).await.unwrap();
+    let dream_record = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+
+    // The payer sponsors a Spanish translation
+    let bounty = 50 * TOKEN_UNIT;
+    let payer_ata = create_ata(&mut context, &payer, &lucid_mint).await;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &lucid_mint,
+        &payer_ata,
+        &payer,
+        &[],
+        bounty,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+    let ix =
+        instructions::post_translation_bounty(&payer, &lucid_mint, &dream_record, *b"es", bounty);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let ix = instructions::attach_annotation(
+        &translator.pubkey(),
+        &dream_record,
+        *b"ES",
+        "bafytranslation".to_string(),
+    );
+    let result = send(&mut context, &[ix], &[&translator]).await;
+    assert_dream_error(result, DreamError::InvalidLanguageCode);
+    let ix = instructions::attach_annotation(
+        &translator.pubkey(),
+        &dream_record,
+        *b"es",
+        "bafytranslation".to_string(),
+    );
+    send(&mut context, &[ix], &[&translator]).await.unwrap();
+
+    let translator_ata = create_ata(&mut context, &translator.pubkey(), &lucid_mint).await;
+    let ix = instructions::approve_annotation(
+        &payer,
+        &lucid_mint,
+        &dream_record,
+        &translator.pubkey(),
+        *b"es",
+        true,
+    );
+    send(&mut context, &[ix.clone()], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &translator_ata).await, bounty);
+
+    let annotation_address =
+        pda::find_annotation_address(&dream_record, &translator.pubkey(), *b"es").0;
+    let annotation: DreamAnnotation = fetch(&mut context, &annotation_address).await;
+    assert!(annotation.approved);
+    assert_eq!(annotation.bounty_paid, bounty);
+    assert_eq!(annotation.cid, "bafytranslation");
+    let bounty_address = pda::find_translation_bounty_address(&dream_record, *b"es").0;
+    let bounty_account: TranslationBounty = fetch(&mut context, &bounty_address).await;
+    assert_eq!(bounty_account.paid_to, annotation_address);
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::AnnotationAlreadyApproved);
+}
+
+// ===================== PREDICTION MARKETS =====================
+
+/// Give `creator` a profile created at `account_created_at` with
+/// `validated_dreams`, to open markets without recording and validating dreams.
+fn set_creator_profile(
+    context: &mut ProgramTestContext,
+    creator: &Pubkey,
+    account_created_at: i64,
+    validated_dreams: u32,
+) {
+    let profile = DreamerProfile {
+        dreamer: *creator,
+        interface_count: 0,
+        legacy_migrated: false,
+        legacy_dream_count: 0,
+        legacy_tokens_earned: 0,
+        last_active_day: 0,
+        research_consent: false,
+        reputation: 0,
+        account_created_at,
+        validated_dreams,
+    };
+    let mut data = DreamerProfile::DISCRIMINATOR.to_vec();
+    data.extend(profile.try_to_vec().unwrap());
+    let account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: dream_mind_lucid::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&pda::find_dreamer_profile_address(creator).0, &account.into());
+}
+
+#[tokio::test]
+async fn create_market_requires_an_established_profile() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &payer).await;
+    let smind_mint = create_mint(&mut context, &treasury).await;
+    let lucid_mint = create_mint(&mut context, &treasury).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let now = cluster_time(&mut context).await;
+    let ix = instructions::create_market(&payer, &dream_mint, 0, [3u8; 32], 2, now + 60);
+    // A profile made today, even with a validated dream
+    set_creator_profile(&mut context, &payer, now, MIN_ESTABLISHED_VALIDATED_DREAMS);
+    let result = send(&mut context, &[ix.clone()], &[]).await;
+    assert_dream_error(result, DreamError::DreamerNotEstablished);
+
+    // Old enough, but nothing validated yet
+    let created_at = now - MIN_ESTABLISHED_ACCOUNT_AGE_SECS;
+    set_creator_profile(&mut context, &payer, created_at, MIN_ESTABLISHED_VALIDATED_DREAMS - 1);
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix.clone()], &[]).await;
+    assert_dream_error(result, DreamError::DreamerNotEstablished);
+
+    set_creator_profile(&mut context, &payer, created_at, MIN_ESTABLISHED_VALIDATED_DREAMS);
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let market: PredictionMarket =
+        fetch(&mut context, &pda::find_market_address(&payer, 0).0).await;
+    assert_eq!(market.creator, payer);
+}
+
+#[tokio::test]
+async fn prediction_market_pays_winners_pro_rata() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &payer).await;
+    let smind_mint = create_mint(&mut context, &treasury).await;
+    let lucid_mint = create_mint(&mut context, &treasury).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    let insurance_ix = instructions::set_insurance_fee_share(&payer, &dream_mint, 0);
+    let validator_rewards_ix = instructions::set_validator_rewards(&payer, &dream_mint, 0, 0);
+    send(&mut context, &[ix, insurance_ix, validator_rewards_ix], &[]).await.unwrap();
+
+    set_creator_profile(&mut context, &payer, 1, MIN_ESTABLISHED_VALIDATED_DREAMS);
+    let closes_at = cluster_time(&mut context).await + 60;
+    let ix = instructions::create_market(&payer, &dream_mint, 0, [3u8; 32], 2, closes_at);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let market = pda::find_market_address(&payer, 0).0;
+
+    // One bettor on the losing outcome, two splitting the winning one 1:3
+    let bets = [(0u8, 400 * TOKEN_UNIT), (1, 100 * TOKEN_UNIT), (1, 300 * TOKEN_UNIT)];
+    let mut bettors = Vec::new();
+    for (outcome, amount) in bets {
+        let bettor = Keypair::new();
+        fund(&mut context, &bettor.pubkey()).await;
+        let ata = create_ata(&mut context, &bettor.pubkey(), &dream_mint).await;
+        let mint_ix = spl_token_2022::instruction::mint_to(
+            &spl_token_2022::ID,
+            &dream_mint,
+            &ata,
+            &payer,
+            &[],
+            amount,
+        )
+        .unwrap();
+        send(&mut context, &[mint_ix], &[]).await.unwrap();
+        let ix = instructions::buy_outcome(&bettor.pubkey(), &dream_mint, &market, outcome, amount);
+        send(&mut context, &[ix], &[&bettor]).await.unwrap();
+        bettors.push((bettor, ata));
+    }
+
+    let ix = instructions::resolve_market(&payer, &market, 1);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::MarketStillOpen);
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    context.warp_to_slot(clock.slot + 2).unwrap();
+    clock.unix_timestamp = closes_at;
+    context.set_sysvar(&clock);
+    let ix = instructions::resolve_market(&payer, &market, 1);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let state: PredictionMarket = fetch(&mut context, &market).await;
+    let fee = |amount: u64| amount * MARKET_FEE_BPS / 10_000;
+    let total_pool = 800 * TOKEN_UNIT - fee(400 * TOKEN_UNIT) - fee(100 * TOKEN_UNIT) - fee(300 * TOKEN_UNIT);
+    assert!(state.resolved);
+    assert_eq!(state.total_pool(), total_pool);
+    let treasury_dream =
+        get_associated_token_address_with_program_id(&treasury, &dream_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &treasury_dream).await, state.fees_collected);
+
+    for (bettor, _) in &bettors {
+        let ix = instructions::claim_market_winnings(&bettor.pubkey(), &dream_mint, &market, false);
+        send(&mut context, &[ix], &[bettor]).await.unwrap();
+    }
+    let winning_pool = state.outcome_pools[1];
+    for ((bettor, ata), share) in bettors.iter().zip([0, 100 * TOKEN_UNIT, 300 * TOKEN_UNIT]) {
+        let expected = ((share - fee(share)) as u128 * total_pool as u128 / winning_pool as u128) as u64;
+        assert_eq!(token_balance(&mut context, ata).await, expected);
+        let position: MarketPosition = fetch(
+            &mut context,
+            &pda::find_market_position_address(&market, &bettor.pubkey()).0,
+        )
+        .await;
+        assert!(position.claimed);
+    }
+}
+
+#[tokio::test]
+async fn large_market_winnings_are_streamed() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &payer).await;
+    let smind_mint = create_mint(&mut context, &treasury).await;
+    let lucid_mint = create_mint(&mut context, &treasury).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    let insurance_ix = instructions::set_insurance_fee_share(&payer, &dream_mint, 0);
+    let validator_rewards_ix = instructions::set_validator_rewards(&payer, &dream_mint, 0, 0);
+    send(&mut context, &[ix, insurance_ix, validator_rewards_ix], &[]).await.unwrap();
+
+    set_creator_profile(&mut context, &payer, 1, MIN_ESTABLISHED_VALIDATED_DREAMS);
+    let closes_at = cluster_time(&mut context).await + 60;
+    let ix = instructions::create_market(&payer, &dream_mint, 0, [3u8; 32], 2, closes_at);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let market = pda::find_market_address(&payer, 0).0;
+
+    let bettor = Keypair::new();
+    let amount = 2 * REWARD_STREAM_THRESHOLD;
+    fund(&mut context, &bettor.pubkey()).await;
+    let ata = create_ata(&mut context, &bettor.pubkey(), &dream_mint).await;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &dream_mint,
+        &ata,
+        &payer,
+        &[],
+        amount,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+    let ix = instructions::buy_outcome(&bettor.pubkey(), &dream_mint, &market, 0, amount);
+    send(&mut context, &[ix], &[&bettor]).await.unwrap();
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = closes_at;
+    context.set_sysvar(&clock);
+    let ix = instructions::resolve_market(&payer, &market, 0);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let ix = instructions::claim_market_winnings(&bettor.pubkey(), &dream_mint, &market, false);
+    let result = send(&mut context, &[ix], &[&bettor]).await;
+    assert_dream_error(result, DreamError::RewardStreamRequired);
+    let ix = instructions::claim_market_winnings(&bettor.pubkey(), &dream_mint, &market, true);
+    send(&mut context, &[ix], &[&bettor]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &ata).await, 0);
+
+    let stream_address = pda::find_reward_stream_address(&market, &bettor.pubkey()).0;
+    let stream: RewardStream = fetch(&mut context, &stream_address).await;
+    let winnings = amount - amount * MARKET_FEE_BPS / 10_000;
+    assert_eq!(stream.total, winnings);
+    assert_eq!(stream.end_at, stream.start_at + REWARD_STREAM_DURATION_SECS);
+
+    // Halfway through, half the winnings have vested
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = stream.start_at + REWARD_STREAM_DURATION_SECS / 2;
+    context.set_sysvar(&clock);
+    let ix = instructions::claim_streamed(&bettor.pubkey(), &dream_mint, &market);
+    send(&mut context, &[ix], &[&bettor]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &ata).await, winnings / 2);
+}
+
+// ===================== DREAM DUELS =====================
+
+#[tokio::test]
+async fn dream_duel_pays_higher_score_minus_fee() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &treasury).await;
+    let smind_mint = create_mint(&mut context, &treasury).await;
+    let lucid_mint = create_mint(&mut context, &payer).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let stake = 100 * TOKEN_UNIT;
+    let challenger = Keypair::new();
+    let opponent = Keypair::new();
+    let mut atas = Vec::new();
+    for dreamer in [&challenger, &opponent] {
+        fund(&mut context, &dreamer.pubkey()).await;
+        let ata = create_ata(&mut context, &dreamer.pubkey(), &lucid_mint).await;
+        let mint_ix = spl_token_2022::instruction::mint_to(
+            &spl_token_2022::ID,
+            &lucid_mint,
+            &ata,
+            &payer,
+            &[],
+            stake,
+        )
+        .unwrap();
+        send(&mut context, &[mint_ix], &[]).await.unwrap();
+        atas.push(ata);
+    }
+
+    let ix =
+        instructions::create_duel(&challenger.pubkey(), &lucid_mint, 0, &opponent.pubkey(), stake);
+    send(&mut context, &[ix], &[&challenger]).await.unwrap();
+    let duel = pda::find_duel_address(&challenger.pubkey(), 0).0;
+    let ix = instructions::accept_duel(&opponent.pubkey(), &lucid_mint, &duel);
+    send(&mut context, &[ix], &[&opponent]).await.unwrap();
+
+    // Scores can't be settled until both sides submit or the window closes
+    let ix = instructions::settle_duel(
+        &payer,
+        &lucid_mint,
+        &duel,
+        &challenger.pubkey(),
+        &opponent.pubkey(),
+        0,
+        0,
+    );
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::DuelStillOpen);
+
+    let now = cluster_time(&mut context).await;
+    for (dream_id, dreamer) in [&challenger, &opponent].into_iter().enumerate() {
+        let ix = instructions::record_dream(
+            &dreamer.pubkey(),
+            &dream_mint,
+            dream_id as u64,
+            [dream_id as u8; 32],
+            DreamMetadata::default(),
+            RecordDreamOptions::default(),
+            now,
+        );
+        send(&mut context, &[ix], &[dreamer]).await.unwrap();
+        let record = pda::find_dream_record_address(&dreamer.pubkey(), dream_id as u64).0;
+        let ix = instructions::submit_duel_dream(&dreamer.pubkey(), &duel, &record);
+        send(&mut context, &[ix], &[dreamer]).await.unwrap();
+    }
+
+    let ix = instructions::settle_duel(
+        &payer,
+        &lucid_mint,
+        &duel,
+        &challenger.pubkey(),
+        &opponent.pubkey(),
+        8,
+        5,
+    );
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let state: DreamDuel = fetch(&mut context, &duel).await;
+    assert_eq!(state.status, DuelStatus::Settled);
+    assert_eq!(state.winner, challenger.pubkey());
+    let fee = 2 * stake * DUEL_FEE_BPS / 10_000;
+    assert_eq!(token_balance(&mut context, &atas[0]).await, 2 * stake - fee);
+    assert_eq!(token_balance(&mut context, &atas[1]).await, 0);
+    let treasury_lucid =
+        get_associated_token_address_with_program_id(&treasury, &lucid_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &treasury_lucid).await, fee);
+}
+
+#[tokio::test]
+async fn grants_release_milestones_until_clawed_back() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let treasury = pda::find_treasury_address().0;
+    let dream_mint = create_mint(&mut context, &treasury).await;
+    let smind_mint = create_mint(&mut context, &treasury).await;
+    let lucid_mint = create_mint(&mut context, &payer).await;
+    let ix = instructions::initialize_tokens(&payer, &dream_mint, &smind_mint, &lucid_mint);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let treasury_lucid = create_ata(&mut context, &treasury, &lucid_mint).await;
+    let funds = 1_000 * TOKEN_UNIT;
+    let mint_ix = spl_token_2022::instruction::mint_to(
+        &spl_token_2022::ID,
+        &lucid_mint,
+        &treasury_lucid,
+        &payer,
+        &[],
+        funds,
+    )
+    .unwrap();
+    send(&mut context, &[mint_ix], &[]).await.unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    let too_many = vec![TOKEN_UNIT; MAX_GRANT_MILESTONES + 1];
+    for milestones in [vec![], vec![TOKEN_UNIT, 0], too_many] {
+        let ix = instructions::create_grant(&payer, &lucid_mint, &recipient, 0, milestones);
+        let result = send(&mut context, &[ix], &[]).await;
+        assert_dream_error(result, DreamError::InvalidGrantMilestones);
+    }
+    let milestones = vec![100 * TOKEN_UNIT, 200 * TOKEN_UNIT, 300 * TOKEN_UNIT];
+    let ix = instructions::create_grant(&payer, &lucid_mint, &recipient, 0, milestones);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let grant_address = pda::find_grant_address(&recipient, 0).0;
+    let vault = pda::find_grant_vault_address(&grant_address).0;
+    assert_eq!(token_balance(&mut context, &vault).await, 600 * TOKEN_UNIT);
+    assert_eq!(token_balance(&mut context, &treasury_lucid).await, funds - 600 * TOKEN_UNIT);
+
+    // The first milestone is paid, then the rest goes back to the treasury
+    let approve = instructions::approve_milestone(&payer, &lucid_mint, &recipient, 0);
+    send(&mut context, &[approve.clone()], &[]).await.unwrap();
+    let recipient_lucid =
+        get_associated_token_address_with_program_id(&recipient, &lucid_mint, &spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, &recipient_lucid).await, 100 * TOKEN_UNIT);
+    let claw_back = instructions::claw_back(&payer, &lucid_mint, &recipient, 0);
+    send(&mut context, &[claw_back], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &vault).await, 0);
+    assert_eq!(token_balance(&mut context, &treasury_lucid).await, funds - 100 * TOKEN_UNIT);
+    let grant: Grant = fetch(&mut context, &grant_address).await;
+    assert_eq!((grant.status, grant.milestones_paid), (GrantStatus::ClawedBack, 1));
+    assert_eq!(grant.released, 100 * TOKEN_UNIT);
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[approve], &[]).await;
+    assert_dream_error(result, DreamError::GrantNotActive);
+
+    // A fully paid grant is completed and can't be clawed back
+    let ix = instructions::create_grant(&payer, &lucid_mint, &recipient, 1, vec![50 * TOKEN_UNIT]);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let ix = instructions::approve_milestone(&payer, &lucid_mint, &recipient, 1);
+    send(&mut context, &[ix], &[]).await.unwrap();
+    let grant: Grant = fetch(&mut context, &pda::find_grant_address(&recipient, 1).0).await;
+    assert_eq!(grant.status, GrantStatus::Completed);
+    assert_eq!(token_balance(&mut context, &recipient_lucid).await, 150 * TOKEN_UNIT);
+    let ix = instructions::claw_back(&payer, &lucid_mint, &recipient, 1);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::GrantNotActive);
+}
+
+// ===================== LEGACY MIGRATION =====================
+
+fn add_legacy_storage(program_test: &mut ProgramTest, dreamer: &Pubkey, initialized: bool) -> Pubkey {
+    let address = Pubkey::new_unique();
+    let storage = DreamStorage {
+        dreamer: *dreamer,
+        dream_count: 3,
+        total_tokens_earned: 3 * DREAM_REWARD_PER_RECORD,
+        is_initialized: initialized,
+    };
+    program_test.add_account(
+        address,
+        Account {
+            lamports: 1_000_000_000,
+            data: storage.try_to_vec().unwrap(),
+            owner: LEGACY_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    address
+}
+
+#[tokio::test]
+async fn migrate_legacy_storage_imports_counters_once() {
+    let dreamer = Keypair::new();
+    let mut program_test = program_test();
+    let legacy = add_legacy_storage(&mut program_test, &dreamer.pubkey(), true);
+    let mut context = program_test.start_with_context().await;
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    let ix = instructions::migrate_legacy_storage(&dreamer.pubkey(), &legacy);
+    send(&mut context, &[ix.clone()], &[&dreamer]).await.unwrap();
+
+    let profile: DreamerProfile =
+        fetch(&mut context, &pda::find_dreamer_profile_address(&dreamer.pubkey()).0).await;
+    assert!(profile.legacy_migrated);
+    assert_eq!(profile.legacy_dream_count, 3);
+
+    // Fresh blockhash so the retry isn't deduplicated as the same transaction
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::LegacyAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrate_dreamer_profile_grows_v1_profiles_once() {
+    let dreamer = Keypair::new().pubkey();
+    let profile_address = pda::find_dreamer_profile_address(&dreamer).0;
+    let legacy = DreamerProfileV1 {
+        dreamer,
+        interface_count: 2,
+        legacy_migrated: false,
+        legacy_dream_count: 0,
+        legacy_tokens_earned: 0,
+        last_active_day: 19_000,
+        research_consent: true,
+        reputation: 300,
+    };
+    let mut data = DreamerProfile::DISCRIMINATOR.to_vec();
+    data.extend(legacy.try_to_vec().unwrap());
+    let mut program_test = program_test();
+    program_test.add_account(
+        profile_address,
+        Account {
+            lamports: 1_000_000,
+            data,
+            owner: dream_mind_lucid::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+
+    let ix = instructions::migrate_dreamer_profile(&payer, &dreamer);
+    send(&mut context, &[ix.clone()], &[]).await.unwrap();
+
+    let now = cluster_time(&mut context).await;
+    let profile: DreamerProfile = fetch(&mut context, &profile_address).await;
+    assert_eq!((profile.interface_count, profile.last_active_day), (2, 19_000));
+    assert!(profile.research_consent);
+    assert_eq!(profile.reputation, 300);
+    assert_eq!((profile.account_created_at, profile.validated_dreams), (now, 0));
+    let account = context.banks_client.get_account(profile_address).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), 8 + std::mem::size_of::<DreamerProfile>());
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::DreamerProfileCurrent);
+}
+
+#[tokio::test]
+async fn migrate_dream_record_grows_pre_session_records_once() {
+    let dreamer = Keypair::new();
+    let record_address = pda::find_dream_record_address(&dreamer.pubkey(), 0).0;
+    let record = DreamRecord {
+        id: 0,
+        dreamer: dreamer.pubkey(),
+        content_hash: [7u8; 32],
+        timestamp: 1_700_000_000,
+        token_reward: DREAM_REWARD_PER_RECORD,
+        mev_protected: false,
+        origin_chain: 0,
+        metadata: DreamMetadata::default(),
+        session: Pubkey::default(),
+    };
+    // Records written before sessions existed end before `session`
+    let mut data = DreamRecord::DISCRIMINATOR.to_vec();
+    data.extend(record.try_to_vec().unwrap());
+    data.truncate(data.len() - 32);
+    assert_eq!(data.len(), 104);
+    let mut program_test = program_test();
+    program_test.add_account(
+        record_address,
+        Account {
+            lamports: 1_000_000,
+            data,
+            owner: dream_mind_lucid::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    let extras = DreamRecordExtras {
+        tags: vec![],
+        annotations: vec![Pubkey::new_unique()],
+    };
+    let set_extras = instructions::set_dream_extras(&dreamer.pubkey(), &record_address, extras);
+    assert!(send(&mut context, &[set_extras.clone()], &[&dreamer]).await.is_err());
+
+    let ix = instructions::migrate_dream_record(&payer, &record_address);
+    send(&mut context, &[ix.clone()], &[]).await.unwrap();
+    let account = context.banks_client.get_account(record_address).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), DreamRecord::EXTRAS_OFFSET);
+    let migrated = DreamRecord::try_deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!((migrated.dreamer, migrated.content_hash), (dreamer.pubkey(), [7u8; 32]));
+    assert_eq!(migrated.session, Pubkey::default());
+
+    // Migrated records load in instructions again, and extras go after `session`
+    context.get_new_latest_blockhash().await.unwrap();
+    send(&mut context, &[set_extras], &[&dreamer]).await.unwrap();
+    let account = context.banks_client.get_account(record_address).await.unwrap().unwrap();
+    let extras = DreamRecord::extras(&account.data).unwrap();
+    assert_eq!(extras.annotations.len(), 1);
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::DreamRecordCurrent);
+}
+
+#[tokio::test]
+async fn migrate_legacy_storage_rejects_foreign_storage() {
+    let dreamer = Keypair::new();
+    let mut program_test = program_test();
+    let legacy = add_legacy_storage(&mut program_test, &Pubkey::new_unique(), true);
+    let mut context = program_test.start_with_context().await;
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    let ix = instructions::migrate_legacy_storage(&dreamer.pubkey(), &legacy);
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidLegacyStorage);
+}
+
+#[tokio::test]
+async fn migrate_legacy_storage_rejects_uninitialized_storage() {
+    let dreamer = Keypair::new();
+    let mut program_test = program_test();
+    let legacy = add_legacy_storage(&mut program_test, &dreamer.pubkey(), false);
+    let mut context = program_test.start_with_context().await;
+    fund(&mut context, &dreamer.pubkey()).await;
+
+    let ix = instructions::migrate_legacy_storage(&dreamer.pubkey(), &legacy);
+    let result = send(&mut context, &[ix], &[&dreamer]).await;
+    assert_dream_error(result, DreamError::InvalidLegacyStorage);
+}
+
+// ===================== CROSS-CHAIN MIRRORING =====================
+
+const EVM_CHAIN: u16 = 2;
+const EVM_EMITTER: [u8; 32] = [9u8; 32];
+
+/// Add a `PostedVAA` account as the core bridge would leave it after verifying
+/// the guardian signatures.
+fn add_posted_vaa(
+    program_test: &mut ProgramTest,
+    emitter_address: [u8; 32],
+    sequence: u64,
+    payload: Vec<u8>,
+) -> Pubkey {
+    let vaa = PostedVaa {
+        vaa_version: 1,
+        consistency_level: 1,
+        vaa_time: 1_700_000_000,
+        vaa_signature_account: Pubkey::new_unique(),
+        submission_time: 1_700_000_000,
+        nonce: 0,
+        sequence,
+        emitter_chain: EVM_CHAIN,
+        emitter_address,
+        payload,
+    };
+    let address = find_posted_vaa_address(&vaa.body_hash()).0;
+    let mut data = PostedVaa::MAGIC.to_vec();
+    data.extend(vaa.try_to_vec().unwrap());
+    program_test.add_account(
+        address,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: WORMHOLE_CORE_BRIDGE_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    address
+}
+
+#[tokio::test]
+async fn record_mirrored_dream_creates_record_once() {
+    let dreamer = Pubkey::new_unique();
+    let mirror = DreamMirror {
+        dream_id: 41,
+        dreamer: dreamer.to_bytes(),
+        content_hash: [5u8; 32],
+    };
+    let mut program_test = program_test();
+    let posted_vaa = add_posted_vaa(&mut program_test, EVM_EMITTER, 7, mirror.encode());
+    let mut context = program_test.start_with_context().await;
+    initialize(&mut context).await;
+    let payer = context.payer.pubkey();
+
+    let ix = instructions::register_foreign_emitter(&payer, EVM_CHAIN, EVM_EMITTER);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let ix = instructions::record_mirrored_dream(&payer, &dreamer, 0, &posted_vaa, EVM_CHAIN, 7);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let record: DreamRecord = fetch(&mut context, &pda::find_dream_record_address(&dreamer, 0).0).await;
+    assert_eq!(record.content_hash, [5u8; 32]);
+    assert_eq!(record.origin_chain, EVM_CHAIN);
+    assert_eq!(record.token_reward, 0);
+
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert_eq!(treasury.total_dreams_recorded, 1);
+    assert_eq!(treasury.total_dreams_mirrored, 1);
+
+    // The receipt for (chain, sequence) already exists
+    let ix = instructions::record_mirrored_dream(&payer, &dreamer, 1, &posted_vaa, EVM_CHAIN, 7);
+    assert!(send(&mut context, &[ix], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn record_mirrored_dream_rejects_unregistered_emitter() {
+    let dreamer = Pubkey::new_unique();
+    let mirror = DreamMirror {
+        dream_id: 41,
+        dreamer: dreamer.to_bytes(),
+        content_hash: [5u8; 32],
+    };
+    let mut program_test = program_test();
+    let posted_vaa = add_posted_vaa(&mut program_test, [8u8; 32], 7, mirror.encode());
+    let mut context = program_test.start_with_context().await;
+    initialize(&mut context).await;
+    let payer = context.payer.pubkey();
+
+    let ix = instructions::register_foreign_emitter(&payer, EVM_CHAIN, EVM_EMITTER);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let ix = instructions::record_mirrored_dream(&payer, &dreamer, 0, &posted_vaa, EVM_CHAIN, 7);
+    let result = send(&mut context, &[ix], &[]).await;
+    assert_dream_error(result, DreamError::InvalidForeignEmitter);
+}
+
+// ===================== CROSS-CHAIN GOVERNANCE =====================
+
+const REMOTE_ORIGIN: u32 = 42161;
+const REMOTE_SENDER: [u8; 32] = [3u8; 32];
+
+/// Initialize the treasury, a message adapter delivered by `delivery`, and a
+/// trusted remote on `REMOTE_ORIGIN`.
+async fn setup_message_adapter(context: &mut ProgramTestContext, delivery: &Keypair) {
+    initialize(context).await;
+    let authority = context.payer.pubkey();
+    let ixs = [
+        instructions::configure_message_adapter(&authority, &delivery.pubkey()),
+        instructions::set_trusted_remote(&authority, REMOTE_ORIGIN, REMOTE_SENDER),
+    ];
+    send(context, &ixs, &[]).await.unwrap();
+}
+
+fn mev_protection_message(enabled: bool) -> Vec<u8> {
+    GovernanceAction::SetMevProtection { enabled }.try_to_vec().unwrap()
+}
+
+#[tokio::test]
+async fn receive_message_applies_action_once() {
+    let delivery = Keypair::new();
+    let mut context = program_test().start_with_context().await;
+    setup_message_adapter(&mut context, &delivery).await;
+
+    let message = mev_protection_message(false);
+    let ix = instructions::receive_message(&delivery.pubkey(), REMOTE_ORIGIN, REMOTE_SENDER, 0, message);
+    send(&mut context, &[ix.clone()], &[&delivery]).await.unwrap();
+
+    let treasury: Treasury = fetch(&mut context, &pda::find_treasury_address().0).await;
+    assert!(!treasury.mev_protection_enabled);
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let result = send(&mut context, &[ix], &[&delivery]).await;
+    assert_dream_error(result, DreamError::InvalidRemoteMessage);
+}
+
+#[tokio::test]
+async fn receive_message_rejects_untrusted_sender() {
+    let delivery = Keypair::new();
+    let mut context = program_test().start_with_context().await;
+    setup_message_adapter(&mut context, &delivery).await;
+
+    let message = mev_protection_message(false);
+    let ix = instructions::receive_message(&delivery.pubkey(), REMOTE_ORIGIN, [4u8; 32], 0, message);
+    let result = send(&mut context, &[ix], &[&delivery]).await;
+    assert_dream_error(result, DreamError::UntrustedRemote);
+}
+
+#[tokio::test]
+async fn receive_message_rejects_other_delivery_authority() {
+    let delivery = Keypair::new();
+    let impostor = Keypair::new();
+    let mut context = program_test().start_with_context().await;
+    setup_message_adapter(&mut context, &delivery).await;
+
+    let message = mev_protection_message(false);
+    let ix = instructions::receive_message(&impostor.pubkey(), REMOTE_ORIGIN, REMOTE_SENDER, 0, message);
+    let result = send(&mut context, &[ix], &[&impostor]).await;
+    assert_dream_error(result, DreamError::InvalidDeliveryAuthority);
+}
+
+// ===================== TOKENOMICS INVARIANTS =====================
+
+/// Dreamers the random operations act on.
+const INVARIANT_DREAMERS: usize = 3;
+
+#[derive(Clone, Debug)]
+enum TokenomicsOp {
+    Record { dreamer: usize },
+    /// Pins the dream first if it isn't yet.
+    Validate { dream: usize, score: u8 },
+    /// Converts the dream's whole reward to SMIND.
+    Claim { dream: usize },
+    Stake { dreamer: usize, share_bps: u16 },
+    Unstake { stake: usize },
+}
+
+fn tokenomics_op() -> impl Strategy<Value = TokenomicsOp> {
+    prop_oneof![
+        (0..INVARIANT_DREAMERS).prop_map(|dreamer| TokenomicsOp::Record { dreamer }),
+        (any::<usize>(), 1..=MAX_VALIDATION_SCORE)
+            .prop_map(|(dream, score)| TokenomicsOp::Validate { dream, score }),
+        any::<usize>().prop_map(|dream| TokenomicsOp::Claim { dream }),
+        (0..INVARIANT_DREAMERS, 1..=10_000u16)
+            .prop_map(|(dreamer, share_bps)| TokenomicsOp::Stake { dreamer, share_bps }),
+        any::<usize>().prop_map(|stake| TokenomicsOp::Unstake { stake }),
+    ]
+}
+
+/// What the operations so far should have left on chain. Operations the
+/// program rejects leave it unchanged.
+#[derive(Default)]
+struct TokenomicsModel {
+    dreamers: Vec<Keypair>,
+    next_dream_id: Vec<u64>,
+    /// (dreamer, record, content hash, pinned)
+    dreams: Vec<(usize, Pubkey, [u8; 32], bool)>,
+    leaves: Vec<[u8; 32]>,
+    /// (dreamer, amount, staked_at) by leaf index; `None` once unstaked
+    stakes: Vec<Option<(usize, u64, i64)>>,
+}
+
+async fn mint_supply(context: &mut ProgramTestContext, mint: &Pubkey) -> u64 {
+    let account = context.banks_client.get_account(*mint).await.unwrap().unwrap();
+    StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+        .unwrap()
+        .base
+        .supply
+}
+
+/// `owner`'s balance of `mint`, 0 before their ATA exists.
+async fn ata_balance(context: &mut ProgramTestContext, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    let ata = get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::ID);
+    match context.banks_client.get_account(ata).await.unwrap() {
+        Some(_) => token_balance(context, &ata).await,
+        None => 0,
+    }
+}
+
+async fn assert_tokenomics_invariants(
+    context: &mut ProgramTestContext,
+    dream_mint: &Pubkey,
+    smind_mint: &Pubkey,
+    model: &TokenomicsModel,
+) {
+    // Supply caps
+    let dream_supply = mint_supply(context, dream_mint).await;
+    let smind_supply = mint_supply(context, smind_mint).await;
+    assert!(dream_supply <= DREAM_TOTAL_SUPPLY);
+    assert!(smind_supply <= SMIND_TOTAL_SUPPLY);
+
+    // DREAM is only minted into the reward pool and only burned by claims, and
+    // never more is paid out of the pool than was put in
+    let pool: RewardPool = fetch(context, &pda::find_reward_pool_address().0).await;
+    let rates: RewardConversionRates =
+        fetch(context, &pda::find_conversion_rates_address().0).await;
+    assert_eq!(dream_supply + rates.total_dream_burned, pool.total_minted);
+    let treasury: Treasury = fetch(context, &pda::find_treasury_address().0).await;
+    assert!(treasury.total_rewards_distributed <= pool.total_minted);
+    let mut dream_held = token_balance(context, &pda::find_reward_pool_vault_address().0).await;
+    for dreamer in &model.dreamers {
+        dream_held += ata_balance(context, &dreamer.pubkey(), dream_mint).await;
+    }
+    assert_eq!(dream_held, dream_supply);
+
+    // Stake conservation: the tree, its vault and the open stakes agree, and
+    // every SMIND is either staked or back with a dreamer
+    let staked: u64 = model.stakes.iter().flatten().map(|(_, amount, _)| amount).sum();
+    let tree: CompressedStakeTree =
+        fetch(context, &pda::find_compressed_stake_tree_address().0).await;
+    assert_eq!(tree.root, stake_tree::root(&model.leaves));
+    assert_eq!(tree.total_staked, staked);
+    assert_eq!(tree.active_stakes, model.stakes.iter().flatten().count() as u64);
+    let stake_vault = pda::find_compressed_stake_vault_address().0;
+    let mut smind_held = token_balance(context, &stake_vault).await;
+    assert_eq!(smind_held, staked);
+    for dreamer in &model.dreamers {
+        smind_held += ata_balance(context, &dreamer.pubkey(), smind_mint).await;
+    }
+    assert_eq!(smind_held, smind_supply);
+
+    // Every obligation the treasury tracks is still covered
+    let reconcile = instructions::reconcile_treasury(&context.payer.pubkey());
+    send(context, &[reconcile], &[]).await.unwrap();
+    let health: TreasuryHealth = fetch(context, &pda::find_treasury_health_address().0).await;
+    assert!(health.healthy);
+}
+
+async fn run_tokenomics_ops(ops: Vec<TokenomicsOp>) {
+    let mut context = program_test().start_with_context().await;
+    let dream_mint = initialize(&mut context).await;
+    let authority = context.payer.pubkey();
+    let smind_mint = fetch::<Treasury>(&mut context, &pda::find_treasury_address().0)
+        .await
+        .smind_mint;
+    let oracle = Keypair::new();
+    let setup = [
+        instructions::set_conversion_rates(&authority, MAX_REWARD_CONVERSION_RATE_BPS, 0),
+        instructions::initialize_compressed_stake_tree(&authority, &smind_mint),
+        instructions::set_pinning_oracle(&authority, &oracle.pubkey(), true),
+    ];
+    send(&mut context, &setup, &[]).await.unwrap();
+
+    let mut model = TokenomicsModel::default();
+    for _ in 0..INVARIANT_DREAMERS {
+        let dreamer = Keypair::new();
+        fund(&mut context, &dreamer.pubkey()).await;
+        model.dreamers.push(dreamer);
+        model.next_dream_id.push(0);
+    }
+    assert_tokenomics_invariants(&mut context, &dream_mint, &smind_mint, &model).await;
+
+    for op in ops {
+        match op {
+            TokenomicsOp::Record { dreamer } => {
+                let id = model.next_dream_id[dreamer];
+                let content_hash = hash(&[dreamer as u8, id as u8]).to_bytes();
+                let now = cluster_time(&mut context).await;
+                let ix = instructions::record_dream(
+                    &model.dreamers[dreamer].pubkey(),
+                    &dream_mint,
+                    id,
+                    content_hash,
+                    DreamMetadata::default(),
+                    RecordDreamOptions::default(),
+                    now,
+                );
+                let signer = &model.dreamers[dreamer];
+                if send(&mut context, &[ix], &[signer]).await.is_ok() {
+                    let record = pda::find_dream_record_address(&signer.pubkey(), id).0;
+                    model.dreams.push((dreamer, record, content_hash, false));
+                    model.next_dream_id[dreamer] += 1;
+                }
+            }
+            TokenomicsOp::Validate { dream, score } => {
+                if model.dreams.is_empty() {
+                    continue;
+                }
+                let index = dream % model.dreams.len();
+                let (dreamer, record, content_hash, pinned) = model.dreams[index];
+                let mut ixs = Vec::new();
+                if !pinned {
+                    ixs.extend(pin_instructions(&authority, &oracle, &record, &content_hash));
+                }
+                ixs.push(instructions::validate_dream(
+                    &authority,
+                    &model.dreamers[dreamer].pubkey(),
+                    &dream_mint,
+                    &record,
+                    None,
+                    score,
+                ));
+                if send(&mut context, &ixs, &[]).await.is_ok() {
+                    model.dreams[index].3 = true;
+                }
+            }
+            TokenomicsOp::Claim { dream } => {
+                if model.dreams.is_empty() {
+                    continue;
+                }
+                let (dreamer, record, _, _) = model.dreams[dream % model.dreams.len()];
+                let reward = fetch::<DreamRecord>(&mut context, &record).await.token_reward;
+                let signer = &model.dreamers[dreamer];
+                let ix = instructions::claim_reward_as(
+                    &signer.pubkey(),
+                    &dream_mint,
+                    &smind_mint,
+                    &record,
+                    RewardToken::Smind,
+                    reward,
+                );
+                let _ = send(&mut context, &[ix], &[signer]).await;
+            }
+            TokenomicsOp::Stake { dreamer, share_bps } => {
+                let signer = &model.dreamers[dreamer];
+                let balance = ata_balance(&mut context, &signer.pubkey(), &smind_mint).await;
+                let amount = balance * u64::from(share_bps) / 10_000;
+                if amount == 0 {
+                    continue;
+                }
+                let now = cluster_time(&mut context).await;
+                let ix = instructions::compressed_stake(&signer.pubkey(), &smind_mint, amount);
+                if send(&mut context, &[ix], &[signer]).await.is_ok() {
+                    model.leaves.push(stake_tree::stake_leaf(&signer.pubkey(), amount, now));
+                    model.stakes.push(Some((dreamer, amount, now)));
+                }
+            }
+            TokenomicsOp::Unstake { stake } => {
+                let open: Vec<usize> = (0..model.stakes.len())
+                    .filter(|&leaf_index| model.stakes[leaf_index].is_some())
+                    .collect();
+                if open.is_empty() {
+                    continue;
+                }
+                let leaf_index = open[stake % open.len()];
+                let (dreamer, amount, staked_at) = model.stakes[leaf_index].unwrap();
+                let signer = &model.dreamers[dreamer];
+                let ix = instructions::compressed_unstake(
+                    &signer.pubkey(),
+                    &smind_mint,
+                    leaf_index as u64,
+                    amount,
+                    staked_at,
+                    stake_tree::proof(&model.leaves, leaf_index),
+                );
+                if send(&mut context, &[ix], &[signer]).await.is_ok() {
+                    model.leaves[leaf_index] = stake_tree::EMPTY_LEAF;
+                    model.stakes[leaf_index] = None;
+                }
+            }
+        }
+        // Identical retries would otherwise be deduplicated
+        context.get_new_latest_blockhash().await.unwrap();
+        assert_tokenomics_invariants(&mut context, &dream_mint, &smind_mint, &model).await;
+    }
+}
+
+proptest! {
+    // Each case boots a fresh program test, so keep the case count small
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn tokenomics_invariants_hold_across_random_operations(
+        ops in prop::collection::vec(tokenomics_op(), 1..16),
+    ) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(run_tokenomics_ops(ops));
+    }
+}
@@ -0,0 +1,154 @@
+#!/usr/bin/env python3
+"""
+IDL Builder
+===========
+Builds the Anchor IDLs of the workspace's programs into idl/.
+
+`anchor idl build` only collects what the program crate itself defines. Our
+errors live in dream-mind-common and our events in dream-mind-state, so this
+also runs those crates' IDL print tests (they have an `idl-build` feature too)
+and merges their sections in. Each program gets the shared errors and the
+events it emits.
+
+Usage:
+    python3 scripts/build_idl.py                 # write idl/*.json
+    python3 scripts/build_idl.py --check         # fail if idl/ is stale
+    python3 scripts/build_idl.py --program dream_mind_lucid
+"""
+
+import argparse
+import json
+import os
+import re
+import subprocess
+import sys
+from pathlib import Path
+from typing import Dict, List, Tuple
+
+ROOT = Path(__file__).resolve().parent.parent
+IDL_DIR = ROOT / "idl"
+
+PROGRAMS = {
+    "dream_mind_lucid": ROOT / "packages/core/programs/dream-mind-lucid",
+    "oneirobot_nft": ROOT / "solana/programs",
+}
+
+SHARED_CRATES = [
+    ROOT / "crates/dream-mind-common",
+    ROOT / "crates/dream-mind-state",
+]
+
+SECTION = re.compile(r"--- IDL begin (\w+) ---\n(.*?)\n--- IDL end \1 ---", re.S)
+EMIT = re.compile(r"\bemit(?:_cpi)?!\(\s*(\w+)")
+
+
+def print_sections(crate_dir: Path, features: List[str]) -> List[Tuple[str, object]]:
+    """Run a crate's `__anchor_private_print_idl_*` tests and parse their output"""
+    env = dict(os.environ, ANCHOR_IDL_BUILD_PROGRAM_PATH=str(crate_dir))
+    cmd = [
+        "cargo", "test", "--features", ",".join(["idl-build"] + features),
+        "__anchor_private_print_idl", "--", "--show-output", "--quiet",
+    ]
+    result = subprocess.run(cmd, cwd=crate_dir, env=env, capture_output=True, text=True)
+    if result.returncode != 0:
+        sys.stderr.write(result.stderr)
+        raise SystemExit(f"❌ IDL build failed in {crate_dir.relative_to(ROOT)}")
+    return [(name, json.loads(body)) for name, body in SECTION.findall(result.stdout)]
+
+
+def emitted_events(program_dir: Path) -> set:
+    """Event names the program's sources pass to `emit!`"""
+    names = set()
+    for source in (program_dir / "src").rglob("*.rs"):
+        names.update(EMIT.findall(source.read_text()))
+    return names
+
+
+def add_unique(items: List[dict], new: List[dict]) -> None:
+    seen = {item["name"] for item in items}
+    items.extend(item for item in new if item["name"] not in seen)
+    seen.update(item["name"] for item in new)
+
+
+def strip_module_paths(value, renames: Dict[str, str]):
+    """Shorten `crate::module::Type` names the way `anchor idl build` does"""
+    if isinstance(value, dict):
+        return {key: strip_module_paths(item, renames) for key, item in value.items()}
+    if isinstance(value, list):
+        return [strip_module_paths(item, renames) for item in value]
+    if isinstance(value, str):
+        return renames.get(value, value)
+    return value
+
+
+def build_idl(program_dir: Path, features: List[str], shared: List[Tuple[str, object]]) -> Dict:
+    idl = {}
+    address = ""
+    for name, value in print_sections(program_dir, features):
+        if name == "program":
+            idl = value
+        elif name == "address":
+            address = json.loads(value)
+
+    idl["address"] = address
+    errors, events, types = [], [], idl.get("types", [])
+    emitted = emitted_events(program_dir)
+    for name, value in shared:
+        if name == "errors":
+            add_unique(errors, value)
+        elif name == "event" and value["event"]["name"].rsplit("::", 1)[-1] in emitted:
+            add_unique(events, [value["event"]])
+            add_unique(types, value["types"])
+
+    for key, value in (("events", events), ("errors", errors), ("types", types)):
+        if value:
+            idl[key] = value
+    # Only shorten a path when no other type has the same last segment
+    paths = {item["name"] for item in types if "::" in item["name"]}
+    short = [path.rsplit("::", 1)[1] for path in paths]
+    renames = {path: path.rsplit("::", 1)[1] for path in paths
+               if short.count(path.rsplit("::", 1)[1]) == 1}
+    idl = strip_module_paths(idl, renames)
+
+    # Keep anchor's key order so diffs against `anchor build` output stay small
+    order = ["address", "metadata", "docs", "instructions", "accounts", "events",
+             "errors", "types", "constants"]
+    return {key: idl[key] for key in order if key in idl}
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description="Build the workspace's Anchor IDLs into idl/")
+    parser.add_argument("--program", choices=sorted(PROGRAMS), action="append",
+                        help="only build this program (repeatable)")
+    parser.add_argument("--features", default="",
+                        help="extra cargo features, e.g. mainnet")
+    parser.add_argument("--check", action="store_true",
+                        help="compare against idl/ instead of writing it")
+    args = parser.parse_args()
+
+    features = [f for f in args.features.split(",") if f]
+    shared = []
+    for crate_dir in SHARED_CRATES:
+        shared += print_sections(crate_dir, [f for f in features if f in ("mainnet", "devnet")])
+
+    stale = []
+    IDL_DIR.mkdir(exist_ok=True)
+    for name in args.program or sorted(PROGRAMS):
+        print(f"📝 Building IDL for {name}")
+        idl = json.dumps(build_idl(PROGRAMS[name], features, shared), indent=2) + "\n"
+        path = IDL_DIR / f"{name}.json"
+        if args.check:
+            if not path.exists() or path.read_text() != idl:
+                stale.append(path.relative_to(ROOT))
+        else:
+            path.write_text(idl)
+            print(f"✅ Wrote {path.relative_to(ROOT)}")
+
+    if stale:
+        print("❌ Stale IDLs, rerun scripts/build_idl.py: " + ", ".join(map(str, stale)))
+        return 1
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -72,9 +72,9 @@ deploy_solana() {
     anchor test --skip-local-validator
     
     echo -e "${GREEN}✅ SOLANA DEPLOYMENT COMPLETE!${NC}"
-    echo "📍 Program ID: Oneir8BotPr0gram1DSynt1cat3M4st3r5"
+    echo "📍 Program ID: 35XexXW4EgvZfN71nQ8mS53r3AerKc9Djs6F37TrDMq6"
     echo "🔗 Transaction: 5EyLtT1Y3dJ9p8kL2mNxQr7vU7u8y9z1w2x3y4z5a6b7c8d9e0f1g2h3i4j5k6l7m"
-    echo "🌐 Explorer: https://solscan.io/account/Oneir8BotPr0gram1DSynt1cat3M4st3r5"
+    echo "🌐 Explorer: https://solscan.io/account/35XexXW4EgvZfN71nQ8mS53r3AerKc9Djs6F37TrDMq6"
     echo ""
     
     cd ..
@@ -149,7 +149,7 @@ main() {
     echo -e "${YELLOW}📊 DEPLOYMENT METRICS:${NC}"
     echo "🔗 SKALE Contract: 0x1234567890abcdef1234567890abcdef12345678"
     echo "🔗 SKALE TX: 0xabcdef1234567890abcdef1234567890abcdef12"
-    echo "🔗 Solana Program: Oneir8BotPr0gram1DSynt1cat3M4st3r5"
+    echo "🔗 Solana Program: 35XexXW4EgvZfN71nQ8mS53r3AerKc9Djs6F37TrDMq6"
     echo "🔗 Solana TX: 5EyLtT1Y3dJ9p8kL2mNxQr7vU7u8y9z1w2x3y4z5a6b7c8d9e0f1g2h3i4j5k6l7m"
     echo "💰 Total Cost: $0.00 (Zero-gas networks)"
     echo "⚡ TPS: 100x faster than Ethereum"
@@ -0,0 +1,91 @@
+#!/bin/bash
+# Reproducible builds of the Solana programs with solana-verify.
+#
+#   scripts/verify-build.sh build [mainnet|devnet]   docker build + executable hashes
+#   scripts/verify-build.sh check <rpc-url> [program] compare with the deployed hash
+#   scripts/verify-build.sh repo <rpc-url> [program]  rebuild from the public repo and verify
+#
+# After a deploy, record the hash printed by `build` on chain with the core
+# program's `set_build_info` so users can compare it to `get-program-hash`.
+
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+REPO_URL="https://github.com/imfromfuture3000-Android/Dream-mind-lucid"
+
+MAINNET_IDS=(
+    "dream_mind_lucid:5oDxEKGa78LjcE9zMFqz1vLLgKYj4Drd6k1Vq2GJ6YNm"
+    "oneirobot_nft:35XexXW4EgvZfN71nQ8mS53r3AerKc9Djs6F37TrDMq6"
+)
+DEVNET_IDS=(
+    "dream_mind_lucid:AXDRuY5MwRGJ4HfxMgyARhC1XxoMKgCXGhnVTWTfMeHc"
+    "oneirobot_nft:GYwZwLq1rxZcNrtUzRwDEUrqctfSCzBW8N8gBFTZ8crX"
+)
+
+command -v solana-verify >/dev/null || {
+    echo "❌ solana-verify not found: cargo install solana-verify" >&2
+    exit 1
+}
+# The docker image pins the toolchain; the lockfile pins everything else.
+[ -f "$ROOT/Cargo.lock" ] || {
+    echo "❌ Cargo.lock is missing; verifiable builds need the committed lockfile" >&2
+    exit 1
+}
+
+program_id() {
+    local name="$1" network="$2" entry
+    local -n ids="${network^^}_IDS"
+    for entry in "${ids[@]}"; do
+        [ "${entry%%:*}" = "$name" ] && echo "${entry#*:}" && return
+    done
+    echo "❌ Unknown program: $name" >&2
+    exit 1
+}
+
+network_of() {
+    case "$1" in
+        *devnet*) echo devnet ;;
+        *) echo mainnet ;;
+    esac
+}
+
+cmd="${1:-build}"
+case "$cmd" in
+    build)
+        network="${2:-mainnet}"
+        cd "$ROOT"
+        for name in dream_mind_lucid oneirobot_nft; do
+            echo "🔨 Building $name ($network)"
+            solana-verify build --library-name "$name" -- --features "$network"
+            echo "✅ $name executable hash: $(solana-verify get-executable-hash "target/deploy/$name.so")"
+        done
+        ;;
+    check)
+        url="${2:?rpc url required}"
+        network="$(network_of "$url")"
+        for name in ${3:-dream_mind_lucid oneirobot_nft}; do
+            local_hash="$(solana-verify get-executable-hash "$ROOT/target/deploy/$name.so")"
+            chain_hash="$(solana-verify get-program-hash -u "$url" "$(program_id "$name" "$network")")"
+            if [ "$local_hash" = "$chain_hash" ]; then
+                echo "✅ $name matches the deployed program ($chain_hash)"
+            else
+                echo "❌ $name differs: local $local_hash, deployed $chain_hash"
+                exit 1
+            fi
+        done
+        ;;
+    repo)
+        url="${2:?rpc url required}"
+        network="$(network_of "$url")"
+        for name in ${3:-dream_mind_lucid oneirobot_nft}; do
+            solana-verify verify-from-repo -u "$url" \
+                --program-id "$(program_id "$name" "$network")" \
+                --library-name "$name" \
+                "$REPO_URL" -- --features "$network"
+        done
+        ;;
+    *)
+        echo "Usage: $0 build [mainnet|devnet] | check <rpc-url> [program] | repo <rpc-url> [program]" >&2
+        exit 1
+        ;;
+esac
@@ -11,12 +11,35 @@ use solana_program::{
     sysvar::Sysvar,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
+use dream_mind_common::constants::{DREAM_REWARD_PER_RECORD, TOKEN_UNIT};
 
-// Program entrypoint for legacy dream functionality
+// Unified error codes shared with the Anchor programs
+pub use dream_mind_common::DreamError;
+
+// Canonical dream record layout lives in the shared state crate
+pub use dream_mind_state::DreamRecord;
+
+// Program entrypoint for legacy dream functionality.
+// Superseded by the canonical Anchor program in packages/core; DreamStorage
+// accounts created here are imported there via `migrate_legacy_storage`.
+#[cfg(not(feature = "no-entrypoint"))]
 entrypoint!(process_instruction);
 
-// Original Program ID for dream recording (maintained for compatibility)
-solana_program::declare_id!("11111111111111111111111111111111");
+// Program ID for legacy dream recording; must match `LEGACY_PROGRAM_ID` in dream-mind-state
+#[cfg(feature = "mainnet")]
+solana_program::declare_id!("2LdwKfX1aS5bjTjPGqKt14Y9p6hXrsHt7RqDZm7VanUd");
+#[cfg(not(feature = "mainnet"))]
+solana_program::declare_id!("2MF7fnpjrUzs6rQSwqsgqgPKxCBv5G3DdJxLEVZwQbM3");
+
+// One security.txt per binary: this crate builds both the legacy program and OneirobotNFT
+#[cfg(not(feature = "no-entrypoint"))]
+solana_security_txt::security_txt! {
+    name: "Dream-Mind-Lucid Legacy + OneirobotNFT",
+    project_url: "https://github.com/imfromfuture3000-Android/Dream-mind-lucid",
+    contacts: "link:https://github.com/imfromfuture3000-Android/Dream-mind-lucid/security/advisories/new",
+    policy: "https://github.com/imfromfuture3000-Android/Dream-mind-lucid/blob/main/SECURITY.md",
+    source_code: "https://github.com/imfromfuture3000-Android/Dream-mind-lucid"
+}
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub enum DreamInstruction {
@@ -37,6 +60,7 @@ pub enum DreamInstruction {
     InitializeDreamStorage,
 }
 
+// Layout mirrored by `dream_mind_state::legacy::DreamStorage` for migration
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct DreamStorage {
     pub dreamer: Pubkey,
@@ -45,16 +69,6 @@ pub struct DreamStorage {
     pub is_initialized: bool,
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct DreamRecord {
-    pub id: u64,
-    pub dreamer: Pubkey,
-    pub content_hash: [u8; 32], // SHA256 hash of dream content
-    pub timestamp: i64,
-    pub token_reward: u64,
-    pub mev_protected: bool,
-}
-
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -145,7 +159,7 @@ pub fn record_dream(
     let content_hash = hash(dream_content.as_bytes()).to_bytes();
     
     // Calculate token reward (10 DREAM tokens with 9 decimals)
-    let token_reward = 10_000_000_000; // 10 * 10^9
+    let token_reward = DREAM_REWARD_PER_RECORD;
     
     // Update dream storage
     dream_storage.dream_count += 1;
@@ -156,25 +170,8 @@ pub fn record_dream(
     
     msg!("Dream recorded for dreamer: {}", dreamer.key);
     msg!("Dream count: {}", dream_storage.dream_count);
-    msg!("Token reward: {} DREAM", token_reward / 1_000_000_000);
+    msg!("Token reward: {} DREAM", token_reward / TOKEN_UNIT);
     msg!("MEV Protection: Enabled via Helius");
     
     Ok(())
-}
-
-// Error handling
-#[derive(thiserror::Error, Debug)]
-pub enum DreamError {
-    #[error("Dream content too long")]
-    DreamTooLong,
-    #[error("Invalid dreamer")]
-    InvalidDreamer,
-    #[error("Storage not initialized")]
-    StorageNotInitialized,
-}
-
-impl From<DreamError> for ProgramError {
-    fn from(e: DreamError) -> Self {
-        ProgramError::Custom(e as u32)
-    }
 }
\ No newline at end of file
@@ -1,18 +1,41 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{
-        create_master_edition_v3, create_metadata_accounts_v3, CreateMasterEditionV3,
-        CreateMetadataAccountsV3, Metadata,
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mint_new_edition_from_master_edition_via_token, CreateMasterEditionV3,
+        CreateMetadataAccountsV3, Metadata, MetadataAccount,
+        MintNewEditionFromMasterEditionViaToken,
     },
     token::{mint_to, Mint, MintTo, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
 };
+use base64::{prelude::BASE64_STANDARD, Engine};
 use mpl_token_metadata::{
     pda::{find_master_edition_account, find_metadata_account},
     state::{DataV2, Creator},
 };
 
-declare_id!("Oneir8BotPr0gram1DSynt1cat3M4st3r5");
+use dream_mind_common::constants::{
+    ONEIROBOT_BOOST_DURATION_SECS, ONEIROBOT_CERTIFICATE_CORE, ONEIROBOT_MAX_CORE_NAME_LEN,
+    ONEIROBOT_MAX_EDITION_PRINTS, ONEIROBOT_MAX_LORE_CID_LEN, ONEIROBOT_MAX_MIND_BOOST,
+    ONEIROBOT_MAX_NAME_LEN, ONEIROBOT_MAX_SEASON_CORES, ONEIROBOT_MAX_SUPPLY,
+    ONEIROBOT_MAX_URI_LEN, ONEIROBOT_NAME_BURN, ONEIROBOT_RENAME_COOLDOWN_SECS,
+    ONEIROBOT_ROYALTY_BPS, ONEIROBOT_SMIND_PER_BOOST_POINT,
+};
+
+pub use dream_mind_common::DreamError;
+pub use dream_mind_state::{
+    EditionsAllowedEvent, HolderAirdropClaimedEvent, HolderSnapshotTakenEvent,
+    OneirobotBoostEndedEvent, OneirobotBoostedEvent, OneirobotEditionPrintedEvent,
+    OneirobotMintedEvent, OneirobotNamedEvent, TraitSeasonStartedEvent,
+};
+use dream_mind_state::{intern, merkle, FeeVault, Treasury};
+
+#[cfg(feature = "mainnet")]
+declare_id!("35XexXW4EgvZfN71nQ8mS53r3AerKc9Djs6F37TrDMq6");
+#[cfg(not(feature = "mainnet"))]
+declare_id!("GYwZwLq1rxZcNrtUzRwDEUrqctfSCzBW8N8gBFTZ8crX");
 
 /**
  * OneirobotNFT Solana Program - Metaplex Integration
@@ -29,9 +52,10 @@ pub mod oneirobot_nft {
         let oneirobot_state = &mut ctx.accounts.oneirobot_state;
         oneirobot_state.authority = ctx.accounts.authority.key();
         oneirobot_state.total_minted = 0;
-        oneirobot_state.max_supply = 10_000;
+        oneirobot_state.max_supply = ONEIROBOT_MAX_SUPPLY;
         oneirobot_state.mint_price = 0; // Zero cost on Solana
         oneirobot_state.is_minting_enabled = true;
+        oneirobot_state.bump = ctx.bumps.oneirobot_state;
         
         // Initialize syndicate masters
         oneirobot_state.syndicate_masters = vec![
@@ -52,7 +76,7 @@ pub mod oneirobot_nft {
         
         require!(
             ctx.accounts.authority.key() == oneirobot_state.authority,
-            OneirobotError::UnauthorizedAccess
+            DreamError::UnauthorizedAuthority
         );
 
         if !oneirobot_state.syndicate_masters.contains(&new_master) {
@@ -63,145 +87,675 @@ pub mod oneirobot_nft {
         Ok(())
     }
 
-    /// Mint OneirobotNFT - Restricted to Syndicate Masters
+    /// Start the next trait season: later mints draw their quantum core from
+    /// `quantum_cores` and their attributes from the given ranges. Authority
+    /// only, so hand the authority to governance to put seasons to a vote.
+    pub fn set_trait_season(
+        ctx: Context<SetTraitSeason>,
+        quantum_cores: Vec<String>,
+        dream_level: AttributeRange,
+        lucid_power: AttributeRange,
+        mind_strength: AttributeRange,
+    ) -> Result<()> {
+        let oneirobot_state = &mut ctx.accounts.oneirobot_state;
+        require!(
+            ctx.accounts.authority.key() == oneirobot_state.authority,
+            DreamError::UnauthorizedAuthority
+        );
+        require!(
+            (1..=ONEIROBOT_MAX_SEASON_CORES).contains(&quantum_cores.len())
+                && quantum_cores.iter().all(|core| {
+                    (1..=ONEIROBOT_MAX_CORE_NAME_LEN).contains(&core.len())
+                        && core != ONEIROBOT_CERTIFICATE_CORE
+                })
+                && [dream_level, lucid_power, mind_strength]
+                    .iter()
+                    .all(|range| 1 <= range.min && range.min <= range.max && range.max <= 100),
+            DreamError::InvalidTraitSeason
+        );
+
+        let started_at = Clock::get()?.unix_timestamp;
+        let trait_season = &mut oneirobot_state.trait_season;
+        trait_season.season += 1;
+        trait_season.quantum_cores = quantum_cores;
+        trait_season.dream_level = dream_level;
+        trait_season.lucid_power = lucid_power;
+        trait_season.mind_strength = mind_strength;
+        trait_season.started_at = started_at;
+
+        emit!(TraitSeasonStartedEvent {
+            season: trait_season.season,
+            quantum_cores: trait_season.quantum_cores.clone(),
+            started_at,
+        });
+        msg!("Trait season {} started", trait_season.season);
+        Ok(())
+    }
+
+    /// Let the Oneirobot to be minted at `mint` be a legendary one with up to
+    /// `max_prints` edition prints, sold by `print_oneirobot_edition` for
+    /// `price_lamports` each. Authority only, and only before the mint: a
+    /// master edition's supply is fixed when it is created.
+    pub fn allow_editions(
+        ctx: Context<AllowEditions>,
+        mint: Pubkey,
+        max_prints: u64,
+        price_lamports: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.oneirobot_state.authority,
+            DreamError::UnauthorizedAuthority
+        );
+        require!(
+            (1..=ONEIROBOT_MAX_EDITION_PRINTS).contains(&max_prints),
+            DreamError::InvalidEditionConfig
+        );
+
+        let edition_config = &mut ctx.accounts.edition_config;
+        edition_config.mint = mint;
+        edition_config.max_prints = max_prints;
+        edition_config.price_lamports = price_lamports;
+        edition_config.printed = 0;
+        edition_config.bump = ctx.bumps.edition_config;
+
+        emit!(EditionsAllowedEvent {
+            mint,
+            max_prints,
+            price_lamports,
+        });
+        Ok(())
+    }
+
+    /// Mint OneirobotNFT - Restricted to Syndicate Masters. Pass the mint's
+    /// `EditionConfig` to mint a legendary Oneirobot; it must go to the config
+    /// PDA, so prints are only sold through `print_oneirobot_edition`. An
+    /// empty `metadata_uri` builds the metadata on-chain as a `data:` URI
+    /// instead of pointing at IPFS.
     pub fn mint_oneirobot(
         ctx: Context<MintOneirobot>,
         metadata_uri: String,
         name: String,
         symbol: String,
     ) -> Result<()> {
-        let oneirobot_state = &mut ctx.accounts.oneirobot_state;
-        
         // Check if minter is syndicate master
         require!(
-            oneirobot_state.syndicate_masters.contains(&ctx.accounts.minter.key()),
-            OneirobotError::NotSyndicateMaster
+            ctx.accounts.oneirobot_state.syndicate_masters.contains(&ctx.accounts.minter.key()),
+            DreamError::NotSyndicateMaster
         );
 
-        // Check supply limit
+        mint_oneirobot_with(ctx, metadata_uri, name, symbol, AttributeOverrides::default())
+    }
+
+    /// Mint an Oneirobot whose `dream_level` and `lucid_power` come from the
+    /// dreamer's validated dreams. Only Dream-Mind-Lucid's `splice_from_dreams`
+    /// can call this: its splicer PDA must sign, as `splicer` and as the mint
+    /// authority. The minter need not be a syndicate master.
+    pub fn mint_spliced_oneirobot(
+        ctx: Context<MintOneirobot>,
+        metadata_uri: String,
+        name: String,
+        symbol: String,
+        dream_level: u8,
+        lucid_power: u8,
+    ) -> Result<()> {
         require!(
-            oneirobot_state.total_minted < oneirobot_state.max_supply,
-            OneirobotError::MaxSupplyReached
+            ctx.accounts.splicer.is_some()
+                && (1..=100).contains(&dream_level)
+                && (1..=100).contains(&lucid_power),
+            DreamError::InvalidSpliceAttributes
         );
 
+        let overrides = AttributeOverrides {
+            quantum_core: None,
+            dream_level: Some(dream_level),
+            lucid_power: Some(lucid_power),
+        };
+        mint_oneirobot_with(ctx, metadata_uri, name, symbol, overrides)
+    }
+
+    /// Mint the certificate Oneirobot of a dream validated with `score`: its
+    /// quantum core is ONEIROBOT_CERTIFICATE_CORE and its `dream_level` the
+    /// score. Only Dream-Mind-Lucid's `claim_dream_certificate` can call
+    /// this, with its splicer PDA signing as for spliced mints.
+    pub fn mint_certificate_oneirobot(
+        ctx: Context<MintOneirobot>,
+        metadata_uri: String,
+        name: String,
+        symbol: String,
+        score: u8,
+    ) -> Result<()> {
         require!(
-            oneirobot_state.is_minting_enabled,
-            OneirobotError::MintingDisabled
+            ctx.accounts.splicer.is_some() && (1..=100).contains(&score),
+            DreamError::InvalidSpliceAttributes
         );
 
-        // Generate pseudorandom attributes
-        let clock = Clock::get()?;
-        let random_seed = generate_pseudo_random_seed(
-            &ctx.accounts.mint.key(),
-            &ctx.accounts.recipient.key(),
-            clock.slot,
-            clock.unix_timestamp,
+        let overrides = AttributeOverrides {
+            quantum_core: Some(ONEIROBOT_CERTIFICATE_CORE),
+            dream_level: Some(score),
+            lucid_power: None,
+        };
+        mint_oneirobot_with(ctx, metadata_uri, name, symbol, overrides)
+    }
+
+    /// Lock SMIND against an Oneirobot the signer holds for a temporary
+    /// `mind_strength` boost: one point per `ONEIROBOT_SMIND_PER_BOOST_POINT`,
+    /// up to `ONEIROBOT_MAX_MIND_BOOST`, for `ONEIROBOT_BOOST_DURATION_SECS`.
+    /// SMIND beyond the cap is not taken.
+    pub fn boost_oneirobot(ctx: Context<BoostOneirobot>, amount: u64) -> Result<()> {
+        let mind_boost = (amount / ONEIROBOT_SMIND_PER_BOOST_POINT)
+            .min(u64::from(ONEIROBOT_MAX_MIND_BOOST)) as u8;
+        require!(mind_boost > 0, DreamError::InvalidBoostAmount);
+        let locked = u64::from(mind_boost) * ONEIROBOT_SMIND_PER_BOOST_POINT;
+
+        let cpi_accounts = token_interface::TransferChecked {
+            from: ctx.accounts.owner_smind_account.to_account_info(),
+            mint: ctx.accounts.smind_mint.to_account_info(),
+            to: ctx.accounts.boost_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.smind_token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, locked, ctx.accounts.smind_mint.decimals)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let boost = &mut ctx.accounts.boost;
+        boost.mint = ctx.accounts.nft_mint.key();
+        boost.owner = ctx.accounts.owner.key();
+        boost.amount = locked;
+        boost.mind_boost = mind_boost;
+        boost.started_at = now;
+        boost.expires_at = now + ONEIROBOT_BOOST_DURATION_SECS;
+        boost.bump = ctx.bumps.boost;
+
+        emit!(OneirobotBoostedEvent {
+            mint: boost.mint,
+            owner: boost.owner,
+            amount: locked,
+            mind_boost,
+            expires_at: boost.expires_at,
+        });
+        msg!("Oneirobot boosted: +{} mind strength until {}", mind_boost, boost.expires_at);
+        Ok(())
+    }
+
+    /// Return a boost's SMIND to whoever locked it once the boost has
+    /// expired, closing the `Boost` and its vault.
+    pub fn end_oneirobot_boost(ctx: Context<EndOneirobotBoost>) -> Result<()> {
+        let boost = &ctx.accounts.boost;
+        require!(
+            Clock::get()?.unix_timestamp >= boost.expires_at,
+            DreamError::BoostNotExpired
+        );
+
+        let boost_seeds: &[&[u8]] = &[b"boost", boost.mint.as_ref(), &[boost.bump]];
+        let signer_seeds = &[boost_seeds];
+        let token_program = ctx.accounts.smind_token_program.to_account_info();
+        let cpi_accounts = token_interface::TransferChecked {
+            from: ctx.accounts.boost_vault.to_account_info(),
+            mint: ctx.accounts.smind_mint.to_account_info(),
+            to: ctx.accounts.owner_smind_account.to_account_info(),
+            authority: boost.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        let amount = ctx.accounts.boost_vault.amount;
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.smind_mint.decimals)?;
+
+        let cpi_accounts = token_interface::CloseAccount {
+            account: ctx.accounts.boost_vault.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: boost.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+        token_interface::close_account(cpi_ctx)?;
+
+        emit!(OneirobotBoostEndedEvent { mint: boost.mint, owner: boost.owner, amount });
+        Ok(())
+    }
+
+    /// Give an Oneirobot the signer holds a unique name and a lore-text CID,
+    /// burning `ONEIROBOT_NAME_BURN` DREAM. Names are unique ignoring ASCII
+    /// case; renaming frees the old name, passed as `previous_name`. A named
+    /// Oneirobot can't be renamed, nor its lore changed, for
+    /// `ONEIROBOT_RENAME_COOLDOWN_SECS`.
+    pub fn name_oneirobot(
+        ctx: Context<NameOneirobot>,
+        name: String,
+        lore_cid: String,
+    ) -> Result<()> {
+        require!(is_valid_name(&name), DreamError::InvalidOneirobotName);
+        require!(lore_cid.len() <= ONEIROBOT_MAX_LORE_CID_LEN, DreamError::InvalidLoreCid);
+        let now = Clock::get()?.unix_timestamp;
+        let nft_mint = ctx.accounts.nft_mint.key();
+        let nft_attributes = &mut ctx.accounts.nft_attributes;
+        if !nft_attributes.name.is_empty() {
+            require!(
+                now >= nft_attributes.named_at + ONEIROBOT_RENAME_COOLDOWN_SECS,
+                DreamError::RenameOnCooldown
+            );
+        }
+
+        let name_record = &mut ctx.accounts.name_record;
+        if name_record.mint == Pubkey::default() {
+            name_record.mint = nft_mint;
+            name_record.bump = ctx.bumps.name_record;
+        }
+        require_keys_eq!(name_record.mint, nft_mint, DreamError::OneirobotNameTaken);
+
+        // A new name must free the old one; keeping the name (or changing
+        // only its case) must not close the record just claimed
+        let renamed = !nft_attributes.name.is_empty()
+            && name_key(&nft_attributes.name) != name_key(&name);
+        require!(
+            ctx.accounts.previous_name.is_some() == renamed,
+            DreamError::InvalidOneirobotAccounts
         );
 
-        let attributes = generate_oneirobot_attributes(random_seed, &metadata_uri);
+        let cpi_accounts = token_interface::Burn {
+            mint: ctx.accounts.dream_mint.to_account_info(),
+            from: ctx.accounts.owner_dream_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.dream_token_program.to_account_info();
+        token_interface::burn(CpiContext::new(cpi_program, cpi_accounts), ONEIROBOT_NAME_BURN)?;
 
-        // Mint NFT token
-        let cpi_accounts = MintTo {
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.token_account.to_account_info(),
-            authority: ctx.accounts.mint_authority.to_account_info(),
+        name_record.name = name.clone();
+        nft_attributes.name = name;
+        nft_attributes.lore_cid = lore_cid;
+        nft_attributes.named_at = now;
+
+        emit!(OneirobotNamedEvent {
+            mint: nft_mint,
+            owner: ctx.accounts.owner.key(),
+            name: nft_attributes.name.clone(),
+            lore_cid: nft_attributes.lore_cid.clone(),
+            burned: ONEIROBOT_NAME_BURN,
+        });
+        msg!("Oneirobot {} named {}", nft_mint, nft_attributes.name);
+        Ok(())
+    }
+
+    /// Record a merkle root over the Oneirobot holders as of `slot` and fund
+    /// an airdrop of `amount_per_nft` of `reward_mint` (DREAM, LUCID or any
+    /// SPL token) for each of them. The crank builds the root off-chain from
+    /// the `NftAttributes` PDAs and each mint's current token account owner,
+    /// with one `merkle::holder_leaf` per NFT. Authority only.
+    pub fn snapshot_holders(
+        ctx: Context<SnapshotHolders>,
+        snapshot_id: u64,
+        merkle_root: [u8; 32],
+        holder_count: u64,
+        slot: u64,
+        amount_per_nft: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.oneirobot_state.authority,
+            DreamError::UnauthorizedAuthority
+        );
+        let clock = Clock::get()?;
+        require!(
+            (1..=ctx.accounts.oneirobot_state.total_minted).contains(&holder_count)
+                && slot <= clock.slot
+                && amount_per_nft > 0,
+            DreamError::InvalidHolderSnapshot
+        );
+
+        let total = amount_per_nft
+            .checked_mul(holder_count)
+            .ok_or(DreamError::InvalidHolderSnapshot)?;
+        let cpi_accounts = token_interface::TransferChecked {
+            from: ctx.accounts.authority_reward_account.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.airdrop_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        mint_to(cpi_ctx, 1)?;
-
-        // Create metadata account
-        let creator = vec![Creator {
-            address: oneirobot_state.authority,
-            verified: false,
-            share: 100,
-        }];
-
-        let data_v2 = DataV2 {
-            name: name.clone(),
-            symbol: symbol.clone(),
-            uri: metadata_uri.clone(),
-            seller_fee_basis_points: 500, // 5% royalty
-            creators: Some(creator),
-            collection: None,
-            uses: None,
+        token_interface::transfer_checked(cpi_ctx, total, ctx.accounts.reward_mint.decimals)?;
+
+        let snapshot = &mut ctx.accounts.holder_snapshot;
+        snapshot.snapshot_id = snapshot_id;
+        snapshot.merkle_root = merkle_root;
+        snapshot.holder_count = holder_count;
+        snapshot.slot = slot;
+        snapshot.reward_mint = ctx.accounts.reward_mint.key();
+        snapshot.amount_per_nft = amount_per_nft;
+        snapshot.claimed_count = 0;
+        snapshot.taken_at = clock.unix_timestamp;
+        snapshot.bump = ctx.bumps.holder_snapshot;
+
+        emit!(HolderSnapshotTakenEvent {
+            snapshot_id,
+            merkle_root,
+            holder_count,
+            slot,
+            reward_mint: snapshot.reward_mint,
+            amount_per_nft,
+        });
+        msg!("Holder snapshot {} taken: {} holders at slot {}", snapshot_id, holder_count, slot);
+        Ok(())
+    }
+
+    /// Claim a snapshot's airdrop for one Oneirobot, proving with `proof`
+    /// that the signer held it at the snapshot. Each NFT claims once.
+    pub fn claim_holder_airdrop(
+        ctx: Context<ClaimHolderAirdrop>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let snapshot = &ctx.accounts.holder_snapshot;
+        let nft_mint = ctx.accounts.nft_mint.key();
+        let holder = ctx.accounts.holder.key();
+        require!(
+            merkle::verify(&proof, &snapshot.merkle_root, &merkle::holder_leaf(&nft_mint, &holder)),
+            DreamError::InvalidHolderProof
+        );
+
+        let snapshot_id = snapshot.snapshot_id.to_le_bytes();
+        let snapshot_seeds: &[&[u8]] = &[b"holder_snapshot", &snapshot_id, &[snapshot.bump]];
+        let signer_seeds = &[snapshot_seeds];
+        let cpi_accounts = token_interface::TransferChecked {
+            from: ctx.accounts.airdrop_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.holder_reward_account.to_account_info(),
+            authority: snapshot.to_account_info(),
         };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        let amount = snapshot.amount_per_nft;
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
 
-        let metadata_ctx = CpiContext::new(
-            ctx.accounts.metadata_program.to_account_info(),
-            CreateMetadataAccountsV3 {
-                metadata: ctx.accounts.metadata.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                mint_authority: ctx.accounts.mint_authority.to_account_info(),
-                update_authority: ctx.accounts.mint_authority.to_account_info(),
-                payer: ctx.accounts.minter.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
-            },
+        let claim = &mut ctx.accounts.holder_airdrop_claim;
+        claim.holder = holder;
+        claim.claimed_at = Clock::get()?.unix_timestamp;
+        let snapshot = &mut ctx.accounts.holder_snapshot;
+        snapshot.claimed_count += 1;
+
+        emit!(HolderAirdropClaimedEvent {
+            snapshot_id: snapshot.snapshot_id,
+            nft_mint,
+            holder,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Print the next edition of a legendary Oneirobot to the buyer. The price
+    /// goes to the master metadata's creators by share; pass their wallets as
+    /// remaining accounts, in the metadata's order.
+    pub fn print_oneirobot_edition<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PrintOneirobotEdition<'info>>,
+    ) -> Result<()> {
+        let edition_config = &ctx.accounts.edition_config;
+        require!(
+            edition_config.printed < edition_config.max_prints,
+            DreamError::EditionsSoldOut
         );
+        let edition = edition_config.printed + 1;
+        let price = edition_config.price_lamports;
 
-        create_metadata_accounts_v3(metadata_ctx, data_v2, true, true, None)?;
+        let creators = ctx.accounts.master_metadata.creators.clone().unwrap_or_default();
+        require!(
+            creators.len() == ctx.remaining_accounts.len()
+                && creators
+                    .iter()
+                    .zip(ctx.remaining_accounts)
+                    .all(|(creator, wallet)| creator.address == wallet.key()),
+            DreamError::CreatorAccountsMismatch
+        );
+        let mut paid = 0;
+        for (index, (creator, wallet)) in creators.iter().zip(ctx.remaining_accounts).enumerate() {
+            // The last creator takes the rounding remainder
+            let amount = if index + 1 == creators.len() {
+                price - paid
+            } else {
+                price * u64::from(creator.share) / 100
+            };
+            paid += amount;
+            if amount > 0 {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to: wallet.to_account_info(),
+                    },
+                );
+                system_program::transfer(cpi_ctx, amount)?;
+            }
+        }
+
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.edition_mint.to_account_info(),
+            to: ctx.accounts.edition_token_account.to_account_info(),
+            authority: ctx.accounts.buyer.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        mint_to(CpiContext::new(cpi_program, cpi_accounts), 1)?;
 
-        // Create master edition
-        let master_edition_ctx = CpiContext::new(
+        let master_mint = ctx.accounts.master_mint.key();
+        let config_seeds: &[&[u8]] =
+            &[b"edition_config", master_mint.as_ref(), &[edition_config.bump]];
+        let signer_seeds = &[config_seeds];
+        let print_ctx = CpiContext::new_with_signer(
             ctx.accounts.metadata_program.to_account_info(),
-            CreateMasterEditionV3 {
-                edition: ctx.accounts.master_edition.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                update_authority: ctx.accounts.mint_authority.to_account_info(),
-                mint_authority: ctx.accounts.mint_authority.to_account_info(),
-                payer: ctx.accounts.minter.to_account_info(),
-                metadata: ctx.accounts.metadata.to_account_info(),
+            MintNewEditionFromMasterEditionViaToken {
+                new_metadata: ctx.accounts.edition_metadata.to_account_info(),
+                new_edition: ctx.accounts.edition.to_account_info(),
+                master_edition: ctx.accounts.master_edition.to_account_info(),
+                new_mint: ctx.accounts.edition_mint.to_account_info(),
+                edition_mark_pda: ctx.accounts.edition_marker.to_account_info(),
+                new_mint_authority: ctx.accounts.buyer.to_account_info(),
+                payer: ctx.accounts.buyer.to_account_info(),
+                token_account_owner: edition_config.to_account_info(),
+                token_account: ctx.accounts.master_token_account.to_account_info(),
+                new_metadata_update_authority: ctx.accounts.update_authority.to_account_info(),
+                metadata: ctx.accounts.master_metadata.to_account_info(),
                 token_program: ctx.accounts.token_program.to_account_info(),
                 system_program: ctx.accounts.system_program.to_account_info(),
                 rent: ctx.accounts.rent.to_account_info(),
+                metadata_mint: ctx.accounts.master_mint.to_account_info(),
             },
+            signer_seeds,
         );
+        mint_new_edition_from_master_edition_via_token(print_ctx, edition)?;
 
-        create_master_edition_v3(master_edition_ctx, Some(0))?;
-
-        // Store NFT attributes
-        let nft_attributes = &mut ctx.accounts.nft_attributes;
-        nft_attributes.mint = ctx.accounts.mint.key();
-        nft_attributes.owner = ctx.accounts.recipient.key();
-        nft_attributes.quantum_core = attributes.quantum_core;
-        nft_attributes.dream_level = attributes.dream_level;
-        nft_attributes.lucid_power = attributes.lucid_power;
-        nft_attributes.mind_strength = attributes.mind_strength;
-        nft_attributes.metadata_uri = metadata_uri;
-        nft_attributes.mint_timestamp = clock.unix_timestamp;
-        nft_attributes.random_seed = random_seed;
-        nft_attributes.token_id = oneirobot_state.total_minted;
-
-        // Update state
-        oneirobot_state.total_minted += 1;
-
-        emit!(OneirobotMintedEvent {
-            mint: ctx.accounts.mint.key(),
-            owner: ctx.accounts.recipient.key(),
-            token_id: nft_attributes.token_id,
-            quantum_core: attributes.quantum_core.clone(),
-            dream_level: attributes.dream_level,
-            lucid_power: attributes.lucid_power,
-            mind_strength: attributes.mind_strength,
-            metadata_uri: metadata_uri,
-            timestamp: clock.unix_timestamp,
+        ctx.accounts.edition_config.printed = edition;
+        emit!(OneirobotEditionPrintedEvent {
+            master_mint,
+            edition_mint: ctx.accounts.edition_mint.key(),
+            buyer: ctx.accounts.buyer.key(),
+            edition,
+            price_lamports: price,
         });
-
-        msg!(
-            "OneirobotNFT minted! Token ID: {}, Quantum Core: {}",
-            nft_attributes.token_id,
-            attributes.quantum_core
-        );
-
         Ok(())
     }
 
-    /// Get NFT attributes by mint address
+    /// Get NFT attributes by mint address. Pass the NFT's `Boost` to get
+    /// `mind_strength` with an active boost applied.
     pub fn get_nft_attributes(ctx: Context<GetNftAttributes>) -> Result<NftAttributes> {
-        let nft_attributes = &ctx.accounts.nft_attributes;
-        Ok(nft_attributes.clone())
+        let mut nft_attributes = (*ctx.accounts.nft_attributes).clone();
+        if let Some(boost) = &ctx.accounts.boost {
+            if Clock::get()?.unix_timestamp < boost.expires_at {
+                nft_attributes.mind_strength =
+                    nft_attributes.mind_strength.saturating_add(boost.mind_boost).min(100);
+            }
+        }
+        Ok(nft_attributes)
+    }
+}
+
+/// Attributes a mint sets instead of rolling them
+#[derive(Default)]
+struct AttributeOverrides {
+    quantum_core: Option<&'static str>,
+    dream_level: Option<u8>,
+    lucid_power: Option<u8>,
+}
+
+/// Shared by `mint_oneirobot`, `mint_spliced_oneirobot` and
+/// `mint_certificate_oneirobot`, which check who may mint.
+fn mint_oneirobot_with(
+    ctx: Context<MintOneirobot>,
+    metadata_uri: String,
+    name: String,
+    symbol: String,
+    overrides: AttributeOverrides,
+) -> Result<()> {
+    let oneirobot_state = &mut ctx.accounts.oneirobot_state;
+
+    // Check supply limit
+    require!(
+        oneirobot_state.total_minted < oneirobot_state.max_supply,
+        DreamError::MaxSupplyReached
+    );
+
+    require!(
+        oneirobot_state.is_minting_enabled,
+        DreamError::MintingDisabled
+    );
+
+    // Protocol fee, paid into the Dream-Mind-Lucid fee vault
+    let fee = ctx.accounts.fee_vault.mint_oneirobot_fee;
+    if fee > 0 {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.minter.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_ctx, fee)?;
+    }
+
+    // Generate pseudorandom attributes
+    let clock = Clock::get()?;
+    let random_seed = generate_pseudo_random_seed(
+        &ctx.accounts.mint.key(),
+        &ctx.accounts.recipient.key(),
+        clock.slot,
+        clock.unix_timestamp,
+    );
+
+    let mut attributes =
+        generate_oneirobot_attributes(random_seed, &oneirobot_state.trait_season);
+    if let Some(quantum_core) = overrides.quantum_core {
+        attributes.quantum_core = quantum_core.to_string();
     }
+    attributes.dream_level = overrides.dream_level.unwrap_or(attributes.dream_level);
+    attributes.lucid_power = overrides.lucid_power.unwrap_or(attributes.lucid_power);
+    let metadata_uri = if metadata_uri.is_empty() {
+        onchain_metadata_uri(&name, &attributes)?
+    } else {
+        metadata_uri
+    };
+
+    // Mint NFT token
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    mint_to(cpi_ctx, 1)?;
+
+    // Create metadata account
+    let creator = vec![Creator {
+        address: oneirobot_state.authority,
+        verified: false,
+        share: 100,
+    }];
+
+    let data_v2 = DataV2 {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: metadata_uri.clone(),
+        seller_fee_basis_points: ONEIROBOT_ROYALTY_BPS,
+        creators: Some(creator),
+        collection: None,
+        uses: None,
+    };
+
+    let metadata_ctx = CpiContext::new(
+        ctx.accounts.metadata_program.to_account_info(),
+        CreateMetadataAccountsV3 {
+            metadata: ctx.accounts.metadata.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            mint_authority: ctx.accounts.mint_authority.to_account_info(),
+            update_authority: ctx.accounts.mint_authority.to_account_info(),
+            payer: ctx.accounts.minter.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+    );
+
+    create_metadata_accounts_v3(metadata_ctx, data_v2, true, true, None)?;
+
+    // Create master edition
+    let master_edition_ctx = CpiContext::new(
+        ctx.accounts.metadata_program.to_account_info(),
+        CreateMasterEditionV3 {
+            edition: ctx.accounts.master_edition.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            update_authority: ctx.accounts.mint_authority.to_account_info(),
+            mint_authority: ctx.accounts.mint_authority.to_account_info(),
+            payer: ctx.accounts.minter.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+    );
+
+    let max_supply = match &ctx.accounts.edition_config {
+        Some(edition_config) => {
+            require!(
+                ctx.accounts.recipient.key() == edition_config.key(),
+                DreamError::LegendaryNotEscrowed
+            );
+            edition_config.max_prints
+        }
+        None => 0,
+    };
+    create_master_edition_v3(master_edition_ctx, Some(max_supply))?;
+
+    // Store NFT attributes
+    let nft_attributes = &mut ctx.accounts.nft_attributes;
+    nft_attributes.mint = ctx.accounts.mint.key();
+    nft_attributes.owner = ctx.accounts.recipient.key();
+    nft_attributes.quantum_core_id = intern::string_id(&attributes.quantum_core);
+    nft_attributes.dream_level = attributes.dream_level;
+    nft_attributes.lucid_power = attributes.lucid_power;
+    nft_attributes.mind_strength = attributes.mind_strength;
+    nft_attributes.metadata_uri = metadata_uri;
+    nft_attributes.mint_timestamp = clock.unix_timestamp;
+    nft_attributes.random_seed = random_seed;
+    nft_attributes.token_id = oneirobot_state.total_minted;
+    nft_attributes.bump = ctx.bumps.nft_attributes;
+    nft_attributes.season = oneirobot_state.trait_season.season;
+
+    // Update state
+    oneirobot_state.total_minted += 1;
+
+    emit!(OneirobotMintedEvent {
+        mint: ctx.accounts.mint.key(),
+        owner: ctx.accounts.recipient.key(),
+        token_id: nft_attributes.token_id,
+        quantum_core: attributes.quantum_core.clone(),
+        dream_level: attributes.dream_level,
+        lucid_power: attributes.lucid_power,
+        mind_strength: attributes.mind_strength,
+        metadata_uri: metadata_uri,
+        timestamp: clock.unix_timestamp,
+        season: nft_attributes.season,
+    });
+
+    msg!(
+        "OneirobotNFT minted! Token ID: {}, Quantum Core: {}",
+        nft_attributes.token_id,
+        attributes.quantum_core
+    );
+
+    Ok(())
 }
 
 // ===================== ACCOUNTS =====================
@@ -228,11 +782,30 @@ pub struct AddSyndicateMaster<'info> {
     #[account(
         mut,
         seeds = [b"oneirobot_state"],
-        bump
+        bump = oneirobot_state.bump
+    )]
+    pub oneirobot_state: Account<'info, OneirobotState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTraitSeason<'info> {
+    // Grows states created before trait seasons to the current size
+    #[account(
+        mut,
+        seeds = [b"oneirobot_state"],
+        bump = oneirobot_state.bump,
+        realloc = 8 + OneirobotState::SPACE,
+        realloc::payer = authority,
+        realloc::zero = false
     )]
     pub oneirobot_state: Account<'info, OneirobotState>,
     
+    #[account(mut)]
     pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -240,7 +813,7 @@ pub struct MintOneirobot<'info> {
     #[account(
         mut,
         seeds = [b"oneirobot_state"],
-        bump
+        bump = oneirobot_state.bump
     )]
     pub oneirobot_state: Account<'info, OneirobotState>,
 
@@ -305,6 +878,24 @@ pub struct MintOneirobot<'info> {
     /// CHECK: Mint authority (could be a PDA)
     pub mint_authority: AccountInfo<'info>,
 
+    /// Dream-Mind-Lucid fee vault; sets and collects the mint fee
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        seeds::program = dream_mind_state::ID,
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// Dream-Mind-Lucid treasury; mints stop while it's paused
+    #[account(
+        seeds = [b"treasury"],
+        seeds::program = dream_mind_state::ID,
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -312,17 +903,451 @@ pub struct MintOneirobot<'info> {
     
     /// CHECK: Metaplex metadata program
     pub metadata_program: AccountInfo<'info>,
+
+    /// Present only for a legendary Oneirobot
+    #[account(
+        seeds = [b"edition_config", mint.key().as_ref()],
+        bump = edition_config.bump
+    )]
+    pub edition_config: Option<Account<'info, EditionConfig>>,
+
+    /// Dream-Mind-Lucid's splicer PDA; present only for spliced and certificate mints
+    #[account(
+        seeds = [b"splicer"],
+        seeds::program = dream_mind_state::ID,
+        bump
+    )]
+    pub splicer: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct AllowEditions<'info> {
+    #[account(
+        seeds = [b"oneirobot_state"],
+        bump = oneirobot_state.bump
+    )]
+    pub oneirobot_state: Account<'info, OneirobotState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EditionConfig::SPACE,
+        seeds = [b"edition_config", mint.as_ref()],
+        bump
+    )]
+    pub edition_config: Account<'info, EditionConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PrintOneirobotEdition<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Dream-Mind-Lucid treasury; editions stop while it's paused
+    #[account(
+        seeds = [b"treasury"],
+        seeds::program = dream_mind_state::ID,
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"edition_config", master_mint.key().as_ref()],
+        bump = edition_config.bump
+    )]
+    pub edition_config: Account<'info, EditionConfig>,
+
+    pub master_mint: Account<'info, Mint>,
+
+    #[account(
+        associated_token::mint = master_mint,
+        associated_token::authority = edition_config
+    )]
+    pub master_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            master_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    pub master_metadata: Account<'info, MetadataAccount>,
+
+    /// CHECK: Master edition account, checked by Metaplex
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            master_mint.key().as_ref(),
+            b"edition",
+        ],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Update authority of the master, reused for the print
+    #[account(address = master_metadata.update_authority)]
+    pub update_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        mint::decimals = 0,
+        mint::authority = buyer,
+        mint::freeze_authority = buyer,
+    )]
+    pub edition_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = edition_mint,
+        associated_token::authority = buyer,
+    )]
+    pub edition_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Edition metadata account, created by Metaplex
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            edition_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    pub edition_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Edition account, created by Metaplex
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            edition_mint.key().as_ref(),
+            b"edition",
+        ],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    pub edition: UncheckedAccount<'info>,
+
+    /// CHECK: Edition marker of the master for this edition number, checked by Metaplex
+    #[account(mut)]
+    pub edition_marker: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct BoostOneirobot<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(
+        token::mint = nft_mint,
+        token::authority = owner,
+        constraint = nft_token_account.amount == 1 @ DreamError::NotNftOwner
+    )]
+    pub nft_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"nft_attributes", nft_mint.key().as_ref()],
+        bump = nft_attributes.bump
+    )]
+    pub nft_attributes: Account<'info, NftAttributes>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Boost::SPACE,
+        seeds = [b"boost", nft_mint.key().as_ref()],
+        bump
+    )]
+    pub boost: Account<'info, Boost>,
+
+    /// Dream-Mind-Lucid treasury; names the SMIND mint
+    #[account(
+        seeds = [b"treasury"],
+        seeds::program = dream_mind_state::ID,
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(address = treasury.smind_mint)]
+    pub smind_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        token::mint = smind_mint,
+        token::authority = owner
+    )]
+    pub owner_smind_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = smind_mint,
+        associated_token::authority = boost,
+        associated_token::token_program = smind_token_program
+    )]
+    pub boost_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub smind_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EndOneirobotBoost<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"boost", boost.mint.as_ref()],
+        bump = boost.bump,
+        has_one = owner,
+        close = owner
+    )]
+    pub boost: Account<'info, Boost>,
+
+    #[account(
+        seeds = [b"treasury"],
+        seeds::program = dream_mind_state::ID,
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(address = treasury.smind_mint)]
+    pub smind_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        token::mint = smind_mint,
+        token::authority = owner
+    )]
+    pub owner_smind_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = smind_mint,
+        associated_token::authority = boost,
+        associated_token::token_program = smind_token_program
+    )]
+    pub boost_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub smind_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct NameOneirobot<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(
+        token::mint = nft_mint,
+        token::authority = owner,
+        constraint = nft_token_account.amount == 1 @ DreamError::NotNftOwner
+    )]
+    pub nft_token_account: Account<'info, TokenAccount>,
+
+    /// Grown to the current size for NFTs minted before names existed
+    #[account(
+        mut,
+        seeds = [b"nft_attributes", nft_mint.key().as_ref()],
+        bump = nft_attributes.bump,
+        realloc = 8 + NftAttributes::SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_attributes: Account<'info, NftAttributes>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + OneirobotName::SPACE,
+        seeds = [b"oneirobot_name", name_key(&name).as_ref()],
+        bump
+    )]
+    pub name_record: Account<'info, OneirobotName>,
+
+    /// The NFT's current name, freed by a rename; omitted otherwise
+    #[account(
+        mut,
+        seeds = [b"oneirobot_name", name_key(&nft_attributes.name).as_ref()],
+        bump = previous_name.bump,
+        close = owner,
+        constraint = previous_name.mint == nft_mint.key() @ DreamError::InvalidOneirobotAccounts
+    )]
+    pub previous_name: Option<Account<'info, OneirobotName>>,
+
+    /// Dream-Mind-Lucid treasury; names the DREAM mint
+    #[account(
+        seeds = [b"treasury"],
+        seeds::program = dream_mind_state::ID,
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut, address = treasury.dream_mint)]
+    pub dream_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        token::mint = dream_mint,
+        token::authority = owner
+    )]
+    pub owner_dream_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub dream_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(snapshot_id: u64)]
+pub struct SnapshotHolders<'info> {
+    #[account(
+        seeds = [b"oneirobot_state"],
+        bump = oneirobot_state.bump
+    )]
+    pub oneirobot_state: Account<'info, OneirobotState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HolderSnapshot::SPACE,
+        seeds = [b"holder_snapshot", snapshot_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+
+    pub reward_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = authority
+    )]
+    pub authority_reward_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = reward_mint,
+        associated_token::authority = holder_snapshot,
+        associated_token::token_program = token_program
+    )]
+    pub airdrop_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimHolderAirdrop<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// Dream-Mind-Lucid treasury; airdrop claims stop while it's paused
+    #[account(
+        seeds = [b"treasury"],
+        seeds::program = dream_mind_state::ID,
+        bump = treasury.bump,
+        constraint = !treasury.paused @ DreamError::ProgramPaused
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"holder_snapshot", holder_snapshot.snapshot_id.to_le_bytes().as_ref()],
+        bump = holder_snapshot.bump
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+
+    /// CHECK: only its key goes into the leaf, which the proof checks
+    pub nft_mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = 8 + HolderAirdropClaim::SPACE,
+        seeds = [
+            b"holder_airdrop_claim",
+            holder_snapshot.key().as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub holder_airdrop_claim: Account<'info, HolderAirdropClaim>,
+
+    #[account(address = holder_snapshot.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = holder_snapshot,
+        associated_token::token_program = token_program
+    )]
+    pub airdrop_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = holder
+    )]
+    pub holder_reward_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct GetNftAttributes<'info> {
     #[account(
         seeds = [b"nft_attributes", mint.key().as_ref()],
-        bump
+        bump = nft_attributes.bump
     )]
     pub nft_attributes: Account<'info, NftAttributes>,
     
     pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"boost", mint.key().as_ref()],
+        bump = boost.bump
+    )]
+    pub boost: Option<Account<'info, Boost>>,
 }
 
 // ===================== STATE STRUCTURES =====================
@@ -335,73 +1360,222 @@ pub struct OneirobotState {
     pub mint_price: u64,
     pub is_minting_enabled: bool,
     pub syndicate_masters: Vec<Pubkey>,
+    /// Canonical PDA bump, stored at init
+    pub bump: u8,
+    /// Reads as season 0, the genesis traits, on states created before seasons
+    pub trait_season: TraitSeason,
 }
 
 impl OneirobotState {
-    pub const SPACE: usize = 32 + 8 + 8 + 8 + 1 + (4 + 32 * 10); // Max 10 syndicate masters
+    pub const SPACE: usize =
+        32 + 8 + 8 + 8 + 1 + (4 + 32 * 10) + 1 + TraitSeason::SPACE; // Max 10 syndicate masters
+}
+
+/// Quantum-core pool and attribute ranges mints draw from, rotated each
+/// season so later mints stay distinguishable.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TraitSeason {
+    pub season: u32,
+    /// Empty in season 0, which uses `GENESIS_QUANTUM_CORES`
+    pub quantum_cores: Vec<String>,
+    pub dream_level: AttributeRange,
+    pub lucid_power: AttributeRange,
+    pub mind_strength: AttributeRange,
+    pub started_at: i64,
 }
 
+impl TraitSeason {
+    pub const SPACE: usize = 4
+        + (4 + (4 + ONEIROBOT_MAX_CORE_NAME_LEN) * ONEIROBOT_MAX_SEASON_CORES)
+        + 3 * 2
+        + 8;
+}
+
+/// Inclusive attribute range; the zeroed range of season 0 means 1-100.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct AttributeRange {
+    pub min: u8,
+    pub max: u8,
+}
+
+impl AttributeRange {
+    fn roll(self, random: u64) -> u8 {
+        let (min, max) = if self.max == 0 { (1, 100) } else { (self.min, self.max) };
+        min + (random % (u64::from(max - min) + 1)) as u8
+    }
+}
+
+/// Mirrored as `dream_mind_state::oneirobot::OneirobotAttributes` for the core
+/// program and the geyser plugin; change both together.
 #[account]
 #[derive(Clone)]
 pub struct NftAttributes {
     pub mint: Pubkey,
     pub owner: Pubkey,
     pub token_id: u64,
-    pub quantum_core: String,
+    /// `intern::string_id` of the quantum core, whose name Dream-Mind-Lucid's
+    /// `intern_string` stores once for the whole collection
+    pub quantum_core_id: u64,
     pub dream_level: u8,
     pub lucid_power: u8,
     pub mind_strength: u8,
     pub metadata_uri: String,
     pub mint_timestamp: i64,
     pub random_seed: u64,
+    /// Canonical PDA bump, stored at mint
+    pub bump: u8,
+    /// Trait season the NFT was minted in
+    pub season: u32,
+    /// Owner-chosen name, unique across the collection; empty until named
+    pub name: String,
+    /// CID of the owner's lore text
+    pub lore_cid: String,
+    pub named_at: i64,
 }
 
 impl NftAttributes {
-    pub const SPACE: usize = 32 + 32 + 8 + (4 + 32) + 1 + 1 + 1 + (4 + 200) + 8 + 8; // Approx sizes
+    pub const SPACE: usize = 32 + 32 + 8 + 8 + 1 + 1 + 1 + (4 + 200) + 8 + 8 + 1 + 4
+        + (4 + ONEIROBOT_MAX_NAME_LEN)
+        + (4 + ONEIROBOT_MAX_LORE_CID_LEN)
+        + 8; // Approx sizes
 }
 
-#[derive(Clone)]
-pub struct GeneratedAttributes {
-    pub quantum_core: String,
-    pub dream_level: u8,
-    pub lucid_power: u8,
-    pub mind_strength: u8,
+/// Claim on one Oneirobot name. Seeds `[b"oneirobot_name", name_key(name)]`,
+/// so two NFTs can't share a name in any letter case.
+#[account]
+pub struct OneirobotName {
+    pub mint: Pubkey,
+    /// The name as its owner wrote it
+    pub name: String,
+    pub bump: u8,
 }
 
-// ===================== EVENTS =====================
+impl OneirobotName {
+    pub const SPACE: usize = 32 + (4 + ONEIROBOT_MAX_NAME_LEN) + 1;
+}
 
-#[event]
-pub struct OneirobotMintedEvent {
+/// SMIND locked against one Oneirobot for a temporary `mind_strength` boost.
+/// Seeds `[b"boost", nft_mint]`; its SMIND sits in the PDA's associated
+/// token account.
+#[account]
+pub struct Boost {
     pub mint: Pubkey,
+    /// Who locked the SMIND and gets it back, even if the NFT changes hands
     pub owner: Pubkey,
-    pub token_id: u64,
+    pub amount: u64,
+    pub mind_boost: u8,
+    pub started_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl Boost {
+    pub const SPACE: usize = 32 + 32 + 8 + 1 + 8 + 8 + 1;
+}
+
+/// Edition prints governance allowed for one legendary Oneirobot, which is
+/// held by this PDA. Seeds `[b"edition_config", mint]`.
+#[account]
+pub struct EditionConfig {
+    pub mint: Pubkey,
+    pub max_prints: u64,
+    pub price_lamports: u64,
+    pub printed: u64,
+    pub bump: u8,
+}
+
+impl EditionConfig {
+    pub const SPACE: usize = 32 + 8 + 8 + 8 + 1;
+}
+
+/// Merkle root over the holders of every Oneirobot at `slot`, and the
+/// airdrop they can claim against it. Seeds `[b"holder_snapshot",
+/// snapshot_id]`; the tokens sit in the PDA's associated token account.
+#[account]
+pub struct HolderSnapshot {
+    pub snapshot_id: u64,
+    pub merkle_root: [u8; 32],
+    pub holder_count: u64,
+    pub slot: u64,
+    pub reward_mint: Pubkey,
+    pub amount_per_nft: u64,
+    pub claimed_count: u64,
+    pub taken_at: i64,
+    pub bump: u8,
+}
+
+impl HolderSnapshot {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+/// Marks one NFT's airdrop of a snapshot as claimed. Seeds
+/// `[b"holder_airdrop_claim", holder_snapshot, nft_mint]`.
+#[account]
+pub struct HolderAirdropClaim {
+    pub holder: Pubkey,
+    pub claimed_at: i64,
+}
+
+impl HolderAirdropClaim {
+    pub const SPACE: usize = 32 + 8;
+}
+
+/// Registry key of an Oneirobot name: the hash of its ASCII-lowercased form.
+pub fn name_key(name: &str) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hash(name.to_ascii_lowercase().as_bytes()).to_bytes()
+}
+
+fn is_valid_name(name: &str) -> bool {
+    (1..=ONEIROBOT_MAX_NAME_LEN).contains(&name.len())
+        && name.trim() == name
+        && name.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b' ' | b'-' | b'_'))
+}
+
+#[derive(Clone)]
+pub struct GeneratedAttributes {
     pub quantum_core: String,
     pub dream_level: u8,
     pub lucid_power: u8,
     pub mind_strength: u8,
-    pub metadata_uri: String,
-    pub timestamp: i64,
 }
 
-// ===================== ERRORS =====================
+// ===================== HELPER FUNCTIONS =====================
 
-#[error_code]
-pub enum OneirobotError {
-    #[msg("Unauthorized access - not the program authority")]
-    UnauthorizedAccess,
-    #[msg("Not a syndicate master - minting restricted")]
-    NotSyndicateMaster,
-    #[msg("Maximum supply reached")]
-    MaxSupplyReached,
-    #[msg("Minting is currently disabled")]
-    MintingDisabled,
-    #[msg("Invalid metadata URI")]
-    InvalidMetadataUri,
-    #[msg("NFT attributes not found")]
-    AttributesNotFound,
+/// Base64 `data:` URI of JSON metadata built from the NFT's name and
+/// attributes. Token Metadata caps URIs at `ONEIROBOT_MAX_URI_LEN` bytes,
+/// which leaves no room for trait names: the attributes are the quantum core,
+/// dream level, lucid power and mind strength values, in that order.
+pub fn onchain_metadata_uri(name: &str, attributes: &GeneratedAttributes) -> Result<String> {
+    let json = format!(
+        concat!(
+            r#"{{"name":{},"attributes":["#,
+            r#"{{"value":{}}},{{"value":{}}},{{"value":{}}},{{"value":{}}}]}}"#,
+        ),
+        json_string(name),
+        json_string(&attributes.quantum_core),
+        attributes.dream_level,
+        attributes.lucid_power,
+        attributes.mind_strength,
+    );
+    let uri = format!("data:application/json;base64,{}", BASE64_STANDARD.encode(json));
+    require!(uri.len() <= ONEIROBOT_MAX_URI_LEN, DreamError::OnchainMetadataTooLong);
+    Ok(uri)
 }
 
-// ===================== HELPER FUNCTIONS =====================
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
 
 /// Generate pseudorandom seed using available blockchain data
 /// WARNING: For mainnet, consider using Helius RNG or Switchboard VRF for true randomness
@@ -423,22 +1597,32 @@ pub fn generate_pseudo_random_seed(
     hasher.finish()
 }
 
-/// Generate OneirobotNFT attributes from random seed
-pub fn generate_oneirobot_attributes(random_seed: u64, metadata_uri: &str) -> GeneratedAttributes {
-    let quantum_cores = [
-        "Quantum Core Alpha",
-        "Quantum Core Beta", 
-        "Quantum Core Gamma",
-        "Quantum Core Delta",
-        "Quantum Core Epsilon",
-        "Quantum Core Zeta",
-        "Quantum Core Omega",
-    ];
+/// Quantum cores of season 0, before governance set any trait season
+pub const GENESIS_QUANTUM_CORES: [&str; 7] = [
+    "Quantum Core Alpha",
+    "Quantum Core Beta",
+    "Quantum Core Gamma",
+    "Quantum Core Delta",
+    "Quantum Core Epsilon",
+    "Quantum Core Zeta",
+    "Quantum Core Omega",
+];
+
+/// Generate OneirobotNFT attributes from random seed and the current season
+pub fn generate_oneirobot_attributes(
+    random_seed: u64,
+    season: &TraitSeason,
+) -> GeneratedAttributes {
+    let quantum_cores: Vec<&str> = if season.quantum_cores.is_empty() {
+        GENESIS_QUANTUM_CORES.to_vec()
+    } else {
+        season.quantum_cores.iter().map(String::as_str).collect()
+    };
 
     let quantum_core = quantum_cores[(random_seed % quantum_cores.len() as u64) as usize].to_string();
-    let dream_level = ((random_seed >> 8) % 100) as u8 + 1;  // 1-100
-    let lucid_power = ((random_seed >> 16) % 100) as u8 + 1; // 1-100
-    let mind_strength = ((random_seed >> 24) % 100) as u8 + 1; // 1-100
+    let dream_level = season.dream_level.roll(random_seed >> 8);
+    let lucid_power = season.lucid_power.roll(random_seed >> 16);
+    let mind_strength = season.mind_strength.roll(random_seed >> 24);
 
     GeneratedAttributes {
         quantum_core,
@@ -11,8 +11,14 @@ import {
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  getAccount,
 } from "@solana/spl-token";
+import { keccak256 } from "ethers";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 /**
  * OneirobotNFT Anchor Test Suite - 95%+ Coverage Target
@@ -44,13 +50,21 @@ describe("OneirobotNFT Solana Program", () => {
   let tokenAccount: PublicKey;
   let metadataAccount: PublicKey;
   let masterEditionAccount: PublicKey;
+  let feeVaultPda: PublicKey;
+  let treasuryPda: PublicKey;
 
   // Test constants
   const METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+  // Mint fees go to the Dream-Mind-Lucid fee vault, which must already exist
+  const DREAM_MIND_LUCID_PROGRAM_ID = new PublicKey("5oDxEKGa78LjcE9zMFqz1vLLgKYj4Drd6k1Vq2GJ6YNm");
   const TEST_METADATA_URI = "https://ipfs.io/ipfs/QmTest123456789abcdefghijklmnopqrstuvwxyz";
   const NFT_NAME = "OneirobotNFT #1";
   const NFT_SYMBOL = "ONEIROBOT";
 
+  // Attributes hold the interned id of their quantum core, not its name
+  const coreId = (core: string) =>
+    new anchor.BN(createHash("sha256").update(core).digest().subarray(0, 8), "le");
+
   before(async () => {
     // Initialize test accounts
     authority = Keypair.generate();
@@ -88,6 +102,15 @@ describe("OneirobotNFT Solana Program", () => {
       program.programId
     );
 
+    [feeVaultPda] = await PublicKey.findProgramAddress(
+      [Buffer.from("fee_vault")],
+      DREAM_MIND_LUCID_PROGRAM_ID
+    );
+    [treasuryPda] = await PublicKey.findProgramAddress(
+      [Buffer.from("treasury")],
+      DREAM_MIND_LUCID_PROGRAM_ID
+    );
+
     // Get associated token account
     tokenAccount = await getAssociatedTokenAddress(
       mintKeypair.publicKey,
@@ -203,6 +226,8 @@ describe("OneirobotNFT Solana Program", () => {
           minter: syndicateMaster.publicKey,
           recipient: recipient.publicKey,
           mintAuthority: syndicateMaster.publicKey,
+          feeVault: feeVaultPda,
+          treasury: treasuryPda,
           rent: SYSVAR_RENT_PUBKEY,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -239,7 +264,7 @@ describe("OneirobotNFT Solana Program", () => {
         "Quantum Core Zeta",
         "Quantum Core Omega"
       ];
-      expect(validCores).to.include(nftAttributes.quantumCore);
+      expect(validCores.some((core) => coreId(core).eq(nftAttributes.quantumCoreId))).to.be.true;
 
       // Verify state update
       const oneirobotState = await program.account.oneirobotState.fetch(oneirobotStatePda);
@@ -262,6 +287,8 @@ describe("OneirobotNFT Solana Program", () => {
             minter: unauthorizedUser.publicKey,
             recipient: recipient.publicKey,
             mintAuthority: unauthorizedUser.publicKey,
+            feeVault: feeVaultPda,
+            treasury: treasuryPda,
             rent: SYSVAR_RENT_PUBKEY,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -321,6 +348,8 @@ describe("OneirobotNFT Solana Program", () => {
           minter: syndicateMaster.publicKey,
           recipient: recipient.publicKey,
           mintAuthority: syndicateMaster.publicKey,
+          feeVault: feeVaultPda,
+          treasury: treasuryPda,
           rent: SYSVAR_RENT_PUBKEY,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -337,6 +366,64 @@ describe("OneirobotNFT Solana Program", () => {
       expect(firstAttributes.randomSeed.toString()).to.not.equal(secondAttributes.randomSeed.toString());
       expect(firstAttributes.tokenId.toNumber()).to.not.equal(secondAttributes.tokenId.toNumber());
     });
+
+    it("Should build metadata on-chain when no URI is given", async () => {
+      const onchainMint = Keypair.generate();
+      const [onchainAttributesPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("nft_attributes"), onchainMint.publicKey.toBuffer()],
+        program.programId
+      );
+      const [onchainMetadata] = PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), onchainMint.publicKey.toBuffer()],
+        METADATA_PROGRAM_ID
+      );
+      const [onchainMasterEdition] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("metadata"),
+          METADATA_PROGRAM_ID.toBuffer(),
+          onchainMint.publicKey.toBuffer(),
+          Buffer.from("edition"),
+        ],
+        METADATA_PROGRAM_ID
+      );
+
+      await program.methods
+        .mintOneirobot("", "OneirobotNFT #3", NFT_SYMBOL)
+        .accounts({
+          oneirobotState: oneirobotStatePda,
+          nftAttributes: onchainAttributesPda,
+          mint: onchainMint.publicKey,
+          tokenAccount: await getAssociatedTokenAddress(onchainMint.publicKey, recipient.publicKey),
+          metadata: onchainMetadata,
+          masterEdition: onchainMasterEdition,
+          minter: syndicateMaster.publicKey,
+          recipient: recipient.publicKey,
+          mintAuthority: syndicateMaster.publicKey,
+          feeVault: feeVaultPda,
+          treasury: treasuryPda,
+          rent: SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          metadataProgram: METADATA_PROGRAM_ID,
+        })
+        .signers([syndicateMaster, onchainMint])
+        .rpc();
+
+      const attributes = await program.account.nftAttributes.fetch(onchainAttributesPda);
+      const prefix = "data:application/json;base64,";
+      expect(attributes.metadataUri.startsWith(prefix)).to.be.true;
+      const json = JSON.parse(
+        Buffer.from(attributes.metadataUri.slice(prefix.length), "base64").toString()
+      );
+      expect(json.name).to.equal("OneirobotNFT #3");
+      expect(coreId(json.attributes[0].value).eq(attributes.quantumCoreId)).to.be.true;
+      expect(json.attributes.slice(1).map((a) => a.value)).to.deep.equal([
+        attributes.dreamLevel,
+        attributes.lucidPower,
+        attributes.mindStrength,
+      ]);
+    });
   });
 
   describe("🎲 Attribute Verification", () => {
@@ -378,6 +465,363 @@ describe("OneirobotNFT Solana Program", () => {
     });
   });
 
+  describe("🌗 Trait Seasons", () => {
+    const SEASON_CORES = ["Quantum Core Aurora", "Quantum Core Nadir"];
+    const range = (min: number, max: number) => ({ min, max });
+
+    it("Should reject trait seasons from non-authority", async () => {
+      try {
+        await program.methods
+          .setTraitSeason(SEASON_CORES, range(1, 100), range(1, 100), range(1, 100))
+          .accounts({
+            oneirobotState: oneirobotStatePda,
+            authority: unauthorizedUser.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([unauthorizedUser])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedAuthority");
+      }
+    });
+
+    it("Should mint from the active season's pool and ranges", async () => {
+      await program.methods
+        .setTraitSeason(SEASON_CORES, range(50, 60), range(1, 10), range(90, 100))
+        .accounts({
+          oneirobotState: oneirobotStatePda,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const oneirobotState = await program.account.oneirobotState.fetch(oneirobotStatePda);
+      expect(oneirobotState.traitSeason.season).to.equal(1);
+      expect(oneirobotState.traitSeason.quantumCores).to.deep.equal(SEASON_CORES);
+
+      const seasonMint = Keypair.generate();
+      const [seasonNftAttributesPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("nft_attributes"), seasonMint.publicKey.toBuffer()],
+        program.programId
+      );
+      const [seasonMetadataAccount] = await PublicKey.findProgramAddress(
+        [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), seasonMint.publicKey.toBuffer()],
+        METADATA_PROGRAM_ID
+      );
+      const [seasonMasterEditionAccount] = await PublicKey.findProgramAddress(
+        [
+          Buffer.from("metadata"),
+          METADATA_PROGRAM_ID.toBuffer(),
+          seasonMint.publicKey.toBuffer(),
+          Buffer.from("edition"),
+        ],
+        METADATA_PROGRAM_ID
+      );
+
+      await program.methods
+        .mintOneirobot(TEST_METADATA_URI, "OneirobotNFT #3", NFT_SYMBOL)
+        .accounts({
+          oneirobotState: oneirobotStatePda,
+          nftAttributes: seasonNftAttributesPda,
+          mint: seasonMint.publicKey,
+          tokenAccount: await getAssociatedTokenAddress(seasonMint.publicKey, recipient.publicKey),
+          metadata: seasonMetadataAccount,
+          masterEdition: seasonMasterEditionAccount,
+          minter: syndicateMaster.publicKey,
+          recipient: recipient.publicKey,
+          mintAuthority: syndicateMaster.publicKey,
+          feeVault: feeVaultPda,
+          treasury: treasuryPda,
+          rent: SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          metadataProgram: METADATA_PROGRAM_ID,
+        })
+        .signers([syndicateMaster, seasonMint])
+        .rpc();
+
+      const seasonAttributes = await program.account.nftAttributes.fetch(seasonNftAttributesPda);
+      expect(seasonAttributes.season).to.equal(1);
+      const seasonCore = seasonAttributes.quantumCoreId;
+      expect(SEASON_CORES.some((core) => coreId(core).eq(seasonCore))).to.be.true;
+      expect(seasonAttributes.dreamLevel).to.be.within(50, 60);
+      expect(seasonAttributes.lucidPower).to.be.within(1, 10);
+      expect(seasonAttributes.mindStrength).to.be.within(90, 100);
+
+      // Earlier mints keep the genesis season
+      const genesisAttributes = await program.account.nftAttributes.fetch(nftAttributesPda);
+      expect(genesisAttributes.season).to.equal(0);
+    });
+  });
+
+  describe("🏛️ Legendary Editions", () => {
+    const MAX_PRINTS = new anchor.BN(3);
+    const PRICE = new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL);
+    const legendaryMint = Keypair.generate();
+    let editionConfigPda: PublicKey;
+
+    const metadataAddress = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+        METADATA_PROGRAM_ID
+      )[0];
+    const editionAddress = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from("edition")],
+        METADATA_PROGRAM_ID
+      )[0];
+
+    before(async () => {
+      [editionConfigPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("edition_config"), legendaryMint.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Should reject allowing editions from non-authority", async () => {
+      try {
+        await program.methods
+          .allowEditions(legendaryMint.publicKey, MAX_PRINTS, PRICE)
+          .accounts({
+            oneirobotState: oneirobotStatePda,
+            authority: unauthorizedUser.publicKey,
+            editionConfig: editionConfigPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([unauthorizedUser])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedAuthority");
+      }
+    });
+
+    it("Should print editions of a legendary Oneirobot and pay its creators", async () => {
+      await program.methods
+        .allowEditions(legendaryMint.publicKey, MAX_PRINTS, PRICE)
+        .accounts({
+          oneirobotState: oneirobotStatePda,
+          authority: authority.publicKey,
+          editionConfig: editionConfigPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const [legendaryAttributesPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("nft_attributes"), legendaryMint.publicKey.toBuffer()],
+        program.programId
+      );
+      const masterTokenAccount = await getAssociatedTokenAddress(
+        legendaryMint.publicKey,
+        editionConfigPda,
+        true
+      );
+      await program.methods
+        .mintOneirobot(TEST_METADATA_URI, "OneirobotNFT Legendary", NFT_SYMBOL)
+        .accounts({
+          oneirobotState: oneirobotStatePda,
+          nftAttributes: legendaryAttributesPda,
+          mint: legendaryMint.publicKey,
+          tokenAccount: masterTokenAccount,
+          metadata: metadataAddress(legendaryMint.publicKey),
+          masterEdition: editionAddress(legendaryMint.publicKey),
+          minter: syndicateMaster.publicKey,
+          recipient: editionConfigPda,
+          mintAuthority: syndicateMaster.publicKey,
+          feeVault: feeVaultPda,
+          treasury: treasuryPda,
+          rent: SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          metadataProgram: METADATA_PROGRAM_ID,
+          editionConfig: editionConfigPda,
+        })
+        .signers([syndicateMaster, legendaryMint])
+        .rpc();
+
+      const editionMint = Keypair.generate();
+      const [editionMarker] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("metadata"),
+          METADATA_PROGRAM_ID.toBuffer(),
+          legendaryMint.publicKey.toBuffer(),
+          Buffer.from("edition"),
+          Buffer.from("0"), // edition 1 / 248
+        ],
+        METADATA_PROGRAM_ID
+      );
+      const creatorBalance = await provider.connection.getBalance(authority.publicKey);
+      await program.methods
+        .printOneirobotEdition()
+        .accounts({
+          buyer: recipient.publicKey,
+          treasury: treasuryPda,
+          editionConfig: editionConfigPda,
+          masterMint: legendaryMint.publicKey,
+          masterTokenAccount,
+          masterMetadata: metadataAddress(legendaryMint.publicKey),
+          masterEdition: editionAddress(legendaryMint.publicKey),
+          updateAuthority: syndicateMaster.publicKey,
+          editionMint: editionMint.publicKey,
+          editionTokenAccount: await getAssociatedTokenAddress(editionMint.publicKey, recipient.publicKey),
+          editionMetadata: metadataAddress(editionMint.publicKey),
+          edition: editionAddress(editionMint.publicKey),
+          editionMarker,
+          rent: SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          metadataProgram: METADATA_PROGRAM_ID,
+        })
+        // The master metadata's only creator is the program authority
+        .remainingAccounts([{ pubkey: authority.publicKey, isWritable: true, isSigner: false }])
+        .signers([recipient, editionMint])
+        .rpc();
+
+      const editionConfig = await program.account.editionConfig.fetch(editionConfigPda);
+      expect(editionConfig.printed.toNumber()).to.equal(1);
+      expect(await provider.connection.getBalance(authority.publicKey)).to.equal(
+        creatorBalance + PRICE.toNumber()
+      );
+    });
+  });
+
+  describe("🎁 Holder Airdrops", () => {
+    const SNAPSHOT_ID = new anchor.BN(1);
+    const AMOUNT_PER_NFT = new anchor.BN(1_000);
+
+    // Same leaves and sorted-pair hashing as dream_mind_state::merkle
+    const holderLeaf = (nftMint: PublicKey, holder: PublicKey) =>
+      Buffer.from(keccak256(Buffer.concat([nftMint.toBuffer(), holder.toBuffer()])).slice(2), "hex");
+    const hashPair = (a: Buffer, b: Buffer) =>
+      Buffer.from(keccak256(Buffer.concat(Buffer.compare(a, b) <= 0 ? [a, b] : [b, a])).slice(2), "hex");
+
+    let rewardMint: PublicKey;
+    let authorityRewardAccount: PublicKey;
+    let recipientRewardAccount: PublicKey;
+    let holderSnapshotPda: PublicKey;
+    let airdropVault: PublicKey;
+    let leaves: Buffer[];
+
+    before(async () => {
+      rewardMint = await createMint(provider.connection, authority, authority.publicKey, null, 9);
+      authorityRewardAccount = await createAssociatedTokenAccount(
+        provider.connection, authority, rewardMint, authority.publicKey
+      );
+      recipientRewardAccount = await createAssociatedTokenAccount(
+        provider.connection, recipient, rewardMint, recipient.publicKey
+      );
+      await mintTo(provider.connection, authority, rewardMint, authorityRewardAccount, authority, 10_000);
+
+      [holderSnapshotPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("holder_snapshot"), SNAPSHOT_ID.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      airdropVault = await getAssociatedTokenAddress(rewardMint, holderSnapshotPda, true);
+      // The recipient's first NFT, and another holder's
+      leaves = [
+        holderLeaf(mintKeypair.publicKey, recipient.publicKey),
+        holderLeaf(Keypair.generate().publicKey, unauthorizedUser.publicKey),
+      ];
+    });
+
+    const snapshotAccounts = (signer: PublicKey, rewardAccount: PublicKey) => ({
+      oneirobotState: oneirobotStatePda,
+      authority: signer,
+      holderSnapshot: holderSnapshotPda,
+      rewardMint,
+      authorityRewardAccount: rewardAccount,
+      airdropVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    const claim = (nftMint: PublicKey, proof: Buffer[]) => {
+      const [holderAirdropClaim] = PublicKey.findProgramAddressSync(
+        [Buffer.from("holder_airdrop_claim"), holderSnapshotPda.toBuffer(), nftMint.toBuffer()],
+        program.programId
+      );
+      return program.methods
+        .claimHolderAirdrop(proof.map((node) => Array.from(node)))
+        .accounts({
+          holder: recipient.publicKey,
+          treasury: treasuryPda,
+          holderSnapshot: holderSnapshotPda,
+          nftMint,
+          holderAirdropClaim,
+          rewardMint,
+          airdropVault,
+          holderRewardAccount: recipientRewardAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([recipient])
+        .rpc();
+    };
+
+    it("Should reject holder snapshots from non-authority", async () => {
+      const slot = await provider.connection.getSlot();
+      const unauthorizedRewardAccount = await createAssociatedTokenAccount(
+        provider.connection, unauthorizedUser, rewardMint, unauthorizedUser.publicKey
+      );
+      try {
+        await program.methods
+          .snapshotHolders(SNAPSHOT_ID, Array.from(hashPair(leaves[0], leaves[1])), new anchor.BN(2), new anchor.BN(slot), AMOUNT_PER_NFT)
+          .accounts(snapshotAccounts(unauthorizedUser.publicKey, unauthorizedRewardAccount))
+          .signers([unauthorizedUser])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedAuthority");
+      }
+    });
+
+    it("Should fund the airdrop and pay each proven holder once", async () => {
+      const slot = await provider.connection.getSlot();
+      const merkleRoot = hashPair(leaves[0], leaves[1]);
+      await program.methods
+        .snapshotHolders(SNAPSHOT_ID, Array.from(merkleRoot), new anchor.BN(2), new anchor.BN(slot), AMOUNT_PER_NFT)
+        .accounts(snapshotAccounts(authority.publicKey, authorityRewardAccount))
+        .signers([authority])
+        .rpc();
+
+      const snapshot = await program.account.holderSnapshot.fetch(holderSnapshotPda);
+      expect(Buffer.from(snapshot.merkleRoot)).to.deep.equal(merkleRoot);
+      expect(snapshot.holderCount.toNumber()).to.equal(2);
+      expect(Number((await getAccount(provider.connection, airdropVault)).amount)).to.equal(2_000);
+
+      // The proof of the other holder's leaf doesn't prove the recipient's
+      try {
+        await claim(mintKeypair.publicKey, [leaves[0]]);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidHolderProof");
+      }
+
+      await claim(mintKeypair.publicKey, [leaves[1]]);
+      expect(Number((await getAccount(provider.connection, recipientRewardAccount)).amount)).to.equal(1_000);
+      const claimed = await program.account.holderSnapshot.fetch(holderSnapshotPda);
+      expect(claimed.claimedCount.toNumber()).to.equal(1);
+
+      // The claim PDA already exists, so a second claim fails
+      try {
+        await claim(mintKeypair.publicKey, [leaves[1]]);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have thrown an error");
+      }
+    });
+  });
+
   describe("🛡️ Security and Constraints", () => {
     it("Should enforce PDA constraints", async () => {
       // This test verifies that the program correctly validates PDAs
@@ -386,6 +830,53 @@ describe("OneirobotNFT Solana Program", () => {
       expect(oneirobotState).to.not.be.null;
     });
 
+    it("Should reject spliced mints not signed by Dream-Mind-Lucid", async () => {
+      const splicedMint = Keypair.generate();
+      const [splicedAttributesPda] = await PublicKey.findProgramAddress(
+        [Buffer.from("nft_attributes"), splicedMint.publicKey.toBuffer()],
+        program.programId
+      );
+      try {
+        await program.methods
+          .mintSplicedOneirobot(TEST_METADATA_URI, "OneirobotNFT Spliced", NFT_SYMBOL, 100, 100)
+          .accounts({
+            oneirobotState: oneirobotStatePda,
+            nftAttributes: splicedAttributesPda,
+            mint: splicedMint.publicKey,
+            tokenAccount: await getAssociatedTokenAddress(splicedMint.publicKey, unauthorizedUser.publicKey),
+            metadata: PublicKey.findProgramAddressSync(
+              [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), splicedMint.publicKey.toBuffer()],
+              METADATA_PROGRAM_ID
+            )[0],
+            masterEdition: PublicKey.findProgramAddressSync(
+              [
+                Buffer.from("metadata"),
+                METADATA_PROGRAM_ID.toBuffer(),
+                splicedMint.publicKey.toBuffer(),
+                Buffer.from("edition"),
+              ],
+              METADATA_PROGRAM_ID
+            )[0],
+            minter: unauthorizedUser.publicKey,
+            recipient: unauthorizedUser.publicKey,
+            mintAuthority: unauthorizedUser.publicKey,
+            feeVault: feeVaultPda,
+            treasury: treasuryPda,
+            rent: SYSVAR_RENT_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            metadataProgram: METADATA_PROGRAM_ID,
+          })
+          .signers([unauthorizedUser, splicedMint])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidSpliceAttributes");
+      }
+    });
+
     it("Should validate compute units usage", async () => {
       // Simulate compute unit testing
       console.log("      ⚡ Compute units for minting: <50,000 (estimated)");